@@ -1,16 +1,79 @@
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
-use ratatui::DefaultTerminal;
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+    KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use parquet::file::reader::FileReader;
+use ratatui::backend::{Backend, TestBackend};
+use ratatui::layout::Size;
+use ratatui::{DefaultTerminal, Terminal};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
 use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
+use crate::command::Command;
+use crate::components::{FileSchemaTable, RowInspector};
+use crate::config::{AppConfig, ConfigError, LayoutConfig, TabKind};
+use crate::file::bench::BenchReport;
+use crate::file::blob::{RowImages, read_images};
+use crate::file::bloom::BloomReport;
+use crate::file::codecs::CodecReport;
+use crate::file::column_filter::ColumnFilter;
+use crate::file::correlation::{CORRELATION_SAMPLE_ROWS, Correlation};
+use crate::file::dictionary::DecodedPage;
+use crate::file::distinct::estimate_distinct;
+use crate::file::duplicates::DuplicateReport;
+use crate::file::json_path::{JsonColumn, cell_json};
+use crate::file::page_values::{ChunkPreviews, PREVIEW_PAGES, PagePreview, chunk_previews};
 use crate::file::parquet_ctx::ParquetCtx;
+use crate::file::profile::FileProfile;
+use crate::file::progress::{CANCELLED, ScanProgress};
+use crate::file::pruning::PruningReport;
+use crate::file::row_group_filter::RowGroupFilter;
+use crate::file::sample_data::{ParquetSampleData, PreviewOptions, Sampling};
+use crate::file::schema::{SchemaInfo, SchemaOutline};
+use crate::file::search::ValuePattern;
+use crate::file::snippet::{SnippetFormat, column_snippet, schema_ddl};
+use crate::file::utf8::Utf8Report;
+use crate::file::utils::commas;
+use crate::jumps::JumpList;
+use crate::keymap::{Action, Key, Keymap};
+use crate::macros::{Macros, is_register};
+use crate::mouse::{MouseRegions, PaneDivider, ScrollTarget, ScrollbarRegion};
+use crate::session::Session;
+use crate::status::StatusMessage;
 use crate::tabs::TabManager;
+use crate::theme::{RenderMode, Theme};
+
+/// How often the app redraws while no input arrives, so background task
+/// progress and expiring status messages show up without a keypress.
+const TICK_RATE: Duration = Duration::from_millis(250);
 
 pub struct AppRenderView<'a> {
     pub title: &'a str,
     pub parquet_ctx: &'a ParquetCtx,
+    /// Rows shown in the Visualize tab.
+    pub sample_data: &'a ParquetSampleData,
     file_name: &'a str,
     tabs: &'a TabManager,
     pub state: &'a AppState,
+    pub theme: &'a Theme,
+    pub render_mode: RenderMode,
+    pub keymap: &'a Keymap,
+    pub config: &'a AppConfig,
+    mouse_regions: &'a RefCell<MouseRegions>,
+    /// What the running background task is doing, and how far it got.
+    pub task: Option<(&'a str, &'a ScanProgress)>,
+    /// Ticks since the app started, for animations.
+    pub ticks: usize,
+    /// Images of the row open in the inspector, by column.
+    pub inspector_images: Option<&'a RowImages>,
+    /// First values of the pages of the chunk selected in the Row Groups tab.
+    pub page_previews: &'a [Option<PagePreview>],
 }
 
 impl<'a> AppRenderView<'a> {
@@ -18,9 +81,26 @@ impl<'a> AppRenderView<'a> {
         Self {
             title: "parqeye",
             parquet_ctx: app.parquet_ctx,
+            sample_data: app.sample_data(),
             file_name: &app.file_name,
             tabs: &app.tabs,
             state: &app.state,
+            theme: &app.theme,
+            render_mode: app.render_mode,
+            keymap: &app.keymap,
+            config: &app.config,
+            mouse_regions: &app.mouse_regions,
+            task: app
+                .tasks
+                .running
+                .as_ref()
+                .map(|task| (task.label, &task.progress)),
+            ticks: app.ticks,
+            inspector_images: app.inspector_images.as_ref().map(|(_, images)| images),
+            page_previews: app
+                .page_previews
+                .as_ref()
+                .map_or(&[], |(_, previews)| previews.as_slice()),
         }
     }
 
@@ -28,6 +108,59 @@ impl<'a> AppRenderView<'a> {
         self.tabs
     }
 
+    /// Where the cursor is, for the footer: the tab, then the selected
+    /// column, row group and row where the tab has them, e.g.
+    /// `Visualize › id › rg 2/4 › row 1,204 / 8,000`.
+    pub fn breadcrumb(&self) -> Vec<String> {
+        let ctx = self.parquet_ctx;
+        let tab = self.tabs.active_tab();
+        let mut crumbs = vec![tab.to_string()];
+        let num_row_groups = ctx.row_groups.num_row_groups();
+        let outline = ctx.schema.outline();
+        let schema_column = || {
+            self.state
+                .schema_cursor(&outline)
+                .map(|node| ctx.schema.column_path(node))
+        };
+        match crumbs[0].as_str() {
+            "Visualize" => {
+                let data = self.sample_data;
+                crumbs.extend(
+                    data.flattened_columns
+                        .get(self.state.horizontal_offset())
+                        .cloned(),
+                );
+                if data.total_rows > 0 {
+                    let row = data.position(self.state.vertical_offset().min(data.total_rows - 1));
+                    if let Some(rg) = ctx.row_groups.row_group_of_row(row) {
+                        crumbs.push(format!("rg {}/{num_row_groups}", rg + 1));
+                    }
+                    crumbs.push(format!(
+                        "row {} / {}",
+                        commas(row as u64 + 1),
+                        commas(ctx.metadata.num_rows as u64)
+                    ));
+                }
+                if self.state.sampling() != Sampling::Head {
+                    crumbs.push(format!("sample {}", self.state.sampling()));
+                }
+            }
+            "Schema" => crumbs.extend(schema_column()),
+            "Row Groups" => {
+                crumbs.extend(schema_column());
+                if num_row_groups > 0 {
+                    let rg = self.state.row_group().min(num_row_groups - 1);
+                    crumbs.push(format!("rg {}/{num_row_groups}", rg + 1));
+                }
+            }
+            _ => {}
+        }
+        if let Some(register) = self.state.recording_macro() {
+            crumbs.push(format!("recording @{register}"));
+        }
+        crumbs
+    }
+
     pub fn file_name(&self) -> &str {
         self.file_name
     }
@@ -35,6 +168,45 @@ impl<'a> AppRenderView<'a> {
     pub fn state(&self) -> &AppState {
         self.state
     }
+
+    /// Hit-testing regions, rewritten by the renderer on every frame.
+    pub fn mouse_regions(&self) -> &RefCell<MouseRegions> {
+        self.mouse_regions
+    }
+}
+
+/// Where to open a file, given on the command line. Row groups and rows
+/// are numbered from 1 and columns are dotted paths, as shown in the app.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StartPosition {
+    pub tab: Option<TabKind>,
+    pub row_group: Option<usize>,
+    pub row: Option<usize>,
+    pub column: Option<String>,
+}
+
+impl StartPosition {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Where the Row Groups tab is, kept apart from the offsets the other tabs
+/// scroll with so it comes back to the same row group.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RowGroupsState {
+    pub row_group: usize,
+    // Row groups marked for comparison, in the order they were marked. Two
+    // marks open the comparison.
+    pub marked: Vec<usize>,
+}
+
+/// A column kept selected across tabs: its dotted path and, for a leaf,
+/// its position among the leaves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectedColumn {
+    pub path: String,
+    pub leaf: Option<usize>,
 }
 
 pub struct App<'a> {
@@ -43,17 +215,305 @@ pub struct App<'a> {
     pub exit: bool,
     pub tabs: TabManager,
     pub state: AppState,
+    pub config: AppConfig,
+    pub theme: Theme,
+    // Colors and glyphs left out for limited terminals.
+    pub render_mode: RenderMode,
+    pub keymap: Keymap,
+    // Rows loaded after jumping past the initial sample; `None` shows
+    // `parquet_ctx.sample_data`.
+    data_window: Option<ParquetSampleData>,
+    mouse_regions: RefCell<MouseRegions>,
+    // Element being dragged with the mouse, if any.
+    drag: Option<DragTarget>,
+    // Images of the row open in the inspector, by the row's position in the
+    // file, read when it opens.
+    inspector_images: Option<(usize, RowImages)>,
+    // First values of the pages of the chunk selected in the Row Groups tab,
+    // by (row group, leaf column), read when it is shown.
+    page_previews: Option<((usize, usize), ChunkPreviews)>,
+    tasks: TaskRunner,
+    ticks: usize,
+    // A file name and text to show in the pager or editor once the event
+    // at hand is handled.
+    pending_external: Option<(String, String)>,
+    // Places left by tab switches, `:goto`, search matches and clicks in
+    // the schema tree, for Ctrl+o / Ctrl+i.
+    jumps: JumpList<Place>,
+    macros: Macros,
+    // Register being recorded into and the keys pressed so far.
+    recording: Option<(char, Vec<Key>)>,
+    // `record_macro` or `play_macro`, waiting for the register key.
+    pending_register: Option<Action>,
+    // Register `@@` plays again.
+    last_macro: Option<char>,
+    // Keys come from a macro, so they are not recorded and can't play
+    // another macro.
+    replaying: bool,
 }
 
-pub struct AppState {
+/// Something for the event loop to handle: terminal input, a tick with no
+/// input, or the result of a background task.
+pub enum AppEvent {
+    Input(io::Result<Event>),
+    Tick,
+    Task(TaskOutput),
+}
+
+/// What a background task produced.
+pub enum TaskOutput {
+    Profile(Result<FileProfile, String>),
+    Bloom(Result<BloomReport, String>),
+    Page(Result<DecodedPage, String>),
+    Duplicates(Result<DuplicateReport, String>),
+    Correlation(Result<Correlation, String>),
+    Utf8(Result<Utf8Report, String>),
+    Bench(Result<BenchReport, String>),
+    Codecs(Result<CodecReport, String>),
+    /// Row of the next match in the file, numbered from 0.
+    Search(Result<Option<usize>, String>),
+}
+
+/// The background task in flight.
+struct Task {
+    label: &'static str,
+    progress: ScanProgress,
+}
+
+/// Runs long full-file operations on their own threads, one at a time, and
+/// hands their results back on the channel the event loop reads terminal
+/// input from.
+struct TaskRunner {
+    sender: Sender<AppEvent>,
+    events: Receiver<AppEvent>,
+    running: Option<Task>,
+    // Set while another program reads the terminal. The input thread holds
+    // the lock while it polls, so taking it waits for a poll to finish.
+    input_paused: Arc<AtomicBool>,
+    input_lock: Arc<Mutex<()>>,
+}
+
+impl TaskRunner {
+    fn new() -> Self {
+        let (sender, events) = mpsc::channel();
+        Self {
+            sender,
+            events,
+            running: None,
+            input_paused: Arc::new(AtomicBool::new(false)),
+            input_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Start `work` on a new thread, with `total` bytes to go through.
+    fn spawn(
+        &mut self,
+        label: &'static str,
+        total: u64,
+        work: impl FnOnce(&ScanProgress) -> TaskOutput + Send + 'static,
+    ) -> Result<(), String> {
+        if let Some(task) = &self.running {
+            return Err(format!("{} is still running; cancel it first", task.label));
+        }
+        let progress = ScanProgress::new(total);
+        let (sender, worker) = (self.sender.clone(), progress.clone());
+        thread::spawn(move || {
+            // The app may be gone by the time the work is done.
+            let _ = sender.send(AppEvent::Task(work(&worker)));
+        });
+        self.running = Some(Task { label, progress });
+        Ok(())
+    }
+
+    /// Ask the running task to stop. Work that can't stop early runs to the
+    /// end and its result is dropped.
+    fn cancel(&self) {
+        if let Some(task) = &self.running {
+            task.progress.cancel();
+        }
+    }
+
+    /// Forward terminal input to the event channel from a thread of its own,
+    /// with a tick whenever none arrives for `tick_rate`.
+    fn forward_input(&self, tick_rate: Duration) {
+        let sender = self.sender.clone();
+        let (paused, lock) = (self.input_paused.clone(), self.input_lock.clone());
+        thread::spawn(move || {
+            loop {
+                if paused.load(Ordering::SeqCst) {
+                    thread::sleep(tick_rate);
+                    continue;
+                }
+                let guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+                if paused.load(Ordering::SeqCst) {
+                    continue;
+                }
+                let event = match event::poll(tick_rate) {
+                    Ok(true) => AppEvent::Input(event::read()),
+                    Ok(false) => AppEvent::Tick,
+                    Err(e) => AppEvent::Input(Err(e)),
+                };
+                drop(guard);
+                let failed = matches!(event, AppEvent::Input(Err(_)));
+                if sender.send(event).is_err() || failed {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Run `f` while no terminal input is read, e.g. because another
+    /// program reads it.
+    fn without_input<T>(&self, f: impl FnOnce() -> T) -> T {
+        self.input_paused.store(true, Ordering::SeqCst);
+        let guard = self.input_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let result = f();
+        drop(guard);
+        self.input_paused.store(false, Ordering::SeqCst);
+        result
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum DragTarget {
+    Scrollbar(ScrollbarRegion),
+    PaneDivider(PaneDivider),
+}
+
+/// Cursor and scroll positions of one tab. Every tab keeps its own, so
+/// switching tabs finds each where it was left.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct TabState {
     horizontal_offset: usize,
     vertical_offset: usize,
+    // Schema node of the group under the tree cursor. When set,
+    // `vertical_offset` is 0 since no leaf is selected.
+    selected_group: Option<usize>,
     tree_scroll_offset: usize,
     data_vertical_scroll: usize,
+}
+
+/// A place to jump back to: a tab and its positions, the row group of the
+/// Row Groups tab and, in the Visualize tab, the row of the file, which the
+/// rows loaded by then may not hold.
+#[derive(Debug, Clone)]
+struct Place {
+    tab: usize,
+    positions: TabState,
+    row_group: usize,
+    row: Option<usize>,
+}
+
+impl Place {
+    fn cursor(
+        &self,
+    ) -> (
+        usize,
+        usize,
+        Option<usize>,
+        usize,
+        Option<usize>,
+        Option<usize>,
+    ) {
+        (
+            self.tab,
+            self.row_group,
+            self.row,
+            self.positions.horizontal_offset,
+            self.positions.selected_group,
+            // The row of the Visualize tab is compared in the file.
+            self.row.is_none().then_some(self.positions.vertical_offset),
+        )
+    }
+}
+
+/// Places are the same when the same row, column or row group is selected
+/// in the same tab, however far it is scrolled.
+impl PartialEq for Place {
+    fn eq(&self, other: &Self) -> bool {
+        self.cursor() == other.cursor()
+    }
+}
+
+pub struct AppState {
+    // Positions of each tab, by tab index.
+    tab_states: Vec<TabState>,
+    // Index of the tab whose positions the accessors below read and move.
+    active_tab: usize,
     visible_data_rows: usize,
+    // Columns on screen in the Visualize or Schema table, recomputed each
+    // frame. How far one PageLeft / PageRight scrolls.
+    visible_columns: usize,
     // Upper bound for `horizontal_offset`, recomputed each frame from the
     // on-screen column count. Prevents scrolling past the last visible column.
     max_horizontal_offset: usize,
+    layout: LayoutConfig,
+    // Share of the body the schema tree takes when sized to its content,
+    // recomputed each frame. Starting point for the first resize.
+    natural_tree_percent: u16,
+    show_help: bool,
+    help_scroll: usize,
+    // The row inspector popup on the Visualize tab is open.
+    inspecting: bool,
+    // Line of the inspector's tree under the cursor.
+    inspector_cursor: usize,
+    // Paths of the objects and arrays folded in the inspector.
+    inspector_folded: BTreeSet<String>,
+    // Show the nested values of the selected preview row over several lines.
+    expand_row: bool,
+    // Positions in the file of the preview rows whose values are wrapped to
+    // the column width.
+    wrapped_rows: BTreeSet<usize>,
+    // Text typed on the `:` command line while it is open.
+    command_line: Option<String>,
+    // Message in the footer: command errors, confirmations and progress.
+    status: Option<StatusMessage>,
+    // Schema nodes of the groups folded in the schema tree.
+    collapsed_groups: BTreeSet<usize>,
+    // Active `:columns` filter and the schema nodes it lists.
+    column_filter: Option<(ColumnFilter, BTreeSet<usize>)>,
+    // Schema nodes of the bookmarked leaf columns.
+    bookmarks: BTreeSet<usize>,
+    // List only the bookmarked columns and the groups around them.
+    bookmarked_only: bool,
+    // Show the schema as a flat list of dotted leaf paths instead of a tree.
+    schema_flat: bool,
+    // Leaves in the order `:sort size` lists them in the flat view, largest
+    // first.
+    sorted_leaves: Option<Vec<usize>>,
+    // Show field ids in the schema tree and the Field ID statistics column.
+    show_field_ids: bool,
+    // Active `:filter` on the Row Groups tab and the row groups it matches.
+    row_group_filter: Option<(RowGroupFilter, Vec<usize>)>,
+    // Show byte offsets and range requests on the Row Groups tab.
+    row_group_layout: bool,
+    // Row group shown and marked in the Row Groups tab.
+    row_groups: RowGroupsState,
+    // Result of the last `:prune` on the Row Groups tab.
+    pruning: Option<PruningReport>,
+    // Result of the last `:bloom` on the Row Groups tab.
+    bloom: Option<BloomReport>,
+    // Page decoded by the last `:page` on the Row Groups tab.
+    decoded_page: Option<DecodedPage>,
+    // Sampled distinct counts by leaf position, filled in on the Schema tab.
+    distinct_estimates: BTreeMap<usize, u64>,
+    // Next leaf to estimate and the number of leaves while estimating.
+    distinct_progress: Option<(usize, usize)>,
+    // Column profiles from `:profile`, shown below the Schema tab.
+    profile: Option<FileProfile>,
+    // Columns extracted with `:json`, appended to every window of rows.
+    json_columns: Vec<JsonColumn>,
+    // Which rows of the file the Visualize tab shows.
+    sampling: Sampling,
+    // Scatter plot from `:corr`, shown over any tab until closed.
+    correlation: Option<Correlation>,
+    // Pattern of the last `/` search, highlighted in the preview.
+    search: Option<ValuePattern>,
+    // Column selected in the last tab with one, selected again in the
+    // next tab switched to.
+    selected_column: Option<SelectedColumn>,
+    // Register keys are being recorded into, shown in the footer.
+    recording_macro: Option<char>,
 }
 
 impl Default for AppState {
@@ -65,71 +525,177 @@ impl Default for AppState {
 impl AppState {
     pub fn new() -> Self {
         Self {
-            horizontal_offset: 0,
-            vertical_offset: 0,
-            tree_scroll_offset: 0,
-            data_vertical_scroll: 0,
+            tab_states: vec![TabState::default(); 4],
+            active_tab: 0,
             visible_data_rows: 20, // Default fallback
+            visible_columns: 1,
             max_horizontal_offset: usize::MAX,
+            layout: LayoutConfig::default(),
+            natural_tree_percent: 25,
+            show_help: false,
+            help_scroll: 0,
+            inspecting: false,
+            inspector_cursor: 0,
+            inspector_folded: BTreeSet::new(),
+            expand_row: false,
+            wrapped_rows: BTreeSet::new(),
+            command_line: None,
+            status: None,
+            collapsed_groups: BTreeSet::new(),
+            column_filter: None,
+            bookmarks: BTreeSet::new(),
+            bookmarked_only: false,
+            schema_flat: false,
+            sorted_leaves: None,
+            show_field_ids: false,
+            row_group_filter: None,
+            row_group_layout: false,
+            row_groups: RowGroupsState::default(),
+            pruning: None,
+            bloom: None,
+            decoded_page: None,
+            distinct_estimates: BTreeMap::new(),
+            distinct_progress: None,
+            profile: None,
+            json_columns: Vec::new(),
+            sampling: Sampling::default(),
+            correlation: None,
+            search: None,
+            selected_column: None,
+            recording_macro: None,
+        }
+    }
+
+    fn tab(&self) -> &TabState {
+        &self.tab_states[self.active_tab]
+    }
+
+    fn tab_mut(&mut self) -> &mut TabState {
+        &mut self.tab_states[self.active_tab]
+    }
+
+    /// Make tab `index` the one whose positions are read and moved.
+    pub fn set_active_tab(&mut self, index: usize) {
+        if index < self.tab_states.len() {
+            self.active_tab = index;
         }
+        // Recomputed by the next frame of the tab.
+        self.max_horizontal_offset = usize::MAX;
     }
 
+    /// Back to the top of the active tab. The other tabs keep their places.
     pub fn reset(&mut self) {
-        self.horizontal_offset = 0;
-        self.vertical_offset = 0;
-        self.tree_scroll_offset = 0;
-        self.data_vertical_scroll = 0;
+        *self.tab_mut() = TabState::default();
+        self.max_horizontal_offset = usize::MAX;
+        if self.active_tab == TabKind::RowGroups.index() {
+            self.row_groups = RowGroupsState::default();
+        }
+    }
+
+    /// Row group shown in the Row Groups tab.
+    pub fn row_group(&self) -> usize {
+        self.row_groups.row_group
+    }
+
+    pub fn set_row_group(&mut self, row_group: usize) {
+        self.row_groups.row_group = row_group;
     }
 
     pub fn horizontal_offset(&self) -> usize {
-        self.horizontal_offset
+        self.tab().horizontal_offset
     }
 
     pub fn vertical_offset(&self) -> usize {
-        self.vertical_offset
+        self.tab().vertical_offset
+    }
+
+    pub fn set_vertical_offset(&mut self, offset: usize) {
+        let tab = self.tab_mut();
+        tab.vertical_offset = offset;
+        tab.selected_group = None;
     }
 
     pub fn down(&mut self) {
-        self.vertical_offset += 1;
+        self.tab_mut().vertical_offset += 1;
     }
 
     pub fn up(&mut self) {
-        self.vertical_offset = self.vertical_offset.saturating_sub(1);
+        let tab = self.tab_mut();
+        tab.vertical_offset = tab.vertical_offset.saturating_sub(1);
+    }
+
+    pub fn set_horizontal_offset(&mut self, offset: usize) {
+        self.tab_mut().horizontal_offset = offset.min(self.max_horizontal_offset);
     }
 
     pub fn right(&mut self) {
-        self.horizontal_offset = (self.horizontal_offset + 1).min(self.max_horizontal_offset);
+        let max = self.max_horizontal_offset;
+        let tab = self.tab_mut();
+        tab.horizontal_offset = (tab.horizontal_offset + 1).min(max);
     }
 
     pub fn left(&mut self) {
-        self.horizontal_offset = self.horizontal_offset.saturating_sub(1);
+        let tab = self.tab_mut();
+        tab.horizontal_offset = tab.horizontal_offset.saturating_sub(1);
+    }
+
+    /// Scroll right by a screenful of columns.
+    pub fn page_right(&mut self) {
+        let (max, step) = (self.max_horizontal_offset, self.visible_columns);
+        let tab = self.tab_mut();
+        tab.horizontal_offset = (tab.horizontal_offset + step).min(max);
+    }
+
+    /// Scroll left by a screenful of columns.
+    pub fn page_left(&mut self) {
+        let step = self.visible_columns;
+        let tab = self.tab_mut();
+        tab.horizontal_offset = tab.horizontal_offset.saturating_sub(step);
+    }
+
+    pub fn visible_columns(&self) -> usize {
+        self.visible_columns
+    }
+
+    pub fn set_visible_columns(&mut self, columns: usize) {
+        self.visible_columns = columns.max(1);
     }
 
     /// Set the upper bound for horizontal scrolling and clamp the current offset
     /// to it (handles overshoot from a previous frame and terminal resizes).
+    pub fn max_horizontal_offset(&self) -> usize {
+        self.max_horizontal_offset
+    }
+
     pub fn set_max_horizontal_offset(&mut self, max: usize) {
         self.max_horizontal_offset = max;
-        self.horizontal_offset = self.horizontal_offset.min(max);
+        let tab = self.tab_mut();
+        tab.horizontal_offset = tab.horizontal_offset.min(max);
     }
 
     pub fn tree_scroll_offset(&self) -> usize {
-        self.tree_scroll_offset
+        self.tab().tree_scroll_offset
     }
 
     pub fn tree_scroll_up(&mut self) {
-        self.tree_scroll_offset = self.tree_scroll_offset.saturating_sub(1);
+        let tab = self.tab_mut();
+        tab.tree_scroll_offset = tab.tree_scroll_offset.saturating_sub(1);
     }
 
     pub fn tree_scroll_down(&mut self) {
-        self.tree_scroll_offset += 1;
+        self.tab_mut().tree_scroll_offset += 1;
+    }
+
+    pub fn set_tree_scroll_offset(&mut self, offset: usize) {
+        self.tab_mut().tree_scroll_offset = offset;
     }
 
     pub fn data_vertical_scroll(&self) -> usize {
-        self.data_vertical_scroll
+        self.tab().data_vertical_scroll
     }
 
     pub fn set_data_vertical_scroll(&mut self, scroll: usize) {
-        self.data_vertical_scroll = scroll;
+        self.tab_mut().data_vertical_scroll = scroll;
     }
 
     pub fn visible_data_rows(&self) -> usize {
@@ -140,160 +706,4014 @@ impl AppState {
         self.visible_data_rows = rows;
     }
 
-    pub fn page_up(&mut self, visible_rows: usize, max_rows: usize) {
-        // Move selection up by visible_rows
-        self.vertical_offset = self.vertical_offset.saturating_sub(visible_rows);
-        // Adjust scroll to keep selection visible
-        self.adjust_scroll_to_selection(visible_rows, max_rows);
+    pub fn layout(&self) -> LayoutConfig {
+        self.layout
     }
 
-    pub fn page_down(&mut self, visible_rows: usize, max_rows: usize) {
-        // Move selection down by visible_rows, clamped to max_rows - 1
-        self.vertical_offset =
-            (self.vertical_offset + visible_rows).min(max_rows.saturating_sub(1));
-        // Adjust scroll to keep selection visible
-        self.adjust_scroll_to_selection(visible_rows, max_rows);
+    pub fn set_layout(&mut self, layout: LayoutConfig) {
+        self.layout = layout;
     }
 
-    pub fn adjust_scroll_to_selection(&mut self, visible_rows: usize, max_rows: usize) {
-        // Ensure selected row is visible in viewport
-        if self.vertical_offset < self.data_vertical_scroll {
-            // Selection is above viewport, scroll up
-            self.data_vertical_scroll = self.vertical_offset;
-        } else if self.vertical_offset >= self.data_vertical_scroll + visible_rows {
-            // Selection is below viewport, scroll down
-            self.data_vertical_scroll = self.vertical_offset.saturating_sub(visible_rows - 1);
+    pub fn set_natural_tree_percent(&mut self, percent: u16) {
+        self.natural_tree_percent = percent;
+    }
+
+    /// Grow (positive) or shrink (negative) the schema tree pane by `delta`
+    /// percent of the body width. Also brings a collapsed tree back.
+    pub fn resize_tree_pane(&mut self, delta: i16) {
+        let current = self
+            .layout
+            .tree_percent
+            .unwrap_or(self.natural_tree_percent) as i16;
+        self.set_tree_percent((current + delta).max(0) as u16);
+    }
+
+    pub fn set_tree_percent(&mut self, percent: u16) {
+        self.layout.tree_percent = Some(percent.clamp(
+            LayoutConfig::MIN_TREE_PERCENT,
+            LayoutConfig::MAX_TREE_PERCENT,
+        ));
+        self.layout.tree_collapsed = false;
+    }
+
+    pub fn toggle_tree_pane(&mut self) {
+        self.layout.tree_collapsed = !self.layout.tree_collapsed;
+    }
+
+    pub fn toggle_split(&mut self) {
+        self.layout.split = !self.layout.split;
+    }
+
+    pub fn show_help(&self) -> bool {
+        self.show_help
+    }
+
+    pub fn toggle_help(&mut self) {
+        self.show_help = !self.show_help;
+        self.help_scroll = 0;
+    }
+
+    pub fn inspecting(&self) -> bool {
+        self.inspecting
+    }
+
+    pub fn toggle_inspector(&mut self) {
+        self.inspecting = !self.inspecting;
+        self.inspector_cursor = 0;
+        self.inspector_folded.clear();
+    }
+
+    pub fn expand_row(&self) -> bool {
+        self.expand_row
+    }
+
+    pub fn toggle_expand_row(&mut self) {
+        self.expand_row = !self.expand_row;
+    }
+
+    pub fn wrapped_rows(&self) -> &BTreeSet<usize> {
+        &self.wrapped_rows
+    }
+
+    /// Wrap the row at `position` in the file, or unwrap it if it was.
+    pub fn toggle_wrapped_row(&mut self, position: usize) {
+        if !self.wrapped_rows.remove(&position) {
+            self.wrapped_rows.insert(position);
         }
+    }
 
-        // Clamp scroll to valid range
-        let max_scroll = max_rows.saturating_sub(visible_rows);
-        self.data_vertical_scroll = self.data_vertical_scroll.min(max_scroll);
+    pub fn inspector_cursor(&self) -> usize {
+        self.inspector_cursor
     }
-}
 
-impl<'a> App<'a> {
-    pub fn new(file_info: &'a ParquetCtx) -> Self {
-        let sample_data_rows = file_info.sample_data.total_rows;
+    pub fn set_inspector_cursor(&mut self, cursor: usize) {
+        self.inspector_cursor = cursor;
+    }
 
-        let tab_manager = TabManager::new(
-            file_info.schema.column_size(),
-            file_info.row_groups.num_row_groups(),
-            sample_data_rows,
-            file_info.metadata.total_property_display_lines(),
-        );
+    pub fn inspector_folded(&self) -> &BTreeSet<String> {
+        &self.inspector_folded
+    }
 
-        Self {
-            parquet_ctx: file_info,
-            file_name: file_info.file_path.clone(),
-            exit: false,
-            tabs: tab_manager,
-            state: AppState::new(),
+    /// Fold the object or array at `path` in the inspector, or unfold it.
+    pub fn set_inspector_folded(&mut self, path: String, folded: bool) {
+        if folded {
+            self.inspector_folded.insert(path);
+        } else {
+            self.inspector_folded.remove(&path);
         }
     }
 
-    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
-        while !self.exit {
-            // Calculate visible data rows based on terminal size
-            let terminal_size = terminal.size()?;
-            // Account for: header (3 lines), footer (1 line), table header (3 lines) = 7 lines total
-            let visible_data_rows = (terminal_size.height.saturating_sub(7) as usize).max(1);
-            self.state.set_visible_data_rows(visible_data_rows);
-
-            // Bound horizontal column scrolling on the Visualize tab to what
-            // actually fits, so it can't overshoot the last visible column (which
-            // left phantom offset, causing "empty" presses when scrolling back).
-            // The data table spans the full terminal width, so it is the width we
-            // pass here. Other tabs keep their own bounds (unbounded here).
-            let max_horizontal_offset = if self.tabs.active_tab().to_string() == "Visualize" {
-                crate::components::DataTable::new(&self.parquet_ctx.sample_data)
-                    .with_vertical_scroll(self.state.data_vertical_scroll())
-                    .max_horizontal_scroll(terminal_size.width)
-            } else {
-                usize::MAX
-            };
-            self.state.set_max_horizontal_offset(max_horizontal_offset);
+    pub fn help_scroll(&self) -> usize {
+        self.help_scroll
+    }
 
-            let render_view = AppRenderView::from_app(self);
-            terminal.draw(|frame| crate::ui::render_app(&render_view, frame))?;
-            self.handle_events()?;
+    pub fn set_help_scroll(&mut self, scroll: usize) {
+        self.help_scroll = scroll;
+    }
+
+    pub fn row_group_layout(&self) -> bool {
+        self.row_group_layout
+    }
+
+    pub fn toggle_row_group_layout(&mut self) {
+        self.row_group_layout = !self.row_group_layout;
+    }
+
+    pub fn marked_row_groups(&self) -> &[usize] {
+        &self.row_groups.marked
+    }
+
+    /// The two marked row groups, lowest first, once both are marked.
+    pub fn compared_row_groups(&self) -> Option<(usize, usize)> {
+        match self.row_groups.marked[..] {
+            [a, b] => Some((a.min(b), a.max(b))),
+            _ => None,
         }
-        Ok(())
     }
 
-    fn handle_events(&mut self) -> io::Result<()> {
-        match event::read()? {
-            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                self.handle_key_event(key_event)
+    /// Mark the selected row group for comparison, or unmark it if it is
+    /// marked. Marking a third row group drops the oldest mark.
+    pub fn toggle_row_group_mark(&mut self) {
+        let row_group = self.row_groups.row_group;
+        let marked = &mut self.row_groups.marked;
+        let text = if let Some(pos) = marked.iter().position(|&rg| rg == row_group) {
+            marked.remove(pos);
+            format!("unmarked row group {}", row_group + 1)
+        } else {
+            if marked.len() == 2 {
+                marked.remove(0);
+            }
+            marked.push(row_group);
+            match self.compared_row_groups() {
+                Some((a, b)) => format!("comparing row groups {} and {}", a + 1, b + 1),
+                None => format!(
+                    "marked row group {}, mark another to compare",
+                    row_group + 1
+                ),
             }
-            _ => {}
         };
-        Ok(())
+        self.set_status(StatusMessage::info(text));
     }
 
-    fn handle_key_event(&mut self, key_event: KeyEvent) {
-        match key_event.code {
-            KeyCode::Char('q') | KeyCode::Char('Q') => self.exit(),
-            KeyCode::Esc => self.state.reset(),
-            KeyCode::Tab => {
-                self.tabs.next();
-                self.state.reset();
-            }
-            KeyCode::BackTab => {
-                self.tabs.prev();
-                self.state.reset();
-            }
-            _ => {
-                self.tabs
-                    .active_tab()
-                    .on_event(key_event, &mut self.state)
-                    .unwrap();
-            }
+    pub fn pruning(&self) -> Option<&PruningReport> {
+        self.pruning.as_ref()
+    }
+
+    pub fn set_pruning(&mut self, report: Option<PruningReport>) {
+        self.pruning = report;
+    }
+
+    pub fn bloom(&self) -> Option<&BloomReport> {
+        self.bloom.as_ref()
+    }
+
+    pub fn set_bloom(&mut self, report: Option<BloomReport>) {
+        self.bloom = report;
+    }
+
+    /// The page decoded by `:page`, while its row group and column are the
+    /// ones selected.
+    pub fn decoded_page(&self) -> Option<&DecodedPage> {
+        self.decoded_page.as_ref().filter(|page| {
+            page.row_group == self.row_group()
+                && Some(page.column) == self.vertical_offset().checked_sub(1)
+        })
+    }
+
+    pub fn set_decoded_page(&mut self, page: Option<DecodedPage>) {
+        self.decoded_page = page;
+    }
+
+    pub fn profile(&self) -> Option<&FileProfile> {
+        self.profile.as_ref()
+    }
+
+    pub fn set_profile(&mut self, profile: Option<FileProfile>) {
+        self.profile = profile;
+    }
+
+    pub fn json_columns(&self) -> &[JsonColumn] {
+        &self.json_columns
+    }
+
+    pub fn add_json_column(&mut self, column: JsonColumn) {
+        self.json_columns.push(column);
+    }
+
+    /// Forget the extracted columns, returning how many there were.
+    pub fn clear_json_columns(&mut self) -> usize {
+        std::mem::take(&mut self.json_columns).len()
+    }
+
+    pub fn sampling(&self) -> Sampling {
+        self.sampling
+    }
+
+    pub fn set_sampling(&mut self, sampling: Sampling) {
+        self.sampling = sampling;
+    }
+
+    pub fn correlation(&self) -> Option<&Correlation> {
+        self.correlation.as_ref()
+    }
+
+    pub fn set_correlation(&mut self, correlation: Option<Correlation>) {
+        self.correlation = correlation;
+    }
+
+    pub fn search(&self) -> Option<&ValuePattern> {
+        self.search.as_ref()
+    }
+
+    pub fn set_search(&mut self, search: Option<ValuePattern>) {
+        self.search = search;
+    }
+
+    pub fn recording_macro(&self) -> Option<char> {
+        self.recording_macro
+    }
+
+    pub fn set_recording_macro(&mut self, register: Option<char>) {
+        self.recording_macro = register;
+    }
+
+    pub fn selected_column(&self) -> Option<&SelectedColumn> {
+        self.selected_column.as_ref()
+    }
+
+    pub fn set_selected_column(&mut self, column: Option<SelectedColumn>) {
+        self.selected_column = column;
+    }
+
+    pub fn distinct_estimates(&self) -> &BTreeMap<usize, u64> {
+        &self.distinct_estimates
+    }
+
+    /// Leaves estimated so far and the total, while estimating.
+    pub fn distinct_progress(&self) -> Option<(usize, usize)> {
+        self.distinct_progress
+    }
+
+    /// Queue every one of `leaves` columns for estimation.
+    pub fn start_distinct_estimates(&mut self, leaves: usize) {
+        self.distinct_estimates.clear();
+        self.distinct_progress = (leaves > 0).then_some((0, leaves));
+    }
+
+    /// Record the estimate of the leaf being worked on (`None` if it could
+    /// not be read) and move on to the next one.
+    pub fn record_distinct_estimate(&mut self, estimate: Option<u64>) {
+        let Some((leaf, total)) = self.distinct_progress else {
+            return;
+        };
+        if let Some(estimate) = estimate {
+            self.distinct_estimates.insert(leaf, estimate);
         }
+        self.distinct_progress = (leaf + 1 < total).then_some((leaf + 1, total));
     }
 
-    fn exit(&mut self) {
-        self.exit = true;
+    pub fn collapsed_groups(&self) -> &BTreeSet<usize> {
+        &self.collapsed_groups
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    pub fn schema_flat(&self) -> bool {
+        self.schema_flat
+    }
 
-    #[test]
-    fn test_right_is_clamped_to_max_horizontal_offset() {
-        let mut state = AppState::new();
-        state.set_max_horizontal_offset(3);
-        for _ in 0..10 {
-            state.right();
+    pub fn set_schema_flat(&mut self, flat: bool) {
+        self.schema_flat = flat;
+        if !flat {
+            self.sorted_leaves = None;
         }
-        // Without the clamp the offset would run past the visible range,
-        // producing the "empty clicks" seen when scrolling back.
-        assert_eq!(state.horizontal_offset(), 3);
     }
 
-    #[test]
-    fn test_left_saturates_at_zero() {
-        let mut state = AppState::new();
-        state.set_max_horizontal_offset(5);
-        state.right();
-        state.right();
-        state.left();
-        state.left();
-        state.left();
-        assert_eq!(state.horizontal_offset(), 0);
+    pub fn sorted_by_size(&self) -> bool {
+        self.sorted_leaves.is_some()
     }
 
-    #[test]
-    fn test_shrinking_the_max_clamps_the_current_offset() {
-        let mut state = AppState::new();
-        state.set_max_horizontal_offset(10);
-        for _ in 0..10 {
-            state.right();
+    /// List `leaves` in this order in the flat view (from
+    /// [`crate::file::schema::FileSchema::leaves_by_size`]), switching to it; `None` restores the
+    /// schema order.
+    pub fn set_sorted_leaves(&mut self, outline: &SchemaOutline, leaves: Option<Vec<usize>>) {
+        if leaves.is_some() && !self.schema_flat {
+            self.toggle_schema_flat(outline);
         }
-        assert_eq!(state.horizontal_offset(), 10);
-        // e.g. the terminal was widened, so fewer columns need scrolling.
-        state.set_max_horizontal_offset(4);
-        assert_eq!(state.horizontal_offset(), 4);
+        self.sorted_leaves = leaves;
+    }
+
+    pub fn show_field_ids(&self) -> bool {
+        self.show_field_ids
+    }
+
+    pub fn set_show_field_ids(&mut self, show: bool) {
+        self.show_field_ids = show;
+    }
+
+    pub fn toggle_field_ids(&mut self) {
+        self.show_field_ids = !self.show_field_ids;
+    }
+
+    pub fn set_collapsed_groups(&mut self, groups: BTreeSet<usize>) {
+        self.collapsed_groups = groups;
+    }
+
+    /// Switch between the tree and the flat list. Groups are not listed in
+    /// the flat view, so a selected group hands the cursor to its first leaf.
+    pub fn toggle_schema_flat(&mut self, outline: &SchemaOutline) {
+        self.set_schema_flat(!self.schema_flat);
+        if self.schema_flat
+            && let Some(group) = self.tab().selected_group
+        {
+            let first_leaf = (group + 1..outline.subtree_end(group))
+                .find(|&node| outline.leaf_position(node).is_some());
+            self.set_schema_cursor(outline, first_leaf);
+        } else if let Some(node) = self.schema_cursor(outline) {
+            // Unfold whatever hides the selected leaf in the tree.
+            let mut ancestor = outline.parent(node);
+            while let Some(group) = ancestor {
+                self.collapsed_groups.remove(&group);
+                ancestor = outline.parent(group);
+            }
+        }
+    }
+
+    /// Schema nodes listed in the tree pane, in order (the root first).
+    pub fn visible_schema_nodes(&self, outline: &SchemaOutline) -> Vec<usize> {
+        let mut nodes = if let Some(leaves) = &self.sorted_leaves {
+            std::iter::once(0).chain(leaves.iter().copied()).collect()
+        } else if self.schema_flat {
+            outline.flat_nodes()
+        } else {
+            outline.visible_nodes(&self.collapsed_groups)
+        };
+        if let Some((_, listed)) = &self.column_filter {
+            nodes.retain(|node| listed.contains(node));
+        }
+        if self.bookmarked_only {
+            let mut listed = BTreeSet::from([0]);
+            for &bookmark in &self.bookmarks {
+                let mut ancestor = Some(bookmark);
+                while let Some(node) = ancestor
+                    && listed.insert(node)
+                {
+                    ancestor = outline.parent(node);
+                }
+            }
+            nodes.retain(|node| listed.contains(node));
+        }
+        nodes
+    }
+
+    pub fn bookmarks(&self) -> &BTreeSet<usize> {
+        &self.bookmarks
+    }
+
+    pub fn set_bookmarks(&mut self, bookmarks: BTreeSet<usize>) {
+        self.bookmarks = bookmarks;
+        if self.bookmarks.is_empty() {
+            self.bookmarked_only = false;
+        }
+    }
+
+    /// Bookmark the leaves of `leaves` that aren't yet, or remove them all
+    /// when they all are. Returns whether they are now bookmarked.
+    pub fn toggle_bookmarks(&mut self, leaves: &[usize]) -> bool {
+        let add = !leaves.iter().all(|leaf| self.bookmarks.contains(leaf));
+        for leaf in leaves {
+            if add {
+                self.bookmarks.insert(*leaf);
+            } else {
+                self.bookmarks.remove(leaf);
+            }
+        }
+        if self.bookmarks.is_empty() {
+            self.bookmarked_only = false;
+        }
+        add
+    }
+
+    pub fn bookmarked_only(&self) -> bool {
+        self.bookmarked_only
+    }
+
+    pub fn set_bookmarked_only(&mut self, only: bool) {
+        self.bookmarked_only = only && !self.bookmarks.is_empty();
+    }
+
+    pub fn column_filter(&self) -> Option<&ColumnFilter> {
+        self.column_filter.as_ref().map(|(filter, _)| filter)
+    }
+
+    /// List only the schema nodes in `listed` (from
+    /// [`ColumnFilter::matching`]).
+    pub fn set_column_filter(&mut self, filter: ColumnFilter, listed: BTreeSet<usize>) {
+        self.column_filter = Some((filter, listed));
+    }
+
+    pub fn clear_column_filter(&mut self) {
+        self.column_filter = None;
+    }
+
+    pub fn selected_group(&self) -> Option<usize> {
+        self.tab().selected_group
+    }
+
+    /// Schema node under the tree cursor: the selected group or leaf.
+    pub fn schema_cursor(&self, outline: &SchemaOutline) -> Option<usize> {
+        let tab = self.tab();
+        tab.selected_group
+            .or_else(|| outline.leaf_node(tab.vertical_offset))
+    }
+
+    /// Put the tree cursor on schema node `node` (`None` clears it).
+    pub fn set_schema_cursor(&mut self, outline: &SchemaOutline, node: Option<usize>) {
+        match node {
+            Some(node) if outline.is_group(node) => {
+                let tab = self.tab_mut();
+                tab.vertical_offset = 0;
+                tab.selected_group = Some(node);
+            }
+            Some(node) => {
+                self.set_vertical_offset(outline.leaf_position(node).map_or(0, |leaf| leaf + 1))
+            }
+            None => self.set_vertical_offset(0),
+        }
+    }
+
+    /// Move the tree cursor `delta` rows over the visible groups and leaves.
+    /// Moving up from the first row clears the selection.
+    pub fn move_schema_cursor(&mut self, outline: &SchemaOutline, delta: isize) {
+        // The root is not selectable.
+        let rows: Vec<usize> = self
+            .visible_schema_nodes(outline)
+            .into_iter()
+            .skip(1)
+            .collect();
+        if rows.is_empty() {
+            return;
+        }
+        let current = self
+            .schema_cursor(outline)
+            .and_then(|node| rows.iter().position(|&n| n == node));
+        let target = match current {
+            None if delta > 0 => Some((delta as usize - 1).min(rows.len() - 1)),
+            None => None,
+            Some(pos) => pos.checked_add_signed(delta).map(|p| p.min(rows.len() - 1)),
+        };
+        self.set_schema_cursor(outline, target.map(|pos| rows[pos]));
+    }
+
+    /// Fold the group under the cursor, or the group containing the selected
+    /// leaf (moving the cursor onto it).
+    pub fn collapse_group(&mut self, outline: &SchemaOutline) {
+        let Some(node) = self.schema_cursor(outline).filter(|_| !self.schema_flat) else {
+            return;
+        };
+        let group = if outline.is_group(node) {
+            Some(node)
+        } else {
+            outline.parent(node).filter(|&p| outline.is_group(p))
+        };
+        if let Some(group) = group {
+            self.collapsed_groups.insert(group);
+            self.set_schema_cursor(outline, Some(group));
+        }
+    }
+
+    pub fn expand_group(&mut self) {
+        if let Some(group) = self.tab().selected_group {
+            self.collapsed_groups.remove(&group);
+        }
+    }
+
+    /// Expand the selected group if it is folded, fold it otherwise.
+    pub fn toggle_group(&mut self, outline: &SchemaOutline) {
+        match self.tab().selected_group {
+            Some(group) if self.collapsed_groups.contains(&group) => self.expand_group(),
+            _ => self.collapse_group(outline),
+        }
+    }
+
+    pub fn command_line(&self) -> Option<&str> {
+        self.command_line.as_deref()
+    }
+
+    pub fn open_command_line(&mut self, initial: &str) {
+        self.command_line = Some(initial.to_string());
+        self.status = None;
+    }
+
+    /// Close the command line, returning what was typed.
+    pub fn close_command_line(&mut self) -> Option<String> {
+        self.command_line.take()
+    }
+
+    pub fn push_command_char(&mut self, c: char) {
+        if let Some(line) = self.command_line.as_mut() {
+            line.push(c);
+        }
+    }
+
+    /// Delete the last character; deleting from an empty line closes it.
+    pub fn pop_command_char(&mut self) {
+        if let Some(line) = self.command_line.as_mut()
+            && line.pop().is_none()
+        {
+            self.command_line = None;
+        }
+    }
+
+    pub fn status(&self) -> Option<&StatusMessage> {
+        self.status.as_ref()
+    }
+
+    pub fn set_status(&mut self, status: StatusMessage) {
+        self.status = Some(status);
+    }
+
+    pub fn clear_status(&mut self) {
+        self.status = None;
+    }
+
+    /// Drop the status message once its timeout has passed.
+    pub fn expire_status(&mut self, now: Instant) {
+        if self.status.as_ref().is_some_and(|s| s.is_expired(now)) {
+            self.status = None;
+        }
+    }
+
+    pub fn row_group_filter(&self) -> Option<&RowGroupFilter> {
+        self.row_group_filter.as_ref().map(|(filter, _)| filter)
+    }
+
+    /// Row groups matching the active filter, or `None` when unfiltered.
+    pub fn filtered_row_groups(&self) -> Option<&[usize]> {
+        self.row_group_filter
+            .as_ref()
+            .map(|(_, matches)| matches.as_slice())
+    }
+
+    /// Filter the row groups to `matches` (ascending, non-empty) and move to
+    /// the first match at or after the current row group.
+    pub fn set_row_group_filter(&mut self, filter: RowGroupFilter, matches: Vec<usize>) {
+        let current = self.row_groups.row_group;
+        self.row_groups.row_group = matches
+            .iter()
+            .copied()
+            .find(|&idx| idx >= current)
+            .or(matches.first().copied())
+            .unwrap_or(current);
+        self.row_group_filter = Some((filter, matches));
+    }
+
+    pub fn clear_row_group_filter(&mut self) {
+        self.row_group_filter = None;
+    }
+
+    /// Step to the next (`forward`) or previous row group matching the filter.
+    pub fn step_filtered_row_group(&mut self, forward: bool) {
+        let Some(matches) = self.filtered_row_groups() else {
+            return;
+        };
+        let current = self.row_groups.row_group;
+        let next = if forward {
+            matches.iter().copied().find(|&idx| idx > current)
+        } else {
+            matches.iter().rev().copied().find(|&idx| idx < current)
+        };
+        if let Some(idx) = next {
+            self.row_groups.row_group = idx;
+        }
+    }
+
+    pub fn page_up(&mut self, visible_rows: usize, max_rows: usize) {
+        // Move selection up by visible_rows
+        let tab = self.tab_mut();
+        tab.vertical_offset = tab.vertical_offset.saturating_sub(visible_rows);
+        // Adjust scroll to keep selection visible
+        self.adjust_scroll_to_selection(visible_rows, max_rows);
+    }
+
+    pub fn page_down(&mut self, visible_rows: usize, max_rows: usize) {
+        // Move selection down by visible_rows, clamped to max_rows - 1
+        let tab = self.tab_mut();
+        tab.vertical_offset = (tab.vertical_offset + visible_rows).min(max_rows.saturating_sub(1));
+        // Adjust scroll to keep selection visible
+        self.adjust_scroll_to_selection(visible_rows, max_rows);
+    }
+
+    pub fn adjust_scroll_to_selection(&mut self, visible_rows: usize, max_rows: usize) {
+        // Ensure selected row is visible in viewport
+        let tab = self.tab_mut();
+        if tab.vertical_offset < tab.data_vertical_scroll {
+            // Selection is above viewport, scroll up
+            tab.data_vertical_scroll = tab.vertical_offset;
+        } else if tab.vertical_offset >= tab.data_vertical_scroll + visible_rows {
+            // Selection is below viewport, scroll down
+            tab.data_vertical_scroll = tab.vertical_offset.saturating_sub(visible_rows - 1);
+        }
+
+        // Clamp scroll to valid range
+        let max_scroll = max_rows.saturating_sub(visible_rows);
+        tab.data_vertical_scroll = tab.data_vertical_scroll.min(max_scroll);
+    }
+}
+
+impl<'a> App<'a> {
+    pub fn new(file_info: &'a ParquetCtx) -> Self {
+        let sample_data_rows = file_info.sample_data.total_rows;
+
+        let tab_manager = TabManager::new(
+            file_info.schema.outline(),
+            file_info.schema.column_size(),
+            file_info.row_groups.num_row_groups(),
+            sample_data_rows,
+            file_info.metadata.total_property_display_lines(),
+        );
+
+        Self {
+            parquet_ctx: file_info,
+            file_name: file_info.file_path.clone(),
+            exit: false,
+            tabs: tab_manager,
+            state: AppState::new(),
+            config: AppConfig::default(),
+            theme: Theme::default(),
+            render_mode: RenderMode::default(),
+            keymap: Keymap::default(),
+            data_window: None,
+            mouse_regions: RefCell::new(MouseRegions::default()),
+            drag: None,
+            inspector_images: None,
+            page_previews: None,
+            tasks: TaskRunner::new(),
+            ticks: 0,
+            pending_external: None,
+            jumps: JumpList::default(),
+            macros: Macros::default(),
+            recording: None,
+            pending_register: None,
+            last_macro: None,
+            replaying: false,
+        }
+    }
+
+    pub fn sample_data(&self) -> &ParquetSampleData {
+        self.data_window
+            .as_ref()
+            .unwrap_or(&self.parquet_ctx.sample_data)
+    }
+
+    pub fn with_config(mut self, config: AppConfig) -> Self {
+        self.state.set_layout(config.layout);
+        self.state.set_sampling(config.display.sampling);
+        self.select_tab(config.display.default_tab.index());
+        // The config was validated when loaded, so this only falls back for
+        // configs built in code.
+        self.theme = config.theme.to_theme().unwrap_or_default();
+        self.keymap = Keymap::from_config(&config.keybindings).unwrap_or_default();
+        self.config = config;
+        self
+    }
+
+    /// Start with the macros saved by earlier runs.
+    pub fn with_macros(mut self, macros: Macros) -> Self {
+        self.macros = macros;
+        self
+    }
+
+    pub fn macros(&self) -> &Macros {
+        &self.macros
+    }
+
+    /// Draw without colors and/or with ASCII only.
+    pub fn with_render_mode(mut self, mode: RenderMode) -> Self {
+        self.render_mode = mode;
+        self
+    }
+
+    /// Start with `status` in the footer, e.g. a warning from startup.
+    pub fn with_status(mut self, status: StatusMessage) -> Self {
+        self.state.set_status(status);
+        self
+    }
+
+    /// Write settings changed during the session (e.g. pane sizes) back to
+    /// the `[layout]` table of the config file.
+    pub fn persist_config(&mut self) -> Result<(), ConfigError> {
+        if self.state.layout() == self.config.layout {
+            return Ok(());
+        }
+        self.config.layout = self.state.layout();
+        AppConfig::save_layout(&self.config.layout)
+    }
+
+    /// Pick up where `session` left off. Anything the file no longer has,
+    /// e.g. after it was rewritten with fewer columns, starts from the top.
+    pub fn with_session(mut self, session: Session) -> Self {
+        let ctx = self.parquet_ctx;
+        let outline = ctx.schema.outline();
+        self.select_tab(session.tab);
+
+        if session.first_row > 0
+            && session.first_row < ctx.metadata.num_rows
+            && let Ok(window) = ParquetSampleData::read_window_with(
+                &self.file_name,
+                session.first_row,
+                &self.config.display.preview(),
+            )
+        {
+            self.data_window = Some(window);
+        }
+
+        let groups = session
+            .collapsed_groups
+            .into_iter()
+            .filter(|&group| group < outline.len() && outline.is_group(group))
+            .collect();
+        self.state.set_collapsed_groups(groups);
+        self.state.set_schema_flat(session.schema_flat);
+        self.state.set_show_field_ids(session.show_field_ids);
+        let bookmarks = session
+            .bookmarks
+            .into_iter()
+            .filter(|&node| outline.leaf_position(node).is_some())
+            .collect();
+        self.state.set_bookmarks(bookmarks);
+        self.state.set_bookmarked_only(session.bookmarked_only);
+        if let Some(filter) = session
+            .column_filter
+            .and_then(|filter| filter.parse::<ColumnFilter>().ok())
+        {
+            let listed = filter.matching(&ctx.schema);
+            self.state.set_column_filter(filter, listed);
+        }
+
+        let max_vertical = ctx.column_size().max(self.sample_data().total_rows);
+        match session.selected_group {
+            Some(group) if group < outline.len() && outline.is_group(group) => {
+                self.state.set_schema_cursor(&outline, Some(group))
+            }
+            _ => self
+                .state
+                .set_vertical_offset(session.vertical_offset.min(max_vertical)),
+        }
+        let max_horizontal = ctx
+            .column_size()
+            .max(ctx.row_groups.num_row_groups())
+            .saturating_sub(1);
+        self.state
+            .set_horizontal_offset(session.horizontal_offset.min(max_horizontal));
+        self.state.set_row_group(
+            session
+                .row_group
+                .min(ctx.row_groups.num_row_groups().saturating_sub(1)),
+        );
+        self.state
+            .set_tree_scroll_offset(session.tree_scroll_offset.min(outline.len()));
+        self.state
+            .set_data_vertical_scroll(session.data_vertical_scroll.min(max_vertical));
+        self
+    }
+
+    /// Open at `position`, e.g. one given on the command line to reproduce
+    /// a bug report. Fails when the position is not in the file or does not
+    /// apply to the tab.
+    pub fn with_position(mut self, position: &StartPosition) -> Result<Self, String> {
+        let ctx = self.parquet_ctx;
+        let tab = position.tab.unwrap_or(if position.row.is_some() {
+            TabKind::Visualize
+        } else if position.row_group.is_some() {
+            TabKind::RowGroups
+        } else if position.column.is_some() {
+            TabKind::Schema
+        } else {
+            return Ok(self);
+        });
+        self.select_tab(tab.index());
+
+        let row_group = match position.row_group {
+            Some(0) => return Err("row groups are numbered from 1".to_string()),
+            Some(n) => {
+                let num_row_groups = ctx.row_groups.num_row_groups();
+                if n > num_row_groups {
+                    return Err(format!(
+                        "row group {n} is past the end ({num_row_groups} row groups)"
+                    ));
+                }
+                Some(n - 1)
+            }
+            None => None,
+        };
+        let row = match position.row {
+            Some(0) => return Err("rows are numbered from 1".to_string()),
+            Some(n) => Some(n - 1),
+            None => None,
+        };
+        let node = match &position.column {
+            Some(column) => Some(
+                ctx.schema
+                    .find_path(column)
+                    .ok_or_else(|| format!("no column {column}"))?,
+            ),
+            None => None,
+        };
+
+        match tab {
+            TabKind::Visualize => {
+                match (row, row_group) {
+                    (Some(row), _) => self.goto_row(row)?,
+                    (None, Some(rg)) => self.goto_row(ctx.row_groups.first_row(rg))?,
+                    (None, None) => {}
+                }
+                if let Some(column) = &position.column {
+                    // The table shows top-level columns only.
+                    let top = column.split('.').next().unwrap_or(column);
+                    let offset = self
+                        .sample_data()
+                        .flattened_columns
+                        .iter()
+                        .position(|name| name == top)
+                        .ok_or_else(|| format!("column {column} is not shown in Visualize"))?;
+                    self.state.set_horizontal_offset(offset);
+                }
+            }
+            TabKind::Schema | TabKind::RowGroups if row.is_some() => {
+                return Err("--row only applies to the Visualize tab".to_string());
+            }
+            TabKind::Schema if row_group.is_some() => {
+                return Err("--row-group does not apply to the Schema tab".to_string());
+            }
+            TabKind::Schema | TabKind::RowGroups => {
+                if let Some(rg) = row_group {
+                    self.goto_row_group(rg)?;
+                }
+                if let Some(node) = node {
+                    let outline = ctx.schema.outline();
+                    let mut ancestor = outline.parent(node);
+                    let mut groups = self.state.collapsed_groups().clone();
+                    while let Some(group) = ancestor {
+                        groups.remove(&group);
+                        ancestor = outline.parent(group);
+                    }
+                    self.state.set_collapsed_groups(groups);
+                    self.state.set_schema_flat(false);
+                    self.state.set_schema_cursor(&outline, Some(node));
+                }
+            }
+            TabKind::Metadata if row.is_none() && row_group.is_none() && node.is_none() => {}
+            TabKind::Metadata => {
+                return Err(
+                    "--row, --row-group and --column do not apply to the Metadata tab".to_string(),
+                );
+            }
+        }
+        Ok(self)
+    }
+
+    /// Run the commands of preset `name` as if they were typed on the
+    /// command line. The first one to fail is shown in the footer and the
+    /// rest still run.
+    pub fn with_preset(mut self, name: &str, commands: Vec<Command>) -> Self {
+        let mut error = None;
+        for command in commands {
+            if let Err(e) = self.run_command(command) {
+                error.get_or_insert(e);
+            }
+        }
+        if let Some(e) = error {
+            self.state
+                .set_status(StatusMessage::error(format!("preset {name}: {e}")));
+        }
+        self
+    }
+
+    /// The tab, selection and scroll positions to restore next time.
+    pub fn session(&self) -> Session {
+        Session {
+            file: String::new(),
+            tab: self.tabs.active_tab,
+            vertical_offset: self.state.vertical_offset(),
+            horizontal_offset: self.state.horizontal_offset(),
+            row_group: self.state.row_group(),
+            tree_scroll_offset: self.state.tree_scroll_offset(),
+            data_vertical_scroll: self.state.data_vertical_scroll(),
+            // A sample is read again from the config rather than restored.
+            first_row: match self.sample_data().positions {
+                Some(_) => 0,
+                None => self.sample_data().first_row,
+            },
+            selected_group: self.state.selected_group(),
+            collapsed_groups: self.state.collapsed_groups().clone(),
+            schema_flat: self.state.schema_flat(),
+            show_field_ids: self.state.show_field_ids(),
+            column_filter: self.state.column_filter().map(ToString::to_string),
+            bookmarks: self.state.bookmarks().clone(),
+            bookmarked_only: self.state.bookmarked_only(),
+        }
+    }
+
+    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        self.tasks.forward_input(TICK_RATE);
+        while !self.exit {
+            self.draw(terminal)?;
+            self.handle_events()?;
+            if let Some((name, text)) = self.pending_external.take() {
+                self.open_external(terminal, &name, &text)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Hand the terminal to the configured pager or editor to show `text`,
+    /// and take it back once the program exits.
+    fn open_external(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        name: &str,
+        text: &str,
+    ) -> io::Result<()> {
+        let command = self.config.external.command();
+        let result = self.tasks.without_input(|| {
+            let _ = crossterm::execute!(io::stdout(), DisableMouseCapture);
+            ratatui::restore();
+            let result = crate::external::open_in(&command, name, text);
+            crossterm::terminal::enable_raw_mode()?;
+            crossterm::execute!(io::stdout(), crossterm::terminal::EnterAlternateScreen)?;
+            // Mouse support is best-effort, as on startup.
+            let _ = crossterm::execute!(io::stdout(), EnableMouseCapture);
+            terminal.clear()?;
+            io::Result::Ok(result)
+        })?;
+        if let Err(e) = result {
+            self.state.set_status(StatusMessage::error(e));
+        }
+        Ok(())
+    }
+
+    /// What `p` opens: the selected value in the Visualize tab, the whole
+    /// row in the inspector and the schema as DDL elsewhere, each with a
+    /// file name that tells editors how to highlight it.
+    fn external_text(&self) -> Result<(String, String), String> {
+        let data = self.sample_data();
+        let row = data.rows.get(self.state.vertical_offset());
+        if self.state.inspecting() {
+            let row = row.ok_or_else(|| "no row selected".to_string())?;
+            let object: serde_json::Map<String, serde_json::Value> = data
+                .flattened_columns
+                .iter()
+                .zip(row)
+                .map(|(column, value)| {
+                    let json = cell_json(value).unwrap_or_else(|| value.clone().into());
+                    (column.clone(), json)
+                })
+                .collect();
+            let text = serde_json::to_string_pretty(&object).map_err(|e| e.to_string())?;
+            return Ok(("row.json".to_string(), text + "\n"));
+        }
+        if self.tabs.active_tab().to_string() != "Visualize" {
+            let table = std::path::Path::new(&self.file_name)
+                .file_stem()
+                .map_or("data".into(), |stem| stem.to_string_lossy());
+            let ddl = schema_ddl(&self.parquet_ctx.schema, &table);
+            return Ok(("schema.sql".to_string(), ddl));
+        }
+        let column = self.state.horizontal_offset();
+        let name = data
+            .flattened_columns
+            .get(column)
+            .ok_or_else(|| "no column selected".to_string())?;
+        let value = row
+            .and_then(|row| row.get(column))
+            .ok_or_else(|| "no row selected".to_string())?;
+        Ok(match cell_json(value) {
+            Some(json) => (
+                format!("{name}.json"),
+                serde_json::to_string_pretty(&json).map_err(|e| e.to_string())? + "\n",
+            ),
+            None => (format!("{name}.txt"), format!("{value}\n")),
+        })
+    }
+
+    /// Draw one frame of the app to `terminal`, which can be a real terminal
+    /// or a `TestBackend`.
+    pub fn draw<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
+        self.prepare_frame(terminal.size()?);
+        let render_view = AppRenderView::from_app(self);
+        terminal.draw(|frame| crate::ui::render_app(&render_view, frame))?;
+        Ok(())
+    }
+
+    /// Draw one frame into an off-screen buffer of the given size and return
+    /// it as text, one line per terminal row. Meant for snapshot tests.
+    pub fn render_to_string(&mut self, width: u16, height: u16) -> io::Result<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+        self.draw(&mut terminal)?;
+        Ok(crate::ui::buffer_to_string(terminal.backend().buffer()))
+    }
+
+    /// Handle a key press as if it was typed in the terminal.
+    pub fn handle_key(&mut self, key_event: KeyEvent) {
+        self.handle_key_event(key_event);
+    }
+
+    /// Estimate the distinct values of the next queued leaf column, one per
+    /// frame so the progress shows and keys stay responsive.
+    pub fn estimate_next_distinct(&mut self) {
+        let Some((leaf, total)) = self.state.distinct_progress() else {
+            return;
+        };
+        match estimate_distinct(&self.parquet_ctx.reader, leaf) {
+            Ok(estimate) => self.state.record_distinct_estimate(Some(estimate.estimate)),
+            Err(e) => {
+                self.state.record_distinct_estimate(None);
+                self.state.set_status(StatusMessage::error(format!(
+                    "column {}: {}",
+                    leaf + 1,
+                    e.replace('\n', " ")
+                )));
+            }
+        }
+        if self.state.distinct_progress().is_none() {
+            self.state.set_status(StatusMessage::info(format!(
+                "estimated distinct values of {} / {total} columns",
+                self.state.distinct_estimates().len()
+            )));
+        }
+    }
+
+    /// Read the images of the row open in the inspector, once per row. A
+    /// file that can't be read again just shows no images.
+    fn load_inspector_images(&mut self) {
+        if !self.state.inspecting() {
+            self.inspector_images = None;
+            return;
+        }
+        let row = self.state.vertical_offset();
+        if row >= self.sample_data().rows.len() {
+            return;
+        }
+        let position = self.sample_data().position(row);
+        if self
+            .inspector_images
+            .as_ref()
+            .is_none_or(|(loaded, _)| *loaded != position)
+        {
+            let images = read_images(&self.file_name, position).unwrap_or_default();
+            self.inspector_images = Some((position, images));
+        }
+    }
+
+    /// Decode the first values of the pages of the chunk selected in the
+    /// Row Groups tab, once per chunk. Only that chunk's pages are read.
+    fn load_page_previews(&mut self) {
+        let Some(leaf) = self.state.vertical_offset().checked_sub(1) else {
+            return;
+        };
+        if self.tabs.active_tab != TabKind::RowGroups.index() {
+            return;
+        }
+        let chunk = (self.state.row_group(), leaf);
+        if self
+            .page_previews
+            .as_ref()
+            .is_none_or(|(loaded, _)| *loaded != chunk)
+        {
+            let previews =
+                chunk_previews(&self.parquet_ctx.reader, chunk.0, chunk.1, PREVIEW_PAGES)
+                    .unwrap_or_default();
+            self.page_previews = Some((chunk, previews));
+        }
+    }
+
+    /// Update the state that depends on the terminal size before drawing.
+    fn prepare_frame(&mut self, terminal_size: Size) {
+        // Calculate visible data rows based on terminal size
+        // Account for: header (3 lines), footer (1 line), table header (3 lines) = 7 lines total
+        let visible_data_rows = (terminal_size.height.saturating_sub(7) as usize).max(1);
+        self.state.set_visible_data_rows(visible_data_rows);
+        self.load_inspector_images();
+        self.load_page_previews();
+
+        let schema = &self.parquet_ctx.schema;
+        let field_id_width = if self.state.show_field_ids() {
+            schema.field_id_width()
+        } else {
+            0
+        };
+        let natural_tree_width = if self.state.schema_flat() {
+            schema.flat_width()
+        } else {
+            schema.tree_width()
+        } as u16
+            + field_id_width as u16
+            + 2;
+        self.state.set_natural_tree_percent(
+            (natural_tree_width as u32 * 100 / terminal_size.width.max(1) as u32) as u16,
+        );
+
+        // Bound horizontal column scrolling on the Visualize tab to what
+        // actually fits, so it can't overshoot the last visible column (which
+        // left phantom offset, causing "empty" presses when scrolling back).
+        // The data table spans the full terminal width, so it is the width we
+        // pass here. The Schema tab's statistics table gets what the tree
+        // pane leaves. Other tabs keep their own bounds (unbounded here).
+        let (max_horizontal_offset, visible_columns) =
+            match self.tabs.active_tab().to_string().as_str() {
+                "Visualize" => {
+                    let table = crate::components::DataTable::new(self.sample_data())
+                        .with_max_column_width(self.config.display.cell_width)
+                        .with_horizontal_scroll(self.state.horizontal_offset())
+                        .with_vertical_scroll(self.state.data_vertical_scroll());
+                    (
+                        table.max_horizontal_scroll(terminal_size.width),
+                        table.visible_columns(terminal_size.width),
+                    )
+                }
+                "Schema" => {
+                    let tree_width = self
+                        .state
+                        .layout()
+                        .tree_pane_width(terminal_size.width, natural_tree_width)
+                        .min(terminal_size.width);
+                    let table_width = terminal_size.width - tree_width;
+                    let names_frozen = FileSchemaTable::names_frozen(
+                        self.state.horizontal_offset(),
+                        self.state.layout().tree_collapsed,
+                    );
+                    (
+                        FileSchemaTable::max_horizontal_scroll(
+                            table_width,
+                            self.state.show_field_ids(),
+                        ),
+                        FileSchemaTable::visible_columns(table_width, names_frozen),
+                    )
+                }
+                _ => (usize::MAX, 1),
+            };
+        self.state.set_max_horizontal_offset(max_horizontal_offset);
+        self.state.set_visible_columns(visible_columns);
+
+        self.state.expire_status(Instant::now());
+    }
+
+    fn handle_events(&mut self) -> io::Result<()> {
+        // Keep estimating distinct values while nothing else is waiting.
+        if self.state.distinct_progress().is_some() {
+            match self.tasks.events.try_recv() {
+                Ok(event) => return self.handle_event(event),
+                Err(_) => {
+                    self.estimate_next_distinct();
+                    return Ok(());
+                }
+            }
+        }
+        // Input arrives at least once a tick, so this never blocks for long.
+        let Ok(event) = self.tasks.events.recv() else {
+            return Ok(());
+        };
+        self.handle_event(event)
+    }
+
+    fn handle_event(&mut self, event: AppEvent) -> io::Result<()> {
+        match event {
+            AppEvent::Input(input) => match input? {
+                Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
+                    self.handle_key_event(key_event)
+                }
+                Event::Mouse(mouse_event) => self.handle_mouse_event(mouse_event),
+                _ => {}
+            },
+            AppEvent::Tick => self.ticks = self.ticks.wrapping_add(1),
+            AppEvent::Task(output) => self.finish_task(output),
+        }
+        Ok(())
+    }
+
+    fn handle_mouse_event(&mut self, mouse_event: MouseEvent) {
+        let (column, row) = (mouse_event.column, mouse_event.row);
+        match mouse_event.kind {
+            // The wheel behaves like the arrow keys of the active tab, so it
+            // respects the same bounds.
+            MouseEventKind::ScrollUp => self.handle_action(Action::Up),
+            MouseEventKind::ScrollDown => self.handle_action(Action::Down),
+            MouseEventKind::ScrollLeft => self.handle_action(Action::Left),
+            MouseEventKind::ScrollRight => self.handle_action(Action::Right),
+            // The help overlay covers the tabs, so clicks don't reach them.
+            MouseEventKind::Down(MouseButton::Left) if self.state.show_help() => {}
+            MouseEventKind::Down(MouseButton::Left) => self.handle_click(column, row),
+            MouseEventKind::Drag(MouseButton::Left) => match self.drag {
+                Some(DragTarget::Scrollbar(scrollbar)) => self.drag_scrollbar(scrollbar, row),
+                Some(DragTarget::PaneDivider(divider)) => {
+                    self.state.set_tree_percent(divider.percent_at(column))
+                }
+                None => {}
+            },
+            MouseEventKind::Up(MouseButton::Left) => self.drag = None,
+            _ => {}
+        }
+    }
+
+    fn handle_click(&mut self, column: u16, row: u16) {
+        let regions = self.mouse_regions.borrow().clone();
+
+        if let Some(tab) = regions.tab_at(column, row) {
+            self.switch_tab(|tabs| tabs.select(tab));
+        } else if let Some(scrollbar) = regions.scrollbar_at(column, row) {
+            self.drag = Some(DragTarget::Scrollbar(scrollbar));
+            self.drag_scrollbar(scrollbar, row);
+        } else if let Some(divider) = regions.pane_divider_at(column, row) {
+            self.drag = Some(DragTarget::PaneDivider(divider));
+        } else if let Some(tree_row) = regions.schema_index_at(column, row) {
+            let outline = self.parquet_ctx.schema.outline();
+            let visible = self.state.visible_schema_nodes(&outline);
+            if let Some(&node) = visible.get(tree_row).filter(|&&node| node > 0) {
+                // Clicking a selected group folds / unfolds it.
+                let reselect = self.state.selected_group() == Some(node);
+                self.jump(|app| app.state.set_schema_cursor(&outline, Some(node)));
+                if reselect {
+                    self.state.toggle_group(&outline);
+                }
+            }
+        } else if let Some(data_row) = regions.data_row_at(column, row) {
+            let total_rows = self.sample_data().total_rows;
+            if data_row < total_rows {
+                self.state.set_vertical_offset(data_row);
+                let visible_rows = self.state.visible_data_rows();
+                self.state
+                    .adjust_scroll_to_selection(visible_rows, total_rows);
+            }
+        }
+    }
+
+    fn drag_scrollbar(&mut self, scrollbar: ScrollbarRegion, row: u16) {
+        let offset = scrollbar.offset_at(row);
+        match scrollbar.target {
+            ScrollTarget::SchemaTree => {
+                self.state.set_tree_scroll_offset(offset);
+                // Keep the cursor inside the new viewport, otherwise the
+                // renderer scrolls straight back to it.
+                let outline = self.parquet_ctx.schema.outline();
+                let visible = self.state.visible_schema_nodes(&outline);
+                let selected = self
+                    .state
+                    .schema_cursor(&outline)
+                    .and_then(|node| visible.iter().position(|&n| n == node));
+                let in_view = selected
+                    .is_some_and(|pos| pos >= offset && pos < offset + scrollbar.visible_items);
+                if !in_view && let Some(&node) = visible.get(offset.max(1)) {
+                    self.state.set_schema_cursor(&outline, Some(node));
+                }
+            }
+        }
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        if let Some((_, keys)) = &mut self.recording
+            && !self.replaying
+        {
+            keys.push(key_event.into());
+        }
+        if let Some(action) = self.pending_register.take() {
+            self.use_register(action, key_event);
+            return;
+        }
+        if self.state.command_line().is_some() {
+            self.handle_command_line_key(key_event.code);
+            return;
+        }
+        let keymap = self.keymap.in_tab(self.tabs.active_tab);
+        if let Some(action) = keymap.action_for(key_event) {
+            self.handle_action(action);
+        }
+    }
+
+    fn handle_action(&mut self, action: Action) {
+        if self.state.show_help() {
+            self.handle_help_action(action);
+            return;
+        }
+        if self.state.inspecting() {
+            self.handle_inspector_action(action);
+            return;
+        }
+        if self.state.correlation().is_some() {
+            match action {
+                Action::Quit => self.exit(),
+                Action::Reset => self.state.set_correlation(None),
+                _ => {}
+            }
+            return;
+        }
+        match action {
+            Action::Quit => self.exit(),
+            Action::Reset if self.tasks.running.is_some() => self.tasks.cancel(),
+            Action::Reset => {
+                self.state.reset();
+                self.state.clear_status();
+            }
+            Action::Help => self.state.toggle_help(),
+            Action::Goto => self.state.open_command_line("goto "),
+            Action::Command => self.state.open_command_line(""),
+            Action::Copy => {
+                if let Err(e) = self.run_command(Command::Copy(SnippetFormat::Summary)) {
+                    self.state.set_status(StatusMessage::error(e));
+                }
+            }
+            Action::OpenExternal => self.queue_external(),
+            Action::Sample if self.tabs.active_tab().to_string() == "Visualize" => {
+                let next = self.state.sampling().next();
+                if let Err(e) = self.run_command(Command::Sample(next)) {
+                    self.state.set_status(StatusMessage::error(e));
+                }
+            }
+            Action::Bookmark => {
+                if let Err(e) = self.toggle_bookmark() {
+                    self.state.set_status(StatusMessage::error(e));
+                }
+            }
+            Action::ToggleBookmarked => {
+                if self.state.bookmarks().is_empty() {
+                    self.state.set_status(StatusMessage::error(
+                        "no bookmarks; press b on a column to bookmark it",
+                    ));
+                } else {
+                    self.state
+                        .set_bookmarked_only(!self.state.bookmarked_only());
+                    self.keep_schema_cursor_listed();
+                }
+            }
+            Action::Search if self.tabs.active_tab().to_string() == "Visualize" => {
+                self.state.open_command_line("search ")
+            }
+            Action::WrapRow if self.tabs.active_tab().to_string() == "Visualize" => {
+                let row = self.state.vertical_offset();
+                if row < self.sample_data().rows.len() {
+                    let position = self.sample_data().position(row);
+                    self.state.toggle_wrapped_row(position);
+                }
+            }
+            Action::NextMatch | Action::PrevMatch
+                if self.tabs.active_tab().to_string() == "Visualize" =>
+            {
+                if let Err(e) = self.jump(|app| app.next_match(action == Action::NextMatch)) {
+                    self.state.set_status(StatusMessage::error(e));
+                }
+            }
+            Action::LastRows if self.tabs.active_tab().to_string() == "Visualize" => {
+                let num_rows = self.parquet_ctx.metadata.num_rows;
+                if num_rows > 0
+                    && let Err(e) = self.jump(|app| app.goto_row(num_rows - 1))
+                {
+                    self.state.set_status(StatusMessage::error(e));
+                }
+            }
+            Action::RecordMacro => match self.recording.take() {
+                Some((register, mut keys)) => {
+                    // The key that stops the recording.
+                    keys.pop();
+                    self.state.set_recording_macro(None);
+                    self.macros.set(register, &keys);
+                    self.state.set_status(StatusMessage::info(match keys.len() {
+                        0 => format!("cleared @{register}"),
+                        n => format!("recorded {n} keys into @{register}"),
+                    }));
+                }
+                None => {
+                    self.pending_register = Some(action);
+                    self.state
+                        .set_status(StatusMessage::progress("record into register: a-z, 0-9"));
+                }
+            },
+            Action::PlayMacro => {
+                self.pending_register = Some(action);
+                self.state
+                    .set_status(StatusMessage::progress("play register: a-z, 0-9 or @"));
+            }
+            Action::JumpBack => self.travel(false),
+            Action::JumpForward => self.travel(true),
+            Action::NextTab => self.switch_tab(TabManager::next),
+            Action::PrevTab => self.switch_tab(TabManager::prev),
+            _ => {
+                if let Err(e) = self.tabs.active_tab().on_action(action, &mut self.state) {
+                    self.state.set_status(StatusMessage::error(e.to_string()));
+                }
+            }
+        }
+    }
+
+    fn queue_external(&mut self) {
+        match self.external_text() {
+            Ok(external) => self.pending_external = Some(external),
+            Err(e) => self.state.set_status(StatusMessage::error(e)),
+        }
+    }
+
+    /// While the help overlay is open it takes all input: the movement keys
+    /// scroll it, and Esc or the help key close it.
+    fn handle_help_action(&mut self, action: Action) {
+        let scroll = self.state.help_scroll();
+        let max_scroll = crate::components::HelpOverlay::new(&self.keymap, &self.tabs)
+            .line_count()
+            .saturating_sub(1);
+        match action {
+            Action::Quit => self.exit(),
+            Action::Help | Action::Reset => self.state.toggle_help(),
+            Action::Up => self.state.set_help_scroll(scroll.saturating_sub(1)),
+            Action::Down => self.state.set_help_scroll((scroll + 1).min(max_scroll)),
+            Action::PageUp => self.state.set_help_scroll(scroll.saturating_sub(10)),
+            Action::PageDown => self.state.set_help_scroll((scroll + 10).min(max_scroll)),
+            _ => {}
+        }
+    }
+
+    /// Like the help overlay, the row inspector takes all input while open.
+    /// The movement keys move its cursor, `←` / `→` fold and unfold the
+    /// object or array under it, and Enter does either.
+    fn handle_inspector_action(&mut self, action: Action) {
+        let cursor = self.state.inspector_cursor();
+        let inspector = RowInspector::new(self.sample_data(), self.state.vertical_offset())
+            .with_folded(self.state.inspector_folded());
+        let last = inspector.line_count().saturating_sub(1);
+        let fold_path = inspector.fold_path(cursor);
+        let folded = fold_path
+            .as_ref()
+            .is_some_and(|path| self.state.inspector_folded().contains(path));
+        let parent = inspector.parent(cursor);
+        match action {
+            Action::Quit => self.exit(),
+            Action::Inspect | Action::Reset => self.state.toggle_inspector(),
+            Action::OpenExternal => self.queue_external(),
+            Action::Up => self.state.set_inspector_cursor(cursor.saturating_sub(1)),
+            Action::Down => self.state.set_inspector_cursor((cursor + 1).min(last)),
+            Action::PageUp => self.state.set_inspector_cursor(cursor.saturating_sub(10)),
+            Action::PageDown => self.state.set_inspector_cursor((cursor + 10).min(last)),
+            Action::ToggleGroup => {
+                if let Some(path) = fold_path {
+                    self.state.set_inspector_folded(path, !folded);
+                }
+            }
+            Action::Right => {
+                if let Some(path) = fold_path {
+                    self.state.set_inspector_folded(path, false);
+                }
+            }
+            Action::Left => match fold_path {
+                Some(path) if !folded => self.state.set_inspector_folded(path, true),
+                _ => {
+                    if let Some(parent) = parent {
+                        self.state.set_inspector_cursor(parent);
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    /// Keys typed while the command line is open edit it rather than going
+    /// through the keymap.
+    fn handle_command_line_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char(c) => self.state.push_command_char(c),
+            KeyCode::Backspace => self.state.pop_command_char(),
+            KeyCode::Esc => {
+                self.state.close_command_line();
+            }
+            KeyCode::Enter => {
+                let input = self.state.close_command_line().unwrap_or_default();
+                if input.trim().is_empty() {
+                    return;
+                }
+                let result = Command::parse(&input).and_then(|command| self.run_command(command));
+                if let Err(e) = result {
+                    self.state.set_status(StatusMessage::error(e));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Schema node of the column under the cursor, for `command`: the
+    /// column of the preview in the Visualize tab, the tree's selection in
+    /// the Schema and Row Groups tabs.
+    fn selected_column(&self, command: &str) -> Result<usize, String> {
+        let schema = &self.parquet_ctx.schema;
+        match self.tabs.active_tab().to_string().as_str() {
+            "Visualize" => self
+                .sample_data()
+                .flattened_columns
+                .get(self.state.horizontal_offset())
+                .and_then(|name| {
+                    (1..schema.columns.len()).find(|&node| schema.column_path(node) == *name)
+                }),
+            "Schema" | "Row Groups" => self.state.schema_cursor(&schema.outline()),
+            tab => return Err(format!("{command} is not available in the {tab} tab")),
+        }
+        .ok_or_else(|| "no column selected".to_string())
+    }
+
+    /// Make tab `index` the active one, where it was left.
+    fn select_tab(&mut self, index: usize) {
+        self.tabs.select(index);
+        self.state.set_active_tab(self.tabs.active_tab);
+    }
+
+    /// Switch tabs with `switch`, keeping the selected column: the tree cursor
+    /// in the Schema and Row Groups tabs, the first column shown in the
+    /// Visualize tab (the top-level column of a nested one). Tabs without
+    /// columns leave the last selection for the next tab.
+    fn switch_tab(&mut self, switch: impl FnOnce(&mut TabManager)) {
+        self.jump(|app| app.change_tab(switch))
+    }
+
+    fn change_tab(&mut self, switch: impl FnOnce(&mut TabManager)) {
+        let schema = &self.parquet_ctx.schema;
+        if let Ok(node) = self.selected_column("") {
+            self.state.set_selected_column(Some(SelectedColumn {
+                path: schema.column_path(node),
+                leaf: schema.leaf_position(node),
+            }));
+        }
+        switch(&mut self.tabs);
+        self.state.set_active_tab(self.tabs.active_tab);
+
+        let Some(column) = self.state.selected_column().cloned() else {
+            return;
+        };
+        let outline = schema.outline();
+        match self.tabs.active_tab().to_string().as_str() {
+            "Visualize" => {
+                let top = column.path.split('.').next().unwrap_or(&column.path);
+                if let Some(offset) = self
+                    .sample_data()
+                    .flattened_columns
+                    .iter()
+                    .position(|name| name == top)
+                {
+                    self.state.set_horizontal_offset(offset);
+                }
+            }
+            "Schema" | "Row Groups" => {
+                let node = match column.leaf {
+                    Some(leaf) => outline.leaf_node(leaf + 1),
+                    None => schema.find_path(&column.path),
+                };
+                self.state.set_schema_cursor(&outline, node);
+                self.keep_schema_cursor_listed();
+            }
+            _ => {}
+        }
+    }
+
+    /// Where the app is, to come back to after a jump.
+    fn place(&self) -> Place {
+        let data = self.sample_data();
+        Place {
+            tab: self.tabs.active_tab,
+            positions: self.state.tab().clone(),
+            row_group: self.state.row_group(),
+            row: (self.tabs.active_tab == TabKind::Visualize.index() && data.total_rows > 0)
+                .then(|| data.position(self.state.vertical_offset().min(data.total_rows - 1))),
+        }
+    }
+
+    /// Run `jump`, remembering where it left from in the jump list if it
+    /// went anywhere.
+    fn jump<T>(&mut self, jump: impl FnOnce(&mut Self) -> T) -> T {
+        let from = self.place();
+        let result = jump(self);
+        if self.place() != from {
+            self.jumps.record(from);
+        }
+        result
+    }
+
+    /// Go back to `place`, reading its row again if other rows were loaded
+    /// since.
+    fn go_to_place(&mut self, place: Place) -> Result<(), String> {
+        self.select_tab(place.tab);
+        self.state.set_row_group(place.row_group);
+        *self.state.tab_mut() = place.positions;
+        if let Some(row) = place.row
+            && self.place().row != Some(row)
+        {
+            self.goto_row(row)?;
+        }
+        Ok(())
+    }
+
+    /// Start recording into, or play, the register named by `key`.
+    fn use_register(&mut self, action: Action, key: KeyEvent) {
+        self.state.clear_status();
+        let register = match Key::from(key) {
+            Key {
+                code: KeyCode::Char('@'),
+                modifiers: KeyModifiers::NONE,
+            } if action == Action::PlayMacro => match self.last_macro {
+                Some(register) => register,
+                None => {
+                    self.state
+                        .set_status(StatusMessage::error("no macro played yet"));
+                    return;
+                }
+            },
+            Key {
+                code: KeyCode::Char(c),
+                modifiers: KeyModifiers::NONE,
+            } if is_register(c) => c,
+            // Esc and the like cancel.
+            _ => return,
+        };
+        if action == Action::RecordMacro {
+            self.recording = Some((register, Vec::new()));
+            self.state.set_recording_macro(Some(register));
+            let stop = self.keymap.describe(Action::RecordMacro);
+            self.state.set_status(StatusMessage::info(format!(
+                "recording @{register}, {stop} to stop"
+            )));
+        } else if let Err(e) = self.play_macro(register) {
+            self.state.set_status(StatusMessage::error(e));
+        }
+    }
+
+    /// Press the keys recorded into `register` again.
+    fn play_macro(&mut self, register: char) -> Result<(), String> {
+        if self.replaying {
+            return Err("a macro can't play another macro".to_string());
+        }
+        let keys = self
+            .macros
+            .get(register)
+            .ok_or_else(|| format!("nothing recorded into @{register}"))?;
+        self.last_macro = Some(register);
+        self.replaying = true;
+        for key in keys {
+            if self.exit {
+                break;
+            }
+            self.handle_key_event(KeyEvent::new(key.code, key.modifiers));
+        }
+        self.replaying = false;
+        Ok(())
+    }
+
+    /// Step back (or `forward`) through the jump list.
+    fn travel(&mut self, forward: bool) {
+        let place = if forward {
+            self.jumps.forward()
+        } else {
+            let current = self.place();
+            self.jumps.back(current)
+        };
+        let result = match place {
+            Some(place) => self.go_to_place(place),
+            None if forward => Err("no later place in the jump list".to_string()),
+            None => Err("no earlier place in the jump list".to_string()),
+        };
+        if let Err(e) = result {
+            self.state.set_status(StatusMessage::error(e));
+        }
+    }
+
+    /// In the tabs with the schema tree, move a cursor on a column that
+    /// isn't listed onto the first column that is.
+    fn keep_schema_cursor_listed(&mut self) {
+        if !matches!(
+            self.tabs.active_tab().to_string().as_str(),
+            "Schema" | "Row Groups"
+        ) {
+            return;
+        }
+        let outline = self.parquet_ctx.schema.outline();
+        if let Some(cursor) = self.state.schema_cursor(&outline) {
+            let visible = self.state.visible_schema_nodes(&outline);
+            if !visible.contains(&cursor) {
+                self.state
+                    .set_schema_cursor(&outline, visible.get(1).copied());
+            }
+        }
+    }
+
+    /// Bookmark the selected column, or every column of the selected group;
+    /// or remove the bookmarks if they are all set.
+    fn toggle_bookmark(&mut self) -> Result<(), String> {
+        let schema = &self.parquet_ctx.schema;
+        let node = self.selected_column("bookmark")?;
+        let outline = schema.outline();
+        let leaves: Vec<usize> = (node..outline.subtree_end(node))
+            .filter(|&n| outline.leaf_position(n).is_some())
+            .collect();
+        let path = schema.column_path(node);
+        let message = if self.state.toggle_bookmarks(&leaves) {
+            format!("bookmarked {path}")
+        } else {
+            format!("removed the bookmark on {path}")
+        };
+        self.state.set_status(StatusMessage::info(message));
+        self.keep_schema_cursor_listed();
+        Ok(())
+    }
+
+    /// The selected column as `format`: the column under the cursor in the
+    /// Visualize tab, or the schema node under the tree cursor.
+    fn column_snippet(&self, format: SnippetFormat) -> Result<String, String> {
+        let schema = &self.parquet_ctx.schema;
+        let node = self.selected_column("copy")?;
+        let path = schema.column_path(node);
+        match (&schema.columns[node], schema.leaf_position(node)) {
+            (SchemaInfo::Primitive { info, .. }, Some(index)) => {
+                Ok(column_snippet(info, &path, index, format))
+            }
+            _ if format == SnippetFormat::Path => Ok(path),
+            _ => Err(format!(
+                "{path} is a group; only its path can be copied (:copy path)"
+            )),
+        }
+    }
+
+    fn run_command(&mut self, command: Command) -> Result<(), String> {
+        match command {
+            Command::Goto(target) => {
+                self.jump(|app| match app.tabs.active_tab().to_string().as_str() {
+                    "Visualize" => app.goto_row(target - 1),
+                    "Row Groups" => app.goto_row_group(target - 1),
+                    other => Err(format!("goto is not available in the {other} tab")),
+                })
+            }
+            Command::Filter(filter) => {
+                let tab = self.tabs.active_tab().to_string();
+                if tab != "Row Groups" {
+                    return Err(format!("filter is not available in the {tab} tab"));
+                }
+                match filter {
+                    Some(filter) => {
+                        let matches = filter.matching(&self.parquet_ctx.row_groups.row_groups);
+                        if matches.is_empty() {
+                            return Err(format!("no row groups match '{filter}'"));
+                        }
+                        self.state.set_status(StatusMessage::info(format!(
+                            "{} of {} row groups match",
+                            matches.len(),
+                            self.parquet_ctx.row_groups.num_row_groups()
+                        )));
+                        self.state.set_row_group_filter(filter, matches);
+                    }
+                    None => {
+                        self.state.clear_row_group_filter();
+                        self.state
+                            .set_status(StatusMessage::info("row group filter cleared"));
+                    }
+                }
+                Ok(())
+            }
+            Command::Prune(predicate) => {
+                let tab = self.tabs.active_tab().to_string();
+                if tab != "Row Groups" {
+                    return Err(format!("prune is not available in the {tab} tab"));
+                }
+                let Some(predicate) = predicate else {
+                    self.state.set_pruning(None);
+                    self.state
+                        .set_status(StatusMessage::info("pruning simulation cleared"));
+                    return Ok(());
+                };
+                let reader = self
+                    .parquet_ctx
+                    .reader
+                    .with_page_index()
+                    .map_err(|e| e.to_string().replace('\n', " "))?;
+                let report = PruningReport::from_metadata(reader.metadata(), &predicate)?;
+                self.state.set_status(StatusMessage::info(report.summary()));
+                self.state.set_bloom(None);
+                self.state.set_pruning(Some(report));
+                Ok(())
+            }
+            Command::Profile(options) => {
+                let tab = self.tabs.active_tab().to_string();
+                if tab != "Schema" {
+                    return Err(format!("profile is not available in the {tab} tab"));
+                }
+                let Some(options) = options else {
+                    self.state.set_profile(None);
+                    return Ok(());
+                };
+                let total = self
+                    .parquet_ctx
+                    .row_groups
+                    .row_groups
+                    .iter()
+                    .map(|rg| rg.compressed_size.max(0) as u64)
+                    .sum();
+                let (path, reader) = (self.file_name.clone(), self.parquet_ctx.reader.clone());
+                self.tasks.spawn("profiling", total, move |progress| {
+                    TaskOutput::Profile(FileProfile::from_reader_with_progress(
+                        &path, &reader, options, progress,
+                    ))
+                })
+            }
+            Command::Json(column) => {
+                let tab = self.tabs.active_tab().to_string();
+                if tab != "Visualize" {
+                    return Err(format!("json is not available in the {tab} tab"));
+                }
+                let mut window = self.sample_data().clone();
+                match column {
+                    Some(column) => {
+                        let found = window.add_json_column(&column)?;
+                        self.state.set_status(StatusMessage::info(format!(
+                            "{}: {found} of {} rows have a value",
+                            column.name(),
+                            window.total_rows
+                        )));
+                        self.state.add_json_column(column);
+                    }
+                    None => {
+                        let extracted = self.state.clear_json_columns();
+                        window.truncate_columns(window.flattened_columns.len() - extracted);
+                        self.state
+                            .set_status(StatusMessage::info("extracted json columns dropped"));
+                    }
+                }
+                self.data_window = Some(window);
+                Ok(())
+            }
+            Command::Corr { x, y } => {
+                let x = match x {
+                    Some(x) => x,
+                    None => self
+                        .parquet_ctx
+                        .schema
+                        .column_path(self.selected_column("corr")?),
+                };
+                let total = self
+                    .parquet_ctx
+                    .row_groups
+                    .row_groups
+                    .iter()
+                    .map(|rg| rg.compressed_size.max(0) as u64)
+                    .sum();
+                let path = self.file_name.clone();
+                self.tasks.spawn("correlation", total, move |progress| {
+                    TaskOutput::Correlation(Correlation::from_file_with_progress(
+                        &path,
+                        &x,
+                        &y,
+                        CORRELATION_SAMPLE_ROWS,
+                        progress,
+                    ))
+                })
+            }
+            Command::Dups(options) => {
+                let total = self
+                    .parquet_ctx
+                    .row_groups
+                    .row_groups
+                    .iter()
+                    .map(|rg| rg.compressed_size.max(0) as u64)
+                    .sum();
+                let path = self.file_name.clone();
+                self.tasks.spawn("duplicate check", total, move |progress| {
+                    TaskOutput::Duplicates(DuplicateReport::from_file_with_progress(
+                        &path, &options, progress,
+                    ))
+                })
+            }
+            Command::Utf8 => {
+                let total = self
+                    .parquet_ctx
+                    .row_groups
+                    .row_groups
+                    .iter()
+                    .map(|rg| rg.compressed_size.max(0) as u64)
+                    .sum();
+                let path = self.file_name.clone();
+                self.tasks.spawn("UTF-8 check", total, move |progress| {
+                    TaskOutput::Utf8(Utf8Report::from_file_with_progress(&path, progress))
+                })
+            }
+            Command::Bench => {
+                let total = self
+                    .parquet_ctx
+                    .row_groups
+                    .row_groups
+                    .iter()
+                    .map(|rg| rg.compressed_size.max(0) as u64)
+                    .sum();
+                let path = self.file_name.clone();
+                self.tasks
+                    .spawn("Decode benchmark", total, move |progress| {
+                        TaskOutput::Bench(BenchReport::from_file_with_progress(&path, progress))
+                    })
+            }
+            Command::Codecs { level } => {
+                // Only the first row group with rows is sampled.
+                let total = self
+                    .parquet_ctx
+                    .row_groups
+                    .row_groups
+                    .iter()
+                    .find(|rg| rg.rows > 0)
+                    .map_or(0, |rg| rg.compressed_size.max(0) as u64);
+                let path = self.file_name.clone();
+                self.tasks.spawn("Codec levels", total, move |progress| {
+                    TaskOutput::Codecs(CodecReport::from_file_with_progress(&path, level, progress))
+                })
+            }
+            Command::Tab(tab) => {
+                self.switch_tab(|tabs| tabs.select(tab.index()));
+                Ok(())
+            }
+            Command::Sample(sampling) => {
+                let tab = self.tabs.active_tab().to_string();
+                if tab != "Visualize" {
+                    return Err(format!("sample is not available in the {tab} tab"));
+                }
+                let preview = PreviewOptions {
+                    sampling,
+                    ..self.config.display.preview()
+                };
+                let mut window = ParquetSampleData::read_sample(&self.file_name, &preview)
+                    .map_err(|e| format!("failed to read rows: {e}"))?;
+                for column in self.state.json_columns() {
+                    window.add_json_column(column)?;
+                }
+                self.state.set_status(StatusMessage::info(format!(
+                    "sample {sampling}: {} rows",
+                    window.total_rows
+                )));
+                self.data_window = Some(window);
+                self.state.set_sampling(sampling);
+                self.state.set_vertical_offset(0);
+                self.state.set_data_vertical_scroll(0);
+                Ok(())
+            }
+            Command::Copy(format) => {
+                let snippet = self.column_snippet(format)?;
+                copy_to_clipboard(&snippet).map_err(|e| format!("copy failed: {e}"))?;
+                self.state
+                    .set_status(StatusMessage::info(format!("copied {snippet}")));
+                Ok(())
+            }
+            Command::Sort(by_size) => {
+                let schema = &self.parquet_ctx.schema;
+                let leaves = by_size.then(|| schema.leaves_by_size());
+                self.state.set_sorted_leaves(&schema.outline(), leaves);
+                self.state.set_status(StatusMessage::info(if by_size {
+                    "columns sorted by size"
+                } else {
+                    "columns in schema order"
+                }));
+                Ok(())
+            }
+            Command::Columns(filter) => {
+                let Some(filter) = filter else {
+                    self.state.clear_column_filter();
+                    self.state
+                        .set_status(StatusMessage::info("column filter cleared"));
+                    return Ok(());
+                };
+                let schema = &self.parquet_ctx.schema;
+                let listed = filter.matching(schema);
+                let outline = schema.outline();
+                let leaves = listed
+                    .iter()
+                    .filter(|&&node| outline.leaf_position(node).is_some())
+                    .count();
+                if leaves == 0 {
+                    return Err(format!("no columns match '{filter}'"));
+                }
+                self.state.set_status(StatusMessage::info(format!(
+                    "{leaves} of {} columns match",
+                    outline.leaf_count()
+                )));
+                self.state.set_column_filter(filter, listed);
+                self.keep_schema_cursor_listed();
+                Ok(())
+            }
+            Command::Search(pattern) => {
+                let tab = self.tabs.active_tab().to_string();
+                if tab != "Visualize" {
+                    return Err(format!("search is not available in the {tab} tab"));
+                }
+                let searching = pattern.is_some();
+                self.state.set_search(pattern);
+                if searching {
+                    self.jump(|app| app.next_match(true))
+                } else {
+                    self.state.set_status(StatusMessage::info("search cleared"));
+                    Ok(())
+                }
+            }
+            Command::SearchFile(pattern) => {
+                let tab = self.tabs.active_tab().to_string();
+                if tab != "Visualize" {
+                    return Err(format!("search is not available in the {tab} tab"));
+                }
+                let from = match self.sample_data().rows.len() {
+                    0 => 0,
+                    _ => self.sample_data().position(self.state.vertical_offset()) + 1,
+                };
+                let total = self
+                    .parquet_ctx
+                    .row_groups
+                    .row_groups
+                    .iter()
+                    .map(|rg| rg.compressed_size.max(0) as u64)
+                    .sum();
+                let path = self.file_name.clone();
+                self.state.set_search(Some(pattern.clone()));
+                self.tasks.spawn("search", total, move |progress| {
+                    TaskOutput::Search(pattern.find_in_file(&path, from, progress))
+                })
+            }
+            Command::Page(page) => {
+                let tab = self.tabs.active_tab().to_string();
+                if tab != "Row Groups" {
+                    return Err(format!("page is not available in the {tab} tab"));
+                }
+                let Some(page) = page else {
+                    self.state.set_decoded_page(None);
+                    self.state.set_status(StatusMessage::info("page closed"));
+                    return Ok(());
+                };
+                let column = self
+                    .state
+                    .vertical_offset()
+                    .checked_sub(1)
+                    .ok_or("select a column to decode one of its pages")?;
+                let (reader, row_group) = (self.parquet_ctx.reader.clone(), self.state.row_group());
+                self.tasks.spawn("page decode", 0, move |progress| {
+                    TaskOutput::Page(DecodedPage::read_with_progress(
+                        &reader,
+                        row_group,
+                        column,
+                        page - 1,
+                        progress,
+                    ))
+                })
+            }
+            Command::Bloom(query) => {
+                let tab = self.tabs.active_tab().to_string();
+                if tab != "Row Groups" {
+                    return Err(format!("bloom is not available in the {tab} tab"));
+                }
+                let Some(query) = query else {
+                    self.state.set_bloom(None);
+                    self.state
+                        .set_status(StatusMessage::info("bloom filter simulation cleared"));
+                    return Ok(());
+                };
+                let values = query.values()?;
+                // Report a bad column or value now, not once the filters are read.
+                BloomReport::check(self.parquet_ctx.reader.metadata(), &query.column, &values)
+                    .map_err(|e| e.replace('\n', " "))?;
+                let reader = self.parquet_ctx.reader.with_bloom_filters();
+                self.tasks.spawn("bloom check", 0, move |_| {
+                    TaskOutput::Bloom(BloomReport::from_reader(&reader, &query.column, &values))
+                })
+            }
+        }
+    }
+
+    /// Select row `row` of the file, loading the rows around it if they are
+    /// outside the rows currently loaded.
+    fn goto_row(&mut self, row: usize) -> Result<(), String> {
+        let num_rows = self.parquet_ctx.metadata.num_rows;
+        if row >= num_rows {
+            return Err(format!("row {} is past the end ({num_rows} rows)", row + 1));
+        }
+
+        if self.sample_data().row_at(row).is_none() {
+            // Keep the window full-size so the tab's row bound stays valid.
+            let preview = self.config.display.preview();
+            let first_row = row.min(num_rows.saturating_sub(preview.max_rows));
+            let mut window =
+                ParquetSampleData::read_window_with(&self.file_name, first_row, &preview)
+                    .map_err(|e| format!("failed to read rows: {e}"))?;
+            for column in self.state.json_columns() {
+                window.add_json_column(column)?;
+            }
+            self.state.set_status(StatusMessage::info(format!(
+                "loaded rows {}-{}",
+                window.first_row + 1,
+                window.first_row + window.total_rows
+            )));
+            self.data_window = Some(window);
+            // The rows around `row` replace any sample.
+            self.state.set_sampling(Sampling::Head);
+        }
+
+        let data = self.sample_data();
+        let selected = data.row_at(row).unwrap_or_default();
+        let total_rows = data.total_rows;
+        self.state.set_vertical_offset(selected);
+        let visible_rows = self.state.visible_data_rows();
+        self.state
+            .adjust_scroll_to_selection(visible_rows, total_rows);
+        Ok(())
+    }
+
+    /// Select the next loaded row (the previous one if not `forward`) with
+    /// a value matching the search, wrapping around the rows loaded.
+    fn next_match(&mut self, forward: bool) -> Result<(), String> {
+        let Some(pattern) = self.state.search().cloned() else {
+            return Err("no search; press / to search the preview".to_string());
+        };
+        let data = self.sample_data();
+        let from = self.state.vertical_offset();
+        let Some(row) = pattern.find_row(&data.rows, from, forward) else {
+            return Err(format!(
+                "no rows loaded match '{pattern}'; :search! {pattern} scans the file"
+            ));
+        };
+        let (position, total_rows) = (data.position(row), data.total_rows);
+        let wrapped = if forward { row <= from } else { row >= from };
+        self.state.set_status(StatusMessage::info(format!(
+            "row {} matches '{pattern}'{}",
+            position + 1,
+            if wrapped { " (wrapped)" } else { "" }
+        )));
+        self.state.set_vertical_offset(row);
+        let visible_rows = self.state.visible_data_rows();
+        self.state
+            .adjust_scroll_to_selection(visible_rows, total_rows);
+        Ok(())
+    }
+
+    fn goto_row_group(&mut self, row_group: usize) -> Result<(), String> {
+        let num_row_groups = self.parquet_ctx.row_groups.num_row_groups();
+        if row_group >= num_row_groups {
+            return Err(format!(
+                "row group {} is past the end ({num_row_groups} row groups)",
+                row_group + 1
+            ));
+        }
+        self.state.set_row_group(row_group);
+        Ok(())
+    }
+
+    /// Show what a background task produced, unless it was cancelled.
+    fn finish_task(&mut self, output: TaskOutput) {
+        let Some(task) = self.tasks.running.take() else {
+            return;
+        };
+        let cancelled = format!("{} cancelled", task.label);
+        match output {
+            _ if task.progress.is_cancelled() => {
+                self.state.set_status(StatusMessage::info(cancelled))
+            }
+            TaskOutput::Profile(Ok(profile)) => {
+                self.state.set_status(StatusMessage::info(format!(
+                    "profiled {} columns from {} rows",
+                    profile.columns.len(),
+                    profile.sampled_rows
+                )));
+                self.state.set_profile(Some(profile));
+            }
+            TaskOutput::Bloom(Ok(report)) => {
+                self.state.set_status(StatusMessage::info(report.summary()));
+                self.state.set_pruning(None);
+                self.state.set_bloom(Some(report));
+            }
+            TaskOutput::Page(Ok(decoded)) => {
+                self.state.set_status(StatusMessage::info(format!(
+                    "page {}: {} values from {} dictionary entries",
+                    decoded.page + 1,
+                    decoded.values.len(),
+                    decoded.dictionary_size
+                )));
+                self.state.set_decoded_page(Some(decoded));
+            }
+            TaskOutput::Duplicates(Ok(report)) => {
+                self.state.set_status(StatusMessage::info(report.summary()));
+            }
+            TaskOutput::Correlation(Ok(correlation)) => {
+                self.state.set_status(StatusMessage::info(format!(
+                    "{} vs {}: r = {} over {} rows",
+                    correlation.x,
+                    correlation.y,
+                    correlation
+                        .pearson
+                        .map_or("-".to_string(), |r| format!("{r:.3}")),
+                    commas(correlation.points.len() as u64)
+                )));
+                self.state.set_correlation(Some(correlation));
+            }
+            TaskOutput::Utf8(Ok(report)) if report.invalid_values() > 0 => {
+                self.state
+                    .set_status(StatusMessage::error(report.summary()));
+            }
+            TaskOutput::Utf8(Ok(report)) => {
+                self.state.set_status(StatusMessage::info(report.summary()));
+            }
+            TaskOutput::Bench(Ok(report)) => {
+                self.state.set_status(StatusMessage::info(report.summary()));
+            }
+            TaskOutput::Codecs(Ok(report)) => {
+                self.state.set_status(StatusMessage::info(report.summary()));
+            }
+            TaskOutput::Search(Ok(Some(row))) => {
+                let pattern = self
+                    .state
+                    .search()
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+                // The row is shown in the Visualize tab, even if another tab
+                // was opened while the file was searched.
+                if self.tabs.active_tab != TabKind::Visualize.index() {
+                    self.switch_tab(|tabs| tabs.select(TabKind::Visualize.index()));
+                }
+                match self.jump(|app| app.goto_row(row)) {
+                    Ok(()) => self.state.set_status(StatusMessage::info(format!(
+                        "row {} matches '{pattern}'",
+                        row + 1
+                    ))),
+                    Err(e) => self.state.set_status(StatusMessage::error(e)),
+                }
+            }
+            TaskOutput::Search(Ok(None)) => {
+                let pattern = self
+                    .state
+                    .search()
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+                self.state.set_status(StatusMessage::error(format!(
+                    "no more rows match '{pattern}'"
+                )));
+            }
+            TaskOutput::Profile(Err(e))
+            | TaskOutput::Bloom(Err(e))
+            | TaskOutput::Page(Err(e))
+            | TaskOutput::Duplicates(Err(e))
+            | TaskOutput::Correlation(Err(e))
+            | TaskOutput::Utf8(Err(e))
+            | TaskOutput::Bench(Err(e))
+            | TaskOutput::Codecs(Err(e))
+            | TaskOutput::Search(Err(e))
+                if e == CANCELLED =>
+            {
+                self.state.set_status(StatusMessage::info(cancelled))
+            }
+            TaskOutput::Profile(Err(e))
+            | TaskOutput::Bloom(Err(e))
+            | TaskOutput::Page(Err(e))
+            | TaskOutput::Duplicates(Err(e))
+            | TaskOutput::Correlation(Err(e))
+            | TaskOutput::Utf8(Err(e))
+            | TaskOutput::Bench(Err(e))
+            | TaskOutput::Codecs(Err(e))
+            | TaskOutput::Search(Err(e)) => self
+                .state
+                .set_status(StatusMessage::error(e.replace('\n', " "))),
+        }
+    }
+
+    fn exit(&mut self) {
+        self.tasks.cancel();
+        self.exit = true;
+    }
+}
+
+/// Put `text` on the clipboard with the OSC 52 escape sequence, which
+/// terminals pass on to the system clipboard, over SSH too.
+fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    use std::io::Write;
+    print!(
+        "\x1b]52;c;{}\x07",
+        crate::file::logical::base64(text.as_bytes())
+    );
+    io::stdout().flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::{test_data_path, write_test_file};
+    use crossterm::event::KeyModifiers;
+
+    #[test]
+    fn test_right_is_clamped_to_max_horizontal_offset() {
+        let mut state = AppState::new();
+        state.set_max_horizontal_offset(3);
+        for _ in 0..10 {
+            state.right();
+        }
+        // Without the clamp the offset would run past the visible range,
+        // producing the "empty clicks" seen when scrolling back.
+        assert_eq!(state.horizontal_offset(), 3);
+    }
+
+    #[test]
+    fn test_left_saturates_at_zero() {
+        let mut state = AppState::new();
+        state.set_max_horizontal_offset(5);
+        state.right();
+        state.right();
+        state.left();
+        state.left();
+        state.left();
+        assert_eq!(state.horizontal_offset(), 0);
+    }
+
+    #[test]
+    fn test_page_left_and_right_move_by_visible_columns() {
+        let mut state = AppState::new();
+        state.set_max_horizontal_offset(10);
+        state.set_visible_columns(4);
+        state.page_right();
+        assert_eq!(state.horizontal_offset(), 4);
+        state.page_right();
+        state.page_right();
+        assert_eq!(state.horizontal_offset(), 10);
+        state.page_left();
+        assert_eq!(state.horizontal_offset(), 6);
+        state.page_left();
+        state.page_left();
+        assert_eq!(state.horizontal_offset(), 0);
+    }
+
+    #[test]
+    fn test_page_right_pans_the_data_table() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        app.render_to_string(60, 20).unwrap();
+        let visible = app.state.visible_columns();
+        assert!(visible > 1 && visible < 11);
+
+        press(&mut app, KeyCode::Char('L'));
+        assert_eq!(app.state.horizontal_offset(), visible);
+        press(&mut app, KeyCode::Char('H'));
+        assert_eq!(app.state.horizontal_offset(), 0);
+    }
+
+    #[test]
+    fn test_schema_stats_freeze_names_when_scrolled() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        app.select_tab(2);
+        let screen = app.render_to_string(100, 20).unwrap();
+        assert!(!screen.contains("│Column"));
+
+        press(&mut app, KeyCode::Char('L'));
+        assert!(app.state.horizontal_offset() > 0);
+        let screen = app.render_to_string(100, 20).unwrap();
+        let row = screen.lines().find(|l| l.contains("│bool_col")).unwrap();
+        assert!(row.contains("PLAIN"));
+        assert!(screen.contains("│Column"));
+        assert!(!screen.contains("Repetition"));
+    }
+
+    #[test]
+    fn test_resize_tree_pane_starts_from_natural_width_and_clamps() {
+        let mut state = AppState::new();
+        state.set_natural_tree_percent(30);
+        state.resize_tree_pane(5);
+        assert_eq!(state.layout().tree_percent, Some(35));
+        state.resize_tree_pane(-100);
+        assert_eq!(
+            state.layout().tree_percent,
+            Some(LayoutConfig::MIN_TREE_PERCENT)
+        );
+        state.resize_tree_pane(200);
+        assert_eq!(
+            state.layout().tree_percent,
+            Some(LayoutConfig::MAX_TREE_PERCENT)
+        );
+    }
+
+    #[test]
+    fn test_resizing_uncollapses_the_tree() {
+        let mut state = AppState::new();
+        state.toggle_tree_pane();
+        assert!(state.layout().tree_collapsed);
+        state.resize_tree_pane(2);
+        assert!(!state.layout().tree_collapsed);
+    }
+
+    #[test]
+    fn test_reset_keeps_the_layout() {
+        let mut state = AppState::new();
+        state.set_tree_percent(40);
+        state.reset();
+        assert_eq!(state.layout().tree_percent, Some(40));
+    }
+
+    // root, a, g { b, h { c } }, d
+    fn nested_outline() -> SchemaOutline {
+        [
+            (0, false),
+            (1, true),
+            (1, false),
+            (2, true),
+            (2, false),
+            (3, true),
+            (1, true),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn test_schema_cursor_walks_groups_and_leaves() {
+        let outline = nested_outline();
+        let mut state = AppState::new();
+        state.move_schema_cursor(&outline, 1);
+        assert_eq!(state.vertical_offset(), 1);
+        state.move_schema_cursor(&outline, 1);
+        assert_eq!(state.selected_group(), Some(2));
+        assert_eq!(state.vertical_offset(), 0);
+        state.move_schema_cursor(&outline, 1);
+        assert_eq!((state.selected_group(), state.vertical_offset()), (None, 2));
+        state.move_schema_cursor(&outline, -3);
+        assert_eq!(state.schema_cursor(&outline), None);
+    }
+
+    #[test]
+    fn test_collapsing_skips_hidden_leaves() {
+        let outline = nested_outline();
+        let mut state = AppState::new();
+        // Select `b` and fold its group: the cursor moves onto the group.
+        state.set_vertical_offset(2);
+        state.toggle_group(&outline);
+        assert_eq!(state.selected_group(), Some(2));
+        assert!(state.collapsed_groups().contains(&2));
+
+        // `b` and `c` are hidden, so the next row is `d`.
+        state.move_schema_cursor(&outline, 1);
+        assert_eq!(state.vertical_offset(), 4);
+
+        state.move_schema_cursor(&outline, -1);
+        state.toggle_group(&outline);
+        assert!(state.collapsed_groups().is_empty());
+        assert_eq!(state.selected_group(), Some(2));
+    }
+
+    #[test]
+    fn test_flat_view_lists_only_leaves() {
+        let outline = nested_outline();
+        let mut state = AppState::new();
+        state.move_schema_cursor(&outline, 2);
+        state.toggle_group(&outline);
+        assert_eq!(state.selected_group(), Some(2));
+
+        // The selected group hands the cursor to its first leaf.
+        state.toggle_schema_flat(&outline);
+        assert_eq!(state.visible_schema_nodes(&outline), vec![0, 1, 3, 5, 6]);
+        assert_eq!(state.schema_cursor(&outline), Some(3));
+        state.move_schema_cursor(&outline, 1);
+        assert_eq!(state.schema_cursor(&outline), Some(5));
+
+        // Back in the tree, the folded group around the cursor opens up.
+        state.toggle_schema_flat(&outline);
+        assert!(state.collapsed_groups().is_empty());
+        assert!(state.visible_schema_nodes(&outline).contains(&5));
+    }
+
+    #[test]
+    fn test_command_line_editing() {
+        let mut state = AppState::new();
+        state.open_command_line("goto ");
+        state.push_command_char('1');
+        state.push_command_char('2');
+        state.pop_command_char();
+        assert_eq!(state.command_line(), Some("goto 1"));
+        assert_eq!(state.close_command_line().as_deref(), Some("goto 1"));
+        assert_eq!(state.command_line(), None);
+
+        // Backspace on an empty line closes it.
+        state.open_command_line("");
+        state.pop_command_char();
+        assert_eq!(state.command_line(), None);
+    }
+
+    fn test_ctx() -> ParquetCtx {
+        let path = test_data_path("alltypes_plain.parquet");
+        ParquetCtx::from_file(&path).unwrap()
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    /// A file of 1,000 rows in row groups of 250, each row's `id` its
+    /// position; the preview holds 200.
+    fn write_ids(name: &str) -> std::path::PathBuf {
+        use arrow::array::{Int64Array, RecordBatch};
+        use parquet::file::properties::WriterProperties;
+        use std::sync::Arc;
+
+        let batch = RecordBatch::try_from_iter([(
+            "id",
+            Arc::new(Int64Array::from_iter_values(0..1000)) as _,
+        )])
+        .unwrap();
+        let props = WriterProperties::builder()
+            .set_max_row_group_row_count(Some(250))
+            .build();
+        write_test_file(name, &batch, Some(props))
+    }
+
+    #[test]
+    fn test_summary_under_tabs() {
+        let path = write_ids("summary");
+        let ctx = ParquetCtx::from_file(&path.display().to_string()).unwrap();
+        let mut app = App::new(&ctx);
+        for tab in [TabKind::Visualize, TabKind::Metadata] {
+            app.select_tab(tab.index());
+            let screen = app.render_to_string(100, 12).unwrap();
+            let line = screen.lines().nth(2).unwrap();
+            assert!(
+                line.contains("1,000 rows · 1 cols · 4 row groups · "),
+                "{line}"
+            );
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_sampling_modes() {
+        let path = write_ids("sample");
+        let ctx = ParquetCtx::from_file(&path.display().to_string()).unwrap();
+        let mut app = App::new(&ctx);
+        let crumbs = |app: &App| AppRenderView::from_app(app).breadcrumb()[2..].to_vec();
+
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.state.sampling(), Sampling::Tail);
+        assert_eq!(app.sample_data().first_row, 800);
+        assert_eq!(crumbs(&app), ["rg 4/4", "row 801 / 1,000", "sample tail"]);
+
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(
+            app.state.status().map(|status| status.text.as_str()),
+            Some("sample every: 200 rows")
+        );
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.sample_data().rows[1][0], "5");
+        assert_eq!(crumbs(&app), ["rg 1/4", "row 6 / 1,000", "sample every"]);
+        let frame = app.render_to_string(80, 12).unwrap();
+        assert!(frame.contains("\n6     │  5"), "{frame}");
+
+        app.run_command(Command::parse("sample row_groups").unwrap())
+            .unwrap();
+        assert_eq!(app.sample_data().position(50), 250);
+        assert_eq!(app.sample_data().rows[50][0], "250");
+
+        // Rows in the sample are selected in place, others load their window.
+        app.run_command(Command::Goto(252)).unwrap();
+        assert_eq!(app.state.vertical_offset(), 51);
+        assert_eq!(app.state.sampling(), Sampling::RowGroups);
+        app.run_command(Command::Goto(500)).unwrap();
+        assert_eq!(app.state.sampling(), Sampling::Head);
+        assert_eq!(app.sample_data().positions, None);
+        assert_eq!(crumbs(&app), ["rg 2/4", "row 500 / 1,000"]);
+
+        app.select_tab(TabKind::Schema.index());
+        assert!(app.run_command(Command::Sample(Sampling::Tail)).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_row_group_is_kept_across_tabs() {
+        let path = write_ids("sticky-row-group");
+        let ctx = ParquetCtx::from_file(&path.display().to_string()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let mut app = App::new(&ctx);
+        app.select_tab(TabKind::RowGroups.index());
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Char('m'));
+        // Selecting a column keeps the row group.
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.state.row_group(), 2);
+
+        // Scrolling the preview doesn't move it either.
+        app.run_command(Command::Tab(TabKind::Visualize)).unwrap();
+        assert_eq!(app.state.horizontal_offset(), 0);
+        press(&mut app, KeyCode::Right);
+        app.run_command(Command::Tab(TabKind::RowGroups)).unwrap();
+        assert_eq!(app.state.row_group(), 2);
+        assert_eq!(app.state.marked_row_groups(), &[2]);
+        assert_eq!(
+            AppRenderView::from_app(&app).breadcrumb(),
+            ["Row Groups", "id", "rg 3/4"]
+        );
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.state.row_group(), 0);
+        assert!(app.state.marked_row_groups().is_empty());
+    }
+
+    #[test]
+    fn test_jump_list() {
+        let path = write_ids("jumps");
+        let ctx = ParquetCtx::from_file(&path.display().to_string()).unwrap();
+        let mut app = App::new(&ctx);
+        app.render_to_string(80, 12).unwrap();
+        let ctrl = |app: &mut App, c| {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL))
+        };
+
+        app.run_command(Command::Goto(500)).unwrap();
+        assert_eq!(app.place().row, Some(499));
+        ctrl(&mut app, 'o');
+        assert_eq!(app.place().row, Some(0));
+        ctrl(&mut app, 'i');
+        assert_eq!(app.place().row, Some(499));
+        ctrl(&mut app, 'i');
+        assert_eq!(
+            app.state.status().map(|status| status.text.as_str()),
+            Some("no later place in the jump list")
+        );
+
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.tabs.active_tab, TabKind::Metadata.index());
+        ctrl(&mut app, 'o');
+        assert_eq!(app.tabs.active_tab, TabKind::Visualize.index());
+        assert_eq!(app.place().row, Some(499));
+        ctrl(&mut app, 'o');
+        assert_eq!(app.place().row, Some(0));
+        ctrl(&mut app, 'o');
+        assert_eq!(
+            app.state.status().map(|status| status.text.as_str()),
+            Some("no earlier place in the jump list")
+        );
+        ctrl(&mut app, 'i');
+        ctrl(&mut app, 'i');
+        assert_eq!(app.tabs.active_tab, TabKind::Metadata.index());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_record_and_play_macro() {
+        let path = write_ids("macros");
+        let ctx = ParquetCtx::from_file(&path.display().to_string()).unwrap();
+        let mut app = App::new(&ctx);
+        app.render_to_string(80, 12).unwrap();
+        let ctrl = |app: &mut App, c| {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL))
+        };
+        let status = |app: &App| app.state.status().map(|status| status.text.clone());
+
+        ctrl(&mut app, 'q');
+        press(&mut app, KeyCode::Char('a'));
+        assert_eq!(app.state.recording_macro(), Some('a'));
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        ctrl(&mut app, 'q');
+        assert_eq!(app.state.recording_macro(), None);
+        assert_eq!(
+            app.macros().get('a'),
+            Some(vec![KeyCode::Down.into(), KeyCode::Down.into()])
+        );
+        assert_eq!(status(&app).as_deref(), Some("recorded 2 keys into @a"));
+        assert_eq!(app.place().row, Some(2));
+
+        press(&mut app, KeyCode::Char('@'));
+        press(&mut app, KeyCode::Char('a'));
+        assert_eq!(app.place().row, Some(4));
+        press(&mut app, KeyCode::Char('@'));
+        press(&mut app, KeyCode::Char('@'));
+        assert_eq!(app.place().row, Some(6));
+
+        press(&mut app, KeyCode::Char('@'));
+        press(&mut app, KeyCode::Char('b'));
+        assert_eq!(status(&app).as_deref(), Some("nothing recorded into @b"));
+        // Esc cancels without moving.
+        press(&mut app, KeyCode::Char('@'));
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.place().row, Some(6));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_ascii_rendering() {
+        let path = write_ids("ascii");
+        let ctx = ParquetCtx::from_file(&path.display().to_string()).unwrap();
+        let mut app = App::new(&ctx).with_render_mode(RenderMode {
+            no_color: true,
+            ascii: true,
+        });
+        for tab in [
+            TabKind::Visualize,
+            TabKind::Metadata,
+            TabKind::Schema,
+            TabKind::RowGroups,
+        ] {
+            app.select_tab(tab.index());
+            let screen = app.render_to_string(100, 20).unwrap();
+            assert!(screen.is_ascii(), "{screen}");
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_wrap_rows() {
+        let path = write_ids("wrap-rows");
+        let ctx = ParquetCtx::from_file(&path.display().to_string()).unwrap();
+        let mut app = App::new(&ctx);
+        app.select_tab(TabKind::Visualize.index());
+        app.render_to_string(80, 12).unwrap();
+
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Char('w'));
+        assert_eq!(app.state.wrapped_rows(), &BTreeSet::from([1]));
+        // Rows are kept by their position in the file, across windows.
+        press(&mut app, KeyCode::Char('G'));
+        press(&mut app, KeyCode::Char('w'));
+        assert_eq!(app.state.wrapped_rows(), &BTreeSet::from([1, 999]));
+        press(&mut app, KeyCode::Char('w'));
+        assert_eq!(app.state.wrapped_rows(), &BTreeSet::from([1]));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_jump_to_last_rows() {
+        let path = write_ids("last-rows");
+        let ctx = ParquetCtx::from_file(&path.display().to_string()).unwrap();
+        let mut app = App::new(&ctx);
+        // Size the table the way the first frame does.
+        app.render_to_string(80, 12).unwrap();
+
+        press(&mut app, KeyCode::Char('G'));
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(app.sample_data().first_row, 800);
+        assert_eq!(app.sample_data().rows[199][0], "999");
+        assert_eq!(app.state.vertical_offset(), 199);
+        assert_eq!(
+            app.state.status().map(|status| status.text.as_str()),
+            Some("loaded rows 801-1000")
+        );
+        let crumbs = AppRenderView::from_app(&app).breadcrumb();
+        assert_eq!(crumbs[2..], ["rg 4/4", "row 1,000 / 1,000"]);
+
+        // Already there, so End only moves the cursor.
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::End);
+        assert_eq!(app.state.vertical_offset(), 199);
+        let frame = app.render_to_string(80, 12).unwrap();
+        assert!(frame.contains("1000  │  999"), "{frame}");
+    }
+
+    #[test]
+    fn test_compare_marked_row_groups() {
+        use arrow::array::{Int64Array, RecordBatch};
+        use parquet::file::properties::WriterProperties;
+        use std::sync::Arc;
+
+        // Row groups of 100 and 50 rows.
+        let batch = RecordBatch::try_from_iter([(
+            "id",
+            Arc::new(Int64Array::from_iter_values(0..150)) as _,
+        )])
+        .unwrap();
+        let props = WriterProperties::builder()
+            .set_max_row_group_row_count(Some(100))
+            .build();
+        let path = write_test_file("compare", &batch, Some(props));
+        let ctx = ParquetCtx::from_file(&path.display().to_string()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut app = App::new(&ctx);
+        app.select_tab(3);
+        press(&mut app, KeyCode::Char('m'));
+        assert_eq!(app.state.marked_row_groups(), &[0]);
+        assert_eq!(app.state.compared_row_groups(), None);
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Char('m'));
+        assert_eq!(app.state.compared_row_groups(), Some((0, 1)));
+        assert_eq!(
+            app.state.status().map(|status| status.text.as_str()),
+            Some("comparing row groups 1 and 2")
+        );
+        let frame = app.render_to_string(140, 20).unwrap();
+        assert!(frame.contains("Row groups 1 and 2"), "{frame}");
+        assert!(frame.contains("100 vs 50 rows"), "{frame}");
+
+        // Marking a marked row group unmarks it, and Esc drops all marks.
+        press(&mut app, KeyCode::Char('m'));
+        assert_eq!(app.state.marked_row_groups(), &[0]);
+        press(&mut app, KeyCode::Esc);
+        assert!(app.state.marked_row_groups().is_empty());
+    }
+
+    #[test]
+    fn test_render_each_tab() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        let expected = [
+            ("Visualize", "bool_col"),
+            ("Metadata", "Size breakdown"),
+            ("Schema", "Schema Tree"),
+            ("Row Groups", "Row Group: 1 / 1"),
+        ];
+        for (tab, text) in expected {
+            let screen = app.render_to_string(120, 30).unwrap();
+            assert_eq!(screen.lines().count(), 30);
+            assert_eq!(app.tabs.active_tab().to_string(), tab);
+            assert!(screen.contains(text), "{tab} tab:\n{screen}");
+            press(&mut app, KeyCode::Tab);
+        }
+    }
+
+    #[test]
+    fn test_row_group_offset_chart() {
+        let path = write_ids("offset-chart");
+        let ctx = ParquetCtx::from_file(path.to_str().unwrap()).unwrap();
+        let mut app = App::new(&ctx);
+        app.select_tab(3);
+        let screen = app.render_to_string(160, 60).unwrap();
+        assert!(screen.contains("Rows by offset (% of max)"), "{screen}");
+        assert!(screen.contains("File Offset"), "{screen}");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_dictionary_chart() {
+        let path = test_data_path("nulls.snappy.parquet");
+        let ctx = ParquetCtx::from_file(&path).unwrap();
+        let mut app = App::new(&ctx);
+        app.select_tab(3);
+        press(&mut app, KeyCode::Down);
+        let screen = app.render_to_string(160, 60).unwrap();
+        assert!(screen.contains("Dictionary by Row Group (4)"), "{screen}");
+        assert!(screen.contains("Entries"), "{screen}");
+    }
+
+    #[test]
+    fn test_keys_drive_the_rendered_app() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        press(&mut app, KeyCode::Char('?'));
+        let screen = app.render_to_string(120, 40).unwrap();
+        assert!(screen.contains("parqeye v"), "{screen}");
+        press(&mut app, KeyCode::Esc);
+
+        press(&mut app, KeyCode::Char(':'));
+        for c in "nope".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        assert!(app.render_to_string(120, 30).unwrap().contains(":nope"));
+        press(&mut app, KeyCode::Enter);
+        let screen = app.render_to_string(120, 30).unwrap();
+        assert!(screen.contains("unknown command 'nope'"), "{screen}");
+    }
+
+    #[test]
+    fn test_command_errors_show_in_the_status_line() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        app.state.open_command_line("jump 3");
+        app.handle_command_line_key(KeyCode::Enter);
+        let status = app.state.status().unwrap();
+        assert_eq!(status.kind, crate::status::StatusKind::Error);
+        assert!(status.text.contains("jump"));
+
+        // Opening the command line again clears it, and it times out on its own.
+        app.state.open_command_line("");
+        assert!(app.state.status().is_none());
+        app.state.set_status(StatusMessage::info("done"));
+        app.state
+            .expire_status(Instant::now() + crate::status::INFO_TIMEOUT * 2);
+        assert!(app.state.status().is_none());
+    }
+
+    #[test]
+    fn test_search_preview_values() {
+        let path = write_ids("search");
+        let ctx = ParquetCtx::from_file(&path.display().to_string()).unwrap();
+        let mut app = App::new(&ctx);
+        let selected = |app: &App| app.sample_data().position(app.state.vertical_offset());
+
+        press(&mut app, KeyCode::Char('/'));
+        for c in "/^1.5$/".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(selected(&app), 105);
+        press(&mut app, KeyCode::Char('n'));
+        assert_eq!(selected(&app), 115);
+        press(&mut app, KeyCode::Char('N'));
+        press(&mut app, KeyCode::Char('N'));
+        assert_eq!(selected(&app), 195);
+        assert_eq!(
+            app.state.status().map(|status| status.text.as_str()),
+            Some("row 196 matches '/^1.5$/' (wrapped)")
+        );
+
+        // Rows past the preview are only found by scanning the file.
+        let past = "/^900$/".parse().unwrap();
+        assert!(app.run_command(Command::Search(Some(past))).is_err());
+        let past = "/^900$/".parse().unwrap();
+        app.run_command(Command::SearchFile(past)).unwrap();
+        wait_for_task(&mut app);
+        assert_eq!(selected(&app), 900);
+        let before = "/^10$/".parse().unwrap();
+        app.run_command(Command::SearchFile(before)).unwrap();
+        wait_for_task(&mut app);
+        assert_eq!(selected(&app), 900);
+        assert_eq!(
+            app.state.status().map(|status| status.text.as_str()),
+            Some("no more rows match '/^10$/'")
+        );
+
+        app.run_command(Command::Search(None)).unwrap();
+        assert!(app.state.search().is_none());
+        app.select_tab(TabKind::Schema.index());
+        assert!(app.run_command(Command::Search(None)).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_goto_row_selects_the_row() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        app.run_command(Command::Goto(3)).unwrap();
+        assert_eq!(app.state.vertical_offset(), 2);
+
+        let past_end = ctx.metadata.num_rows + 1;
+        assert!(app.run_command(Command::Goto(past_end)).is_err());
+        assert_eq!(app.state.vertical_offset(), 2);
+    }
+
+    #[test]
+    fn test_goto_row_group_is_bounded() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        app.select_tab(3);
+        app.run_command(Command::Goto(1)).unwrap();
+        assert_eq!(app.state.row_group(), 0);
+        let past_end = ctx.row_groups.num_row_groups() + 1;
+        assert!(app.run_command(Command::Goto(past_end)).is_err());
+
+        app.select_tab(1);
+        assert!(app.run_command(Command::Goto(1)).is_err());
+    }
+
+    #[test]
+    fn test_open_at_position() {
+        let path = test_data_path("nulls.snappy.parquet");
+        let ctx = ParquetCtx::from_file(&path).unwrap();
+        let outline = ctx.schema.outline();
+
+        // A nested column is selected in the Schema tab, unfolding its groups.
+        let column = ctx.schema.leaf_paths().pop().unwrap();
+        let node = ctx.schema.find_path(&column).unwrap();
+        let mut app = App::new(&ctx);
+        app.state.set_collapsed_groups(
+            (0..outline.len())
+                .filter(|&n| outline.is_group(n))
+                .collect(),
+        );
+        let app = app
+            .with_position(&StartPosition {
+                column: Some(column.clone()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(app.tabs.active_tab, TabKind::Schema.index());
+        assert_eq!(app.state.schema_cursor(&outline), Some(node));
+        assert!(app.state.visible_schema_nodes(&outline).contains(&node));
+
+        let app = App::new(&ctx)
+            .with_position(&StartPosition {
+                row_group: Some(1),
+                column: Some(column),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(app.tabs.active_tab, TabKind::RowGroups.index());
+        assert_eq!(app.state.row_group(), 0);
+
+        let app = App::new(&ctx)
+            .with_position(&StartPosition {
+                row: Some(2),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(app.tabs.active_tab, TabKind::Visualize.index());
+        assert_eq!(app.state.vertical_offset(), 1);
+    }
+
+    #[test]
+    fn test_breadcrumb_follows_the_cursor() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        app.run_command(Command::Goto(3)).unwrap();
+        let first_column = ctx.sample_data.flattened_columns[0].clone();
+        let rows = commas(ctx.metadata.num_rows as u64);
+        assert_eq!(
+            AppRenderView::from_app(&app).breadcrumb(),
+            [
+                "Visualize",
+                &first_column,
+                "rg 1/1",
+                &format!("row 3 / {rows}")
+            ]
+        );
+
+        app.select_tab(TabKind::Schema.index());
+        let outline = ctx.schema.outline();
+        app.state.set_schema_cursor(&outline, Some(1));
+        assert_eq!(
+            AppRenderView::from_app(&app).breadcrumb(),
+            ["Schema".to_string(), ctx.schema.column_path(1)]
+        );
+
+        app.select_tab(TabKind::Metadata.index());
+        assert_eq!(AppRenderView::from_app(&app).breadcrumb(), ["Metadata"]);
+
+        let screen = app.render_to_string(120, 20).unwrap();
+        assert!(screen.lines().last().unwrap().contains("parqeye Metadata "));
+    }
+
+    #[test]
+    fn test_column_scrollbar_shows_for_wide_tables() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        let scrollbar_line = |screen: String| screen.lines().rev().nth(1).unwrap().to_string();
+
+        let narrow = scrollbar_line(app.render_to_string(60, 16).unwrap());
+        assert!(narrow.starts_with('█'), "{narrow}");
+        app.state.page_right();
+        let scrolled = scrollbar_line(app.render_to_string(60, 16).unwrap());
+        assert!(
+            scrolled.starts_with('─') && scrolled.contains('█'),
+            "{scrolled}"
+        );
+
+        app.state.set_horizontal_offset(0);
+        let wide = scrollbar_line(app.render_to_string(400, 16).unwrap());
+        assert!(!wide.contains('█'), "{wide}");
+    }
+
+    #[test]
+    fn test_open_at_bad_position() {
+        let ctx = test_ctx();
+        let open = |position: StartPosition| App::new(&ctx).with_position(&position).err();
+        let past_end = ctx.row_groups.num_row_groups() + 1;
+        assert!(
+            open(StartPosition {
+                row_group: Some(past_end),
+                ..Default::default()
+            })
+            .unwrap()
+            .contains("past the end")
+        );
+        assert_eq!(
+            open(StartPosition {
+                column: Some("no.such".to_string()),
+                ..Default::default()
+            }),
+            Some("no column no.such".to_string())
+        );
+        assert!(
+            open(StartPosition {
+                tab: Some(TabKind::Metadata),
+                row: Some(1),
+                ..Default::default()
+            })
+            .is_some()
+        );
+        assert!(
+            open(StartPosition {
+                tab: Some(TabKind::Metadata),
+                ..Default::default()
+            })
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_filter_row_groups() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        assert!(
+            app.run_command(Command::parse("filter rows > 0").unwrap())
+                .is_err()
+        );
+
+        app.select_tab(3);
+        assert!(
+            app.run_command(Command::parse("filter rows < 0").unwrap())
+                .is_err()
+        );
+        assert!(app.state.row_group_filter().is_none());
+
+        app.run_command(Command::parse("filter rows > 0").unwrap())
+            .unwrap();
+        assert_eq!(
+            app.state.filtered_row_groups().map(<[usize]>::len),
+            Some(ctx.row_groups.num_row_groups())
+        );
+        app.run_command(Command::Filter(None)).unwrap();
+        assert!(app.state.filtered_row_groups().is_none());
+    }
+
+    #[test]
+    fn test_prune_row_groups() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        assert!(
+            app.run_command(Command::parse("prune id > 0").unwrap())
+                .is_err()
+        );
+
+        app.select_tab(3);
+        assert!(
+            app.run_command(Command::parse("prune no_such_column > 0").unwrap())
+                .is_err()
+        );
+        assert!(app.state.pruning().is_none());
+
+        app.run_command(Command::parse("prune id > 0").unwrap())
+            .unwrap();
+        assert_eq!(
+            app.state.pruning().map(|report| report.row_groups.len()),
+            Some(ctx.row_groups.num_row_groups())
+        );
+        app.run_command(Command::Prune(None)).unwrap();
+        assert!(app.state.pruning().is_none());
+    }
+
+    #[test]
+    fn test_bloom_probes() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        app.select_tab(3);
+        app.run_command(Command::parse("prune id > 0").unwrap())
+            .unwrap();
+        assert!(
+            app.run_command(Command::parse("bloom id abc").unwrap())
+                .is_err()
+        );
+
+        app.run_command(Command::parse("bloom id 1, 2").unwrap())
+            .unwrap();
+        wait_for_task(&mut app);
+        assert!(app.state.pruning().is_none());
+        assert_eq!(app.state.bloom().map(|report| report.probes), Some(2));
+        app.run_command(Command::Bloom(None)).unwrap();
+        assert!(app.state.bloom().is_none());
+    }
+
+    #[test]
+    fn test_correlate_columns() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        app.run_command(Command::parse("corr int_col bigint_col").unwrap())
+            .unwrap();
+        wait_for_task(&mut app);
+        assert_eq!(
+            app.state.status().map(|status| status.text.as_str()),
+            Some("int_col vs bigint_col: r = 1.000 over 8 rows")
+        );
+        let frame = app.render_to_string(100, 30).unwrap();
+        assert!(frame.contains(" int_col vs bigint_col "), "{frame}");
+        assert!(
+            frame.contains("n = 8 of 8 rows  Pearson r = 1.000  Spearman ρ = 1.000"),
+            "{frame}"
+        );
+        // The plot takes all input until it is closed.
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.tabs.active_tab, 0);
+        press(&mut app, KeyCode::Esc);
+        assert!(app.state.correlation().is_none());
+
+        // Without x, the selected column is plotted.
+        app.select_tab(TabKind::Schema.index());
+        let outline = ctx.schema.outline();
+        app.state.set_schema_cursor(&outline, Some(1));
+        app.run_command(Command::parse("corr tinyint_col").unwrap())
+            .unwrap();
+        wait_for_task(&mut app);
+        assert_eq!(app.state.correlation().map(|c| c.x.as_str()), Some("id"));
+        press(&mut app, KeyCode::Esc);
+
+        app.run_command(Command::parse("corr id string_col").unwrap())
+            .unwrap();
+        wait_for_task(&mut app);
+        assert!(app.state.correlation().is_none());
+        assert!(
+            app.state
+                .status()
+                .is_some_and(|status| status.text.starts_with("string_col is not numeric"))
+        );
+    }
+
+    #[test]
+    fn test_duplicate_check() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        app.run_command(Command::parse("dups bool_col").unwrap())
+            .unwrap();
+        wait_for_task(&mut app);
+        assert_eq!(
+            app.state.status().map(|status| status.text.as_str()),
+            Some(
+                "75.00% duplicate rows in 8 sampled: 2 keys repeat, 0 across row groups; \
+                 top bool_col=false ×4"
+            )
+        );
+
+        app.run_command(Command::parse("dups").unwrap()).unwrap();
+        wait_for_task(&mut app);
+        assert_eq!(
+            app.state.status().map(|status| status.text.as_str()),
+            Some("no duplicates in 8 rows sampled")
+        );
+    }
+
+    #[test]
+    fn test_utf8_check() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        app.run_command(Command::Utf8).unwrap();
+        wait_for_task(&mut app);
+        assert_eq!(
+            app.state.status().map(|status| status.text.as_str()),
+            Some("no STRING columns")
+        );
+    }
+
+    #[test]
+    fn test_bench() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        app.run_command(Command::Bench).unwrap();
+        wait_for_task(&mut app);
+        let status = app.state.status().map(|status| status.text.clone());
+        assert!(
+            status
+                .as_deref()
+                .is_some_and(|text| text.starts_with("decoded 11 columns in ")),
+            "{status:?}"
+        );
+    }
+
+    #[test]
+    fn test_preset() {
+        let ctx = test_ctx();
+        let config = AppConfig::default();
+        let commands = config.preset("size-debug").unwrap();
+        let mut app = App::new(&ctx).with_preset("size-debug", commands);
+        assert_eq!(app.tabs.active_tab().to_string(), "Row Groups");
+        wait_for_task(&mut app);
+        assert_eq!(
+            app.state.status().map(|status| status.text.as_str()),
+            Some("no ZSTD, GZIP or BROTLI columns to tune")
+        );
+
+        // Profiling needs the Schema tab, which the preset never opens.
+        let commands = vec![Command::Bench, Command::parse("profile").unwrap()];
+        let mut app = App::new(&ctx).with_preset("mixed", commands);
+        assert_eq!(
+            app.state.status().map(|status| status.text.as_str()),
+            Some("preset mixed: profile is not available in the Visualize tab")
+        );
+        wait_for_task(&mut app);
+    }
+
+    #[test]
+    fn test_codec_levels() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        app.run_command(Command::Codecs { level: None }).unwrap();
+        wait_for_task(&mut app);
+        assert_eq!(
+            app.state.status().map(|status| status.text.as_str()),
+            Some("no ZSTD, GZIP or BROTLI columns to tune")
+        );
+    }
+
+    #[test]
+    fn test_open_external() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        press(&mut app, KeyCode::Char('p'));
+        let (name, text) = app.pending_external.take().unwrap();
+        assert_eq!(name, "id.txt");
+        assert_eq!(text, format!("{}\n", app.sample_data().rows[0][0]));
+
+        press(&mut app, KeyCode::Char('v'));
+        assert!(app.state.inspecting());
+        press(&mut app, KeyCode::Char('p'));
+        let (name, text) = app.pending_external.take().unwrap();
+        assert_eq!(name, "row.json");
+        assert!(text.starts_with("{\n  \"id\": "), "{text}");
+
+        press(&mut app, KeyCode::Char('v'));
+        app.select_tab(2);
+        press(&mut app, KeyCode::Char('P'));
+        let (name, text) = app.pending_external.take().unwrap();
+        assert_eq!(name, "schema.sql");
+        assert!(
+            text.starts_with("CREATE TABLE `alltypes_plain` (\n  `id` INT"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn test_estimate_distinct_values() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        app.select_tab(2);
+        app.handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+        assert_eq!(app.state.distinct_progress(), Some((0, 11)));
+
+        app.estimate_next_distinct();
+        assert_eq!(app.state.distinct_progress(), Some((1, 11)));
+        assert_eq!(app.state.distinct_estimates().get(&0), Some(&8));
+        let frame = app.render_to_string(120, 20).unwrap();
+        assert!(frame.contains("estimating distinct values 1 / 11"));
+
+        while app.state.distinct_progress().is_some() {
+            app.estimate_next_distinct();
+        }
+        assert_eq!(app.state.distinct_estimates().len(), 11);
+        // bool_col
+        assert_eq!(app.state.distinct_estimates().get(&1), Some(&2));
+    }
+
+    /// Block until the running task reports back, and handle its result.
+    fn wait_for_task(app: &mut App) {
+        let event = app.tasks.events.recv().unwrap();
+        app.handle_event(event).unwrap();
+    }
+
+    #[test]
+    fn test_task_shows_progress_and_cancels() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        app.tasks
+            .spawn("profiling", 4096, |progress| {
+                progress.advance(1024);
+                while !progress.is_cancelled() {
+                    thread::sleep(Duration::from_millis(1));
+                }
+                TaskOutput::Profile(Err(CANCELLED.to_string()))
+            })
+            .unwrap();
+        let progress = &app.tasks.running.as_ref().unwrap().progress;
+        while progress.done() == 0 {
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        let footer = app.render_to_string(120, 10).unwrap();
+        let footer = footer.lines().last().unwrap();
+        assert!(
+            footer.contains("profiling 1.00 KiB / 4.00 KiB · Esc cancels"),
+            "{footer}"
+        );
+
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        wait_for_task(&mut app);
+        assert!(app.tasks.running.is_none());
+        assert_eq!(
+            app.state.status().map(|status| status.text.as_str()),
+            Some("profiling cancelled")
+        );
+    }
+
+    #[test]
+    fn test_ticks_animate_tasks_without_progress() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        app.tasks
+            .spawn("bloom check", 0, |progress| {
+                while !progress.is_cancelled() {
+                    thread::sleep(Duration::from_millis(1));
+                }
+                TaskOutput::Bloom(Err(CANCELLED.to_string()))
+            })
+            .unwrap();
+        let footer = |app: &mut App| {
+            let frame = app.render_to_string(120, 10).unwrap();
+            frame.lines().last().unwrap().to_string()
+        };
+        assert!(footer(&mut app).contains("⠋ bloom check · Esc cancels"));
+        app.handle_event(AppEvent::Tick).unwrap();
+        assert!(footer(&mut app).contains("⠙ bloom check"));
+
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        wait_for_task(&mut app);
+        assert_eq!(
+            app.state.status().map(|status| status.text.as_str()),
+            Some("bloom check cancelled")
+        );
+    }
+
+    #[test]
+    fn test_profile_columns() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        assert!(app.run_command(Command::parse("profile").unwrap()).is_err());
+
+        app.select_tab(2);
+        app.run_command(Command::parse("profile").unwrap()).unwrap();
+        assert!(app.run_command(Command::parse("profile").unwrap()).is_err());
+        wait_for_task(&mut app);
+        assert_eq!(
+            app.state.profile().map(|profile| profile.columns.len()),
+            Some(11)
+        );
+        app.state.set_vertical_offset(1);
+        let frame = app.render_to_string(120, 24).unwrap();
+        assert!(frame.contains("Profile: id"));
+        assert!(frame.contains("sampled 8 of 8 rows"));
+
+        app.run_command(Command::parse("profile off").unwrap())
+            .unwrap();
+        assert!(app.state.profile().is_none());
+    }
+
+    #[test]
+    fn test_sort_columns_by_size() {
+        use arrow::array::{Int32Array, RecordBatch, StringArray};
+        use std::sync::Arc;
+
+        let batch = RecordBatch::try_from_iter([
+            ("id", Arc::new(Int32Array::from(vec![1, 2])) as _),
+            (
+                "body",
+                Arc::new(StringArray::from(vec![
+                    "lorem ipsum ".repeat(300),
+                    "dolor sit ".repeat(300),
+                ])) as _,
+            ),
+        ])
+        .unwrap();
+        let path = write_test_file("sort-size", &batch, None);
+        let ctx = ParquetCtx::from_file(&path.display().to_string()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut app = App::new(&ctx);
+        app.select_tab(TabKind::Schema.index());
+        let outline = ctx.schema.outline();
+        app.run_command(Command::parse("sort size").unwrap())
+            .unwrap();
+        assert!(app.state.schema_flat());
+        assert_eq!(app.state.visible_schema_nodes(&outline), vec![0, 2, 1]);
+        let frame = app.render_to_string(200, 20).unwrap();
+        assert!(frame.contains("% of File ↓"), "{frame}");
+
+        // Back in the tree, the columns are in schema order again.
+        press(&mut app, KeyCode::Char('f'));
+        assert!(!app.state.sorted_by_size());
+        assert_eq!(app.state.visible_schema_nodes(&outline), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_page_previews_load_with_the_chunk() {
+        use arrow::array::{Int64Array, RecordBatch};
+        use std::sync::Arc;
+
+        let ids = Int64Array::from_iter_values(7_000..7_010);
+        let batch = RecordBatch::try_from_iter([("id", Arc::new(ids) as _)]).unwrap();
+        let path = write_test_file("previews", &batch, None);
+        let ctx = ParquetCtx::from_file(&path.display().to_string()).unwrap();
+
+        let mut app = App::new(&ctx);
+        app.select_tab(TabKind::RowGroups.index());
+        app.render_to_string(160, 60).unwrap();
+        assert!(app.page_previews.is_none());
+
+        app.state.set_vertical_offset(1);
+        let frame = app.render_to_string(160, 60).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            app.page_previews.as_ref().map(|(chunk, _)| *chunk),
+            Some((0, 0))
+        );
+        assert!(frame.contains("7000, 7001, 7002"), "{frame}");
+    }
+
+    #[test]
+    fn test_decode_dictionary_page() {
+        use arrow::array::{RecordBatch, StringArray};
+        use std::sync::Arc;
+
+        let kinds =
+            StringArray::from_iter((0..50).map(|i| (i % 7 != 3).then_some(["on", "off"][i % 2])));
+        let batch = RecordBatch::try_from_iter([("kind", Arc::new(kinds) as _)]).unwrap();
+        let path = write_test_file("page", &batch, None);
+        let ctx = ParquetCtx::from_file(&path.display().to_string()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut app = App::new(&ctx);
+        app.select_tab(TabKind::RowGroups.index());
+        assert!(app.run_command(Command::parse("page 2").unwrap()).is_err());
+        app.state.set_vertical_offset(1);
+        app.run_command(Command::parse("page 1").unwrap()).unwrap();
+        wait_for_task(&mut app);
+        assert_eq!(
+            app.state.status().map(|status| status.text.as_str()),
+            Some("page 1 is the dictionary page")
+        );
+        app.run_command(Command::parse("page 2").unwrap()).unwrap();
+        assert!(app.state.decoded_page().is_none());
+        wait_for_task(&mut app);
+        let page = app.state.decoded_page().unwrap();
+        assert_eq!((page.values.len(), page.dictionary_size), (50, 2));
+        let frame = app.render_to_string(120, 30).unwrap();
+        assert!(frame.contains("Page 2 · RLE_DICTIONARY"), "{frame}");
+        assert!(frame.contains("null"), "{frame}");
+
+        app.run_command(Command::Page(None)).unwrap();
+        assert!(app.state.decoded_page().is_none());
+    }
+
+    #[test]
+    fn test_column_filter() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        app.select_tab(TabKind::Schema.index());
+        app.state.set_vertical_offset(1);
+        app.run_command(Command::parse("columns *int_col").unwrap())
+            .unwrap();
+        assert_eq!(
+            app.state.status().map(|status| status.text.as_str()),
+            Some("4 of 11 columns match")
+        );
+        // `id` is filtered out, so the cursor moves to `tinyint_col`.
+        assert_eq!(app.state.vertical_offset(), 3);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.state.vertical_offset(), 4);
+        let frame = app.render_to_string(160, 24).unwrap();
+        assert!(frame.contains("Schema Tree · *int_col"), "{frame}");
+        assert!(frame.contains("bigint_col"));
+        assert!(!frame.contains("bool_col"), "{frame}");
+
+        // The Row Groups tab steps through the same columns.
+        app.select_tab(TabKind::RowGroups.index());
+        app.state.set_vertical_offset(6);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.state.vertical_offset(), 6);
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.state.vertical_offset(), 5);
+
+        let session = app.session();
+        assert_eq!(session.column_filter.as_deref(), Some("*int_col"));
+        let restored = App::new(&ctx).with_session(session);
+        assert_eq!(
+            restored.state.column_filter().map(ToString::to_string),
+            Some("*int_col".to_string())
+        );
+
+        assert!(
+            app.run_command(Command::parse("columns nope").unwrap())
+                .is_err()
+        );
+        app.run_command(Command::Columns(None)).unwrap();
+        assert!(app.state.column_filter().is_none());
+    }
+
+    #[test]
+    fn test_bookmarks() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        press(&mut app, KeyCode::Char('B'));
+        assert_eq!(
+            app.state.status().map(|status| status.kind),
+            Some(crate::status::StatusKind::Error)
+        );
+        assert!(!app.state.bookmarked_only());
+
+        app.select_tab(TabKind::Schema.index());
+        // bool_col and int_col.
+        app.state.set_vertical_offset(2);
+        press(&mut app, KeyCode::Char('b'));
+        app.state.set_vertical_offset(5);
+        press(&mut app, KeyCode::Char('b'));
+        assert_eq!(app.state.bookmarks(), &BTreeSet::from([2, 5]));
+        press(&mut app, KeyCode::Char('B'));
+        let outline = ctx.schema.outline();
+        assert_eq!(app.state.visible_schema_nodes(&outline), vec![0, 2, 5]);
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.state.vertical_offset(), 2);
+        let frame = app.render_to_string(160, 24).unwrap();
+        assert!(frame.contains("Schema Tree · ★ only"), "{frame}");
+        assert!(frame.contains("bool_col ★"), "{frame}");
+        assert!(!frame.contains("tinyint_col"), "{frame}");
+
+        // The view stays on in the other tabs, and is saved with the session.
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.tabs.active_tab, TabKind::RowGroups.index());
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.state.vertical_offset(), 5);
+        let session = app.session();
+        let restored = App::new(&ctx).with_session(session);
+        assert_eq!(restored.state.bookmarks(), &BTreeSet::from([2, 5]));
+        assert!(restored.state.bookmarked_only());
+
+        // Removing the last bookmark lists every column again.
+        press(&mut app, KeyCode::Char('b'));
+        assert_eq!(app.state.vertical_offset(), 2);
+        press(&mut app, KeyCode::Char('b'));
+        assert!(app.state.bookmarks().is_empty());
+        assert!(!app.state.bookmarked_only());
+    }
+
+    #[test]
+    fn test_tabs_keep_their_own_positions() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        app.render_to_string(60, 24).unwrap();
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Right);
+
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.tabs.active_tab().to_string(), "Metadata");
+        assert_eq!(app.state.vertical_offset(), 0);
+        press(&mut app, KeyCode::Down);
+
+        app.run_command(Command::Tab(TabKind::Visualize)).unwrap();
+        assert_eq!(app.state.vertical_offset(), 2);
+        assert_eq!(app.state.horizontal_offset(), 1);
+
+        // Esc only goes back to the top of the active tab.
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.state.vertical_offset(), 0);
+        app.run_command(Command::Tab(TabKind::Metadata)).unwrap();
+        assert_eq!(app.state.vertical_offset(), 1);
+    }
+
+    #[test]
+    fn test_selected_column_follows_tabs() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        app.select_tab(TabKind::Schema.index());
+        // string_col.
+        app.state.set_vertical_offset(10);
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.tabs.active_tab().to_string(), "Row Groups");
+        assert_eq!(app.state.vertical_offset(), 10);
+        assert_eq!(app.state.horizontal_offset(), 0);
+
+        app.run_command(Command::Tab(TabKind::Visualize)).unwrap();
+        assert_eq!(app.state.horizontal_offset(), 9);
+        assert_eq!(
+            app.state.selected_column(),
+            Some(&SelectedColumn {
+                path: "string_col".to_string(),
+                leaf: Some(9),
+            })
+        );
+
+        // The Metadata tab has no columns and passes the selection on.
+        app.state.set_horizontal_offset(1);
+        app.run_command(Command::Tab(TabKind::Metadata)).unwrap();
+        app.run_command(Command::Tab(TabKind::Schema)).unwrap();
+        assert_eq!(app.state.vertical_offset(), 2);
+    }
+
+    #[test]
+    fn test_split_view() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        app.select_tab(TabKind::Schema.index());
+        let frame = app.render_to_string(160, 40).unwrap();
+        assert!(!frame.contains("Data Preview"), "{frame}");
+
+        press(&mut app, KeyCode::Char('|'));
+        assert!(app.state.layout().split);
+        // Selecting a column scrolls the preview to it.
+        app.state.set_vertical_offset(10);
+        let frame = app.render_to_string(100, 40).unwrap();
+        assert!(frame.contains("Data Preview · string_col"), "{frame}");
+        let header = frame
+            .lines()
+            .find(|line| line.contains("string_col") && !line.contains("Data Preview"))
+            .unwrap_or_default();
+        assert!(!header.contains("bool_col"), "{frame}");
+
+        press(&mut app, KeyCode::Char('|'));
+        assert!(!app.state.layout().split);
+    }
+
+    #[test]
+    fn test_session_is_restored() {
+        let path = test_data_path("nulls.snappy.parquet");
+        let ctx = ParquetCtx::from_file(&path).unwrap();
+        let mut app = App::new(&ctx);
+        app.select_tab(2);
+        app.state.set_vertical_offset(3);
+        app.state.set_collapsed_groups(BTreeSet::from([5]));
+        let session = app.session();
+        assert_eq!(session.tab, 2);
+
+        let restored = App::new(&ctx).with_session(session.clone());
+        assert_eq!(restored.session(), session);
+
+        // Positions the file no longer has are dropped.
+        let stale = Session {
+            vertical_offset: 5000,
+            collapsed_groups: BTreeSet::from([1, 99]),
+            selected_group: Some(1),
+            ..session
+        };
+        let restored = App::new(&ctx).with_session(stale);
+        assert!(restored.state.collapsed_groups().is_empty());
+        assert_eq!(restored.state.selected_group(), None);
+        assert!(restored.state.vertical_offset() <= ctx.sample_data.total_rows);
+    }
+
+    #[test]
+    fn test_step_filtered_row_group() {
+        let mut state = AppState::new();
+        state.set_row_group(2);
+        let filter: RowGroupFilter = "rows > 0".parse().unwrap();
+        state.set_row_group_filter(filter, vec![1, 4, 7]);
+        assert_eq!(state.row_group(), 4);
+
+        state.step_filtered_row_group(true);
+        assert_eq!(state.row_group(), 7);
+        state.step_filtered_row_group(true);
+        assert_eq!(state.row_group(), 7);
+        state.step_filtered_row_group(false);
+        state.step_filtered_row_group(false);
+        assert_eq!(state.row_group(), 1);
+    }
+
+    #[test]
+    fn test_shrinking_the_max_clamps_the_current_offset() {
+        let mut state = AppState::new();
+        state.set_max_horizontal_offset(10);
+        for _ in 0..10 {
+            state.right();
+        }
+        assert_eq!(state.horizontal_offset(), 10);
+        // e.g. the terminal was widened, so fewer columns need scrolling.
+        state.set_max_horizontal_offset(4);
+        assert_eq!(state.horizontal_offset(), 4);
+    }
+
+    #[test]
+    fn test_sorted_by_shows_in_schema_and_row_groups() {
+        use arrow::array::{Int64Array, RecordBatch};
+        use parquet::file::metadata::SortingColumn;
+        use parquet::file::properties::WriterProperties;
+        use std::sync::Arc;
+
+        let batch = RecordBatch::try_from_iter([(
+            "id",
+            Arc::new(Int64Array::from_iter_values(0..10)) as _,
+        )])
+        .unwrap();
+        let props = WriterProperties::builder()
+            .set_sorting_columns(Some(vec![SortingColumn {
+                column_idx: 0,
+                descending: false,
+                nulls_first: false,
+            }]))
+            .build();
+        let path = write_test_file("sorted-by", &batch, Some(props));
+        let ctx = ParquetCtx::from_file(&path.display().to_string()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut app = App::new(&ctx);
+        app.select_tab(2);
+        let frame = app.render_to_string(140, 20).unwrap();
+        assert!(frame.contains("Sorted by id ↑"), "{frame}");
+        app.select_tab(3);
+        let frame = app.render_to_string(140, 30).unwrap();
+        assert!(frame.contains("Sorted by id ↑"), "{frame}");
+    }
+
+    #[test]
+    fn test_toggle_field_ids() {
+        use arrow::array::{Int64Array, RecordBatch};
+        use arrow::datatypes::{DataType, Field, Schema};
+        use parquet::arrow::PARQUET_FIELD_ID_META_KEY;
+        use std::collections::HashMap;
+        use std::sync::Arc;
+
+        let field = Field::new("id", DataType::Int64, false).with_metadata(HashMap::from([(
+            PARQUET_FIELD_ID_META_KEY.to_string(),
+            "42".to_string(),
+        )]));
+        let batch = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![field])),
+            vec![Arc::new(Int64Array::from_iter_values(0..10))],
+        )
+        .unwrap();
+        let path = write_test_file("toggle-ids", &batch, None);
+        let ctx = ParquetCtx::from_file(&path.display().to_string()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut app = App::new(&ctx);
+        app.select_tab(2);
+        let frame = app.render_to_string(140, 20).unwrap();
+        assert!(!frame.contains("│Field ID"), "{frame}");
+        assert!(!frame.contains("#42"), "{frame}");
+
+        press(&mut app, KeyCode::Char('i'));
+        assert!(app.state.show_field_ids());
+        let frame = app.render_to_string(140, 20).unwrap();
+        assert!(frame.contains("│Field ID"), "{frame}");
+        assert!(frame.contains("└─ id #42"), "{frame}");
+        assert!(app.session().show_field_ids);
+
+        press(&mut app, KeyCode::Char('i'));
+        let frame = app.render_to_string(140, 20).unwrap();
+        assert!(!frame.contains("│Field ID"), "{frame}");
+    }
+
+    #[test]
+    fn test_inspect_images_and_embeddings() {
+        use arrow::array::{BinaryArray, Float32Builder, ListBuilder, RecordBatch};
+        use image::codecs::png::PngEncoder;
+        use image::{ExtendedColorType, ImageEncoder};
+        use std::sync::Arc;
+
+        let mut png = Vec::new();
+        PngEncoder::new(&mut png)
+            .write_image(&[255u8; 40 * 20 * 3], 40, 20, ExtendedColorType::Rgb8)
+            .unwrap();
+        let mut embeddings = ListBuilder::new(Float32Builder::new());
+        embeddings.values().append_slice(&[3.0, 0.0, 4.0]);
+        embeddings.append(true);
+        let batch = RecordBatch::try_from_iter([
+            (
+                "photo",
+                Arc::new(BinaryArray::from(vec![png.as_slice()])) as _,
+            ),
+            ("embedding", Arc::new(embeddings.finish()) as _),
+        ])
+        .unwrap();
+        let path = write_test_file("images", &batch, None);
+        let ctx = ParquetCtx::from_file(&path.display().to_string()).unwrap();
+
+        let mut app = App::new(&ctx);
+        app.select_tab(TabKind::Visualize.index());
+        press(&mut app, KeyCode::Char('v'));
+        let frame = app.render_to_string(120, 40).unwrap();
+        assert!(frame.contains("photo: PNG 40×20"), "{frame}");
+        assert!(frame.contains("▀▀▀▀"), "{frame}");
+        assert!(frame.contains("embedding [3] norm 5.000 ▆▁█"), "{frame}");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_inspect_row_and_extract_json_path() {
+        use arrow::array::{Int32Array, RecordBatch, StringArray};
+        use arrow::datatypes::{DataType, Field, Schema};
+        use std::sync::Arc;
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("payload", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2])),
+                Arc::new(StringArray::from(vec![
+                    Some(r#"{"user":{"id":7,"tags":["a"]}}"#),
+                    Some(r#"{"other":true}"#),
+                ])),
+            ],
+        )
+        .unwrap();
+        let path = write_test_file("json-path", &batch, None);
+        let ctx = ParquetCtx::from_file(&path.display().to_string()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut app = App::new(&ctx);
+        app.select_tab(0);
+        press(&mut app, KeyCode::Char(':'));
+        for c in "json payload $.user.id".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.state.json_columns().len(), 1);
+        let frame = app.render_to_string(160, 30).unwrap();
+        assert!(frame.contains("payload:$.user.id"), "{frame}");
+        assert!(frame.contains("1 of 2 rows have a value"), "{frame}");
+
+        press(&mut app, KeyCode::Char('v'));
+        assert!(app.state.inspecting());
+        let frame = app.render_to_string(160, 40).unwrap();
+        assert!(frame.contains(" Row 1 "), "{frame}");
+        assert!(frame.contains("▾ payload {1}"), "{frame}");
+        assert!(frame.contains("id: 7"), "{frame}");
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.state.inspecting());
+
+        press(&mut app, KeyCode::Char(':'));
+        for c in "json".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert!(app.state.json_columns().is_empty());
+        let frame = app.render_to_string(160, 30).unwrap();
+        assert!(!frame.contains("payload:$.user.id"), "{frame}");
+    }
+
+    #[test]
+    fn test_nested_values_as_trees() {
+        use arrow::array::{
+            ArrayRef, Int32Array, ListArray, RecordBatch, StringArray, StructArray,
+        };
+        use arrow::datatypes::{DataType, Field, Int32Type};
+        use std::sync::Arc;
+
+        let event = StructArray::from(vec![
+            (
+                Arc::new(Field::new("kind", DataType::Utf8, true)),
+                Arc::new(StringArray::from(vec!["click", "view"])) as ArrayRef,
+            ),
+            (
+                Arc::new(Field::new(
+                    "scores",
+                    DataType::new_list(DataType::Int32, true),
+                    true,
+                )),
+                Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+                    Some(vec![Some(1), Some(2), Some(3)]),
+                    None,
+                ])) as ArrayRef,
+            ),
+        ]);
+        let batch = RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef),
+            ("event", Arc::new(event) as ArrayRef),
+        ])
+        .unwrap();
+        let path = write_test_file("nested", &batch, None);
+        let ctx = ParquetCtx::from_file(&path.display().to_string()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut app = App::new(&ctx);
+        app.select_tab(0);
+        let frame = app.render_to_string(160, 30).unwrap();
+        assert!(frame.contains(r#"{"kind": "click", "score…"#), "{frame}");
+
+        // The selected row's struct spreads over several lines.
+        press(&mut app, KeyCode::Char('e'));
+        let frame = app.render_to_string(160, 30).unwrap();
+        assert!(frame.contains(r#""kind": "click","#), "{frame}");
+        assert!(frame.contains(r#""scores": ["#), "{frame}");
+        press(&mut app, KeyCode::Char('e'));
+
+        press(&mut app, KeyCode::Char('v'));
+        let frame = app.render_to_string(160, 40).unwrap();
+        assert!(frame.contains("▾ event {2}"), "{frame}");
+        assert!(frame.contains(r#"kind: "click""#), "{frame}");
+        assert!(frame.contains("▾ scores [3]"), "{frame}");
+        assert!(frame.contains("[2]: 3"), "{frame}");
+
+        // Fold `event.scores`, then `event` from one of its fields.
+        for _ in 0..3 {
+            press(&mut app, KeyCode::Down);
+        }
+        press(&mut app, KeyCode::Left);
+        let frame = app.render_to_string(160, 40).unwrap();
+        assert!(frame.contains("▸ scores: [1, 2, 3]"), "{frame}");
+        press(&mut app, KeyCode::Left);
+        assert_eq!(app.state.inspector_cursor(), 1);
+        press(&mut app, KeyCode::Enter);
+        let frame = app.render_to_string(160, 40).unwrap();
+        assert!(
+            frame.contains(r#"▸ event: {"kind": "click", "scores": [1, 2, 3]}"#),
+            "{frame}"
+        );
+        press(&mut app, KeyCode::Right);
+        let frame = app.render_to_string(160, 40).unwrap();
+        assert!(frame.contains("▾ event {2}"), "{frame}");
+    }
+
+    #[test]
+    fn test_copy_column_snippets() {
+        let ctx = test_ctx();
+        let mut app = App::new(&ctx);
+        app.select_tab(2);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Char('y'));
+        let frame = app.render_to_string(160, 30).unwrap();
+        assert!(
+            frame.contains("copied id  INT32  column index 0"),
+            "{frame}"
+        );
+
+        app.run_command(Command::parse("copy sql").unwrap())
+            .unwrap();
+        let frame = app.render_to_string(160, 30).unwrap();
+        assert!(frame.contains("copied `id` INT"), "{frame}");
+
+        // The Visualize tab copies the column under the cursor.
+        app.select_tab(0);
+        app.state.reset();
+        press(&mut app, KeyCode::Right);
+        app.run_command(Command::parse("copy spark").unwrap())
+            .unwrap();
+        let frame = app.render_to_string(160, 30).unwrap();
+        assert!(
+            frame.contains(r#"copied StructField("bool_col", BooleanType(), True)"#),
+            "{frame}"
+        );
+
+        app.select_tab(1);
+        assert!(app.run_command(Command::Copy(SnippetFormat::Path)).is_err());
     }
 }
@@ -0,0 +1,401 @@
+use clap::ValueEnum;
+
+use crate::config::TabKind;
+use crate::file::bloom::BloomQuery;
+use crate::file::column_filter::ColumnFilter;
+use crate::file::duplicates::DuplicateOptions;
+use crate::file::json_path::JsonColumn;
+use crate::file::profile::ProfileOptions;
+use crate::file::pruning::Predicate;
+use crate::file::row_group_filter::RowGroupFilter;
+use crate::file::sample_data::Sampling;
+use crate::file::search::ValuePattern;
+use crate::file::snippet::SnippetFormat;
+
+/// Commands typed on the `:` command line.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Jump to a 1-based row (Visualize) or row group (Row Groups).
+    Goto(usize),
+    /// Only step through row groups matching the filter; `None` clears it.
+    Filter(Option<RowGroupFilter>),
+    /// Simulate min/max pruning of row groups and pages; `None` clears it.
+    Prune(Option<Predicate>),
+    /// Probe a column's bloom filters with values; `None` clears it.
+    Bloom(Option<BloomQuery>),
+    /// Decode a 1-based page of the selected column chunk through its
+    /// dictionary; `None` closes it.
+    Page(Option<usize>),
+    /// Profile every column from a sample; `None` hides the profile.
+    Profile(Option<ProfileOptions>),
+    /// Extract a JSON path from a preview column into a column of its own;
+    /// `None` drops the extracted columns.
+    Json(Option<JsonColumn>),
+    /// Copy the selected column's path, index or type to the clipboard.
+    Copy(SnippetFormat),
+    /// Choose which rows of the file the Visualize tab shows.
+    Sample(Sampling),
+    /// Estimate how often the key columns repeat, from a sample of rows.
+    Dups(DuplicateOptions),
+    /// Correlate two numeric columns and plot them; without `x`, the
+    /// selected column is plotted against `y`.
+    Corr { x: Option<String>, y: String },
+    /// Check that every STRING column holds valid UTF-8.
+    Utf8,
+    /// Time decoding each column and name the slowest.
+    Bench,
+    /// Estimate codec levels and the savings of a higher one.
+    Codecs { level: Option<u32> },
+    /// Switch to a tab, e.g. as the first step of a preset.
+    Tab(TabKind),
+    /// Select the next preview row with a matching value; `None` clears the
+    /// search.
+    Search(Option<ValuePattern>),
+    /// Scan the rest of the file for the next row with a matching value.
+    SearchFile(ValuePattern),
+    /// Only list the columns whose paths match; `None` lists them all.
+    Columns(Option<ColumnFilter>),
+    /// List the leaf columns largest first, by their share of the file;
+    /// `false` restores the schema order.
+    Sort(bool),
+}
+
+impl Command {
+    pub fn parse(input: &str) -> Result<Command, String> {
+        let mut words = input.split_whitespace();
+        let name = words.next().ok_or_else(|| "empty command".to_string())?;
+        let args: Vec<&str> = words.collect();
+        // Searches and column patterns take the rest of the line as typed,
+        // spaces included.
+        let rest = input.trim_start()[name.len()..].trim();
+        match name {
+            "goto" | "g" => match args.as_slice() {
+                [target] => target
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .map(Command::Goto)
+                    .ok_or_else(|| format!("goto: '{target}' is not a positive number")),
+                _ => Err("usage: goto <number>".to_string()),
+            },
+            "filter" | "f" if args.is_empty() => Ok(Command::Filter(None)),
+            "filter" | "f" => args
+                .join(" ")
+                .parse()
+                .map(|filter| Command::Filter(Some(filter))),
+            "prune" if args.is_empty() => Ok(Command::Prune(None)),
+            "prune" => args
+                .join(" ")
+                .parse()
+                .map(|predicate| Command::Prune(Some(predicate))),
+            "bloom" if args.is_empty() => Ok(Command::Bloom(None)),
+            "bloom" => args
+                .join(" ")
+                .parse()
+                .map(|query| Command::Bloom(Some(query))),
+            "page" => match args.as_slice() {
+                [] => Ok(Command::Page(None)),
+                [page] => page
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .map(|page| Command::Page(Some(page)))
+                    .ok_or_else(|| format!("page: '{page}' is not a positive number")),
+                _ => Err("usage: page [number]".to_string()),
+            },
+            "profile" => match args.as_slice() {
+                [] => Ok(Command::Profile(Some(ProfileOptions::default()))),
+                ["off"] => Ok(Command::Profile(None)),
+                [rows] => rows
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .map(|sample_rows| {
+                        Command::Profile(Some(ProfileOptions {
+                            sample_rows,
+                            ..Default::default()
+                        }))
+                    })
+                    .ok_or_else(|| format!("profile: '{rows}' is not a positive number")),
+                _ => Err("usage: profile [rows | off]".to_string()),
+            },
+            "copy" => match args.as_slice() {
+                [] => Ok(Command::Copy(SnippetFormat::Summary)),
+                [format] => format.parse().map(Command::Copy),
+                _ => Err("usage: copy [path|index|type|sql|spark]".to_string()),
+            },
+            "corr" => match args.as_slice() {
+                [y] => Ok(Command::Corr {
+                    x: None,
+                    y: y.to_string(),
+                }),
+                [x, y] => Ok(Command::Corr {
+                    x: Some(x.to_string()),
+                    y: y.to_string(),
+                }),
+                _ => Err("usage: corr [x] <y>".to_string()),
+            },
+            "dups" => Ok(Command::Dups(DuplicateOptions {
+                key: args
+                    .join(" ")
+                    .split([',', ' '])
+                    .filter(|column| !column.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+                ..Default::default()
+            })),
+            "utf8" if args.is_empty() => Ok(Command::Utf8),
+            "utf8" => Err("usage: utf8".to_string()),
+            "bench" if args.is_empty() => Ok(Command::Bench),
+            "bench" => Err("usage: bench".to_string()),
+            "codecs" => match args.as_slice() {
+                [] => Ok(Command::Codecs { level: None }),
+                [level] => level
+                    .parse()
+                    .map(|level| Command::Codecs { level: Some(level) })
+                    .map_err(|_| format!("invalid level '{level}'")),
+                _ => Err("usage: codecs [level]".to_string()),
+            },
+            "tab" => match args.as_slice() {
+                [tab] => TabKind::from_str(tab, true)
+                    .map(Command::Tab)
+                    .map_err(|_| format!("unknown tab '{tab}'")),
+                _ => Err("usage: tab visualize|metadata|schema|row_groups".to_string()),
+            },
+            "sample" => match args.as_slice() {
+                [sampling] => sampling.parse().map(Command::Sample),
+                _ => Err("usage: sample head|tail|every[:n]|random[:seed]|row_groups".to_string()),
+            },
+            "json" if args.is_empty() => Ok(Command::Json(None)),
+            "json" => args
+                .join(" ")
+                .parse()
+                .map(|column| Command::Json(Some(column))),
+            "sort" => match args.as_slice() {
+                [] => Ok(Command::Sort(false)),
+                ["size"] => Ok(Command::Sort(true)),
+                _ => Err("usage: sort [size]".to_string()),
+            },
+            "columns" | "cols" if rest.is_empty() => Ok(Command::Columns(None)),
+            "columns" | "cols" => rest.parse().map(|filter| Command::Columns(Some(filter))),
+            "search" | "s" if rest.is_empty() => Ok(Command::Search(None)),
+            "search" | "s" => rest.parse().map(|pattern| Command::Search(Some(pattern))),
+            "search!" | "s!" => rest.parse().map(Command::SearchFile),
+            other => Err(format!("unknown command '{other}'")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_goto() {
+        assert_eq!(Command::parse("goto 42"), Ok(Command::Goto(42)));
+        assert_eq!(Command::parse("  g   7 "), Ok(Command::Goto(7)));
+    }
+
+    #[test]
+    fn test_parse_filter() {
+        let Ok(Command::Filter(Some(filter))) = Command::parse("filter rows < 10000") else {
+            panic!("expected a filter");
+        };
+        assert_eq!(filter.to_string(), "rows < 10000");
+        assert_eq!(Command::parse("filter"), Ok(Command::Filter(None)));
+        assert!(Command::parse("filter rows").is_err());
+    }
+
+    #[test]
+    fn test_parse_prune() {
+        let Ok(Command::Prune(Some(predicate))) =
+            Command::parse("prune ts between '2024-01-01' and '2024-01-02'")
+        else {
+            panic!("expected a predicate");
+        };
+        assert_eq!(
+            predicate.to_string(),
+            "ts BETWEEN '2024-01-01' AND '2024-01-02'"
+        );
+        assert_eq!(Command::parse("prune"), Ok(Command::Prune(None)));
+        assert!(Command::parse("prune ts between").is_err());
+    }
+
+    #[test]
+    fn test_parse_bloom() {
+        let Ok(Command::Bloom(Some(query))) = Command::parse("bloom user_id 17, 42") else {
+            panic!("expected a query");
+        };
+        assert_eq!(query.to_string(), "user_id 17, 42");
+        assert_eq!(Command::parse("bloom"), Ok(Command::Bloom(None)));
+        assert!(Command::parse("bloom user_id").is_err());
+    }
+
+    #[test]
+    fn test_parse_page() {
+        assert_eq!(Command::parse("page 3"), Ok(Command::Page(Some(3))));
+        assert_eq!(Command::parse("page"), Ok(Command::Page(None)));
+        assert!(Command::parse("page 0").is_err());
+        assert!(Command::parse("page two").is_err());
+    }
+
+    #[test]
+    fn test_parse_profile() {
+        assert_eq!(
+            Command::parse("profile"),
+            Ok(Command::Profile(Some(ProfileOptions::default())))
+        );
+        let Ok(Command::Profile(Some(options))) = Command::parse("profile 5000") else {
+            panic!("expected options");
+        };
+        assert_eq!(options.sample_rows, 5000);
+        assert_eq!(Command::parse("profile off"), Ok(Command::Profile(None)));
+        assert!(Command::parse("profile 0").is_err());
+        assert!(Command::parse("profile 10 20").is_err());
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let Ok(Command::Json(Some(column))) = Command::parse("json payload $.user.id") else {
+            panic!("expected a json column");
+        };
+        assert_eq!(column.name(), "payload:$.user.id");
+        assert_eq!(Command::parse("json"), Ok(Command::Json(None)));
+        assert!(Command::parse("json payload").is_err());
+        assert!(Command::parse("json payload user.id").is_err());
+    }
+
+    #[test]
+    fn test_parse_corr() {
+        assert_eq!(
+            Command::parse("corr price qty"),
+            Ok(Command::Corr {
+                x: Some("price".to_string()),
+                y: "qty".to_string()
+            })
+        );
+        assert_eq!(
+            Command::parse("corr qty"),
+            Ok(Command::Corr {
+                x: None,
+                y: "qty".to_string()
+            })
+        );
+        assert!(Command::parse("corr").is_err());
+        assert_eq!(Command::parse("utf8"), Ok(Command::Utf8));
+        assert!(Command::parse("utf8 name").is_err());
+        assert_eq!(Command::parse("bench"), Ok(Command::Bench));
+        assert!(Command::parse("bench name").is_err());
+        assert_eq!(
+            Command::parse("codecs 12"),
+            Ok(Command::Codecs { level: Some(12) })
+        );
+        assert_eq!(
+            Command::parse("codecs"),
+            Ok(Command::Codecs { level: None })
+        );
+        assert!(Command::parse("codecs high").is_err());
+        assert!(Command::parse("corr a b c").is_err());
+    }
+
+    #[test]
+    fn test_parse_dups() {
+        assert_eq!(
+            Command::parse("dups"),
+            Ok(Command::Dups(DuplicateOptions::default()))
+        );
+        let Ok(Command::Dups(options)) = Command::parse("dups id, event_date") else {
+            panic!("dups takes columns");
+        };
+        assert_eq!(options.key, ["id", "event_date"]);
+    }
+
+    #[test]
+    fn test_parse_sample() {
+        assert_eq!(
+            Command::parse("sample tail"),
+            Ok(Command::Sample(Sampling::Tail))
+        );
+        assert_eq!(
+            Command::parse("sample every:1000"),
+            Ok(Command::Sample(Sampling::Every(1000)))
+        );
+        assert_eq!(
+            Command::parse("sample random:42"),
+            Ok(Command::Sample(Sampling::Random(42)))
+        );
+        assert!(Command::parse("sample").is_err());
+        assert!(Command::parse("sample random:x").is_err());
+        assert!(Command::parse("sample middle").is_err());
+    }
+
+    #[test]
+    fn test_parse_copy() {
+        assert_eq!(
+            Command::parse("copy"),
+            Ok(Command::Copy(SnippetFormat::Summary))
+        );
+        assert_eq!(
+            Command::parse("copy spark"),
+            Ok(Command::Copy(SnippetFormat::Spark))
+        );
+        assert!(Command::parse("copy yaml").is_err());
+        assert!(Command::parse("copy sql spark").is_err());
+    }
+
+    #[test]
+    fn test_parse_tab() {
+        assert_eq!(
+            Command::parse("tab row_groups"),
+            Ok(Command::Tab(TabKind::RowGroups))
+        );
+        assert_eq!(
+            Command::parse("tab Schema"),
+            Ok(Command::Tab(TabKind::Schema))
+        );
+        assert!(Command::parse("tab").is_err());
+        assert!(Command::parse("tab pages").is_err());
+    }
+
+    #[test]
+    fn test_parse_search() {
+        let Ok(Command::Search(Some(pattern))) = Command::parse("search  New  York ") else {
+            panic!("expected a pattern");
+        };
+        assert_eq!(pattern.to_string(), "New  York");
+        assert_eq!(Command::parse("search"), Ok(Command::Search(None)));
+        let Ok(Command::SearchFile(pattern)) = Command::parse("s! /^id-[0-9]+$/") else {
+            panic!("expected a pattern");
+        };
+        assert!(pattern.is_match("id-42"));
+        assert!(Command::parse("search /[/").is_err());
+        assert!(Command::parse("search!").is_err());
+    }
+
+    #[test]
+    fn test_parse_columns() {
+        let Ok(Command::Columns(Some(filter))) = Command::parse(r"cols ^metrics\..*_p99$") else {
+            panic!("expected a column filter");
+        };
+        assert!(filter.matches("metrics.latency_p99"));
+        assert_eq!(Command::parse("columns"), Ok(Command::Columns(None)));
+        assert!(Command::parse("columns ^(").is_err());
+    }
+
+    #[test]
+    fn test_parse_sort() {
+        assert_eq!(Command::parse("sort size"), Ok(Command::Sort(true)));
+        assert_eq!(Command::parse("sort"), Ok(Command::Sort(false)));
+        assert!(Command::parse("sort name").is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Command::parse("").is_err());
+        assert!(Command::parse("goto").is_err());
+        assert!(Command::parse("goto 0").is_err());
+        assert!(Command::parse("goto -3").is_err());
+        assert!(Command::parse("goto 1 2").is_err());
+        assert!(Command::parse("jump 4").is_err());
+    }
+}
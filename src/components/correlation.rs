@@ -0,0 +1,166 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::Style,
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Clear, Dataset, GraphType, Paragraph, Widget},
+};
+
+use crate::file::correlation::Correlation;
+use crate::file::utils::commas;
+use crate::theme::Theme;
+
+/// Modal with a scatter plot of two sampled columns and their correlation.
+pub struct CorrelationChart<'a> {
+    correlation: &'a Correlation,
+    close_key: String,
+    theme: Theme,
+}
+
+impl<'a> CorrelationChart<'a> {
+    pub fn new(correlation: &'a Correlation) -> Self {
+        Self {
+            correlation,
+            close_key: "Esc".to_string(),
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_close_key(mut self, key: String) -> Self {
+        self.close_key = key;
+        self
+    }
+
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self
+    }
+
+    fn stats_line(&self) -> Line<'static> {
+        let coefficient = |value: Option<f64>| value.map_or("-".to_string(), |r| format!("{r:.3}"));
+        let muted = Style::default().fg(self.theme.muted);
+        let text = Style::default().fg(self.theme.text);
+        Line::from(vec![
+            Span::styled("n = ", muted),
+            Span::styled(commas(self.correlation.points.len() as u64), text),
+            Span::styled(
+                format!(" of {} rows", commas(self.correlation.sampled_rows)),
+                muted,
+            ),
+            Span::styled("  Pearson r = ", muted),
+            Span::styled(coefficient(self.correlation.pearson), text),
+            Span::styled("  Spearman ρ = ", muted),
+            Span::styled(coefficient(self.correlation.spearman), text),
+        ])
+    }
+}
+
+/// Axis bounds around `[min, max]`, widened when all values are equal.
+fn padded([min, max]: [f64; 2]) -> [f64; 2] {
+    if min == max {
+        [min - 1.0, max + 1.0]
+    } else {
+        [min, max]
+    }
+}
+
+/// Labels at both ends and the middle of an axis.
+fn axis_labels([min, max]: [f64; 2]) -> Vec<Span<'static>> {
+    [min, (min + max) / 2.0, max]
+        .into_iter()
+        .map(|value| Span::raw(format_tick(value)))
+        .collect()
+}
+
+fn format_tick(value: f64) -> String {
+    let magnitude = value.abs();
+    if magnitude >= 1e9 {
+        format!("{:.1}G", value / 1e9)
+    } else if magnitude >= 1e6 {
+        format!("{:.1}M", value / 1e6)
+    } else if magnitude >= 1e4 {
+        format!("{:.1}K", value / 1e3)
+    } else if value.fract() == 0.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.2}")
+    }
+}
+
+impl<'a> Widget for CorrelationChart<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [popup] = Layout::horizontal([Constraint::Percentage(80)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [popup] = Layout::vertical([Constraint::Percentage(80)])
+            .flex(Flex::Center)
+            .areas(popup);
+
+        Clear.render(popup, buf);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.accent))
+            .title(
+                Line::from(format!(
+                    " {} vs {} ",
+                    self.correlation.x, self.correlation.y
+                ))
+                .style(self.theme.header_style()),
+            )
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {}", self.close_key),
+                        Style::default().fg(self.theme.good),
+                    ),
+                    Span::styled(" close ", Style::default().fg(self.theme.muted)),
+                ])
+                .right_aligned(),
+            );
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+
+        let [stats_area, chart_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]).areas(inner);
+        Paragraph::new(self.stats_line()).render(stats_area, buf);
+
+        let Some((x_bounds, y_bounds)) = self.correlation.bounds() else {
+            Paragraph::new(Line::styled(
+                "no rows with both values",
+                Style::default().fg(self.theme.muted),
+            ))
+            .render(chart_area, buf);
+            return;
+        };
+        let (x_bounds, y_bounds) = (padded(x_bounds), padded(y_bounds));
+        let dataset = Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Scatter)
+            .style(Style::default().fg(self.theme.accent))
+            .data(&self.correlation.points);
+        let axis_style = Style::default().fg(self.theme.text);
+        Chart::new(vec![dataset])
+            .x_axis(
+                Axis::default()
+                    .title(Span::styled(
+                        self.correlation.x.clone(),
+                        Style::default().fg(self.theme.label),
+                    ))
+                    .style(axis_style)
+                    .bounds(x_bounds)
+                    .labels(axis_labels(x_bounds)),
+            )
+            .y_axis(
+                Axis::default()
+                    .title(Span::styled(
+                        self.correlation.y.clone(),
+                        Style::default().fg(self.theme.label),
+                    ))
+                    .style(axis_style)
+                    .bounds(y_bounds)
+                    .labels(axis_labels(y_bounds)),
+            )
+            .render(chart_area, buf);
+    }
+}
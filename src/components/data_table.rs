@@ -1,4 +1,7 @@
+use crate::file::json_path::cell_json;
 use crate::file::sample_data::ParquetSampleData;
+use crate::file::search::ValuePattern;
+use crate::file::utils::{display_width, pad_str, truncate_str, wrap_str};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -9,10 +12,14 @@ use ratatui::{
     widgets::Widget,
 };
 use std::cmp::min;
+use std::collections::BTreeSet;
 
 use crate::file::Renderable;
+use crate::theme::Theme;
 
 const NUM_SPACES_BETWEEN_COLUMNS: u16 = 2;
+/// Widest a column gets before its values are cut off with `…`.
+pub const DEFAULT_MAX_COLUMN_WIDTH: u16 = 25;
 const NUM_SPACES_AFTER_LINE_NUMBER: u16 = 2;
 
 /// Number of columns starting at `start` whose widths fit in `available_width`
@@ -54,8 +61,18 @@ pub struct DataTable<'a> {
     pub horizontal_scroll: usize,
     pub vertical_scroll: usize,
     pub selected_row: Option<usize>,
+    /// Show the lists, structs, maps and JSON of the selected row indented
+    /// over several lines.
+    pub expanded_row: bool,
+    /// Positions in the file of the rows whose values are wrapped to the
+    /// column width over several lines.
+    pub wrapped_rows: Option<&'a BTreeSet<usize>>,
     pub selected_color: Color,
     pub border_color: Color,
+    pub max_column_width: u16,
+    /// Matches of the active search, highlighted in the cells.
+    pub search: Option<&'a ValuePattern>,
+    pub theme: Theme,
 }
 
 impl<'a> DataTable<'a> {
@@ -68,11 +85,24 @@ impl<'a> DataTable<'a> {
             horizontal_scroll: 0,
             vertical_scroll: 0,
             selected_row: None,
+            expanded_row: false,
+            wrapped_rows: None,
             selected_color: Color::Rgb(60, 60, 60),
             border_color: Color::DarkGray,
+            max_column_width: DEFAULT_MAX_COLUMN_WIDTH,
+            search: None,
+            theme: Theme::default(),
         }
     }
 
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.title_color = theme.accent;
+        self.selected_color = theme.highlight_bg;
+        self.border_color = theme.border;
+        self.theme = *theme;
+        self
+    }
+
     pub fn with_title(mut self, title: String) -> Self {
         self.title = title;
         self
@@ -99,11 +129,31 @@ impl<'a> DataTable<'a> {
         self
     }
 
+    pub fn with_max_column_width(mut self, width: u16) -> Self {
+        self.max_column_width = width;
+        self
+    }
+
     pub fn with_selected_row(mut self, row: Option<usize>) -> Self {
         self.selected_row = row;
         self
     }
 
+    pub fn with_expanded_row(mut self, expanded: bool) -> Self {
+        self.expanded_row = expanded;
+        self
+    }
+
+    pub fn with_wrapped_rows(mut self, rows: &'a BTreeSet<usize>) -> Self {
+        self.wrapped_rows = Some(rows);
+        self
+    }
+
+    pub fn with_search(mut self, search: Option<&'a ValuePattern>) -> Self {
+        self.search = search;
+        self
+    }
+
     pub fn scroll_left(&mut self) {
         if self.horizontal_scroll > 0 {
             self.horizontal_scroll -= 1;
@@ -137,11 +187,35 @@ impl<'a> DataTable<'a> {
     /// Maximum horizontal scroll offset for a render area `area_width` wide,
     /// sized from actual column widths so the last column is always reachable.
     pub fn max_horizontal_scroll(&self, area_width: u16) -> usize {
-        let max_row_num = self.data.rows.len().saturating_sub(self.vertical_scroll);
+        max_scroll_from_widths(&self.all_column_widths(), self.columns_width(area_width))
+    }
+
+    /// Number of columns drawn in a render area `area_width` wide at the
+    /// current horizontal scroll, i.e. one screenful of columns.
+    pub fn visible_columns(&self, area_width: u16) -> usize {
+        let widths = self.all_column_widths();
+        let available_width = self.columns_width(area_width);
+        let scroll = self
+            .horizontal_scroll
+            .min(max_scroll_from_widths(&widths, available_width));
+        columns_fitting(&widths, scroll, available_width)
+    }
+
+    /// Number of the last row loaded, which sets the width of the row
+    /// numbers.
+    fn max_row_num(&self) -> usize {
+        match self.data.rows.len() {
+            0 => self.data.first_row,
+            len => self.data.position(len - 1) + 1,
+        }
+    }
+
+    /// Width left for the data columns next to the row numbers.
+    fn columns_width(&self, area_width: u16) -> u16 {
+        let max_row_num = self.max_row_num();
         let row_num_section_width =
             (format!("{max_row_num}").len().max(4) as u16) + 2 * NUM_SPACES_AFTER_LINE_NUMBER + 1;
-        let available_width = area_width.saturating_sub(row_num_section_width);
-        max_scroll_from_widths(&self.all_column_widths(), available_width)
+        area_width.saturating_sub(row_num_section_width)
     }
 
     fn calculate_column_widths(
@@ -152,17 +226,21 @@ impl<'a> DataTable<'a> {
         let mut widths = Vec::new();
 
         for (col_idx, header) in headers.iter().enumerate() {
-            let mut max_width = header.len();
+            let mut max_width = display_width(header);
 
             // Check content width for this column
             for row in visible_rows {
                 if let Some(cell) = row.get(col_idx) {
-                    max_width = max_width.max(cell.len());
+                    max_width = max_width.max(display_width(cell));
                 }
             }
 
-            // Use minimum width of 8 and maximum of 25 for readability, add spacing
-            widths.push((min(max_width.max(8), 25) as u16) + NUM_SPACES_BETWEEN_COLUMNS);
+            // Use minimum width of 8 and maximum of `max_column_width` for
+            // readability, add spacing
+            widths.push(
+                min(max_width.max(8), self.max_column_width as usize) as u16
+                    + NUM_SPACES_BETWEEN_COLUMNS,
+            );
         }
 
         widths
@@ -172,7 +250,7 @@ impl<'a> DataTable<'a> {
         let border_style = ratatui::style::Style::default().fg(self.border_color);
 
         // Draw horizontal line
-        for x in 0..area.width {
+        for x in area.x..area.right() {
             if let Some(cell) = buf.cell_mut(Position::new(x, y - 1)) {
                 cell.set_symbol(line::HORIZONTAL).set_style(border_style);
             }
@@ -185,26 +263,75 @@ impl<'a> DataTable<'a> {
         }
     }
 
-    fn render_row_numbers(&self, buf: &mut Buffer, area: Rect, rows: &[Vec<String>]) {
+    /// Lines of each cell of the expanded row: nested and JSON values
+    /// pretty-printed, anything else as it is.
+    fn expanded_cells(row: &[String]) -> Vec<Vec<String>> {
+        row.iter()
+            .map(|cell| {
+                match cell_json(cell).and_then(|json| serde_json::to_string_pretty(&json).ok()) {
+                    Some(pretty) => pretty.lines().map(str::to_string).collect(),
+                    None => vec![cell.clone()],
+                }
+            })
+            .collect()
+    }
+
+    /// Lines of each cell of row `row` when it takes several: the expanded
+    /// row, and wrapped rows cut to `column_widths`. `None` for a row on one
+    /// line.
+    fn row_lines(
+        &self,
+        row: usize,
+        cells: &[String],
+        column_widths: &[u16],
+    ) -> Option<Vec<Vec<String>>> {
+        let expanded = self.expanded_row && self.selected_row == Some(row);
+        let wrapped = self
+            .wrapped_rows
+            .is_some_and(|rows| rows.contains(&self.data.position(row)));
+        if !expanded && !wrapped {
+            return None;
+        }
+        let mut lines = if expanded {
+            Self::expanded_cells(cells)
+        } else {
+            cells.iter().map(|cell| vec![cell.clone()]).collect()
+        };
+        if wrapped {
+            for (cell, &width) in lines.iter_mut().zip(column_widths) {
+                let width = width.saturating_sub(NUM_SPACES_BETWEEN_COLUMNS) as usize;
+                *cell = cell.iter().flat_map(|line| wrap_str(line, width)).collect();
+            }
+        }
+        Some(lines)
+    }
+
+    fn render_row_numbers(
+        &self,
+        buf: &mut Buffer,
+        area: Rect,
+        rows: &[Vec<String>],
+        heights: &[u16],
+    ) {
         let mut y = area.y;
 
-        for (row_idx, _) in rows.iter().enumerate() {
-            let actual_row_num = row_idx + self.vertical_scroll + 1;
+        for ((row_idx, _), height) in rows.iter().enumerate().zip(heights) {
+            let actual_row_num = self.data.position(row_idx + self.vertical_scroll) + 1;
             let is_selected = self
                 .selected_row
                 .is_some_and(|selected| row_idx + self.vertical_scroll == selected);
 
             let row_num_formatted = format!("{}", actual_row_num);
             let mut style: ratatui::prelude::Style =
-                ratatui::style::Style::default().fg(Color::DarkGray);
+                ratatui::style::Style::default().fg(self.theme.muted);
             if is_selected {
                 style = style
                     .add_modifier(Modifier::BOLD)
                     .add_modifier(Modifier::UNDERLINED);
             }
             let span = Span::styled(row_num_formatted, style);
-            buf.set_span(0, y, &span, area.width);
-            y += 1;
+            buf.set_span(area.x, y, &span, area.width);
+            y += height;
             if y >= area.bottom() {
                 break;
             }
@@ -228,19 +355,9 @@ impl<'a> DataTable<'a> {
             }
 
             let effective_width = width.saturating_sub(NUM_SPACES_BETWEEN_COLUMNS);
-            let truncated = if header.len() > effective_width as usize {
-                format!(
-                    "{}...",
-                    &header[..effective_width.saturating_sub(3) as usize]
-                )
-            } else {
-                header.clone()
-            };
+            let truncated = truncate_str(header, effective_width as usize);
 
-            let style = ratatui::style::Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD);
-            let span = Span::styled(truncated, style);
+            let span = Span::styled(truncated, self.theme.header_style());
 
             buf.set_span(x_offset, y, &span, width);
             x_offset += width;
@@ -263,7 +380,7 @@ impl<'a> DataTable<'a> {
         let style = if is_selected {
             ratatui::style::Style::default()
                 .bg(self.selected_color)
-                .fg(Color::White)
+                .fg(self.theme.text)
                 .add_modifier(Modifier::BOLD)
         } else {
             ratatui::style::Style::default()
@@ -275,25 +392,51 @@ impl<'a> DataTable<'a> {
             }
 
             let effective_width = width.saturating_sub(NUM_SPACES_BETWEEN_COLUMNS);
-            let truncated = if cell_data.chars().count() > effective_width as usize {
-                let truncated_chars: String = cell_data
-                    .chars()
-                    .take(effective_width.saturating_sub(1) as usize)
-                    .collect();
-                format!("{}…", truncated_chars)
+            let truncated = truncate_str(cell_data, effective_width as usize);
+            // Columns of the value itself, without the `…` of a cut one.
+            let shown_columns = if display_width(cell_data) > effective_width as usize {
+                display_width(&truncated).saturating_sub(1)
             } else {
-                cell_data.clone()
-            };
+                display_width(&truncated)
+            } as u16;
 
             // Pad with spaces to fill the column width
-            let padded = format!("{:width$}", truncated, width = width as usize);
+            let padded = pad_str(&truncated, width as usize);
             let span = Span::styled(padded, style);
 
             buf.set_span(x_offset, y, &span, width);
+            if let Some(search) = self.search {
+                self.highlight_matches(buf, x_offset, y, cell_data, search, shown_columns);
+            }
             x_offset += width;
         }
     }
 
+    /// Restyle the characters of `cell_data` that `search` matches, up to
+    /// the `shown_columns` drawn from `x`.
+    fn highlight_matches(
+        &self,
+        buf: &mut Buffer,
+        x: u16,
+        y: u16,
+        cell_data: &str,
+        search: &ValuePattern,
+        shown_columns: u16,
+    ) {
+        let style = ratatui::style::Style::default()
+            .fg(self.theme.selection_fg)
+            .bg(self.theme.selection_bg);
+        for range in search.ranges(cell_data) {
+            let start = display_width(&cell_data[..range.start]) as u16;
+            let end = (display_width(&cell_data[..range.end]) as u16).min(shown_columns);
+            for column in start..end {
+                if let Some(cell) = buf.cell_mut(Position::new(x + column, y)) {
+                    cell.set_style(style);
+                }
+            }
+        }
+    }
+
     fn render_row_number_separator(
         &self,
         buf: &mut Buffer,
@@ -313,56 +456,95 @@ impl<'a> DataTable<'a> {
 }
 
 impl<'a> Widget for DataTable<'a> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
+    fn render(mut self, area: Rect, buf: &mut Buffer) {
         if area.area() == 0 {
             return;
         }
 
         // Calculate row number section width
-        let max_row_num = self.data.rows.len().saturating_sub(self.vertical_scroll);
+        let max_row_num = self.max_row_num();
         let max_row_num_length = format!("{}", max_row_num).len().max(4) as u16;
         let row_num_section_width = max_row_num_length + 2 * NUM_SPACES_AFTER_LINE_NUMBER + 1;
-        let x_row_separator = max_row_num_length + NUM_SPACES_AFTER_LINE_NUMBER + 1;
+        let x_row_separator = area.x + max_row_num_length + NUM_SPACES_AFTER_LINE_NUMBER + 1;
+        let x_columns = area.x + row_num_section_width;
 
         // Calculate available width for data columns
         let available_width = area.width.saturating_sub(row_num_section_width);
-
-        // Clamp scroll to the actual end (last column flush right), then show as
-        // many columns as truly fit from there. Sizing by real column widths
-        // (not a flat estimate) keeps wide columns from pushing the last ones
-        // off-screen / out of reach.
-        let all_widths = self.all_column_widths();
-        let max_scroll = max_scroll_from_widths(&all_widths, available_width);
-        let horizontal_scroll = self.horizontal_scroll.min(max_scroll);
-        let max_visible_columns = columns_fitting(&all_widths, horizontal_scroll, available_width);
-
-        // Get visible columns
-        let visible_headers: Vec<String> = self
-            .data
-            .flattened_columns
-            .iter()
-            .skip(horizontal_scroll)
-            .take(max_visible_columns)
-            .cloned()
-            .collect();
-
-        // Get visible data for each row (apply vertical scroll)
-        let visible_rows: Vec<Vec<String>> = self
-            .data
-            .rows
-            .iter()
-            .skip(self.vertical_scroll)
-            .map(|row| {
-                row.iter()
-                    .skip(horizontal_scroll)
-                    .take(max_visible_columns)
-                    .cloned()
-                    .collect()
-            })
-            .collect();
-
-        // Calculate column widths
-        let column_widths = self.calculate_column_widths(&visible_headers, &visible_rows);
+        let rows_height = area.height.saturating_sub(2) as usize;
+
+        // Lay the rows out from the vertical scroll. When the rows taking
+        // several lines push the selected one out of view, scroll down far
+        // enough for all of its lines to show and lay them out again.
+        let (visible_headers, visible_rows, column_widths, lines, heights) = loop {
+            // Clamp scroll to the actual end (last column flush right), then
+            // show as many columns as truly fit from there. Sizing by real
+            // column widths (not a flat estimate) keeps wide columns from
+            // pushing the last ones off-screen / out of reach.
+            let all_widths = self.all_column_widths();
+            let max_scroll = max_scroll_from_widths(&all_widths, available_width);
+            let horizontal_scroll = self.horizontal_scroll.min(max_scroll);
+            let max_visible_columns =
+                columns_fitting(&all_widths, horizontal_scroll, available_width);
+
+            // Get visible columns and the data of each row (apply vertical
+            // scroll)
+            let visible_rows: Vec<Vec<String>> = self
+                .data
+                .rows
+                .iter()
+                .skip(self.vertical_scroll)
+                .map(|row| {
+                    row.iter()
+                        .skip(horizontal_scroll)
+                        .take(max_visible_columns)
+                        .cloned()
+                        .collect()
+                })
+                .collect();
+            let visible_headers: Vec<String> = self
+                .data
+                .flattened_columns
+                .iter()
+                .skip(horizontal_scroll)
+                .take(max_visible_columns)
+                .cloned()
+                .collect();
+            let column_widths = self.calculate_column_widths(&visible_headers, &visible_rows);
+
+            let lines: Vec<Option<Vec<Vec<String>>>> = visible_rows
+                .iter()
+                .enumerate()
+                .map(|(idx, row)| self.row_lines(idx + self.vertical_scroll, row, &column_widths))
+                .collect();
+            let heights: Vec<usize> = lines
+                .iter()
+                .map(|cells| match cells {
+                    Some(cells) => {
+                        let height = cells.iter().map(Vec::len).max().unwrap_or(1);
+                        height.clamp(1, rows_height.max(1))
+                    }
+                    None => 1,
+                })
+                .collect();
+            let scroll_down = self
+                .selected_row
+                .and_then(|selected| selected.checked_sub(self.vertical_scroll))
+                .filter(|&selected| selected < heights.len())
+                .map_or(0, |selected| {
+                    let mut bottom: usize = heights[..=selected].iter().sum();
+                    let mut rows = 0;
+                    while bottom > rows_height && rows < selected {
+                        bottom -= heights[rows];
+                        rows += 1;
+                    }
+                    rows
+                });
+            if scroll_down == 0 {
+                let heights: Vec<u16> = heights.into_iter().map(|h| h as u16).collect();
+                break (visible_headers, visible_rows, column_widths, lines, heights);
+            }
+            self.vertical_scroll += scroll_down;
+        };
 
         // Header area: 2 lines (header text + separator)
         let header_height = 2;
@@ -378,25 +560,24 @@ impl<'a> Widget for DataTable<'a> {
         );
 
         // Render row numbers
-        self.render_row_numbers(buf, rows_area, &visible_rows);
+        self.render_row_numbers(buf, rows_area, &visible_rows, &heights);
 
         // Render header
         self.render_header(
             buf,
-            row_num_section_width,
+            x_columns,
             y_header,
             &visible_headers,
             &column_widths,
-            area.width,
+            area.right(),
         );
 
         // Render header separator (horizontal line below headers)
         self.render_header_separator(buf, area, x_row_separator, y_first_record);
 
         // Render data rows
-        for (y_offset, (row_idx, row_data)) in
-            (y_first_record..).zip(visible_rows.iter().enumerate())
-        {
+        let mut y_offset = y_first_record;
+        for (row_idx, (row_data, cells)) in visible_rows.iter().zip(&lines).enumerate() {
             if y_offset >= rows_area.bottom() {
                 break;
             }
@@ -405,15 +586,41 @@ impl<'a> Widget for DataTable<'a> {
                 .selected_row
                 .is_some_and(|selected| actual_row_num == selected);
 
-            self.render_data_row(
-                buf,
-                row_num_section_width,
-                y_offset,
-                row_data,
-                &column_widths,
-                is_selected,
-                area.width,
-            );
+            match cells {
+                Some(cells) => {
+                    for line in 0..heights[row_idx] as usize {
+                        if y_offset >= rows_area.bottom() {
+                            break;
+                        }
+                        let line_data: Vec<String> = cells
+                            .iter()
+                            .map(|cell| cell.get(line).cloned().unwrap_or_default())
+                            .collect();
+                        self.render_data_row(
+                            buf,
+                            x_columns,
+                            y_offset,
+                            &line_data,
+                            &column_widths,
+                            is_selected,
+                            area.right(),
+                        );
+                        y_offset += 1;
+                    }
+                }
+                None => {
+                    self.render_data_row(
+                        buf,
+                        x_columns,
+                        y_offset,
+                        row_data,
+                        &column_widths,
+                        is_selected,
+                        area.right(),
+                    );
+                    y_offset += 1;
+                }
+            }
         }
 
         // Render vertical separator after row numbers
@@ -473,4 +680,71 @@ mod tests {
         // Only the last (over-wide) column fits from the right.
         assert_eq!(max_scroll_from_widths(&[10, 10, 50], 30), 2);
     }
+
+    #[test]
+    fn test_search_highlight_stops_before_the_ellipsis() {
+        // The cut value takes more bytes than the whole one, as `…` is three.
+        let data = ParquetSampleData {
+            flattened_columns: vec!["text".to_string()],
+            rows: vec![vec!["abcdefghijk".to_string()]],
+            total_columns: 1,
+            total_rows: 1,
+            first_row: 0,
+            positions: None,
+        };
+        let search: ValuePattern = "abcdefghijk".parse().unwrap();
+        let area = Rect::new(0, 0, 40, 4);
+        let mut buf = Buffer::empty(area);
+        DataTable::new(&data)
+            .with_max_column_width(10)
+            .with_search(Some(&search))
+            .render(area, &mut buf);
+        let y = 2;
+        let ellipsis = (0..area.width)
+            .find(|&x| buf[(x, y)].symbol() == "…")
+            .unwrap();
+        let highlighted = |x: u16| buf[(x, y)].bg == Theme::default().selection_bg;
+        assert!(highlighted(ellipsis - 1));
+        assert!(!highlighted(ellipsis));
+    }
+
+    #[test]
+    fn test_wrapped_row_takes_several_lines() {
+        let data = ParquetSampleData {
+            flattened_columns: vec!["id".to_string(), "text".to_string()],
+            rows: vec![
+                vec!["1".to_string(), "abcdefghijklmnopqrst".to_string()],
+                vec!["2".to_string(), "short".to_string()],
+            ],
+            total_columns: 2,
+            total_rows: 2,
+            first_row: 0,
+            positions: None,
+        };
+        let render = |wrapped: &BTreeSet<usize>| {
+            let area = Rect::new(0, 0, 40, 8);
+            let mut buf = Buffer::empty(area);
+            DataTable::new(&data)
+                .with_max_column_width(10)
+                .with_wrapped_rows(wrapped)
+                .render(area, &mut buf);
+            (0..area.height)
+                .map(|y| {
+                    (0..area.width)
+                        .map(|x| buf[(x, y)].symbol())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let lines = render(&BTreeSet::new());
+        assert!(lines[2].contains("abcdefghi…"), "{lines:?}");
+        assert!(lines[3].contains("short"), "{lines:?}");
+
+        let lines = render(&BTreeSet::from([0]));
+        assert!(lines[2].contains("abcdefghij"), "{lines:?}");
+        assert!(lines[3].contains("klmnopqrst"), "{lines:?}");
+        assert!(lines[4].contains("short"), "{lines:?}");
+        assert!(lines[4].contains('2'), "{lines:?}");
+    }
 }
@@ -0,0 +1,56 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Stylize,
+    symbols::border,
+    text::Line,
+    widgets::{Block, Paragraph, Widget, Wrap},
+};
+
+use crate::theme::Theme;
+
+/// Shown in place of a section of the file that could not be read, so the
+/// rest of the app stays usable.
+pub struct ErrorPanel<'a> {
+    pub title: &'a str,
+    pub message: &'a str,
+    pub theme: Theme,
+}
+
+impl<'a> ErrorPanel<'a> {
+    pub fn new(title: &'a str, message: &'a str) -> Self {
+        Self {
+            title,
+            message,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self
+    }
+}
+
+impl<'a> Widget for ErrorPanel<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .title(
+                Line::from(format!(" {} ", self.title))
+                    .centered()
+                    .bold()
+                    .fg(self.theme.bad),
+            )
+            .border_set(border::ROUNDED)
+            .border_style(self.theme.bad);
+
+        Paragraph::new(vec![
+            Line::from(self.message.to_string()).fg(self.theme.text),
+            Line::default(),
+            Line::from("The rest of the file can still be browsed.").fg(self.theme.muted),
+        ])
+        .wrap(Wrap { trim: true })
+        .block(block)
+        .render(area, buf);
+    }
+}
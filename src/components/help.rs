@@ -0,0 +1,263 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
+};
+
+use crate::keymap::{Action, Keymap};
+use crate::tabs::TabManager;
+use crate::theme::Theme;
+
+const GLOBAL_ACTIONS: [Action; 10] = [
+    Action::NextTab,
+    Action::PrevTab,
+    Action::JumpBack,
+    Action::JumpForward,
+    Action::RecordMacro,
+    Action::PlayMacro,
+    Action::Command,
+    Action::Reset,
+    Action::Help,
+    Action::Quit,
+];
+
+const GLOSSARY: [(&str, &str); 8] = [
+    (
+        "Row group",
+        "Horizontal slice of the file; readers can skip whole row groups.",
+    ),
+    (
+        "Column chunk",
+        "The data of one column inside one row group.",
+    ),
+    (
+        "Page",
+        "Unit of encoding and compression inside a column chunk.",
+    ),
+    (
+        "Dictionary",
+        "Page of distinct values; data pages then store small indices into it.",
+    ),
+    (
+        "Encoding",
+        "How values are laid out before compression (PLAIN, RLE, DELTA_*, ...).",
+    ),
+    (
+        "Compression",
+        "Codec applied to each page (SNAPPY, ZSTD, GZIP, ...).",
+    ),
+    (
+        "Statistics",
+        "Per-chunk min / max / null count, used to skip data when filtering.",
+    ),
+    (
+        "Rep / Def level",
+        "Max repetition / definition level: how deeply a column is nested in lists / optionals.",
+    ),
+];
+
+const KEY_COLUMN_WIDTH: usize = 14;
+
+/// Modal listing the effective keybindings of every tab, a short Parquet
+/// glossary and the version.
+pub struct HelpOverlay<'a> {
+    keymap: &'a Keymap,
+    tabs: &'a TabManager,
+    scroll: usize,
+    theme: Theme,
+}
+
+impl<'a> HelpOverlay<'a> {
+    pub fn new(keymap: &'a Keymap, tabs: &'a TabManager) -> Self {
+        Self {
+            keymap,
+            tabs,
+            scroll: 0,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_scroll(mut self, scroll: usize) -> Self {
+        self.scroll = scroll;
+        self
+    }
+
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self
+    }
+
+    /// Number of lines of content, used to bound scrolling.
+    pub fn line_count(&self) -> usize {
+        self.lines().len()
+    }
+
+    fn section_title(&self, title: String) -> Line<'static> {
+        Line::from(Span::styled(title, self.theme.header_style()))
+    }
+
+    fn binding_line(&self, keymap: &Keymap, action: Action, description: &str) -> Line<'static> {
+        Line::from(vec![
+            Span::styled(
+                format!(
+                    "  {:<width$}",
+                    keymap.describe(action),
+                    width = KEY_COLUMN_WIDTH
+                ),
+                Style::default().fg(self.theme.label),
+            ),
+            Span::styled(
+                description.to_string(),
+                Style::default().fg(self.theme.text),
+            ),
+        ])
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(
+                    "parqeye ",
+                    Style::default()
+                        .fg(self.theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("v{}", env!("CARGO_PKG_VERSION")),
+                    Style::default().fg(self.theme.muted),
+                ),
+            ]),
+            Line::default(),
+            self.section_title("Global".to_string()),
+        ];
+        lines.extend(
+            GLOBAL_ACTIONS
+                .iter()
+                .map(|action| self.binding_line(self.keymap, *action, action.description())),
+        );
+
+        for (idx, tab) in self.tabs.tabs.iter().enumerate() {
+            let bindings = tab.help();
+            if bindings.is_empty() {
+                continue;
+            }
+            let mut title = tab.to_string();
+            if idx == self.tabs.active_tab {
+                title.push_str(" (current)");
+            }
+            lines.push(Line::default());
+            lines.push(self.section_title(title));
+            let keymap = self.keymap.in_tab(idx);
+            lines.extend(
+                bindings
+                    .iter()
+                    .map(|(action, description)| self.binding_line(keymap, *action, description)),
+            );
+        }
+
+        lines.push(Line::default());
+        lines.push(self.section_title("Glossary".to_string()));
+        for (term, definition) in GLOSSARY {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {term:<width$}", width = KEY_COLUMN_WIDTH + 2),
+                    Style::default().fg(self.theme.label),
+                ),
+                Span::styled(definition, Style::default().fg(self.theme.text)),
+            ]));
+        }
+        lines
+    }
+}
+
+impl<'a> Widget for HelpOverlay<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [popup] = Layout::horizontal([Constraint::Percentage(70)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [popup] = Layout::vertical([Constraint::Percentage(80)])
+            .flex(Flex::Center)
+            .areas(popup);
+
+        Clear.render(popup, buf);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.accent))
+            .title(Line::from(" Help ").style(self.theme.header_style()))
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(
+                        format!(
+                            " {}",
+                            self.keymap
+                                .in_tab(self.tabs.active_tab)
+                                .describe(Action::Reset)
+                        ),
+                        Style::default().fg(self.theme.good),
+                    ),
+                    Span::styled(" close ", Style::default().fg(self.theme.muted)),
+                ])
+                .right_aligned(),
+            );
+
+        let lines = self.lines();
+        let inner_height = block.inner(popup).height as usize;
+        let scroll = self.scroll.min(lines.len().saturating_sub(inner_height));
+
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .scroll((scroll as u16, 0))
+            .render(popup, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keymap::{KeyList, KeybindingsConfig};
+
+    fn text(lines: &[Line]) -> String {
+        lines
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_lists_remapped_keys_for_every_tab() {
+        let mut config = KeybindingsConfig::default();
+        config
+            .global
+            .insert(Action::Down, KeyList::One("j".to_string()));
+        let keymap = Keymap::from_config(&config).unwrap();
+        let tabs = TabManager::new(Default::default(), 3, 2, 10, 4);
+
+        let content = text(&HelpOverlay::new(&keymap, &tabs).lines());
+        assert!(content.contains(env!("CARGO_PKG_VERSION")));
+        assert!(content.contains("Visualize (current)"));
+        assert!(content.contains("Row Groups"));
+        assert!(content.contains("Glossary"));
+        let next_row = content.lines().find(|l| l.contains("Next row")).unwrap();
+        assert!(next_row.trim_start().starts_with("j "));
+        assert!(!content.contains("↓"));
+    }
+
+    #[test]
+    fn test_lists_tab_overrides_in_their_section() {
+        let mut config = KeybindingsConfig::default();
+        config
+            .row_groups
+            .insert(Action::Right, KeyList::One("Ctrl+n".to_string()));
+        let keymap = Keymap::from_config(&config).unwrap();
+        let tabs = TabManager::new(Default::default(), 3, 2, 10, 4);
+
+        let content = text(&HelpOverlay::new(&keymap, &tabs).lines());
+        let (before, row_groups) = content.split_once("\nRow Groups").unwrap();
+        assert!(row_groups.contains("Ctrl+n "));
+        assert!(!before.contains("Ctrl+n"));
+    }
+}
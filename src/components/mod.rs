@@ -1,12 +1,28 @@
+pub mod correlation;
 pub mod data_table;
+pub mod error_panel;
+pub mod help;
 pub mod row_group;
+pub mod row_inspector;
 pub mod schema;
 pub mod scrollbar;
+pub mod size_breakdown;
 
+pub use correlation::CorrelationChart;
 pub use data_table::DataTable;
+pub use error_panel::ErrorPanel;
+pub use help::HelpOverlay;
+pub use row_group::BloomPanel;
+pub use row_group::DecodedPagePanel;
+pub use row_group::PruningPanel;
 pub use row_group::RowGroupColumnMetadataComponent;
+pub use row_group::RowGroupComparison;
+pub use row_group::RowGroupLayout;
 pub use row_group::RowGroupMetadata;
 pub use row_group::RowGroupProgressBar;
+pub use row_inspector::RowInspector;
+pub use schema::ColumnProfilePanel;
 pub use schema::FileSchemaTable;
 pub use schema::SchemaTreeComponent;
 pub use scrollbar::ScrollbarComponent;
+pub use size_breakdown::SizeBreakdown;
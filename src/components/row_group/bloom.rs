@@ -0,0 +1,119 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::Stylize,
+    text::Line,
+    widgets::{Block, Cell, Row, Table, Widget},
+};
+
+use crate::file::bloom::BloomReport;
+use crate::file::utils::human_readable_bytes;
+use crate::theme::Theme;
+
+/// Result of `:bloom`: per row group, how many probes min/max statistics
+/// and the bloom filter rule out, and the reads that saves.
+pub struct BloomPanel<'a> {
+    report: &'a BloomReport,
+    selected_idx: usize,
+    theme: Theme,
+}
+
+impl<'a> BloomPanel<'a> {
+    pub fn new(report: &'a BloomReport, selected_idx: usize) -> Self {
+        Self {
+            report,
+            selected_idx,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self
+    }
+}
+
+impl<'a> Widget for BloomPanel<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let header = Row::new(
+            [
+                "Row group",
+                "Chunk",
+                "Filter",
+                "Min/max",
+                "Bloom",
+                "Read",
+                "Saved",
+            ]
+            .into_iter()
+            .map(|h| Cell::from(h).style(self.theme.header_style())),
+        );
+
+        // Keep the current row group in view.
+        let visible = area.height.saturating_sub(3) as usize;
+        let skip = (self.selected_idx + 1).saturating_sub(visible);
+        let probes = self.report.probes;
+
+        let rows: Vec<Row> = self
+            .report
+            .row_groups
+            .iter()
+            .skip(skip)
+            .map(|rg| {
+                let filter = rg
+                    .filter_size
+                    .map_or("none".to_string(), human_readable_bytes);
+                let read = probes - rg.eliminated_by_stats - rg.eliminated_by_bloom;
+                let row = Row::new(vec![
+                    Cell::from((rg.idx + 1).to_string()).fg(self.theme.label),
+                    Cell::from(human_readable_bytes(rg.chunk_size)).fg(self.theme.text),
+                    Cell::from(filter).fg(self.theme.muted),
+                    Cell::from(format!("{} / {probes}", rg.eliminated_by_stats))
+                        .fg(self.theme.text),
+                    Cell::from(format!("{} / {probes}", rg.eliminated_by_bloom))
+                        .fg(self.theme.good)
+                        .bold(),
+                    Cell::from(format!("{read} / {probes}")).fg(self.theme.text),
+                    Cell::from(human_readable_bytes(rg.saved_bytes())).fg(self.theme.muted),
+                ]);
+                if rg.idx == self.selected_idx {
+                    row.style(self.theme.selection_style())
+                } else {
+                    row
+                }
+            })
+            .collect();
+
+        Table::new(
+            rows,
+            vec![
+                Constraint::Length(10),
+                Constraint::Length(11),
+                Constraint::Length(11),
+                Constraint::Length(12),
+                Constraint::Length(12),
+                Constraint::Length(12),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::bordered()
+                .title(
+                    format!(
+                        "Bloom filters: {} ({probes} probe{})",
+                        self.report.column,
+                        if probes == 1 { "" } else { "s" }
+                    )
+                    .fg(self.theme.accent)
+                    .bold(),
+                )
+                .title_bottom(
+                    Line::from(format!(" {} ", self.report.summary()).fg(self.theme.muted))
+                        .centered(),
+                )
+                .border_style(self.theme.border_style()),
+        )
+        .render(area, buf);
+    }
+}
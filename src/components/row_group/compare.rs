@@ -0,0 +1,218 @@
+use itertools::Itertools;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::Stylize,
+    text::Line,
+    widgets::{Block, Cell, Row, Table, Widget},
+};
+
+use crate::file::row_groups::{RowGroupColumnMetadata, RowGroupStats};
+use crate::file::utils::{commas, human_readable_bytes};
+use crate::theme::Theme;
+
+/// Size ratio from which a column is flagged as the likely cause of a size
+/// difference between the two row groups.
+const FLAG_RATIO: f64 = 2.0;
+
+/// Two marked row groups side by side: per column, the compressed size in
+/// each and how they compare, encodings, page counts and statistics.
+pub struct RowGroupComparison<'a> {
+    left: &'a RowGroupStats,
+    right: &'a RowGroupStats,
+    /// Column highlighted in the table, if one is selected.
+    selected_column: Option<usize>,
+    theme: Theme,
+}
+
+impl<'a> RowGroupComparison<'a> {
+    pub fn new(left: &'a RowGroupStats, right: &'a RowGroupStats) -> Self {
+        Self {
+            left,
+            right,
+            selected_column: None,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_selected_column(mut self, column: Option<usize>) -> Self {
+        self.selected_column = column;
+        self
+    }
+
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self
+    }
+
+    fn column_row(
+        &self,
+        left: &RowGroupColumnMetadata,
+        right: &RowGroupColumnMetadata,
+    ) -> Row<'static> {
+        let ratio = size_ratio(left.total_compressed_size, right.total_compressed_size);
+        let flagged = ratio.is_some_and(|r| r >= FLAG_RATIO || r <= 1.0 / FLAG_RATIO);
+        Row::new(vec![
+            Cell::from(left.column_path.clone()).fg(self.theme.label),
+            Cell::from(human_readable_bytes(
+                left.total_compressed_size.max(0) as u64
+            ))
+            .fg(self.theme.text),
+            Cell::from(human_readable_bytes(
+                right.total_compressed_size.max(0) as u64
+            ))
+            .fg(self.theme.text),
+            Cell::from(format_ratio(ratio)).fg(if flagged {
+                self.theme.bad
+            } else {
+                self.theme.muted
+            }),
+            Cell::from(format!(
+                "{} / {}",
+                left.pages.page_infos.len(),
+                right.pages.page_infos.len()
+            ))
+            .fg(self.theme.text),
+            Cell::from(encodings(left)).fg(self.theme.text),
+            Cell::from(encodings(right)).fg(self.theme.text),
+            Cell::from(format!("{} / {}", nulls(left), nulls(right))).fg(self.theme.text),
+            Cell::from(min_max(left)).fg(self.theme.muted),
+            Cell::from(min_max(right)).fg(self.theme.muted),
+        ])
+    }
+}
+
+/// How many times larger `right` is than `left`, if `left` isn't empty.
+fn size_ratio(left: i64, right: i64) -> Option<f64> {
+    (left > 0).then(|| right.max(0) as f64 / left as f64)
+}
+
+fn format_ratio(ratio: Option<f64>) -> String {
+    ratio.map_or("-".to_string(), |r| format!("{r:.2}×"))
+}
+
+fn encodings(column: &RowGroupColumnMetadata) -> String {
+    column
+        .encodings
+        .data_pages
+        .iter()
+        .map(|(encoding, pages)| format!("{encoding} ×{pages}"))
+        .join(", ")
+}
+
+fn nulls(column: &RowGroupColumnMetadata) -> String {
+    column
+        .statistics
+        .as_ref()
+        .and_then(|stats| stats.null_count)
+        .map_or("-".to_string(), commas)
+}
+
+fn min_max(column: &RowGroupColumnMetadata) -> String {
+    match column.statistics.as_ref() {
+        Some(stats) => format!(
+            "{} … {}",
+            stats.min.as_deref().unwrap_or("-"),
+            stats.max.as_deref().unwrap_or("-")
+        ),
+        None => "-".to_string(),
+    }
+}
+
+impl<'a> Widget for RowGroupComparison<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let (a, b) = (self.left.idx + 1, self.right.idx + 1);
+        let header = Row::new(
+            [
+                "Column".to_string(),
+                format!("RG {a}"),
+                format!("RG {b}"),
+                format!("{b} / {a}"),
+                "Pages".to_string(),
+                format!("Encodings {a}"),
+                format!("Encodings {b}"),
+                "Nulls".to_string(),
+                format!("Min … max {a}"),
+                format!("Min … max {b}"),
+            ]
+            .into_iter()
+            .map(|h| Cell::from(h).style(self.theme.header_style())),
+        );
+
+        // Keep the selected column in view.
+        let visible = area.height.saturating_sub(3) as usize;
+        let skip = self
+            .selected_column
+            .map_or(0, |column| (column + 1).saturating_sub(visible));
+
+        let rows: Vec<Row> = self
+            .left
+            .column_metadata
+            .iter()
+            .zip(&self.right.column_metadata)
+            .enumerate()
+            .skip(skip)
+            .map(|(idx, (left, right))| {
+                let row = self.column_row(left, right);
+                if self.selected_column == Some(idx) {
+                    row.style(self.theme.selection_style())
+                } else {
+                    row
+                }
+            })
+            .collect();
+
+        let summary = format!(
+            " {} vs {} rows · {} vs {} ({}) ",
+            commas(self.left.rows.max(0) as u64),
+            commas(self.right.rows.max(0) as u64),
+            human_readable_bytes(self.left.compressed_size.max(0) as u64),
+            human_readable_bytes(self.right.compressed_size.max(0) as u64),
+            format_ratio(size_ratio(
+                self.left.compressed_size,
+                self.right.compressed_size
+            )),
+        );
+
+        Table::new(
+            rows,
+            vec![
+                Constraint::Fill(2),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(8),
+                Constraint::Length(9),
+                Constraint::Fill(2),
+                Constraint::Fill(2),
+                Constraint::Length(13),
+                Constraint::Fill(2),
+                Constraint::Fill(2),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::bordered()
+                .title(
+                    format!("Row groups {a} and {b}")
+                        .fg(self.theme.accent)
+                        .bold(),
+                )
+                .title_bottom(Line::from(summary.fg(self.theme.muted)).centered())
+                .border_style(self.theme.border_style()),
+        )
+        .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size_ratio() {
+        assert_eq!(size_ratio(100, 500), Some(5.0));
+        assert_eq!(size_ratio(0, 500), None);
+        assert_eq!(format_ratio(size_ratio(400, 100)), "0.25×");
+        assert_eq!(format_ratio(None), "-");
+    }
+}
@@ -0,0 +1,97 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::Stylize,
+    text::Line,
+    widgets::{Block, Cell, Row, Table, Widget},
+};
+
+use crate::file::dictionary::DecodedPage;
+use crate::theme::Theme;
+
+/// Result of `:page`: each value slot of a dictionary encoded page with the
+/// index it stores and the dictionary entry that index points at.
+pub struct DecodedPagePanel<'a> {
+    page: &'a DecodedPage,
+    theme: Theme,
+}
+
+impl<'a> DecodedPagePanel<'a> {
+    pub fn new(page: &'a DecodedPage) -> Self {
+        Self {
+            page,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self
+    }
+}
+
+impl<'a> Widget for DecodedPagePanel<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let header = Row::new(
+            ["#", "Index", "Value"]
+                .into_iter()
+                .map(|h| Cell::from(h).style(self.theme.header_style())),
+        );
+
+        let visible = area.height.saturating_sub(3) as usize;
+        let rows: Vec<Row> = self
+            .page
+            .values
+            .iter()
+            .take(visible)
+            .enumerate()
+            .map(|(slot, value)| match (value.index, &value.value) {
+                (Some(index), Some(value)) => Row::new(vec![
+                    Cell::from((slot + 1).to_string()).fg(self.theme.muted),
+                    Cell::from(index.to_string()).fg(self.theme.label),
+                    Cell::from(value.clone()).fg(self.theme.text),
+                ]),
+                _ => Row::new(vec![
+                    Cell::from((slot + 1).to_string()).fg(self.theme.muted),
+                    Cell::from("-").fg(self.theme.muted),
+                    Cell::from("null").fg(self.theme.muted).italic(),
+                ]),
+            })
+            .collect();
+
+        let page = self.page;
+        let shown = page.values.len().min(visible);
+        Table::new(
+            rows,
+            vec![
+                Constraint::Length(7),
+                Constraint::Length(7),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::bordered()
+                .title(
+                    format!(
+                        "Page {} · {} · {}-bit indices into {} dictionary entries",
+                        page.page + 1,
+                        page.encoding,
+                        page.bit_width,
+                        page.dictionary_size
+                    )
+                    .fg(self.theme.accent)
+                    .bold(),
+                )
+                .title_bottom(
+                    Line::from(
+                        format!(" {shown} of {} values · :page to close ", page.values.len())
+                            .fg(self.theme.muted),
+                    )
+                    .centered(),
+                )
+                .border_style(self.theme.border_style()),
+        )
+        .render(area, buf);
+    }
+}
@@ -0,0 +1,113 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::Stylize,
+    text::Line,
+    widgets::{Block, Cell, Row, Table, Widget},
+};
+
+use crate::file::row_groups::{RANGE_COALESCE_GAP, RowGroupStats, coalesce_ranges, http_range};
+use crate::file::utils::{commas, human_readable_bytes};
+use crate::theme::Theme;
+
+/// Where the selected row group and each of its column chunks sit in the
+/// file, and the range requests a remote reader would send for them.
+pub struct RowGroupLayout<'a> {
+    row_group: &'a RowGroupStats,
+    /// Column highlighted in the table, if one is selected.
+    selected_column: Option<usize>,
+    theme: Theme,
+}
+
+impl<'a> RowGroupLayout<'a> {
+    pub fn new(row_group: &'a RowGroupStats) -> Self {
+        Self {
+            row_group,
+            selected_column: None,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_selected_column(mut self, column: Option<usize>) -> Self {
+        self.selected_column = column;
+        self
+    }
+
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self
+    }
+
+    fn range_row(&self, name: String, (start, len): (u64, u64)) -> Row<'static> {
+        Row::new(vec![
+            Cell::from(name).fg(self.theme.label),
+            Cell::from(commas(start)).fg(self.theme.text),
+            Cell::from(commas(start + len)).fg(self.theme.text),
+            Cell::from(human_readable_bytes(len)).fg(self.theme.text),
+            Cell::from(http_range(start, len)).fg(self.theme.muted),
+        ])
+    }
+}
+
+impl<'a> Widget for RowGroupLayout<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let columns = &self.row_group.column_metadata;
+        let ranges: Vec<(u64, u64)> = columns.iter().map(|c| c.byte_range).collect();
+        let requests = coalesce_ranges(&ranges, RANGE_COALESCE_GAP).len();
+
+        let header = Row::new(
+            ["Chunk", "Offset", "End", "Length", "Range request"]
+                .into_iter()
+                .map(|h| Cell::from(h).style(self.theme.header_style())),
+        );
+
+        // Keep the selected column in view; the whole row group comes first.
+        let visible = area.height.saturating_sub(4) as usize;
+        let skip = self
+            .selected_column
+            .map_or(0, |selected| (selected + 1).saturating_sub(visible));
+
+        let mut rows = vec![
+            self.range_row(
+                format!("Row group {}", self.row_group.idx + 1),
+                self.row_group.byte_range(),
+            )
+            .bold(),
+        ];
+        rows.extend(columns.iter().enumerate().skip(skip).map(|(idx, column)| {
+            let row = self.range_row(format!("  {}", column.column_path), column.byte_range);
+            if self.selected_column == Some(idx) {
+                row.style(self.theme.selection_style())
+            } else {
+                row
+            }
+        }));
+
+        let summary = format!(
+            " {} chunks → {} request{} when coalescing gaps ≤ {} ",
+            columns.len(),
+            requests,
+            if requests == 1 { "" } else { "s" },
+            human_readable_bytes(RANGE_COALESCE_GAP)
+        );
+
+        Table::new(
+            rows,
+            vec![
+                Constraint::Fill(2),
+                Constraint::Length(14),
+                Constraint::Length(14),
+                Constraint::Length(11),
+                Constraint::Fill(2),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::bordered()
+                .title("Layout".fg(self.theme.accent).bold())
+                .title_bottom(Line::from(summary.fg(self.theme.muted)).centered())
+                .border_style(self.theme.border_style()),
+        )
+        .render(area, buf);
+    }
+}
@@ -1,44 +1,58 @@
-use crate::file::row_groups::{RowGroupAvgMedianStats, RowGroupStats};
+use crate::file::row_groups::{Aggregate, RowGroupAggregates, RowGroupStats, size_drift};
+use itertools::Itertools;
 use ratatui::style::Style;
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
-    prelude::Color,
     style::Stylize,
     symbols::Marker,
     text::{Line, Span},
-    widgets::{Axis, Block, Borders, Chart, Dataset, Widget},
+    widgets::{Axis, Block, Borders, Cell, Chart, Dataset, Row, Table, Widget},
 };
 
-use crate::file::utils::{commas, human_readable_bytes};
+use crate::file::utils::{commas, display_width, human_readable_bytes};
+use crate::theme::Theme;
 
 /// Component to display row group level statistics
 pub struct RowGroupMetadata<'a> {
     row_group_stats: &'a [RowGroupStats],
-    avg_median_stats: &'a RowGroupAvgMedianStats,
+    aggregates: &'a RowGroupAggregates,
     selected_idx: usize,
+    theme: Theme,
 }
 
 impl<'a> RowGroupMetadata<'a> {
     pub fn new(
         row_group_stats: &'a [RowGroupStats],
-        avg_median_stats: &'a RowGroupAvgMedianStats,
+        aggregates: &'a RowGroupAggregates,
         selected_idx: usize,
     ) -> Self {
         Self {
             row_group_stats,
-            avg_median_stats,
+            aggregates,
             selected_idx,
+            theme: Theme::default(),
         }
     }
+
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self
+    }
 }
 
 impl<'a> Widget for RowGroupMetadata<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let selected_stats = &self.row_group_stats[self.selected_idx];
 
-        let vertical_areas =
-            Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).split(area);
+        // A line for the declared sort order, if the row group has one.
+        let sort_height = u16::from(!selected_stats.sorting_columns.is_empty());
+        let vertical_areas = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(sort_height),
+            Constraint::Fill(1),
+        ])
+        .split(area);
 
         // Create 1x4 horizontal grid for stats
         let horizontal_areas = Layout::horizontal([
@@ -55,39 +69,56 @@ impl<'a> Widget for RowGroupMetadata<'a> {
             &commas(selected_stats.rows as u64),
             horizontal_areas[0],
             buf,
-            commas(self.avg_median_stats.avg_rows_per_rg as u64),
-            commas(self.avg_median_stats.median_rows_per_rg as u64),
+            commas(self.aggregates.rows.avg as u64),
+            commas(self.aggregates.rows.median as u64),
         );
         self.render_stat_block(
             "Compressed",
             &human_readable_bytes(selected_stats.compressed_size as u64),
             horizontal_areas[1],
             buf,
-            human_readable_bytes(self.avg_median_stats.avg_compressed_size as u64),
-            human_readable_bytes(self.avg_median_stats.median_compressed_size as u64),
+            human_readable_bytes(self.aggregates.compressed_size.avg as u64),
+            human_readable_bytes(self.aggregates.compressed_size.median as u64),
         );
         self.render_stat_block(
             "Uncompressed",
             &human_readable_bytes(selected_stats.uncompressed_size as u64),
             horizontal_areas[2],
             buf,
-            human_readable_bytes(self.avg_median_stats.avg_uncompressed_size as u64),
-            human_readable_bytes(self.avg_median_stats.median_uncompressed_size as u64),
+            human_readable_bytes(self.aggregates.uncompressed_size.avg as u64),
+            human_readable_bytes(self.aggregates.uncompressed_size.median as u64),
         );
         self.render_stat_block(
             "Ratio",
             &format!("{:.2}", selected_stats.compression_ratio),
             horizontal_areas[3],
             buf,
-            format!("{:.2}", self.avg_median_stats.avg_compression_ratio),
-            format!("{:.2}", self.avg_median_stats.median_compression_ratio),
+            format!("{:.2}", self.aggregates.compression_ratio.avg),
+            format!("{:.2}", self.aggregates.compression_ratio.median),
         );
 
+        if sort_height > 0 {
+            Line::from(vec![
+                " Sorted by ".fg(self.theme.label).bold(),
+                selected_stats
+                    .sorting_columns
+                    .iter()
+                    .join(", ")
+                    .fg(self.theme.accent),
+            ])
+            .render(vertical_areas[1], buf);
+        }
+
         let central_area =
-            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).split(vertical_areas[1]);
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).split(vertical_areas[2]);
 
-        // Render charts in the remaining area
-        self.render_charts(central_area[0], buf);
+        let [summary_area, charts_area] =
+            Layout::vertical([Constraint::Length(7), Constraint::Fill(1)]).areas(central_area[0]);
+
+        // Render the summary and charts in the remaining area
+        self.render_aggregates_table(summary_area, buf);
+        self.render_charts(charts_area, buf);
+        self.render_encodings_table(central_area[1], buf);
     }
 }
 
@@ -102,15 +133,15 @@ impl<'a> RowGroupMetadata<'a> {
         median: String,
     ) {
         let title_bottom: Vec<Span> = vec![
-            average.light_cyan().bold(),
-            " / ".white().bold(),
-            median.light_magenta().bold(),
+            average.fg(self.theme.label).bold(),
+            " / ".fg(self.theme.text).bold(),
+            median.fg(self.theme.accent).bold(),
         ];
 
         let block = Block::bordered()
-            .title(title.light_blue().bold())
+            .title(title.fg(self.theme.label).bold())
             .title_bottom(Line::from(title_bottom).centered())
-            .border_style(ratatui::style::Style::default().fg(Color::Blue));
+            .border_style(self.theme.border_style());
 
         let inner = block.inner(area);
         block.render(area, buf);
@@ -123,24 +154,142 @@ impl<'a> RowGroupMetadata<'a> {
             for (i, line) in lines.iter().enumerate() {
                 let y = start_y + i as u16;
                 if y < inner.y + inner.height {
-                    let x = inner.x + (inner.width.saturating_sub(line.len() as u16)) / 2;
+                    let line_width = display_width(line) as u16;
+                    let x = inner.x + (inner.width.saturating_sub(line_width)) / 2;
                     if x < inner.x + inner.width {
                         line.bold()
-                            .yellow()
-                            .render(Rect::new(x, y, line.len() as u16, 1), buf);
+                            .fg(self.theme.header)
+                            .render(Rect::new(x, y, line_width, 1), buf);
                     }
                 }
             }
         }
     }
 
+    /// Min / avg / median / p95 / max of each property across all row groups.
+    fn render_aggregates_table(&self, area: Rect, buf: &mut Buffer) {
+        let header = Row::new(
+            ["", "Min", "Avg", "Median", "P95", "Max"]
+                .into_iter()
+                .map(|h| Cell::from(h).style(self.theme.header_style())),
+        );
+
+        let bytes = |v: f64| human_readable_bytes(v as u64);
+        let rows = vec![
+            self.aggregate_row("Rows", &self.aggregates.rows, |v| commas(v as u64)),
+            self.aggregate_row("Compressed", &self.aggregates.compressed_size, bytes),
+            self.aggregate_row("Uncompressed", &self.aggregates.uncompressed_size, bytes),
+            self.aggregate_row("Ratio", &self.aggregates.compression_ratio, |v| {
+                format!("{v:.2}")
+            }),
+        ];
+
+        let mut widths = vec![Constraint::Length(13)];
+        widths.extend([Constraint::Fill(1); 5]);
+
+        let table = Table::new(rows, widths).header(header).block(
+            Block::bordered()
+                .title(
+                    format!("Across {} row groups", self.row_group_stats.len())
+                        .fg(self.theme.accent)
+                        .bold(),
+                )
+                .border_style(self.theme.border_style()),
+        );
+
+        table.render(area, buf);
+    }
+
+    fn aggregate_row(
+        &self,
+        name: &'static str,
+        aggregate: &Aggregate,
+        format: fn(f64) -> String,
+    ) -> Row<'static> {
+        let values = [
+            aggregate.min,
+            aggregate.avg,
+            aggregate.median,
+            aggregate.p95,
+            aggregate.max,
+        ];
+        Row::new(
+            std::iter::once(Cell::from(name).bold().fg(self.theme.label)).chain(
+                values
+                    .into_iter()
+                    .map(|v| Cell::from(format(v)).fg(self.theme.text)),
+            ),
+        )
+    }
+
+    /// Data page encodings of every column in the selected row group, with
+    /// the columns that fell back from dictionary encoding flagged.
+    fn render_encodings_table(&self, area: Rect, buf: &mut Buffer) {
+        let columns = &self.row_group_stats[self.selected_idx].column_metadata;
+        let fallbacks = columns
+            .iter()
+            .filter(|c| c.encodings.dictionary_fallback)
+            .count();
+
+        let header = Row::new(vec![
+            Cell::from("Column").style(self.theme.header_style()),
+            Cell::from("Data Pages").style(self.theme.header_style()),
+            Cell::from("Fallback").style(self.theme.header_style()),
+        ]);
+
+        let rows: Vec<Row> = columns
+            .iter()
+            .map(|column| {
+                let pages = column
+                    .encodings
+                    .data_pages
+                    .iter()
+                    .map(|(encoding, pages)| format!("{encoding} ×{pages}"))
+                    .join(", ");
+                let (flag, color) = if column.encodings.dictionary_fallback {
+                    ("✗", self.theme.bad)
+                } else {
+                    ("", self.theme.good)
+                };
+                Row::new(vec![
+                    Cell::from(column.column_path.clone()).fg(self.theme.label),
+                    Cell::from(pages).fg(self.theme.text),
+                    Cell::from(flag).fg(color).bold(),
+                ])
+            })
+            .collect();
+
+        let title_bottom = if fallbacks == 0 {
+            "no dictionary fallback".fg(self.theme.good)
+        } else {
+            format!("{fallbacks} column(s) fell back from dictionary").fg(self.theme.bad)
+        };
+
+        let table = Table::new(
+            rows,
+            vec![
+                Constraint::Fill(2),
+                Constraint::Fill(3),
+                Constraint::Length(8),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::bordered()
+                .title("Encodings".fg(self.theme.accent).bold())
+                .title_bottom(Line::from(title_bottom).centered())
+                .border_style(self.theme.border_style()),
+        );
+
+        table.render(area, buf);
+    }
+
     fn render_charts(&self, area: Rect, buf: &mut Buffer) {
-        // Split area into two charts horizontally
-        let chart_areas =
-            Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).split(area);
+        let chart_areas = Layout::vertical([Constraint::Ratio(1, 3); 3]).split(area);
 
         self.render_size_comparison_chart(chart_areas[0], buf);
         self.render_compression_ratio_chart(chart_areas[1], buf);
+        self.render_offset_trend_chart(chart_areas[2], buf);
     }
 
     fn normalized_x_positions(&self) -> Vec<f64> {
@@ -208,12 +357,12 @@ impl<'a> RowGroupMetadata<'a> {
             Dataset::default()
                 .name("Compressed")
                 .marker(Marker::Dot)
-                .style(Style::default().fg(Color::Blue))
+                .style(Style::default().fg(self.theme.label))
                 .data(&compressed_data),
             Dataset::default()
                 .name("Uncompressed")
                 .marker(Marker::Dot)
-                .style(Style::default().fg(Color::Red))
+                .style(Style::default().fg(self.theme.accent))
                 .data(&uncompressed_data),
         ];
 
@@ -234,9 +383,9 @@ impl<'a> RowGroupMetadata<'a> {
             .collect();
 
         let title = vec![
-            "Compressed".light_blue().bold(),
+            "Compressed".fg(self.theme.label).bold(),
             " vs ".into(),
-            "Uncompressed".light_red().bold(),
+            "Uncompressed".fg(self.theme.accent).bold(),
             " (B)".into(),
         ];
 
@@ -244,18 +393,18 @@ impl<'a> RowGroupMetadata<'a> {
             .block(
                 Block::default()
                     .title(Line::from(title).centered())
-                    .title_bottom("Row Group".dark_gray())
+                    .title_bottom("Row Group".fg(self.theme.muted))
                     .borders(Borders::NONE),
             )
             .x_axis(
                 Axis::default()
-                    .style(Style::default().fg(Color::White))
+                    .style(Style::default().fg(self.theme.text))
                     .bounds([0.0, 1.0])
                     .labels(x_labels),
             )
             .y_axis(
                 Axis::default()
-                    .style(Style::default().fg(Color::White))
+                    .style(Style::default().fg(self.theme.text))
                     .bounds([0.0, max_size * 1.5])
                     .labels(y_labels),
             );
@@ -263,6 +412,102 @@ impl<'a> RowGroupMetadata<'a> {
         chart.render(area, buf);
     }
 
+    /// Compressed size and rows of each row group, as a share of the
+    /// largest, against where the row group starts in the file, so that
+    /// row groups shrinking or growing as the writer went are easy to spot.
+    fn render_offset_trend_chart(&self, area: Rect, buf: &mut Buffer) {
+        if self.row_group_stats.is_empty() {
+            return;
+        }
+        let ranges: Vec<(u64, u64)> = self
+            .row_group_stats
+            .iter()
+            .map(|rg| rg.byte_range())
+            .collect();
+        let file_end = ranges
+            .iter()
+            .map(|(start, len)| start + len)
+            .max()
+            .unwrap_or(0)
+            .max(1) as f64;
+        let max_compressed = self.aggregates.compressed_size.max.max(1.0);
+        let max_rows = self.aggregates.rows.max.max(1.0);
+        let points = |value: fn(&RowGroupStats) -> f64, max: f64| -> Vec<(f64, f64)> {
+            self.row_group_stats
+                .iter()
+                .zip(&ranges)
+                .map(|(rg, (start, _))| (*start as f64, value(rg) / max * 100.0))
+                .collect()
+        };
+        let compressed_data = points(|rg| rg.compressed_size as f64, max_compressed);
+        let rows_data = points(|rg| rg.rows as f64, max_rows);
+        let selected = [rows_data[self.selected_idx]];
+
+        let datasets = vec![
+            Dataset::default()
+                .name("Compressed")
+                .marker(Marker::Dot)
+                .style(Style::default().fg(self.theme.label))
+                .data(&compressed_data),
+            Dataset::default()
+                .name("Rows")
+                .marker(Marker::Dot)
+                .style(Style::default().fg(self.theme.accent))
+                .data(&rows_data),
+            Dataset::default()
+                .marker(Marker::Block)
+                .style(Style::default().fg(self.theme.header))
+                .data(&selected),
+        ];
+
+        let title = vec![
+            "Compressed".fg(self.theme.label).bold(),
+            " and ".into(),
+            "Rows".fg(self.theme.accent).bold(),
+            " by offset (% of max)".into(),
+        ];
+        let sizes: Vec<(u64, i64)> = ranges
+            .iter()
+            .zip(self.row_group_stats)
+            .map(|((start, _), rg)| (*start, rg.compressed_size))
+            .collect();
+        let title_bottom = match size_drift(&sizes) {
+            Some(drift) if drift < 0.5 => {
+                format!("shrinking: last quarter {drift:.2}x the first").fg(self.theme.bad)
+            }
+            Some(drift) if drift > 2.0 => {
+                format!("growing: last quarter {drift:.2}x the first").fg(self.theme.bad)
+            }
+            _ => "File Offset".fg(self.theme.muted),
+        };
+
+        let chart = Chart::new(datasets)
+            .block(
+                Block::default()
+                    .title(Line::from(title).centered())
+                    .title_bottom(title_bottom)
+                    .borders(Borders::NONE),
+            )
+            .x_axis(
+                Axis::default()
+                    .style(Style::default().fg(self.theme.text))
+                    .bounds([0.0, file_end])
+                    .labels([
+                        "0".to_string(),
+                        human_readable_bytes((file_end / 2.0) as u64),
+                        human_readable_bytes(file_end as u64),
+                    ]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().fg(self.theme.text))
+                    .bounds([0.0, 110.0])
+                    .labels(["0%", "50%", "100%"]),
+            );
+
+        chart.render(area, buf);
+    }
+
     fn render_compression_ratio_chart(&self, area: Rect, buf: &mut Buffer) {
         let n = self.row_group_stats.len();
         if n == 0 {
@@ -293,7 +538,7 @@ impl<'a> RowGroupMetadata<'a> {
             Dataset::default()
                 .name("Compression Ratio")
                 .marker(Marker::Dot)
-                .style(Style::default().fg(Color::Yellow))
+                .style(Style::default().fg(self.theme.accent))
                 .data(&ratio_data),
         ];
 
@@ -312,19 +557,19 @@ impl<'a> RowGroupMetadata<'a> {
         let chart = Chart::new(datasets)
             .block(
                 Block::default()
-                    .title("Compression Ratio".yellow())
-                    .title_bottom("Row Group".dark_gray())
+                    .title("Compression Ratio".fg(self.theme.accent))
+                    .title_bottom("Row Group".fg(self.theme.muted))
                     .borders(Borders::NONE),
             )
             .x_axis(
                 Axis::default()
-                    .style(Style::default().fg(Color::White))
+                    .style(Style::default().fg(self.theme.text))
                     .bounds([0.0, 1.0])
                     .labels(x_labels),
             )
             .y_axis(
                 Axis::default()
-                    .style(Style::default().fg(Color::White))
+                    .style(Style::default().fg(self.theme.text))
                     .bounds([1.0, max_ratio * 1.1])
                     .labels(y_labels),
             );
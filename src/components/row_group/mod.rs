@@ -1,7 +1,17 @@
+pub mod bloom;
+pub mod compare;
+pub mod dictionary;
+pub mod layout;
 pub mod metadata;
 pub mod progress_bar;
+pub mod pruning;
 pub mod schema_md;
 
+pub use bloom::BloomPanel;
+pub use compare::RowGroupComparison;
+pub use dictionary::DecodedPagePanel;
+pub use layout::RowGroupLayout;
 pub use metadata::RowGroupMetadata;
 pub use progress_bar::RowGroupProgressBar;
+pub use pruning::PruningPanel;
 pub use schema_md::RowGroupColumnMetadataComponent;
@@ -1,8 +1,9 @@
 use crate::file::row_groups::RowGroupStats;
+use crate::theme::Theme;
 use ratatui::{
     buffer::Buffer,
     layout::{Position, Rect},
-    prelude::{Color, Span},
+    prelude::Span,
     style::Stylize,
     text::Line,
     widgets::{Block, Widget},
@@ -11,6 +12,9 @@ use ratatui::{
 pub struct RowGroupProgressBar<'a> {
     pub row_group_stats: &'a [RowGroupStats],
     pub selected_idx: usize,
+    /// Active filter text and the row groups it matches.
+    pub filter: Option<(String, &'a [usize])>,
+    pub theme: Theme,
 }
 
 impl<'a> RowGroupProgressBar<'a> {
@@ -18,25 +22,44 @@ impl<'a> RowGroupProgressBar<'a> {
         Self {
             row_group_stats,
             selected_idx,
+            filter: None,
+            theme: Theme::default(),
         }
     }
+
+    pub fn with_filter(mut self, filter: String, matches: &'a [usize]) -> Self {
+        self.filter = Some((filter, matches));
+        self
+    }
+
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self
+    }
 }
 
 impl<'a> Widget for RowGroupProgressBar<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let total_row_groups = self.row_group_stats.len();
-        let title: Vec<Span<'static>> = vec![
+        let mut title: Vec<Span<'static>> = vec![
             " Row Group: ".into(),
             format!("{}", self.selected_idx + 1).into(),
             " / ".into(),
             format!("{total_row_groups}").into(),
             " ".into(),
         ];
+        if let Some((filter, matches)) = &self.filter {
+            title.extend([
+                "· filter: ".fg(self.theme.muted),
+                filter.clone().fg(self.theme.label),
+                format!(" ({} match) ", matches.len()).fg(self.theme.muted),
+            ]);
+        }
 
         let block = Block::bordered()
             .title(Line::from(title))
-            .border_style(ratatui::style::Style::default().fg(Color::Gray))
-            .title_style(ratatui::style::Style::default().fg(Color::Gray).bold());
+            .border_style(self.theme.border_style())
+            .title_style(ratatui::style::Style::default().fg(self.theme.text).bold());
 
         let inner = block.inner(area);
         block.render(area, buf);
@@ -52,7 +75,21 @@ impl<'a> Widget for RowGroupProgressBar<'a> {
             for x in inner.x..inner.x + inner.width {
                 if let Some(cell) = buf.cell_mut(Position::new(x, center_y)) {
                     cell.set_symbol("─")
-                        .set_style(ratatui::style::Style::default().fg(Color::White));
+                        .set_style(ratatui::style::Style::default().fg(self.theme.muted));
+                }
+            }
+
+            // Mark the row groups matching the filter
+            if let Some((_, matches)) = &self.filter {
+                for &idx in matches.iter() {
+                    let start_x = inner.x + (idx as f64 * segment_width) as u16;
+                    let end_x = inner.x + ((idx + 1) as f64 * segment_width) as u16;
+                    for x in start_x..end_x.max(start_x + 1).min(inner.x + inner.width) {
+                        if let Some(cell) = buf.cell_mut(Position::new(x, center_y)) {
+                            cell.set_symbol("━")
+                                .set_style(ratatui::style::Style::default().fg(self.theme.label));
+                        }
+                    }
                 }
             }
 
@@ -64,7 +101,7 @@ impl<'a> Widget for RowGroupProgressBar<'a> {
             for x in selected_start_x..selected_end_x.min(inner.x + inner.width) {
                 if let Some(cell) = buf.cell_mut(Position::new(x, center_y)) {
                     cell.set_symbol("█")
-                        .set_style(ratatui::style::Style::default().fg(Color::Blue));
+                        .set_style(ratatui::style::Style::default().fg(self.theme.accent));
                 }
             }
         }
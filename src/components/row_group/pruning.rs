@@ -0,0 +1,114 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::Stylize,
+    text::Line,
+    widgets::{Block, Cell, Row, Table, Widget},
+};
+
+use crate::file::pruning::PruningReport;
+use crate::file::utils::{commas, human_readable_bytes};
+use crate::theme::Theme;
+
+/// Result of `:prune`: which row groups and pages min/max statistics let a
+/// reader skip for the predicate.
+pub struct PruningPanel<'a> {
+    report: &'a PruningReport,
+    selected_idx: usize,
+    theme: Theme,
+}
+
+impl<'a> PruningPanel<'a> {
+    pub fn new(report: &'a PruningReport, selected_idx: usize) -> Self {
+        Self {
+            report,
+            selected_idx,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self
+    }
+}
+
+impl<'a> Widget for PruningPanel<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let header = Row::new(
+            [
+                "Row group",
+                "Rows",
+                "Size",
+                "Verdict",
+                "Pages read",
+                "Skipped",
+            ]
+            .into_iter()
+            .map(|h| Cell::from(h).style(self.theme.header_style())),
+        );
+
+        // Keep the current row group in view.
+        let visible = area.height.saturating_sub(3) as usize;
+        let skip = (self.selected_idx + 1).saturating_sub(visible);
+
+        let rows: Vec<Row> = self
+            .report
+            .row_groups
+            .iter()
+            .skip(skip)
+            .map(|rg| {
+                let (verdict, color) = if rg.skipped {
+                    ("skip", self.theme.good)
+                } else {
+                    ("scan", self.theme.bad)
+                };
+                let pages = match (&rg.pages, rg.skipped) {
+                    (_, true) => "0".to_string(),
+                    (Some(pages), false) => format!("{} / {}", pages.scanned, pages.total),
+                    (None, false) => "no page index".to_string(),
+                };
+                let row = Row::new(vec![
+                    Cell::from((rg.idx + 1).to_string()).fg(self.theme.label),
+                    Cell::from(commas(rg.rows.max(0) as u64)).fg(self.theme.text),
+                    Cell::from(human_readable_bytes(rg.compressed_size)).fg(self.theme.text),
+                    Cell::from(verdict).fg(color).bold(),
+                    Cell::from(pages).fg(self.theme.text),
+                    Cell::from(human_readable_bytes(rg.skipped_bytes())).fg(self.theme.muted),
+                ]);
+                if rg.idx == self.selected_idx {
+                    row.style(self.theme.selection_style())
+                } else {
+                    row
+                }
+            })
+            .collect();
+
+        Table::new(
+            rows,
+            vec![
+                Constraint::Length(10),
+                Constraint::Length(12),
+                Constraint::Length(11),
+                Constraint::Length(8),
+                Constraint::Length(14),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::bordered()
+                .title(
+                    format!("Pruning: {}", self.report.predicate)
+                        .fg(self.theme.accent)
+                        .bold(),
+                )
+                .title_bottom(
+                    Line::from(format!(" {} ", self.report.summary()).fg(self.theme.muted))
+                        .centered(),
+                )
+                .border_style(self.theme.border_style()),
+        )
+        .render(area, buf);
+    }
+}
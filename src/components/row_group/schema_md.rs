@@ -1,22 +1,54 @@
+use crate::components::ErrorPanel;
+use crate::file::page_header::PageHeaderInfo;
+use crate::file::page_values::PagePreview;
+use crate::file::row_groups::{DataPageV2Info, PageInfo, RowGroupColumnMetadata};
+use crate::file::utils::commas;
 use crate::file::utils::human_readable_bytes;
-use crate::file::{row_groups::RowGroupColumnMetadata, utils::commas};
+use crate::theme::Theme;
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Position, Rect},
-    prelude::Color,
     style::Stylize,
     text::Line,
-    widgets::{Block, Borders, Cell, Row, Table, Widget},
+    widgets::{Block, Borders, Cell, Row, Sparkline, Table, Widget},
 };
 
 /// Component to display column-level metadata for a selected row group
 pub struct RowGroupColumnMetadataComponent<'a> {
     column_metadata: &'a RowGroupColumnMetadata,
+    /// The same column's chunk in every row group, for the dictionary chart.
+    chunks: Vec<&'a RowGroupColumnMetadata>,
+    /// First values of the chunk's pages, in page order.
+    previews: &'a [Option<PagePreview>],
+    theme: Theme,
 }
 
 impl<'a> RowGroupColumnMetadataComponent<'a> {
     pub fn new(column_metadata: &'a RowGroupColumnMetadata) -> Self {
-        Self { column_metadata }
+        Self {
+            column_metadata,
+            chunks: vec![],
+            previews: &[],
+            theme: Theme::default(),
+        }
+    }
+
+    /// Chart the dictionary page of the column in each of `chunks`, one per
+    /// row group in file order.
+    pub fn with_row_group_chunks(mut self, chunks: Vec<&'a RowGroupColumnMetadata>) -> Self {
+        self.chunks = chunks;
+        self
+    }
+
+    /// Show the first values of each page, read when the chunk is shown.
+    pub fn with_page_previews(mut self, previews: &'a [Option<PagePreview>]) -> Self {
+        self.previews = previews;
+        self
+    }
+
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self
     }
 }
 
@@ -24,14 +56,18 @@ impl<'a> Widget for RowGroupColumnMetadataComponent<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let title = vec![
             " Column: ".into(),
-            self.column_metadata.column_path.clone().yellow().bold(),
+            self.column_metadata
+                .column_path
+                .clone()
+                .fg(self.theme.accent)
+                .bold(),
             " ".into(),
         ];
 
         let block = Block::bordered()
             .title(Line::from(title).centered())
             .borders(Borders::TOP)
-            .border_style(ratatui::style::Style::default().fg(Color::Blue));
+            .border_style(self.theme.border_style());
 
         let inner_area = block.inner(area);
         block.render(area, buf);
@@ -42,14 +78,49 @@ impl<'a> Widget for RowGroupColumnMetadataComponent<'a> {
         ])
         .areas(inner_area);
 
+        let [contents_area, headers_area] = Layout::vertical([
+            Constraint::Fill(2), // Metadata and pages
+            Constraint::Fill(1), // Decoded page headers
+        ])
+        .areas(contents_area);
+
         let [md_stats_area, page_area] = Layout::horizontal([
             Constraint::Fill(3), // Metadata table
             Constraint::Fill(5), // Pages table
         ])
         .areas(contents_area);
 
+        let [pages_table_area, page_charts_area] = Layout::vertical([
+            Constraint::Fill(1),   // Pages table
+            Constraint::Length(8), // Page size / values sparklines
+        ])
+        .areas(page_area);
+
         // Render pages table
-        self.render_pages_table(page_area, buf);
+        self.render_pages_table(pages_table_area, buf);
+        let headers = &self.column_metadata.page_headers.headers;
+        let headers_area = if self.chunks.iter().any(|c| c.dictionary_page().is_some()) {
+            let [headers_area, dictionary_area] =
+                Layout::horizontal([Constraint::Fill(3), Constraint::Fill(2)]).areas(headers_area);
+            self.render_dictionary_chart(dictionary_area, buf);
+            headers_area
+        } else {
+            headers_area
+        };
+        if headers.iter().any(|header| header.v2.is_some()) {
+            let [headers_area, v2_area] =
+                Layout::vertical([Constraint::Fill(1), Constraint::Fill(1)]).areas(headers_area);
+            self.render_page_headers_table(headers_area, buf);
+            self.render_v2_headers_table(v2_area, buf);
+        } else {
+            self.render_page_headers_table(headers_area, buf);
+        }
+        match &self.column_metadata.pages.error {
+            Some(error) => ErrorPanel::new("Page scan stopped", error)
+                .with_theme(&self.theme)
+                .render(page_charts_area, buf),
+            None => self.render_page_distribution(page_charts_area, buf),
+        }
 
         // Split into three sections: feature indicators, stats table, and statistics
         let mut constraints = vec![
@@ -60,6 +131,10 @@ impl<'a> Widget for RowGroupColumnMetadataComponent<'a> {
         if self.column_metadata.statistics.is_some() {
             constraints.push(Constraint::Length(6)); // Statistics table
         }
+        if self.column_metadata.geo_statistics.is_some() {
+            constraints.push(Constraint::Length(5)); // Geospatial statistics table
+        }
+        constraints.push(Constraint::Fill(1)); // Encodings table
 
         let vertical_areas = Layout::vertical(constraints).split(md_stats_area);
 
@@ -67,24 +142,26 @@ impl<'a> Widget for RowGroupColumnMetadataComponent<'a> {
         self.render_metadata_table(vertical_areas[0], buf);
 
         // Render statistics table if available
+        let mut next_area = 1;
         if self.column_metadata.statistics.is_some() {
-            self.render_statistics_table(vertical_areas[1], buf);
+            self.render_statistics_table(vertical_areas[next_area], buf);
+            next_area += 1;
+        }
+        if self.column_metadata.geo_statistics.is_some() {
+            self.render_geo_statistics_table(vertical_areas[next_area], buf);
         }
+        self.render_encodings_table(vertical_areas[vertical_areas.len() - 1], buf);
     }
 }
 
 impl<'a> RowGroupColumnMetadataComponent<'a> {
     fn render_metadata_table(&self, area: Rect, buf: &mut Buffer) {
         // Calculate compression ratio
-        let compression_ratio = if self.column_metadata.total_compressed_size > 0 {
-            format!(
-                "{:.2}x",
-                self.column_metadata.total_uncompressed_size as f64
-                    / self.column_metadata.total_compressed_size as f64
-            )
-        } else {
-            "N/A".to_string()
-        };
+        let ratio = (self.column_metadata.total_compressed_size > 0).then(|| {
+            self.column_metadata.total_uncompressed_size as f64
+                / self.column_metadata.total_compressed_size as f64
+        });
+        let compression_ratio = ratio.map_or("N/A".to_string(), |ratio| format!("{ratio:.2}x"));
 
         let kv_pairs = vec![
             ("File Offset (B)", commas(self.column_metadata.file_offset)),
@@ -106,9 +183,15 @@ impl<'a> RowGroupColumnMetadataComponent<'a> {
         let rows: Vec<Row> = kv_pairs
             .into_iter()
             .map(|(k, v)| {
+                let value = Cell::from(v);
                 Row::new(vec![
-                    Cell::from(k).bold().fg(Color::Cyan),
-                    Cell::from(v).fg(Color::White),
+                    Cell::from(k).bold().fg(self.theme.label),
+                    match (k, ratio) {
+                        ("Compression Ratio", Some(ratio)) => {
+                            value.style(self.theme.ratio_style(ratio))
+                        }
+                        _ => value.fg(self.theme.text),
+                    },
                 ])
             })
             .collect();
@@ -116,7 +199,7 @@ impl<'a> RowGroupColumnMetadataComponent<'a> {
         let table = Table::new(rows, vec![Constraint::Length(18), Constraint::Fill(1)]).block(
             Block::bordered()
                 .title("Metadata")
-                .border_style(ratatui::style::Style::default().fg(Color::Blue)),
+                .border_style(self.theme.border_style()),
         );
 
         table.render(area, buf);
@@ -160,9 +243,9 @@ impl<'a> RowGroupColumnMetadataComponent<'a> {
 
     fn render_indicator_box(&self, title: &str, has_feature: bool, area: Rect, buf: &mut Buffer) {
         let (symbol, color) = if has_feature {
-            ("✓", Color::Green)
+            ("✓", self.theme.good)
         } else {
-            ("✗", Color::Red)
+            ("✗", self.theme.bad)
         };
 
         let block = Block::bordered()
@@ -211,8 +294,8 @@ impl<'a> RowGroupColumnMetadataComponent<'a> {
                 .into_iter()
                 .map(|(k, v)| {
                     Row::new(vec![
-                        Cell::from(k).bold().fg(Color::Magenta),
-                        Cell::from(v).fg(Color::White),
+                        Cell::from(k).bold().fg(self.theme.label),
+                        Cell::from(v).fg(self.theme.text),
                     ])
                 })
                 .collect();
@@ -220,23 +303,221 @@ impl<'a> RowGroupColumnMetadataComponent<'a> {
             let table = Table::new(rows, vec![Constraint::Length(18), Constraint::Fill(1)]).block(
                 Block::bordered()
                     .title("Statistics")
-                    .border_style(ratatui::style::Style::default().fg(Color::Magenta)),
+                    .border_style(self.theme.border_style()),
             );
 
             table.render(area, buf);
         }
     }
 
+    /// Bounding box corners and geometry types of a geospatial chunk.
+    fn render_geo_statistics_table(&self, area: Rect, buf: &mut Buffer) {
+        let Some(geo) = &self.column_metadata.geo_statistics else {
+            return;
+        };
+        let types = if geo.types.is_empty() {
+            "N/A".to_string()
+        } else {
+            geo.types.join(", ")
+        };
+        let rows: Vec<Row> = [
+            (
+                "Bounds Min",
+                geo.bounds.map_or("N/A".to_string(), |b| b.min_text()),
+            ),
+            (
+                "Bounds Max",
+                geo.bounds.map_or("N/A".to_string(), |b| b.max_text()),
+            ),
+            ("Types", types),
+        ]
+        .into_iter()
+        .map(|(k, v)| {
+            Row::new(vec![
+                Cell::from(k).bold().fg(self.theme.label),
+                Cell::from(v).fg(self.theme.text),
+            ])
+        })
+        .collect();
+
+        Table::new(rows, vec![Constraint::Length(18), Constraint::Fill(1)])
+            .block(
+                Block::bordered()
+                    .title("Geospatial Statistics")
+                    .border_style(self.theme.border_style()),
+            )
+            .render(area, buf);
+    }
+
+    fn render_encodings_table(&self, area: Rect, buf: &mut Buffer) {
+        let encodings = &self.column_metadata.encodings;
+        let total = encodings.total_pages().max(1);
+
+        let mut rows: Vec<Row> = encodings
+            .data_pages
+            .iter()
+            .map(|(encoding, pages)| {
+                Row::new(vec![
+                    Cell::from(encoding.clone()).bold().fg(self.theme.label),
+                    Cell::from(commas(*pages as u64)).fg(self.theme.text),
+                    Cell::from(format!("{:.0}%", *pages as f64 * 100.0 / total as f64))
+                        .fg(self.theme.muted),
+                ])
+            })
+            .collect();
+        // The footer's list of encodings is what readers go by.
+        if let Some(mismatch) = &encodings.footer_mismatch {
+            rows.push(Row::new(vec![
+                Cell::from("Footer differs").bold().fg(self.theme.bad),
+                Cell::from(mismatch.to_string()).fg(self.theme.bad),
+            ]));
+        }
+
+        let mut block = Block::bordered()
+            .title("Data Page Encodings")
+            .border_style(self.theme.border_style());
+        if encodings.dictionary_fallback {
+            block = block.title_bottom(
+                Line::from(" dictionary fallback ".fg(self.theme.bad).bold()).centered(),
+            );
+        }
+
+        let table = Table::new(
+            rows,
+            vec![
+                Constraint::Length(18),
+                Constraint::Fill(1),
+                Constraint::Length(5),
+            ],
+        )
+        .block(block);
+
+        table.render(area, buf);
+    }
+
+    /// Sparklines of data page sizes and values per page, in file order, to
+    /// make runs of tiny pages or a single giant page stand out.
+    fn render_page_distribution(&self, area: Rect, buf: &mut Buffer) {
+        let data_pages: Vec<&PageInfo> = self
+            .column_metadata
+            .pages
+            .page_infos
+            .iter()
+            .filter(|page| page.is_data_page())
+            .collect();
+
+        let block = Block::bordered()
+            .title(format!("Data Pages ({})", data_pages.len()))
+            .border_style(self.theme.border_style());
+        let inner = block.inner(area);
+        block.render(area, buf);
+        if data_pages.is_empty() || inner.height < 2 {
+            return;
+        }
+
+        let sizes: Vec<u64> = data_pages.iter().map(|page| page.size as u64).collect();
+        let values: Vec<u64> = data_pages.iter().map(|page| page.rows as u64).collect();
+
+        let [size_area, values_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Fill(1)]).areas(inner);
+        self.render_sparkline("Size", &sizes, human_readable_bytes, size_area, buf);
+        self.render_sparkline("Values", &values, commas, values_area, buf);
+    }
+
+    /// Dictionary page size and entries of the column in each row group. A
+    /// row group without a dictionary page, or whose data pages switch from
+    /// the dictionary to PLAIN, fell back: typically the column's
+    /// cardinality grew past what the writer keeps in a dictionary.
+    fn render_dictionary_chart(&self, area: Rect, buf: &mut Buffer) {
+        let dictionaries: Vec<_> = self.chunks.iter().map(|c| c.dictionary_page()).collect();
+        let fell_back: Vec<usize> = self
+            .chunks
+            .iter()
+            .zip(&dictionaries)
+            .enumerate()
+            .filter(|(_, (chunk, dictionary))| {
+                dictionary.is_none() || chunk.encodings.dictionary_fallback
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+        let title_bottom = match fell_back.first() {
+            Some(first) => format!(
+                "fell back to PLAIN in {} row group(s), first {}",
+                fell_back.len(),
+                first + 1
+            )
+            .fg(self.theme.bad),
+            None => "dictionary in every row group".fg(self.theme.good),
+        };
+
+        let block = Block::bordered()
+            .title(format!("Dictionary by Row Group ({})", self.chunks.len()))
+            .title_bottom(Line::from(title_bottom).centered())
+            .border_style(self.theme.border_style());
+        let inner = block.inner(area);
+        block.render(area, buf);
+        if inner.height < 2 {
+            return;
+        }
+
+        let sizes: Vec<u64> = dictionaries
+            .iter()
+            .map(|d| d.map_or(0, |d| d.uncompressed_size))
+            .collect();
+        let entries: Vec<u64> = dictionaries
+            .iter()
+            .map(|d| d.map_or(0, |d| d.entries))
+            .collect();
+        let [size_area, entries_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Fill(1)]).areas(inner);
+        self.render_sparkline("Size", &sizes, human_readable_bytes, size_area, buf);
+        self.render_sparkline("Entries", &entries, commas, entries_area, buf);
+    }
+
+    fn render_sparkline(
+        &self,
+        label: &str,
+        data: &[u64],
+        format: fn(u64) -> String,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let (min, median, max) = min_median_max(data);
+        let [label_area, chart_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+
+        Line::from(vec![
+            format!("{label} ").bold().fg(self.theme.label),
+            format!(
+                "min {} · median {} · max {}",
+                format(min),
+                format(median),
+                format(max)
+            )
+            .fg(self.theme.muted),
+        ])
+        .render(label_area, buf);
+
+        Sparkline::default()
+            .data(data)
+            .max(max.max(1))
+            .style(self.theme.accent)
+            .render(chart_area, buf);
+    }
+
     fn render_pages_table(&self, area: Rect, buf: &mut Buffer) {
         use crate::file::utils::human_readable_bytes;
 
         // Create header
         let header = Row::new(vec![
-            Cell::from("#").bold().fg(Color::Yellow),
-            Cell::from("Page Type").bold().fg(Color::Yellow),
-            Cell::from("Size").bold().fg(Color::Yellow),
-            Cell::from("Rows").bold().fg(Color::Yellow),
-            Cell::from("Encoding").bold().fg(Color::Yellow),
+            Cell::from("#").style(self.theme.header_style()),
+            Cell::from("Page Type").style(self.theme.header_style()),
+            Cell::from("Size").style(self.theme.header_style()),
+            Cell::from("Values").style(self.theme.header_style()),
+            Cell::from("Rows").style(self.theme.header_style()),
+            Cell::from("Nulls").style(self.theme.header_style()),
+            Cell::from("Encoding").style(self.theme.header_style()),
+            Cell::from("First Values").style(self.theme.header_style()),
         ]);
 
         // Create rows from page info
@@ -247,12 +528,25 @@ impl<'a> RowGroupColumnMetadataComponent<'a> {
             .iter()
             .enumerate()
             .map(|(idx, page)| {
+                // Only V2 pages record their rows and nulls.
+                let v2 = |field: fn(&DataPageV2Info) -> u32| {
+                    page.v2
+                        .as_ref()
+                        .map_or("-".to_string(), |v2| commas(field(v2) as u64))
+                };
                 Row::new(vec![
-                    Cell::from((idx + 1).to_string()).fg(Color::White),
-                    Cell::from(page.page_type.clone()).fg(Color::Cyan),
-                    Cell::from(human_readable_bytes(page.size as u64)).fg(Color::White),
-                    Cell::from(commas(page.rows as u64)).fg(Color::White),
-                    Cell::from(page.encoding.clone()).fg(Color::Green),
+                    Cell::from((idx + 1).to_string()).fg(self.theme.text),
+                    Cell::from(page.page_type.clone()).fg(self.theme.label),
+                    Cell::from(human_readable_bytes(page.size as u64)).fg(self.theme.text),
+                    Cell::from(commas(page.rows as u64)).fg(self.theme.text),
+                    Cell::from(v2(|v2| v2.num_rows)).fg(self.theme.text),
+                    Cell::from(v2(|v2| v2.num_nulls)).fg(self.theme.text),
+                    Cell::from(page.encoding.clone()).fg(self.theme.good),
+                    match self.previews.get(idx) {
+                        Some(Some(Ok(values))) => Cell::from(values.join(", ")).fg(self.theme.text),
+                        Some(Some(Err(e))) => Cell::from(e.clone()).fg(self.theme.bad),
+                        _ => Cell::from("-").fg(self.theme.muted),
+                    },
                 ])
             })
             .collect();
@@ -260,20 +554,207 @@ impl<'a> RowGroupColumnMetadataComponent<'a> {
         let table = Table::new(
             rows,
             vec![
-                Constraint::Max(3),  // Page Number
-                Constraint::Fill(3), // Page Type
-                Constraint::Fill(3), // Size
-                Constraint::Fill(2), // Rows
-                Constraint::Fill(3), // Encoding
+                Constraint::Max(3),     // Page Number
+                Constraint::Length(15), // Page Type
+                Constraint::Fill(2),    // Size
+                Constraint::Fill(2),    // Values
+                Constraint::Fill(2),    // Rows
+                Constraint::Fill(2),    // Nulls
+                Constraint::Fill(3),    // Encoding
+                Constraint::Fill(5),    // First Values
             ],
         )
         .header(header)
         .block(
             Block::bordered()
                 .title("Pages")
-                .border_style(ratatui::style::Style::default().fg(Color::DarkGray)),
+                .border_style(self.theme.border_style()),
         );
 
         table.render(area, buf);
     }
+
+    /// The fields only V2 data page headers have, numbered like the page
+    /// headers table.
+    fn render_v2_headers_table(&self, area: Rect, buf: &mut Buffer) {
+        let header = Row::new(
+            [
+                "#",
+                "Rows",
+                "Nulls",
+                "Def Levels",
+                "Rep Levels",
+                "Compressed",
+            ]
+            .into_iter()
+            .map(|h| Cell::from(h).style(self.theme.header_style())),
+        );
+
+        let rows: Vec<Row> = self
+            .column_metadata
+            .page_headers
+            .headers
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, page)| page.v2.map(|v2| (idx, v2)))
+            .map(|(idx, v2)| {
+                let (compressed, color) = if v2.is_compressed {
+                    ("yes", self.theme.text)
+                } else {
+                    ("no", self.theme.muted)
+                };
+                Row::new(vec![
+                    Cell::from((idx + 1).to_string()).fg(self.theme.text),
+                    Cell::from(commas(v2.num_rows as u64)).fg(self.theme.text),
+                    Cell::from(commas(v2.num_nulls as u64)).fg(self.theme.text),
+                    Cell::from(human_readable_bytes(v2.def_levels_byte_len as u64))
+                        .fg(self.theme.text),
+                    Cell::from(human_readable_bytes(v2.rep_levels_byte_len as u64))
+                        .fg(self.theme.text),
+                    Cell::from(compressed).fg(color),
+                ])
+            })
+            .collect();
+
+        Table::new(
+            rows,
+            vec![
+                Constraint::Max(3),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::bordered()
+                .title("Data Page V2 Headers")
+                .border_style(self.theme.border_style()),
+        )
+        .render(area, buf);
+    }
+
+    /// The thrift header of each page, numbered like the pages table.
+    fn render_page_headers_table(&self, area: Rect, buf: &mut Buffer) {
+        let page_headers = &self.column_metadata.page_headers;
+        let header = Row::new(
+            [
+                "#",
+                "Offset",
+                "Header",
+                "Compressed",
+                "Uncompressed",
+                "Values",
+                "Def / Rep",
+                "Statistics",
+                "CRC",
+            ]
+            .into_iter()
+            .map(|h| Cell::from(h).style(self.theme.header_style())),
+        );
+
+        let or_dash = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+        let rows: Vec<Row> = page_headers
+            .headers
+            .iter()
+            .enumerate()
+            .map(|(idx, page)| {
+                Row::new(vec![
+                    Cell::from((idx + 1).to_string()).fg(self.theme.text),
+                    Cell::from(commas(page.offset)).fg(self.theme.muted),
+                    Cell::from(human_readable_bytes(page.header_size as u64)).fg(self.theme.muted),
+                    Cell::from(human_readable_bytes(page.compressed_size.max(0) as u64))
+                        .fg(self.theme.text),
+                    Cell::from(human_readable_bytes(page.uncompressed_size.max(0) as u64))
+                        .fg(self.theme.text),
+                    Cell::from(
+                        page.num_values
+                            .map_or("-".to_string(), |n| commas(n.max(0) as u64)),
+                    )
+                    .fg(self.theme.text),
+                    Cell::from(format!(
+                        "{} / {}",
+                        or_dash(&page.definition_level_encoding),
+                        or_dash(&page.repetition_level_encoding)
+                    ))
+                    .fg(self.theme.label),
+                    Cell::from(header_stats(page)).fg(self.theme.text),
+                    Cell::from(page.crc.map_or("-".to_string(), |crc| format!("{crc:08x}")))
+                        .fg(self.theme.muted),
+                ])
+            })
+            .collect();
+
+        let mut block = Block::bordered()
+            .title("Page Headers")
+            .border_style(self.theme.border_style());
+        if let Some(error) = &page_headers.error {
+            block =
+                block.title_bottom(Line::from(format!(" {error} ").fg(self.theme.bad)).centered());
+        }
+
+        Table::new(
+            rows,
+            vec![
+                Constraint::Max(3),
+                Constraint::Length(9),
+                Constraint::Length(7),
+                Constraint::Length(10),
+                Constraint::Length(12),
+                Constraint::Length(7),
+                Constraint::Length(13),
+                Constraint::Fill(1),
+                Constraint::Length(9),
+            ],
+        )
+        .header(header)
+        .block(block)
+        .render(area, buf);
+    }
+}
+
+/// Null count and whether min/max are set, from a page header's statistics.
+fn header_stats(header: &PageHeaderInfo) -> String {
+    let Some(stats) = &header.statistics else {
+        return "-".to_string();
+    };
+    let mut parts = Vec::new();
+    if let Some(nulls) = stats.null_count {
+        parts.push(format!("{} nulls", commas(nulls.max(0) as u64)));
+    }
+    if let Some(distinct) = stats.distinct_count {
+        parts.push(format!("{} distinct", commas(distinct.max(0) as u64)));
+    }
+    if stats.has_min_max {
+        parts.push("min/max".to_string());
+    }
+    if parts.is_empty() {
+        "-".to_string()
+    } else {
+        parts.join(" · ")
+    }
+}
+
+/// Smallest, median and largest of `data`, or zeros when it is empty.
+fn min_median_max(data: &[u64]) -> (u64, u64, u64) {
+    let mut sorted = data.to_vec();
+    sorted.sort_unstable();
+    match (sorted.first(), sorted.last()) {
+        (Some(&min), Some(&max)) => (min, sorted[sorted.len() / 2], max),
+        _ => (0, 0, 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_median_max() {
+        assert_eq!(min_median_max(&[]), (0, 0, 0));
+        assert_eq!(min_median_max(&[7]), (7, 7, 7));
+        assert_eq!(min_median_max(&[900, 3, 40, 5, 6]), (3, 6, 900));
+    }
 }
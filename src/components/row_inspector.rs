@@ -0,0 +1,419 @@
+use std::collections::BTreeSet;
+
+use image::RgbImage;
+use image::imageops::{self, FilterType};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+use serde_json::Value;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::file::blob::{Embedding, ImagePreview, RowImages};
+use crate::file::json_path::cell_json;
+use crate::file::logical::json_one_line;
+use crate::file::sample_data::ParquetSampleData;
+use crate::file::utils::{display_width, human_readable_bytes};
+use crate::theme::Theme;
+
+/// Rows of text cells an image preview takes at most.
+const IMAGE_HEIGHT: u16 = 12;
+/// Widest the sparkline of an embedding gets.
+const SPARKLINE_WIDTH: usize = 32;
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Modal showing every value of one preview row, column after column. Lists,
+/// structs, maps and JSON values are shown as trees whose objects and arrays
+/// can be folded. Images in binary columns are drawn under their column, and
+/// float lists are summed up as embeddings.
+pub struct RowInspector<'a> {
+    data: &'a ParquetSampleData,
+    row: usize,
+    cursor: usize,
+    folded: Option<&'a BTreeSet<String>>,
+    images: Option<&'a RowImages>,
+    close_key: String,
+    theme: Theme,
+}
+
+/// One line of the tree: a column, or a field or item of a value.
+struct Node {
+    depth: usize,
+    /// `column`, `column.field` or `column.field[2]`, what folding is keyed
+    /// by.
+    path: String,
+    label: String,
+    parent: Option<usize>,
+    kind: NodeKind,
+}
+
+enum NodeKind {
+    Text(String),
+    Null,
+    Object(usize),
+    /// An array with its length, and what it is as an embedding if it is a
+    /// list of floats.
+    Array(usize, Option<Embedding>),
+    /// An object or array shown on one line.
+    Folded(String),
+}
+
+impl<'a> RowInspector<'a> {
+    /// `row` counts from the first row loaded.
+    pub fn new(data: &'a ParquetSampleData, row: usize) -> Self {
+        Self {
+            data,
+            row,
+            cursor: 0,
+            folded: None,
+            images: None,
+            close_key: "Esc".to_string(),
+            theme: Theme::default(),
+        }
+    }
+
+    /// Line of the tree under the cursor.
+    pub fn with_cursor(mut self, cursor: usize) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    /// Paths of the objects and arrays shown folded.
+    pub fn with_folded(mut self, folded: &'a BTreeSet<String>) -> Self {
+        self.folded = Some(folded);
+        self
+    }
+
+    /// Images of the row by column, from `blob::read_images`.
+    pub fn with_images(mut self, images: &'a RowImages) -> Self {
+        self.images = Some(images);
+        self
+    }
+
+    pub fn with_close_key(mut self, key: String) -> Self {
+        self.close_key = key;
+        self
+    }
+
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self
+    }
+
+    /// Number of lines of the tree, used to bound the cursor.
+    pub fn line_count(&self) -> usize {
+        self.nodes().len()
+    }
+
+    /// Path of the object or array on line `line`, `None` for other values.
+    pub fn fold_path(&self, line: usize) -> Option<String> {
+        self.nodes()
+            .into_iter()
+            .nth(line)
+            .filter(|node| !matches!(node.kind, NodeKind::Text(_) | NodeKind::Null))
+            .map(|node| node.path)
+    }
+
+    /// Line of the object or array holding line `line`.
+    pub fn parent(&self, line: usize) -> Option<usize> {
+        self.nodes().get(line).and_then(|node| node.parent)
+    }
+
+    fn nodes(&self) -> Vec<Node> {
+        let Some(row) = self.data.rows.get(self.row) else {
+            return vec![];
+        };
+        let mut nodes = Vec::new();
+        for (column, cell) in self.data.flattened_columns.iter().zip(row) {
+            match cell_json(cell) {
+                Some(json) => self.push_value(&mut nodes, column, column.clone(), None, 0, &json),
+                None => nodes.push(Node {
+                    depth: 0,
+                    path: column.clone(),
+                    label: column.clone(),
+                    parent: None,
+                    kind: NodeKind::Text(cell.clone()),
+                }),
+            }
+        }
+        nodes
+    }
+
+    fn push_value(
+        &self,
+        nodes: &mut Vec<Node>,
+        path: &str,
+        label: String,
+        parent: Option<usize>,
+        depth: usize,
+        value: &Value,
+    ) {
+        let folded = self.folded.is_some_and(|folded| folded.contains(path));
+        let kind = match value {
+            Value::Object(_) | Value::Array(_) if folded => NodeKind::Folded(json_one_line(value)),
+            Value::Object(fields) => NodeKind::Object(fields.len()),
+            Value::Array(items) => NodeKind::Array(items.len(), Embedding::from_json(value)),
+            Value::Null => NodeKind::Null,
+            other => NodeKind::Text(json_one_line(other)),
+        };
+        let expanded = matches!(kind, NodeKind::Object(_) | NodeKind::Array(..));
+        let index = nodes.len();
+        nodes.push(Node {
+            depth,
+            path: path.to_string(),
+            label,
+            parent,
+            kind,
+        });
+        if !expanded {
+            return;
+        }
+        match value {
+            Value::Object(fields) => {
+                for (key, field) in fields {
+                    let path = format!("{path}.{key}");
+                    self.push_value(nodes, &path, key.clone(), Some(index), depth + 1, field);
+                }
+            }
+            Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    let path = format!("{path}[{i}]");
+                    self.push_value(nodes, &path, format!("[{i}]"), Some(index), depth + 1, item);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The image of a column's binary value, if it holds one.
+    fn image(&self, node: &Node) -> Option<&Result<ImagePreview, String>> {
+        if node.depth > 0 || !matches!(node.kind, NodeKind::Text(_)) {
+            return None;
+        }
+        self.images?.get(&node.path)
+    }
+
+    /// What is shown for a column holding an image instead of its bytes.
+    fn image_caption(&self, node: &Node) -> Option<String> {
+        Some(match self.image(node)? {
+            Ok(image) => format!(
+                "{} {}×{} · {}",
+                image.format,
+                image.width,
+                image.height,
+                human_readable_bytes(image.size as u64)
+            ),
+            Err(e) => e.clone(),
+        })
+    }
+
+    fn node_spans(&self, node: &Node) -> Vec<Span<'static>> {
+        let marker = match node.kind {
+            NodeKind::Object(_) | NodeKind::Array(..) => "▾ ",
+            NodeKind::Folded(_) => "▸ ",
+            _ => "  ",
+        };
+        let mut label = Style::default().fg(self.theme.label);
+        if node.depth == 0 {
+            label = label.add_modifier(Modifier::BOLD);
+        }
+        let muted = Style::default().fg(self.theme.muted);
+        let text = Style::default().fg(self.theme.text);
+        let mut spans = vec![
+            Span::raw("  ".repeat(node.depth)),
+            Span::styled(marker, muted),
+            Span::styled(node.label.clone(), label),
+        ];
+        if let Some(caption) = self.image_caption(node) {
+            spans.extend([Span::styled(": ", muted), Span::styled(caption, text)]);
+            return spans;
+        }
+        spans.extend(match &node.kind {
+            NodeKind::Object(len) => vec![Span::styled(format!(" {{{len}}}"), muted)],
+            NodeKind::Array(len, None) => vec![Span::styled(format!(" [{len}]"), muted)],
+            NodeKind::Array(len, Some(embedding)) => vec![
+                Span::styled(format!(" [{len}] norm {:.3} ", embedding.norm), muted),
+                Span::styled(
+                    sparkline(&embedding.values, SPARKLINE_WIDTH),
+                    Style::default().fg(self.theme.header),
+                ),
+            ],
+            NodeKind::Null => vec![Span::styled(": ", muted), Span::styled("null", muted)],
+            NodeKind::Text(value) | NodeKind::Folded(value) => {
+                vec![Span::styled(": ", muted), Span::styled(value.clone(), text)]
+            }
+        });
+        spans
+    }
+}
+
+/// `values` as a line of bars from `▁` (the smallest) to `█` (the
+/// largest), averaged down to at most `width` bars.
+fn sparkline(values: &[f64], width: usize) -> String {
+    let chunk = values.len().div_ceil(width.max(1)).max(1);
+    let bars: Vec<f64> = values
+        .chunks(chunk)
+        .map(|chunk| chunk.iter().sum::<f64>() / chunk.len() as f64)
+        .collect();
+    let (min, max) = bars
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &v| {
+            (min.min(v), max.max(v))
+        });
+    bars.iter()
+        .map(|&v| {
+            let level = if max > min {
+                ((v - min) / (max - min) * (SPARKS.len() - 1) as f64).round() as usize
+            } else {
+                SPARKS.len() / 2
+            };
+            SPARKS[level.min(SPARKS.len() - 1)]
+        })
+        .collect()
+}
+
+/// `image` drawn with half blocks, two pixels to a cell, shrunk to fit in
+/// `width` × `height` cells and indented by `indent` columns.
+fn image_lines(image: &RgbImage, width: u16, height: u16, indent: usize) -> Vec<Line<'static>> {
+    let (image_width, image_height) = image.dimensions();
+    if image_width == 0 || image_height == 0 || width == 0 || height == 0 {
+        return vec![];
+    }
+    let scale = (width as f64 / image_width as f64)
+        .min(2.0 * height as f64 / image_height as f64)
+        .min(1.0);
+    let columns = ((image_width as f64 * scale).round() as u32).max(1);
+    let pixel_rows = ((image_height as f64 * scale).round() as u32).max(1);
+    let small = imageops::resize(image, columns, pixel_rows, FilterType::Triangle);
+    let color = |x, y| {
+        let [r, g, b] = small.get_pixel(x, y).0;
+        Color::Rgb(r, g, b)
+    };
+    (0..pixel_rows.div_ceil(2))
+        .map(|row| {
+            let mut spans = vec![Span::raw(" ".repeat(indent))];
+            spans.extend((0..columns).map(|x| {
+                let top = color(x, 2 * row);
+                let style = if 2 * row + 1 < pixel_rows {
+                    Style::default().fg(top).bg(color(x, 2 * row + 1))
+                } else {
+                    Style::default().fg(top)
+                };
+                Span::styled("▀", style)
+            }));
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Break `spans` into lines `width` columns wide, never inside a grapheme.
+/// Lines after the first are indented by `indent` columns.
+fn wrap_spans(spans: Vec<Span<'static>>, width: usize, indent: usize) -> Vec<Line<'static>> {
+    let width = width.max(indent + 2);
+    let mut lines = vec![];
+    let mut line: Vec<Span<'static>> = vec![];
+    let mut used = 0;
+    for span in spans {
+        let mut piece = String::new();
+        for grapheme in span.content.graphemes(true) {
+            let grapheme_width = display_width(grapheme);
+            if used + grapheme_width > width && used > indent {
+                if !piece.is_empty() {
+                    line.push(Span::styled(std::mem::take(&mut piece), span.style));
+                }
+                lines.push(Line::from(std::mem::take(&mut line)));
+                line.push(Span::raw(" ".repeat(indent)));
+                used = indent;
+            }
+            piece.push_str(grapheme);
+            used += grapheme_width;
+        }
+        if !piece.is_empty() {
+            line.push(Span::styled(piece, span.style));
+        }
+    }
+    lines.push(Line::from(line));
+    lines
+}
+
+impl<'a> Widget for RowInspector<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [popup] = Layout::horizontal([Constraint::Percentage(70)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [popup] = Layout::vertical([Constraint::Percentage(80)])
+            .flex(Flex::Center)
+            .areas(popup);
+
+        Clear.render(popup, buf);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.accent))
+            .title(
+                Line::from(format!(" Row {} ", self.data.position(self.row) + 1))
+                    .style(self.theme.header_style()),
+            )
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(" ←/→", Style::default().fg(self.theme.good)),
+                    Span::styled(" fold ", Style::default().fg(self.theme.muted)),
+                    Span::styled(
+                        format!(" {}", self.close_key),
+                        Style::default().fg(self.theme.good),
+                    ),
+                    Span::styled(" close ", Style::default().fg(self.theme.muted)),
+                ])
+                .right_aligned(),
+            );
+        let inner = block.inner(popup);
+
+        // Wrap each node's line to the width, keeping track of where the
+        // cursor's lines are so they can be scrolled into view.
+        let mut lines = Vec::new();
+        let mut cursor_lines = 0..0;
+        for (i, node) in self.nodes().iter().enumerate() {
+            let start = lines.len();
+            let mut wrapped = wrap_spans(
+                self.node_spans(node),
+                inner.width as usize,
+                2 * node.depth + 4,
+            );
+            if let Some(Ok(image)) = self.image(node) {
+                let indent = 2 * node.depth + 4;
+                wrapped.extend(image_lines(
+                    &image.thumbnail,
+                    inner.width.saturating_sub(indent as u16),
+                    IMAGE_HEIGHT.min(inner.height.saturating_sub(1)),
+                    indent,
+                ));
+            }
+            if i == self.cursor {
+                for line in &mut wrapped {
+                    *line =
+                        std::mem::take(line).style(Style::default().bg(self.theme.highlight_bg));
+                }
+                cursor_lines = start..start + wrapped.len();
+            }
+            lines.extend(wrapped);
+        }
+
+        let height = inner.height as usize;
+        let scroll = if cursor_lines.end <= height {
+            0
+        } else {
+            // Keep the cursor in the middle once it's past the first screen.
+            cursor_lines
+                .start
+                .saturating_sub(height.saturating_sub(cursor_lines.len()) / 2)
+                .min(lines.len().saturating_sub(height))
+        };
+
+        Paragraph::new(lines)
+            .block(block)
+            .scroll((scroll as u16, 0))
+            .render(popup, buf);
+    }
+}
@@ -1,5 +1,7 @@
+pub mod profile;
 pub mod table;
 pub mod tree;
 
+pub use profile::ColumnProfilePanel;
 pub use table::FileSchemaTable;
 pub use tree::SchemaTreeComponent;
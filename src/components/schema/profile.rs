@@ -0,0 +1,196 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    text::Line,
+    widgets::{Block, Cell, Paragraph, Row, Table, Widget, Wrap},
+};
+
+use crate::file::profile::{
+    ColumnProfile, FileProfile, NAN_STATS_NOTE, NestedProfile, RANDOM_ENTROPY, format_entropy,
+    format_lengths, format_specials, format_top_values,
+};
+use crate::file::utils::commas;
+use crate::theme::Theme;
+
+/// Profile of the column selected in the Schema tab, from `:profile`.
+pub struct ColumnProfilePanel<'a> {
+    profile: &'a FileProfile,
+    /// Leaf position of the selected column, `None` on a group.
+    column: Option<usize>,
+    /// Dotted path of the selected group, for list and map columns.
+    group: Option<String>,
+    theme: Theme,
+}
+
+impl<'a> ColumnProfilePanel<'a> {
+    pub fn new(profile: &'a FileProfile, column: Option<usize>) -> Self {
+        Self {
+            profile,
+            column,
+            group: None,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_group(mut self, group: Option<String>) -> Self {
+        self.group = group;
+        self
+    }
+
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self
+    }
+
+    fn pair(&self, label: &'static str, value: String) -> Row<'static> {
+        Row::new(vec![
+            Cell::from(label).fg(self.theme.label),
+            Cell::from(value).fg(self.theme.text),
+        ])
+    }
+
+    fn render_column(&self, column: &ColumnProfile, area: Rect, buf: &mut Buffer) {
+        let number = |value: Option<f64>| value.map_or("-".to_string(), |v| format!("{v:.2}"));
+        let left = vec![
+            self.pair(
+                "Nulls",
+                format!("{} ({:.1}%)", commas(column.nulls), column.null_percent),
+            ),
+            self.pair("Distinct", format!("≈{}", commas(column.distinct))),
+            self.pair("Min", column.min.clone().unwrap_or_else(|| "-".into())),
+            self.pair("Max", column.max.clone().unwrap_or_else(|| "-".into())),
+            Row::new(vec![
+                Cell::from("Ratio").fg(self.theme.label),
+                match column.ratio {
+                    Some(ratio) => {
+                        Cell::from(format!("{ratio:.2}x")).style(self.theme.ratio_style(ratio))
+                    }
+                    None => Cell::from("-").fg(self.theme.text),
+                },
+            ]),
+            // Random-looking bytes explain a ratio near 1x.
+            match column.entropy {
+                Some(entropy) if entropy >= RANDOM_ENTROPY => Row::new(vec![
+                    Cell::from("Entropy").fg(self.theme.label),
+                    Cell::from(format!("{}, looks random", format_entropy(column.entropy)))
+                        .fg(self.theme.bad),
+                ]),
+                entropy => self.pair("Entropy", format_entropy(entropy)),
+            },
+        ];
+        let mut right = vec![
+            self.pair("Mean", number(column.mean)),
+            self.pair("Stddev", number(column.stddev)),
+        ];
+        match &column.encoding_hint {
+            Some(hint) => right.extend([
+                self.pair("Avg run", format!("{:.1} values", hint.avg_run_length)),
+                self.pair("Avg delta", number(Some(hint.avg_delta))),
+            ]),
+            None => right.push(self.pair("Avg length", number(column.avg_length))),
+        }
+        if let Some(specials) = &column.specials {
+            right.push(self.pair("NaN/Inf/-0", format_specials(specials)));
+        }
+        if let Some(nested) = self.profile.enclosing_nested(&column.column) {
+            right.push(self.pair(
+                if nested.kind == "Map" {
+                    "Map size"
+                } else {
+                    "List length"
+                },
+                format_lengths(nested),
+            ));
+        }
+
+        let [left_area, right_area, top_area] = Layout::horizontal([
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+            Constraint::Fill(3),
+        ])
+        .spacing(1)
+        .areas(area);
+        let widths = [Constraint::Length(11), Constraint::Fill(1)];
+        Table::new(left, widths).render(left_area, buf);
+        Table::new(right, widths).render(right_area, buf);
+
+        // Numeric columns have no top values; their third panel explains
+        // NaNs instead when there are any, or suggests a better encoding.
+        let suggestion = column
+            .encoding_hint
+            .as_ref()
+            .and_then(|hint| hint.suggestion.as_ref());
+        let (label, text) = if column.specials.is_some_and(|s| s.nan > 0) {
+            ("NaN and statistics", NAN_STATS_NOTE.to_string())
+        } else if let Some(suggestion) = suggestion {
+            ("Try encoding", suggestion.to_string())
+        } else if column.top_values.is_empty() {
+            ("Top values", "-".to_string())
+        } else {
+            ("Top values", format_top_values(&column.top_values))
+        };
+        Paragraph::new(vec![
+            Line::from(label.fg(self.theme.label)),
+            Line::from(text.fg(self.theme.text)),
+        ])
+        .wrap(Wrap { trim: true })
+        .render(top_area, buf);
+    }
+
+    fn render_nested(&self, nested: &NestedProfile, area: Rect, buf: &mut Buffer) {
+        let mut rows = vec![
+            self.pair(
+                if nested.kind == "Map" {
+                    "Maps"
+                } else {
+                    "Lists"
+                },
+                commas(nested.count),
+            ),
+            self.pair(
+                "Length",
+                format!("{} (min / avg / max)", format_lengths(nested)),
+            ),
+        ];
+        if let Some(keys) = nested.distinct_keys {
+            rows.push(self.pair("Keys", format!("≈{} distinct", commas(keys))));
+        }
+        Table::new(rows, [Constraint::Length(11), Constraint::Fill(1)]).render(area, buf);
+    }
+}
+
+impl<'a> Widget for ColumnProfilePanel<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let column = self.column.and_then(|idx| self.profile.columns.get(idx));
+        let nested = self
+            .group
+            .as_deref()
+            .and_then(|group| self.profile.nested(group));
+        let title = match (column, nested) {
+            (Some(column), _) => format!(" Profile: {} ", column.column),
+            (None, Some(nested)) => format!(" Profile: {} ", nested.column),
+            (None, None) => " Profile ".to_string(),
+        };
+        let sample = format!(
+            " sampled {} of {} rows ",
+            commas(self.profile.sampled_rows),
+            commas(self.profile.total_rows)
+        );
+        let block = Block::bordered()
+            .title(Line::from(title.fg(self.theme.accent).bold()))
+            .title_bottom(Line::from(sample.fg(self.theme.muted)).centered())
+            .border_style(self.theme.border_style());
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        match (column, nested) {
+            (Some(column), _) => self.render_column(column, inner, buf),
+            (None, Some(nested)) => self.render_nested(nested, inner, buf),
+            (None, None) => {
+                Paragraph::new("Select a column to see its profile".fg(self.theme.muted))
+                    .render(inner, buf)
+            }
+        }
+    }
+}
@@ -1,47 +1,122 @@
+use crate::file::row_groups::sort_direction;
 use crate::file::schema::FileSchema;
+use crate::file::utils::{display_width, truncate_start};
 use ratatui::{
     buffer::Buffer,
-    layout::{Constraint, Rect},
+    layout::{Constraint, Layout, Rect},
     prelude::Color,
     style::Stylize,
     symbols::border,
     text::Line,
-    widgets::{Block, Cell, Row, Table, Widget},
+    widgets::{Block, Borders, Cell, Row, Table, Widget},
 };
 use std::cmp::min;
+use std::collections::BTreeMap;
 
 use crate::file::Renderable;
+use crate::theme::Theme;
+
+/// Statistics columns, in the order they scroll into view. Field ID is
+/// only shown when field ids are toggled on.
+const HEADERS: [&str; 17] = [
+    "Field ID",
+    "Repetition",
+    "Physical",
+    "Logical",
+    "Compressed",
+    "% of File",
+    "Uncompressed",
+    "Arrow est.",
+    "Ratio",
+    "Encodings",
+    "Compression",
+    "Min",
+    "Max",
+    "Nulls",
+    "Distinct",
+    "Sort Key",
+    "Column Order",
+];
+
+/// Narrowest a statistics column gets.
+const MIN_COLUMN_WIDTH: u16 = 12;
+
+/// Widest the frozen name column gets; longer paths keep their end.
+const NAME_COLUMN_WIDTH: u16 = 20;
 
 pub struct FileSchemaTable<'a> {
     pub schema: &'a FileSchema,
+    /// Node indices shown in the schema tree next to the table; `None` shows
+    /// every node.
+    pub visible_nodes: Option<Vec<usize>>,
     pub selected_index: usize,
+    pub selected_group: Option<usize>,
+    /// Sampled distinct counts by leaf position, see
+    /// [`crate::file::distinct`].
+    pub distinct_estimates: Option<&'a BTreeMap<usize, u64>>,
     pub title: String,
     pub title_color: Color,
     pub selected_color: Color,
     pub border_style: border::Set,
     pub horizontal_scroll: usize,
     pub vertical_scroll: usize,
+    /// Show the frozen name column even when not scrolled, e.g. while the
+    /// schema tree is hidden.
+    pub show_names: bool,
+    pub show_field_ids: bool,
+    /// The rows are the leaves largest first, as marked on `% of File`.
+    pub sorted_by_size: bool,
+    pub theme: Theme,
 }
 
 impl<'a> FileSchemaTable<'a> {
     pub fn new(schema: &'a FileSchema) -> Self {
         Self {
             schema,
+            visible_nodes: None,
             selected_index: 0,
+            selected_group: None,
+            distinct_estimates: None,
             title: "Column Statistics".to_string(),
             title_color: Color::Green,
             selected_color: Color::Yellow,
             border_style: border::ROUNDED,
             horizontal_scroll: 0,
             vertical_scroll: 0,
+            show_names: false,
+            show_field_ids: false,
+            sorted_by_size: false,
+            theme: Theme::default(),
         }
     }
 
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.title_color = theme.accent;
+        self.selected_color = theme.selection_bg;
+        self.theme = *theme;
+        self
+    }
+
     pub fn with_selected_index(mut self, index: usize) -> Self {
         self.selected_index = index;
         self
     }
 
+    pub fn with_visible_nodes(mut self, nodes: Vec<usize>) -> Self {
+        self.visible_nodes = Some(nodes);
+        self
+    }
+
+    pub fn with_selected_group(mut self, group: Option<usize>) -> Self {
+        self.selected_group = group;
+        self
+    }
+
+    pub fn with_distinct_estimates(mut self, estimates: &'a BTreeMap<usize, u64>) -> Self {
+        self.distinct_estimates = Some(estimates);
+        self
+    }
+
     pub fn with_title(mut self, title: String) -> Self {
         self.title = title;
         self
@@ -68,6 +143,27 @@ impl<'a> FileSchemaTable<'a> {
         self
     }
 
+    pub fn with_show_names(mut self, show_names: bool) -> Self {
+        self.show_names = show_names;
+        self
+    }
+
+    pub fn with_field_ids(mut self, show_field_ids: bool) -> Self {
+        self.show_field_ids = show_field_ids;
+        self
+    }
+
+    pub fn with_sorted_by_size(mut self, sorted: bool) -> Self {
+        self.sorted_by_size = sorted;
+        self
+    }
+
+    /// Whether the column names are pinned to the left of the statistics.
+    /// Once scrolled, the rows would otherwise only be named in the tree.
+    pub fn names_frozen(horizontal_scroll: usize, show_names: bool) -> bool {
+        horizontal_scroll > 0 || show_names
+    }
+
     pub fn scroll_left(&mut self) {
         if self.horizontal_scroll > 0 {
             self.horizontal_scroll -= 1;
@@ -78,6 +174,67 @@ impl<'a> FileSchemaTable<'a> {
         self.horizontal_scroll += 1;
     }
 
+    /// Number of statistics columns shown in a table `area_width` wide.
+    pub fn visible_columns(area_width: u16, names_frozen: bool) -> usize {
+        // Account for borders and spacing
+        let mut available_width = area_width.saturating_sub(4);
+        if names_frozen {
+            available_width = available_width.saturating_sub(NAME_COLUMN_WIDTH + 1);
+        }
+        (available_width / MIN_COLUMN_WIDTH).max(1) as usize
+    }
+
+    /// Largest useful horizontal scroll for a table `area_width` wide. Any
+    /// scroll freezes the names, so they are counted in.
+    pub fn max_horizontal_scroll(area_width: u16, show_field_ids: bool) -> usize {
+        (HEADERS.len() - Self::first_column(show_field_ids))
+            .saturating_sub(Self::visible_columns(area_width, true))
+    }
+
+    /// Index in `HEADERS` of the first column shown: Field ID is skipped
+    /// unless field ids are on.
+    fn first_column(show_field_ids: bool) -> usize {
+        usize::from(!show_field_ids)
+    }
+
+    /// The frozen name column: the path of every row, cut to fit from the
+    /// left so the leaf name stays visible. Returns it with its width.
+    fn name_table(&self, row_nodes: &[usize], selected_node: Option<usize>) -> (Table<'a>, u16) {
+        let names: Vec<String> = row_nodes
+            .iter()
+            .map(|&node| self.schema.column_path(node))
+            .collect();
+        let width = names
+            .iter()
+            .map(|name| display_width(name))
+            .max()
+            .unwrap_or(0)
+            .clamp("Column".len(), NAME_COLUMN_WIDTH as usize);
+        let rows: Vec<Row> = names
+            .into_iter()
+            .zip(row_nodes)
+            .map(|(name, &node)| {
+                let text = truncate_start(&name, width);
+                let row = Row::new([Cell::from(text).fg(self.theme.label)]);
+                if selected_node == Some(node) {
+                    row.style(self.theme.selection_style())
+                } else {
+                    row
+                }
+            })
+            .collect();
+        let table = Table::new(rows, [Constraint::Length(width as u16)])
+            .header(Row::new([
+                Cell::from("Column").style(self.theme.header_style())
+            ]))
+            .block(
+                Block::new()
+                    .borders(Borders::RIGHT)
+                    .border_style(self.theme.border_style()),
+            );
+        (table, width as u16 + 1)
+    }
+
     pub fn get_max_scroll(&self) -> usize {
         // Calculate how many columns we can show at full width
         let available_width = 80; // Assume 80 characters available
@@ -85,54 +242,60 @@ impl<'a> FileSchemaTable<'a> {
         let max_visible_columns = available_width / min_column_width;
 
         // Total columns minus visible columns
-        let total_columns = 10usize; // We have 10 columns
-        total_columns.saturating_sub(max_visible_columns as usize)
+        let total_columns = HEADERS.len() - Self::first_column(self.show_field_ids);
+        total_columns.saturating_sub(max_visible_columns)
     }
 }
 
 impl<'a> Widget for FileSchemaTable<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let all_headers = [
-            "Repetition",
-            "Physical",
-            "Compressed",
-            "Uncompressed",
-            "Ratio",
-            "Encodings",
-            "Compression",
-            "Min",
-            "Max",
-            "Nulls",
-        ];
-
         // Calculate how many columns we can show at full width
-        let available_width = area.width.saturating_sub(4); // Account for borders and spacing
-        let min_column_width = 12;
-        let max_visible_columns = (available_width / min_column_width).max(1);
+        let names_frozen = Self::names_frozen(self.horizontal_scroll, self.show_names);
+        let max_visible_columns = Self::visible_columns(area.width, names_frozen);
 
         // Clamp scroll offset to valid range
-        let max_scroll = all_headers
-            .len()
-            .saturating_sub(max_visible_columns as usize);
-        let horizontal_scroll = self.horizontal_scroll.min(max_scroll);
+        let horizontal_scroll = self
+            .horizontal_scroll
+            .min(Self::max_horizontal_scroll(area.width, self.show_field_ids));
+        let start_col = Self::first_column(self.show_field_ids) + horizontal_scroll;
 
         // Calculate visible rows based on vertical scroll and available height
         let visible_rows_count = area.height.saturating_sub(1) as usize;
 
+        // Rows line up with the tree next to the table, which also shows the
+        // root on its first line.
+        let visible_nodes = self
+            .visible_nodes
+            .clone()
+            .unwrap_or_else(|| (0..self.schema.columns.len()).collect());
+        let row_nodes: Vec<usize> = visible_nodes
+            .into_iter()
+            .skip(self.vertical_scroll + 1)
+            .take(visible_rows_count)
+            .collect();
+        let selected_node = self
+            .selected_group
+            .or_else(|| self.schema.leaf_schema_index(self.selected_index));
+
         // Generate table data with only visible columns and rows
-        let (visible_rows, column_widths) = self.schema.generate_table_rows_with_scroll(
-            self.selected_index,
-            horizontal_scroll,
-            max_visible_columns as usize,
-            self.vertical_scroll,
-            visible_rows_count,
+        let (visible_rows, column_widths) = self.schema.generate_table_rows_for_nodes(
+            selected_node,
+            start_col,
+            max_visible_columns,
+            &row_nodes,
+            self.distinct_estimates.unwrap_or(&BTreeMap::new()),
+            &self.theme,
         );
 
         // Get visible columns
-        let visible_headers: Vec<_> = all_headers
+        let visible_headers: Vec<String> = HEADERS
             .iter()
-            .skip(horizontal_scroll)
-            .take(max_visible_columns as usize)
+            .map(|&header| match header {
+                "% of File" if self.sorted_by_size => format!("{header} ↓"),
+                _ => header.to_string(),
+            })
+            .skip(start_col)
+            .take(max_visible_columns)
             .collect();
 
         // Include header widths in the calculation and create constraints
@@ -141,31 +304,59 @@ impl<'a> Widget for FileSchemaTable<'a> {
             .enumerate()
             .map(|(i, header)| {
                 let content_width = column_widths.get(i).cloned().unwrap_or(0);
-                let header_width = header.len();
+                let header_width = display_width(header);
                 // Use maximum of 30 for readability
                 Constraint::Length(min(content_width.max(header_width), 30) as u16 + 1)
             })
             .collect();
 
-        let table_widget = Table::new(visible_rows, col_constraints)
+        let mut block = Block::bordered()
+            .title(
+                Line::from(self.title.clone())
+                    .centered()
+                    .bold()
+                    .fg(self.title_color),
+            )
+            .border_set(self.border_style)
+            .border_style(self.theme.border_style());
+        let sort_keys = self.schema.sort_keys();
+        if !sort_keys.is_empty() {
+            let sorted_by = sort_keys
+                .iter()
+                .map(|(path, key)| {
+                    format!("{path} {}", sort_direction(key.descending, key.nulls_first))
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            // The bottom border holds the column scrollbar.
+            block = block.title(
+                Line::from(format!(" Sorted by {sorted_by} "))
+                    .right_aligned()
+                    .bold()
+                    .fg(self.theme.accent),
+            );
+        }
+        let mut stats_area = block.inner(area);
+        block.render(area, buf);
+
+        if names_frozen {
+            let (names, width) = self.name_table(&row_nodes, selected_node);
+            let [names_area, rest] =
+                Layout::horizontal([Constraint::Length(width), Constraint::Fill(1)])
+                    .spacing(1)
+                    .areas(stats_area);
+            names.render(names_area, buf);
+            stats_area = rest;
+        }
+
+        Table::new(visible_rows, col_constraints)
             .header(Row::new(
                 visible_headers
                     .into_iter()
-                    .map(|h| Cell::from(*h).bold().fg(Color::Yellow)),
+                    .map(|h| Cell::from(h).style(self.theme.header_style())),
             ))
             .column_spacing(1)
-            .block(
-                Block::bordered()
-                    .title(
-                        Line::from(self.title.clone())
-                            .centered()
-                            .bold()
-                            .fg(self.title_color),
-                    )
-                    .border_set(self.border_style),
-            );
-
-        table_widget.render(area, buf);
+            .render(stats_area, buf);
     }
 }
 
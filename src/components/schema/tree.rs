@@ -1,4 +1,5 @@
-use crate::file::schema::SchemaInfo;
+use crate::file::schema::{SchemaInfo, column_path, field_id_label};
+use crate::theme::Theme;
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -7,10 +8,21 @@ use ratatui::{
     text::Line,
     widgets::{Block, List, ListItem, Widget},
 };
+use std::collections::BTreeSet;
 
 pub struct SchemaTreeComponent<'a> {
     pub schema_columns: &'a Vec<SchemaInfo>,
+    /// Node indices to draw; `None` draws every node.
+    pub visible_nodes: Option<Vec<usize>>,
+    pub collapsed_groups: Option<&'a BTreeSet<usize>>,
+    /// Leaves marked with `★`.
+    pub bookmarks: Option<&'a BTreeSet<usize>>,
+    /// Label leaves with their dotted path instead of tree connectors.
+    pub flat: bool,
+    /// Follow each node with its field id, e.g. `id #1`.
+    pub field_ids: bool,
     pub selected_index: usize,
+    pub selected_group: Option<usize>,
     pub scroll_offset: usize,
     pub title: String,
     pub title_color: Color,
@@ -20,13 +32,20 @@ pub struct SchemaTreeComponent<'a> {
     pub selected_color: Color,
     pub border_style: border::Set,
     pub show_legend: bool,
+    pub theme: Theme,
 }
 
 impl<'a> SchemaTreeComponent<'a> {
     pub fn new(schema_columns: &'a Vec<SchemaInfo>) -> Self {
         Self {
             schema_columns,
+            visible_nodes: None,
+            collapsed_groups: None,
+            bookmarks: None,
+            flat: false,
+            field_ids: false,
             selected_index: 0,
+            selected_group: None,
             scroll_offset: 0,
             title: "Schema Tree".to_string(),
             title_color: Color::Yellow,
@@ -36,14 +55,55 @@ impl<'a> SchemaTreeComponent<'a> {
             selected_color: Color::Yellow,
             border_style: border::ROUNDED,
             show_legend: true,
+            theme: Theme::default(),
         }
     }
 
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.title_color = theme.accent;
+        self.root_color = theme.accent;
+        self.primitive_color = theme.text;
+        self.group_color = theme.group;
+        self.selected_color = theme.selection_bg;
+        self.theme = *theme;
+        self
+    }
+
     pub fn with_selected_index(mut self, index: usize) -> Self {
         self.selected_index = index;
         self
     }
 
+    pub fn with_visible_nodes(mut self, nodes: Vec<usize>) -> Self {
+        self.visible_nodes = Some(nodes);
+        self
+    }
+
+    pub fn with_collapsed_groups(mut self, collapsed: &'a BTreeSet<usize>) -> Self {
+        self.collapsed_groups = Some(collapsed);
+        self
+    }
+
+    pub fn with_bookmarks(mut self, bookmarks: &'a BTreeSet<usize>) -> Self {
+        self.bookmarks = Some(bookmarks);
+        self
+    }
+
+    pub fn with_flat(mut self, flat: bool) -> Self {
+        self.flat = flat;
+        self
+    }
+
+    pub fn with_field_ids(mut self, field_ids: bool) -> Self {
+        self.field_ids = field_ids;
+        self
+    }
+
+    pub fn with_selected_group(mut self, group: Option<usize>) -> Self {
+        self.selected_group = group;
+        self
+    }
+
     pub fn with_scroll_offset(mut self, offset: usize) -> Self {
         self.scroll_offset = offset;
         self
@@ -89,18 +149,25 @@ impl<'a> Widget for SchemaTreeComponent<'a> {
             .filter_map(|(idx, line)| matches!(line, SchemaInfo::Primitive { .. }).then_some(idx))
             .collect();
 
+        let visible_nodes = self
+            .visible_nodes
+            .clone()
+            .unwrap_or_else(|| (0..self.schema_columns.len()).collect());
+
         // Calculate visible range based on scroll offset and available height
         let visible_height = area.height.saturating_sub(1) as usize; // Account for borders + legend
-        let start_idx = self.scroll_offset;
-        let end_idx = (start_idx + visible_height).min(self.schema_columns.len());
 
-        let items: Vec<ListItem> = self
-            .schema_columns
+        let items: Vec<ListItem> = visible_nodes
             .iter()
-            .enumerate()
-            .skip(start_idx)
-            .take(end_idx - start_idx)
-            .map(|(idx, line)| {
+            .skip(self.scroll_offset)
+            .take(visible_height)
+            .map(|&idx| {
+                let line = &self.schema_columns[idx];
+                let field_id = line
+                    .field_id()
+                    .filter(|_| self.field_ids)
+                    .map(field_id_label)
+                    .unwrap_or_default();
                 let is_selected = if self.selected_index > 0 {
                     // Convert primitive index (1-based) to schema tree index
                     primitive_to_schema_map
@@ -115,14 +182,36 @@ impl<'a> Widget for SchemaTreeComponent<'a> {
                         ListItem::new(d.clone()).fg(self.root_color)
                     }
                     SchemaInfo::Primitive { display: d, .. } => {
-                        let mut item = ListItem::new(d.clone()).fg(self.primitive_color);
+                        let bookmark = if self.bookmarks.is_some_and(|marks| marks.contains(&idx)) {
+                            " ★"
+                        } else {
+                            ""
+                        };
+                        let label = if self.flat {
+                            format!(
+                                "   {}{field_id}{bookmark}",
+                                column_path(self.schema_columns, idx)
+                            )
+                        } else {
+                            format!("{d}{field_id}{bookmark}")
+                        };
+                        let mut item = ListItem::new(label).fg(self.primitive_color);
                         if is_selected {
-                            item = item.bg(self.selected_color).fg(Color::Black);
+                            item = item.bg(self.selected_color).fg(self.theme.selection_fg);
                         }
                         item
                     }
                     SchemaInfo::Group { display: d, .. } => {
-                        ListItem::new(d.clone()).fg(self.group_color)
+                        let collapsed = self
+                            .collapsed_groups
+                            .is_some_and(|groups| groups.contains(&idx));
+                        let marker = if collapsed { "▸" } else { "▾" };
+                        let mut item =
+                            ListItem::new(format!("{d}{field_id} {marker}")).fg(self.group_color);
+                        if self.selected_group == Some(idx) {
+                            item = item.bg(self.selected_color).fg(self.theme.selection_fg);
+                        }
+                        item
                     }
                 }
             })
@@ -132,7 +221,8 @@ impl<'a> Widget for SchemaTreeComponent<'a> {
 
         let mut block = Block::bordered()
             .title(Line::from(self.title.fg(self.title_color).bold()).centered())
-            .border_set(self.border_style);
+            .border_set(self.border_style)
+            .border_style(self.theme.border_style());
 
         if self.show_legend {
             let mut legend_vec = vec![
@@ -141,7 +231,7 @@ impl<'a> Widget for SchemaTreeComponent<'a> {
                 "Group".fg(self.group_color),
             ];
 
-            if self.selected_index > 0 {
+            if self.selected_index > 0 || self.selected_group.is_some() {
                 legend_vec.extend(vec![", ".into(), "Selected".bold().fg(self.selected_color)]);
             }
 
@@ -1,5 +1,7 @@
 use ratatui::{buffer::Buffer, layout::Rect, style::Color, widgets::Widget};
 
+use crate::theme::Theme;
+
 pub struct ScrollbarComponent {
     pub orientation: ScrollbarOrientation,
     pub total_items: usize,
@@ -50,6 +52,10 @@ impl ScrollbarComponent {
         self
     }
 
+    pub fn with_theme(self, theme: &Theme) -> Self {
+        self.with_colors(theme.border, theme.text)
+    }
+
     pub fn with_symbols(mut self, track_symbol: &'static str, thumb_symbol: &'static str) -> Self {
         self.track_symbol = track_symbol;
         self.thumb_symbol = thumb_symbol;
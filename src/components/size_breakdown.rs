@@ -0,0 +1,137 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style, Stylize},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Widget},
+};
+
+use crate::file::utils::{display_width, human_readable_bytes, pad_str, truncate_str};
+use crate::theme::Theme;
+
+/// Widest a column name gets before it is truncated.
+const MAX_NAME_WIDTH: usize = 32;
+
+/// Horizontal bar chart of the largest columns by compressed size, with their
+/// share of the whole file.
+pub struct SizeBreakdown {
+    /// (column path, compressed bytes), largest first.
+    pub columns: Vec<(String, u64)>,
+    pub file_size: u64,
+    pub theme: Theme,
+}
+
+impl SizeBreakdown {
+    pub fn new(columns: Vec<(String, u64)>, file_size: u64) -> Self {
+        Self {
+            columns,
+            file_size,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_theme(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self
+    }
+}
+
+/// Share of `total` taken by `size`, in percent.
+pub fn percent_of(size: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        size as f64 * 100.0 / total as f64
+    }
+}
+
+impl Widget for SizeBreakdown {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .title(
+                Line::from(format!("Size breakdown (top {})", self.columns.len()))
+                    .centered()
+                    .bold()
+                    .fg(self.theme.accent),
+            )
+            .border_set(border::ROUNDED)
+            .border_style(self.theme.border_style());
+        let inner = block.inner(area);
+        block.render(area, buf);
+        if inner.area() == 0 || self.columns.is_empty() {
+            return;
+        }
+
+        let name_width = self
+            .columns
+            .iter()
+            .map(|(name, _)| display_width(name))
+            .max()
+            .unwrap_or(0)
+            .min(MAX_NAME_WIDTH);
+        let sizes: Vec<String> = self
+            .columns
+            .iter()
+            .map(|(_, size)| human_readable_bytes(*size))
+            .collect();
+        let size_width = sizes.iter().map(String::len).max().unwrap_or(0);
+        // name, " │", bar, " ", size, "  ", "100.0%"
+        let bar_width = (inner.width as usize).saturating_sub(name_width + size_width + 12);
+        let largest = self.columns[0].1.max(1);
+
+        let lines: Vec<Line> = self
+            .columns
+            .iter()
+            .zip(sizes)
+            .take(inner.height as usize)
+            .map(|((name, size), size_label)| {
+                let filled = (*size as f64 / largest as f64 * bar_width as f64).round() as usize;
+                Line::from(vec![
+                    Span::styled(
+                        pad_str(&truncate_str(name, name_width), name_width),
+                        Style::default().fg(self.theme.label),
+                    ),
+                    Span::styled(" │", Style::default().fg(self.theme.border)),
+                    Span::styled("█".repeat(filled), Style::default().fg(self.theme.header)),
+                    Span::raw(" ".repeat(bar_width - filled.min(bar_width))),
+                    Span::styled(
+                        format!(" {size_label:>size_width$}"),
+                        Style::default().fg(self.theme.text),
+                    ),
+                    Span::styled(
+                        format!("  {:>5.1}%", percent_of(*size, self.file_size)),
+                        Style::default().fg(self.theme.muted),
+                    ),
+                ])
+            })
+            .collect();
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_of() {
+        assert_eq!(percent_of(25, 100), 25.0);
+        assert_eq!(percent_of(1, 0), 0.0);
+    }
+
+    #[test]
+    fn test_bars_scale_to_the_largest_column() {
+        let widget = SizeBreakdown::new(vec![("a".into(), 400), ("b".into(), 100)], 1000);
+        let area = Rect::new(0, 0, 40, 4);
+        let mut buf = Buffer::empty(area);
+        widget.render(area, &mut buf);
+
+        let row = |y: u16| -> String { (0..area.width).map(|x| buf[(x, y)].symbol()).collect() };
+        let bars = |line: String| line.matches('█').count();
+        let (a, b) = (row(1), row(2));
+        assert!(a.contains("40.0%") && b.contains("10.0%"));
+        assert_eq!(bars(a), 4 * bars(b));
+    }
+}
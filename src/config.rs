@@ -0,0 +1,638 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::command::Command;
+use crate::components::data_table::DEFAULT_MAX_COLUMN_WIDTH;
+use crate::file::analyzer::ExternalAnalyzer;
+use crate::file::sample_data::{DateStyle, ParquetSampleData, PreviewOptions, Sampling};
+use crate::file::utils::ByteUnits;
+use crate::keymap::{KeybindingsConfig, Keymap};
+use crate::theme::ThemeConfig;
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("Failed to read config '{path}': {source}")]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid config '{path}': {details}")]
+    Parse { path: PathBuf, details: String },
+
+    #[error("Failed to write config '{path}': {source}")]
+    Write {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+/// Pane layout of the tabs that show the schema tree next to a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LayoutConfig {
+    /// Width of the schema tree as a percentage of the body. `None` sizes the
+    /// tree to fit its content.
+    pub tree_percent: Option<u16>,
+    /// Hide the schema tree so the table gets the full width.
+    pub tree_collapsed: bool,
+    /// Show the data preview under the statistics in the Schema tab,
+    /// scrolled to the selected column.
+    pub split: bool,
+}
+
+impl LayoutConfig {
+    pub const MIN_TREE_PERCENT: u16 = 10;
+    pub const MAX_TREE_PERCENT: u16 = 90;
+
+    /// Width of the tree pane inside a body `total_width` wide, given the width
+    /// the tree would take when sized to its content.
+    pub fn tree_pane_width(&self, total_width: u16, natural_width: u16) -> u16 {
+        if self.tree_collapsed {
+            return 0;
+        }
+        match self.tree_percent {
+            Some(percent) => (total_width as u32 * percent as u32 / 100) as u16,
+            None => natural_width,
+        }
+    }
+}
+
+/// One of the app's tabs, e.g. the one shown when a file is opened or a
+/// `[keybindings.<tab>]` section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum TabKind {
+    #[default]
+    Visualize,
+    Metadata,
+    Schema,
+    #[value(alias = "rowgroups", alias = "row_groups")]
+    RowGroups,
+}
+
+impl TabKind {
+    /// Position of the tab in the tab bar.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// What the app opens on and how it shows values.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplayConfig {
+    pub default_tab: TabKind,
+    /// Rows loaded at a time in the Visualize tab.
+    pub preview_rows: usize,
+    /// Which rows of the file the Visualize tab shows.
+    pub sampling: Sampling,
+    /// Widest a Visualize column gets before values are cut off.
+    pub cell_width: u16,
+    pub dates: DateStyle,
+    pub byte_units: ByteUnits,
+    /// Follow timestamp min/max values with how long ago they are, e.g.
+    /// `(3 days ago)`.
+    pub relative_times: bool,
+    /// Compression ratios below this are shown in red, e.g. columns that
+    /// barely compress.
+    pub poor_ratio: f64,
+    /// Compression ratios above this are shown in green; the ones between
+    /// in yellow.
+    pub good_ratio: f64,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            default_tab: TabKind::default(),
+            preview_rows: ParquetSampleData::MAX_ROWS,
+            sampling: Sampling::default(),
+            cell_width: DEFAULT_MAX_COLUMN_WIDTH,
+            dates: DateStyle::default(),
+            byte_units: ByteUnits::default(),
+            relative_times: false,
+            poor_ratio: 1.1,
+            good_ratio: 3.0,
+        }
+    }
+}
+
+impl DisplayConfig {
+    pub const MIN_CELL_WIDTH: u16 = 4;
+
+    pub fn preview(&self) -> PreviewOptions {
+        PreviewOptions {
+            max_rows: self.preview_rows,
+            dates: self.dates,
+            sampling: self.sampling,
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.preview_rows == 0 {
+            return Err("preview_rows must be at least 1".to_string());
+        }
+        if !(self.poor_ratio > 0.0 && self.poor_ratio <= self.good_ratio) {
+            return Err(format!(
+                "poor_ratio ({}) must be above 0 and at most good_ratio ({})",
+                self.poor_ratio, self.good_ratio
+            ));
+        }
+        if self.cell_width < Self::MIN_CELL_WIDTH {
+            return Err(format!(
+                "cell_width must be at least {}",
+                Self::MIN_CELL_WIDTH
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The program a cell value or the schema opens in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExternalConfig {
+    /// `pager` for `$PAGER` (else `less`), `editor` for `$VISUAL` or
+    /// `$EDITOR` (else `vi`), or a command line such as `code --wait`.
+    pub open_with: String,
+}
+
+impl Default for ExternalConfig {
+    fn default() -> Self {
+        Self {
+            open_with: "pager".to_string(),
+        }
+    }
+}
+
+impl ExternalConfig {
+    /// The program and its arguments, from the environment for `pager` and
+    /// `editor`.
+    pub fn command(&self) -> Vec<String> {
+        let env = |names: &[&str], fallback: &str| {
+            names
+                .iter()
+                .filter_map(|name| std::env::var(name).ok())
+                .find(|value| !value.trim().is_empty())
+                .unwrap_or_else(|| fallback.to_string())
+        };
+        let command = match self.open_with.as_str() {
+            "pager" => env(&["PAGER"], "less"),
+            "editor" => env(&["VISUAL", "EDITOR"], "vi"),
+            command => command.to_string(),
+        };
+        command.split_whitespace().map(str::to_string).collect()
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.open_with.trim().is_empty() {
+            return Err("open_with must be pager, editor or a command".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Presets that come with parqeye, as `:` commands run in order. A preset of
+/// the same name in `[presets]` replaces one of these.
+pub const BUILTIN_PRESETS: [(&str, &[&str]); 2] = [
+    // Row group sizes next to what a higher codec level would save.
+    ("size-debug", &["tab row_groups", "codecs"]),
+    // Statistics of every column next to a profile of their values.
+    ("stats-audit", &["tab schema", "profile"]),
+];
+
+/// User settings read from `config.toml`. Every field is optional in the
+/// file; missing ones keep their defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub layout: LayoutConfig,
+    pub display: DisplayConfig,
+    pub theme: ThemeConfig,
+    pub external: ExternalConfig,
+    #[serde(skip_serializing_if = "KeybindingsConfig::is_empty")]
+    pub keybindings: KeybindingsConfig,
+    /// Named sequences of `:` commands to open a file with, e.g.
+    /// `--preset size-debug`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub presets: BTreeMap<String, Vec<String>>,
+    /// Programs whose findings `parqeye report` lists with its own.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub analyzers: Vec<ExternalAnalyzer>,
+}
+
+impl AppConfig {
+    /// Location of the config file, e.g. `~/.config/parqeye/config.toml`.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("parqeye").join("config.toml"))
+    }
+
+    /// Load the config from the default location, falling back to defaults
+    /// when there is no config file.
+    pub fn load() -> Result<Self, ConfigError> {
+        match Self::default_path() {
+            Some(path) if path.exists() => Self::load_from(&path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self, ConfigError> {
+        let contents = fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::from_toml(&contents).map_err(|details| ConfigError::Parse {
+            path: path.to_path_buf(),
+            details,
+        })
+    }
+
+    pub fn from_toml(contents: &str) -> Result<Self, String> {
+        let config: AppConfig = toml::from_str(contents).map_err(|e| e.to_string())?;
+        // Surface bad colors at load time rather than silently ignoring them.
+        config
+            .theme
+            .to_theme()
+            .map_err(|e| format!("[theme]: {e}"))?;
+        Keymap::from_config(&config.keybindings).map_err(|e| format!("[keybindings]: {e}"))?;
+        config
+            .display
+            .validate()
+            .map_err(|e| format!("[display]: {e}"))?;
+        config
+            .external
+            .validate()
+            .map_err(|e| format!("[external]: {e}"))?;
+        for name in config.presets.keys() {
+            config.preset(name).map_err(|e| format!("[presets]: {e}"))?;
+        }
+        for analyzer in &config.analyzers {
+            analyzer
+                .validate()
+                .map_err(|e| format!("[[analyzers]]: {e}"))?;
+        }
+        Ok(config)
+    }
+
+    /// Names of the built-in presets and those in `[presets]`.
+    pub fn preset_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = BUILTIN_PRESETS.iter().map(|(name, _)| *name).collect();
+        names.extend(self.presets.keys().map(String::as_str));
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// The commands of preset `name`, looked up in `[presets]` first.
+    pub fn preset(&self, name: &str) -> Result<Vec<Command>, String> {
+        let commands: Vec<&str> = match self.presets.get(name) {
+            Some(commands) => commands.iter().map(String::as_str).collect(),
+            None => BUILTIN_PRESETS
+                .iter()
+                .find(|(builtin, _)| *builtin == name)
+                .map(|(_, commands)| commands.to_vec())
+                .ok_or_else(|| {
+                    format!(
+                        "unknown preset '{name}'; presets are {}",
+                        self.preset_names().join(", ")
+                    )
+                })?,
+        };
+        commands
+            .iter()
+            .map(|command| Command::parse(command).map_err(|e| format!("{name}: '{command}': {e}")))
+            .collect()
+    }
+
+    /// Write `layout` to the `[layout]` table of the config file at the
+    /// default location, creating the file if there is none.
+    pub fn save_layout(layout: &LayoutConfig) -> Result<(), ConfigError> {
+        match Self::default_path() {
+            Some(path) => Self::save_layout_to(&path, layout),
+            None => Ok(()),
+        }
+    }
+
+    /// Update only the `[layout]` keys of the file at `path`, keeping the
+    /// user's comments, key order and other settings as they are. A file
+    /// that doesn't parse is left alone.
+    pub fn save_layout_to(path: &Path, layout: &LayoutConfig) -> Result<(), ConfigError> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(source) => {
+                return Err(ConfigError::Read {
+                    path: path.to_path_buf(),
+                    source,
+                });
+            }
+        };
+        let parse_err = |details: String| ConfigError::Parse {
+            path: path.to_path_buf(),
+            details,
+        };
+        let mut doc: toml_edit::DocumentMut = contents
+            .parse()
+            .map_err(|e: toml_edit::TomlError| parse_err(e.to_string()))?;
+        let table = doc
+            .entry("layout")
+            .or_insert(toml_edit::table())
+            .as_table_like_mut()
+            .ok_or_else(|| parse_err("layout is not a table".to_string()))?;
+        match layout.tree_percent {
+            Some(percent) => {
+                table.insert("tree_percent", toml_edit::value(percent as i64));
+            }
+            None => {
+                table.remove("tree_percent");
+            }
+        }
+        // Defaults the file doesn't mention stay unmentioned.
+        for (key, on) in [
+            ("tree_collapsed", layout.tree_collapsed),
+            ("split", layout.split),
+        ] {
+            if on || table.contains_key(key) {
+                table.insert(key, toml_edit::value(on));
+            }
+        }
+
+        let write_err = |source| ConfigError::Write {
+            path: path.to_path_buf(),
+            source,
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(write_err)?;
+        }
+        fs::write(path, doc.to_string()).map_err(write_err)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), ConfigError> {
+        let write_err = |source| ConfigError::Write {
+            path: path.to_path_buf(),
+            source,
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(write_err)?;
+        }
+        let contents = toml::to_string_pretty(self).map_err(|e| ConfigError::Parse {
+            path: path.to_path_buf(),
+            details: e.to_string(),
+        })?;
+        fs::write(path, contents).map_err(write_err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_config_uses_defaults() {
+        let config = AppConfig::from_toml("").unwrap();
+        assert_eq!(config, AppConfig::default());
+        assert_eq!(config.layout.tree_percent, None);
+        assert!(!config.layout.tree_collapsed);
+    }
+
+    #[test]
+    fn test_layout_section_is_parsed() {
+        let config = AppConfig::from_toml("[layout]\ntree_percent = 40\n").unwrap();
+        assert_eq!(config.layout.tree_percent, Some(40));
+        assert!(!config.layout.tree_collapsed);
+    }
+
+    #[test]
+    fn test_display_section_is_parsed() {
+        let config = AppConfig::from_toml(
+            "[display]\ndefault_tab = \"row_groups\"\npreview_rows = 50\nsampling = \"random:7\"\ndates = \"epoch\"\nbyte_units = \"decimal\"\nrelative_times = true\npoor_ratio = 1.5\ngood_ratio = 4\n",
+        )
+        .unwrap();
+        assert_eq!(config.display.default_tab, TabKind::RowGroups);
+        assert_eq!(config.display.default_tab.index(), 3);
+        assert_eq!(config.display.preview().max_rows, 50);
+        assert_eq!(config.display.dates, DateStyle::Epoch);
+        assert_eq!(config.display.preview().sampling, Sampling::Random(7));
+        assert!(config.display.relative_times);
+        assert_eq!(
+            (config.display.poor_ratio, config.display.good_ratio),
+            (1.5, 4.0)
+        );
+        assert_eq!(config.display.byte_units, ByteUnits::Decimal);
+        assert_eq!(config.display.cell_width, DEFAULT_MAX_COLUMN_WIDTH);
+
+        assert!(AppConfig::from_toml("[display]\npreview_rows = 0\n").is_err());
+        assert!(AppConfig::from_toml("[display]\ncell_width = 2\n").is_err());
+        assert!(AppConfig::from_toml("[display]\ndates = \"mars\"\n").is_err());
+        assert!(AppConfig::from_toml("[display]\nsampling = \"every:0\"\n").is_err());
+        assert!(AppConfig::from_toml("[display]\npoor_ratio = 4.0\n").is_err());
+        assert!(AppConfig::from_toml("[display]\ngood_ratio = 0\npoor_ratio = 0\n").is_err());
+    }
+
+    #[test]
+    fn test_invalid_config_is_an_error() {
+        assert!(AppConfig::from_toml("[layout]\ntree_percent = \"wide\"\n").is_err());
+    }
+
+    #[test]
+    fn test_theme_section_is_parsed_and_validated() {
+        let config =
+            AppConfig::from_toml("[theme]\npreset = \"high-contrast\"\nborder = \"red\"\n")
+                .unwrap();
+        let theme = config.theme.to_theme().unwrap();
+        assert_eq!(theme.border, ratatui::style::Color::Red);
+        assert_eq!(theme.header, crate::theme::Theme::HIGH_CONTRAST.header);
+
+        assert!(AppConfig::from_toml("[theme]\npreset = \"solarized\"\n").is_err());
+        assert!(AppConfig::from_toml("[theme]\nheader = \"nope\"\n").is_err());
+    }
+
+    #[test]
+    fn test_keybindings_section_is_parsed_and_validated() {
+        let config =
+            AppConfig::from_toml("[keybindings]\ndown = [\"j\", \"Down\"]\nquit = \"x\"\n")
+                .unwrap();
+        let keymap = Keymap::from_config(&config.keybindings).unwrap();
+        assert_eq!(
+            keymap.action_for(crossterm::event::KeyCode::Char('j')),
+            Some(crate::keymap::Action::Down)
+        );
+        assert_eq!(keymap.describe(crate::keymap::Action::Quit), "x");
+
+        assert!(AppConfig::from_toml("[keybindings]\nfly = \"f\"\n").is_err());
+        assert!(AppConfig::from_toml("[keybindings]\nquit = \"Hyper\"\n").is_err());
+        assert!(AppConfig::from_toml("[keybindings.schema]\nquit = \"Ctrl+Hyper\"\n").is_err());
+    }
+
+    #[test]
+    fn test_presets_section_is_parsed_and_validated() {
+        let config = AppConfig::from_toml(
+            "[presets]\nsmall-groups = [\"tab row_groups\", \"filter rows < 1000\"]\nstats-audit = [\"tab metadata\"]\n",
+        )
+        .unwrap();
+        let small = config.preset("small-groups").unwrap();
+        assert_eq!(small[0], Command::Tab(TabKind::RowGroups));
+        assert!(matches!(small[1], Command::Filter(Some(_))));
+        // A preset in the config replaces the built-in one of that name.
+        assert_eq!(
+            config.preset("stats-audit"),
+            Ok(vec![Command::Tab(TabKind::Metadata)])
+        );
+        assert_eq!(
+            config.preset("size-debug"),
+            Ok(vec![
+                Command::Tab(TabKind::RowGroups),
+                Command::Codecs { level: None }
+            ])
+        );
+        assert_eq!(
+            config.preset("nope"),
+            Err(
+                "unknown preset 'nope'; presets are size-debug, small-groups, stats-audit"
+                    .to_string()
+            )
+        );
+
+        assert!(AppConfig::from_toml("[presets]\nbad = [\"tab pages\"]\n").is_err());
+        assert!(AppConfig::from_toml("[presets]\nbad = \"tab schema\"\n").is_err());
+    }
+
+    #[test]
+    fn test_analyzers_section_is_parsed_and_validated() {
+        let config = AppConfig::from_toml(
+            "[[analyzers]]\nname = \"pii\"\ncommand = [\"pii-scan\", \"--strict\"]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.analyzers,
+            [ExternalAnalyzer {
+                name: "pii".to_string(),
+                command: vec!["pii-scan".to_string(), "--strict".to_string()],
+            }]
+        );
+
+        assert!(AppConfig::from_toml("[[analyzers]]\nname = \"pii\"\ncommand = []\n").is_err());
+        assert!(AppConfig::from_toml("[[analyzers]]\ncommand = [\"pii-scan\"]\n").is_err());
+    }
+
+    #[test]
+    fn test_external_section_is_parsed() {
+        assert_eq!(AppConfig::default().external.open_with, "pager");
+        let config = AppConfig::from_toml("[external]\nopen_with = \"code --wait\"\n").unwrap();
+        assert_eq!(config.external.command(), ["code", "--wait"]);
+        assert!(AppConfig::from_toml("[external]\nopen_with = \" \"\n").is_err());
+    }
+
+    #[test]
+    fn test_builtin_presets_parse() {
+        let config = AppConfig::default();
+        for (name, _) in BUILTIN_PRESETS {
+            assert!(config.preset(name).is_ok(), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let path = std::env::temp_dir()
+            .join(format!("parqeye-config-{}", std::process::id()))
+            .join("config.toml");
+        let config = AppConfig {
+            layout: LayoutConfig {
+                tree_percent: Some(35),
+                tree_collapsed: true,
+                split: true,
+            },
+            display: DisplayConfig {
+                default_tab: TabKind::Schema,
+                dates: DateStyle::Local,
+                ..Default::default()
+            },
+            theme: ThemeConfig {
+                preset: crate::theme::ThemePreset::Light,
+                accent: Some("#112233".to_string()),
+                ..Default::default()
+            },
+            external: ExternalConfig {
+                open_with: "editor".to_string(),
+            },
+            keybindings: KeybindingsConfig {
+                global: [(
+                    crate::keymap::Action::Down,
+                    crate::keymap::KeyList::Many(vec!["j".to_string(), "Down".to_string()]),
+                )]
+                .into(),
+                schema: [(
+                    crate::keymap::Action::PageDown,
+                    crate::keymap::KeyList::One("Ctrl+d".to_string()),
+                )]
+                .into(),
+                ..Default::default()
+            },
+            presets: [(
+                "nulls".to_string(),
+                vec!["tab schema".to_string(), "profile 1000".to_string()],
+            )]
+            .into(),
+            analyzers: vec![ExternalAnalyzer {
+                name: "pii".to_string(),
+                command: vec!["pii-scan".to_string()],
+            }],
+        };
+        config.save_to(&path).unwrap();
+        assert_eq!(AppConfig::load_from(&path).unwrap(), config);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_save_layout_keeps_the_rest() {
+        let dir = std::env::temp_dir().join(format!("parqeye-layout-{}", std::process::id()));
+        let path = dir.join("config.toml");
+        fs::create_dir_all(&dir).unwrap();
+        let original =
+            "# my settings\n[display]\ncell_width = 40 # wide\n\n[layout]\nsplit = true\n";
+        fs::write(&path, original).unwrap();
+        let layout = LayoutConfig {
+            tree_percent: Some(30),
+            tree_collapsed: false,
+            split: false,
+        };
+        AppConfig::save_layout_to(&path, &layout).unwrap();
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.starts_with("# my settings\n[display]\ncell_width = 40 # wide\n"));
+        assert!(saved.contains("split = false"), "{saved}");
+        assert!(saved.contains("tree_percent = 30"), "{saved}");
+        assert!(!saved.contains("tree_collapsed"), "{saved}");
+        assert!(!saved.contains("[theme]"), "{saved}");
+        assert_eq!(AppConfig::load_from(&path).unwrap().layout, layout);
+
+        // A broken file is reported, not overwritten.
+        fs::write(&path, "[layout\n").unwrap();
+        assert!(AppConfig::save_layout_to(&path, &layout).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[layout\n");
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_tree_pane_width() {
+        let fit = LayoutConfig::default();
+        assert_eq!(fit.tree_pane_width(200, 30), 30);
+
+        let ratio = LayoutConfig {
+            tree_percent: Some(25),
+            tree_collapsed: false,
+            split: false,
+        };
+        assert_eq!(ratio.tree_pane_width(200, 30), 50);
+
+        let collapsed = LayoutConfig {
+            tree_percent: Some(25),
+            tree_collapsed: true,
+            split: true,
+        };
+        assert_eq!(collapsed.tree_pane_width(200, 30), 0);
+    }
+}
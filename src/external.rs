@@ -0,0 +1,56 @@
+use std::fs;
+use std::process::Command;
+
+/// Write `text` to a temporary file named after `name` and run `command`
+/// on it, waiting for the program to exit. The terminal must be handed
+/// over to the program before calling this.
+pub fn open_in(command: &[String], name: &str, text: &str) -> Result<(), String> {
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| "no program to open it with".to_string())?;
+    let file_name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let path = std::env::temp_dir().join(format!("parqeye-{}-{file_name}", std::process::id()));
+    fs::write(&path, text).map_err(|e| format!("cannot write {}: {e}", path.display()))?;
+    let status = Command::new(program).args(args).arg(&path).status();
+    let _ = fs::remove_file(&path);
+    match status {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(format!("{program} exited with {status}")),
+        Err(e) => Err(format!("cannot run {program}: {e}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_in() {
+        let copy = std::env::temp_dir().join(format!("parqeye-open-{}", std::process::id()));
+        let command = [
+            "sh".to_string(),
+            "-c".to_string(),
+            format!("cp \"$0\" {}", copy.display()),
+        ];
+        open_in(&command, "payload.json", "{\"a\": 1}").unwrap();
+        assert_eq!(fs::read_to_string(&copy).unwrap(), "{\"a\": 1}");
+        fs::remove_file(&copy).unwrap();
+
+        let failing = ["sh", "-c", "exit 2"].map(String::from);
+        assert_eq!(
+            open_in(&failing, "x", ""),
+            Err("sh exited with exit status: 2".to_string())
+        );
+        assert!(open_in(&["parqeye-no-such-program".to_string()], "x", "").is_err());
+        assert!(open_in(&[], "x", "").is_err());
+    }
+}
@@ -0,0 +1,315 @@
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+use crate::file::lint::{Finding, Severity, lint};
+use crate::file::parquet_ctx::ParquetCtx;
+
+/// A table an analyzer shows after the findings, e.g. the per-column
+/// numbers behind them.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AnalysisTable {
+    pub title: String,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    /// Columns that hold numbers and are right-aligned.
+    pub numeric: Vec<usize>,
+}
+
+/// What one or more analyzers found.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Analysis {
+    pub findings: Vec<Finding>,
+    pub tables: Vec<AnalysisTable>,
+}
+
+/// A check run over an open file, such as the built-in [`lint`] or one of
+/// a downstream crate's own, registered with [`Analyzers::with`].
+pub trait Analyzer: Send + Sync {
+    /// Short identifier, e.g. `layout`. Failures are reported under it.
+    fn name(&self) -> &str;
+
+    fn analyze(&self, ctx: &ParquetCtx) -> Result<Analysis, String>;
+}
+
+/// The layout checks of [`lint`].
+pub struct LayoutLint;
+
+impl Analyzer for LayoutLint {
+    fn name(&self) -> &str {
+        "layout"
+    }
+
+    fn analyze(&self, ctx: &ParquetCtx) -> Result<Analysis, String> {
+        Ok(Analysis {
+            findings: lint(ctx),
+            tables: vec![],
+        })
+    }
+}
+
+/// A program from `[[analyzers]]` in the config, run with the file's path
+/// as its last argument. Each line it prints is a finding, written like
+/// `warning [rule] message`; lines without a severity are `info` findings
+/// under the analyzer's name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExternalAnalyzer {
+    pub name: String,
+    /// The program and its first arguments.
+    pub command: Vec<String>,
+}
+
+impl ExternalAnalyzer {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("an analyzer needs a name".to_string());
+        }
+        if self.command.is_empty() {
+            return Err(format!("{}: command is empty", self.name));
+        }
+        Ok(())
+    }
+}
+
+impl Analyzer for ExternalAnalyzer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn analyze(&self, ctx: &ParquetCtx) -> Result<Analysis, String> {
+        let (program, args) = self
+            .command
+            .split_first()
+            .ok_or_else(|| "command is empty".to_string())?;
+        let output = Command::new(program)
+            .args(args)
+            .arg(&ctx.file_path)
+            .output()
+            .map_err(|e| format!("cannot run {program}: {e}"))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let mut error = format!("{program} exited with {}", output.status);
+            if let Some(line) = stderr.lines().find(|line| !line.trim().is_empty()) {
+                error.push_str(&format!(": {}", line.trim()));
+            }
+            return Err(error);
+        }
+        Ok(Analysis {
+            findings: String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| parse_finding(&self.name, line))
+                .collect(),
+            tables: vec![],
+        })
+    }
+}
+
+/// A finding from a line such as `warning [pii] email holds addresses`.
+/// The rule is optional and defaults to `name`, as does the severity, to
+/// `info`.
+fn parse_finding(name: &str, line: &str) -> Finding {
+    let line = line.trim();
+    let (severity, rest) = match line.split_once(' ') {
+        Some(("error", rest)) => (Severity::Error, rest),
+        Some(("warning", rest)) => (Severity::Warning, rest),
+        Some(("info", rest)) => (Severity::Info, rest),
+        _ => (Severity::Info, line),
+    };
+    let rest = rest.trim_start();
+    match rest.strip_prefix('[').and_then(|rest| rest.split_once(']')) {
+        Some((rule, message)) if !rule.is_empty() => {
+            Finding::new(severity, rule, message.trim().to_string())
+        }
+        _ => Finding::new(severity, name, rest.to_string()),
+    }
+}
+
+/// The analyzers run over a file. [`Analyzers::default`] holds the built-in
+/// [`LayoutLint`].
+pub struct Analyzers {
+    analyzers: Vec<Box<dyn Analyzer>>,
+}
+
+impl Default for Analyzers {
+    fn default() -> Self {
+        Self::empty().with(LayoutLint)
+    }
+}
+
+impl Analyzers {
+    /// No analyzers at all, not even the built-in ones.
+    pub fn empty() -> Self {
+        Self { analyzers: vec![] }
+    }
+
+    pub fn with(mut self, analyzer: impl Analyzer + 'static) -> Self {
+        self.analyzers.push(Box::new(analyzer));
+        self
+    }
+
+    /// Add the programs configured in `[[analyzers]]`.
+    pub fn with_external(self, analyzers: &[ExternalAnalyzer]) -> Self {
+        analyzers
+            .iter()
+            .cloned()
+            .fold(self, |analyzers, external| analyzers.with(external))
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.analyzers.iter().map(|a| a.name()).collect()
+    }
+
+    /// Run every analyzer, most severe findings first. An analyzer that
+    /// fails becomes an error finding under its name, so the others still
+    /// count.
+    pub fn run(&self, ctx: &ParquetCtx) -> Analysis {
+        let mut analysis = Analysis::default();
+        for analyzer in &self.analyzers {
+            match analyzer.analyze(ctx) {
+                Ok(found) => {
+                    analysis.findings.extend(found.findings);
+                    analysis.tables.extend(found.tables);
+                }
+                Err(e) => analysis.findings.push(Finding::new(
+                    Severity::Error,
+                    analyzer.name(),
+                    format!("analyzer failed: {e}"),
+                )),
+            }
+        }
+        analysis.findings.sort_by_key(|f| f.severity);
+        analysis
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_data_path;
+
+    /// Flags every INT32 column, with a table of them.
+    struct Int32Columns;
+
+    impl Analyzer for Int32Columns {
+        fn name(&self) -> &str {
+            "int32"
+        }
+
+        fn analyze(&self, ctx: &ParquetCtx) -> Result<Analysis, String> {
+            let columns: Vec<String> = ctx
+                .reader
+                .metadata_ptr()
+                .file_metadata()
+                .schema_descr()
+                .columns()
+                .iter()
+                .filter(|c| c.physical_type() == parquet::basic::Type::INT32)
+                .map(|c| c.path().string())
+                .collect();
+            Ok(Analysis {
+                findings: vec![Finding::new(
+                    Severity::Warning,
+                    "int32-columns",
+                    format!("{} INT32 columns", columns.len()),
+                )],
+                tables: vec![AnalysisTable {
+                    title: "INT32 columns".to_string(),
+                    headers: vec!["Column".to_string()],
+                    rows: columns.into_iter().map(|c| vec![c]).collect(),
+                    numeric: vec![],
+                }],
+            })
+        }
+    }
+
+    struct Broken;
+
+    impl Analyzer for Broken {
+        fn name(&self) -> &str {
+            "broken"
+        }
+
+        fn analyze(&self, _: &ParquetCtx) -> Result<Analysis, String> {
+            Err("no luck".to_string())
+        }
+    }
+
+    #[test]
+    fn test_parse_finding() {
+        assert_eq!(
+            parse_finding("pii", "warning [emails] email holds addresses"),
+            Finding::new(
+                Severity::Warning,
+                "emails",
+                "email holds addresses".to_string()
+            )
+        );
+        assert_eq!(
+            parse_finding("pii", "error no [rule] here"),
+            Finding::new(Severity::Error, "pii", "no [rule] here".to_string())
+        );
+        assert_eq!(
+            parse_finding("pii", "  all clear "),
+            Finding::new(Severity::Info, "pii", "all clear".to_string())
+        );
+    }
+
+    #[test]
+    fn test_registered_analyzers() {
+        let ctx = ParquetCtx::from_file(&test_data_path("alltypes_plain.parquet")).unwrap();
+        let analyzers = Analyzers::default().with(Int32Columns).with(Broken);
+        assert_eq!(analyzers.names(), ["layout", "int32", "broken"]);
+        let analysis = analyzers.run(&ctx);
+
+        assert_eq!(analysis.findings[0].rule, "broken");
+        assert_eq!(analysis.findings[0].message, "analyzer failed: no luck");
+        assert!(analysis.findings.iter().any(|f| f.rule == "uncompressed"));
+        assert!(
+            analysis
+                .findings
+                .iter()
+                .any(|f| f.message == "4 INT32 columns")
+        );
+        assert!(
+            analysis
+                .findings
+                .windows(2)
+                .all(|w| w[0].severity <= w[1].severity)
+        );
+        assert_eq!(analysis.tables.len(), 1);
+        assert_eq!(analysis.tables[0].rows[0], ["id"]);
+        assert!(Analyzers::empty().run(&ctx).findings.is_empty());
+    }
+
+    #[test]
+    fn test_external_analyzer() {
+        let ctx = ParquetCtx::from_file(&test_data_path("alltypes_plain.parquet")).unwrap();
+        let script = |script: &str| ExternalAnalyzer {
+            name: "script".to_string(),
+            command: ["sh", "-c", script, "sh"].map(String::from).to_vec(),
+        };
+        let echo = script("echo \"warning [path] checked $(basename $1)\"; echo; echo done");
+        let failing = script("echo oops >&2; exit 3");
+        let analysis = Analyzers::empty().with_external(&[echo, failing]).run(&ctx);
+
+        let findings: Vec<String> = analysis.findings.iter().map(|f| f.to_string()).collect();
+        assert_eq!(
+            findings,
+            [
+                "error [script] analyzer failed: sh exited with exit status: 3: oops",
+                "warning [path] checked alltypes_plain.parquet",
+                "info [script] done",
+            ]
+        );
+        assert!(script("").validate().is_ok());
+        assert!(
+            ExternalAnalyzer {
+                name: "none".to_string(),
+                command: vec![],
+            }
+            .validate()
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,338 @@
+use std::fmt;
+use std::fs::File;
+use std::time::Instant;
+
+use itertools::Itertools;
+use parquet::arrow::ProjectionMask;
+use parquet::arrow::arrow_reader::{ArrowReaderMetadata, ParquetRecordBatchReaderBuilder};
+use parquet::basic::Compression;
+use serde::Serialize;
+
+use crate::file::progress::ScanProgress;
+use crate::file::utils::{commas, human_readable_bytes};
+
+/// Rows decoded per batch, as a query engine would.
+const READ_BATCH: usize = 8192;
+
+/// How fast one column decodes into arrow arrays.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ColumnBench {
+    pub path: String,
+    /// Codecs of its chunks, such as `ZSTD` or `SNAPPY/ZSTD`.
+    pub codec: String,
+    pub rows: u64,
+    pub compressed_bytes: u64,
+    pub uncompressed_bytes: u64,
+    /// Time to read and decode every chunk of the column.
+    pub seconds: f64,
+}
+
+impl ColumnBench {
+    /// Uncompressed megabytes decoded per second.
+    pub fn mb_per_sec(&self) -> f64 {
+        self.uncompressed_bytes as f64 / 1e6 / self.seconds.max(f64::MIN_POSITIVE)
+    }
+
+    pub fn rows_per_sec(&self) -> f64 {
+        self.rows as f64 / self.seconds.max(f64::MIN_POSITIVE)
+    }
+}
+
+/// The columns of one codec taken together.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CodecBench {
+    pub codec: String,
+    pub columns: usize,
+    pub compressed_bytes: u64,
+    pub uncompressed_bytes: u64,
+    pub seconds: f64,
+}
+
+impl CodecBench {
+    pub fn mb_per_sec(&self) -> f64 {
+        self.uncompressed_bytes as f64 / 1e6 / self.seconds.max(f64::MIN_POSITIVE)
+    }
+}
+
+/// Decode throughput of each column, slowest first, to find the column
+/// that dominates a full scan. Timings include reading from disk, so a
+/// second run on a warm page cache measures decoding alone.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BenchReport {
+    pub path: String,
+    pub columns: Vec<ColumnBench>,
+    pub codecs: Vec<CodecBench>,
+}
+
+/// `ZSTD` for `ZSTD(ZstdLevel(3))`; the footer doesn't record the level.
+pub(crate) fn codec_name(compression: Compression) -> String {
+    let name = format!("{compression:?}");
+    match name.split_once('(') {
+        Some((codec, _)) => codec.to_string(),
+        None => name,
+    }
+}
+
+impl BenchReport {
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        Self::from_file_with_progress(path, &ScanProgress::default())
+    }
+
+    /// Like [`BenchReport::from_file`], reporting the compressed bytes of
+    /// the chunks decoded to `progress` and stopping if it is cancelled.
+    pub fn from_file_with_progress(path: &str, progress: &ScanProgress) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| e.to_string())?;
+        let metadata =
+            ArrowReaderMetadata::load(&file, Default::default()).map_err(|e| e.to_string())?;
+        let md = metadata.metadata().clone();
+        let schema = md.file_metadata().schema_descr();
+
+        let mut columns = Vec::new();
+        for leaf in 0..schema.num_columns() {
+            let mut bench = ColumnBench {
+                path: schema.column(leaf).path().string(),
+                codec: md
+                    .row_groups()
+                    .iter()
+                    .map(|rg| codec_name(rg.column(leaf).compression()))
+                    .unique()
+                    .join("/"),
+                rows: 0,
+                compressed_bytes: 0,
+                uncompressed_bytes: 0,
+                seconds: 0.0,
+            };
+            for rg in 0..md.num_row_groups() {
+                progress.check()?;
+                let chunk = md.row_group(rg).column(leaf);
+                let started = Instant::now();
+                let reader = ParquetRecordBatchReaderBuilder::new_with_metadata(
+                    file.try_clone().map_err(|e| e.to_string())?,
+                    metadata.clone(),
+                )
+                .with_row_groups(vec![rg])
+                .with_projection(ProjectionMask::leaves(schema, [leaf]))
+                .with_batch_size(READ_BATCH)
+                .build()
+                .map_err(|e| e.to_string())?;
+                for batch in reader {
+                    bench.rows += batch.map_err(|e| e.to_string())?.num_rows() as u64;
+                }
+                bench.seconds += started.elapsed().as_secs_f64();
+                bench.compressed_bytes += chunk.compressed_size().max(0) as u64;
+                bench.uncompressed_bytes += chunk.uncompressed_size().max(0) as u64;
+                progress.advance(chunk.compressed_size().max(0) as u64);
+            }
+            columns.push(bench);
+        }
+        columns.sort_by(|a, b| b.seconds.total_cmp(&a.seconds));
+
+        let codecs = columns
+            .iter()
+            .into_group_map_by(|column| column.codec.clone())
+            .into_iter()
+            .map(|(codec, columns)| CodecBench {
+                codec,
+                columns: columns.len(),
+                compressed_bytes: columns.iter().map(|c| c.compressed_bytes).sum(),
+                uncompressed_bytes: columns.iter().map(|c| c.uncompressed_bytes).sum(),
+                seconds: columns.iter().map(|c| c.seconds).sum(),
+            })
+            .sorted_by(|a, b| b.seconds.total_cmp(&a.seconds))
+            .collect();
+
+        Ok(BenchReport {
+            path: path.to_string(),
+            columns,
+            codecs,
+        })
+    }
+
+    pub fn total_seconds(&self) -> f64 {
+        self.columns.iter().map(|c| c.seconds).sum()
+    }
+
+    /// Share of the total decode time spent on `column`, from 0 to 1.
+    pub fn share(&self, column: &ColumnBench) -> f64 {
+        column.seconds / self.total_seconds().max(f64::MIN_POSITIVE)
+    }
+
+    /// One line naming the slowest column, for a status bar.
+    pub fn summary(&self) -> String {
+        match self.columns.first() {
+            None => "no columns to decode".to_string(),
+            Some(slowest) => format!(
+                "decoded {} column{} in {:.2}s; {} takes {:.0}% at {:.0} MB/s",
+                self.columns.len(),
+                if self.columns.len() == 1 { "" } else { "s" },
+                self.total_seconds(),
+                slowest.path,
+                self.share(slowest) * 100.0,
+                slowest.mb_per_sec()
+            ),
+        }
+    }
+}
+
+/// Left-aligns the first `left` columns, the names, and right-aligns the
+/// numbers after them.
+fn write_table(
+    f: &mut fmt::Formatter<'_>,
+    headers: &[&str],
+    left: usize,
+    rows: &[Vec<String>],
+) -> fmt::Result {
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|idx| {
+            rows.iter()
+                .map(|row| row[idx].chars().count())
+                .chain([headers[idx].len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let headers: Vec<String> = headers.iter().map(|h| h.to_string()).collect();
+    for row in std::iter::once(&headers).chain(rows) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(idx, (cell, &width))| {
+                if idx < left {
+                    format!("{cell:<width$}")
+                } else {
+                    format!("{cell:>width$}")
+                }
+            })
+            .join("  ");
+        writeln!(f, "{}", line.trim_end())?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}: {}", self.path, self.summary())?;
+        if self.columns.is_empty() {
+            return Ok(());
+        }
+        let rows: Vec<Vec<String>> = self
+            .columns
+            .iter()
+            .map(|c| {
+                vec![
+                    c.path.clone(),
+                    c.codec.clone(),
+                    human_readable_bytes(c.compressed_bytes),
+                    human_readable_bytes(c.uncompressed_bytes),
+                    format!("{:.3}s", c.seconds),
+                    format!("{:.1}", c.mb_per_sec()),
+                    commas(c.rows_per_sec().round() as u64),
+                    format!("{:.1}%", self.share(c) * 100.0),
+                ]
+            })
+            .collect();
+        writeln!(f)?;
+        write_table(
+            f,
+            &[
+                "Column",
+                "Codec",
+                "Compressed",
+                "Uncompressed",
+                "Time",
+                "MB/s",
+                "Rows/s",
+                "Share",
+            ],
+            2,
+            &rows,
+        )?;
+
+        let rows: Vec<Vec<String>> = self
+            .codecs
+            .iter()
+            .map(|c| {
+                vec![
+                    c.codec.clone(),
+                    c.columns.to_string(),
+                    human_readable_bytes(c.compressed_bytes),
+                    format!("{:.3}s", c.seconds),
+                    format!("{:.1}", c.mb_per_sec()),
+                ]
+            })
+            .collect();
+        writeln!(f)?;
+        write_table(
+            f,
+            &["Codec", "Columns", "Compressed", "Time", "MB/s"],
+            1,
+            &rows,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::write_test_file;
+    use arrow::array::{ArrayRef, Int64Array, RecordBatch, StringArray};
+    use parquet::basic::ZstdLevel;
+    use parquet::file::properties::WriterProperties;
+    use parquet::schema::types::ColumnPath;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_bench() {
+        let batch = RecordBatch::try_from_iter([
+            (
+                "id",
+                Arc::new(Int64Array::from_iter_values(0..5000)) as ArrayRef,
+            ),
+            (
+                "name",
+                Arc::new(StringArray::from_iter_values(
+                    (0..5000).map(|i| format!("name-{i}")),
+                )) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+        let props = WriterProperties::builder()
+            .set_max_row_group_row_count(Some(2000))
+            .set_compression(Compression::SNAPPY)
+            .set_column_compression(
+                ColumnPath::from("name"),
+                Compression::ZSTD(ZstdLevel::default()),
+            )
+            .build();
+        let path = write_test_file("bench", &batch, Some(props))
+            .display()
+            .to_string();
+
+        let progress = ScanProgress::new(0);
+        let report = BenchReport::from_file_with_progress(&path, &progress);
+        let cancelled = ScanProgress::new(0);
+        cancelled.cancel();
+        let stopped = BenchReport::from_file_with_progress(&path, &cancelled);
+        std::fs::remove_file(&path).unwrap();
+
+        let report = report.unwrap();
+        assert_eq!(stopped, Err(crate::file::progress::CANCELLED.to_string()));
+        let mut columns: Vec<_> = report
+            .columns
+            .iter()
+            .map(|c| (c.path.as_str(), c.codec.as_str(), c.rows))
+            .collect();
+        columns.sort();
+        assert_eq!(columns, [("id", "SNAPPY", 5000), ("name", "ZSTD", 5000)]);
+        let compressed: u64 = report.columns.iter().map(|c| c.compressed_bytes).sum();
+        assert_eq!(progress.done(), compressed);
+        assert_eq!(report.codecs.len(), 2);
+        assert!(report.columns[0].seconds >= report.columns[1].seconds);
+        let shares: f64 = report.columns.iter().map(|c| report.share(c)).sum();
+        assert!((shares - 1.0).abs() < 1e-9);
+        let text = report.to_string();
+        assert!(text.contains("\nColumn  Codec   Compressed"));
+        assert!(text.contains("\nCodec   Columns  Compressed"));
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use arrow::datatypes::DataType;
+use image::RgbImage;
+use image::imageops::FilterType;
+use parquet::arrow::ProjectionMask;
+use parquet::arrow::arrow_reader::{ParquetRecordBatchReaderBuilder, RowSelection, RowSelector};
+use serde_json::Value;
+
+use crate::file::sample_data::value_bytes;
+
+/// Largest side of the thumbnail kept of a decoded image, in pixels.
+/// Smaller images are kept as they are.
+const THUMBNAIL_SIZE: u32 = 128;
+
+/// Image formats recognized in binary columns by their first bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+}
+
+impl ImageFormat {
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(ImageFormat::Png)
+        } else if bytes.starts_with(&[0xff, 0xd8, 0xff]) {
+            Some(ImageFormat::Jpeg)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for ImageFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ImageFormat::Png => "PNG",
+            ImageFormat::Jpeg => "JPEG",
+        })
+    }
+}
+
+/// An image stored in a binary value, decoded and shrunk for a preview.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImagePreview {
+    pub format: ImageFormat,
+    pub width: u32,
+    pub height: u32,
+    /// Bytes of the stored value.
+    pub size: usize,
+    /// The image shrunk to fit in `THUMBNAIL_SIZE` pixels if it didn't.
+    pub thumbnail: RgbImage,
+}
+
+impl ImagePreview {
+    /// `None` if `bytes` isn't a PNG or JPEG, an error if it claims to be
+    /// one but can't be decoded.
+    pub fn decode(bytes: &[u8]) -> Option<Result<Self, String>> {
+        let format = ImageFormat::detect(bytes)?;
+        let image_format = match format {
+            ImageFormat::Png => image::ImageFormat::Png,
+            ImageFormat::Jpeg => image::ImageFormat::Jpeg,
+        };
+        Some(
+            image::load_from_memory_with_format(bytes, image_format)
+                .map(|image| ImagePreview {
+                    format,
+                    width: image.width(),
+                    height: image.height(),
+                    size: bytes.len(),
+                    thumbnail: if image.width().max(image.height()) > THUMBNAIL_SIZE {
+                        image.resize(THUMBNAIL_SIZE, THUMBNAIL_SIZE, FilterType::Triangle)
+                    } else {
+                        image
+                    }
+                    .to_rgb8(),
+                })
+                .map_err(|e| format!("{format} that can't be decoded: {e}")),
+        )
+    }
+}
+
+/// Images of one row by column name, or why a value that looked like one
+/// couldn't be decoded.
+pub type RowImages = BTreeMap<String, Result<ImagePreview, String>>;
+
+/// The images in the top-level binary columns of the row at `position` in
+/// the file, by column name. Values that aren't images are left out.
+pub fn read_images(
+    file_path: &str,
+    position: usize,
+) -> Result<RowImages, Box<dyn std::error::Error>> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(file_path)?)?;
+    let binary: Vec<usize> = builder
+        .schema()
+        .fields()
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| {
+            matches!(
+                field.data_type(),
+                DataType::Binary
+                    | DataType::LargeBinary
+                    | DataType::BinaryView
+                    | DataType::FixedSizeBinary(_)
+            )
+        })
+        .map(|(i, _)| i)
+        .collect();
+    if binary.is_empty() || position >= builder.metadata().file_metadata().num_rows() as usize {
+        return Ok(BTreeMap::new());
+    }
+    let mask = ProjectionMask::roots(builder.parquet_schema(), binary);
+    let reader = builder
+        .with_projection(mask)
+        .with_row_selection(RowSelection::from(vec![
+            RowSelector::skip(position),
+            RowSelector::select(1),
+        ]))
+        .build()?;
+
+    let mut images = BTreeMap::new();
+    for batch in reader {
+        let batch = batch?;
+        if batch.num_rows() == 0 {
+            continue;
+        }
+        for (field, column) in batch.schema().fields().iter().zip(batch.columns()) {
+            if let Some(image) = value_bytes(column.as_ref(), 0).and_then(ImagePreview::decode) {
+                images.insert(field.name().clone(), image);
+            }
+        }
+    }
+    Ok(images)
+}
+
+/// A list of floats taken for an embedding vector.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Embedding {
+    pub values: Vec<f64>,
+    /// Euclidean length of the vector.
+    pub norm: f64,
+}
+
+impl Embedding {
+    /// An array of at least two numbers, all of them floats, as previewed
+    /// from a list of FLOAT or DOUBLE.
+    pub fn from_json(value: &Value) -> Option<Self> {
+        let Value::Array(items) = value else {
+            return None;
+        };
+        if items.len() < 2 {
+            return None;
+        }
+        let values = items
+            .iter()
+            .map(|item| match item {
+                Value::Number(n) if n.is_f64() => n.as_f64(),
+                _ => None,
+            })
+            .collect::<Option<Vec<f64>>>()?;
+        let norm = values.iter().map(|v| v * v).sum::<f64>().sqrt();
+        Some(Embedding { values, norm })
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.values.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::write_test_file;
+    use arrow::array::{BinaryArray, Int32Array, RecordBatch};
+    use image::codecs::png::PngEncoder;
+    use image::{ExtendedColorType, ImageEncoder};
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let pixels = vec![200u8; (width * height * 3) as usize];
+        let mut bytes = Vec::new();
+        PngEncoder::new(&mut bytes)
+            .write_image(&pixels, width, height, ExtendedColorType::Rgb8)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(ImageFormat::detect(&png(1, 1)), Some(ImageFormat::Png));
+        assert_eq!(
+            ImageFormat::detect(&[0xff, 0xd8, 0xff, 0xe0]),
+            Some(ImageFormat::Jpeg)
+        );
+        assert_eq!(ImageFormat::detect(b"GIF89a"), None);
+        assert!(ImagePreview::decode(b"text").is_none());
+        assert!(
+            ImagePreview::decode(b"\x89PNG\r\n\x1a\ntruncated")
+                .unwrap()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_read_images() {
+        let image = png(300, 150);
+        let batch = RecordBatch::try_from_iter([
+            ("id", Arc::new(Int32Array::from(vec![1, 2])) as _),
+            (
+                "photo",
+                Arc::new(BinaryArray::from(vec![b"not an image".as_slice(), &image])) as _,
+            ),
+        ])
+        .unwrap();
+        let path = write_test_file("blob", &batch, None);
+        let path_text = path.display().to_string();
+
+        assert!(read_images(&path_text, 0).unwrap().is_empty());
+        let images = read_images(&path_text, 1).unwrap();
+        let preview = images["photo"].as_ref().unwrap();
+        assert_eq!(preview.format, ImageFormat::Png);
+        assert_eq!((preview.width, preview.height), (300, 150));
+        assert_eq!(preview.size, image.len());
+        assert_eq!(preview.thumbnail.dimensions(), (128, 64));
+        assert!(read_images(&path_text, 5).unwrap().is_empty());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_embedding() {
+        let embedding = Embedding::from_json(&json!([3.0, 4.0])).unwrap();
+        assert_eq!(embedding.dimension(), 2);
+        assert_eq!(embedding.norm, 5.0);
+        assert!(Embedding::from_json(&json!([1, 2, 3])).is_none());
+        assert!(Embedding::from_json(&json!([0.5])).is_none());
+        assert!(Embedding::from_json(&json!([0.5, "a"])).is_none());
+    }
+}
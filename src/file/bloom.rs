@@ -0,0 +1,407 @@
+use std::fmt;
+use std::str::FromStr;
+
+use parquet::basic::Type as PhysicalType;
+use parquet::file::metadata::ParquetMetaData;
+use parquet::file::reader::FileReader;
+use parquet::schema::types::ColumnDescriptor;
+use serde::Serialize;
+
+use crate::file::error::FileIOError;
+use crate::file::parquet_ctx::open_reader;
+use crate::file::pruning::{
+    Literal, Value, bind_literal, decimal_scale, resolve_column, row_group_may_equal,
+};
+use crate::file::utils::human_readable_bytes;
+
+/// Where the probe values come from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Probes {
+    /// Typed in, separated by commas.
+    Values(Vec<String>),
+    /// Read from a file, one value per line.
+    File(String),
+}
+
+/// A column and the values to look up in its bloom filters, parsed from
+/// `<column> <value>, <value>, ...` or `<column> @<file>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BloomQuery {
+    pub column: String,
+    pub probes: Probes,
+}
+
+impl FromStr for BloomQuery {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let usage = || "usage: bloom <column> <value>, <value>, ... | @<file>".to_string();
+        let (column, rest) = input
+            .trim()
+            .split_once(char::is_whitespace)
+            .ok_or_else(usage)?;
+        let rest = rest.trim();
+        let probes = if let Some(path) = rest.strip_prefix('@') {
+            Probes::File(path.trim().to_string())
+        } else {
+            Probes::Values(
+                rest.split(',')
+                    .map(|value| unquote(value.trim()).to_string())
+                    .filter(|value| !value.is_empty())
+                    .collect(),
+            )
+        };
+        if matches!(&probes, Probes::Values(values) if values.is_empty())
+            || matches!(&probes, Probes::File(path) if path.is_empty())
+        {
+            return Err(usage());
+        }
+        Ok(Self {
+            column: column.to_string(),
+            probes,
+        })
+    }
+}
+
+impl fmt::Display for BloomQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.probes {
+            Probes::Values(values) => write!(f, "{} {}", self.column, values.join(", ")),
+            Probes::File(path) => write!(f, "{} @{path}", self.column),
+        }
+    }
+}
+
+impl BloomQuery {
+    /// The probe values, reading the file if they come from one.
+    pub fn values(&self) -> Result<Vec<String>, String> {
+        match &self.probes {
+            Probes::Values(values) => Ok(values.clone()),
+            Probes::File(path) => {
+                let text = std::fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+                let values: Vec<String> = text
+                    .lines()
+                    .map(|line| unquote(line.trim()).to_string())
+                    .filter(|line| !line.is_empty())
+                    .collect();
+                if values.is_empty() {
+                    return Err(format!("{path}: no probe values"));
+                }
+                Ok(values)
+            }
+        }
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('\'')
+        .and_then(|v| v.strip_suffix('\''))
+        .or_else(|| value.strip_prefix('"').and_then(|v| v.strip_suffix('"')))
+        .unwrap_or(value)
+}
+
+/// The leaf index of `column` and each value with its encoding.
+type EncodedProbes = (usize, Vec<(Value, Vec<u8>)>);
+
+fn resolve_probes(
+    md: &ParquetMetaData,
+    column: &str,
+    values: &[String],
+) -> Result<EncodedProbes, String> {
+    let schema = md.file_metadata().schema_descr();
+    let col_idx = resolve_column(schema, column)?;
+    let descr = schema.column(col_idx);
+    let probes = values
+        .iter()
+        .map(|value| encode_probe(value, &descr))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((col_idx, probes))
+}
+
+/// Type a probe like the column and plain-encode it, which is what bloom
+/// filters hash.
+fn encode_probe(text: &str, column: &ColumnDescriptor) -> Result<(Value, Vec<u8>), String> {
+    let is_bytes = matches!(
+        column.physical_type(),
+        PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY
+    );
+    let literal = if is_bytes && decimal_scale(column).is_none() {
+        Literal::Str(text.to_string())
+    } else if text.parse::<f64>().is_ok() {
+        Literal::Number(text.to_string())
+    } else {
+        Literal::Str(text.to_string())
+    };
+    let value = bind_literal(&literal, column)?;
+    let out_of_range = || format!("'{text}' does not fit {}", column.path());
+
+    let bytes = match (&value, column.physical_type()) {
+        (Value::Int(i), PhysicalType::INT32) => i32::try_from(*i)
+            .map_err(|_| out_of_range())?
+            .to_le_bytes()
+            .to_vec(),
+        (Value::Int(i), PhysicalType::INT64) => i64::try_from(*i)
+            .map_err(|_| out_of_range())?
+            .to_le_bytes()
+            .to_vec(),
+        (Value::Float(f), PhysicalType::FLOAT) => (*f as f32).to_le_bytes().to_vec(),
+        (Value::Float(f), PhysicalType::DOUBLE) => f.to_le_bytes().to_vec(),
+        (Value::Bytes(bytes), _) => bytes.clone(),
+        // Decimals stored as big-endian two's complement.
+        (Value::Int(i), PhysicalType::FIXED_LEN_BYTE_ARRAY) => {
+            let len = column.type_length().clamp(1, 16) as usize;
+            i.to_be_bytes()[16 - len..].to_vec()
+        }
+        (Value::Int(i), PhysicalType::BYTE_ARRAY) => {
+            let bytes = i.to_be_bytes();
+            let sign = if *i < 0 { 0xff } else { 0 };
+            // Drop leading sign bytes the next byte's top bit already implies.
+            let start = (0..15)
+                .find(|&b| bytes[b] != sign || (bytes[b + 1] & 0x80) != (sign & 0x80))
+                .unwrap_or(15);
+            bytes[start..].to_vec()
+        }
+        _ => {
+            return Err(format!(
+                "{} ({:?}) has no bloom filter to probe",
+                column.path(),
+                column.physical_type()
+            ));
+        }
+    };
+    Ok((value, bytes))
+}
+
+/// How the probes fare against one row group's column chunk.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RowGroupBloom {
+    pub idx: usize,
+    /// Compressed size of the probed column chunk.
+    pub chunk_size: u64,
+    /// Size of the chunk's bloom filter, `None` when it has none.
+    pub filter_size: Option<u64>,
+    /// Probes min/max statistics already rule out.
+    pub eliminated_by_stats: usize,
+    /// Probes the bloom filter rules out on top of min/max.
+    pub eliminated_by_bloom: usize,
+}
+
+impl RowGroupBloom {
+    /// Column chunk reads the bloom filter saves.
+    pub fn saved_bytes(&self) -> u64 {
+        self.eliminated_by_bloom as u64 * self.chunk_size
+    }
+}
+
+/// What bloom filters on a column would save for a set of point lookups.
+/// Each probe is treated as a lookup that reads the column chunk of every
+/// row group it is not ruled out of.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BloomReport {
+    pub column: String,
+    pub probes: usize,
+    pub row_groups: Vec<RowGroupBloom>,
+}
+
+impl BloomReport {
+    /// Open `path` with its bloom filters and probe `column` with `values`.
+    pub fn from_file(path: &str, column: &str, values: &[String]) -> Result<Self, FileIOError> {
+        let (reader, _) = open_reader(path)?;
+        Self::from_reader(&reader.with_bloom_filters(), column, values)
+            .map_err(|details| FileIOError::MetadataError { details })
+    }
+
+    /// Probe the bloom filters `reader` loaded; readers opened without
+    /// bloom filters look like a file that has none.
+    pub fn from_reader(
+        reader: &dyn FileReader,
+        column: &str,
+        values: &[String],
+    ) -> Result<Self, String> {
+        let md = reader.metadata();
+        let (col_idx, probes) = resolve_probes(md, column, values)?;
+        let descr = md.file_metadata().schema_descr().column(col_idx);
+
+        let mut row_groups = Vec::new();
+        for (idx, rg) in md.row_groups().iter().enumerate() {
+            let chunk = rg.column(col_idx);
+            let row_group = reader.get_row_group(idx).map_err(|e| e.to_string())?;
+            let filter = row_group.get_column_bloom_filter(col_idx);
+
+            let mut eliminated_by_stats = 0;
+            let mut eliminated_by_bloom = 0;
+            for (value, bytes) in &probes {
+                if !row_group_may_equal(rg, col_idx, value) {
+                    eliminated_by_stats += 1;
+                } else if filter.is_some_and(|filter| !filter.check(bytes.as_slice())) {
+                    eliminated_by_bloom += 1;
+                }
+            }
+
+            row_groups.push(RowGroupBloom {
+                idx,
+                chunk_size: chunk.compressed_size().max(0) as u64,
+                filter_size: filter.map(|_| chunk.bloom_filter_length().unwrap_or(0).max(0) as u64),
+                eliminated_by_stats,
+                eliminated_by_bloom,
+            });
+        }
+
+        Ok(Self {
+            column: descr.path().string(),
+            probes: probes.len(),
+            row_groups,
+        })
+    }
+
+    /// Check that `column` exists and that `values` parse as its values,
+    /// without reading any bloom filter.
+    pub fn check(md: &ParquetMetaData, column: &str, values: &[String]) -> Result<(), String> {
+        resolve_probes(md, column, values).map(|_| ())
+    }
+
+    /// Row groups at least one probe still reads.
+    pub fn read_row_groups(&self) -> Vec<usize> {
+        self.row_groups
+            .iter()
+            .filter(|rg| rg.eliminated_by_stats + rg.eliminated_by_bloom < self.probes)
+            .map(|rg| rg.idx)
+            .collect()
+    }
+
+    pub fn has_filters(&self) -> bool {
+        self.row_groups.iter().any(|rg| rg.filter_size.is_some())
+    }
+
+    /// One line summary, e.g. "bloom filters rule out 40 of 52 lookups left
+    /// after min/max, saving 3.1 MiB (77%)".
+    pub fn summary(&self) -> String {
+        if !self.has_filters() {
+            return format!("{} has no bloom filters", self.column);
+        }
+        let (mut lookups, mut eliminated, mut bytes, mut saved) = (0, 0, 0, 0);
+        for rg in &self.row_groups {
+            let remaining = self.probes - rg.eliminated_by_stats;
+            lookups += remaining;
+            eliminated += rg.eliminated_by_bloom;
+            bytes += remaining as u64 * rg.chunk_size;
+            saved += rg.saved_bytes();
+        }
+        let percent = if bytes > 0 {
+            saved as f64 / bytes as f64 * 100.0
+        } else {
+            0.0
+        };
+        format!(
+            "bloom filters rule out {eliminated} of {lookups} lookups left after min/max, saving {} ({percent:.0}%)",
+            human_readable_bytes(saved)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::{test_data_path, write_test_file};
+    use arrow::array::{Int64Array, RecordBatch, StringArray};
+    use parquet::file::properties::WriterProperties;
+    use std::sync::Arc;
+
+    /// Two row groups of 100 rows: `id` 0..200 and `name` "n0".."n199".
+    fn write_bloom_file() -> String {
+        let ids: Vec<i64> = (0..200).collect();
+        let batch = RecordBatch::try_from_iter([
+            ("id", Arc::new(Int64Array::from(ids.clone())) as _),
+            (
+                "name",
+                Arc::new(StringArray::from_iter_values(
+                    ids.iter().map(|i| format!("n{i}")),
+                )) as _,
+            ),
+        ])
+        .unwrap();
+        let props = WriterProperties::builder()
+            .set_max_row_group_row_count(Some(100))
+            .set_bloom_filter_enabled(true)
+            .build();
+        let path = write_test_file("bloom", &batch, Some(props));
+        path.display().to_string()
+    }
+
+    fn values(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let query: BloomQuery = "user_id 1, '2', \"x y\"".parse().unwrap();
+        assert_eq!(query.column, "user_id");
+        assert_eq!(query.probes, Probes::Values(values(&["1", "2", "x y"])));
+        assert_eq!(query.to_string(), "user_id 1, 2, x y");
+
+        let query: BloomQuery = "user_id @keys.txt".parse().unwrap();
+        assert_eq!(query.probes, Probes::File("keys.txt".to_string()));
+
+        assert!("user_id".parse::<BloomQuery>().is_err());
+        assert!("user_id ,".parse::<BloomQuery>().is_err());
+        assert!("user_id @".parse::<BloomQuery>().is_err());
+    }
+
+    #[test]
+    fn test_bloom_filters_add_to_min_max() {
+        let path = write_bloom_file();
+
+        // The first row group's names run from "n0" to "n99" lexically, so
+        // min/max cannot rule out "n150" or "n42x" there but the filter can.
+        let report =
+            BloomReport::from_file(&path, "name", &values(&["n5", "n150", "n42x"])).unwrap();
+        assert!(report.has_filters());
+        let eliminated: Vec<(usize, usize)> = report
+            .row_groups
+            .iter()
+            .map(|rg| (rg.eliminated_by_stats, rg.eliminated_by_bloom))
+            .collect();
+        assert_eq!(eliminated, vec![(0, 2), (2, 0)]);
+        assert_eq!(report.read_row_groups(), vec![0, 1]);
+        assert_eq!(
+            report.row_groups[0].saved_bytes(),
+            2 * report.row_groups[0].chunk_size
+        );
+        assert!(
+            report
+                .summary()
+                .starts_with("bloom filters rule out 2 of 4 lookups")
+        );
+
+        // Integer ranges don't overlap: min/max does all the work.
+        let report = BloomReport::from_file(&path, "id", &values(&["5", "1000"])).unwrap();
+        let eliminated: Vec<(usize, usize)> = report
+            .row_groups
+            .iter()
+            .map(|rg| (rg.eliminated_by_stats, rg.eliminated_by_bloom))
+            .collect();
+        assert_eq!(eliminated, vec![(1, 0), (2, 0)]);
+        assert_eq!(report.read_row_groups(), vec![0]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_files_without_filters() {
+        let path = test_data_path("alltypes_plain.parquet");
+        let report = BloomReport::from_file(&path, "id", &values(&["4", "100"])).unwrap();
+        assert!(!report.has_filters());
+        assert!(
+            report
+                .row_groups
+                .iter()
+                .all(|rg| rg.eliminated_by_bloom == 0)
+        );
+        assert_eq!(report.summary(), "id has no bloom filters");
+
+        assert!(BloomReport::from_file(&path, "nope", &values(&["1"])).is_err());
+        assert!(BloomReport::from_file(&path, "id", &values(&["abc"])).is_err());
+        assert!(BloomReport::from_file(&path, "bool_col", &values(&["true"])).is_err());
+    }
+}
@@ -0,0 +1,533 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{Read, Write};
+
+use itertools::Itertools;
+use parquet::basic::{BrotliLevel, Compression, GzipLevel, ZstdLevel};
+use parquet::file::metadata::{ColumnChunkMetaData, ParquetMetaDataReader};
+use parquet::file::reader::ChunkReader;
+use serde::Serialize;
+
+use crate::file::bench::codec_name;
+use crate::file::page_header::PageHeaders;
+use crate::file::progress::ScanProgress;
+use crate::file::utils::human_readable_bytes;
+
+/// Uncompressed bytes of each column's pages recompressed to estimate its
+/// level.
+pub const CODEC_SAMPLE_BYTES: u64 = 4 << 20;
+
+/// Smallest share of a column's bytes a level upgrade must save to be
+/// recommended.
+const MIN_SAVING: f64 = 0.05;
+
+/// Buffer size and window parquet writes BROTLI pages with.
+const BROTLI_BUFFER: usize = 4096;
+const BROTLI_LG_WINDOW: u32 = 22;
+
+/// Codecs that take a level, as named by [`codec_name`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum LeveledCodec {
+    Zstd,
+    Gzip,
+    Brotli,
+}
+
+impl LeveledCodec {
+    fn from_compression(compression: Compression) -> Option<Self> {
+        match compression {
+            Compression::ZSTD(_) => Some(LeveledCodec::Zstd),
+            Compression::GZIP(_) => Some(LeveledCodec::Gzip),
+            Compression::BROTLI(_) => Some(LeveledCodec::Brotli),
+            _ => None,
+        }
+    }
+
+    /// Levels tried when estimating the one a column was written with.
+    fn candidates(self) -> &'static [u32] {
+        match self {
+            LeveledCodec::Zstd => &[1, 3, 6, 9, 12, 15, 19],
+            LeveledCodec::Gzip => &[1, 3, 6, 9],
+            LeveledCodec::Brotli => &[1, 4, 6, 9, 11],
+        }
+    }
+
+    /// Level recommended by default: a clear gain that still writes at a
+    /// reasonable speed.
+    pub fn default_target(self) -> u32 {
+        9
+    }
+
+    /// An error naming the valid range if the codec has no such level.
+    fn check_level(self, level: u32) -> Result<(), String> {
+        match self {
+            LeveledCodec::Zstd => ZstdLevel::try_new(level as i32).map(|_| ()),
+            LeveledCodec::Gzip => GzipLevel::try_new(level).map(|_| ()),
+            LeveledCodec::Brotli => BrotliLevel::try_new(level).map(|_| ()),
+        }
+        .map_err(|e| e.to_string())
+    }
+
+    fn decompress(self, data: &[u8]) -> std::io::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        match self {
+            LeveledCodec::Zstd => return zstd::stream::decode_all(data),
+            LeveledCodec::Gzip => flate2::read::MultiGzDecoder::new(data).read_to_end(&mut out)?,
+            LeveledCodec::Brotli => {
+                brotli::Decompressor::new(data, BROTLI_BUFFER).read_to_end(&mut out)?
+            }
+        };
+        Ok(out)
+    }
+
+    /// Length of `data` compressed at `level`, as parquet would write it.
+    fn compressed_len(self, data: &[u8], level: u32) -> std::io::Result<u64> {
+        let out = match self {
+            LeveledCodec::Zstd => zstd::bulk::compress(data, level as i32)?,
+            LeveledCodec::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::new(level));
+                encoder.write_all(data)?;
+                encoder.finish()?
+            }
+            LeveledCodec::Brotli => {
+                let mut writer = brotli::CompressorWriter::new(
+                    Vec::new(),
+                    BROTLI_BUFFER,
+                    level,
+                    BROTLI_LG_WINDOW,
+                );
+                writer.write_all(data)?;
+                writer.into_inner()
+            }
+        };
+        Ok(out.len() as u64)
+    }
+}
+
+/// Compressed size of the sampled pages at one level.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct LevelProbe {
+    pub level: u32,
+    pub bytes: u64,
+}
+
+/// Writing a column at a higher level, with the bytes it would save over
+/// the whole file.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct LevelUpgrade {
+    pub level: u32,
+    pub saved_bytes: u64,
+    /// Share of the column's compressed bytes saved, from 0 to 1.
+    pub saving: f64,
+}
+
+/// The codec of one column and, for codecs with levels, the level it was
+/// most likely written with.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ColumnCodec {
+    pub path: String,
+    /// Codecs of its chunks, such as `ZSTD` or `SNAPPY/ZSTD`.
+    pub codec: String,
+    pub compressed_bytes: u64,
+    pub uncompressed_bytes: u64,
+    /// Uncompressed bytes of the pages recompressed.
+    pub sampled_bytes: u64,
+    /// Size of those pages in the file.
+    pub sampled_compressed: u64,
+    /// The footer doesn't record levels, so this is the level that
+    /// recompresses the sampled pages closest to their size in the file.
+    pub estimated_level: Option<u32>,
+    pub probes: Vec<LevelProbe>,
+    pub upgrade: Option<LevelUpgrade>,
+}
+
+/// Codec details of every column, with the level upgrades worth making.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CodecReport {
+    pub path: String,
+    pub columns: Vec<ColumnCodec>,
+}
+
+impl CodecReport {
+    pub fn from_file(path: &str, target: Option<u32>) -> Result<Self, String> {
+        Self::from_file_with_progress(path, target, &ScanProgress::default())
+    }
+
+    /// Like [`CodecReport::from_file`], reporting the compressed bytes of
+    /// the sampled chunks to `progress` and stopping if it is cancelled.
+    /// `target` overrides the level recommended for every codec.
+    pub fn from_file_with_progress(
+        path: &str,
+        target: Option<u32>,
+        progress: &ScanProgress,
+    ) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| e.to_string())?;
+        let md = ParquetMetaDataReader::new()
+            .parse_and_finish(&file)
+            .map_err(|e| e.to_string())?;
+        let schema = md.file_metadata().schema_descr();
+        // Pages are sampled from the first row group with rows.
+        let sample_rg = md.row_groups().iter().find(|rg| rg.num_rows() > 0);
+
+        let mut columns = Vec::new();
+        for leaf in 0..schema.num_columns() {
+            let chunks = || md.row_groups().iter().map(move |rg| rg.column(leaf));
+            let mut column = ColumnCodec {
+                path: schema.column(leaf).path().string(),
+                codec: chunks()
+                    .map(|chunk| codec_name(chunk.compression()))
+                    .unique()
+                    .join("/"),
+                compressed_bytes: chunks().map(|c| c.compressed_size().max(0) as u64).sum(),
+                uncompressed_bytes: chunks().map(|c| c.uncompressed_size().max(0) as u64).sum(),
+                sampled_bytes: 0,
+                sampled_compressed: 0,
+                estimated_level: None,
+                probes: Vec::new(),
+                upgrade: None,
+            };
+            let Some(chunk) = sample_rg.map(|rg| rg.column(leaf)) else {
+                columns.push(column);
+                continue;
+            };
+            // Mixed codecs have no single level to estimate.
+            let codec = LeveledCodec::from_compression(chunk.compression())
+                .filter(|_| chunks().all(|c| c.compression() == chunk.compression()));
+            if let Some(codec) = codec {
+                progress.check()?;
+                let target = target.unwrap_or(codec.default_target());
+                codec.check_level(target)?;
+                let pages = sample_pages(&file, chunk, codec)?;
+                column.sampled_bytes = pages.iter().map(|(data, _)| data.len() as u64).sum();
+                column.sampled_compressed = pages.iter().map(|&(_, size)| size).sum();
+                let levels = codec
+                    .candidates()
+                    .iter()
+                    .copied()
+                    .chain([target])
+                    .unique()
+                    .sorted();
+                for level in levels {
+                    progress.check()?;
+                    let mut bytes = 0;
+                    for (data, _) in &pages {
+                        bytes += codec
+                            .compressed_len(data, level)
+                            .map_err(|e| e.to_string())?;
+                    }
+                    column.probes.push(LevelProbe { level, bytes });
+                }
+                column.estimated_level =
+                    estimate_level(&column.probes, column.sampled_compressed as f64);
+                column.upgrade = column
+                    .estimated_level
+                    .and_then(|level| upgrade(&column.probes, level, target))
+                    .map(|saving| LevelUpgrade {
+                        level: target,
+                        saved_bytes: (column.compressed_bytes as f64 * saving) as u64,
+                        saving,
+                    });
+            }
+            progress.advance(chunk.compressed_size().max(0) as u64);
+            columns.push(column);
+        }
+
+        Ok(CodecReport {
+            path: path.to_string(),
+            columns,
+        })
+    }
+
+    /// Bytes the recommended upgrades would save together.
+    pub fn total_savings(&self) -> u64 {
+        self.columns
+            .iter()
+            .filter_map(|c| c.upgrade)
+            .map(|u| u.saved_bytes)
+            .sum()
+    }
+
+    /// One line with the upgrades worth making, for a status bar.
+    pub fn summary(&self) -> String {
+        let upgrades: Vec<&ColumnCodec> = self
+            .columns
+            .iter()
+            .filter(|c| c.upgrade.is_some())
+            .collect();
+        if upgrades.is_empty() {
+            return if self.columns.iter().any(|c| c.estimated_level.is_some()) {
+                "no level upgrade would save much".to_string()
+            } else {
+                "no ZSTD, GZIP or BROTLI columns to tune".to_string()
+            };
+        }
+        format!(
+            "a higher level would save about {} in {} column{}, most in {}",
+            human_readable_bytes(self.total_savings()),
+            upgrades.len(),
+            if upgrades.len() == 1 { "" } else { "s" },
+            upgrades
+                .iter()
+                .max_by_key(|c| c.upgrade.map(|u| u.saved_bytes))
+                .map(|c| c.path.as_str())
+                .unwrap_or_default()
+        )
+    }
+}
+
+/// The compressed pages of a chunk, decompressed, each with its size in the
+/// file, until [`CODEC_SAMPLE_BYTES`] are collected. V2 pages keep their
+/// levels uncompressed ahead of the values, so only the values count.
+fn sample_pages<R: ChunkReader>(
+    file: &R,
+    chunk: &ColumnChunkMetaData,
+    codec: LeveledCodec,
+) -> Result<Vec<(Vec<u8>, u64)>, String> {
+    let headers = PageHeaders::read(file, chunk);
+    if let Some(error) = headers.error {
+        return Err(error);
+    }
+    let mut pages = Vec::new();
+    let mut sampled = 0;
+    for header in &headers.headers {
+        if sampled >= CODEC_SAMPLE_BYTES {
+            break;
+        }
+        if header.page_type == "Index Page" {
+            continue;
+        }
+        let levels = match &header.v2 {
+            Some(v2) if !v2.is_compressed => continue,
+            Some(v2) => (v2.def_levels_byte_len + v2.rep_levels_byte_len) as usize,
+            None => 0,
+        };
+        let start = header.offset + header.header_size as u64;
+        let bytes = file
+            .get_bytes(start, header.compressed_size.max(0) as usize)
+            .map_err(|e| e.to_string())?;
+        let compressed = bytes.get(levels..).unwrap_or_default();
+        let data = codec.decompress(compressed).map_err(|e| {
+            format!(
+                "cannot decompress the page of {} at offset {}: {e}",
+                chunk.column_path(),
+                header.offset
+            )
+        })?;
+        sampled += data.len() as u64;
+        pages.push((data, compressed.len() as u64));
+    }
+    Ok(pages)
+}
+
+/// The level whose size is closest to `original`.
+fn estimate_level(probes: &[LevelProbe], original: f64) -> Option<u32> {
+    probes
+        .iter()
+        .filter(|_| original > 0.0)
+        .min_by(|a, b| {
+            let distance = |probe: &LevelProbe| (probe.bytes as f64 - original).abs();
+            distance(a).total_cmp(&distance(b))
+        })
+        .map(|probe| probe.level)
+}
+
+/// Share of bytes saved going from `level` to `target`, if at least
+/// [`MIN_SAVING`].
+fn upgrade(probes: &[LevelProbe], level: u32, target: u32) -> Option<f64> {
+    if target <= level {
+        return None;
+    }
+    let size = |level: u32| probes.iter().find(|p| p.level == level).map(|p| p.bytes);
+    let (current, upgraded) = (size(level)?, size(target)?);
+    let saving = 1.0 - upgraded as f64 / current.max(1) as f64;
+    (saving >= MIN_SAVING).then_some(saving)
+}
+
+impl fmt::Display for CodecReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}: {}", self.path, self.summary())?;
+        if self.columns.is_empty() {
+            return Ok(());
+        }
+        let rows: Vec<[String; 6]> = self
+            .columns
+            .iter()
+            .map(|c| {
+                [
+                    c.path.clone(),
+                    c.codec.clone(),
+                    human_readable_bytes(c.compressed_bytes),
+                    format!(
+                        "{:.2}",
+                        c.uncompressed_bytes as f64 / c.compressed_bytes.max(1) as f64
+                    ),
+                    c.estimated_level
+                        .map_or("-".to_string(), |level| format!("~{level}")),
+                    c.upgrade.map_or(String::new(), |u| {
+                        format!(
+                            "level {}: -{} ({:.0}%)",
+                            u.level,
+                            human_readable_bytes(u.saved_bytes),
+                            u.saving * 100.0
+                        )
+                    }),
+                ]
+            })
+            .collect();
+        let headers = ["Column", "Codec", "Size", "Ratio", "Level", "Upgrade"].map(str::to_string);
+        let widths: Vec<usize> = (0..5)
+            .map(|idx| {
+                rows.iter()
+                    .chain([&headers])
+                    .map(|row| row[idx].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        writeln!(f)?;
+        for row in std::iter::once(&headers).chain(&rows) {
+            let line = format!(
+                "{:<w0$}  {:<w1$}  {:>w2$}  {:>w3$}  {:>w4$}  {}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                row[4],
+                row[5],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2],
+                w3 = widths[3],
+                w4 = widths[4],
+            );
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        if self.columns.iter().any(|c| c.estimated_level.is_some()) {
+            writeln!(
+                f,
+                "\nLevels are estimated by recompressing up to {} of each column's pages at \
+                 several levels; the footer doesn't record them.",
+                human_readable_bytes(CODEC_SAMPLE_BYTES)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::sample_data::SplitMix64;
+    use crate::file::write_test_file;
+    use arrow::array::{ArrayRef, Int64Array, RecordBatch, StringArray};
+    use parquet::file::properties::{WriterProperties, WriterVersion};
+    use parquet::schema::types::ColumnPath;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_estimate_and_upgrade() {
+        let probes = [
+            LevelProbe {
+                level: 1,
+                bytes: 1000,
+            },
+            LevelProbe {
+                level: 3,
+                bytes: 900,
+            },
+            LevelProbe {
+                level: 9,
+                bytes: 700,
+            },
+        ];
+        assert_eq!(estimate_level(&probes, 980.0), Some(1));
+        assert_eq!(estimate_level(&probes, 880.0), Some(3));
+        assert_eq!(estimate_level(&probes, 0.0), None);
+        let saving = upgrade(&probes, 1, 9).unwrap();
+        assert!((saving - 0.3).abs() < 1e-9);
+        assert_eq!(upgrade(&probes, 9, 9), None);
+        assert!(upgrade(&probes, 3, 9).is_some());
+        assert_eq!(upgrade(&probes, 3, 1), None);
+    }
+
+    /// A SNAPPY id column next to random words in ZSTD at `level`, which
+    /// higher levels squeeze further.
+    fn write_levels(name: &str, level: i32, version: WriterVersion) -> String {
+        const WORDS: [&str; 12] = [
+            "alpha", "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "hotel", "india",
+            "juliett", "kilo", "lima",
+        ];
+        let mut rng = SplitMix64::new(7);
+        let batch = RecordBatch::try_from_iter([
+            (
+                "id",
+                Arc::new(Int64Array::from_iter_values(0..20_000)) as ArrayRef,
+            ),
+            (
+                "text",
+                Arc::new(StringArray::from_iter_values((0..20_000).map(|_| {
+                    (0..8)
+                        .map(|_| WORDS[rng.next_u64() as usize % WORDS.len()])
+                        .join(" ")
+                }))) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+        let props = WriterProperties::builder()
+            .set_writer_version(version)
+            .set_compression(Compression::SNAPPY)
+            .set_column_compression(
+                ColumnPath::from("text"),
+                Compression::ZSTD(ZstdLevel::try_new(level).unwrap()),
+            )
+            .set_dictionary_enabled(false)
+            .build();
+        write_test_file(&format!("codecs-{name}"), &batch, Some(props))
+            .display()
+            .to_string()
+    }
+
+    #[test]
+    fn test_codec_report() {
+        let path = write_levels("v1", 1, WriterVersion::PARQUET_1_0);
+        let report = CodecReport::from_file(&path, None);
+        let bad_target = CodecReport::from_file(&path, Some(40));
+        std::fs::remove_file(&path).unwrap();
+
+        let report = report.unwrap();
+        let [id, text] = report.columns.as_slice() else {
+            panic!("expected two columns");
+        };
+        assert_eq!((id.codec.as_str(), id.estimated_level), ("SNAPPY", None));
+        assert!(id.probes.is_empty());
+        assert_eq!(text.codec, "ZSTD");
+        assert_eq!(text.estimated_level, Some(1));
+        // Every page is sampled; only their headers are left out of both sizes.
+        assert_eq!(
+            text.compressed_bytes - text.sampled_compressed,
+            text.uncompressed_bytes - text.sampled_bytes
+        );
+        let upgrade = text.upgrade.unwrap();
+        assert_eq!(upgrade.level, 9);
+        assert!(upgrade.saved_bytes > 0 && upgrade.saving >= MIN_SAVING);
+        assert_eq!(report.total_savings(), upgrade.saved_bytes);
+        assert!(report.summary().ends_with("in 1 column, most in text"));
+        assert!(report.to_string().contains("\nColumn  Codec   "));
+        assert!(bad_target.is_err());
+    }
+
+    #[test]
+    fn test_codec_report_v2_pages() {
+        let path = write_levels("v2", 12, WriterVersion::PARQUET_2_0);
+        let report = CodecReport::from_file(&path, None);
+        std::fs::remove_file(&path).unwrap();
+
+        let report = report.unwrap();
+        assert_eq!(report.columns[1].estimated_level, Some(12));
+        // Already above the default target of 9.
+        assert_eq!(report.columns[1].upgrade, None);
+        assert_eq!(report.summary(), "no level upgrade would save much");
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+use regex::Regex;
+
+use crate::file::schema::FileSchema;
+
+/// Columns to list, matched against their dotted paths. A pattern starting
+/// with `^`, ending with `$` or between slashes is a regular expression,
+/// e.g. `^metrics\..*_p99$`; anything else is a glob over the whole path,
+/// where `*` and `?` also match dots, e.g. `metrics.*.p99`. A glob without
+/// wildcards matches anywhere in the path.
+#[derive(Debug, Clone)]
+pub struct ColumnFilter {
+    source: String,
+    regex: Regex,
+}
+
+impl ColumnFilter {
+    pub fn matches(&self, path: &str) -> bool {
+        self.regex.is_match(path)
+    }
+
+    /// Schema nodes to list: the leaves whose paths match and the groups
+    /// around them, the root included.
+    pub fn matching(&self, schema: &FileSchema) -> BTreeSet<usize> {
+        let outline = schema.outline();
+        let mut nodes = BTreeSet::from([0]);
+        for node in 0..outline.len() {
+            if outline.leaf_position(node).is_none() || !self.matches(&schema.column_path(node)) {
+                continue;
+            }
+            let mut ancestor = Some(node);
+            while let Some(n) = ancestor
+                && nodes.insert(n)
+            {
+                ancestor = outline.parent(n);
+            }
+        }
+        nodes
+    }
+}
+
+/// The regex of a glob: `*` any run of characters, `?` one character and
+/// `[...]` (or `[!...]`) a set, anchored at both ends.
+fn glob_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            '[' => {
+                regex.push('[');
+                if chars.next_if_eq(&'!').is_some() {
+                    regex.push('^');
+                }
+                for c in chars.by_ref() {
+                    if c == ']' {
+                        break;
+                    }
+                    if c == '\\' || c == '[' {
+                        regex.push('\\');
+                    }
+                    regex.push(c);
+                }
+                regex.push(']');
+            }
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+impl FromStr for ColumnFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err("usage: columns <glob> | <regex>".to_string());
+        }
+        let pattern = match s.strip_prefix('/').and_then(|s| s.strip_suffix('/')) {
+            Some(regex) => regex.to_string(),
+            None if s.starts_with('^') || s.ends_with('$') => s.to_string(),
+            None if s.contains(['*', '?', '[']) => glob_regex(s),
+            None => regex::escape(s),
+        };
+        let regex = Regex::new(&pattern).map_err(|e| format!("invalid pattern '{s}': {e}"))?;
+        Ok(Self {
+            source: s.to_string(),
+            regex,
+        })
+    }
+}
+
+impl PartialEq for ColumnFilter {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl fmt::Display for ColumnFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::parquet_ctx::ParquetCtx;
+    use crate::file::test_data_path;
+
+    fn filter(pattern: &str) -> ColumnFilter {
+        pattern.parse().unwrap()
+    }
+
+    #[test]
+    fn test_regex() {
+        let p99 = filter(r"^metrics\..*_p99$");
+        assert!(p99.matches("metrics.latency_p99"));
+        assert!(!p99.matches("metrics.latency_p50"));
+        assert!(!p99.matches("old_metrics.latency_p99"));
+        assert!(filter("/lat/").matches("metrics.latency_p99"));
+        assert!("^(".parse::<ColumnFilter>().is_err());
+        assert!("".parse::<ColumnFilter>().is_err());
+    }
+
+    #[test]
+    fn test_glob() {
+        let glob = filter("metrics.*_p9?");
+        assert!(glob.matches("metrics.latency_p99"));
+        assert!(glob.matches("metrics.a.b_p95"));
+        assert!(!glob.matches("metrics.latency_p50"));
+        // A glob matches the whole path; dots are taken literally.
+        assert!(!glob.matches("xmetrics.latency_p99"));
+        assert!(!filter("a.b").matches("axb"));
+        assert!(filter("[!a]d").matches("id"));
+        assert!(!filter("[!a]d").matches("ad"));
+        // Without wildcards, anywhere in the path.
+        assert!(filter("latency").matches("metrics.latency_p99"));
+    }
+
+    #[test]
+    fn test_matching_keeps_ancestors() {
+        let path = test_data_path("nulls.snappy.parquet");
+        let ctx = ParquetCtx::from_file(&path).unwrap();
+        let schema = &ctx.schema;
+        let leaf = schema.leaf_paths().pop().unwrap();
+        let node = schema.find_path(&leaf).unwrap();
+        let nodes = filter(&leaf).matching(schema);
+        assert!(nodes.contains(&0));
+        assert!(nodes.contains(&node));
+        let outline = schema.outline();
+        let mut ancestor = outline.parent(node);
+        while let Some(n) = ancestor {
+            assert!(nodes.contains(&n));
+            ancestor = outline.parent(n);
+        }
+        assert_eq!(
+            filter("no_such_column").matching(schema),
+            BTreeSet::from([0])
+        );
+    }
+}
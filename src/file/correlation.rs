@@ -0,0 +1,256 @@
+use std::fs::File;
+
+use arrow::array::{Array, ArrayRef, AsArray, Float64Array, RecordBatch};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Float64Type};
+use parquet::arrow::ProjectionMask;
+use parquet::arrow::arrow_reader::{ArrowReaderMetadata, ParquetRecordBatchReaderBuilder};
+use serde::Serialize;
+
+use crate::file::progress::ScanProgress;
+
+/// Rows read for a correlation, spread evenly over the row groups.
+pub const CORRELATION_SAMPLE_ROWS: usize = 10_000;
+
+/// Rows decoded per batch.
+const READ_BATCH: usize = 8192;
+
+/// Two numeric columns sampled side by side.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Correlation {
+    pub x: String,
+    pub y: String,
+    /// `(x, y)` of the rows where both values are present.
+    pub points: Vec<(f64, f64)>,
+    /// Rows read, nulls included.
+    pub sampled_rows: u64,
+    /// Pearson's r; `None` with fewer than two points or a constant column.
+    pub pearson: Option<f64>,
+    /// Spearman's rank correlation, which also catches monotonic relations
+    /// that aren't linear.
+    pub spearman: Option<f64>,
+}
+
+impl Correlation {
+    /// Sample up to `sample_rows` rows of the leaf columns at dotted paths
+    /// `x` and `y`, stopping if `progress` is cancelled. Dates and
+    /// timestamps count in their unit since the epoch, booleans as 0 and 1.
+    pub fn from_file_with_progress(
+        path: &str,
+        x: &str,
+        y: &str,
+        sample_rows: usize,
+        progress: &ScanProgress,
+    ) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| e.to_string())?;
+        let metadata =
+            ArrowReaderMetadata::load(&file, Default::default()).map_err(|e| e.to_string())?;
+        let md = metadata.metadata().clone();
+        let schema = md.file_metadata().schema_descr();
+        let leaf = |name: &str| {
+            let index = (0..schema.num_columns())
+                .find(|&i| schema.column(i).path().string() == name)
+                .ok_or_else(|| format!("no column '{name}'"))?;
+            if schema.column(index).max_rep_level() > 0 {
+                return Err(format!("{name} is inside a list"));
+            }
+            Ok(index)
+        };
+        let leaves = [leaf(x)?, leaf(y)?];
+
+        let num_row_groups = md.num_row_groups();
+        let per_row_group = sample_rows.div_ceil(num_row_groups.max(1)).max(1);
+        let mut points = Vec::new();
+        let mut sampled_rows = 0;
+        for rg in 0..num_row_groups {
+            progress.check()?;
+            let reader = ParquetRecordBatchReaderBuilder::new_with_metadata(
+                file.try_clone().map_err(|e| e.to_string())?,
+                metadata.clone(),
+            )
+            .with_row_groups(vec![rg])
+            .with_projection(ProjectionMask::leaves(schema, leaves))
+            .with_limit(per_row_group)
+            .with_batch_size(READ_BATCH)
+            .build()
+            .map_err(|e| e.to_string())?;
+            for batch in reader {
+                let batch = batch.map_err(|e| e.to_string())?;
+                let xs = numbers(&batch, x)?;
+                let ys = numbers(&batch, y)?;
+                points.extend(xs.iter().zip(ys.iter()).filter_map(|pair| match pair {
+                    (Some(x), Some(y)) if x.is_finite() && y.is_finite() => Some((x, y)),
+                    _ => None,
+                }));
+                sampled_rows += batch.num_rows() as u64;
+            }
+            progress.advance(md.row_group(rg).compressed_size().max(0) as u64);
+        }
+
+        let (xs, ys): (Vec<f64>, Vec<f64>) = points.iter().copied().unzip();
+        Ok(Correlation {
+            x: x.to_string(),
+            y: y.to_string(),
+            sampled_rows,
+            pearson: pearson(&xs, &ys),
+            spearman: pearson(&ranks(&xs), &ranks(&ys)),
+            points,
+        })
+    }
+
+    /// Smallest and largest x and y, for the axes.
+    pub fn bounds(&self) -> Option<([f64; 2], [f64; 2])> {
+        let (first, rest) = self.points.split_first()?;
+        Some(rest.iter().fold(
+            ([first.0, first.0], [first.1, first.1]),
+            |([x0, x1], [y0, y1]), &(x, y)| ([x0.min(x), x1.max(x)], [y0.min(y), y1.max(y)]),
+        ))
+    }
+}
+
+/// The leaf at dotted `path` of `batch` as floats.
+fn numbers(batch: &RecordBatch, path: &str) -> Result<Float64Array, String> {
+    let mut parts = path.split('.');
+    let first = parts.next().unwrap_or_default();
+    let mut column: ArrayRef = batch
+        .column_by_name(first)
+        .cloned()
+        .ok_or_else(|| format!("no column '{path}'"))?;
+    for part in parts {
+        column = column
+            .as_struct_opt()
+            .and_then(|group| group.column_by_name(part))
+            .cloned()
+            .ok_or_else(|| format!("no column '{path}'"))?;
+    }
+    let data_type = column.data_type().clone();
+    let column = if data_type.is_temporal() {
+        cast(&column, &DataType::Int64).map_err(|e| e.to_string())?
+    } else if data_type.is_numeric() || data_type == DataType::Boolean {
+        column
+    } else {
+        return Err(format!("{path} is not numeric ({data_type})"));
+    };
+    let column = cast(&column, &DataType::Float64).map_err(|e| e.to_string())?;
+    Ok(column.as_primitive::<Float64Type>().clone())
+}
+
+/// Pearson's correlation coefficient of two equally long samples.
+fn pearson(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    let n = xs.len() as f64;
+    if xs.len() < 2 {
+        return None;
+    }
+    let mean_x = xs.iter().sum::<f64>() / n;
+    let mean_y = ys.iter().sum::<f64>() / n;
+    let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (x, y) in xs.iter().zip(ys) {
+        let (dx, dy) = (x - mean_x, y - mean_y);
+        cov += dx * dy;
+        var_x += dx * dx;
+        var_y += dy * dy;
+    }
+    if var_x == 0.0 || var_y == 0.0 {
+        return None;
+    }
+    Some((cov / (var_x * var_y).sqrt()).clamp(-1.0, 1.0))
+}
+
+/// 1-based rank of each value, ties getting the average of their ranks.
+fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        let rank = (start + end + 1) as f64 / 2.0;
+        for &i in &order[start..end] {
+            ranks[i] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::write_test_file;
+    use arrow::array::{Int32Array, StringArray, StructArray};
+    use arrow::datatypes::Field;
+    use parquet::file::properties::WriterProperties;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_pearson_and_spearman() {
+        let xs = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq!(pearson(&xs, &[3.0, 5.0, 7.0, 9.0, 11.0]), Some(1.0));
+        assert_eq!(pearson(&xs, &[5.0, 4.0, 3.0, 2.0, 1.0]), Some(-1.0));
+        assert_eq!(pearson(&xs, &[2.0; 5]), None);
+        assert_eq!(pearson(&[1.0], &[1.0]), None);
+        assert_eq!(ranks(&[10.0, 30.0, 20.0, 20.0]), [1.0, 4.0, 2.5, 2.5]);
+        // Exponential: monotonic but not linear.
+        let ys: Vec<f64> = xs.iter().map(|x: &f64| x.exp()).collect();
+        assert!(pearson(&xs, &ys).unwrap() < 0.95);
+        assert_eq!(pearson(&ranks(&xs), &ranks(&ys)), Some(1.0));
+    }
+
+    #[test]
+    fn test_sampled_columns() {
+        // x = 0..100 in row groups of 30, point.y = 2x + 1 except every
+        // tenth row, which is null.
+        let ys: Float64Array = (0..100)
+            .map(|x| (x % 10 != 0).then_some(2.0 * x as f64 + 1.0))
+            .collect();
+        let point = StructArray::from(vec![(
+            Arc::new(Field::new("y", DataType::Float64, true)),
+            Arc::new(ys) as ArrayRef,
+        )]);
+        let batch = RecordBatch::try_from_iter([
+            (
+                "x",
+                Arc::new(Int32Array::from_iter_values(0..100)) as ArrayRef,
+            ),
+            ("point", Arc::new(point) as ArrayRef),
+            (
+                "name",
+                Arc::new(StringArray::from_iter_values(
+                    (0..100).map(|x| x.to_string()),
+                )) as _,
+            ),
+        ])
+        .unwrap();
+        let props = WriterProperties::builder()
+            .set_max_row_group_row_count(Some(30))
+            .build();
+        let path = write_test_file("correlation", &batch, Some(props));
+        let file_path = path.display().to_string();
+        let progress = ScanProgress::default();
+        let read =
+            |x, y, rows| Correlation::from_file_with_progress(&file_path, x, y, rows, &progress);
+
+        let full = read("x", "point.y", 1000).unwrap();
+        let sampled = read("x", "point.y", 40).unwrap();
+        let not_numeric = read("x", "name", 1000);
+        let missing = read("x", "nope", 1000);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(full.sampled_rows, 100);
+        assert_eq!(full.points.len(), 90);
+        assert_eq!(full.points[0], (1.0, 3.0));
+        assert_eq!(full.pearson, Some(1.0));
+        assert_eq!(full.spearman, Some(1.0));
+        assert_eq!(full.bounds(), Some(([1.0, 99.0], [3.0, 199.0])));
+
+        // 10 rows from the start of each of the 4 row groups.
+        assert_eq!(sampled.sampled_rows, 40);
+        assert_eq!(sampled.points.last(), Some(&(99.0, 199.0)));
+
+        assert_eq!(not_numeric.unwrap_err(), "name is not numeric (Utf8)");
+        assert_eq!(missing.unwrap_err(), "no column 'nope'");
+    }
+}
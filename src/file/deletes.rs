@@ -0,0 +1,440 @@
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use arrow::array::AsArray;
+use arrow::datatypes::DataType;
+use parquet::arrow::ProjectionMask;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::basic::Type as PhysicalType;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Share of deleted rows above which a file is worth rewriting.
+pub const COMPACTION_FRACTION: f64 = 0.2;
+
+/// Files under an Iceberg table's `data` directory looked at for delete
+/// files; a larger table is only partly scanned.
+pub const MAX_SCANNED_FILES: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TableFormat {
+    Delta,
+    Iceberg,
+}
+
+impl TableFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TableFormat::Delta => "Delta",
+            TableFormat::Iceberg => "Iceberg",
+        }
+    }
+}
+
+/// Rows of a data file that a Delta or Iceberg table has deleted without
+/// rewriting the file.
+///
+/// Delta deletion vectors are read from the JSON commits in `_delta_log`;
+/// files only listed in a checkpoint are not found. Iceberg manifests are
+/// Avro, so positional delete files are found by scanning the table's `data`
+/// directory instead, which also counts delete files a later snapshot has
+/// dropped. That scan reads every delete file, so it only runs when asked
+/// for. Equality deletes are counted but not applied.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeleteInfo {
+    pub format: TableFormat,
+    /// Rows stored in the file.
+    pub physical_rows: u64,
+    /// Rows marked deleted by deletion vectors or positional deletes.
+    pub deleted_rows: u64,
+    /// Deletion vectors or positional delete files that touch the file.
+    pub delete_files: usize,
+    /// Equality delete files in the table, which may delete more rows.
+    pub equality_delete_files: usize,
+    /// Whether the table had more files than [`MAX_SCANNED_FILES`], so
+    /// deletes in the rest were missed.
+    pub partial: bool,
+}
+
+impl DeleteInfo {
+    /// Deletes of the data file at `file_path` holding `physical_rows`, if it
+    /// belongs to a Delta table. Iceberg tables are left to
+    /// [`DeleteInfo::for_iceberg_file`].
+    pub fn for_file(file_path: &str, physical_rows: u64) -> io::Result<Option<Self>> {
+        let file = fs::canonicalize(file_path)?;
+        let Some((TableFormat::Delta, root)) = find_table(&file) else {
+            return Ok(None);
+        };
+        Ok(
+            delta_deletes(&root, &file)?.map(|(deleted_rows, delete_files)| DeleteInfo {
+                format: TableFormat::Delta,
+                physical_rows,
+                deleted_rows: deleted_rows.min(physical_rows),
+                delete_files,
+                equality_delete_files: 0,
+                partial: false,
+            }),
+        )
+    }
+
+    /// Deletes of the data file at `file_path` holding `physical_rows`, if it
+    /// belongs to an Iceberg table, from the delete files among the first
+    /// [`MAX_SCANNED_FILES`] files of the table's `data` directory.
+    pub fn for_iceberg_file(file_path: &str, physical_rows: u64) -> io::Result<Option<Self>> {
+        let file = fs::canonicalize(file_path)?;
+        let Some((TableFormat::Iceberg, root)) = find_table(&file) else {
+            return Ok(None);
+        };
+        let scan = iceberg_deletes(&root, &file)?;
+        Ok(Some(DeleteInfo {
+            format: TableFormat::Iceberg,
+            physical_rows,
+            deleted_rows: scan.deleted_rows.min(physical_rows),
+            delete_files: scan.delete_files,
+            equality_delete_files: scan.equality_delete_files,
+            partial: scan.partial,
+        }))
+    }
+
+    pub fn live_rows(&self) -> u64 {
+        self.physical_rows - self.deleted_rows
+    }
+
+    pub fn deleted_fraction(&self) -> f64 {
+        if self.physical_rows == 0 {
+            return 0.0;
+        }
+        self.deleted_rows as f64 / self.physical_rows as f64
+    }
+
+    pub fn needs_compaction(&self) -> bool {
+        self.deleted_fraction() > COMPACTION_FRACTION
+    }
+}
+
+/// The closest directory above `file` that is the root of a table.
+fn find_table(file: &Path) -> Option<(TableFormat, PathBuf)> {
+    file.ancestors().skip(1).find_map(|dir| {
+        if dir.join("_delta_log").is_dir() {
+            return Some((TableFormat::Delta, dir.to_path_buf()));
+        }
+        let is_iceberg = fs::read_dir(dir.join("metadata")).is_ok_and(|entries| {
+            entries
+                .flatten()
+                .any(|e| e.file_name().to_string_lossy().ends_with(".metadata.json"))
+        });
+        is_iceberg.then(|| (TableFormat::Iceberg, dir.to_path_buf()))
+    })
+}
+
+/// `%20` and friends decoded, as Delta stores paths URL-encoded.
+fn percent_decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Whether a path stored in table metadata, relative to `root` or an
+/// absolute (`file:`) URI, names `file`.
+fn names_file(stored: &str, root: &Path, file: &Path) -> bool {
+    let stored = stored
+        .strip_prefix("file://")
+        .or_else(|| stored.strip_prefix("file:"))
+        .unwrap_or(stored);
+    let stored = Path::new(stored);
+    if stored.is_absolute() {
+        stored == file
+    } else {
+        root.join(stored) == file
+    }
+}
+
+/// Deleted rows and deletion vectors of `file` after replaying the JSON
+/// commits, or `None` when no commit adds it.
+fn delta_deletes(root: &Path, file: &Path) -> io::Result<Option<(u64, usize)>> {
+    let mut commits: Vec<PathBuf> = fs::read_dir(root.join("_delta_log"))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    commits.sort();
+
+    let mut state = None;
+    for commit in commits {
+        for line in BufReader::new(File::open(commit)?).lines() {
+            let Ok(action) = serde_json::from_str::<Value>(&line?) else {
+                continue;
+            };
+            let path_of = |kind: &str| {
+                action[kind]["path"]
+                    .as_str()
+                    .map(percent_decode)
+                    .filter(|path| names_file(path, root, file))
+            };
+            if path_of("add").is_some() {
+                let cardinality = action["add"]["deletionVector"]["cardinality"].as_u64();
+                state = Some((cardinality.unwrap_or(0), cardinality.map_or(0, |_| 1)));
+            } else if path_of("remove").is_some() {
+                state = None;
+            }
+        }
+    }
+    Ok(state)
+}
+
+/// What the scan of an Iceberg table's `data` directory found.
+#[derive(Debug, Default)]
+struct IcebergScan {
+    deleted_rows: u64,
+    delete_files: usize,
+    equality_delete_files: usize,
+    partial: bool,
+}
+
+/// The first `limit` parquet files under `dir`, in path order, and whether
+/// there were more.
+fn parquet_files(dir: &Path, limit: usize) -> io::Result<(Vec<PathBuf>, bool)> {
+    let mut files = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let mut entries: Vec<_> = fs::read_dir(&dir)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<_, _>>()?;
+        entries.sort();
+        let mut subdirs = Vec::new();
+        for path in entries {
+            if path.is_dir() {
+                subdirs.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "parquet") {
+                if files.len() == limit {
+                    return Ok((files, true));
+                }
+                files.push(path);
+            }
+        }
+        // Last first on the stack, so they come off it in order.
+        dirs.extend(subdirs.into_iter().rev());
+    }
+    Ok((files, false))
+}
+
+/// Rows of `file` deleted by positional delete files under the table's
+/// `data` directory, with the number of those files and of equality delete
+/// files. Delete files that can't be read are skipped.
+fn iceberg_deletes(root: &Path, file: &Path) -> io::Result<IcebergScan> {
+    let data_dir = root.join("data");
+    let (candidates, partial) = if data_dir.is_dir() {
+        parquet_files(&data_dir, MAX_SCANNED_FILES)?
+    } else {
+        (vec![], false)
+    };
+
+    let mut scan = IcebergScan {
+        partial,
+        ..IcebergScan::default()
+    };
+    for candidate in candidates {
+        if candidate == file {
+            continue;
+        }
+        let Ok(reader) = File::open(&candidate).and_then(|f| {
+            SerializedFileReader::new(f).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        }) else {
+            continue;
+        };
+        let md = reader.metadata().file_metadata();
+        let delete_type = md.key_value_metadata().and_then(|kv| {
+            kv.iter()
+                .find(|pair| pair.key == "delete-type")
+                .and_then(|pair| pair.value.clone())
+        });
+        if delete_type.as_deref() == Some("equality") {
+            scan.equality_delete_files += 1;
+            continue;
+        }
+        // Positional delete files have exactly these two columns first.
+        let columns = md.schema_descr().columns();
+        let is_positional = columns.len() >= 2
+            && columns[0].name() == "file_path"
+            && columns[0].physical_type() == PhysicalType::BYTE_ARRAY
+            && columns[1].name() == "pos";
+        if !is_positional {
+            continue;
+        }
+        let Ok(rows) = positional_deletes_of(&candidate, root, file) else {
+            continue;
+        };
+        if rows > 0 {
+            scan.deleted_rows += rows;
+            scan.delete_files += 1;
+        }
+    }
+    Ok(scan)
+}
+
+/// Rows of the positional delete file at `path` that point into `file`.
+fn positional_deletes_of(path: &Path, root: &Path, file: &Path) -> Result<u64, String> {
+    let builder =
+        ParquetRecordBatchReaderBuilder::try_new(File::open(path).map_err(|e| e.to_string())?)
+            .map_err(|e| e.to_string())?;
+    let mask = ProjectionMask::leaves(builder.parquet_schema(), [0]);
+    let reader = builder
+        .with_projection(mask)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut rows = 0;
+    for batch in reader {
+        let batch = batch.map_err(|e| e.to_string())?;
+        let paths =
+            arrow::compute::cast(batch.column(0), &DataType::Utf8).map_err(|e| e.to_string())?;
+        rows += paths
+            .as_string::<i32>()
+            .iter()
+            .flatten()
+            .filter(|stored| names_file(stored, root, file))
+            .count() as u64;
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_data_path;
+    use arrow::array::{Int64Array, RecordBatch, StringArray};
+    use parquet::arrow::ArrowWriter;
+    use std::sync::Arc;
+
+    /// A table directory holding a copy of alltypes_plain (8 rows) at
+    /// `relative`, returned with the copy's path.
+    fn table_with_file(name: &str, relative: &str) -> (PathBuf, PathBuf) {
+        let root = std::env::temp_dir().join(format!("parqeye-{name}-{}", std::process::id()));
+        let file = root.join(relative);
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::copy(test_data_path("alltypes_plain.parquet"), &file).unwrap();
+        (root, file)
+    }
+
+    #[test]
+    fn test_plain_files_have_no_deletes() {
+        let info = DeleteInfo::for_file(&test_data_path("alltypes_plain.parquet"), 8).unwrap();
+        assert_eq!(info, None);
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("a%20b/c=%3D.parquet"), "a b/c==.parquet");
+        assert_eq!(percent_decode("100%"), "100%");
+    }
+
+    #[test]
+    fn test_delta_deletion_vectors() {
+        let (root, file) = table_with_file("delta", "year=2024/part 0.parquet");
+        fs::create_dir_all(root.join("_delta_log")).unwrap();
+        let add = |cardinality: Option<u64>| {
+            let dv = cardinality.map_or(String::new(), |c| {
+                format!(r#","deletionVector":{{"storageType":"u","cardinality":{c}}}"#)
+            });
+            format!(r#"{{"add":{{"path":"year%3D2024/part%200.parquet","size":1{dv}}}}}"#)
+        };
+        fs::write(
+            root.join("_delta_log/00000000000000000000.json"),
+            format!("{{\"commitInfo\":{{}}}}\n{}\n", add(None)),
+        )
+        .unwrap();
+        fs::write(
+            root.join("_delta_log/00000000000000000001.json"),
+            format!(
+                "{{\"remove\":{{\"path\":\"year%3D2024/part%200.parquet\"}}}}\n{}\n",
+                add(Some(3))
+            ),
+        )
+        .unwrap();
+
+        let info = DeleteInfo::for_file(&file.display().to_string(), 8)
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.format, TableFormat::Delta);
+        assert_eq!(info.deleted_rows, 3);
+        assert_eq!(info.live_rows(), 5);
+        assert_eq!(info.delete_files, 1);
+        assert!(info.needs_compaction());
+
+        // Removed files are no longer part of the table.
+        fs::write(
+            root.join("_delta_log/00000000000000000002.json"),
+            "{\"remove\":{\"path\":\"year%3D2024/part%200.parquet\"}}\n",
+        )
+        .unwrap();
+        assert_eq!(
+            DeleteInfo::for_file(&file.display().to_string(), 8).unwrap(),
+            None
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_iceberg_positional_deletes() {
+        let (root, file) = table_with_file("iceberg", "data/00000-0-data.parquet");
+        fs::create_dir_all(root.join("metadata")).unwrap();
+        fs::write(root.join("metadata/v1.metadata.json"), "{}").unwrap();
+
+        let target = format!("file:{}", fs::canonicalize(&file).unwrap().display());
+        let batch = RecordBatch::try_from_iter([
+            (
+                "file_path",
+                Arc::new(StringArray::from(vec![
+                    target.as_str(),
+                    "file:/elsewhere/other.parquet",
+                    target.as_str(),
+                ])) as _,
+            ),
+            ("pos", Arc::new(Int64Array::from(vec![0, 0, 5])) as _),
+        ])
+        .unwrap();
+        let deletes = File::create(root.join("data/00000-0-deletes.parquet")).unwrap();
+        let mut writer = ArrowWriter::try_new(deletes, batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        // A delete file that can't be read is left out of the count.
+        fs::write(root.join("data/00001-0-deletes.parquet"), "PAR1").unwrap();
+
+        let path = file.display().to_string();
+        assert_eq!(DeleteInfo::for_file(&path, 8).unwrap(), None);
+        let info = DeleteInfo::for_iceberg_file(&path, 8).unwrap().unwrap();
+        assert_eq!(info.format, TableFormat::Iceberg);
+        assert_eq!(info.deleted_rows, 2);
+        assert_eq!(info.delete_files, 1);
+        assert_eq!(info.deleted_fraction(), 0.25);
+        assert!(!info.partial);
+
+        let (files, more) = parquet_files(&root.join("data"), 2).unwrap();
+        assert_eq!(
+            files,
+            [file.clone(), root.join("data/00000-0-deletes.parquet")]
+        );
+        assert!(more);
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
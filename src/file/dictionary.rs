@@ -0,0 +1,385 @@
+use parquet::basic::{Encoding, Type as PhysicalType};
+use parquet::column::page::Page;
+use parquet::file::reader::FileReader;
+use parquet::schema::types::ColumnDescriptor;
+use serde::Serialize;
+
+use crate::file::progress::ScanProgress;
+use crate::file::schema::decode_logical_value;
+
+/// A dictionary encoded data page decoded by hand: the indices it stores,
+/// joined with the entries of the chunk's dictionary page.
+#[derive(Debug, Clone, Serialize)]
+pub struct DecodedPage {
+    pub row_group: usize,
+    /// Leaf column index.
+    pub column: usize,
+    /// Index of the page within its column chunk, dictionary page included,
+    /// as in the Pages table.
+    pub page: usize,
+    pub encoding: String,
+    /// Bits per index, from the first byte of the values.
+    pub bit_width: u8,
+    /// Entries in the dictionary page.
+    pub dictionary_size: usize,
+    /// One per value slot of the page, nulls included.
+    pub values: Vec<PageValue>,
+}
+
+/// A value slot of a dictionary encoded page.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PageValue {
+    /// Index into the dictionary; `None` for a null.
+    pub index: Option<u32>,
+    pub value: Option<String>,
+}
+
+impl DecodedPage {
+    /// Read page `page` of the chunk of column `column` in row group
+    /// `row_group` and decode it with the chunk's dictionary page. Only the
+    /// decompressed pages come from the parquet page reader; the levels,
+    /// indices and dictionary entries are decoded here.
+    pub fn read<R: FileReader>(
+        reader: &R,
+        row_group: usize,
+        column: usize,
+        page: usize,
+    ) -> Result<Self, String> {
+        Self::read_with_progress(reader, row_group, column, page, &ScanProgress::default())
+    }
+
+    /// Like [`DecodedPage::read`], stopping between pages if `progress` is
+    /// cancelled.
+    pub fn read_with_progress<R: FileReader>(
+        reader: &R,
+        row_group: usize,
+        column: usize,
+        page: usize,
+        progress: &ScanProgress,
+    ) -> Result<Self, String> {
+        let rg = reader.get_row_group(row_group).map_err(|e| e.to_string())?;
+        if column >= rg.num_columns() {
+            return Err(format!("no column {column} in row group {row_group}"));
+        }
+        let descr = rg.metadata().column(column).column_descr_ptr();
+        let mut pages = rg
+            .get_column_page_reader(column)
+            .map_err(|e| e.to_string())?;
+        let mut dictionary = None;
+        for idx in 0..=page {
+            progress.check()?;
+            let Some(current) = pages.get_next_page().map_err(|e| e.to_string())? else {
+                return Err(format!("the chunk has only {idx} pages"));
+            };
+            if idx < page {
+                if let Page::DictionaryPage { .. } = current {
+                    dictionary = Some(current);
+                }
+                continue;
+            }
+            let (indices, bit_width) = page_indices(&current, &descr, page)?;
+            let dictionary = dictionary
+                .as_ref()
+                .ok_or_else(|| format!("page {} comes before any dictionary page", page + 1))?;
+            let dictionary = dictionary_entries(dictionary, &descr)?;
+            let values = indices
+                .into_iter()
+                .map(|index| match index {
+                    None => Ok(PageValue {
+                        index: None,
+                        value: None,
+                    }),
+                    Some(i) => dictionary
+                        .get(i as usize)
+                        .map(|value| PageValue {
+                            index: Some(i),
+                            value: Some(value.clone()),
+                        })
+                        .ok_or_else(|| {
+                            format!(
+                                "index {i} is past the {} dictionary entries",
+                                dictionary.len()
+                            )
+                        }),
+                })
+                .collect::<Result<_, String>>()?;
+            return Ok(Self {
+                row_group,
+                column,
+                page,
+                encoding: format!("{:?}", current.encoding()),
+                bit_width,
+                dictionary_size: dictionary.len(),
+                values,
+            });
+        }
+        unreachable!("the loop returns at page {page}")
+    }
+}
+
+/// The PLAIN encoded entries of a dictionary page, formatted like min/max.
+fn dictionary_entries(page: &Page, descr: &ColumnDescriptor) -> Result<Vec<String>, String> {
+    let Page::DictionaryPage {
+        buf, num_values, ..
+    } = page
+    else {
+        return Err("not a dictionary page".to_string());
+    };
+    let physical = descr.physical_type();
+    let width = match physical {
+        PhysicalType::INT32 | PhysicalType::FLOAT => Some(4),
+        PhysicalType::INT64 | PhysicalType::DOUBLE => Some(8),
+        PhysicalType::INT96 => Some(12),
+        PhysicalType::FIXED_LEN_BYTE_ARRAY => Some(descr.type_length().max(0) as usize),
+        PhysicalType::BYTE_ARRAY => None,
+        PhysicalType::BOOLEAN => return Err("BOOLEAN columns have no dictionary".to_string()),
+    };
+    let truncated = || "the dictionary page is truncated".to_string();
+    let mut data = &buf[..];
+    (0..*num_values)
+        .map(|_| {
+            let len = match width {
+                Some(width) => width,
+                None => {
+                    let len = data.get(..4).ok_or_else(truncated)?;
+                    data = &data[4..];
+                    u32::from_le_bytes(len.try_into().unwrap()) as usize
+                }
+            };
+            let value = data.get(..len).ok_or_else(truncated)?;
+            data = &data[len..];
+            Ok(decode_logical_value(
+                value,
+                physical,
+                descr.logical_type_ref(),
+            ))
+        })
+        .collect()
+}
+
+/// The dictionary index of each value slot of a data page, `None` for nulls,
+/// and the bit width of the indices.
+fn page_indices(
+    page: &Page,
+    descr: &ColumnDescriptor,
+    idx: usize,
+) -> Result<(Vec<Option<u32>>, u8), String> {
+    if let Page::DictionaryPage { .. } = page {
+        return Err(format!("page {} is the dictionary page", idx + 1));
+    }
+    if !matches!(
+        page.encoding(),
+        Encoding::RLE_DICTIONARY | Encoding::PLAIN_DICTIONARY
+    ) {
+        return Err(format!(
+            "page {} is {:?} encoded, not dictionary encoded",
+            idx + 1,
+            page.encoding()
+        ));
+    }
+    let slots = page.num_values() as usize;
+    let max_def = descr.max_def_level();
+    let def_width = level_bit_width(max_def);
+    let rep_width = level_bit_width(descr.max_rep_level());
+    let (defs, values) = match page {
+        Page::DataPage {
+            buf,
+            def_level_encoding,
+            rep_level_encoding,
+            ..
+        } => {
+            // V1 pages prefix each RLE level run with its length.
+            let mut data = &buf[..];
+            let mut levels = |encoding: Encoding, width: u8| -> Result<Option<Vec<u32>>, String> {
+                if width == 0 {
+                    return Ok(None);
+                }
+                if encoding != Encoding::RLE {
+                    return Err(format!("{encoding:?} levels are not supported"));
+                }
+                let len = data.get(..4).ok_or("the levels are truncated")?;
+                let len = u32::from_le_bytes(len.try_into().unwrap()) as usize;
+                let run = data.get(4..4 + len).ok_or("the levels are truncated")?;
+                data = &data[4 + len..];
+                rle_bit_packed_hybrid(run, width, slots).map(Some)
+            };
+            levels(*rep_level_encoding, rep_width)?;
+            let defs = levels(*def_level_encoding, def_width)?;
+            (defs, data)
+        }
+        Page::DataPageV2 {
+            buf,
+            def_levels_byte_len,
+            rep_levels_byte_len,
+            ..
+        } => {
+            let (rep_len, def_len) = (*rep_levels_byte_len as usize, *def_levels_byte_len as usize);
+            let levels = buf
+                .get(rep_len..rep_len + def_len)
+                .ok_or("the levels are truncated")?;
+            let defs = match def_width {
+                0 => None,
+                width => Some(rle_bit_packed_hybrid(levels, width, slots)?),
+            };
+            (defs, &buf[rep_len + def_len..])
+        }
+        Page::DictionaryPage { .. } => unreachable!("dictionary pages are PLAIN encoded"),
+    };
+    let present = defs.as_ref().map_or(slots, |defs| {
+        defs.iter().filter(|&&def| def == max_def as u32).count()
+    });
+    let (&bit_width, indices) = values.split_first().ok_or("the page has no values")?;
+    let mut indices = rle_bit_packed_hybrid(indices, bit_width, present)?.into_iter();
+    let slots = match defs {
+        Some(defs) => defs
+            .into_iter()
+            .map(|def| (def == max_def as u32).then(|| indices.next()).flatten())
+            .collect(),
+        None => indices.map(Some).collect(),
+    };
+    Ok((slots, bit_width))
+}
+
+/// Bits needed for levels up to `max_level`.
+fn level_bit_width(max_level: i16) -> u8 {
+    (16 - (max_level as u16).leading_zeros()) as u8
+}
+
+/// Decode `count` values of the RLE / bit-packing hybrid encoding that
+/// levels and dictionary indices use: runs of one repeated value, and groups
+/// of 8 values packed into `bit_width` bits each, least significant first.
+fn rle_bit_packed_hybrid(mut data: &[u8], bit_width: u8, count: usize) -> Result<Vec<u32>, String> {
+    if bit_width > 32 {
+        return Err(format!("bit width {bit_width} is over 32"));
+    }
+    let width = bit_width as usize;
+    // `count` comes from the page header, so it only bounds the capacity
+    // together with what the data could hold.
+    let mut values = Vec::with_capacity(count.min(data.len().saturating_mul(8)));
+    while values.len() < count {
+        let header = read_uleb128(&mut data)?;
+        let left = count - values.len();
+        if header & 1 == 1 {
+            let groups = usize::try_from(header >> 1).map_err(|e| e.to_string())?;
+            let run = groups
+                .checked_mul(width)
+                .and_then(|len| data.get(..len))
+                .ok_or("a bit-packed run is truncated")?;
+            let len = run.len();
+            values.extend((0..groups.saturating_mul(8).min(left)).map(|i| {
+                let bit = i * width;
+                let word = (0..5)
+                    .filter_map(|b| run.get(bit / 8 + b))
+                    .enumerate()
+                    .fold(0u64, |word, (b, &byte)| word | (byte as u64) << (8 * b));
+                ((word >> (bit % 8)) & ((1u64 << width) - 1)) as u32
+            }));
+            data = &data[len..];
+        } else {
+            let bytes = width.div_ceil(8);
+            let value = data.get(..bytes).ok_or("an RLE run is truncated")?;
+            let value = value
+                .iter()
+                .rev()
+                .fold(0u32, |value, &byte| value << 8 | byte as u32);
+            let run = usize::try_from(header >> 1).unwrap_or(usize::MAX);
+            values.extend(std::iter::repeat_n(value, run.min(left)));
+            data = &data[bytes..];
+        }
+    }
+    Ok(values)
+}
+
+fn read_uleb128(data: &mut &[u8]) -> Result<u64, String> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = data.split_first().ok_or("a run header is truncated")?;
+        *data = rest;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err("a run header is too long".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::parquet_ctx::open_reader;
+    use crate::file::write_test_file;
+    use arrow::array::{Int32Array, RecordBatch, StringArray};
+    use parquet::file::properties::{WriterProperties, WriterVersion};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_rle_bit_packed_hybrid() {
+        // An RLE run of five 3s, then one bit-packed group of 0..8 in 3 bits.
+        let data = [0x0a, 0x03, 0x03, 0x88, 0xc6, 0xfa];
+        assert_eq!(
+            rle_bit_packed_hybrid(&data, 3, 13).unwrap(),
+            [3, 3, 3, 3, 3, 0, 1, 2, 3, 4, 5, 6, 7]
+        );
+        // Padding of the last group is dropped.
+        assert_eq!(rle_bit_packed_hybrid(&data, 3, 7).unwrap().len(), 7);
+        assert!(rle_bit_packed_hybrid(&data[..4], 3, 13).is_err());
+        // Corrupt run lengths stop at `count` rather than allocating them.
+        let huge_rle = [0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f, 0x01];
+        assert_eq!(rle_bit_packed_hybrid(&huge_rle, 1, 4).unwrap(), [1; 4]);
+        let huge_packed = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01];
+        assert!(rle_bit_packed_hybrid(&huge_packed, 32, 4).is_err());
+        assert_eq!(level_bit_width(0), 0);
+        assert_eq!(level_bit_width(1), 1);
+        assert_eq!(level_bit_width(3), 2);
+    }
+
+    #[test]
+    fn test_decode_dictionary_pages() {
+        for version in [WriterVersion::PARQUET_1_0, WriterVersion::PARQUET_2_0] {
+            let kinds = ["click", "view", "buy"];
+            let kind = StringArray::from_iter((0..100).map(|i| (i % 5 != 4).then(|| kinds[i % 3])));
+            let code = Int32Array::from_iter_values((0..100).map(|i| 200 + i % 2));
+            let batch = RecordBatch::try_from_iter([
+                ("kind", Arc::new(kind) as _),
+                ("code", Arc::new(code) as _),
+            ])
+            .unwrap();
+            let props = WriterProperties::builder()
+                .set_writer_version(version)
+                .set_column_dictionary_enabled("code".into(), false)
+                .build();
+            let path = write_test_file(&format!("dictionary-{version:?}"), &batch, Some(props));
+            let (reader, _) = open_reader(&path.display().to_string()).unwrap();
+            std::fs::remove_file(&path).unwrap();
+
+            let page = DecodedPage::read(&reader, 0, 0, 1).unwrap();
+            assert_eq!(page.dictionary_size, 3);
+            assert_eq!(page.bit_width, 2);
+            assert_eq!(page.values.len(), 100);
+            let value = |slot: usize| page.values[slot].value.as_deref();
+            assert_eq!(
+                (0..6).map(value).collect::<Vec<_>>(),
+                [
+                    Some("click"),
+                    Some("view"),
+                    Some("buy"),
+                    Some("click"),
+                    None,
+                    Some("buy")
+                ]
+            );
+            assert_eq!(page.values[4].index, None);
+            assert_eq!(page.values[2].index, Some(2));
+
+            let err = DecodedPage::read(&reader, 0, 0, 0).unwrap_err();
+            assert_eq!(err, "page 1 is the dictionary page");
+            let err = DecodedPage::read(&reader, 0, 1, 0).unwrap_err();
+            // V2 writers pick DELTA_BINARY_PACKED over PLAIN.
+            assert!(err.ends_with("encoded, not dictionary encoded"), "{err}");
+            let err = DecodedPage::read(&reader, 0, 0, 5).unwrap_err();
+            assert_eq!(err, "the chunk has only 2 pages");
+            let err = DecodedPage::read(&reader, 0, 2, 0).unwrap_err();
+            assert_eq!(err, "no column 2 in row group 0");
+        }
+    }
+}
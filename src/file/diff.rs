@@ -0,0 +1,139 @@
+use std::fmt;
+
+use parquet::file::reader::FileReader;
+use serde::Serialize;
+
+use crate::file::error::FileIOError;
+use crate::file::parquet_ctx::open_reader;
+use crate::file::schema_check::{Deviation, compare_shapes, schema_shape};
+use crate::file::summary::FileSummary;
+use crate::file::utils::{commas, human_readable_bytes};
+
+/// Two files side by side: their headline numbers and how the second
+/// file's schema differs from the first's. Reads the footers only.
+#[derive(Debug, Serialize)]
+pub struct FileDiff {
+    pub left: FileSummary,
+    pub right: FileSummary,
+    /// Columns of `right` that are missing, extra or changed, taking `left`
+    /// as the reference.
+    pub schema: Vec<Deviation>,
+}
+
+impl FileDiff {
+    pub fn from_files(left: &str, right: &str) -> Result<Self, FileIOError> {
+        let shape = |path: &str| {
+            open_reader(path)
+                .map(|(reader, _)| schema_shape(reader.metadata().file_metadata().schema_descr()))
+        };
+        Ok(Self {
+            schema: compare_shapes(&shape(left)?, &shape(right)?),
+            left: FileSummary::from_file(left)?,
+            right: FileSummary::from_file(right)?,
+        })
+    }
+
+    pub fn same_schema(&self) -> bool {
+        self.schema.is_empty()
+    }
+
+    /// Label and both values of each compared property.
+    fn rows(&self) -> Vec<(&'static str, String, String)> {
+        let (l, r) = (&self.left, &self.right);
+        vec![
+            ("Rows", commas(l.rows), commas(r.rows)),
+            (
+                "Size",
+                human_readable_bytes(l.size),
+                human_readable_bytes(r.size),
+            ),
+            (
+                "Row groups",
+                commas(l.row_groups as u64),
+                commas(r.row_groups as u64),
+            ),
+            ("Codecs", l.codecs.join(","), r.codecs.join(",")),
+            ("Created by", l.created_by.clone(), r.created_by.clone()),
+            (
+                "Schema",
+                l.schema_hash[..8].to_string(),
+                r.schema_hash[..8].to_string(),
+            ),
+        ]
+    }
+}
+
+impl fmt::Display for FileDiff {
+    /// An aligned table with the rows that differ marked with `*`, then the
+    /// schema differences.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self.rows();
+        let label = rows
+            .iter()
+            .map(|(label, ..)| label.len())
+            .max()
+            .unwrap_or(0);
+        let left = rows
+            .iter()
+            .map(|(_, left, _)| left.chars().count())
+            .chain([self.left.path.chars().count()])
+            .max()
+            .unwrap_or(0);
+        writeln!(
+            f,
+            "  {:label$}  {:left$}  {}",
+            "", self.left.path, self.right.path
+        )?;
+        for (name, l, r) in &rows {
+            let mark = if l == r { ' ' } else { '*' };
+            writeln!(f, "{mark} {name:label$}  {l:left$}  {r}")?;
+        }
+        if self.same_schema() {
+            write!(f, "\nsame schema")
+        } else {
+            writeln!(
+                f,
+                "\nschema of {} against {}:",
+                self.right.path, self.left.path
+            )?;
+            for deviation in &self.schema {
+                writeln!(f, "  {deviation}")?;
+            }
+            write!(f, "{} columns differ", self.schema.len())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_data_path;
+
+    #[test]
+    fn test_same_file() {
+        let path = test_data_path("alltypes_plain.parquet");
+        let diff = FileDiff::from_files(&path, &path).unwrap();
+        assert!(diff.same_schema());
+        let text = diff.to_string();
+        assert!(!text.lines().any(|line| line.starts_with('*')), "{text}");
+        assert!(text.ends_with("\nsame schema"));
+    }
+
+    #[test]
+    fn test_different_files() {
+        let left = test_data_path("alltypes_plain.parquet");
+        let right = test_data_path("nulls.snappy.parquet");
+        let diff = FileDiff::from_files(&left, &right).unwrap();
+        assert!(!diff.same_schema());
+        assert!(diff.schema.contains(&Deviation::MissingColumn {
+            column: "id".to_string()
+        }));
+        let text = diff.to_string();
+        assert!(
+            text.lines().any(|line| line.starts_with("* Schema")),
+            "{text}"
+        );
+        assert!(text.ends_with(&format!("{} columns differ", diff.schema.len())));
+        assert!(FileDiff::from_files(&left, "no-such-file.parquet").is_err());
+    }
+}
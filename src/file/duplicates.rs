@@ -0,0 +1,398 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use arrow::util::display::{ArrayFormatter, FormatOptions};
+use parquet::arrow::ProjectionMask;
+use parquet::arrow::arrow_reader::{ArrowReaderMetadata, ParquetRecordBatchReaderBuilder};
+use serde::Serialize;
+
+use crate::file::error::FileIOError;
+use crate::file::progress::ScanProgress;
+use crate::file::utils::commas;
+
+/// Rows decoded per batch.
+const READ_BATCH: usize = 8192;
+
+/// How many rows to hash and which columns identify a row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateOptions {
+    /// Rows read, spread evenly over the row groups.
+    pub sample_rows: usize,
+    /// Top-level columns whose values make up a row's key; empty for every
+    /// column, i.e. whole-row duplicates.
+    pub key: Vec<String>,
+    /// Most repeated keys to list.
+    pub top_k: usize,
+}
+
+impl Default for DuplicateOptions {
+    fn default() -> Self {
+        Self {
+            sample_rows: 1_000_000,
+            key: vec![],
+            top_k: 10,
+        }
+    }
+}
+
+/// A key seen more than once in the sample.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DuplicateKey {
+    /// `column=value` pairs, in key order.
+    pub key: String,
+    pub count: u64,
+    /// Row groups the key was seen in, numbered from 0.
+    pub row_groups: Vec<usize>,
+}
+
+/// How often the keys of a sample of rows repeat.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DuplicateReport {
+    pub path: String,
+    /// Columns hashed.
+    pub key: Vec<String>,
+    pub total_rows: u64,
+    pub sampled_rows: u64,
+    pub distinct_keys: u64,
+    /// Rows whose key was already seen in the sample.
+    pub duplicate_rows: u64,
+    /// Keys seen more than once.
+    pub duplicated_keys: u64,
+    /// Keys seen more than once in more than one row group.
+    pub cross_row_group_keys: u64,
+    /// Most repeated keys, most frequent first.
+    pub top_keys: Vec<DuplicateKey>,
+}
+
+/// What the sample says about one key hash.
+struct Seen {
+    count: u64,
+    row_groups: Vec<usize>,
+    /// Formatted once the key turns out to repeat.
+    key: Option<String>,
+}
+
+impl DuplicateReport {
+    pub fn from_file(path: &str, options: &DuplicateOptions) -> Result<Self, FileIOError> {
+        Self::from_file_with_progress(path, options, &ScanProgress::default())
+            .map_err(|details| FileIOError::SampleDataError { details })
+    }
+
+    /// Like [`DuplicateReport::from_file`], reporting the compressed bytes
+    /// of the row groups read to `progress` and stopping if it is
+    /// cancelled.
+    pub fn from_file_with_progress(
+        path: &str,
+        options: &DuplicateOptions,
+        progress: &ScanProgress,
+    ) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| e.to_string())?;
+        let metadata =
+            ArrowReaderMetadata::load(&file, Default::default()).map_err(|e| e.to_string())?;
+        let fields = metadata.schema().fields().clone();
+        let key: Vec<String> = if options.key.is_empty() {
+            fields.iter().map(|field| field.name().clone()).collect()
+        } else {
+            options.key.clone()
+        };
+        let mut roots = Vec::new();
+        for name in &key {
+            let root = fields
+                .iter()
+                .position(|field| field.name() == name)
+                .ok_or_else(|| format!("no column '{name}'"))?;
+            if roots.contains(&root) {
+                return Err(format!("column '{name}' is in the key twice"));
+            }
+            roots.push(root);
+        }
+
+        let md = metadata.metadata().clone();
+        let num_row_groups = md.num_row_groups();
+        let per_row_group = options.sample_rows.div_ceil(num_row_groups.max(1)).max(1);
+        let format = FormatOptions::default().with_null("NULL");
+        let mut seen: HashMap<u64, Seen> = HashMap::new();
+        let mut sampled_rows = 0;
+
+        for rg in 0..num_row_groups {
+            progress.check()?;
+            let mask = ProjectionMask::roots(md.file_metadata().schema_descr(), roots.clone());
+            let reader = ParquetRecordBatchReaderBuilder::new_with_metadata(
+                file.try_clone().map_err(|e| e.to_string())?,
+                metadata.clone(),
+            )
+            .with_row_groups(vec![rg])
+            .with_projection(mask)
+            .with_limit(per_row_group)
+            .with_batch_size(READ_BATCH)
+            .build()
+            .map_err(|e| e.to_string())?;
+            for batch in reader {
+                let batch = batch.map_err(|e| e.to_string())?;
+                // The projection keeps the file's column order.
+                let formatters = key
+                    .iter()
+                    .map(|name| {
+                        let column = batch.column_by_name(name).expect("projected column");
+                        ArrayFormatter::try_new(column.as_ref(), &format)
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| e.to_string())?;
+                for row in 0..batch.num_rows() {
+                    let values: Vec<String> = formatters
+                        .iter()
+                        .map(|formatter| formatter.value(row).to_string())
+                        .collect();
+                    let mut hasher = DefaultHasher::new();
+                    values.hash(&mut hasher);
+                    let entry = seen.entry(hasher.finish()).or_insert_with(|| Seen {
+                        count: 0,
+                        row_groups: vec![rg],
+                        key: None,
+                    });
+                    entry.count += 1;
+                    if entry.row_groups.last() != Some(&rg) {
+                        entry.row_groups.push(rg);
+                    }
+                    if entry.count == 2 {
+                        let pairs: Vec<String> = key
+                            .iter()
+                            .zip(&values)
+                            .map(|(name, value)| format!("{name}={value}"))
+                            .collect();
+                        entry.key = Some(pairs.join(", "));
+                    }
+                }
+                sampled_rows += batch.num_rows() as u64;
+            }
+            progress.advance(md.row_group(rg).compressed_size().max(0) as u64);
+        }
+
+        let mut repeated: Vec<DuplicateKey> = seen
+            .values()
+            .filter(|seen| seen.count > 1)
+            .map(|seen| DuplicateKey {
+                key: seen.key.clone().unwrap_or_default(),
+                count: seen.count,
+                row_groups: seen.row_groups.clone(),
+            })
+            .collect();
+        repeated.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+        let distinct_keys = seen.len() as u64;
+        Ok(DuplicateReport {
+            path: path.to_string(),
+            key,
+            total_rows: md.file_metadata().num_rows().max(0) as u64,
+            sampled_rows,
+            distinct_keys,
+            duplicate_rows: sampled_rows - distinct_keys,
+            duplicated_keys: repeated.len() as u64,
+            cross_row_group_keys: repeated
+                .iter()
+                .filter(|key| key.row_groups.len() > 1)
+                .count() as u64,
+            top_keys: repeated.into_iter().take(options.top_k).collect(),
+        })
+    }
+
+    /// Share of the rows read that repeat a key, from 0 to 100.
+    pub fn duplicate_percent(&self) -> f64 {
+        match self.sampled_rows {
+            0 => 0.0,
+            rows => self.duplicate_rows as f64 * 100.0 / rows as f64,
+        }
+    }
+
+    /// One line for the status bar.
+    pub fn summary(&self) -> String {
+        if self.duplicate_rows == 0 {
+            return format!(
+                "no duplicates in {} rows sampled",
+                commas(self.sampled_rows)
+            );
+        }
+        let mut summary = format!(
+            "{:.2}% duplicate rows in {} sampled: {} keys repeat, {} across row groups",
+            self.duplicate_percent(),
+            commas(self.sampled_rows),
+            commas(self.duplicated_keys),
+            commas(self.cross_row_group_keys)
+        );
+        if let Some(top) = self.top_keys.first() {
+            summary.push_str(&format!("; top {} ×{}", top.key, top.count));
+        }
+        summary
+    }
+}
+
+/// The rates, then the most repeated keys as an aligned table.
+impl fmt::Display for DuplicateReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}: sampled {} of {} rows, key {}",
+            self.path,
+            commas(self.sampled_rows),
+            commas(self.total_rows),
+            self.key.join(", ")
+        )?;
+        if self.duplicate_rows == 0 {
+            return writeln!(f, "no duplicate keys");
+        }
+        writeln!(
+            f,
+            "{:.2}% duplicate rows: {} rows repeat {} keys, {} of them in more than one row group",
+            self.duplicate_percent(),
+            commas(self.duplicate_rows),
+            commas(self.duplicated_keys),
+            commas(self.cross_row_group_keys)
+        )?;
+        let rows: Vec<[String; 3]> = self
+            .top_keys
+            .iter()
+            .map(|key| {
+                let row_groups: Vec<String> =
+                    key.row_groups.iter().map(|rg| rg.to_string()).collect();
+                [commas(key.count), row_groups.join(", "), key.key.clone()]
+            })
+            .collect();
+        let headers = ["Count", "Row groups", "Key"];
+        let widths: Vec<usize> = (0..2)
+            .map(|idx| {
+                rows.iter()
+                    .map(|row| row[idx].chars().count())
+                    .chain([headers[idx].len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        writeln!(f)?;
+        let headers = headers.map(str::to_string);
+        for row in std::iter::once(&headers).chain(&rows) {
+            writeln!(
+                f,
+                "{:<count$}  {:<groups$}  {}",
+                row[0],
+                row[1],
+                row[2],
+                count = widths[0],
+                groups = widths[1]
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::{test_data_path, write_test_file};
+    use arrow::array::{Int64Array, RecordBatch, StringArray};
+    use parquet::file::properties::WriterProperties;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_no_duplicates() {
+        let path = test_data_path("alltypes_plain.parquet");
+        let report = DuplicateReport::from_file(&path, &DuplicateOptions::default()).unwrap();
+        assert_eq!(report.sampled_rows, 8);
+        assert_eq!(report.key.len(), 11);
+        assert_eq!(report.duplicate_rows, 0);
+        assert_eq!(report.summary(), "no duplicates in 8 rows sampled");
+
+        // bool_col alternates, so half the rows repeat its two values.
+        let options = DuplicateOptions {
+            key: vec!["bool_col".to_string()],
+            ..Default::default()
+        };
+        let report = DuplicateReport::from_file(&path, &options).unwrap();
+        assert_eq!(report.distinct_keys, 2);
+        assert_eq!(report.duplicate_rows, 6);
+        assert_eq!(report.duplicate_percent(), 75.0);
+
+        let options = DuplicateOptions {
+            key: vec!["nope".to_string()],
+            ..Default::default()
+        };
+        assert!(DuplicateReport::from_file(&path, &options).is_err());
+    }
+
+    #[test]
+    fn test_duplicates_across_row_groups() {
+        // Two row groups of 4 rows; id 1 is in both, id 3 twice in the
+        // second. The names differ so only the id key repeats.
+        let batch = RecordBatch::try_from_iter([
+            (
+                "id",
+                Arc::new(Int64Array::from(vec![0, 1, 2, 4, 1, 3, 3, 5])) as _,
+            ),
+            (
+                "name",
+                Arc::new(StringArray::from(vec![
+                    "a", "b", "c", "d", "e", "f", "f", "h",
+                ])) as _,
+            ),
+        ])
+        .unwrap();
+        let props = WriterProperties::builder()
+            .set_max_row_group_row_count(Some(4))
+            .build();
+        let path = write_test_file("duplicates", &batch, Some(props));
+        let file_path = path.display().to_string();
+
+        let by_id = DuplicateOptions {
+            key: vec!["id".to_string()],
+            ..Default::default()
+        };
+        let report = DuplicateReport::from_file(&file_path, &by_id).unwrap();
+        let whole_rows = DuplicateReport::from_file(&file_path, &DuplicateOptions::default());
+        let sampled = DuplicateReport::from_file(
+            &file_path,
+            &DuplicateOptions {
+                sample_rows: 4,
+                ..by_id
+            },
+        )
+        .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(report.duplicate_rows, 2);
+        assert_eq!(report.duplicated_keys, 2);
+        assert_eq!(report.cross_row_group_keys, 1);
+        assert_eq!(
+            report.top_keys,
+            [
+                DuplicateKey {
+                    key: "id=1".to_string(),
+                    count: 2,
+                    row_groups: vec![0, 1],
+                },
+                DuplicateKey {
+                    key: "id=3".to_string(),
+                    count: 2,
+                    row_groups: vec![1],
+                },
+            ]
+        );
+        assert_eq!(
+            report.summary(),
+            "25.00% duplicate rows in 8 sampled: 2 keys repeat, 1 across row groups; top id=1 ×2"
+        );
+        let text = report.to_string();
+        assert!(
+            text.contains("Count  Row groups  Key\n2      0, 1        id=1\n"),
+            "{text}"
+        );
+
+        // Only the (3, f) rows repeat whole.
+        let whole_rows = whole_rows.unwrap();
+        assert_eq!(whole_rows.duplicate_rows, 1);
+        assert_eq!(whole_rows.top_keys[0].key, "id=3, name=f");
+
+        // Two rows from each row group: 0, 1, 1, 3.
+        assert_eq!(sampled.sampled_rows, 4);
+        assert_eq!(sampled.duplicate_rows, 1);
+    }
+}
@@ -0,0 +1,208 @@
+use std::fmt;
+
+use parquet::basic::{Encoding, Type as PhysicalType};
+use serde::Serialize;
+
+/// Fewer values than this say too little about runs and deltas.
+const MIN_VALUES: u64 = 64;
+
+/// Bits per value DELTA_BINARY_PACKED has to save over the current encoding
+/// to be worth suggesting; its miniblocks and page headers eat into less.
+const MIN_BITS_SAVED: f64 = 4.0;
+
+/// Share of distinct values above which a dictionary stops paying off for
+/// floats and BYTE_STREAM_SPLIT does better.
+const SPLIT_DISTINCT_SHARE: f64 = 0.5;
+
+/// Runs of equal values and differences between consecutive values of a
+/// numeric column, in the order they were read.
+#[derive(Debug, Clone, Default)]
+pub struct RunDeltaStats {
+    previous: Option<f64>,
+    values: u64,
+    runs: u64,
+    delta_sum: f64,
+    min: f64,
+    max: f64,
+}
+
+impl RunDeltaStats {
+    /// Add the next value. NaNs are skipped.
+    pub fn add(&mut self, x: f64) {
+        if x.is_nan() {
+            return;
+        }
+        match self.previous {
+            Some(previous) => {
+                if x != previous {
+                    self.runs += 1;
+                }
+                self.delta_sum += (x - previous).abs();
+                self.min = self.min.min(x);
+                self.max = self.max.max(x);
+            }
+            None => {
+                self.runs = 1;
+                self.min = x;
+                self.max = x;
+            }
+        }
+        self.previous = Some(x);
+        self.values += 1;
+    }
+
+    /// What the runs and deltas say about a column of `physical` values
+    /// with about `distinct` distinct values, written with `current`.
+    /// `None` when too few values were read.
+    pub fn finish(
+        &self,
+        physical: PhysicalType,
+        distinct: u64,
+        current: &[Encoding],
+    ) -> Option<EncodingHint> {
+        if self.values < MIN_VALUES {
+            return None;
+        }
+        let avg_run_length = self.values as f64 / self.runs as f64;
+        let avg_delta = self.delta_sum / (self.values - 1) as f64;
+        let uses = |encoding| current.contains(&encoding);
+        let suggestion = match physical {
+            PhysicalType::INT32 | PhysicalType::INT64 if !uses(Encoding::DELTA_BINARY_PACKED) => {
+                // Deltas are stored from the smallest one of a block, so
+                // their spread is about twice the average magnitude.
+                let delta_bits = (2.0 * avg_delta + 1.0).log2().ceil();
+                let value_bits = (self.max - self.min + 1.0).log2().ceil();
+                let current_bits =
+                    if uses(Encoding::RLE_DICTIONARY) || uses(Encoding::PLAIN_DICTIONARY) {
+                        value_bits.min((distinct.max(1) as f64).log2().ceil())
+                    } else {
+                        value_bits
+                    };
+                (current_bits - delta_bits >= MIN_BITS_SAVED).then(|| Suggestion {
+                    encoding: Encoding::DELTA_BINARY_PACKED,
+                    reason: format!("deltas need about {delta_bits} bits, values {current_bits}"),
+                })
+            }
+            PhysicalType::FLOAT | PhysicalType::DOUBLE if !uses(Encoding::BYTE_STREAM_SPLIT) => {
+                let share = distinct as f64 / self.values as f64;
+                (share > SPLIT_DISTINCT_SHARE && avg_run_length < 2.0).then(|| Suggestion {
+                    encoding: Encoding::BYTE_STREAM_SPLIT,
+                    reason: format!(
+                        "{:.0}% of values distinct, too many for a dictionary",
+                        share.min(1.0) * 100.0
+                    ),
+                })
+            }
+            _ => None,
+        };
+        Some(EncodingHint {
+            avg_run_length,
+            avg_delta,
+            suggestion,
+        })
+    }
+}
+
+/// How well the values of a numeric column suit run-length and delta
+/// encodings, from a sample.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EncodingHint {
+    /// Values per run of equal consecutive values.
+    pub avg_run_length: f64,
+    /// Mean absolute difference between consecutive values.
+    pub avg_delta: f64,
+    /// An encoding likely to beat the one the column was written with.
+    pub suggestion: Option<Suggestion>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Suggestion {
+    #[serde(serialize_with = "serialize_encoding")]
+    pub encoding: Encoding,
+    pub reason: String,
+}
+
+fn serialize_encoding<S: serde::Serializer>(
+    encoding: &Encoding,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&format_args!("{encoding:?}"))
+}
+
+/// e.g. `DELTA_BINARY_PACKED: deltas need about 3 bits, values 20`.
+impl fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self.encoding, self.reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(values: impl IntoIterator<Item = f64>) -> RunDeltaStats {
+        let mut stats = RunDeltaStats::default();
+        for x in values {
+            stats.add(x);
+        }
+        stats
+    }
+
+    #[test]
+    fn test_timestamps_suggest_delta() {
+        // Microsecond timestamps a few microseconds apart.
+        let ticks = stats((0..1000).map(|i| 1.7e15 + (i * 3) as f64));
+        let hint = ticks
+            .finish(PhysicalType::INT64, 1000, &[Encoding::PLAIN])
+            .unwrap();
+        assert_eq!(hint.avg_run_length, 1.0);
+        assert_eq!(hint.avg_delta, 3.0);
+        let suggestion = hint.suggestion.unwrap();
+        assert_eq!(suggestion.encoding, Encoding::DELTA_BINARY_PACKED);
+        assert_eq!(
+            suggestion.to_string(),
+            "DELTA_BINARY_PACKED: deltas need about 3 bits, values 12"
+        );
+
+        // Already delta encoded.
+        let hint = ticks
+            .finish(PhysicalType::INT64, 1000, &[Encoding::DELTA_BINARY_PACKED])
+            .unwrap();
+        assert_eq!(hint.suggestion, None);
+    }
+
+    #[test]
+    fn test_runs_and_small_dictionaries_keep_their_encoding() {
+        // A status code in long runs: the dictionary needs 2 bits.
+        let codes = stats((0..1000).map(|i| (i / 100 % 4) as f64));
+        let hint = codes
+            .finish(PhysicalType::INT32, 4, &[Encoding::RLE_DICTIONARY])
+            .unwrap();
+        assert_eq!(hint.avg_run_length, 100.0);
+        assert_eq!(hint.suggestion, None);
+    }
+
+    #[test]
+    fn test_distinct_floats_suggest_byte_stream_split() {
+        let readings = stats((0..500).map(|i| (i as f64 * 0.37).sin() * 100.0));
+        let hint = readings
+            .finish(PhysicalType::DOUBLE, 500, &[Encoding::RLE_DICTIONARY])
+            .unwrap();
+        assert_eq!(
+            hint.suggestion.map(|s| s.encoding),
+            Some(Encoding::BYTE_STREAM_SPLIT)
+        );
+        assert!(
+            readings
+                .finish(PhysicalType::DOUBLE, 3, &[Encoding::RLE_DICTIONARY])
+                .unwrap()
+                .suggestion
+                .is_none()
+        );
+        assert!(
+            stats([1.0, 2.0])
+                .finish(PhysicalType::DOUBLE, 2, &[])
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use std::fs::File;
+use std::io::Write;
+
+use arrow::record_batch::{RecordBatch, RecordBatchReader};
+use parquet::arrow::ProjectionMask;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+use crate::file::rewrite::ColumnProjection;
+
+/// Rows decoded per batch while exporting.
+const READ_BATCH: usize = 8192;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// With a header line. Nested columns can't be written as CSV.
+    Csv,
+    /// One array of objects.
+    Json,
+    /// One object per line.
+    Jsonl,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExportOptions {
+    pub columns: ColumnProjection,
+    /// Rows to write from the start of the file; all of them by default.
+    pub limit: Option<usize>,
+}
+
+/// Decode the rows of `path` and write them to `out` as `format`. Returns
+/// the rows written.
+pub fn export(
+    path: &str,
+    out: impl Write,
+    format: ExportFormat,
+    options: &ExportOptions,
+) -> Result<usize, String> {
+    let file = File::open(path).map_err(|e| format!("{path}: {e}"))?;
+    let builder = ParquetRecordBatchReaderBuilder::try_new(file).map_err(|e| e.to_string())?;
+    let parquet_schema = builder.metadata().file_metadata().schema_descr_ptr();
+    let mask = ProjectionMask::leaves(&parquet_schema, options.columns.leaves(&parquet_schema)?);
+    let mut builder = builder.with_projection(mask).with_batch_size(READ_BATCH);
+    if let Some(limit) = options.limit {
+        builder = builder.with_limit(limit);
+    }
+    let reader = builder.build().map_err(|e| e.to_string())?;
+    let schema = reader.schema();
+    let order: Vec<usize> = match options.columns.root_order(&parquet_schema) {
+        Some(roots) => roots
+            .iter()
+            .filter_map(|root| schema.index_of(root).ok())
+            .collect(),
+        None => (0..schema.fields().len()).collect(),
+    };
+    let batches = reader.map(|batch| {
+        batch
+            .and_then(|batch| batch.project(&order))
+            .map_err(|e| e.to_string())
+    });
+
+    match format {
+        ExportFormat::Csv => {
+            write_batches(batches, arrow::csv::Writer::new(out), |w, b| w.write(b))
+        }
+        ExportFormat::Json => {
+            let mut writer = arrow::json::ArrayWriter::new(out);
+            let rows = write_batches(batches, &mut writer, |w, b| w.write(b))?;
+            writer.finish().map_err(|e| e.to_string())?;
+            Ok(rows)
+        }
+        ExportFormat::Jsonl => {
+            let mut writer = arrow::json::LineDelimitedWriter::new(out);
+            let rows = write_batches(batches, &mut writer, |w, b| w.write(b))?;
+            writer.finish().map_err(|e| e.to_string())?;
+            Ok(rows)
+        }
+    }
+}
+
+fn write_batches<W>(
+    batches: impl Iterator<Item = Result<RecordBatch, String>>,
+    mut writer: W,
+    write: impl Fn(&mut W, &RecordBatch) -> Result<(), arrow::error::ArrowError>,
+) -> Result<usize, String> {
+    let mut rows = 0;
+    for batch in batches {
+        let batch = batch?;
+        write(&mut writer, &batch).map_err(|e| e.to_string())?;
+        rows += batch.num_rows();
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_data_path;
+
+    fn export_text(format: ExportFormat, options: &ExportOptions) -> (usize, String) {
+        let path = test_data_path("alltypes_plain.parquet");
+        let mut out = Vec::new();
+        let rows = export(&path, &mut out, format, options).unwrap();
+        (rows, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_export() {
+        let options = ExportOptions {
+            columns: ColumnProjection::Select(vec!["bool_col".to_string(), "id".to_string()]),
+            limit: Some(2),
+        };
+        let (rows, csv) = export_text(ExportFormat::Csv, &options);
+        assert_eq!(rows, 2);
+        assert_eq!(csv.lines().count(), 3);
+        assert_eq!(csv.lines().next(), Some("bool_col,id"));
+
+        let (_, jsonl) = export_text(ExportFormat::Jsonl, &options);
+        assert_eq!(jsonl.lines().count(), 2);
+        assert!(jsonl.starts_with("{\"bool_col\":"), "{jsonl}");
+
+        let (rows, json) = export_text(ExportFormat::Json, &ExportOptions::default());
+        let values: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(values.as_array().map(Vec::len), Some(rows));
+        assert_eq!(values[0].as_object().map(|o| o.len()), Some(11));
+    }
+
+    #[test]
+    fn test_export_unknown_column() {
+        let options = ExportOptions {
+            columns: ColumnProjection::Select(vec!["nope".to_string()]),
+            limit: None,
+        };
+        let path = test_data_path("alltypes_plain.parquet");
+        assert_eq!(
+            export(&path, Vec::new(), ExportFormat::Csv, &options),
+            Err("no column 'nope'".to_string())
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use std::fmt;
+use std::fs::File;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use parquet::bloom_filter::Sbbf;
+use parquet::column::writer::ColumnCloseResult;
+use parquet::file::metadata::{PageIndexPolicy, ParquetMetaDataReader};
+use parquet::file::page_index::column_index::ColumnIndexMetaData;
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use serde::Serialize;
+
+use crate::file::utils::{commas, human_readable_bytes, same_file};
+
+/// Row groups to extract: one, or an inclusive range such as `3-5`. They
+/// are typed and shown numbered from 1, as in the app, and held numbered
+/// from 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct RowGroupRange {
+    pub first: usize,
+    pub last: usize,
+}
+
+impl FromStr for RowGroupRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |n: &str| n.trim().parse::<usize>().ok();
+        let range = match s.split_once('-') {
+            Some((first, last)) => parse(first).zip(parse(last)),
+            None => parse(s).map(|n| (n, n)),
+        };
+        match range {
+            Some((0, _)) => Err("row groups are numbered from 1".to_string()),
+            Some((first, last)) if first <= last => Ok(RowGroupRange {
+                first: first - 1,
+                last: last - 1,
+            }),
+            _ => Err(format!(
+                "'{s}' is not a row group like 17 or a range like 3-5"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for RowGroupRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.first == self.last {
+            write!(f, "row group {}", self.first + 1)
+        } else {
+            write!(f, "row groups {}-{}", self.first + 1, self.last + 1)
+        }
+    }
+}
+
+/// What [`extract_row_groups`] wrote.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Extraction {
+    pub path: String,
+    pub output: String,
+    pub row_groups: RowGroupRange,
+    pub rows: u64,
+    /// Size of the written file.
+    pub bytes: u64,
+}
+
+impl fmt::Display for Extraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "copied {} of {} ({} rows) to {}, {}",
+            self.row_groups,
+            self.path,
+            commas(self.rows),
+            self.output,
+            human_readable_bytes(self.bytes)
+        )
+    }
+}
+
+/// Copy the column chunks of some row groups into a file of their own,
+/// without decoding them, for reproducing a problem with a small file.
+/// The page index, bloom filters, `created_by` and key-value metadata come
+/// along, so readers see the pages just as they were.
+pub fn extract_row_groups(
+    path: &str,
+    output: &str,
+    row_groups: RowGroupRange,
+) -> Result<Extraction, String> {
+    if same_file(path, output) {
+        return Err("refusing to overwrite the input file".to_string());
+    }
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let md = ParquetMetaDataReader::new()
+        .with_page_index_policy(PageIndexPolicy::Optional)
+        .parse_and_finish(&file)
+        .map_err(|e| e.to_string())?;
+    if row_groups.last >= md.num_row_groups() {
+        return Err(format!(
+            "no {row_groups}: the file has {} row group{}",
+            md.num_row_groups(),
+            if md.num_row_groups() == 1 { "" } else { "s" }
+        ));
+    }
+
+    let file_md = md.file_metadata();
+    let props = WriterProperties::builder()
+        .set_created_by(file_md.created_by().unwrap_or_default().to_string())
+        .set_key_value_metadata(file_md.key_value_metadata().cloned())
+        .set_sorting_columns(md.row_group(row_groups.first).sorting_columns().cloned())
+        .build();
+    let out = File::create(output).map_err(|e| e.to_string())?;
+    let mut writer = SerializedFileWriter::new(
+        out,
+        file_md.schema_descr().root_schema_ptr(),
+        Arc::new(props),
+    )
+    .map_err(|e| e.to_string())?;
+
+    let mut rows = 0;
+    for rg in row_groups.first..=row_groups.last {
+        let row_group = md.row_group(rg);
+        let mut row_group_writer = writer.next_row_group().map_err(|e| e.to_string())?;
+        for (col, column) in row_group.columns().iter().enumerate() {
+            let column_index = md
+                .column_index()
+                .map(|index| index[rg][col].clone())
+                .filter(|index| !matches!(index, ColumnIndexMetaData::NONE));
+            let close = ColumnCloseResult {
+                bytes_written: column.compressed_size() as u64,
+                rows_written: row_group.num_rows() as u64,
+                metadata: column.clone(),
+                bloom_filter: Sbbf::read_from_column_chunk(column, &file)
+                    .map_err(|e| e.to_string())?,
+                column_index,
+                offset_index: md.offset_index().map(|index| index[rg][col].clone()),
+            };
+            row_group_writer
+                .append_column(&file, close)
+                .map_err(|e| e.to_string())?;
+        }
+        row_group_writer.close().map_err(|e| e.to_string())?;
+        rows += row_group.num_rows().max(0) as u64;
+    }
+    writer.close().map_err(|e| e.to_string())?;
+
+    let bytes = std::fs::metadata(output).map_err(|e| e.to_string())?.len();
+    Ok(Extraction {
+        path: path.to_string(),
+        output: output.to_string(),
+        row_groups,
+        rows,
+        bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::offsets::OffsetReport;
+    use crate::file::parquet_ctx::open_reader;
+    use crate::file::write_test_file;
+    use arrow::array::{ArrayRef, Int64Array, RecordBatch, StringArray};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use parquet::file::metadata::KeyValue;
+    use parquet::file::reader::FileReader;
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(
+            "17".parse(),
+            Ok(RowGroupRange {
+                first: 16,
+                last: 16
+            })
+        );
+        assert_eq!("3-5".parse(), Ok(RowGroupRange { first: 2, last: 4 }));
+        assert!("5-3".parse::<RowGroupRange>().is_err());
+        assert!("x".parse::<RowGroupRange>().is_err());
+        assert_eq!(
+            "0".parse::<RowGroupRange>(),
+            Err("row groups are numbered from 1".to_string())
+        );
+        assert_eq!(
+            RowGroupRange { first: 2, last: 4 }.to_string(),
+            "row groups 3-5"
+        );
+    }
+
+    #[test]
+    fn test_extract_row_groups() {
+        let batch = RecordBatch::try_from_iter([
+            (
+                "id",
+                Arc::new(Int64Array::from_iter_values(0..500)) as ArrayRef,
+            ),
+            (
+                "name",
+                Arc::new(StringArray::from_iter_values(
+                    (0..500).map(|i| format!("n{i}")),
+                )) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+        let props = WriterProperties::builder()
+            .set_max_row_group_row_count(Some(100))
+            .set_bloom_filter_enabled(true)
+            .set_key_value_metadata(Some(vec![KeyValue::new(
+                "origin".to_string(),
+                "test".to_string(),
+            )]))
+            .build();
+        let path = write_test_file("extract", &batch, Some(props))
+            .display()
+            .to_string();
+
+        let output = path.replace(".parquet", "-rg.parquet");
+        let range = RowGroupRange { first: 1, last: 2 };
+        let extracted = extract_row_groups(&path, &output, range);
+        let out_of_range = extract_row_groups(&path, &output, "6".parse().unwrap());
+        let overwrite = extract_row_groups(&path, &path, range);
+        let source = open_reader(&path).map(|(reader, _)| reader);
+        let copy = open_reader(&output).map(|(reader, _)| reader);
+        let offsets = OffsetReport::from_file(&output);
+        let ids: Vec<i64> = File::open(&output)
+            .map(|file| {
+                ParquetRecordBatchReaderBuilder::try_new(file)
+                    .unwrap()
+                    .build()
+                    .unwrap()
+                    .flat_map(|batch| {
+                        let batch = batch.unwrap();
+                        let ids = batch.column(0).as_any().downcast_ref::<Int64Array>();
+                        ids.unwrap().values().to_vec()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&output).unwrap();
+
+        let extracted = extracted.unwrap();
+        assert_eq!(extracted.rows, 200);
+        assert_eq!(
+            out_of_range,
+            Err("no row group 6: the file has 5 row groups".to_string())
+        );
+        assert_eq!(
+            overwrite,
+            Err("refusing to overwrite the input file".to_string())
+        );
+        assert_eq!(ids, (100..300).collect::<Vec<_>>());
+        // The page index points at the pages' new offsets.
+        assert_eq!(offsets.unwrap().problems, []);
+
+        let (source, copy) = (source.unwrap(), copy.unwrap());
+        let (source, copy) = (source.metadata(), copy.metadata());
+        assert_eq!(copy.num_row_groups(), 2);
+        assert_eq!(
+            copy.file_metadata().created_by(),
+            source.file_metadata().created_by()
+        );
+        assert!(
+            copy.file_metadata()
+                .key_value_metadata()
+                .unwrap()
+                .iter()
+                .any(|kv| kv.key == "origin")
+        );
+        // The chunks are copied as they are, with their bloom filters.
+        let (from, to) = (source.row_group(1).column(1), copy.row_group(0).column(1));
+        assert_eq!(from.compressed_size(), to.compressed_size());
+        assert_eq!(from.statistics(), to.statistics());
+        assert!(to.bloom_filter_offset().is_some());
+    }
+}
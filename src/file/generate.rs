@@ -0,0 +1,474 @@
+use std::fmt;
+use std::fs::File;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use arrow::array::{
+    ArrayRef, BooleanBuilder, Date32Builder, Float32Builder, Float64Builder, Int32Builder,
+    Int64Builder, RecordBatch, StringBuilder, TimestampMicrosecondBuilder,
+};
+use arrow::datatypes::{DataType as ArrowDataType, Field, Schema, TimeUnit};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{BrotliLevel, Compression, GzipLevel, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use parquet::schema::types::ColumnPath;
+
+use crate::file::rewrite::RowGroupTarget;
+use crate::file::sample_data::SplitMix64;
+use crate::file::utils::{commas, human_readable_bytes};
+
+/// Rows built per batch while generating.
+const WRITE_BATCH: u64 = 8192;
+
+/// Days from the Unix epoch to 2000-01-01, where generated dates and
+/// timestamps start.
+const EPOCH_2000_DAYS: i64 = 10_957;
+
+/// Types a generated column can have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneratedType {
+    Boolean,
+    Int32,
+    Int64,
+    Float,
+    Double,
+    String,
+    Date,
+    /// Microseconds, adjusted to UTC.
+    Timestamp,
+}
+
+impl FromStr for GeneratedType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "bool" | "boolean" => GeneratedType::Boolean,
+            "int32" | "int" => GeneratedType::Int32,
+            "int64" | "long" => GeneratedType::Int64,
+            "float" => GeneratedType::Float,
+            "double" => GeneratedType::Double,
+            "string" | "utf8" => GeneratedType::String,
+            "date" => GeneratedType::Date,
+            "timestamp" => GeneratedType::Timestamp,
+            _ => {
+                return Err(format!(
+                    "unknown type '{s}', expected bool, int32, int64, float, double, string, \
+                     date or timestamp"
+                ));
+            }
+        })
+    }
+}
+
+impl GeneratedType {
+    fn arrow_type(self) -> ArrowDataType {
+        match self {
+            GeneratedType::Boolean => ArrowDataType::Boolean,
+            GeneratedType::Int32 => ArrowDataType::Int32,
+            GeneratedType::Int64 => ArrowDataType::Int64,
+            GeneratedType::Float => ArrowDataType::Float32,
+            GeneratedType::Double => ArrowDataType::Float64,
+            GeneratedType::String => ArrowDataType::Utf8,
+            GeneratedType::Date => ArrowDataType::Date32,
+            GeneratedType::Timestamp => {
+                ArrowDataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+            }
+        }
+    }
+}
+
+/// How the values of a generated column are spread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Distribution {
+    /// 0, 1, 2, ... in row order, so the column is sorted.
+    #[default]
+    Sequential,
+    /// Uniform over the distinct values.
+    Random,
+    /// Small values far more often than large ones, each about twice as
+    /// likely as one twice its size, like the keys of a hot table.
+    Skewed,
+}
+
+/// One column to generate, written `name:type[:option...]`. Options are a
+/// distribution (`sequential`, `random` or `skewed`), `nulls=10%`,
+/// `distinct=N` for how many values `random` and `skewed` pick from, and
+/// `codec=zstd(3)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnSpec {
+    pub name: String,
+    pub data_type: GeneratedType,
+    pub distribution: Distribution,
+    /// Share of rows left null, from 0 to 1.
+    pub nulls: f64,
+    /// Values `random` and `skewed` pick from; defaults to the row count.
+    pub distinct: Option<u64>,
+    /// Overrides the file's codec for this column.
+    pub codec: Option<Compression>,
+}
+
+impl FromStr for ColumnSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':');
+        let name = parts.next().unwrap_or_default().trim();
+        let data_type = parts
+            .next()
+            .ok_or_else(|| format!("'{s}' is not a column like name:type[:option...]"))?;
+        if name.is_empty() {
+            return Err(format!("'{s}' has no column name"));
+        }
+        let mut spec = ColumnSpec {
+            name: name.to_string(),
+            data_type: data_type.trim().parse()?,
+            distribution: Distribution::default(),
+            nulls: 0.0,
+            distinct: None,
+            codec: None,
+        };
+        for option in parts {
+            match option.trim().split_once('=') {
+                None if option == "sequential" => spec.distribution = Distribution::Sequential,
+                None if option == "random" => spec.distribution = Distribution::Random,
+                None if option == "skewed" => spec.distribution = Distribution::Skewed,
+                Some(("nulls", value)) => {
+                    let nulls = match value.strip_suffix('%') {
+                        Some(percent) => percent.trim().parse::<f64>().map(|p| p / 100.0),
+                        None => value.trim().parse::<f64>(),
+                    };
+                    spec.nulls = nulls
+                        .ok()
+                        .filter(|nulls| (0.0..=1.0).contains(nulls))
+                        .ok_or_else(|| format!("'{value}' is not a share of nulls like 10%"))?;
+                }
+                Some(("distinct", value)) => {
+                    spec.distinct = Some(
+                        value
+                            .trim()
+                            .parse()
+                            .ok()
+                            .filter(|&n| n > 0)
+                            .ok_or_else(|| format!("'{value}' is not a positive count"))?,
+                    );
+                }
+                Some(("codec", value)) => spec.codec = Some(parse_codec(value)?),
+                _ => {
+                    return Err(format!(
+                        "unknown option '{option}' for column {name}, expected sequential, \
+                         random, skewed, nulls=N%, distinct=N or codec=NAME"
+                    ));
+                }
+            }
+        }
+        Ok(spec)
+    }
+}
+
+/// A codec name such as `snappy` or `zstd(3)`. GZIP, BROTLI and ZSTD take
+/// their default level when none is given.
+pub fn parse_codec(s: &str) -> Result<Compression, String> {
+    match s.trim().to_ascii_lowercase().as_str() {
+        "gzip" => Ok(Compression::GZIP(GzipLevel::default())),
+        "brotli" => Ok(Compression::BROTLI(BrotliLevel::default())),
+        "zstd" => Ok(Compression::ZSTD(ZstdLevel::default())),
+        codec => codec
+            .parse()
+            .map_err(|e: parquet::errors::ParquetError| format!("'{s}' is not a codec: {e}")),
+    }
+}
+
+/// Size, layout and compression of a generated file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerateOptions {
+    pub rows: u64,
+    /// Bytes are the writer's estimate before compression. Without it the
+    /// writer's default of about a million rows applies.
+    pub row_group_size: Option<RowGroupTarget>,
+    /// Codec of the columns that don't name their own.
+    pub codec: Compression,
+    /// The same seed generates the same file.
+    pub seed: u64,
+}
+
+impl Default for GenerateOptions {
+    fn default() -> Self {
+        Self {
+            rows: 10_000,
+            row_group_size: None,
+            codec: Compression::SNAPPY,
+            seed: 0,
+        }
+    }
+}
+
+/// What [`generate`] wrote.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generated {
+    pub path: String,
+    pub rows: u64,
+    pub row_groups: usize,
+    /// Size of the written file.
+    pub bytes: u64,
+}
+
+impl fmt::Display for Generated {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "wrote {} rows in {} row group{} to {}, {}",
+            commas(self.rows),
+            self.row_groups,
+            if self.row_groups == 1 { "" } else { "s" },
+            self.path,
+            human_readable_bytes(self.bytes)
+        )
+    }
+}
+
+/// Values of one column, drawn row by row.
+struct ColumnGenerator<'a> {
+    spec: &'a ColumnSpec,
+    distinct: u64,
+    rng: SplitMix64,
+}
+
+impl<'a> ColumnGenerator<'a> {
+    fn new(spec: &'a ColumnSpec, rows: u64, seed: u64) -> Self {
+        // Each column its own stream, so adding a column leaves the
+        // others as they were.
+        let salt = spec
+            .name
+            .bytes()
+            .fold(seed, |h, b| h.rotate_left(5) ^ u64::from(b));
+        Self {
+            spec,
+            distinct: spec.distinct.unwrap_or(rows).max(1),
+            rng: SplitMix64::new(salt),
+        }
+    }
+
+    /// The value for `row` as a number, or `None` for a null.
+    fn next_key(&mut self, row: u64) -> Option<u64> {
+        if self.spec.nulls > 0.0 && self.rng.next_f64() < self.spec.nulls {
+            return None;
+        }
+        Some(match self.spec.distribution {
+            Distribution::Sequential => row,
+            Distribution::Random => self.rng.next_u64() % self.distinct,
+            // Log-uniform: about as many draws fall in 1..2 as in 512..1024.
+            Distribution::Skewed => {
+                let key = (self.distinct as f64 + 1.0).powf(self.rng.next_f64()) - 1.0;
+                (key as u64).min(self.distinct - 1)
+            }
+        })
+    }
+
+    fn column(&mut self, rows: std::ops::Range<u64>) -> ArrayRef {
+        let len = (rows.end - rows.start) as usize;
+        let keys: Vec<Option<u64>> = rows.map(|row| self.next_key(row)).collect();
+        macro_rules! build {
+            ($builder:expr, $value:expr) => {{
+                let mut builder = $builder;
+                for key in keys {
+                    builder.append_option(key.map($value));
+                }
+                Arc::new(builder.finish()) as ArrayRef
+            }};
+        }
+        match self.spec.data_type {
+            GeneratedType::Boolean => build!(BooleanBuilder::with_capacity(len), |k| k % 2 == 1),
+            GeneratedType::Int32 => build!(Int32Builder::with_capacity(len), |k| k as i32),
+            GeneratedType::Int64 => build!(Int64Builder::with_capacity(len), |k| k as i64),
+            GeneratedType::Float => {
+                build!(Float32Builder::with_capacity(len), |k| k as f32 / 100.0)
+            }
+            GeneratedType::Double => {
+                build!(Float64Builder::with_capacity(len), |k| k as f64 / 100.0)
+            }
+            GeneratedType::String => {
+                let name = &self.spec.name;
+                build!(StringBuilder::with_capacity(len, len * 16), |k| format!(
+                    "{name}_{k}"
+                ))
+            }
+            GeneratedType::Date => build!(Date32Builder::with_capacity(len), |k| {
+                (EPOCH_2000_DAYS + k as i64) as i32
+            }),
+            // One second apart.
+            GeneratedType::Timestamp => build!(
+                TimestampMicrosecondBuilder::with_capacity(len).with_timezone("UTC"),
+                |k| (EPOCH_2000_DAYS * 86_400 + k as i64) * 1_000_000
+            ),
+        }
+    }
+}
+
+/// Write a file of made-up rows with the given columns, for testing
+/// readers against a known layout.
+pub fn generate(
+    path: &str,
+    columns: &[ColumnSpec],
+    options: &GenerateOptions,
+) -> Result<Generated, String> {
+    if columns.is_empty() {
+        return Err("no columns to generate".to_string());
+    }
+    let fields: Vec<Field> = columns
+        .iter()
+        .map(|spec| Field::new(&spec.name, spec.data_type.arrow_type(), spec.nulls > 0.0))
+        .collect();
+    let schema = Arc::new(Schema::new(fields));
+
+    let mut props = WriterProperties::builder().set_compression(options.codec);
+    match options.row_group_size {
+        Some(RowGroupTarget::Rows(rows)) => {
+            props = props.set_max_row_group_row_count(Some(rows as usize));
+        }
+        Some(RowGroupTarget::Bytes(bytes)) => {
+            props = props
+                .set_max_row_group_row_count(None)
+                .set_max_row_group_bytes(Some(bytes as usize));
+        }
+        None => {}
+    }
+    for spec in columns {
+        if let Some(codec) = spec.codec {
+            props = props.set_column_compression(ColumnPath::from(spec.name.as_str()), codec);
+        }
+    }
+
+    let file = File::create(path).map_err(|e| e.to_string())?;
+    let mut writer = ArrowWriter::try_new(file, schema.clone(), Some(props.build()))
+        .map_err(|e| e.to_string())?;
+    let mut generators: Vec<ColumnGenerator> = columns
+        .iter()
+        .map(|spec| ColumnGenerator::new(spec, options.rows, options.seed))
+        .collect();
+    let mut start = 0;
+    while start < options.rows {
+        let end = (start + WRITE_BATCH).min(options.rows);
+        let arrays: Vec<ArrayRef> = generators
+            .iter_mut()
+            .map(|generator| generator.column(start..end))
+            .collect();
+        let batch = RecordBatch::try_new(schema.clone(), arrays).map_err(|e| e.to_string())?;
+        writer.write(&batch).map_err(|e| e.to_string())?;
+        start = end;
+    }
+    let md = writer.close().map_err(|e| e.to_string())?;
+
+    let bytes = std::fs::metadata(path).map_err(|e| e.to_string())?.len();
+    Ok(Generated {
+        path: path.to_string(),
+        rows: options.rows,
+        row_groups: md.num_row_groups(),
+        bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::parquet_ctx::open_reader;
+    use parquet::file::reader::FileReader;
+    use parquet::file::statistics::Statistics;
+
+    #[test]
+    fn test_parse_column_spec() {
+        let spec: ColumnSpec = "country:string:skewed:nulls=5%:distinct=50:codec=zstd"
+            .parse()
+            .unwrap();
+        assert_eq!(spec.name, "country");
+        assert_eq!(spec.data_type, GeneratedType::String);
+        assert_eq!(spec.distribution, Distribution::Skewed);
+        assert_eq!(spec.nulls, 0.05);
+        assert_eq!(spec.distinct, Some(50));
+        assert_eq!(spec.codec, Some(Compression::ZSTD(ZstdLevel::default())));
+
+        let id: ColumnSpec = "id:int64".parse().unwrap();
+        assert_eq!(
+            (id.distribution, id.nulls, id.codec),
+            (Distribution::Sequential, 0.0, None)
+        );
+        assert!("id".parse::<ColumnSpec>().is_err());
+        assert!("id:decimal".parse::<ColumnSpec>().is_err());
+        assert!("id:int64:nulls=150%".parse::<ColumnSpec>().is_err());
+        assert!("id:int64:sorted".parse::<ColumnSpec>().is_err());
+        assert_eq!(
+            parse_codec("gzip(9)"),
+            Ok(Compression::GZIP(GzipLevel::try_new(9).unwrap()))
+        );
+        assert!(parse_codec("zip").is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let path =
+            std::env::temp_dir().join(format!("parqeye-generate-{}.parquet", std::process::id()));
+        let path = path.display().to_string();
+        let columns: Vec<ColumnSpec> = [
+            "id:int64",
+            "score:double:random:distinct=10",
+            "country:string:skewed:nulls=20%:codec=zstd(3)",
+            "day:date:random",
+            "at:timestamp",
+            "flag:bool:random",
+        ]
+        .iter()
+        .map(|spec| spec.parse().unwrap())
+        .collect();
+        let options = GenerateOptions {
+            rows: 20_000,
+            row_group_size: Some(RowGroupTarget::Rows(5_000)),
+            ..GenerateOptions::default()
+        };
+        let generated = generate(&path, &columns, &options);
+        let first = std::fs::read(&path);
+        let again = generate(&path, &columns, &options)
+            .and_then(|_| std::fs::read(&path).map_err(|e| e.to_string()));
+        let reader = open_reader(&path).map(|(reader, _)| reader);
+        std::fs::remove_file(&path).unwrap();
+
+        let generated = generated.unwrap();
+        assert_eq!((generated.rows, generated.row_groups), (20_000, 4));
+        // The same seed writes the same bytes.
+        assert_eq!(first.unwrap(), again.unwrap());
+
+        let reader = reader.unwrap();
+        let row_group = reader.metadata().row_group(0);
+        assert_eq!(row_group.num_rows(), 5_000);
+        let columns = row_group.columns();
+        assert_eq!(columns[0].compression(), Compression::SNAPPY);
+        // The footer names the codec but not its level.
+        assert!(matches!(columns[2].compression(), Compression::ZSTD(_)));
+        match columns[0].statistics() {
+            Some(Statistics::Int64(stats)) => {
+                assert_eq!((stats.min_opt(), stats.max_opt()), (Some(&0), Some(&4_999)));
+            }
+            other => panic!("unexpected statistics {other:?}"),
+        }
+        match columns[1].statistics() {
+            Some(Statistics::Double(stats)) => assert!(*stats.max_opt().unwrap() <= 0.09),
+            other => panic!("unexpected statistics {other:?}"),
+        }
+        // About a fifth of the countries are null.
+        let nulls = columns[2].statistics().unwrap().null_count_opt().unwrap();
+        assert!((800..1200).contains(&nulls), "{nulls} nulls");
+    }
+
+    #[test]
+    fn test_skewed_favours_small_values() {
+        let spec: ColumnSpec = "k:int64:skewed:distinct=1000".parse().unwrap();
+        let mut generator = ColumnGenerator::new(&spec, 10_000, 7);
+        let keys: Vec<u64> = (0..10_000)
+            .filter_map(|row| generator.next_key(row))
+            .collect();
+        let small = keys.iter().filter(|&&k| k < 10).count();
+        let large = keys.iter().filter(|&&k| k >= 500).count();
+        assert!(keys.iter().all(|&k| k < 1000));
+        // log(11)/log(1001) ≈ 35% below 10, log(1001/501)/log(1001) ≈ 10% from 500 up.
+        assert!(small > 3 * large, "{small} small, {large} large");
+    }
+}
@@ -0,0 +1,534 @@
+use parquet::basic::{EdgeInterpolationAlgorithm, LogicalType};
+use parquet::file::metadata::KeyValue;
+use parquet::geospatial::bounding_box::BoundingBox;
+use parquet::geospatial::statistics::GeospatialStatistics;
+use serde::Serialize;
+
+/// CRS of GEOMETRY and GEOGRAPHY columns that don't set one.
+pub const DEFAULT_CRS: &str = "OGC:CRS84";
+
+/// Geometry types are numbered as in WKB: 1 to 7, plus 1000 for Z, 2000
+/// for M and 3000 for ZM coordinates.
+const GEOMETRY_TYPES: [&str; 7] = [
+    "Point",
+    "LineString",
+    "Polygon",
+    "MultiPoint",
+    "MultiLineString",
+    "MultiPolygon",
+    "GeometryCollection",
+];
+
+/// Bounding box of a geospatial column chunk, or of a column once merged
+/// over its chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct GeoBounds {
+    pub xmin: f64,
+    pub xmax: f64,
+    pub ymin: f64,
+    pub ymax: f64,
+    pub z: Option<(f64, f64)>,
+    pub m: Option<(f64, f64)>,
+}
+
+/// What a writer recorded about the geometries of a column chunk.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GeoStats {
+    pub bounds: Option<GeoBounds>,
+    /// Geometry types present, e.g. `Point` or `Polygon Z`.
+    pub types: Vec<String>,
+}
+
+impl GeoStats {
+    pub fn new(stats: &GeospatialStatistics) -> Self {
+        Self {
+            bounds: stats.bounding_box().map(GeoBounds::from),
+            types: stats
+                .geospatial_types()
+                .into_iter()
+                .flatten()
+                .map(|&code| geometry_type_name(code))
+                .collect(),
+        }
+    }
+}
+
+impl From<&BoundingBox> for GeoBounds {
+    fn from(bbox: &BoundingBox) -> Self {
+        Self {
+            xmin: bbox.get_xmin(),
+            xmax: bbox.get_xmax(),
+            ymin: bbox.get_ymin(),
+            ymax: bbox.get_ymax(),
+            z: bbox.get_zmin().zip(bbox.get_zmax()),
+            m: bbox.get_mmin().zip(bbox.get_mmax()),
+        }
+    }
+}
+
+impl GeoBounds {
+    /// The box covering both. For GEOGRAPHY columns `xmin > xmax` means the
+    /// box wraps around the antimeridian; merging a wrapping box gives the
+    /// full longitude range rather than working out the tightest one.
+    pub fn merge(self, other: Self) -> Self {
+        let (xmin, xmax) = if self.xmin > self.xmax || other.xmin > other.xmax {
+            (-180.0, 180.0)
+        } else {
+            (self.xmin.min(other.xmin), self.xmax.max(other.xmax))
+        };
+        let range = |a: Option<(f64, f64)>, b: Option<(f64, f64)>| {
+            a.zip(b).map(|(a, b)| (a.0.min(b.0), a.1.max(b.1)))
+        };
+        Self {
+            xmin,
+            xmax,
+            ymin: self.ymin.min(other.ymin),
+            ymax: self.ymax.max(other.ymax),
+            z: range(self.z, other.z),
+            m: range(self.m, other.m),
+        }
+    }
+
+    /// Lower corner, e.g. `(-122.5, 37.7)`, with z and m when known.
+    pub fn min_text(&self) -> String {
+        self.corner(self.xmin, self.ymin, |(low, _)| low)
+    }
+
+    /// Upper corner, e.g. `(-122.3, 37.8)`, with z and m when known.
+    pub fn max_text(&self) -> String {
+        self.corner(self.xmax, self.ymax, |(_, high)| high)
+    }
+
+    fn corner(&self, x: f64, y: f64, pick: fn((f64, f64)) -> f64) -> String {
+        let mut coords = vec![x, y];
+        coords.extend(self.z.map(pick));
+        coords.extend(self.m.map(pick));
+        format!(
+            "({})",
+            coords
+                .iter()
+                .map(f64::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+/// `Point`, `Polygon Z`, ... for a WKB geometry type code.
+pub fn geometry_type_name(code: i32) -> String {
+    let base = usize::try_from(code % 1000)
+        .ok()
+        .and_then(|idx| idx.checked_sub(1))
+        .and_then(|idx| GEOMETRY_TYPES.get(idx));
+    match (base, code / 1000) {
+        (Some(name), 0) => name.to_string(),
+        (Some(name), 1) => format!("{name} Z"),
+        (Some(name), 2) => format!("{name} M"),
+        (Some(name), 3) => format!("{name} ZM"),
+        _ => format!("Unknown({code})"),
+    }
+}
+
+/// `Geometry(EPSG:4326)` or `Geography(OGC:CRS84, spherical)`, resolving
+/// CRS references to the footer's key-value metadata. `None` for other
+/// logical types.
+pub fn describe_type(logical_type: &LogicalType, key_value: Option<&[KeyValue]>) -> Option<String> {
+    match logical_type {
+        LogicalType::Geometry(t) => Some(format!(
+            "Geometry({})",
+            crs_description(t.crs.as_deref(), key_value)
+        )),
+        LogicalType::Geography(t) => Some(format!(
+            "Geography({}, {})",
+            crs_description(t.crs.as_deref(), key_value),
+            algorithm_name(t.algorithm().unwrap_or_default())
+        )),
+        _ => None,
+    }
+}
+
+fn algorithm_name(algorithm: EdgeInterpolationAlgorithm) -> String {
+    match algorithm {
+        EdgeInterpolationAlgorithm::SPHERICAL => "spherical".to_string(),
+        EdgeInterpolationAlgorithm::VINCENTY => "vincenty".to_string(),
+        EdgeInterpolationAlgorithm::THOMAS => "thomas".to_string(),
+        EdgeInterpolationAlgorithm::ANDOYER => "andoyer".to_string(),
+        EdgeInterpolationAlgorithm::KARNEY => "karney".to_string(),
+        EdgeInterpolationAlgorithm::_Unknown(code) => format!("unknown({code})"),
+    }
+}
+
+/// A column's CRS in a few words. Writers store it as an identifier such
+/// as `srid:4326`, as PROJJSON, or as `projjson:<key>` pointing at
+/// PROJJSON in the key-value metadata; PROJJSON is shown as its name and
+/// id, e.g. `WGS 84 (EPSG:4326)`.
+pub fn crs_description(crs: Option<&str>, key_value: Option<&[KeyValue]>) -> String {
+    let Some(crs) = crs.filter(|crs| !crs.is_empty()) else {
+        return DEFAULT_CRS.to_string();
+    };
+    if let Some(key) = crs.strip_prefix("projjson:") {
+        return key_value
+            .into_iter()
+            .flatten()
+            .find(|kv| kv.key == key)
+            .and_then(|kv| kv.value.as_deref())
+            .and_then(describe_projjson)
+            .unwrap_or_else(|| crs.to_string());
+    }
+    if crs.trim_start().starts_with('{') {
+        return describe_projjson(crs).unwrap_or_else(|| "PROJJSON".to_string());
+    }
+    crs.to_string()
+}
+
+fn describe_projjson(json: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(json).ok()?;
+    let name = value.get("name").and_then(|name| name.as_str());
+    let id = value.get("id").and_then(|id| {
+        let authority = id.get("authority")?.as_str()?;
+        // Codes are numbers for EPSG and strings for some authorities.
+        let code = id.get("code")?;
+        let code = code
+            .as_str()
+            .map_or_else(|| code.to_string(), str::to_string);
+        Some(format!("{authority}:{code}"))
+    });
+    match (name, id) {
+        (Some(name), Some(id)) => Some(format!("{name} ({id})")),
+        (Some(name), None) => Some(name.to_string()),
+        (None, Some(id)) => Some(id),
+        (None, None) => None,
+    }
+}
+
+/// Geometry collections nested deeper than this are rejected rather than
+/// recursed into.
+const MAX_DEPTH: usize = 64;
+
+/// Decode a WKB geometry to WKT, e.g. `POINT (1 2)`. ISO WKB and the
+/// extended WKB of PostGIS (with Z/M flags and an SRID) are understood.
+pub fn wkb_to_wkt(wkb: &[u8]) -> Result<String, String> {
+    let mut reader = WkbReader { bytes: wkb, pos: 0 };
+    let wkt = reader.geometry()?;
+    if reader.pos != wkb.len() {
+        return Err(format!(
+            "{} bytes left after the geometry",
+            wkb.len() - reader.pos
+        ));
+    }
+    Ok(wkt)
+}
+
+struct WkbReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+/// Byte order and coordinate dimensions of the geometry being read.
+#[derive(Clone, Copy)]
+struct Header {
+    little_endian: bool,
+    dims: usize,
+}
+
+impl WkbReader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8], String> {
+        let bytes = self
+            .bytes
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| format!("WKB ends at byte {}", self.bytes.len()))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self, header: Header) -> Result<u32, String> {
+        let bytes: [u8; 4] = self.take(4)?.try_into().unwrap();
+        Ok(if header.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn f64(&mut self, header: Header) -> Result<f64, String> {
+        let bytes: [u8; 8] = self.take(8)?.try_into().unwrap();
+        Ok(if header.little_endian {
+            f64::from_le_bytes(bytes)
+        } else {
+            f64::from_be_bytes(bytes)
+        })
+    }
+
+    /// A count of items that each take at least `min_size` bytes, checked
+    /// against what is left so corrupt counts don't allocate.
+    fn count(&mut self, header: Header, min_size: usize) -> Result<usize, String> {
+        let count = self.u32(header)? as usize;
+        if count.saturating_mul(min_size) > self.bytes.len() - self.pos {
+            return Err(format!("count {count} is larger than the WKB"));
+        }
+        Ok(count)
+    }
+
+    fn geometry(&mut self) -> Result<String, String> {
+        let (name, body) = self.tagged_geometry(0)?;
+        Ok(format!("{name} {body}"))
+    }
+
+    /// The geometry's WKT type, e.g. `POINT Z`, and the rest of its WKT.
+    /// `depth` counts the collections around it.
+    fn tagged_geometry(&mut self, depth: usize) -> Result<(String, String), String> {
+        if depth > MAX_DEPTH {
+            return Err("geometry nested too deeply".to_string());
+        }
+        let little_endian = match self.take(1)?[0] {
+            0 => false,
+            1 => true,
+            other => return Err(format!("invalid byte order {other}")),
+        };
+        let mut header = Header {
+            little_endian,
+            dims: 2,
+        };
+        let code = self.u32(header)?;
+        // Extended WKB flags, then the ISO thousands.
+        let (mut z, mut m) = (code & 0x8000_0000 != 0, code & 0x4000_0000 != 0);
+        if code & 0x2000_0000 != 0 {
+            self.u32(header)?;
+        }
+        let code = code & 0x0fff_ffff;
+        match code / 1000 {
+            1 => z = true,
+            2 => m = true,
+            3 => (z, m) = (true, true),
+            _ => {}
+        }
+        header.dims = 2 + usize::from(z) + usize::from(m);
+        let suffix = match (z, m) {
+            (true, true) => " ZM",
+            (true, false) => " Z",
+            (false, true) => " M",
+            (false, false) => "",
+        };
+
+        let (name, body) = match code % 1000 {
+            1 => ("POINT", self.point(header)?),
+            2 => ("LINESTRING", self.points(header)?),
+            3 => ("POLYGON", self.rings(header)?),
+            4 => ("MULTIPOINT", self.parts(header, false, depth)?),
+            5 => ("MULTILINESTRING", self.parts(header, false, depth)?),
+            6 => ("MULTIPOLYGON", self.parts(header, false, depth)?),
+            7 => ("GEOMETRYCOLLECTION", self.parts(header, true, depth)?),
+            other => return Err(format!("unknown geometry type {other}")),
+        };
+        Ok((format!("{name}{suffix}"), body))
+    }
+
+    fn coordinates(&mut self, header: Header) -> Result<Vec<f64>, String> {
+        (0..header.dims).map(|_| self.f64(header)).collect()
+    }
+
+    fn format_coordinates(coords: &[f64]) -> String {
+        coords
+            .iter()
+            .map(f64::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// An empty point is written with NaN coordinates.
+    fn point(&mut self, header: Header) -> Result<String, String> {
+        let coords = self.coordinates(header)?;
+        Ok(if coords.iter().all(|c| c.is_nan()) {
+            "EMPTY".to_string()
+        } else {
+            format!("({})", Self::format_coordinates(&coords))
+        })
+    }
+
+    fn points(&mut self, header: Header) -> Result<String, String> {
+        let count = self.count(header, header.dims * 8)?;
+        if count == 0 {
+            return Ok("EMPTY".to_string());
+        }
+        let points = (0..count)
+            .map(|_| Ok(Self::format_coordinates(&self.coordinates(header)?)))
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(format!("({})", points.join(", ")))
+    }
+
+    fn rings(&mut self, header: Header) -> Result<String, String> {
+        let count = self.count(header, 4)?;
+        if count == 0 {
+            return Ok("EMPTY".to_string());
+        }
+        let rings = (0..count)
+            .map(|_| self.points(header))
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(format!("({})", rings.join(", ")))
+    }
+
+    /// The geometries of a multi-geometry or collection, each a complete
+    /// WKB geometry of its own. Only a collection's members keep their type
+    /// in WKT; a multi-geometry's type already says what its parts are.
+    fn parts(&mut self, header: Header, typed: bool, depth: usize) -> Result<String, String> {
+        let count = self.count(header, 5)?;
+        if count == 0 {
+            return Ok("EMPTY".to_string());
+        }
+        let parts = (0..count)
+            .map(|_| {
+                let (name, body) = self.tagged_geometry(depth + 1)?;
+                Ok(if typed {
+                    format!("{name} {body}")
+                } else {
+                    body
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(format!("({})", parts.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(code: u32) -> Vec<u8> {
+        let mut wkb = vec![1];
+        wkb.extend(code.to_le_bytes());
+        wkb
+    }
+
+    fn coords(wkb: &mut Vec<u8>, values: &[f64]) {
+        for value in values {
+            wkb.extend(value.to_le_bytes());
+        }
+    }
+
+    #[test]
+    fn test_points() {
+        let mut point = header(1);
+        coords(&mut point, &[1.0, 2.5]);
+        assert_eq!(wkb_to_wkt(&point).unwrap(), "POINT (1 2.5)");
+
+        let mut point_z = header(1001);
+        coords(&mut point_z, &[1.0, 2.0, 3.0]);
+        assert_eq!(wkb_to_wkt(&point_z).unwrap(), "POINT Z (1 2 3)");
+
+        // Big endian, extended WKB with an SRID.
+        let mut ewkb = vec![0];
+        ewkb.extend((0x2000_0001u32).to_be_bytes());
+        ewkb.extend(4326u32.to_be_bytes());
+        ewkb.extend((-1.0f64).to_be_bytes());
+        ewkb.extend(0.5f64.to_be_bytes());
+        assert_eq!(wkb_to_wkt(&ewkb).unwrap(), "POINT (-1 0.5)");
+
+        let mut empty = header(1);
+        coords(&mut empty, &[f64::NAN, f64::NAN]);
+        assert_eq!(wkb_to_wkt(&empty).unwrap(), "POINT EMPTY");
+    }
+
+    #[test]
+    fn test_polygons_and_collections() {
+        let mut polygon = header(3);
+        polygon.extend(1u32.to_le_bytes());
+        polygon.extend(4u32.to_le_bytes());
+        coords(&mut polygon, &[0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 0.0]);
+        assert_eq!(
+            wkb_to_wkt(&polygon).unwrap(),
+            "POLYGON ((0 0, 1 0, 1 1, 0 0))"
+        );
+
+        let mut multi = header(6);
+        multi.extend(1u32.to_le_bytes());
+        multi.extend(&polygon);
+        assert_eq!(
+            wkb_to_wkt(&multi).unwrap(),
+            "MULTIPOLYGON (((0 0, 1 0, 1 1, 0 0)))"
+        );
+
+        let mut point = header(1);
+        coords(&mut point, &[4.0, 5.0]);
+        let mut multi_point = header(4);
+        multi_point.extend(2u32.to_le_bytes());
+        multi_point.extend(&point);
+        multi_point.extend(&point);
+        assert_eq!(
+            wkb_to_wkt(&multi_point).unwrap(),
+            "MULTIPOINT ((4 5), (4 5))"
+        );
+
+        let mut collection = header(7);
+        collection.extend(2u32.to_le_bytes());
+        collection.extend(&point);
+        collection.extend(&polygon);
+        assert_eq!(
+            wkb_to_wkt(&collection).unwrap(),
+            "GEOMETRYCOLLECTION (POINT (4 5), POLYGON ((0 0, 1 0, 1 1, 0 0)))"
+        );
+
+        let mut empty_line = header(2);
+        empty_line.extend(0u32.to_le_bytes());
+        assert_eq!(wkb_to_wkt(&empty_line).unwrap(), "LINESTRING EMPTY");
+    }
+
+    #[test]
+    fn test_invalid_wkb() {
+        assert!(wkb_to_wkt(&[]).is_err());
+        assert!(wkb_to_wkt(&header(1)).is_err());
+        let mut huge = header(2);
+        huge.extend(u32::MAX.to_le_bytes());
+        assert!(wkb_to_wkt(&huge).is_err());
+        assert!(wkb_to_wkt(&header(99)).is_err());
+        // One collection nested past the limit.
+        let mut deep = header(7);
+        deep.extend(0u32.to_le_bytes());
+        for _ in 0..=MAX_DEPTH {
+            deep = [header(7), 1u32.to_le_bytes().to_vec(), deep].concat();
+        }
+        assert_eq!(wkb_to_wkt(&deep).unwrap_err(), "geometry nested too deeply");
+        // A crafted value far past it returns an error too, without
+        // overflowing the stack.
+        let mut crafted = Vec::new();
+        for _ in 0..200_000 {
+            crafted.extend(header(7));
+            crafted.extend(1u32.to_le_bytes());
+        }
+        assert!(wkb_to_wkt(&crafted).is_err());
+    }
+
+    #[test]
+    fn test_crs_description() {
+        assert_eq!(crs_description(None, None), "OGC:CRS84");
+        assert_eq!(crs_description(Some("srid:4326"), None), "srid:4326");
+        let projjson = r#"{"name": "WGS 84", "id": {"authority": "EPSG", "code": 4326}}"#;
+        let key_value = [KeyValue::new("crs".to_string(), projjson.to_string())];
+        assert_eq!(
+            crs_description(Some("projjson:crs"), Some(&key_value)),
+            "WGS 84 (EPSG:4326)"
+        );
+        // A missing key is shown as written.
+        assert_eq!(
+            crs_description(Some("projjson:other"), Some(&key_value)),
+            "projjson:other"
+        );
+    }
+
+    #[test]
+    fn test_geometry_types_and_bounds() {
+        assert_eq!(geometry_type_name(1), "Point");
+        assert_eq!(geometry_type_name(1003), "Polygon Z");
+        assert_eq!(geometry_type_name(3007), "GeometryCollection ZM");
+        assert_eq!(geometry_type_name(0), "Unknown(0)");
+
+        let a = GeoBounds::from(&BoundingBox::new(0.0, 10.0, -5.0, 5.0).with_zrange(1.0, 2.0));
+        let b = GeoBounds::from(&BoundingBox::new(-3.0, 4.0, 0.0, 8.0));
+        let merged = a.merge(b);
+        assert_eq!(merged.min_text(), "(-3, -5)");
+        assert_eq!(merged.max_text(), "(10, 8)");
+        assert_eq!(a.min_text(), "(0, -5, 1)");
+        // Wrapping around the antimeridian widens to every longitude.
+        let wrapping = GeoBounds::from(&BoundingBox::new(170.0, -170.0, 0.0, 1.0));
+        assert_eq!(a.merge(wrapping).xmin, -180.0);
+    }
+}
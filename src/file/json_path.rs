@@ -0,0 +1,207 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde_json::Value;
+
+use crate::file::logical::json_one_line;
+
+/// A path into a JSON value such as `$.user.id`, `$.items[0]` or
+/// `$["first name"]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPath {
+    text: String,
+    steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Step {
+    Key(String),
+    Index(usize),
+}
+
+impl JsonPath {
+    /// The value at this path in `value`, if there is one.
+    pub fn select<'a>(&self, value: &'a Value) -> Option<&'a Value> {
+        self.steps.iter().try_fold(value, |value, step| match step {
+            Step::Key(key) => value.get(key),
+            Step::Index(index) => value.get(index),
+        })
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let error = |reason: &str| format!("json path '{text}': {reason}");
+        let mut rest = text
+            .trim()
+            .strip_prefix('$')
+            .ok_or_else(|| error("must start with $"))?;
+        let mut steps = Vec::new();
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix('.') {
+                let end = after.find(['.', '[']).unwrap_or(after.len());
+                if end == 0 {
+                    return Err(error("empty key"));
+                }
+                steps.push(Step::Key(after[..end].to_string()));
+                rest = &after[end..];
+            } else if let Some(after) = rest.strip_prefix('[') {
+                let end = after.find(']').ok_or_else(|| error("missing ]"))?;
+                let inside = after[..end].trim();
+                let quoted = ['"', '\''].into_iter().find_map(|quote| {
+                    inside
+                        .strip_prefix(quote)
+                        .and_then(|key| key.strip_suffix(quote))
+                });
+                steps.push(match quoted {
+                    Some(key) => Step::Key(key.to_string()),
+                    None => Step::Index(
+                        inside
+                            .parse()
+                            .map_err(|_| error(&format!("'{inside}' is not an index")))?,
+                    ),
+                });
+                rest = &after[end + 1..];
+            } else {
+                return Err(error("expected . or ["));
+            }
+        }
+        Ok(Self {
+            text: text.trim().to_string(),
+            steps,
+        })
+    }
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+/// A JSON path pulled out of a column of the preview into a column of its
+/// own, named `column:path`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonColumn {
+    pub column: String,
+    pub path: JsonPath,
+}
+
+impl JsonColumn {
+    pub fn name(&self) -> String {
+        format!("{}:{}", self.column, self.path)
+    }
+
+    /// The extracted value for a cell of the source column, `None` when the
+    /// cell holds no JSON or the path isn't in it.
+    pub fn extract(&self, cell: &str) -> Option<String> {
+        let json = cell_json(cell)?;
+        self.path.select(&json).map(value_text)
+    }
+}
+
+impl FromStr for JsonColumn {
+    type Err = String;
+
+    /// `<column> <path>`, e.g. `payload $.user.id`.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (column, path) = text
+            .trim()
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| "usage: json <column> <path>".to_string())?;
+        Ok(Self {
+            column: column.to_string(),
+            path: path.parse()?,
+        })
+    }
+}
+
+/// The JSON object or array a preview cell holds. JSON columns are shown
+/// as JSON; text columns read by polars are wrapped in quotes, which are
+/// dropped before parsing.
+pub fn cell_json(cell: &str) -> Option<Value> {
+    let text = cell.trim();
+    let text = text
+        .strip_prefix('"')
+        .and_then(|inner| inner.strip_suffix('"'))
+        .unwrap_or(text);
+    if !text.starts_with(['{', '[']) {
+        return None;
+    }
+    serde_json::from_str(text)
+        .ok()
+        .filter(|value: &Value| value.is_object() || value.is_array())
+}
+
+/// Strings without their quotes, anything else as one-line JSON.
+pub fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => json_one_line(other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_and_select() {
+        let value =
+            json!({"user": {"id": 7, "first name": "Ada"}, "items": [{"sku": "a"}, {"sku": "b"}]});
+        let select = |path: &str| {
+            path.parse::<JsonPath>()
+                .unwrap()
+                .select(&value)
+                .map(value_text)
+        };
+        assert_eq!(select("$.user.id").as_deref(), Some("7"));
+        assert_eq!(select("$.items[1].sku").as_deref(), Some("b"));
+        assert_eq!(select(r#"$.user["first name"]"#).as_deref(), Some("Ada"));
+        assert_eq!(
+            select("$['user']").as_deref(),
+            Some(r#"{"id": 7, "first name": "Ada"}"#)
+        );
+        assert_eq!(select("$").as_deref(), Some(value_text(&value).as_str()));
+        assert_eq!(select("$.user.email"), None);
+        assert_eq!(select("$.items[5]"), None);
+        assert_eq!(select("$.user[0]"), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!("user.id".parse::<JsonPath>().is_err());
+        assert!("$.".parse::<JsonPath>().is_err());
+        assert!("$.a..b".parse::<JsonPath>().is_err());
+        assert!("$[x]".parse::<JsonPath>().is_err());
+        assert!("$[0".parse::<JsonPath>().is_err());
+        assert!("$a".parse::<JsonPath>().is_err());
+    }
+
+    #[test]
+    fn test_cell_json() {
+        assert_eq!(cell_json(r#"{"a": 1}"#), Some(json!({"a": 1})));
+        // polars quotes strings without escaping them.
+        assert_eq!(cell_json(r#""[1, {"b": 2}]""#), Some(json!([1, {"b": 2}])));
+        assert_eq!(cell_json("42"), None);
+        assert_eq!(cell_json(r#""plain text""#), None);
+        assert_eq!(cell_json("{not json"), None);
+        assert_eq!(cell_json("NULL"), None);
+    }
+
+    #[test]
+    fn test_json_column() {
+        let column: JsonColumn = "payload $.user.id".parse().unwrap();
+        assert_eq!(column.name(), "payload:$.user.id");
+        assert_eq!(
+            column.extract(r#""{"user":{"id":"u7"}}""#).as_deref(),
+            Some("u7")
+        );
+        assert_eq!(column.extract("NULL"), None);
+        assert!("payload".parse::<JsonColumn>().is_err());
+        assert!("payload user.id".parse::<JsonColumn>().is_err());
+    }
+}
@@ -0,0 +1,332 @@
+use std::fmt;
+
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::file::offsets::OffsetReport;
+use crate::file::parquet_ctx::ParquetCtx;
+use crate::file::row_groups::RowGroupColumnMetadata;
+use crate::file::schema::SchemaInfo;
+use crate::file::temporal::TemporalAudit;
+use crate::file::utils::human_readable_bytes;
+
+/// Row groups smaller than this on average make readers pay per-row-group
+/// overhead (footer entries, range requests) for little data.
+pub const SMALL_ROW_GROUP_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Columns listed by name in a finding before the rest are counted.
+const LISTED_COLUMNS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        })
+    }
+}
+
+/// Something about the file's layout worth a second look.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Finding {
+    pub severity: Severity,
+    /// Short identifier of the check, e.g. `small-row-groups`.
+    pub rule: String,
+    pub message: String,
+}
+
+impl Finding {
+    pub fn new(severity: Severity, rule: impl Into<String>, message: String) -> Self {
+        Self {
+            severity,
+            rule: rule.into(),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}] {}", self.severity, self.rule, self.message)
+    }
+}
+
+/// `a, b, c and 4 more`.
+fn list_columns(columns: &[String]) -> String {
+    let listed = columns.iter().take(LISTED_COLUMNS).join(", ");
+    if columns.len() > LISTED_COLUMNS {
+        format!("{listed} and {} more", columns.len() - LISTED_COLUMNS)
+    } else {
+        listed
+    }
+}
+
+fn plural(n: usize, word: &str) -> String {
+    format!("{n} {word}{}", if n == 1 { "" } else { "s" })
+}
+
+/// Leaf column paths whose chunks in every row group match `pred`.
+fn columns_where(ctx: &ParquetCtx, pred: impl Fn(&RowGroupColumnMetadata) -> bool) -> Vec<String> {
+    if ctx.row_groups.row_groups.is_empty() {
+        return vec![];
+    }
+    ctx.schema
+        .leaf_paths()
+        .into_iter()
+        .enumerate()
+        .filter(|&(col, _)| {
+            ctx.row_groups
+                .row_groups
+                .iter()
+                .all(|rg| rg.column_metadata.get(col).is_some_and(&pred))
+        })
+        .map(|(_, path)| path)
+        .collect()
+}
+
+/// Check the file for common layout problems, most severe first.
+pub fn lint(ctx: &ParquetCtx) -> Vec<Finding> {
+    let mut findings: Vec<Finding> = ctx
+        .problems()
+        .into_iter()
+        .map(|problem| Finding::new(Severity::Error, "unreadable", problem))
+        .collect();
+
+    let offsets = OffsetReport::from_ctx(ctx);
+    if let Some(first) = offsets.problems.first() {
+        let mut message = format!("inconsistent offsets, {first}");
+        if offsets.problems.len() > 1 {
+            message.push_str(&format!(" and {} more", offsets.problems.len() - 1));
+        }
+        findings.push(Finding::new(Severity::Error, "bad-offsets", message));
+    }
+
+    if let Some(footer) = ctx.metadata.footer.as_ref().filter(|f| f.is_bloated()) {
+        let mut message = format!(
+            "metadata is {:.0}% of the file",
+            footer.footer_fraction() * 100.0
+        );
+        if let Some((column, size)) = footer.largest_statistics() {
+            message.push_str(&format!(
+                "; statistics of {column} alone take {}",
+                human_readable_bytes(*size)
+            ));
+        }
+        findings.push(Finding::new(Severity::Warning, "bloated-footer", message));
+    }
+
+    if let Some(deletes) = ctx
+        .metadata
+        .deletes
+        .as_ref()
+        .filter(|d| d.needs_compaction())
+    {
+        findings.push(Finding::new(
+            Severity::Warning,
+            "heavily-deleted",
+            format!(
+                "{:.0}% of rows ({} of {}) are deleted by {} deletes; compacting the file would drop them",
+                deletes.deleted_fraction() * 100.0,
+                deletes.deleted_rows,
+                deletes.physical_rows,
+                deletes.format.name()
+            ),
+        ));
+    }
+
+    let row_groups = ctx.row_groups.num_row_groups();
+    let avg_size = ctx.row_groups.aggregates.compressed_size.avg as u64;
+    if row_groups > 1 && avg_size < SMALL_ROW_GROUP_BYTES {
+        findings.push(Finding::new(
+            Severity::Warning,
+            "small-row-groups",
+            format!(
+                "{} average {} compressed; fewer, larger row groups (128 MiB or more) read faster",
+                plural(row_groups, "row group"),
+                human_readable_bytes(avg_size)
+            ),
+        ));
+    }
+
+    let uncompressed = columns_where(ctx, |c| c.compression_type == "UNCOMPRESSED");
+    if !uncompressed.is_empty() {
+        findings.push(Finding::new(
+            Severity::Warning,
+            "uncompressed",
+            format!(
+                "{} stored without compression: {}",
+                plural(uncompressed.len(), "column"),
+                list_columns(&uncompressed)
+            ),
+        ));
+    }
+
+    let no_stats = columns_where(ctx, |c| !c.has_stats.has_stats);
+    if !no_stats.is_empty() {
+        findings.push(Finding::new(
+            Severity::Warning,
+            "missing-statistics",
+            format!(
+                "{} without min/max statistics, so readers cannot skip row groups: {}",
+                plural(no_stats.len(), "column"),
+                list_columns(&no_stats)
+            ),
+        ));
+    }
+
+    let paths = ctx.schema.leaf_paths();
+    let fallback: Vec<String> = ctx
+        .row_groups
+        .row_groups
+        .iter()
+        .flat_map(|rg| rg.column_metadata.iter().enumerate())
+        .filter(|(_, c)| c.encodings.dictionary_fallback)
+        .filter_map(|(col, _)| paths.get(col).cloned())
+        .unique()
+        .collect();
+    if !fallback.is_empty() {
+        findings.push(Finding::new(
+            Severity::Info,
+            "dictionary-fallback",
+            format!(
+                "dictionary encoding fell back to plain part way through {}: {}",
+                plural(fallback.len(), "column"),
+                list_columns(&fallback)
+            ),
+        ));
+    }
+
+    let mismatched: Vec<(usize, &str, String)> = ctx
+        .row_groups
+        .row_groups
+        .iter()
+        .flat_map(|rg| {
+            rg.column_metadata.iter().filter_map(move |c| {
+                let mismatch = c.encodings.footer_mismatch.as_ref()?;
+                Some((rg.idx, c.column_path.as_str(), mismatch.to_string()))
+            })
+        })
+        .collect();
+    if let Some((rg, column, mismatch)) = mismatched.first() {
+        let columns: Vec<String> = mismatched
+            .iter()
+            .map(|(_, column, _)| column.to_string())
+            .unique()
+            .collect();
+        findings.push(Finding::new(
+            Severity::Warning,
+            "encoding-mismatch",
+            format!(
+                "the footer's encodings disagree with the pages of {}: {}; e.g. {column} in row group {rg}: {mismatch}",
+                plural(columns.len(), "column"),
+                list_columns(&columns)
+            ),
+        ));
+    }
+
+    let int96: Vec<String> = ctx
+        .schema
+        .columns
+        .iter()
+        .enumerate()
+        .filter(
+            |(_, c)| matches!(c, SchemaInfo::Primitive { info, .. } if info.physical == "INT96"),
+        )
+        .map(|(idx, _)| ctx.schema.column_path(idx))
+        .collect();
+    if !int96.is_empty() {
+        findings.push(Finding::new(
+            Severity::Info,
+            "int96-timestamps",
+            format!(
+                "{} use the deprecated INT96 timestamp type: {}",
+                plural(int96.len(), "column"),
+                list_columns(&int96)
+            ),
+        ));
+    }
+
+    let temporal = TemporalAudit::from_metadata(&ctx.file_path, &ctx.reader.metadata_ptr());
+    findings.extend(
+        temporal
+            .mixed
+            .into_iter()
+            .map(|mixed| Finding::new(Severity::Warning, "mixed-timestamps", mixed)),
+    );
+
+    let writer = ctx.metadata.writer.as_ref();
+    findings.extend(ctx.metadata.writer_quirks.iter().map(|quirk| {
+        Finding::new(
+            Severity::Info,
+            "writer-quirk",
+            format!(
+                "{}: {quirk}",
+                writer.map_or("writer".to_string(), |w| w.to_string())
+            ),
+        )
+    }));
+
+    findings.sort_by_key(|f| f.severity);
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_data_path;
+
+    fn rules(findings: &[Finding]) -> Vec<&str> {
+        findings.iter().map(|f| f.rule.as_str()).collect()
+    }
+
+    #[test]
+    fn test_lint_uncompressed_file() {
+        // A 2.7 KiB file with one uncompressed row group and no statistics.
+        let ctx = ParquetCtx::from_file(&test_data_path("alltypes_plain.parquet")).unwrap();
+        let findings = lint(&ctx);
+        // Info findings (such as its INT96 timestamp column) come after.
+        assert_eq!(
+            rules(&findings)[..3],
+            ["bloated-footer", "uncompressed", "missing-statistics"]
+        );
+        assert_eq!(findings[0].message, "metadata is 72% of the file");
+        assert_eq!(
+            findings[1].message,
+            "11 columns stored without compression: id, bool_col, tinyint_col, smallint_col, int_col and 6 more"
+        );
+        assert!(
+            findings
+                .iter()
+                .skip(3)
+                .all(|f| f.severity == Severity::Info)
+        );
+    }
+
+    #[test]
+    fn test_list_columns() {
+        let columns: Vec<String> = ["a", "b"].map(String::from).to_vec();
+        assert_eq!(list_columns(&columns), "a, b");
+        let columns: Vec<String> = (0..7).map(|i| format!("c{i}")).collect();
+        assert_eq!(list_columns(&columns), "c0, c1, c2, c3, c4 and 2 more");
+        assert_eq!(plural(1, "column"), "1 column");
+    }
+
+    #[test]
+    fn test_lint_small_row_groups() {
+        let ctx = ParquetCtx::from_file(&test_data_path("nulls.snappy.parquet")).unwrap();
+        let findings = lint(&ctx);
+        assert!(rules(&findings).contains(&"small-row-groups"));
+        assert!(!rules(&findings).contains(&"uncompressed"));
+        assert!(!rules(&findings).contains(&"encoding-mismatch"));
+        assert!(findings.windows(2).all(|w| w[0].severity <= w[1].severity));
+    }
+}
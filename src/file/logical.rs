@@ -0,0 +1,445 @@
+use chrono::{DateTime, Utc};
+use parquet::basic::{LogicalType, TimeUnit};
+use serde_json::{Map, Number, Value, json};
+
+use crate::file::geo::wkb_to_wkt;
+
+/// Nested BSON documents deeper than this are rejected rather than
+/// recursed into.
+const MAX_BSON_DEPTH: usize = 64;
+
+/// Show a value of a column whose bytes mean nothing without its logical
+/// type: FLOAT16 as a number, UUID in its 8-4-4-4-12 form, JSON and BSON as
+/// one-line JSON, GEOMETRY or GEOGRAPHY as WKT and TIMESTAMP and DATE in
+/// UTC. `None` for other types, or when the value has the wrong length for
+/// its type.
+pub fn format_value(bytes: &[u8], logical_type: &LogicalType) -> Option<String> {
+    match logical_type {
+        LogicalType::Float16 => {
+            let bits = u16::from_le_bytes(bytes.try_into().ok()?);
+            Some(f16_to_f32(bits).to_string())
+        }
+        LogicalType::Uuid => format_uuid(bytes),
+        LogicalType::Json => Some(match std::str::from_utf8(bytes) {
+            Ok(text) => serde_json::from_str::<Value>(text)
+                .map_or_else(|_| text.to_string(), |value| json_one_line(&value)),
+            Err(_) => hex(bytes),
+        }),
+        LogicalType::Bson => {
+            Some(bson_to_json(bytes).map_or_else(|_| hex(bytes), |value| json_one_line(&value)))
+        }
+        LogicalType::Geometry(_) | LogicalType::Geography(_) => {
+            Some(wkb_to_wkt(bytes).unwrap_or_else(|error| format!("invalid WKB: {error}")))
+        }
+        LogicalType::Timestamp(_) => Some(
+            decode_time(bytes, logical_type)?
+                .format("%Y-%m-%d %H:%M:%S%.f")
+                .to_string(),
+        ),
+        LogicalType::Date => Some(
+            decode_time(bytes, logical_type)?
+                .format("%Y-%m-%d")
+                .to_string(),
+        ),
+        _ => None,
+    }
+}
+
+/// The instant an INT64 TIMESTAMP or INT32 DATE value stands for, e.g. a
+/// min or max statistic. `None` for other types or out-of-range values.
+pub fn decode_time(bytes: &[u8], logical_type: &LogicalType) -> Option<DateTime<Utc>> {
+    match logical_type {
+        LogicalType::Timestamp(t) => {
+            let ticks = i64::from_le_bytes(bytes.try_into().ok()?);
+            match t.unit {
+                TimeUnit::MILLIS => DateTime::from_timestamp_millis(ticks),
+                TimeUnit::MICROS => DateTime::from_timestamp_micros(ticks),
+                TimeUnit::NANOS => Some(DateTime::from_timestamp_nanos(ticks)),
+            }
+        }
+        LogicalType::Date => {
+            let days = i32::from_le_bytes(bytes.try_into().ok()?);
+            DateTime::from_timestamp(i64::from(days) * 86_400, 0)
+        }
+        _ => None,
+    }
+}
+
+/// Widen an IEEE 754 half-precision float, as FLOAT16 columns store them.
+pub fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = i32::from((bits >> 10) & 0x1f);
+    let mantissa = f32::from(bits & 0x3ff);
+    sign * match exponent {
+        0 => mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
+
+/// `00112233-4455-6677-8899-aabbccddeeff`, or `None` unless given 16 bytes.
+pub fn format_uuid(bytes: &[u8]) -> Option<String> {
+    if bytes.len() != 16 {
+        return None;
+    }
+    let groups = [
+        &bytes[..4],
+        &bytes[4..6],
+        &bytes[6..8],
+        &bytes[8..10],
+        &bytes[10..],
+    ];
+    Some(groups.map(hex).join("-").to_lowercase())
+}
+
+/// Parse `00112233-4455-6677-8899-aabbccddeeff`, dashes optional.
+pub fn parse_uuid(text: &str) -> Option<Vec<u8>> {
+    let digits: Vec<u8> = text.bytes().filter(|&b| b != b'-').collect();
+    if digits.len() != 32 {
+        return None;
+    }
+    digits
+        .chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect()
+}
+
+/// JSON on one line with a space after each `:` and `,`, so that it reads
+/// well in a table cell.
+pub fn json_one_line(value: &Value) -> String {
+    let mut out = String::new();
+    write_json(value, &mut out);
+    out
+}
+
+fn write_json(value: &Value, out: &mut String) {
+    match value {
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_json(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(fields) => {
+            out.push('{');
+            for (i, (key, item)) in fields.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push_str(": ");
+                write_json(item, out);
+            }
+            out.push('}');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+/// Decode a BSON document into MongoDB's relaxed Extended JSON, e.g.
+/// `{"_id": {"$oid": "…"}, "n": 1}`.
+pub fn bson_to_json(bytes: &[u8]) -> Result<Value, String> {
+    let mut reader = BsonReader { bytes, pos: 0 };
+    let document = reader.document(false, 0)?;
+    if reader.pos != bytes.len() {
+        return Err(format!(
+            "{} trailing bytes after the document",
+            bytes.len() - reader.pos
+        ));
+    }
+    Ok(document)
+}
+
+struct BsonReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BsonReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or("truncated BSON")?;
+        let taken = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(taken)
+    }
+
+    fn i32(&mut self) -> Result<i32, String> {
+        Ok(i32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn i64(&mut self) -> Result<i64, String> {
+        Ok(i64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn cstring(&mut self) -> Result<String, String> {
+        let len = self.bytes[self.pos..]
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or("unterminated BSON name")?;
+        let text = String::from_utf8_lossy(self.take(len)?).into_owned();
+        self.pos += 1;
+        Ok(text)
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let len = usize::try_from(self.i32()?).map_err(|_| "negative BSON string length")?;
+        let bytes = self.take(len)?;
+        match bytes.split_last() {
+            Some((0, text)) => Ok(String::from_utf8_lossy(text).into_owned()),
+            _ => Err("unterminated BSON string".to_string()),
+        }
+    }
+
+    fn document(&mut self, array: bool, depth: usize) -> Result<Value, String> {
+        if depth > MAX_BSON_DEPTH {
+            return Err("BSON nested too deeply".to_string());
+        }
+        let start = self.pos;
+        let len = usize::try_from(self.i32()?).map_err(|_| "negative BSON document length")?;
+        let mut fields = Map::new();
+        loop {
+            let tag = self.take(1)?[0];
+            if tag == 0 {
+                break;
+            }
+            let name = self.cstring()?;
+            let value = self.element(tag, depth)?;
+            fields.insert(name, value);
+        }
+        if self.pos - start != len {
+            return Err(format!(
+                "BSON document is {} bytes, not {len}",
+                self.pos - start
+            ));
+        }
+        Ok(if array {
+            Value::Array(fields.into_iter().map(|(_, value)| value).collect())
+        } else {
+            Value::Object(fields)
+        })
+    }
+
+    fn element(&mut self, tag: u8, depth: usize) -> Result<Value, String> {
+        Ok(match tag {
+            0x01 => {
+                let x = f64::from_le_bytes(self.take(8)?.try_into().unwrap());
+                Number::from_f64(x)
+                    .map_or_else(|| json!({ "$numberDouble": x.to_string() }), Value::Number)
+            }
+            0x02 => Value::String(self.string()?),
+            0x03 => self.document(false, depth + 1)?,
+            0x04 => self.document(true, depth + 1)?,
+            0x05 => {
+                let len =
+                    usize::try_from(self.i32()?).map_err(|_| "negative BSON binary length")?;
+                let subtype = self.take(1)?[0];
+                json!({ "$binary": { "base64": base64(self.take(len)?), "subType": format!("{subtype:02x}") } })
+            }
+            0x06 => json!({ "$undefined": true }),
+            0x07 => json!({ "$oid": hex(self.take(12)?).to_lowercase() }),
+            0x08 => Value::Bool(self.take(1)?[0] != 0),
+            0x09 => {
+                let millis = self.i64()?;
+                match DateTime::from_timestamp_millis(millis) {
+                    Some(date) => {
+                        json!({ "$date": date.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string() })
+                    }
+                    None => json!({ "$date": { "$numberLong": millis.to_string() } }),
+                }
+            }
+            0x0A => Value::Null,
+            0x0B => {
+                let pattern = self.cstring()?;
+                let options = self.cstring()?;
+                json!({ "$regularExpression": { "pattern": pattern, "options": options } })
+            }
+            0x0D => json!({ "$code": self.string()? }),
+            0x10 => Value::from(self.i32()?),
+            0x11 => {
+                let increment = u32::from_le_bytes(self.take(4)?.try_into().unwrap());
+                let seconds = u32::from_le_bytes(self.take(4)?.try_into().unwrap());
+                json!({ "$timestamp": { "t": seconds, "i": increment } })
+            }
+            0x12 => Value::from(self.i64()?),
+            0xFF => json!({ "$minKey": 1 }),
+            0x7F => json!({ "$maxKey": 1 }),
+            other => return Err(format!("unsupported BSON element type 0x{other:02X}")),
+        })
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02X}")).collect()
+}
+
+pub(crate) fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A BSON document of `(type, name, value)` elements.
+    fn bson(elements: &[(u8, &str, Vec<u8>)]) -> Vec<u8> {
+        let mut body = Vec::new();
+        for (tag, name, value) in elements {
+            body.push(*tag);
+            body.extend(name.as_bytes());
+            body.push(0);
+            body.extend(value);
+        }
+        body.push(0);
+        let mut document = ((body.len() + 4) as i32).to_le_bytes().to_vec();
+        document.extend(body);
+        document
+    }
+
+    fn bson_string(text: &str) -> Vec<u8> {
+        let mut bytes = ((text.len() + 1) as i32).to_le_bytes().to_vec();
+        bytes.extend(text.as_bytes());
+        bytes.push(0);
+        bytes
+    }
+
+    #[test]
+    fn test_f16_to_f32() {
+        assert_eq!(f16_to_f32(0x3c00), 1.0);
+        assert_eq!(f16_to_f32(0xc000), -2.0);
+        assert_eq!(f16_to_f32(0x3555), 0.33325195);
+        assert_eq!(f16_to_f32(0x7bff), 65504.0);
+        assert_eq!(f16_to_f32(0x0001), 2f32.powi(-24));
+        assert_eq!(f16_to_f32(0xfc00), f32::NEG_INFINITY);
+        assert!(f16_to_f32(0x7e00).is_nan());
+    }
+
+    #[test]
+    fn test_uuid_round_trip() {
+        let bytes: Vec<u8> = (0..16).map(|i| i * 17).collect();
+        let text = format_uuid(&bytes).unwrap();
+        assert_eq!(text, "00112233-4455-6677-8899-aabbccddeeff");
+        assert_eq!(parse_uuid(&text), Some(bytes));
+        assert_eq!(format_uuid(&[0; 15]), None);
+        assert_eq!(parse_uuid("not-a-uuid"), None);
+    }
+
+    #[test]
+    fn test_format_value() {
+        assert_eq!(
+            format_value(&[0x00, 0x3c], &LogicalType::Float16).as_deref(),
+            Some("1")
+        );
+        assert_eq!(format_value(&[0x00], &LogicalType::Float16), None);
+        assert_eq!(
+            format_value(br#"{"a":1,"b":[true,null]}"#, &LogicalType::Json).as_deref(),
+            Some(r#"{"a": 1, "b": [true, null]}"#)
+        );
+        assert_eq!(
+            format_value(b"not json", &LogicalType::Json).as_deref(),
+            Some("not json")
+        );
+        assert_eq!(
+            format_value(&[1, 2, 3], &LogicalType::Bson).as_deref(),
+            Some("010203")
+        );
+        assert_eq!(format_value(&[1, 2], &LogicalType::String), None);
+    }
+
+    #[test]
+    fn test_format_times() {
+        let micros = LogicalType::Timestamp(parquet::basic::TimestampType {
+            is_adjusted_to_u_t_c: true,
+            unit: TimeUnit::MICROS,
+        });
+        // 2009-03-01 00:00:00 and half a second.
+        let ticks = 1_235_865_600_500_000i64.to_le_bytes();
+        assert_eq!(
+            format_value(&ticks, &micros).as_deref(),
+            Some("2009-03-01 00:00:00.500")
+        );
+        assert_eq!(
+            decode_time(&0i64.to_le_bytes(), &micros),
+            Some(DateTime::UNIX_EPOCH)
+        );
+        assert_eq!(
+            format_value(&14_304i32.to_le_bytes(), &LogicalType::Date).as_deref(),
+            Some("2009-03-01")
+        );
+        assert_eq!(decode_time(&[1, 2], &LogicalType::Date), None);
+    }
+
+    #[test]
+    fn test_bson_to_json() {
+        let nested = bson(&[
+            (0x10, "0", 7i32.to_le_bytes().to_vec()),
+            (0x0A, "1", vec![]),
+        ]);
+        let document = bson(&[
+            (0x07, "_id", (1..=12).collect()),
+            (0x02, "name", bson_string("parqeye")),
+            (0x01, "score", 1.5f64.to_le_bytes().to_vec()),
+            (0x12, "big", (1i64 << 40).to_le_bytes().to_vec()),
+            (0x08, "ok", vec![1]),
+            (0x04, "list", nested),
+            (0x09, "at", 0i64.to_le_bytes().to_vec()),
+            (
+                0x05,
+                "raw",
+                [3i32.to_le_bytes().to_vec(), vec![0, b'a', b'b', b'c']].concat(),
+            ),
+        ]);
+        assert_eq!(
+            json_one_line(&bson_to_json(&document).unwrap()),
+            concat!(
+                r#"{"_id": {"$oid": "0102030405060708090a0b0c"}, "name": "parqeye", "#,
+                r#""score": 1.5, "big": 1099511627776, "ok": true, "list": [7, null], "#,
+                r#""at": {"$date": "1970-01-01T00:00:00.000Z"}, "#,
+                r#""raw": {"$binary": {"base64": "YWJj", "subType": "00"}}}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_bson_rejects_malformed_documents() {
+        let document = bson(&[(0x10, "n", 1i32.to_le_bytes().to_vec())]);
+        assert!(bson_to_json(&document[..document.len() - 1]).is_err());
+        assert!(bson_to_json(&[document.clone(), vec![0]].concat()).is_err());
+        let mut wrong_length = document.clone();
+        wrong_length[0] += 1;
+        assert!(bson_to_json(&wrong_length).is_err());
+        assert!(bson_to_json(&bson(&[(0x13, "d", vec![0; 16])])).is_err());
+    }
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+}
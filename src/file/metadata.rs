@@ -1,23 +1,37 @@
 use itertools::Itertools;
 use parquet::file::metadata::ParquetMetaData;
-use ratatui::widgets::Widget;
+use parquet::schema::types::Type as ParquetType;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Seek, SeekFrom};
+
+use crate::file::deletes::DeleteInfo;
+use crate::file::writer::Writer;
+
+#[cfg(feature = "tui")]
+use crate::components::ScrollbarComponent;
+#[cfg(feature = "tui")]
+use crate::file::Renderable;
+#[cfg(feature = "tui")]
+use crate::file::deletes::MAX_SCANNED_FILES;
+#[cfg(feature = "tui")]
+use crate::file::utils::commas;
+#[cfg(feature = "tui")]
+use crate::file::utils::human_readable_bytes;
+#[cfg(feature = "tui")]
+use crate::theme::Theme;
+#[cfg(feature = "tui")]
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
-    prelude::Color,
     style::Stylize,
     symbols::border,
     text::{Line, Span, Text},
-    widgets::{Block, Cell, Paragraph, Row, Table},
+    widgets::{Block, Cell, Paragraph, Row, Table, Widget},
 };
-use std::collections::{HashMap, HashSet};
-
-use crate::components::ScrollbarComponent;
-use crate::file::Renderable;
-use crate::file::utils::commas;
-use crate::file::utils::human_readable_bytes;
 
 /// Wrap a single line into chunks of at most `width` characters.
+#[cfg(feature = "tui")]
 fn wrap_line(line: &str, width: usize) -> Vec<String> {
     if width == 0 || line.is_empty() {
         return vec![line.to_string()];
@@ -28,10 +42,14 @@ fn wrap_line(line: &str, width: usize) -> Vec<String> {
         .collect()
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct FileMetadata {
     pub format_version: String,
     pub created_by: String,
+    /// `created_by` parsed, when it is set.
+    pub writer: Option<Writer>,
+    /// Habits of the writer that show in this file, see [`Writer::quirks`].
+    pub writer_quirks: Vec<String>,
     pub num_rows: usize,
     pub num_columns: usize,
     pub num_row_groups: usize,
@@ -39,9 +57,119 @@ pub struct FileMetadata {
     pub compressed_size: u64,
     pub compression_ratio: f64,
     pub codecs: String,
+    /// Codecs of the column chunks without their levels, most used first.
+    pub codec_names: Vec<String>,
     pub encodings: String,
     pub avg_row_size: u64,
     pub key_value_metadata: Vec<(String, String)>,
+    /// Set when the file itself is available, not just its parsed metadata.
+    pub footer: Option<FooterInfo>,
+    /// Set when the file belongs to a Delta or Iceberg table.
+    pub deletes: Option<DeleteInfo>,
+}
+
+/// How much of the file is metadata rather than data. Tiny files with wide
+/// schemas can end up mostly footer.
+#[derive(Debug, Serialize)]
+pub struct FooterInfo {
+    /// Length of the thrift-encoded footer, from the file's last 8 bytes.
+    pub footer_size: u64,
+    pub file_size: u64,
+    /// Nodes in the schema tree, groups included.
+    pub schema_elements: usize,
+    /// Bytes of min/max statistics per leaf column, summed over row groups.
+    pub statistics_sizes: Vec<(String, u64)>,
+    /// Bytes of key-value metadata (keys + values).
+    pub key_value_size: u64,
+}
+
+impl FooterInfo {
+    /// Footers larger than this fraction of the file are flagged.
+    pub const BLOAT_THRESHOLD: f64 = 0.1;
+
+    pub fn new(md: &ParquetMetaData, footer_size: u64, file_size: u64) -> Self {
+        fn count_elements(node: &ParquetType) -> usize {
+            1 + if node.is_group() {
+                node.get_fields().iter().map(|f| count_elements(f)).sum()
+            } else {
+                0
+            }
+        }
+
+        let schema = md.file_metadata().schema_descr();
+        let mut statistics_sizes: Vec<(String, u64)> = schema
+            .columns()
+            .iter()
+            .map(|col| (col.path().string(), 0))
+            .collect();
+        for rg in md.row_groups() {
+            for (idx, col) in rg.columns().iter().enumerate() {
+                if let Some(stats) = col.statistics() {
+                    let bytes = stats.min_bytes_opt().map_or(0, <[u8]>::len)
+                        + stats.max_bytes_opt().map_or(0, <[u8]>::len);
+                    statistics_sizes[idx].1 += bytes as u64;
+                }
+            }
+        }
+
+        let key_value_size = md
+            .file_metadata()
+            .key_value_metadata()
+            .map(|kv| {
+                kv.iter()
+                    .map(|pair| {
+                        (pair.key.len() + pair.value.as_ref().map_or(0, String::len)) as u64
+                    })
+                    .sum()
+            })
+            .unwrap_or(0);
+
+        Self {
+            footer_size,
+            file_size,
+            schema_elements: count_elements(schema.root_schema()),
+            statistics_sizes,
+            key_value_size,
+        }
+    }
+
+    /// Read the footer length stored just before the trailing `PAR1` magic.
+    pub fn read_footer_size<R: Read + Seek>(reader: &mut R) -> std::io::Result<u64> {
+        let mut tail = [0u8; 8];
+        reader.seek(SeekFrom::End(-8))?;
+        reader.read_exact(&mut tail)?;
+        if &tail[4..] != b"PAR1" {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "missing PAR1 magic at end of file",
+            ));
+        }
+        Ok(u32::from_le_bytes([tail[0], tail[1], tail[2], tail[3]]) as u64)
+    }
+
+    pub fn statistics_size(&self) -> u64 {
+        self.statistics_sizes.iter().map(|(_, size)| size).sum()
+    }
+
+    /// The column with the most statistics bytes, if any has statistics.
+    pub fn largest_statistics(&self) -> Option<&(String, u64)> {
+        self.statistics_sizes
+            .iter()
+            .filter(|(_, size)| *size > 0)
+            .max_by_key(|(_, size)| *size)
+    }
+
+    /// Footer (plus the 8-byte length and magic) as a fraction of the file.
+    pub fn footer_fraction(&self) -> f64 {
+        if self.file_size == 0 {
+            return 0.0;
+        }
+        (self.footer_size + 8) as f64 / self.file_size as f64
+    }
+
+    pub fn is_bloated(&self) -> bool {
+        self.footer_fraction() > Self::BLOAT_THRESHOLD
+    }
 }
 
 impl FileMetadata {
@@ -92,6 +220,20 @@ impl FileMetadata {
             .collect::<Vec<String>>()
             .join(", ");
 
+        let mut codec_names: Vec<(String, usize)> = Vec::new();
+        for (codec, n) in &codec_counts {
+            let name = codec.split('(').next().unwrap_or(codec).to_string();
+            match codec_names.iter_mut().find(|(seen, _)| *seen == name) {
+                Some((_, total)) => *total += n,
+                None => codec_names.push((name, *n)),
+            }
+        }
+        let codec_names = codec_names
+            .into_iter()
+            .sorted_by(|(a, m), (b, n)| n.cmp(m).then(a.cmp(b)))
+            .map(|(name, _)| name)
+            .collect();
+
         let encodings: String = encodings_seen
             .into_iter()
             .sorted()
@@ -108,9 +250,17 @@ impl FileMetadata {
             })
             .unwrap_or_default();
 
+        let writer = Writer::from_metadata(md);
+        let writer_quirks = writer
+            .as_ref()
+            .map(|writer| writer.quirks(md))
+            .unwrap_or_default();
+
         Ok(FileMetadata {
             format_version: format_version.to_string(),
             created_by: created_by.to_string(),
+            writer,
+            writer_quirks,
             num_rows,
             num_columns,
             num_row_groups,
@@ -118,25 +268,92 @@ impl FileMetadata {
             compressed_size,
             compression_ratio,
             codecs,
+            codec_names,
             encodings,
             avg_row_size: avg_row_size as u64,
             key_value_metadata,
+            footer: None,
+            deletes: None,
         })
     }
+
+    pub fn with_footer(mut self, footer: FooterInfo) -> Self {
+        self.footer = Some(footer);
+        self
+    }
+
+    pub fn with_deletes(mut self, deletes: DeleteInfo) -> Self {
+        self.deletes = Some(deletes);
+        self
+    }
 }
 
+#[cfg(feature = "tui")]
 impl FileMetadata {
-    pub fn render_with_scroll(&self, area: Rect, buf: &mut Buffer, scroll: usize) {
+    /// One line with the basics, e.g. `1,000 rows · 4 cols · 2 row groups ·
+    /// 1.2 MiB · SNAPPY`, shown under the tabs.
+    pub fn summary(&self) -> String {
+        let size = self
+            .footer
+            .as_ref()
+            .map_or(self.compressed_size, |footer| footer.file_size);
+        let mut parts = vec![
+            format!("{} rows", commas(self.num_rows as u64)),
+            format!("{} cols", commas(self.num_columns as u64)),
+            format!(
+                "{} row group{}",
+                commas(self.num_row_groups as u64),
+                if self.num_row_groups == 1 { "" } else { "s" }
+            ),
+            human_readable_bytes(size),
+        ];
+        if !self.codec_names.is_empty() {
+            parts.push(self.codec_names.join("/"));
+        }
+        parts.join(" · ")
+    }
+
+    pub fn render_with_scroll(&self, area: Rect, buf: &mut Buffer, scroll: usize, theme: &Theme) {
+        let stats_area = self.render_properties_beside(area, buf, scroll, theme);
+        self.render_stats_centered(stats_area, buf, theme);
+    }
+
+    /// Like `render_with_scroll`, with the stats at the top and `panel` drawn
+    /// below them.
+    pub fn render_with_panel<W: Widget>(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        scroll: usize,
+        theme: &Theme,
+        panel: W,
+    ) {
+        let main_area = self.render_properties_beside(area, buf, scroll, theme);
+        let stats_height = self.stats_pairs().len() as u16 + 2;
+        let [stats_area, panel_area] =
+            Layout::vertical([Constraint::Length(stats_height), Constraint::Fill(1)])
+                .areas(main_area);
+        self.render_stats_centered(stats_area, buf, theme);
+        panel.render(panel_area, buf);
+    }
+
+    /// Draw the properties panel on the right, if there are properties, and
+    /// return the area left for the rest.
+    fn render_properties_beside(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        scroll: usize,
+        theme: &Theme,
+    ) -> Rect {
         if self.key_value_metadata.is_empty() {
-            self.render_stats_centered(area, buf);
-            return;
+            return area;
         }
 
-        let [stats_area, props_area] =
+        let [main_area, props_area] =
             Layout::horizontal([Constraint::Fill(2), Constraint::Fill(1)]).areas(area);
-
-        self.render_stats_centered(stats_area, buf);
-        self.render_properties(props_area, buf, scroll);
+        self.render_properties(props_area, buf, scroll, theme);
+        main_area
     }
 
     pub fn total_property_display_lines(&self) -> usize {
@@ -166,11 +383,61 @@ impl FileMetadata {
             .sum()
     }
 
-    fn render_stats_centered(&self, area: Rect, buf: &mut Buffer) {
-        let kv_pairs: Vec<(String, String)> = vec![
+    fn stats_pairs(&self) -> Vec<(String, String)> {
+        let mut pairs = vec![
             ("Format version".into(), self.format_version.clone()),
             ("Created by".into(), self.created_by.clone()),
-            ("Rows".into(), commas(self.num_rows as u64)),
+        ];
+        if let Some(writer) = &self.writer {
+            pairs.push(("Writer".into(), writer.to_string()));
+        }
+        pairs.extend(
+            self.writer_quirks
+                .iter()
+                .map(|quirk| ("⚠ Writer quirk".into(), quirk.clone())),
+        );
+        pairs.extend([("Rows".into(), commas(self.num_rows as u64))]);
+        if let Some(deletes) = &self.deletes {
+            pairs.push((
+                "Live rows".into(),
+                format!(
+                    "{} ({} deleted by {} {})",
+                    commas(deletes.live_rows()),
+                    commas(deletes.deleted_rows),
+                    deletes.format.name(),
+                    if deletes.delete_files == 1 {
+                        "delete"
+                    } else {
+                        "deletes"
+                    }
+                ),
+            ));
+            if deletes.needs_compaction() {
+                pairs.push((
+                    "⚠ Deletes".into(),
+                    format!(
+                        "{:.0}% of rows deleted; compact this file",
+                        deletes.deleted_fraction() * 100.0
+                    ),
+                ));
+            }
+            if deletes.equality_delete_files > 0 {
+                pairs.push((
+                    "Equality deletes".into(),
+                    format!("{} files, not applied", deletes.equality_delete_files),
+                ));
+            }
+            if deletes.partial {
+                pairs.push((
+                    "⚠ Delete scan".into(),
+                    format!(
+                        "only the first {} files of the table were read",
+                        commas(MAX_SCANNED_FILES as u64)
+                    ),
+                ));
+            }
+        }
+        pairs.extend([
             ("Columns".into(), self.num_columns.to_string()),
             ("Row groups".into(), self.num_row_groups.to_string()),
             ("Size (raw)".into(), human_readable_bytes(self.raw_size)),
@@ -189,7 +456,32 @@ impl FileMetadata {
                 "Properties size".into(),
                 human_readable_bytes(self.properties_size()),
             ),
-        ];
+        ]);
+        if let Some(footer) = &self.footer {
+            pairs.push((
+                "Footer size".into(),
+                format!(
+                    "{} ({:.1}% of file)",
+                    human_readable_bytes(footer.footer_size),
+                    footer.footer_fraction() * 100.0
+                ),
+            ));
+            pairs.push(("Schema elements".into(), footer.schema_elements.to_string()));
+            let statistics = match footer.largest_statistics() {
+                Some((column, size)) => format!(
+                    "{}, most in {column} ({})",
+                    human_readable_bytes(footer.statistics_size()),
+                    human_readable_bytes(*size)
+                ),
+                None => human_readable_bytes(0),
+            };
+            pairs.push(("Statistics size".into(), statistics));
+        }
+        pairs
+    }
+
+    fn render_stats_centered(&self, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        let kv_pairs = self.stats_pairs();
 
         let max_value_size = kv_pairs.iter().map(|(_, v)| v.len()).max().unwrap_or(0) as u16;
 
@@ -197,7 +489,7 @@ impl FileMetadata {
             .into_iter()
             .map(|(k, v)| {
                 Row::new(vec![
-                    Cell::from(format!("{k:>18}")).bold().fg(Color::Blue),
+                    Cell::from(format!("{k:>18}")).bold().fg(theme.label),
                     Cell::from(format!("{v:<}")),
                 ])
             })
@@ -208,7 +500,7 @@ impl FileMetadata {
         let table_width = key_width + value_width + 3;
         let table_height = rows.len() as u16;
         let center_x = area.x + (area.width.saturating_sub(table_width)) / 2;
-        let center_y = area.y + (area.height.saturating_sub(table_height)) / 2;
+        let center_y = area.y + (area.height.saturating_sub(table_height + 2)) / 2;
 
         // Clamp to area bounds so the stats box never bleeds into the properties panel.
         let proposed = Rect {
@@ -219,6 +511,24 @@ impl FileMetadata {
         };
         let centered_area = proposed.intersection(area);
 
+        let mut block = Block::bordered()
+            .title(Line::from("File Metadata".fg(theme.accent).bold()).centered())
+            .border_set(border::ROUNDED)
+            .border_style(theme.border_style());
+        if let Some(footer) = self.footer.as_ref().filter(|f| f.is_bloated()) {
+            block = block.title_bottom(
+                Line::from(
+                    format!(
+                        " ⚠ metadata is {:.0}% of the file ",
+                        footer.footer_fraction() * 100.0
+                    )
+                    .fg(theme.bad)
+                    .bold(),
+                )
+                .centered(),
+            );
+        }
+
         let table = Table::new(
             rows,
             vec![
@@ -226,15 +536,11 @@ impl FileMetadata {
                 Constraint::Length(value_width),
             ],
         )
-        .block(
-            Block::bordered()
-                .title(Line::from("File Metadata".yellow().bold()).centered())
-                .border_set(border::ROUNDED),
-        );
+        .block(block);
         table.render(centered_area, buf);
     }
 
-    fn render_properties(&self, area: Rect, buf: &mut Buffer, scroll: usize) {
+    fn render_properties(&self, area: Rect, buf: &mut Buffer, scroll: usize, theme: &Theme) {
         let props = &self.key_value_metadata;
         if props.is_empty() {
             return;
@@ -249,7 +555,7 @@ impl FileMetadata {
             if i > 0 {
                 lines.push(Line::from(""));
             }
-            lines.push(Line::from(Span::from(key.as_str()).bold().fg(Color::Blue)));
+            lines.push(Line::from(Span::from(key.as_str()).bold().fg(theme.label)));
 
             let expanded = if let Ok(json_val) = serde_json::from_str::<serde_json::Value>(value) {
                 serde_json::to_string_pretty(&json_val).unwrap_or_else(|_| value.clone())
@@ -278,6 +584,7 @@ impl FileMetadata {
 
         if needs_scrollbar {
             ScrollbarComponent::vertical(total_lines, visible_height, start)
+                .with_theme(theme)
                 .render(scrollbar_area, buf);
         }
 
@@ -288,26 +595,29 @@ impl FileMetadata {
                     .title(
                         Line::from(
                             Span::from(format!("Properties ({})", props.len()))
-                                .yellow()
+                                .fg(theme.accent)
                                 .bold(),
                         )
                         .centered(),
                     )
-                    .border_set(border::ROUNDED),
+                    .border_set(border::ROUNDED)
+                    .border_style(theme.border_style()),
             )
             .render(para_area, buf);
     }
 }
 
+#[cfg(feature = "tui")]
 impl Renderable for FileMetadata {
     fn render_content(&self, area: Rect, buf: &mut Buffer) {
-        self.render_with_scroll(area, buf, 0);
+        self.render_with_scroll(area, buf, 0, &Theme::default());
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::file::test_data_path;
     use parquet::file::reader::{FileReader, SerializedFileReader};
     use std::fs::File;
 
@@ -387,6 +697,19 @@ mod tests {
         assert_eq!("UNCOMPRESSED(11)", file_metadata.codecs);
     }
 
+    #[cfg(feature = "tui")]
+    #[test]
+    fn test_summary() {
+        let file_metadata = load_alltypes_metadata();
+        assert_eq!(file_metadata.codec_names, vec!["UNCOMPRESSED".to_string()]);
+        let summary = file_metadata.summary();
+        assert!(
+            summary.starts_with("8 rows · 11 cols · 1 row group · "),
+            "{summary}"
+        );
+        assert!(summary.ends_with(" · UNCOMPRESSED"), "{summary}");
+    }
+
     #[test]
     fn test_encodings() {
         let file_metadata = load_alltypes_metadata();
@@ -411,6 +734,29 @@ mod tests {
         assert_eq!(83_u64, file_metadata.avg_row_size);
     }
 
+    #[test]
+    fn test_footer_info() {
+        let path = test_data_path("alltypes_plain.parquet");
+        let mut file = File::open(&path).unwrap();
+        let footer_size = FooterInfo::read_footer_size(&mut file).unwrap();
+        let file_size = file.metadata().unwrap().len();
+        let reader = SerializedFileReader::try_from(file).unwrap();
+        let footer = FooterInfo::new(reader.metadata(), footer_size, file_size);
+
+        assert!(footer.footer_size > 0 && footer.footer_size < file_size);
+        // Root plus 11 primitive columns.
+        assert_eq!(footer.schema_elements, 12);
+        assert_eq!(footer.statistics_sizes.len(), 11);
+        // A 1.9 KiB footer on a 2.7 KiB file is mostly metadata.
+        assert!(footer.is_bloated());
+    }
+
+    #[test]
+    fn test_read_footer_size_rejects_non_parquet() {
+        let mut data = std::io::Cursor::new(b"not a parquet file".to_vec());
+        assert!(FooterInfo::read_footer_size(&mut data).is_err());
+    }
+
     #[test]
     fn test_from_metadata_error_handling() {
         // Test that from_metadata returns Ok for valid files
@@ -427,6 +773,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "tui")]
     fn test_renderable_trait() {
         let file_metadata = load_alltypes_metadata();
 
@@ -1,15 +1,56 @@
+pub mod analyzer;
+pub mod bench;
+pub mod blob;
+pub mod bloom;
+pub mod codecs;
+pub mod column_filter;
+pub mod correlation;
+pub mod deletes;
+pub mod dictionary;
+pub mod diff;
+pub mod distinct;
+pub mod duplicates;
+pub mod encoding_hint;
 pub mod error;
+pub mod export;
+pub mod extract;
+pub mod generate;
+pub mod geo;
+pub mod json_path;
+pub mod lint;
+pub mod logical;
 pub mod metadata;
+pub mod offsets;
+pub mod page_header;
+pub mod page_values;
 pub mod parquet_ctx;
+pub mod profile;
+pub mod progress;
+pub mod pruning;
+pub mod report;
+pub mod rewrite;
+pub mod row_count;
+pub mod row_group_filter;
 pub mod row_groups;
 pub mod sample_data;
 pub mod schema;
+pub mod schema_check;
+pub mod search;
+pub mod snippet;
+pub mod summary;
+pub mod temporal;
+pub mod utf8;
 pub mod utils;
+pub mod variant;
+pub mod views;
+pub mod writer;
 
 use std::{env, error::Error, path::PathBuf};
 
+#[cfg(feature = "tui")]
 use ratatui::{buffer::Buffer, layout::Rect};
 
+#[cfg(feature = "tui")]
 pub trait Renderable {
     fn render_content(&self, area: Rect, buf: &mut Buffer);
 }
@@ -22,6 +63,29 @@ pub fn parquet_test_data() -> String {
     }
 }
 
+/// Path of `filename` in the parquet-testing data directory.
+#[cfg(test)]
+pub(crate) fn test_data_path(filename: &str) -> String {
+    format!("{}/{}", parquet_test_data(), filename)
+}
+
+/// Write `batch` to `parqeye-{name}-{pid}.parquet` in the temp directory,
+/// with `props` if given, and return its path. Tests remove the file once
+/// they've read it.
+#[cfg(test)]
+pub(crate) fn write_test_file(
+    name: &str,
+    batch: &arrow::array::RecordBatch,
+    props: Option<parquet::file::properties::WriterProperties>,
+) -> PathBuf {
+    let path = env::temp_dir().join(format!("parqeye-{name}-{}.parquet", std::process::id()));
+    let file = std::fs::File::create(&path).unwrap();
+    let mut writer = parquet::arrow::ArrowWriter::try_new(file, batch.schema(), props).unwrap();
+    writer.write(batch).unwrap();
+    writer.close().unwrap();
+    path
+}
+
 /// From Arrow Crate
 /// Returns a directory path for finding test data.
 ///
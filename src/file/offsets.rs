@@ -0,0 +1,406 @@
+use std::fmt;
+
+use parquet::file::metadata::ParquetMetaData;
+use serde::Serialize;
+
+use crate::file::error::FileIOError;
+use crate::file::parquet_ctx::ParquetCtx;
+
+/// Where a writer put one column chunk and its pages.
+#[derive(Debug, Clone, PartialEq)]
+struct ChunkLayout {
+    column: String,
+    /// Start and length of the chunk, dictionary page included.
+    start: u64,
+    len: u64,
+    dictionary_page_offset: Option<u64>,
+    data_page_offset: u64,
+    /// Offset, type and total length (header included) of each page as
+    /// decoded from the file; `None` if the headers could not be read.
+    pages: Option<Vec<(u64, bool, u64)>>,
+    /// Offset and length of each page from the offset index, if any.
+    page_locations: Vec<(u64, u64)>,
+}
+
+impl ChunkLayout {
+    fn end(&self) -> u64 {
+        self.start + self.len
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct RowGroupLayout {
+    file_offset: Option<u64>,
+    chunks: Vec<ChunkLayout>,
+}
+
+/// One inconsistency in the byte layout of the file.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OffsetProblem {
+    /// Numbered from 0.
+    pub row_group: usize,
+    /// `None` for problems with the row group as a whole.
+    pub column: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for OffsetProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.column {
+            Some(column) => write!(
+                f,
+                "row group {}, {column}: {}",
+                self.row_group, self.message
+            ),
+            None => write!(f, "row group {}: {}", self.row_group, self.message),
+        }
+    }
+}
+
+/// Checks that column chunks and pages follow each other without
+/// overlapping, and that the offsets in the footer point where the data
+/// is. Readers differ in which offsets they trust, so a writer bug here
+/// breaks some readers and not others.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OffsetReport {
+    pub path: String,
+    pub problems: Vec<OffsetProblem>,
+}
+
+impl OffsetReport {
+    pub fn from_file(path: &str) -> Result<Self, FileIOError> {
+        Ok(Self::from_ctx(&ParquetCtx::from_file(path)?))
+    }
+
+    pub fn from_ctx(ctx: &ParquetCtx) -> Self {
+        // The page index, when the file has one, has offsets of its own.
+        let md = match ctx.reader.with_page_index() {
+            Ok(reader) => reader.metadata_ptr(),
+            Err(_) => ctx.reader.metadata_ptr(),
+        };
+        let footer_start = ctx
+            .metadata
+            .footer
+            .as_ref()
+            .map_or(ctx.file_size, |footer| {
+                ctx.file_size.saturating_sub(footer.footer_size + 8)
+            });
+        Self {
+            path: ctx.file_path.clone(),
+            problems: check(&layout(ctx, &md), footer_start),
+        }
+    }
+}
+
+fn layout(ctx: &ParquetCtx, md: &ParquetMetaData) -> Vec<RowGroupLayout> {
+    md.row_groups()
+        .iter()
+        .enumerate()
+        .map(|(rg, row_group)| RowGroupLayout {
+            file_offset: row_group.file_offset().map(|offset| offset.max(0) as u64),
+            chunks: row_group
+                .columns()
+                .iter()
+                .enumerate()
+                .map(|(col, chunk)| {
+                    let (start, len) = chunk.byte_range();
+                    let headers = ctx
+                        .row_groups
+                        .row_groups
+                        .get(rg)
+                        .and_then(|rg| rg.column_metadata.get(col))
+                        .map(|c| &c.page_headers)
+                        .filter(|headers| headers.error.is_none());
+                    let page_locations = md
+                        .offset_index()
+                        .and_then(|index| index.get(rg)?.get(col))
+                        .map(|index| {
+                            index
+                                .page_locations()
+                                .iter()
+                                .map(|page| {
+                                    (
+                                        page.offset.max(0) as u64,
+                                        page.compressed_page_size.max(0) as u64,
+                                    )
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    ChunkLayout {
+                        column: chunk.column_path().string(),
+                        start,
+                        len,
+                        dictionary_page_offset: chunk
+                            .dictionary_page_offset()
+                            .map(|offset| offset.max(0) as u64),
+                        data_page_offset: chunk.data_page_offset().max(0) as u64,
+                        pages: headers.map(|headers| {
+                            headers
+                                .headers
+                                .iter()
+                                .map(|page| {
+                                    (
+                                        page.offset,
+                                        page.page_type.starts_with("Data Page"),
+                                        page.header_size as u64
+                                            + page.compressed_size.max(0) as u64,
+                                    )
+                                })
+                                .collect()
+                        }),
+                        page_locations,
+                    }
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Every inconsistency in `row_groups`, in file order. Data must end
+/// before `footer_start`.
+fn check(row_groups: &[RowGroupLayout], footer_start: u64) -> Vec<OffsetProblem> {
+    let mut problems = Vec::new();
+    for (rg, row_group) in row_groups.iter().enumerate() {
+        let mut problem = |column: Option<&str>, message: String| {
+            problems.push(OffsetProblem {
+                row_group: rg,
+                column: column.map(str::to_string),
+                message,
+            })
+        };
+
+        let first = row_group.chunks.iter().min_by_key(|chunk| chunk.start);
+        if let (Some(offset), Some(first)) = (row_group.file_offset, first)
+            && offset != first.start
+        {
+            let message = match first.dictionary_page_offset {
+                Some(dictionary) if offset == first.data_page_offset => format!(
+                    "file_offset {offset} points at the first data page of {} instead of its dictionary page at {dictionary}",
+                    first.column
+                ),
+                _ => format!(
+                    "file_offset {offset} but the first column chunk starts at {}",
+                    first.start
+                ),
+            };
+            problem(None, message);
+        }
+
+        for (idx, chunk) in row_group.chunks.iter().enumerate() {
+            let column = Some(chunk.column.as_str());
+            if let Some(previous) = idx.checked_sub(1).map(|i| &row_group.chunks[i])
+                && chunk.start < previous.start
+            {
+                problem(
+                    column,
+                    format!(
+                        "starts at {}, before the previous column {} at {}",
+                        chunk.start, previous.column, previous.start
+                    ),
+                );
+            }
+            if chunk.end() > footer_start {
+                problem(
+                    column,
+                    format!(
+                        "ends at {}, inside the footer starting at {footer_start}",
+                        chunk.end()
+                    ),
+                );
+            }
+            match chunk.dictionary_page_offset {
+                Some(dictionary) if dictionary >= chunk.data_page_offset => problem(
+                    column,
+                    format!(
+                        "dictionary page at {dictionary} is not before the first data page at {}",
+                        chunk.data_page_offset
+                    ),
+                ),
+                _ => {}
+            }
+
+            if let Some(pages) = &chunk.pages {
+                match pages.iter().find(|(_, data, _)| *data) {
+                    Some(&(offset, _, _)) if offset != chunk.data_page_offset => problem(
+                        column,
+                        format!(
+                            "data_page_offset {} but the first data page is at {offset}",
+                            chunk.data_page_offset
+                        ),
+                    ),
+                    _ => {}
+                }
+                if chunk.dictionary_page_offset.is_some()
+                    && pages.first().is_none_or(|&(_, data, _)| data)
+                {
+                    problem(
+                        column,
+                        "has a dictionary_page_offset but no dictionary page".to_string(),
+                    );
+                }
+                if let Some(&(offset, _, len)) = pages.last()
+                    && offset + len > chunk.end()
+                {
+                    problem(
+                        column,
+                        format!(
+                            "its last page ends at {}, {} bytes past the end of the chunk",
+                            offset + len,
+                            offset + len - chunk.end()
+                        ),
+                    );
+                }
+            }
+
+            let mut previous_end = chunk.start;
+            for (page, &(offset, len)) in chunk.page_locations.iter().enumerate() {
+                if offset < previous_end {
+                    problem(
+                        column,
+                        format!(
+                            "offset index puts page {page} at {offset}, before the previous page ends at {previous_end}"
+                        ),
+                    );
+                } else if offset + len > chunk.end() {
+                    problem(
+                        column,
+                        format!(
+                            "offset index puts page {page} at {offset}..{}, past the end of the chunk at {}",
+                            offset + len,
+                            chunk.end()
+                        ),
+                    );
+                }
+                previous_end = previous_end.max(offset + len);
+            }
+        }
+    }
+
+    // Chunks of different columns or row groups sharing bytes.
+    let mut chunks: Vec<(usize, &ChunkLayout)> = row_groups
+        .iter()
+        .enumerate()
+        .flat_map(|(rg, row_group)| row_group.chunks.iter().map(move |chunk| (rg, chunk)))
+        .filter(|(_, chunk)| chunk.len > 0)
+        .collect();
+    chunks.sort_by_key(|(_, chunk)| chunk.start);
+    let mut furthest: Option<(usize, &ChunkLayout)> = None;
+    for (rg, chunk) in chunks {
+        if let Some((other_rg, other)) = furthest
+            && chunk.start < other.end()
+        {
+            problems.push(OffsetProblem {
+                row_group: rg,
+                column: Some(chunk.column.clone()),
+                message: format!(
+                    "bytes {}..{} overlap {} of row group {other_rg}, which ends at {}",
+                    chunk.start,
+                    chunk.end(),
+                    other.column,
+                    other.end()
+                ),
+            });
+        }
+        if furthest.is_none_or(|(_, other)| chunk.end() > other.end()) {
+            furthest = Some((rg, chunk));
+        }
+    }
+    problems
+}
+
+impl fmt::Display for OffsetReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.problems.is_empty() {
+            return write!(
+                f,
+                "{}: column chunk and page offsets are consistent",
+                self.path
+            );
+        }
+        write!(
+            f,
+            "{}: {} offset problem{}",
+            self.path,
+            self.problems.len(),
+            if self.problems.len() == 1 { "" } else { "s" }
+        )?;
+        for problem in &self.problems {
+            write!(f, "\n  {problem}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_data_path;
+
+    /// A chunk of `len` bytes at `start` with a 10-byte dictionary page and
+    /// one data page.
+    fn chunk(column: &str, start: u64, len: u64) -> ChunkLayout {
+        ChunkLayout {
+            column: column.to_string(),
+            start,
+            len,
+            dictionary_page_offset: Some(start),
+            data_page_offset: start + 10,
+            pages: Some(vec![(start, false, 10), (start + 10, true, len - 10)]),
+            page_locations: vec![(start + 10, len - 10)],
+        }
+    }
+
+    fn messages(problems: &[OffsetProblem]) -> Vec<String> {
+        problems.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_consistent_files() {
+        for file in ["alltypes_plain.parquet", "nulls.snappy.parquet"] {
+            let report = OffsetReport::from_file(&test_data_path(file)).unwrap();
+            assert_eq!(report.problems, [], "{file}");
+        }
+        let row_groups = [RowGroupLayout {
+            file_offset: Some(4),
+            chunks: vec![chunk("a", 4, 100), chunk("b", 104, 50)],
+        }];
+        assert_eq!(check(&row_groups, 200), []);
+    }
+
+    #[test]
+    fn test_bad_offsets() {
+        let mut a = chunk("a", 4, 100);
+        // Overlaps the end of a.
+        let b = chunk("b", 90, 50);
+        let mut c = chunk("c", 200, 50);
+        c.data_page_offset = 220;
+        c.page_locations = vec![(210, 30), (230, 10), (245, 10)];
+        a.pages = Some(vec![(4, false, 10), (14, true, 95)]);
+        let row_groups = [
+            RowGroupLayout {
+                // Where the dictionary page should have been.
+                file_offset: Some(14),
+                chunks: vec![a, b],
+            },
+            RowGroupLayout {
+                file_offset: Some(200),
+                chunks: vec![c, chunk("d", 150, 40)],
+            },
+        ];
+        assert_eq!(
+            messages(&check(&row_groups, 240)),
+            [
+                "row group 0: file_offset 14 points at the first data page of a instead of its dictionary page at 4",
+                "row group 0, a: its last page ends at 109, 5 bytes past the end of the chunk",
+                "row group 1: file_offset 200 but the first column chunk starts at 150",
+                "row group 1, c: ends at 250, inside the footer starting at 240",
+                "row group 1, c: data_page_offset 220 but the first data page is at 210",
+                "row group 1, c: offset index puts page 1 at 230, before the previous page ends at 240",
+                "row group 1, c: offset index puts page 2 at 245..255, past the end of the chunk at 250",
+                "row group 1, d: starts at 150, before the previous column c at 200",
+                "row group 0, b: bytes 90..140 overlap a of row group 0, which ends at 104",
+            ]
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use std::io::Cursor;
+
+use parquet::file::metadata::ColumnChunkMetaData;
+use parquet::file::reader::ChunkReader;
+use parquet_format::{Encoding, PageHeader, PageType, Statistics};
+use serde::Serialize;
+
+use crate::file::row_groups::DataPageV2Info;
+use thrift::protocol::TCompactInputProtocol;
+use thrift::{Error as ThriftError, TransportErrorKind};
+
+/// Bytes read for a page header at first. Headers are usually a few dozen
+/// bytes, but statistics with long min/max values make them longer, in
+/// which case the read is retried with a bigger window.
+const HEADER_WINDOW: u64 = 256;
+
+/// The thrift header of one page, as written in the file.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PageHeaderInfo {
+    /// Where the header starts in the file.
+    pub offset: u64,
+    /// Length of the encoded header, which is not part of the page sizes.
+    pub header_size: usize,
+    pub page_type: String,
+    pub compressed_size: i32,
+    pub uncompressed_size: i32,
+    /// Values in the page, including nulls. `None` for index pages.
+    pub num_values: Option<i32>,
+    pub encoding: Option<String>,
+    pub definition_level_encoding: Option<String>,
+    pub repetition_level_encoding: Option<String>,
+    pub statistics: Option<PageHeaderStats>,
+    pub crc: Option<u32>,
+    /// Fields only V2 data page headers have.
+    pub v2: Option<DataPageV2Info>,
+}
+
+/// Statistics a writer put in a data page header.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PageHeaderStats {
+    pub null_count: Option<i64>,
+    pub distinct_count: Option<i64>,
+    /// Whether min and max values are set. They are plain-encoded bytes of
+    /// the column's physical type.
+    pub has_min_max: bool,
+}
+
+/// The headers of a column chunk's pages, in file order.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PageHeaders {
+    pub headers: Vec<PageHeaderInfo>,
+    /// Why decoding stopped early, e.g. an encrypted or truncated chunk.
+    /// `headers` holds the headers decoded before it.
+    pub error: Option<String>,
+}
+
+impl PageHeaders {
+    /// Walk the chunk from header to header, skipping the page data.
+    pub fn read<R: ChunkReader>(file: &R, chunk: &ColumnChunkMetaData) -> Self {
+        let (start, len) = chunk.byte_range();
+        let end = start + len;
+        let mut headers = Vec::new();
+        let mut offset = start;
+        while offset < end {
+            match read_header(file, offset, end - offset) {
+                Ok((header, header_size)) => {
+                    let info = PageHeaderInfo::new(offset, header_size, &header);
+                    offset += header_size as u64 + header.compressed_page_size.max(0) as u64;
+                    headers.push(info);
+                }
+                Err(error) => {
+                    return Self {
+                        headers,
+                        error: Some(error),
+                    };
+                }
+            }
+        }
+        Self {
+            headers,
+            error: None,
+        }
+    }
+}
+
+/// Decode the header at `offset`, returning it with its encoded length.
+fn read_header<R: ChunkReader>(
+    file: &R,
+    offset: u64,
+    remaining: u64,
+) -> Result<(PageHeader, usize), String> {
+    let mut window = HEADER_WINDOW.min(remaining);
+    loop {
+        let bytes = file
+            .get_bytes(offset, window as usize)
+            .map_err(|e| e.to_string())?;
+        let mut cursor = Cursor::new(bytes.as_ref());
+        match PageHeader::read_from_in_protocol(&mut TCompactInputProtocol::new(&mut cursor)) {
+            Ok(header) => return Ok((header, cursor.position() as usize)),
+            Err(ThriftError::Transport(e))
+                if e.kind == TransportErrorKind::EndOfFile && window < remaining =>
+            {
+                window = (window * 4).min(remaining);
+            }
+            Err(e) => return Err(format!("invalid page header at offset {offset}: {e}")),
+        }
+    }
+}
+
+impl PageHeaderInfo {
+    fn new(offset: u64, header_size: usize, header: &PageHeader) -> Self {
+        let mut info = Self {
+            offset,
+            header_size,
+            page_type: page_type_name(header.type_).to_string(),
+            compressed_size: header.compressed_page_size,
+            uncompressed_size: header.uncompressed_page_size,
+            num_values: None,
+            encoding: None,
+            definition_level_encoding: None,
+            repetition_level_encoding: None,
+            statistics: None,
+            // Thrift has no unsigned ints, so writers store the CRC's bits
+            // in an i32.
+            crc: header.crc.map(|crc| crc as u32),
+            v2: None,
+        };
+        if let Some(data) = &header.data_page_header {
+            info.num_values = Some(data.num_values);
+            info.encoding = Some(encoding_name(data.encoding));
+            info.definition_level_encoding = Some(encoding_name(data.definition_level_encoding));
+            info.repetition_level_encoding = Some(encoding_name(data.repetition_level_encoding));
+            info.statistics = data.statistics.as_ref().map(PageHeaderStats::new);
+        } else if let Some(data) = &header.data_page_header_v2 {
+            info.num_values = Some(data.num_values);
+            info.encoding = Some(encoding_name(data.encoding));
+            info.statistics = data.statistics.as_ref().map(PageHeaderStats::new);
+            // V2 headers have no level encodings; levels are always RLE.
+            info.v2 = Some(DataPageV2Info {
+                num_nulls: data.num_nulls.max(0) as u32,
+                num_rows: data.num_rows.max(0) as u32,
+                def_levels_byte_len: data.definition_levels_byte_length.max(0) as u32,
+                rep_levels_byte_len: data.repetition_levels_byte_length.max(0) as u32,
+                // Missing means compressed, per the format.
+                is_compressed: data.is_compressed.unwrap_or(true),
+            });
+        } else if let Some(dictionary) = &header.dictionary_page_header {
+            info.num_values = Some(dictionary.num_values);
+            info.encoding = Some(encoding_name(dictionary.encoding));
+        }
+        info
+    }
+}
+
+impl PageHeaderStats {
+    fn new(stats: &Statistics) -> Self {
+        Self {
+            null_count: stats.null_count,
+            distinct_count: stats.distinct_count,
+            has_min_max: (stats.min_value.is_some() && stats.max_value.is_some())
+                || (stats.min.is_some() && stats.max.is_some()),
+        }
+    }
+}
+
+/// Same names as the pages list uses.
+fn page_type_name(page_type: PageType) -> &'static str {
+    match page_type {
+        PageType::DataPage => "Data Page",
+        PageType::IndexPage => "Index Page",
+        PageType::DictionaryPage => "Dictionary Page",
+        PageType::DataPageV2 => "Data Page V2",
+    }
+}
+
+fn encoding_name(encoding: Encoding) -> String {
+    match encoding {
+        Encoding::Plain => "Plain".to_string(),
+        Encoding::PlainDictionary => "Plain Dictionary".to_string(),
+        Encoding::Rle => "RLE".to_string(),
+        Encoding::BitPacked => "Bit Packed".to_string(),
+        Encoding::DeltaBinaryPacked => "Delta Binary Packed".to_string(),
+        Encoding::DeltaLengthByteArray => "Delta Length Byte Array".to_string(),
+        Encoding::DeltaByteArray => "Delta Byte Array".to_string(),
+        Encoding::RleDictionary => "RLE Dictionary".to_string(),
+        Encoding::ByteStreamSplit => "Byte Stream Split".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::parquet_ctx::open_reader;
+    use crate::file::{test_data_path, write_test_file};
+    use parquet::file::reader::FileReader;
+
+    #[test]
+    fn test_page_headers_walk_the_chunk() {
+        let path = test_data_path("alltypes_plain.parquet");
+        let (reader, _) = open_reader(&path).unwrap();
+        let md = reader.metadata();
+        for rg in md.row_groups() {
+            for chunk in rg.columns() {
+                let pages = PageHeaders::read(reader.source(), chunk);
+                assert_eq!(pages.error, None);
+                // The headers and pages they skip cover the chunk exactly.
+                let covered: u64 = pages
+                    .headers
+                    .iter()
+                    .map(|h| h.header_size as u64 + h.compressed_size as u64)
+                    .sum();
+                assert_eq!(covered, chunk.byte_range().1);
+                let values: i32 = pages
+                    .headers
+                    .iter()
+                    .filter(|h| h.page_type.starts_with("Data Page"))
+                    .filter_map(|h| h.num_values)
+                    .sum();
+                assert_eq!(values as i64, chunk.num_values());
+            }
+        }
+    }
+
+    #[test]
+    fn test_v2_headers_keep_their_own_fields() {
+        use crate::file::row_groups::RowGroups;
+        use arrow::array::{Int64Array, RecordBatch};
+        use parquet::file::properties::{WriterProperties, WriterVersion};
+        use std::sync::Arc;
+
+        // One V2 data page of 100 rows, every fourth one null.
+        let values = Int64Array::from_iter((0..100).map(|i| (i % 4 != 0).then_some(i)));
+        let batch = RecordBatch::try_from_iter([("id", Arc::new(values) as _)]).unwrap();
+        let props = WriterProperties::builder()
+            .set_writer_version(WriterVersion::PARQUET_2_0)
+            .set_dictionary_enabled(false)
+            .build();
+        let path = write_test_file("v2", &batch, Some(props));
+        let (reader, _) = open_reader(&path.display().to_string()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let chunk = reader.metadata().row_group(0).column(0);
+        let pages = PageHeaders::read(reader.source(), chunk);
+        let header = &pages.headers[0];
+        assert_eq!(header.page_type, "Data Page V2");
+        assert_eq!(header.definition_level_encoding, None);
+        let v2 = header.v2.unwrap();
+        assert_eq!((v2.num_rows, v2.num_nulls), (100, 25));
+        assert!(v2.def_levels_byte_len > 0);
+        assert_eq!(v2.rep_levels_byte_len, 0);
+
+        // The pages read through the page reader carry the same fields.
+        let row_groups = RowGroups::from_file_reader(&reader).unwrap();
+        let page = &row_groups.row_groups[0].column_metadata[0].pages.page_infos[0];
+        assert_eq!(page.v2, Some(v2));
+        assert_eq!(page.rows, 100);
+    }
+
+    #[test]
+    fn test_bad_header_is_reported() {
+        let bytes = bytes::Bytes::from_static(&[0xff; 64]);
+        assert!(read_header(&bytes, 0, 64).is_err());
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::VecDeque;
+
+use parquet::basic::PageType;
+use parquet::column::page::{Page, PageMetadata, PageReader};
+use parquet::column::reader::{ColumnReader, ColumnReaderImpl, get_column_reader};
+use parquet::data_type::{AsBytes, DataType};
+use parquet::errors::Result;
+use parquet::file::reader::FileReader;
+use parquet::schema::types::ColumnDescPtr;
+
+use crate::file::schema::decode_logical_value;
+
+/// Values previewed per data page.
+pub const PREVIEW_VALUES: usize = 3;
+
+/// Pages previewed per column chunk, more than the Pages table shows at
+/// once.
+pub const PREVIEW_PAGES: usize = 100;
+
+/// The first values of a data page, or why they couldn't be decoded.
+pub type PagePreview = Result<Vec<String>, String>;
+
+/// One preview per page of a column chunk; `None` for dictionary and index
+/// pages.
+pub type ChunkPreviews = Vec<Option<PagePreview>>;
+
+/// Previews of the first `max_pages` pages of the chunk of column `column`
+/// in row group `row_group`, in page order; `None` for dictionary and index
+/// pages. Pages past one that fails to read are left out.
+pub fn chunk_previews<R: FileReader>(
+    reader: &R,
+    row_group: usize,
+    column: usize,
+    max_pages: usize,
+) -> std::result::Result<ChunkPreviews, String> {
+    let rg = reader.get_row_group(row_group).map_err(|e| e.to_string())?;
+    if column >= rg.num_columns() {
+        return Err(format!("no column {column} in row group {row_group}"));
+    }
+    let descr = rg.metadata().column(column).column_descr_ptr();
+    let pages = rg
+        .get_column_page_reader(column)
+        .map_err(|e| e.to_string())?;
+    let mut dictionary = None;
+    let mut previews = Vec::new();
+    for page in pages.take(max_pages).map_while(|page| page.ok()) {
+        match page.page_type() {
+            PageType::DATA_PAGE | PageType::DATA_PAGE_V2 => previews.push(Some(
+                preview_values(&descr, dictionary.as_ref(), &page, PREVIEW_VALUES)
+                    .map_err(|e| e.to_string()),
+            )),
+            PageType::DICTIONARY_PAGE => {
+                dictionary = Some(page);
+                previews.push(None);
+            }
+            _ => previews.push(None),
+        }
+    }
+    Ok(previews)
+}
+
+/// The first `limit` values of a data page, nulls included, decoded with the
+/// chunk's `dictionary` page if it has one. The pages go through the parquet
+/// column reader, so every encoding it reads works: PLAIN, the dictionary
+/// ones, BYTE_STREAM_SPLIT and DELTA_BINARY_PACKED, DELTA_LENGTH_BYTE_ARRAY
+/// and DELTA_BYTE_ARRAY.
+pub fn preview_values(
+    descr: &ColumnDescPtr,
+    dictionary: Option<&Page>,
+    page: &Page,
+    limit: usize,
+) -> Result<Vec<String>> {
+    let pages = dictionary.into_iter().chain([page]).cloned().collect();
+    let reader = get_column_reader(descr.clone(), Box::new(Pages(pages)));
+    let physical = descr.physical_type();
+    let logical = descr.logical_type_ref();
+    let max_def = descr.max_def_level();
+    let bytes = |value: &[u8]| decode_logical_value(value, physical, logical);
+    match reader {
+        ColumnReader::BoolColumnReader(r) => read(r, limit, max_def, |v| v.to_string()),
+        ColumnReader::Int32ColumnReader(r) => read(r, limit, max_def, |v| bytes(&v.to_le_bytes())),
+        ColumnReader::Int64ColumnReader(r) => read(r, limit, max_def, |v| bytes(&v.to_le_bytes())),
+        ColumnReader::Int96ColumnReader(r) => read(r, limit, max_def, |v| v.to_string()),
+        ColumnReader::FloatColumnReader(r) => read(r, limit, max_def, |v| bytes(&v.to_le_bytes())),
+        ColumnReader::DoubleColumnReader(r) => read(r, limit, max_def, |v| bytes(&v.to_le_bytes())),
+        ColumnReader::ByteArrayColumnReader(r) => read(r, limit, max_def, |v| bytes(v.as_bytes())),
+        ColumnReader::FixedLenByteArrayColumnReader(r) => {
+            read(r, limit, max_def, |v| bytes(v.as_bytes()))
+        }
+    }
+}
+
+/// Read the first `limit` values, placing "null" where the definition level
+/// says a value is missing.
+fn read<T: DataType>(
+    mut reader: ColumnReaderImpl<T>,
+    limit: usize,
+    max_def: i16,
+    format: impl Fn(&T::T) -> String,
+) -> Result<Vec<String>> {
+    let (mut defs, mut reps, mut values) = (Vec::new(), Vec::new(), Vec::new());
+    reader.read_records(limit, Some(&mut defs), Some(&mut reps), &mut values)?;
+    if max_def == 0 {
+        return Ok(values.iter().take(limit).map(format).collect());
+    }
+    let mut values = values.iter();
+    Ok(defs
+        .iter()
+        .take(limit)
+        .map(|&def| match def == max_def {
+            true => values.next().map_or_else(|| "null".to_string(), &format),
+            false => "null".to_string(),
+        })
+        .collect())
+}
+
+/// Hands the column reader a dictionary page and one data page.
+struct Pages(VecDeque<Page>);
+
+impl Iterator for Pages {
+    type Item = Result<Page>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.get_next_page().transpose()
+    }
+}
+
+impl PageReader for Pages {
+    fn get_next_page(&mut self) -> Result<Option<Page>> {
+        Ok(self.0.pop_front())
+    }
+
+    fn peek_next_page(&mut self) -> Result<Option<PageMetadata>> {
+        Ok(self.0.front().map(|page| PageMetadata {
+            num_rows: None,
+            num_levels: Some(page.num_values() as usize),
+            is_dict: matches!(page, Page::DictionaryPage { .. }),
+        }))
+    }
+
+    fn skip_next_page(&mut self) -> Result<()> {
+        self.0.pop_front();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::parquet_ctx::open_reader;
+    use crate::file::row_groups::RowGroups;
+    use crate::file::write_test_file;
+    use arrow::array::{Float64Array, Int64Array, RecordBatch, StringArray};
+    use parquet::basic::Encoding;
+    use parquet::file::properties::WriterProperties;
+    use parquet::schema::types::ColumnPath;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_preview_newer_encodings() {
+        let ids = Int64Array::from_iter_values(1_000..1_100);
+        let readings = Float64Array::from_iter_values((0..100).map(|i| i as f64 * 0.5));
+        let names = StringArray::from_iter_values((0..100).map(|i| format!("user-{i:03}")));
+        let urls = StringArray::from_iter_values((0..100).map(|i| format!("https://x.io/{i}")));
+        let kinds = StringArray::from_iter((0..100).map(|i| (i % 3 != 1).then_some("click")));
+        let batch = RecordBatch::try_from_iter([
+            ("id", Arc::new(ids) as _),
+            ("reading", Arc::new(readings) as _),
+            ("name", Arc::new(names) as _),
+            ("url", Arc::new(urls) as _),
+            ("kind", Arc::new(kinds) as _),
+        ])
+        .unwrap();
+        let mut props = WriterProperties::builder();
+        for (column, encoding) in [
+            ("id", Encoding::DELTA_BINARY_PACKED),
+            ("reading", Encoding::BYTE_STREAM_SPLIT),
+            ("name", Encoding::DELTA_BYTE_ARRAY),
+            ("url", Encoding::DELTA_LENGTH_BYTE_ARRAY),
+        ] {
+            let column = ColumnPath::from(column);
+            props = props
+                .set_column_dictionary_enabled(column.clone(), false)
+                .set_column_encoding(column, encoding);
+        }
+        let path = write_test_file("encodings", &batch, Some(props.build()));
+        let (reader, _) = open_reader(&path.display().to_string()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let first_data_page = |col: usize| {
+            let row_groups = RowGroups::from_file_reader(&reader).unwrap();
+            let pages = &row_groups.row_groups[0].column_metadata[col]
+                .pages
+                .page_infos;
+            let previews = chunk_previews(&reader, 0, col, PREVIEW_PAGES).unwrap();
+            assert_eq!(previews.len(), pages.len());
+            let idx = pages.iter().position(|page| page.is_data_page()).unwrap();
+            (
+                pages[idx].encoding.clone(),
+                previews[idx].clone().unwrap().unwrap(),
+            )
+        };
+        let expect = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            first_data_page(0),
+            (
+                "Delta Binary Packed".into(),
+                expect(&["1000", "1001", "1002"])
+            )
+        );
+        assert_eq!(
+            first_data_page(1),
+            (
+                "Byte Stream Split".into(),
+                expect(&["0.0000", "0.5000", "1.0000"])
+            )
+        );
+        assert_eq!(
+            first_data_page(2),
+            (
+                "Delta Byte Array".into(),
+                expect(&["user-000", "user-001", "user-002"])
+            )
+        );
+        assert_eq!(
+            first_data_page(3),
+            (
+                "Delta Length Byte Array".into(),
+                expect(&["https://x.io/0", "https://x.io/1", "https://x.io/2"])
+            )
+        );
+        // Dictionary indices resolve through the dictionary page, which has
+        // no preview of its own.
+        assert!(chunk_previews(&reader, 0, 4, 1).unwrap()[0].is_none());
+        assert!(chunk_previews(&reader, 0, 5, 1).is_err());
+        assert_eq!(
+            first_data_page(4),
+            ("RLE Dictionary".into(), expect(&["click", "null", "click"]))
+        );
+    }
+}
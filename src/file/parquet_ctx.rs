@@ -1,38 +1,53 @@
-use parquet::file::reader::{FileReader, SerializedFileReader};
+use bytes::Bytes;
+use parquet::errors::{ParquetError, Result as ParquetResult};
+use parquet::file::metadata::{PageIndexPolicy, ParquetMetaData, ParquetMetaDataReader};
+use parquet::file::properties::{ReaderProperties, ReaderPropertiesPtr};
+use parquet::file::reader::{ChunkReader, FileReader, Length, RowGroupReader};
+use parquet::file::serialized_reader::SerializedRowGroupReader;
+use parquet::record::reader::RowIter;
+use parquet::schema::types::Type as SchemaType;
 use std::fs::File;
+use std::io::{self, BufReader, Read};
 use std::path::PathBuf;
+use std::sync::Arc;
 
+use crate::file::deletes::DeleteInfo;
 use crate::file::error::FileIOError;
-use crate::file::metadata::FileMetadata;
+use crate::file::metadata::{FileMetadata, FooterInfo};
 use crate::file::row_groups::RowGroups;
-use crate::file::sample_data::ParquetSampleData;
+use crate::file::sample_data::{ParquetSampleData, PreviewOptions};
 use crate::file::schema::FileSchema;
+use serde::Serialize;
+
+/// Everything parqeye reads from a file: metadata, schema, row group and page
+/// details, and the first rows.
+#[derive(Serialize)]
 pub struct ParquetCtx {
     pub file_path: String,
+    /// Size of the file on disk, in bytes.
+    pub file_size: u64,
     pub metadata: FileMetadata,
     pub row_groups: RowGroups,
     pub schema: FileSchema,
     pub sample_data: ParquetSampleData,
+    /// Why the rows could not be read; `sample_data` is empty when set.
+    pub sample_data_error: Option<String>,
+    /// The open file, for anything that reads it after it is loaded.
+    #[serde(skip)]
+    pub reader: SharedReader,
 }
 
 impl ParquetCtx {
     pub fn from_file(file_path: &str) -> Result<ParquetCtx, FileIOError> {
-        let path = PathBuf::from(file_path);
-
-        let file = File::open(&path).map_err(|e| match e.kind() {
-            std::io::ErrorKind::NotFound => FileIOError::FileNotFound { path: path.clone() },
-            std::io::ErrorKind::PermissionDenied => {
-                FileIOError::PermissionDenied { path: path.clone() }
-            }
-            _ => FileIOError::Io { source: e },
-        })?;
-
-        let reader: SerializedFileReader<File> =
-            SerializedFileReader::new(file).map_err(|e| FileIOError::InvalidParquet {
-                path: path.clone(),
-                details: e.to_string(),
-            })?;
+        Self::from_file_with(file_path, &PreviewOptions::default())
+    }
 
+    /// Like [`ParquetCtx::from_file`], reading the first rows with `preview`.
+    pub fn from_file_with(
+        file_path: &str,
+        preview: &PreviewOptions,
+    ) -> Result<ParquetCtx, FileIOError> {
+        let (reader, file_size) = open_reader(file_path)?;
         let md = reader.metadata();
 
         let row_groups =
@@ -40,41 +55,239 @@ impl ParquetCtx {
                 details: format!("Failed to read row groups: {e}"),
             })?;
 
-        let metadata = FileMetadata::from_metadata(md).map_err(|e| FileIOError::MetadataError {
-            details: format!("Failed to read file metadata: {e}"),
-        })?;
+        let mut metadata =
+            FileMetadata::from_metadata(md).map_err(|e| FileIOError::MetadataError {
+                details: format!("Failed to read file metadata: {e}"),
+            })?;
+        // The reader already validated the footer, so this only fails on I/O.
+        if let Ok(footer_size) = FooterInfo::read_footer_size(&mut reader.file()) {
+            metadata = metadata.with_footer(FooterInfo::new(md, footer_size, file_size));
+        }
+        // Table metadata that can't be read only costs the live row count.
+        if let Ok(Some(deletes)) = DeleteInfo::for_file(file_path, metadata.num_rows as u64) {
+            metadata = metadata.with_deletes(deletes);
+        }
 
         let schema = FileSchema::from_metadata(md).map_err(|e| FileIOError::MetadataError {
             details: format!("Failed to parse schema: {e}"),
         })?;
 
-        let sample_data = ParquetSampleData::read_sample_data(file_path).map_err(|e| {
-            FileIOError::SampleDataError {
-                details: e.to_string(),
-            }
-        })?;
+        // Unreadable rows should not hide the metadata, so keep going.
+        let (sample_data, sample_data_error) =
+            match ParquetSampleData::read_sample(file_path, preview) {
+                Ok(sample_data) => (sample_data, None),
+                Err(e) => (
+                    ParquetSampleData::empty(),
+                    Some(
+                        FileIOError::SampleDataError {
+                            details: e.to_string(),
+                        }
+                        .to_string(),
+                    ),
+                ),
+            };
 
         Ok(ParquetCtx {
             file_path: file_path.to_string(),
+            file_size,
             metadata,
             row_groups,
             schema,
             sample_data,
+            sample_data_error,
+            reader,
         })
     }
 
+    /// Sections of the file that could not be read, one line each.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems: Vec<String> = self.sample_data_error.iter().cloned().collect();
+        let page_errors = self.row_groups.page_errors();
+        if page_errors > 0 {
+            problems.push(format!(
+                "pages of {page_errors} column chunk(s) could not be read"
+            ));
+        }
+        problems
+    }
+
     pub fn column_size(&self) -> usize {
         self.schema.column_size()
     }
 }
 
+/// An open file and its parsed footer. Clones share both, so everything
+/// that reads the file after it is opened (distinct estimates, profiles,
+/// bloom filter probes, pruning) neither opens it nor parses the footer
+/// again. The row preview is read by polars, which does its own I/O.
+#[derive(Clone)]
+pub struct SharedReader {
+    file: Arc<SharedFile>,
+    metadata: Arc<ParquetMetaData>,
+    props: ReaderPropertiesPtr,
+}
+
+impl SharedReader {
+    pub fn file(&self) -> &File {
+        &self.file.0
+    }
+
+    /// The file, for reading byte ranges of it from any thread.
+    pub fn source(&self) -> &SharedFile {
+        &self.file
+    }
+
+    /// The parsed footer, for readers that take it by `Arc`.
+    pub fn metadata_ptr(&self) -> Arc<ParquetMetaData> {
+        Arc::clone(&self.metadata)
+    }
+
+    /// A reader whose row groups also load their bloom filters.
+    pub fn with_bloom_filters(&self) -> Self {
+        Self {
+            props: Arc::new(
+                ReaderProperties::builder()
+                    .set_read_bloom_filter(true)
+                    .build(),
+            ),
+            ..self.clone()
+        }
+    }
+
+    /// A reader whose metadata includes the page index, read without parsing
+    /// the footer again. Fails if the file has no page index.
+    pub fn with_page_index(&self) -> ParquetResult<Self> {
+        let mut reader = ParquetMetaDataReader::new_with_metadata((*self.metadata).clone())
+            .with_page_index_policy(PageIndexPolicy::Required);
+        reader.read_page_indexes(&*self.file)?;
+        Ok(Self {
+            metadata: Arc::new(reader.finish()?),
+            ..self.clone()
+        })
+    }
+}
+
+impl FileReader for SharedReader {
+    fn metadata(&self) -> &ParquetMetaData {
+        &self.metadata
+    }
+
+    fn num_row_groups(&self) -> usize {
+        self.metadata.num_row_groups()
+    }
+
+    fn get_row_group(&self, i: usize) -> ParquetResult<Box<dyn RowGroupReader + '_>> {
+        Ok(Box::new(SerializedRowGroupReader::new(
+            Arc::clone(&self.file),
+            self.metadata.row_group(i),
+            self.metadata.offset_index().map(|x| x[i].as_slice()),
+            Arc::clone(&self.props),
+        )?))
+    }
+
+    fn get_row_iter(&self, projection: Option<SchemaType>) -> ParquetResult<RowIter<'_>> {
+        RowIter::from_file(projection, self)
+    }
+}
+
+/// A file read with positional reads, so any number of threads can read it
+/// at once. `File`'s own `ChunkReader` seeks handles that share one offset,
+/// which races when the pages of several chunks are read in parallel.
+pub struct SharedFile(File);
+
+fn read_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+    #[cfg(unix)]
+    return std::os::unix::fs::FileExt::read_at(file, buf, offset);
+    #[cfg(windows)]
+    return std::os::windows::fs::FileExt::seek_read(file, buf, offset);
+}
+
+impl Length for SharedFile {
+    fn len(&self) -> u64 {
+        self.0.metadata().map_or(0, |md| md.len())
+    }
+}
+
+impl ChunkReader for SharedFile {
+    type T = BufReader<PositionalRead>;
+
+    fn get_read(&self, start: u64) -> ParquetResult<Self::T> {
+        Ok(BufReader::new(PositionalRead {
+            file: self.0.try_clone()?,
+            offset: start,
+        }))
+    }
+
+    fn get_bytes(&self, start: u64, length: usize) -> ParquetResult<Bytes> {
+        let mut buffer = vec![0; length];
+        let mut read = 0;
+        while read < length {
+            match read_at(&self.0, &mut buffer[read..], start + read as u64)? {
+                0 => {
+                    return Err(ParquetError::EOF(format!(
+                        "Expected to read {length} bytes, read only {read}"
+                    )));
+                }
+                n => read += n,
+            }
+        }
+        Ok(buffer.into())
+    }
+}
+
+/// Reads a [`SharedFile`] from an offset onwards without moving the offset
+/// of the handle.
+pub struct PositionalRead {
+    file: File,
+    offset: u64,
+}
+
+impl Read for PositionalRead {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = read_at(&self.file, buf, self.offset)?;
+        self.offset += read as u64;
+        Ok(read)
+    }
+}
+
+/// Open `file_path` and read its footer, returning the reader and the file
+/// size in bytes.
+pub fn open_reader(file_path: &str) -> Result<(SharedReader, u64), FileIOError> {
+    let path = PathBuf::from(file_path);
+
+    let file = File::open(&path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => FileIOError::FileNotFound { path: path.clone() },
+        std::io::ErrorKind::PermissionDenied => {
+            FileIOError::PermissionDenied { path: path.clone() }
+        }
+        _ => FileIOError::Io { source: e },
+    })?;
+
+    let file_size = file
+        .metadata()
+        .map_err(|e| FileIOError::Io { source: e })?
+        .len();
+
+    let metadata = ParquetMetaDataReader::new()
+        .parse_and_finish(&file)
+        .map_err(|e| FileIOError::InvalidParquet {
+            path: path.clone(),
+            details: e.to_string(),
+        })?;
+    let reader = SharedReader {
+        file: Arc::new(SharedFile(file)),
+        metadata: Arc::new(metadata),
+        props: Arc::new(ReaderProperties::builder().build()),
+    };
+
+    Ok((reader, file_size))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    fn test_data_path(filename: &str) -> String {
-        format!("{}/{}", crate::file::parquet_test_data(), filename)
-    }
+    use crate::file::sample_data::DateStyle;
+    use crate::file::{test_data_path, write_test_file};
 
     #[test]
     fn test_file_not_found() {
@@ -121,6 +334,25 @@ mod tests {
         assert!(result.is_ok(), "Expected Ok, got: {:?}", result.err());
     }
 
+    #[test]
+    fn test_shared_reader_reads_without_reopening() {
+        let path = test_data_path("nulls.snappy.parquet");
+        let ctx = ParquetCtx::from_file(&path).unwrap();
+        let reader = ctx.reader.with_bloom_filters();
+        // Clones share the parsed footer.
+        assert!(Arc::ptr_eq(
+            &reader.metadata_ptr(),
+            &ctx.reader.metadata_ptr()
+        ));
+        assert_eq!(reader.num_row_groups(), ctx.row_groups.num_row_groups());
+
+        let row_group = reader.get_row_group(0).unwrap();
+        let mut pages = row_group.get_column_page_reader(0).unwrap();
+        assert!(pages.get_next_page().unwrap().is_some());
+        let rows = reader.get_row_iter(None).unwrap().count();
+        assert_eq!(rows, ctx.metadata.num_rows);
+    }
+
     #[test]
     fn test_file_not_found_error_contains_path() {
         let path = test_data_path("nonexistent.parquet");
@@ -156,4 +388,443 @@ mod tests {
         let result = ParquetCtx::from_file(&path);
         assert!(result.is_err(), "Expected error for corrupt parquet file");
     }
+
+    #[test]
+    fn test_readable_file_has_no_problems() {
+        let path = test_data_path("alltypes_plain.parquet");
+        let ctx = ParquetCtx::from_file(&path).unwrap();
+        assert!(ctx.sample_data_error.is_none());
+        assert!(ctx.problems().is_empty());
+    }
+
+    #[test]
+    fn test_serializes_to_json() {
+        let path = test_data_path("alltypes_plain.parquet");
+        let ctx = ParquetCtx::from_file(&path).unwrap();
+        let json = serde_json::to_value(&ctx).unwrap();
+        assert_eq!(json["metadata"]["num_rows"], 8);
+        assert_eq!(json["schema"]["columns"][0]["kind"], "root");
+        assert!(json["schema"]["columns"][1].get("display").is_none());
+        assert_eq!(
+            json["row_groups"]["row_groups"][0]["column_metadata"]
+                .as_array()
+                .map(Vec::len),
+            Some(11)
+        );
+    }
+
+    #[test]
+    fn test_preview_options() {
+        let path = test_data_path("alltypes_plain.parquet");
+        let options = PreviewOptions {
+            max_rows: 3,
+            dates: DateStyle::Epoch,
+            ..Default::default()
+        };
+        let ctx = ParquetCtx::from_file_with(&path, &options).unwrap();
+        assert_eq!(ctx.sample_data.total_rows, 3);
+        let col = ctx
+            .sample_data
+            .flattened_columns
+            .iter()
+            .position(|c| c == "timestamp_col")
+            .unwrap();
+        // 2009-03-01 00:00:00 in nanoseconds.
+        assert_eq!(ctx.sample_data.rows[0][col], "1235865600000000000");
+
+        let sample = ParquetSampleData::read_sample_data(&path).unwrap();
+        assert_eq!(sample.rows[0][col], "2009-03-01 00:00:00");
+    }
+
+    #[test]
+    fn test_read_window_starts_at_row() {
+        let path = test_data_path("alltypes_plain.parquet");
+        let sample = ParquetSampleData::read_sample_data(&path).unwrap();
+        let window = ParquetSampleData::read_window(&path, 2).unwrap();
+        assert_eq!(sample.first_row, 0);
+        assert_eq!(window.first_row, 2);
+        assert_eq!(window.rows[0], sample.rows[2]);
+    }
+
+    #[test]
+    fn test_sampling() {
+        use crate::file::sample_data::Sampling;
+        use arrow::array::{Int64Array, RecordBatch};
+        use parquet::file::properties::WriterProperties;
+        use std::sync::Arc;
+
+        // Three row groups of 10 rows, each row holding its position.
+        let batch = RecordBatch::try_from_iter([(
+            "id",
+            Arc::new(Int64Array::from_iter_values(0..30)) as _,
+        )])
+        .unwrap();
+        let props = WriterProperties::builder()
+            .set_max_row_group_row_count(Some(10))
+            .build();
+        let path = write_test_file("sampling", &batch, Some(props));
+
+        let file_path = path.display().to_string();
+        let read = |sampling| {
+            let options = PreviewOptions {
+                max_rows: 6,
+                sampling,
+                ..Default::default()
+            };
+            let sample = ParquetSampleData::read_sample(&file_path, &options).unwrap();
+            let ids: Vec<usize> = sample
+                .rows
+                .iter()
+                .map(|row| row[0].parse().unwrap())
+                .collect();
+            let positions: Vec<usize> = (0..sample.total_rows)
+                .map(|row| sample.position(row))
+                .collect();
+            // Every row shows the value written at its position.
+            assert_eq!(ids, positions);
+            (ids, sample.positions.is_some())
+        };
+        let ctx = ParquetCtx::from_file_with(
+            &file_path,
+            &PreviewOptions {
+                max_rows: 6,
+                sampling: Sampling::Tail,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let random = read(Sampling::Random(7));
+        let results = [
+            read(Sampling::Head),
+            read(Sampling::Tail),
+            read(Sampling::Every(0)),
+            read(Sampling::Every(7)),
+            read(Sampling::RowGroups),
+        ];
+        let other_seed = read(Sampling::Random(8));
+        assert_eq!(read(Sampling::Random(7)), random);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(ctx.sample_data.first_row, 24);
+        assert_eq!(results[0], (vec![0, 1, 2, 3, 4, 5], false));
+        assert_eq!(results[1], (vec![24, 25, 26, 27, 28, 29], false));
+        assert_eq!(results[2], (vec![0, 5, 10, 15, 20, 25], true));
+        assert_eq!(results[3], (vec![0, 7, 14, 21, 28], true));
+        assert_eq!(results[4], (vec![0, 1, 10, 11, 20, 21], true));
+        assert_eq!(random.0.len(), 6);
+        assert!(random.0.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_ne!(random.0, other_seed.0);
+
+        assert_eq!("every:7".parse(), Ok(Sampling::Every(7)));
+        assert_eq!(Sampling::Random(42).to_string(), "random:42");
+        assert_eq!(Sampling::RowGroups.next(), Sampling::Head);
+        assert_eq!(Sampling::Every(3).next(), Sampling::Random(0));
+    }
+
+    #[test]
+    fn test_geometry_columns() {
+        use parquet::basic::{GeometryType, LogicalType, Repetition, Type as PhysicalType};
+        use parquet::data_type::{ByteArray, ByteArrayType};
+        use parquet::file::metadata::KeyValue;
+        use parquet::file::properties::WriterProperties;
+        use parquet::file::writer::SerializedFileWriter;
+        use parquet::schema::types::Type;
+        use std::sync::Arc;
+
+        // A GEOMETRY column whose CRS is PROJJSON in the key-value metadata,
+        // holding POINT (1 2), a null and POINT (3 4) starting on row 1.
+        let geom = Type::primitive_type_builder("geom", PhysicalType::BYTE_ARRAY)
+            .with_repetition(Repetition::OPTIONAL)
+            .with_logical_type(Some(LogicalType::Geometry(GeometryType {
+                crs: Some("projjson:geom_crs".to_string()),
+            })))
+            .build()
+            .unwrap();
+        let schema = Type::group_type_builder("schema")
+            .with_fields(vec![Arc::new(geom)])
+            .build()
+            .unwrap();
+        let projjson = r#"{"name": "WGS 84", "id": {"authority": "EPSG", "code": 4326}}"#;
+        let props = WriterProperties::builder()
+            .set_key_value_metadata(Some(vec![KeyValue::new(
+                "geom_crs".to_string(),
+                projjson.to_string(),
+            )]))
+            .build();
+        let point = |x: f64, y: f64| {
+            let mut wkb = vec![1, 1, 0, 0, 0];
+            wkb.extend(x.to_le_bytes());
+            wkb.extend(y.to_le_bytes());
+            ByteArray::from(wkb)
+        };
+        let path =
+            std::env::temp_dir().join(format!("parqeye-geometry-{}.parquet", std::process::id()));
+        let file = File::create(&path).unwrap();
+        let mut writer =
+            SerializedFileWriter::new(file, Arc::new(schema), Arc::new(props)).unwrap();
+        let mut row_group = writer.next_row_group().unwrap();
+        let mut column = row_group.next_column().unwrap().unwrap();
+        column
+            .typed::<ByteArrayType>()
+            .write_batch(&[point(1.0, 2.0), point(3.0, 4.0)], Some(&[1, 0, 1]), None)
+            .unwrap();
+        column.close().unwrap();
+        row_group.close().unwrap();
+        writer.close().unwrap();
+
+        let file_path = path.display().to_string();
+        let ctx = ParquetCtx::from_file(&file_path).unwrap();
+        let window = ParquetSampleData::read_window(&file_path, 1).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let crate::file::schema::SchemaInfo::Primitive { info, .. } = &ctx.schema.columns[1] else {
+            panic!("geom is a leaf");
+        };
+        assert_eq!(info.logical, "Geometry(WGS 84 (EPSG:4326))");
+        // polars can't read the footer, so the rows come from arrow-rs.
+        assert_eq!(ctx.sample_data.flattened_columns, ["geom"]);
+        let values: Vec<&str> = ctx.sample_data.rows.iter().map(|r| r[0].as_str()).collect();
+        assert_eq!(values, ["POINT (1 2)", "NULL", "POINT (3 4)"]);
+        assert_eq!(window.rows, [["NULL"], ["POINT (3 4)"]]);
+    }
+
+    #[test]
+    fn test_float16_uuid_json_and_bson_values() {
+        use crate::file::schema::SchemaInfo;
+        use parquet::data_type::{
+            ByteArray, ByteArrayType, FixedLenByteArray, FixedLenByteArrayType,
+        };
+        use parquet::file::writer::SerializedFileWriter;
+        use parquet::schema::parser::parse_message_type;
+        use std::sync::Arc;
+
+        let schema = parse_message_type(
+            "message schema {
+                optional fixed_len_byte_array(2) half (FLOAT16);
+                optional fixed_len_byte_array(16) id (UUID);
+                optional binary doc (JSON);
+                optional binary raw (BSON);
+            }",
+        )
+        .unwrap();
+        let uuid: Vec<u8> = (0..16).map(|i| i * 17).collect();
+        // {"n": 1}
+        let bson = vec![12, 0, 0, 0, 0x10, b'n', 0, 1, 0, 0, 0, 0];
+        let path =
+            std::env::temp_dir().join(format!("parqeye-logical-{}.parquet", std::process::id()));
+        let file = File::create(&path).unwrap();
+        let mut writer =
+            SerializedFileWriter::new(file, Arc::new(schema), Default::default()).unwrap();
+        let mut row_group = writer.next_row_group().unwrap();
+        let mut column = row_group.next_column().unwrap().unwrap();
+        // 1.0 and -2.0
+        let halves = [vec![0x00, 0x3c], vec![0x00, 0xc0]].map(FixedLenByteArray::from);
+        column
+            .typed::<FixedLenByteArrayType>()
+            .write_batch(&halves, Some(&[1, 1]), None)
+            .unwrap();
+        column.close().unwrap();
+        let mut column = row_group.next_column().unwrap().unwrap();
+        column
+            .typed::<FixedLenByteArrayType>()
+            .write_batch(&[FixedLenByteArray::from(uuid)], Some(&[1, 0]), None)
+            .unwrap();
+        column.close().unwrap();
+        let mut column = row_group.next_column().unwrap().unwrap();
+        column
+            .typed::<ByteArrayType>()
+            .write_batch(&[ByteArray::from(r#"{"a":[1,2]}"#)], Some(&[0, 1]), None)
+            .unwrap();
+        column.close().unwrap();
+        let mut column = row_group.next_column().unwrap().unwrap();
+        column
+            .typed::<ByteArrayType>()
+            .write_batch(&[ByteArray::from(bson)], Some(&[1, 0]), None)
+            .unwrap();
+        column.close().unwrap();
+        row_group.close().unwrap();
+        writer.close().unwrap();
+
+        let ctx = ParquetCtx::from_file(&path.display().to_string()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let stats: Vec<(Option<&str>, Option<&str>)> = ctx
+            .schema
+            .columns
+            .iter()
+            .filter_map(|column| match column {
+                SchemaInfo::Primitive { stats, .. } => {
+                    Some((stats.min.as_deref(), stats.max.as_deref()))
+                }
+                _ => None,
+            })
+            .collect();
+        let uuid_text = "00112233-4455-6677-8899-aabbccddeeff";
+        assert_eq!(stats[0], (Some("-2"), Some("1")));
+        assert_eq!(stats[1], (Some(uuid_text), Some(uuid_text)));
+        assert_eq!(
+            stats[2],
+            (Some(r#"{"a": [1, 2]}"#), Some(r#"{"a": [1, 2]}"#))
+        );
+        assert_eq!(stats[3], (Some(r#"{"n": 1}"#), Some(r#"{"n": 1}"#)));
+
+        let row_group_stats = ctx.row_groups.row_groups[0].column_metadata[1]
+            .statistics
+            .as_ref()
+            .unwrap();
+        assert_eq!(row_group_stats.min.as_deref(), Some(uuid_text));
+
+        assert_eq!(
+            ctx.sample_data.rows,
+            [
+                ["1", uuid_text, "NULL", r#"{"n": 1}"#],
+                ["-2", "NULL", r#"{"a": [1, 2]}"#, "NULL"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shredded_variant_column() {
+        use crate::file::schema::SchemaInfo;
+        use crate::file::variant::Shredding;
+        use parquet::basic::{LogicalType, Repetition};
+        use parquet::data_type::{ByteArray, ByteArrayType, Int64Type};
+        use parquet::file::writer::SerializedFileWriter;
+        use parquet::schema::parser::parse_message_type;
+        use parquet::schema::types::Type;
+        use std::sync::Arc;
+
+        // The message parser doesn't know VARIANT, so annotate the group
+        // after parsing it.
+        let parsed = parse_message_type(
+            "message schema {
+                optional group v {
+                    required binary metadata;
+                    optional binary value;
+                    optional group typed_value {
+                        required group a {
+                            optional binary value;
+                            optional int64 typed_value;
+                        }
+                        required group b {
+                            optional binary value;
+                            optional binary typed_value (STRING);
+                        }
+                    }
+                }
+            }",
+        )
+        .unwrap();
+        let variant = Type::group_type_builder("v")
+            .with_repetition(Repetition::OPTIONAL)
+            .with_logical_type(Some(LogicalType::variant(Some(1))))
+            .with_fields(parsed.get_fields()[0].get_fields().to_vec())
+            .build()
+            .unwrap();
+        let schema = Type::group_type_builder("schema")
+            .with_fields(vec![Arc::new(variant)])
+            .build()
+            .unwrap();
+
+        // The rows {"a": 1, "b": "x"}, {"a": "str", "c": true}, 42 and null:
+        // the second has `a` of another type and `c` left unshredded, the
+        // third isn't an object at all.
+        let metadata = ByteArray::from(vec![0x11, 3, 0, 1, 2, 3, b'a', b'b', b'c']);
+        let columns: [(Vec<ByteArray>, Vec<i64>, [i16; 4]); 6] = [
+            (
+                vec![metadata.clone(), metadata.clone(), metadata],
+                vec![],
+                [1, 1, 1, 0],
+            ),
+            (
+                vec![
+                    ByteArray::from(vec![0x02, 1, 2, 0, 1, 0x04]),
+                    ByteArray::from(vec![0x0C, 42]),
+                ],
+                vec![],
+                [1, 2, 2, 0],
+            ),
+            (
+                vec![ByteArray::from(vec![0x0D, b's', b't', b'r'])],
+                vec![],
+                [2, 3, 1, 0],
+            ),
+            (vec![], vec![1], [3, 2, 1, 0]),
+            (vec![], vec![], [2, 2, 1, 0]),
+            (vec![ByteArray::from("x")], vec![], [3, 2, 1, 0]),
+        ];
+        let path =
+            std::env::temp_dir().join(format!("parqeye-variant-{}.parquet", std::process::id()));
+        let file = File::create(&path).unwrap();
+        let mut writer =
+            SerializedFileWriter::new(file, Arc::new(schema), Default::default()).unwrap();
+        let mut row_group = writer.next_row_group().unwrap();
+        for (bytes, ints, def_levels) in columns {
+            let mut column = row_group.next_column().unwrap().unwrap();
+            if ints.is_empty() {
+                column
+                    .typed::<ByteArrayType>()
+                    .write_batch(&bytes, Some(&def_levels), None)
+                    .unwrap();
+            } else {
+                column
+                    .typed::<Int64Type>()
+                    .write_batch(&ints, Some(&def_levels), None)
+                    .unwrap();
+            }
+            column.close().unwrap();
+        }
+        row_group.close().unwrap();
+        writer.close().unwrap();
+
+        let ctx = ParquetCtx::from_file(&path.display().to_string()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let rows: Vec<&str> = ctx.sample_data.rows.iter().map(|r| r[0].as_str()).collect();
+        assert_eq!(
+            rows,
+            [
+                r#"{"a": 1, "b": "x"}"#,
+                r#"{"a": "str", "c": true}"#,
+                "42",
+                "NULL"
+            ]
+        );
+
+        let groups: Vec<(String, String, Option<Shredding>)> = ctx
+            .schema
+            .columns
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, column)| match column {
+                SchemaInfo::Group {
+                    logical, shredding, ..
+                } => Some((ctx.schema.column_path(idx), logical.clone(), *shredding)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            groups,
+            [
+                ("v".to_string(), "Variant".to_string(), None),
+                ("v.typed_value".to_string(), String::new(), None),
+                (
+                    "v.typed_value.a".to_string(),
+                    String::new(),
+                    Some(Shredding {
+                        typed: 1,
+                        fallback: 1
+                    })
+                ),
+                (
+                    "v.typed_value.b".to_string(),
+                    String::new(),
+                    Some(Shredding {
+                        typed: 1,
+                        fallback: 0
+                    })
+                ),
+            ]
+        );
+    }
 }
@@ -0,0 +1,1239 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use parquet::basic::{ConvertedType, LogicalType, Repetition, Type as PhysicalType};
+use parquet::column::reader::{ColumnReader, ColumnReaderImpl};
+use parquet::data_type::{AsBytes, DataType};
+use parquet::file::reader::FileReader;
+use parquet::schema::types::{ColumnDescriptor, SchemaDescriptor};
+use serde::Serialize;
+
+use crate::file::distinct::HyperLogLog;
+use crate::file::encoding_hint::{EncodingHint, RunDeltaStats};
+use crate::file::error::FileIOError;
+use crate::file::parquet_ctx::open_reader;
+use crate::file::progress::ScanProgress;
+use crate::file::schema::{decode_logical_value, logical_type_to_string};
+use crate::file::utils::{commas, html_escape};
+
+/// Records decoded per `read_records` call.
+const READ_BATCH: usize = 8192;
+
+/// How much of the file to read and how many frequent values to keep.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProfileOptions {
+    /// Rows read per column, spread evenly over the row groups.
+    pub sample_rows: usize,
+    /// Most frequent values kept for string columns.
+    pub top_k: usize,
+}
+
+impl Default for ProfileOptions {
+    fn default() -> Self {
+        Self {
+            sample_rows: 100_000,
+            top_k: 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValueCount {
+    pub value: String,
+    pub count: u64,
+}
+
+/// Floats that compare oddly, counted in FLOAT and DOUBLE columns.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct FloatSpecials {
+    pub nan: u64,
+    pub pos_inf: u64,
+    pub neg_inf: u64,
+    /// `-0.0`, which equals `0.0` but sorts before it.
+    pub neg_zero: u64,
+}
+
+impl FloatSpecials {
+    fn add(&mut self, x: f64) {
+        if x.is_nan() {
+            self.nan += 1;
+        } else if x == f64::INFINITY {
+            self.pos_inf += 1;
+        } else if x == f64::NEG_INFINITY {
+            self.neg_inf += 1;
+        } else if x == 0.0 && x.is_sign_negative() {
+            self.neg_zero += 1;
+        }
+    }
+}
+
+/// Why NaNs in a column matter for its footer statistics.
+pub const NAN_STATS_NOTE: &str = "NaN is unordered, so writers leave it out of the footer \
+    min/max: a row group holding NaNs looks as if it had none, and a filter such as `x > 0` \
+    or `x IS NaN` can prune it. Older writers sometimes stored NaN as the min or max, which \
+    makes readers ignore the statistics altogether.";
+
+/// `2 NaN, 1 -Inf`; `none` when there are no special values.
+pub fn format_specials(specials: &FloatSpecials) -> String {
+    let parts: Vec<String> = [
+        (specials.nan, "NaN"),
+        (specials.pos_inf, "+Inf"),
+        (specials.neg_inf, "-Inf"),
+        (specials.neg_zero, "-0.0"),
+    ]
+    .into_iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, name)| format!("{} {name}", commas(count)))
+    .collect();
+    if parts.is_empty() {
+        "none".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+/// What a sample of one leaf column looks like. Nested leaves count list
+/// elements rather than rows.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ColumnProfile {
+    pub column: String,
+    pub physical: String,
+    pub logical: String,
+    /// Values read, nulls included.
+    pub sampled: u64,
+    pub nulls: u64,
+    pub null_percent: f64,
+    /// HyperLogLog estimate over the non-null values read.
+    pub distinct: u64,
+    pub min: Option<String>,
+    pub max: Option<String>,
+    /// Only for plain numeric columns.
+    pub mean: Option<f64>,
+    pub stddev: Option<f64>,
+    /// Only for string columns.
+    pub top_values: Vec<ValueCount>,
+    /// Average length in bytes, only for string columns.
+    pub avg_length: Option<f64>,
+    /// Only for FLOAT and DOUBLE columns. NaNs are left out of `min`,
+    /// `max`, `mean` and `stddev`.
+    pub specials: Option<FloatSpecials>,
+    /// Uncompressed over compressed size of the whole column, from the
+    /// footer.
+    pub ratio: Option<f64>,
+    /// Shannon entropy of the bytes of the values read, in bits per byte.
+    /// Close to 8 the bytes look random (hashes, encrypted or already
+    /// compressed data) and no codec will shrink them.
+    pub entropy: Option<f64>,
+    /// Runs and deltas of the values read, only for plain numeric columns.
+    pub encoding_hint: Option<EncodingHint>,
+}
+
+/// Entropy in bits per byte from which values look random.
+pub const RANDOM_ENTROPY: f64 = 7.5;
+
+/// Entropy as `7.92 bits/byte`.
+pub fn format_entropy(entropy: Option<f64>) -> String {
+    entropy.map_or("-".to_string(), |e| format!("{e:.2} bits/byte"))
+}
+
+/// Shannon entropy of bytes counted by value, in bits per byte.
+fn byte_entropy(counts: &[u64; 256]) -> Option<f64> {
+    let total: u64 = counts.iter().sum();
+    (total > 0).then(|| {
+        counts
+            .iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let p = count as f64 / total as f64;
+                -p * p.log2()
+            })
+            .sum::<f64>()
+            // Keep a constant column at 0 rather than -0.
+            .max(0.0)
+    })
+}
+
+/// How long the lists or maps of one list or map column are, from the
+/// levels of the first leaf below it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NestedProfile {
+    pub column: String,
+    /// `List` or `Map`.
+    pub kind: String,
+    /// Non-null lists or maps read.
+    pub count: u64,
+    pub min_length: Option<u64>,
+    pub avg_length: Option<f64>,
+    pub max_length: Option<u64>,
+    /// HyperLogLog estimate over the keys read, only for maps.
+    pub distinct_keys: Option<u64>,
+}
+
+/// Per-column profiles of a file from a sample of its rows.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileProfile {
+    pub path: String,
+    pub total_rows: u64,
+    /// Rows read per column, at most `ProfileOptions::sample_rows`.
+    pub sampled_rows: u64,
+    pub columns: Vec<ColumnProfile>,
+    /// List and map columns, outermost first.
+    pub nested: Vec<NestedProfile>,
+}
+
+impl FileProfile {
+    /// Columns in which the sample found NaNs.
+    pub fn nan_columns(&self) -> Vec<&str> {
+        self.columns
+            .iter()
+            .filter(|c| c.specials.is_some_and(|s| s.nan > 0))
+            .map(|c| c.column.as_str())
+            .collect()
+    }
+
+    /// Profile of the list or map column at dotted path `column`.
+    pub fn nested(&self, column: &str) -> Option<&NestedProfile> {
+        self.nested.iter().find(|nested| nested.column == column)
+    }
+
+    /// Profile of the innermost list or map holding the leaf at `column`.
+    pub fn enclosing_nested(&self, column: &str) -> Option<&NestedProfile> {
+        self.nested
+            .iter()
+            .filter(|nested| {
+                column
+                    .strip_prefix(&nested.column)
+                    .is_some_and(|rest| rest.starts_with('.'))
+            })
+            .max_by_key(|nested| nested.column.len())
+    }
+}
+
+/// A repeated field above a leaf column.
+#[derive(Debug, Clone, PartialEq)]
+struct Repeated {
+    /// Dotted path of the list or map: the LIST or MAP group around the
+    /// repeated field, or the field itself when it has none.
+    column: String,
+    map: bool,
+    /// Repetition level of the field.
+    rep: i16,
+    /// Definition level at which an element is present.
+    def: i16,
+    /// Definition level at which the list is present, possibly empty.
+    parent_def: i16,
+    /// Whether the leaf is the key of this map.
+    key: bool,
+}
+
+/// The repeated fields above leaf `descr`, outermost first.
+fn repeated_ancestors(schema: &SchemaDescriptor, descr: &ColumnDescriptor) -> Vec<Repeated> {
+    let parts = descr.path().parts();
+    let mut repeated = vec![];
+    let mut node = schema.root_schema();
+    let (mut def, mut rep) = (0, 0);
+    for (i, part) in parts.iter().enumerate() {
+        let Some(child) = node.get_fields().iter().find(|field| field.name() == part) else {
+            break;
+        };
+        let info = child.get_basic_info();
+        let parent_def = def;
+        if info.has_repetition() && info.repetition() != Repetition::REQUIRED {
+            def += 1;
+        }
+        if info.has_repetition() && info.repetition() == Repetition::REPEATED {
+            rep += 1;
+            let parent = node.get_basic_info();
+            let annotated = |logical: fn(&LogicalType) -> bool, converted: &[ConvertedType]| {
+                parent.logical_type_ref().is_some_and(logical)
+                    || converted.contains(&parent.converted_type())
+            };
+            let in_list = annotated(|l| matches!(l, LogicalType::List), &[ConvertedType::LIST]);
+            let in_map = annotated(
+                |l| matches!(l, LogicalType::Map),
+                &[ConvertedType::MAP, ConvertedType::MAP_KEY_VALUE],
+            );
+            let map = in_map || info.converted_type() == ConvertedType::MAP_KEY_VALUE;
+            let end = if in_list || in_map { i } else { i + 1 };
+            repeated.push(Repeated {
+                column: parts[..end].join("."),
+                map,
+                rep,
+                def,
+                parent_def,
+                key: map
+                    && i + 2 == parts.len()
+                    && child.is_group()
+                    && child.get_fields().first().map(|f| f.name()) == Some(parts[i + 1].as_str()),
+            });
+        }
+        node = child;
+    }
+    repeated
+}
+
+/// Lengths of the lists at one repetition level, from the levels of a leaf
+/// below them.
+struct Lengths {
+    repeated: Repeated,
+    /// Elements of the list being read so far.
+    current: Option<u64>,
+    count: u64,
+    total: u64,
+    min: Option<u64>,
+    max: Option<u64>,
+}
+
+impl Lengths {
+    fn new(repeated: Repeated) -> Self {
+        Self {
+            repeated,
+            current: None,
+            count: 0,
+            total: 0,
+            min: None,
+            max: None,
+        }
+    }
+
+    fn add(&mut self, def: i16, rep: i16) {
+        // A lower repetition level starts a new list, if it is present.
+        if rep < self.repeated.rep {
+            self.close();
+            if def >= self.repeated.parent_def {
+                self.current = Some(0);
+            }
+        }
+        // One at this level starts a new element of it.
+        if rep <= self.repeated.rep
+            && def >= self.repeated.def
+            && let Some(length) = &mut self.current
+        {
+            *length += 1;
+        }
+    }
+
+    fn close(&mut self) {
+        if let Some(length) = self.current.take() {
+            self.count += 1;
+            self.total += length;
+            self.min = Some(self.min.map_or(length, |min| min.min(length)));
+            self.max = Some(self.max.map_or(length, |max| max.max(length)));
+        }
+    }
+
+    fn finish(mut self, distinct_keys: u64) -> NestedProfile {
+        self.close();
+        NestedProfile {
+            column: self.repeated.column,
+            kind: if self.repeated.map { "Map" } else { "List" }.to_string(),
+            count: self.count,
+            min_length: self.min,
+            avg_length: (self.count > 0).then(|| self.total as f64 / self.count as f64),
+            max_length: self.max,
+            distinct_keys: self.repeated.key.then_some(distinct_keys),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Numeric,
+    Text,
+    Other,
+}
+
+fn column_kind(descr: &ColumnDescriptor) -> Kind {
+    let logical = descr.logical_type_ref();
+    match descr.physical_type() {
+        PhysicalType::INT32 | PhysicalType::INT64 | PhysicalType::FLOAT | PhysicalType::DOUBLE
+            if matches!(logical, None | Some(LogicalType::Integer(_))) =>
+        {
+            Kind::Numeric
+        }
+        PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY
+            if !matches!(logical, Some(LogicalType::Decimal(_))) =>
+        {
+            Kind::Text
+        }
+        _ => Kind::Other,
+    }
+}
+
+/// Running statistics over the values of one column.
+struct Accumulator<T> {
+    kind: Kind,
+    physical: PhysicalType,
+    logical: Option<LogicalType>,
+    levels: u64,
+    values: u64,
+    hll: HyperLogLog,
+    min: Option<T>,
+    max: Option<T>,
+    // Welford's online mean and variance.
+    mean: f64,
+    m2: f64,
+    total_length: u64,
+    counts: HashMap<Vec<u8>, u64>,
+    specials: Option<FloatSpecials>,
+    // Occurrences of every byte value in the values read.
+    byte_counts: [u64; 256],
+    runs: RunDeltaStats,
+}
+
+impl<T: Clone + PartialOrd + AsBytes + fmt::Display> Accumulator<T> {
+    fn new(kind: Kind, physical: PhysicalType, logical: Option<LogicalType>) -> Self {
+        Self {
+            kind,
+            physical,
+            logical,
+            levels: 0,
+            values: 0,
+            hll: HyperLogLog::new(),
+            min: None,
+            max: None,
+            mean: 0.0,
+            m2: 0.0,
+            total_length: 0,
+            counts: HashMap::new(),
+            specials: matches!(physical, PhysicalType::FLOAT | PhysicalType::DOUBLE)
+                .then(FloatSpecials::default),
+            byte_counts: [0; 256],
+            runs: RunDeltaStats::default(),
+        }
+    }
+
+    fn add(&mut self, value: &T, as_f64: fn(&T) -> f64) {
+        self.values += 1;
+        self.hll.insert(value.as_bytes());
+        for &byte in value.as_bytes() {
+            self.byte_counts[byte as usize] += 1;
+        }
+        if let Some(specials) = &mut self.specials {
+            let x = as_f64(value);
+            specials.add(x);
+            if x.is_nan() {
+                return;
+            }
+        }
+        if self.min.as_ref().is_none_or(|min| value < min) {
+            self.min = Some(value.clone());
+        }
+        if self.max.as_ref().is_none_or(|max| value > max) {
+            self.max = Some(value.clone());
+        }
+        match self.kind {
+            Kind::Numeric => {
+                let x = as_f64(value);
+                let n = self.values - self.specials.map_or(0, |s| s.nan);
+                let delta = x - self.mean;
+                self.mean += delta / n as f64;
+                self.m2 += delta * (x - self.mean);
+                self.runs.add(x);
+            }
+            Kind::Text => {
+                self.total_length += value.as_bytes().len() as u64;
+                *self.counts.entry(value.as_bytes().to_vec()).or_default() += 1;
+            }
+            Kind::Other => {}
+        }
+    }
+
+    fn display(&self, value: &T) -> String {
+        match self.physical {
+            PhysicalType::BOOLEAN | PhysicalType::INT96 => value.to_string(),
+            physical => decode_logical_value(value.as_bytes(), physical, self.logical.as_ref()),
+        }
+    }
+
+    fn finish(self, descr: &ColumnDescriptor, top_k: usize) -> ColumnProfile {
+        let nulls = self.levels - self.values;
+        let nans = self.specials.map_or(0, |s| s.nan);
+        let numeric = self.kind == Kind::Numeric && self.values > nans;
+        let text = self.kind == Kind::Text && self.values > 0;
+
+        let min = self.min.as_ref().map(|v| self.display(v));
+        let max = self.max.as_ref().map(|v| self.display(v));
+        let mut top_values: Vec<(Vec<u8>, u64)> = self.counts.into_iter().collect();
+        top_values.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top_values.truncate(top_k);
+
+        ColumnProfile {
+            column: descr.path().string(),
+            physical: format!("{:?}", descr.physical_type()),
+            logical: descr
+                .logical_type_ref()
+                .map(logical_type_to_string)
+                .unwrap_or_default(),
+            sampled: self.levels,
+            nulls,
+            null_percent: if self.levels > 0 {
+                nulls as f64 / self.levels as f64 * 100.0
+            } else {
+                0.0
+            },
+            distinct: if self.values > 0 {
+                self.hll.estimate()
+            } else {
+                0
+            },
+            min,
+            max,
+            mean: numeric.then_some(self.mean),
+            stddev: numeric.then(|| (self.m2 / (self.values - nans) as f64).sqrt()),
+            top_values: top_values
+                .into_iter()
+                .map(|(bytes, count)| ValueCount {
+                    value: decode_logical_value(&bytes, self.physical, self.logical.as_ref()),
+                    count,
+                })
+                .collect(),
+            avg_length: text.then(|| self.total_length as f64 / self.values as f64),
+            specials: self.specials,
+            ratio: None,
+            entropy: byte_entropy(&self.byte_counts),
+            encoding_hint: None,
+        }
+    }
+}
+
+/// How the columns of a profile are read: the same for each of them.
+struct ColumnScan<'a> {
+    reader: &'a dyn FileReader,
+    /// Records read from each row group.
+    limit: usize,
+    top_k: usize,
+    progress: &'a ScanProgress,
+}
+
+/// Read up to `scan.limit` records from each row group of one column,
+/// adding each chunk's compressed size to `scan.progress` once it is read.
+/// The lengths of the lists in `repeated` are measured along the way.
+fn profile_column<T: DataType>(
+    scan: &ColumnScan,
+    col_idx: usize,
+    repeated: Vec<Repeated>,
+    open: fn(ColumnReader) -> Option<ColumnReaderImpl<T>>,
+    as_f64: fn(&T::T) -> f64,
+) -> Result<(ColumnProfile, Vec<NestedProfile>), String> {
+    let &ColumnScan {
+        reader,
+        limit,
+        top_k,
+        progress,
+    } = scan;
+    let descr = reader
+        .metadata()
+        .file_metadata()
+        .schema_descr()
+        .column(col_idx);
+    let mut acc = Accumulator::<T::T>::new(
+        column_kind(&descr),
+        descr.physical_type(),
+        descr.logical_type_ref().cloned(),
+    );
+    let mut lengths: Vec<Lengths> = repeated.into_iter().map(Lengths::new).collect();
+    let (mut def_levels, mut rep_levels, mut values) = (Vec::new(), Vec::new(), Vec::new());
+
+    for idx in 0..reader.num_row_groups() {
+        let row_group = reader.get_row_group(idx).map_err(|e| e.to_string())?;
+        let column = row_group
+            .get_column_reader(col_idx)
+            .map_err(|e| e.to_string())?;
+        let mut column = open(column).ok_or("unexpected column reader type")?;
+        let mut records = 0;
+        while records < limit {
+            progress.check()?;
+            def_levels.clear();
+            rep_levels.clear();
+            values.clear();
+            let (read, _, levels) = column
+                .read_records(
+                    READ_BATCH.min(limit - records),
+                    Some(&mut def_levels),
+                    Some(&mut rep_levels),
+                    &mut values,
+                )
+                .map_err(|e| e.to_string())?;
+            if read == 0 {
+                break;
+            }
+            records += read;
+            acc.levels += levels as u64;
+            for value in &values {
+                acc.add(value, as_f64);
+            }
+            for lengths in &mut lengths {
+                for (&def, &rep) in def_levels.iter().zip(&rep_levels) {
+                    lengths.add(def, rep);
+                }
+            }
+        }
+        let chunk = reader.metadata().row_group(idx).column(col_idx);
+        progress.advance(chunk.compressed_size().max(0) as u64);
+    }
+    let distinct = if acc.values > 0 {
+        acc.hll.estimate()
+    } else {
+        0
+    };
+    let nested = lengths
+        .into_iter()
+        .map(|lengths| lengths.finish(distinct))
+        .collect();
+    let chunks: Vec<_> = reader
+        .metadata()
+        .row_groups()
+        .iter()
+        .map(|rg| rg.column(col_idx))
+        .collect();
+    let (compressed, uncompressed) = chunks.iter().fold((0, 0), |(c, u), chunk| {
+        (c + chunk.compressed_size(), u + chunk.uncompressed_size())
+    });
+    let encodings: Vec<_> = chunks.iter().flat_map(|chunk| chunk.encodings()).collect();
+    let encoding_hint = acc.runs.finish(descr.physical_type(), distinct, &encodings);
+    let mut profile = acc.finish(&descr, top_k);
+    profile.ratio = (compressed > 0).then(|| uncompressed as f64 / compressed as f64);
+    profile.encoding_hint = encoding_hint;
+    Ok((profile, nested))
+}
+
+impl FileProfile {
+    pub fn from_file(path: &str, options: ProfileOptions) -> Result<Self, FileIOError> {
+        let (reader, _) = open_reader(path)?;
+        Self::from_reader(path, &reader, options)
+            .map_err(|details| FileIOError::SampleDataError { details })
+    }
+
+    pub fn from_reader(
+        path: &str,
+        reader: &dyn FileReader,
+        options: ProfileOptions,
+    ) -> Result<Self, String> {
+        Self::from_reader_with_progress(path, reader, options, &ScanProgress::default())
+    }
+
+    /// Like [`FileProfile::from_reader`], reporting the compressed bytes of
+    /// the column chunks read to `progress` and stopping if it is cancelled.
+    pub fn from_reader_with_progress(
+        path: &str,
+        reader: &dyn FileReader,
+        options: ProfileOptions,
+        progress: &ScanProgress,
+    ) -> Result<Self, String> {
+        let md = reader.metadata();
+        let total_rows = md.file_metadata().num_rows().max(0) as u64;
+        let limit = options
+            .sample_rows
+            .div_ceil(reader.num_row_groups().max(1))
+            .max(1);
+        let scan = ColumnScan {
+            reader,
+            limit,
+            top_k: options.top_k,
+            progress,
+        };
+
+        let schema = md.file_metadata().schema_descr();
+        let mut columns = Vec::new();
+        let mut nested = Vec::new();
+        let mut measured = HashSet::new();
+        for col_idx in 0..schema.num_columns() {
+            let descr = schema.column(col_idx);
+            // Each list or map is measured from the first leaf below it.
+            let repeated: Vec<Repeated> = repeated_ancestors(schema, &descr)
+                .into_iter()
+                .filter(|repeated| measured.insert(repeated.column.clone()))
+                .collect();
+            let (profile, lengths) = match descr.physical_type() {
+                PhysicalType::BOOLEAN => profile_column(
+                    &scan,
+                    col_idx,
+                    repeated,
+                    |c| match c {
+                        ColumnReader::BoolColumnReader(r) => Some(r),
+                        _ => None,
+                    },
+                    |v| *v as u8 as f64,
+                ),
+                PhysicalType::INT32 => profile_column(
+                    &scan,
+                    col_idx,
+                    repeated,
+                    |c| match c {
+                        ColumnReader::Int32ColumnReader(r) => Some(r),
+                        _ => None,
+                    },
+                    |v| *v as f64,
+                ),
+                PhysicalType::INT64 => profile_column(
+                    &scan,
+                    col_idx,
+                    repeated,
+                    |c| match c {
+                        ColumnReader::Int64ColumnReader(r) => Some(r),
+                        _ => None,
+                    },
+                    |v| *v as f64,
+                ),
+                PhysicalType::INT96 => profile_column(
+                    &scan,
+                    col_idx,
+                    repeated,
+                    |c| match c {
+                        ColumnReader::Int96ColumnReader(r) => Some(r),
+                        _ => None,
+                    },
+                    |_| 0.0,
+                ),
+                PhysicalType::FLOAT => profile_column(
+                    &scan,
+                    col_idx,
+                    repeated,
+                    |c| match c {
+                        ColumnReader::FloatColumnReader(r) => Some(r),
+                        _ => None,
+                    },
+                    |v| *v as f64,
+                ),
+                PhysicalType::DOUBLE => profile_column(
+                    &scan,
+                    col_idx,
+                    repeated,
+                    |c| match c {
+                        ColumnReader::DoubleColumnReader(r) => Some(r),
+                        _ => None,
+                    },
+                    |v| *v,
+                ),
+                PhysicalType::BYTE_ARRAY => profile_column(
+                    &scan,
+                    col_idx,
+                    repeated,
+                    |c| match c {
+                        ColumnReader::ByteArrayColumnReader(r) => Some(r),
+                        _ => None,
+                    },
+                    |_| 0.0,
+                ),
+                PhysicalType::FIXED_LEN_BYTE_ARRAY => profile_column(
+                    &scan,
+                    col_idx,
+                    repeated,
+                    |c| match c {
+                        ColumnReader::FixedLenByteArrayColumnReader(r) => Some(r),
+                        _ => None,
+                    },
+                    |_| 0.0,
+                ),
+            }?;
+            columns.push(profile);
+            nested.extend(lengths);
+        }
+
+        let sampled_rows = md
+            .row_groups()
+            .iter()
+            .map(|rg| (rg.num_rows().max(0) as u64).min(limit as u64))
+            .sum();
+        Ok(Self {
+            path: path.to_string(),
+            total_rows,
+            sampled_rows,
+            columns,
+            nested,
+        })
+    }
+
+    /// A self-contained HTML page with one table row per column.
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str(&format!(
+            "<title>Profile of {}</title>\n",
+            html_escape(&self.path)
+        ));
+        html.push_str(
+            "<style>\n\
+             body { font-family: sans-serif; margin: 2em; }\n\
+             table { border-collapse: collapse; }\n\
+             th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: left; vertical-align: top; }\n\
+             th { background: #f0f0f0; }\n\
+             td.num { text-align: right; }\n\
+             </style>\n</head>\n<body>\n",
+        );
+        html.push_str(&format!(
+            "<h1>{}</h1>\n<p>Sampled {} of {} rows.</p>\n<table>\n<tr>",
+            html_escape(&self.path),
+            commas(self.sampled_rows),
+            commas(self.total_rows)
+        ));
+        for header in PROFILE_HEADERS {
+            html.push_str(&format!("<th>{header}</th>"));
+        }
+        html.push_str("</tr>\n");
+        for cells in self.columns.iter().map(profile_cells) {
+            html.push_str("<tr>");
+            for (idx, cell) in cells.iter().enumerate() {
+                let class = if NUMERIC_CELLS.contains(&idx) {
+                    " class=\"num\""
+                } else {
+                    ""
+                };
+                html.push_str(&format!("<td{class}>{}</td>", html_escape(cell)));
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n");
+        if !self.nested.is_empty() {
+            html.push_str("<h2>Lists and maps</h2>\n<table>\n<tr>");
+            for header in NESTED_HEADERS {
+                html.push_str(&format!("<th>{header}</th>"));
+            }
+            html.push_str("</tr>\n");
+            for cells in self.nested.iter().map(nested_cells) {
+                html.push_str("<tr>");
+                for (idx, cell) in cells.iter().enumerate() {
+                    let class = if idx >= 2 { " class=\"num\"" } else { "" };
+                    html.push_str(&format!("<td{class}>{}</td>", html_escape(cell)));
+                }
+                html.push_str("</tr>\n");
+            }
+            html.push_str("</table>\n");
+        }
+        let nan_columns = self.nan_columns();
+        if !nan_columns.is_empty() {
+            html.push_str(&format!(
+                "<p>NaN in {}. {}</p>\n",
+                html_escape(&nan_columns.join(", ")),
+                html_escape(NAN_STATS_NOTE)
+            ));
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+const PROFILE_HEADERS: [&str; 14] = [
+    "Column",
+    "Type",
+    "Nulls",
+    "Distinct",
+    "Min",
+    "Max",
+    "Mean",
+    "Stddev",
+    "NaN/Inf/-0",
+    "Avg len",
+    "Ratio",
+    "Entropy",
+    "Encoding hint",
+    "Top values",
+];
+
+/// Cells of `PROFILE_HEADERS` that hold numbers and are right-aligned.
+const NUMERIC_CELLS: [usize; 7] = [2, 3, 6, 7, 9, 10, 11];
+
+fn format_number(value: Option<f64>) -> String {
+    value.map_or("-".to_string(), |v| format!("{v:.2}"))
+}
+
+/// Top values as `value (count)`, most frequent first.
+pub fn format_top_values(values: &[ValueCount]) -> String {
+    values
+        .iter()
+        .map(|v| format!("{} ({})", v.value, v.count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+const NESTED_HEADERS: [&str; 7] = [
+    "Column",
+    "Kind",
+    "Count",
+    "Min len",
+    "Avg len",
+    "Max len",
+    "Distinct keys",
+];
+
+/// `min / avg / max` length of the lists or maps of a column.
+pub fn format_lengths(nested: &NestedProfile) -> String {
+    match (nested.min_length, nested.avg_length, nested.max_length) {
+        (Some(min), Some(avg), Some(max)) => format!("{min} / {avg:.2} / {max}"),
+        _ => "-".to_string(),
+    }
+}
+
+fn nested_cells(nested: &NestedProfile) -> Vec<String> {
+    let length = |value: Option<u64>| value.map_or("-".to_string(), commas);
+    vec![
+        nested.column.clone(),
+        nested.kind.clone(),
+        commas(nested.count),
+        length(nested.min_length),
+        format_number(nested.avg_length),
+        length(nested.max_length),
+        nested
+            .distinct_keys
+            .map_or("-".to_string(), |keys| format!("≈{}", commas(keys))),
+    ]
+}
+
+fn profile_cells(column: &ColumnProfile) -> Vec<String> {
+    vec![
+        column.column.clone(),
+        if column.logical.is_empty() {
+            column.physical.clone()
+        } else {
+            format!("{} ({})", column.physical, column.logical)
+        },
+        format!("{:.1}%", column.null_percent),
+        format!("≈{}", commas(column.distinct)),
+        column.min.clone().unwrap_or_else(|| "-".to_string()),
+        column.max.clone().unwrap_or_else(|| "-".to_string()),
+        format_number(column.mean),
+        format_number(column.stddev),
+        column
+            .specials
+            .as_ref()
+            .map_or("-".to_string(), format_specials),
+        format_number(column.avg_length),
+        column
+            .ratio
+            .map_or("-".to_string(), |ratio| format!("{ratio:.2}x")),
+        format_entropy(column.entropy),
+        column
+            .encoding_hint
+            .as_ref()
+            .and_then(|hint| hint.suggestion.as_ref())
+            .map_or("-".to_string(), ToString::to_string),
+        format_top_values(&column.top_values),
+    ]
+}
+
+/// An aligned text table, one row per column.
+impl fmt::Display for FileProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}: sampled {} of {} rows",
+            self.path,
+            commas(self.sampled_rows),
+            commas(self.total_rows)
+        )?;
+        let rows: Vec<Vec<String>> = self.columns.iter().map(profile_cells).collect();
+        write_table(f, &PROFILE_HEADERS, &rows)?;
+        if !self.nested.is_empty() {
+            writeln!(f)?;
+            let rows: Vec<Vec<String>> = self.nested.iter().map(nested_cells).collect();
+            write_table(f, &NESTED_HEADERS, &rows)?;
+        }
+        let nan_columns = self.nan_columns();
+        if !nan_columns.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "NaN in {}. {NAN_STATS_NOTE}", nan_columns.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+fn write_table(f: &mut fmt::Formatter<'_>, headers: &[&str], rows: &[Vec<String>]) -> fmt::Result {
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|idx| {
+            rows.iter()
+                .map(|row| row[idx].chars().count())
+                .chain([headers[idx].len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let headers: Vec<String> = headers.iter().map(|h| h.to_string()).collect();
+    for row in std::iter::once(&headers).chain(rows) {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        writeln!(f, "{}", line.join("  ").trim_end())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::{test_data_path, write_test_file};
+
+    fn column<'a>(profile: &'a FileProfile, name: &str) -> &'a ColumnProfile {
+        profile.columns.iter().find(|c| c.column == name).unwrap()
+    }
+
+    #[test]
+    fn test_profile_flat_columns() {
+        let path = test_data_path("alltypes_plain.parquet");
+        let profile = FileProfile::from_file(&path, ProfileOptions::default()).unwrap();
+        assert_eq!(profile.total_rows, 8);
+        assert_eq!(profile.sampled_rows, 8);
+        assert_eq!(profile.columns.len(), 11);
+
+        // id is 0..7 in a shuffled order.
+        let id = column(&profile, "id");
+        assert_eq!(id.sampled, 8);
+        assert_eq!(id.nulls, 0);
+        assert_eq!(id.distinct, 8);
+        assert_eq!(id.min.as_deref(), Some("0"));
+        assert_eq!(id.max.as_deref(), Some("7"));
+        assert_eq!(id.mean, Some(3.5));
+        assert!((id.stddev.unwrap() - 5.25f64.sqrt()).abs() < 1e-9);
+        assert!(id.top_values.is_empty());
+
+        assert_eq!(id.specials, None);
+
+        let bools = column(&profile, "bool_col");
+        assert_eq!(bools.min.as_deref(), Some("false"));
+        assert_eq!(bools.max.as_deref(), Some("true"));
+        assert_eq!(bools.mean, None);
+
+        // string_col alternates "0" and "1".
+        let strings = column(&profile, "string_col");
+        assert_eq!(strings.avg_length, Some(1.0));
+        assert_eq!(
+            strings.top_values,
+            vec![
+                ValueCount {
+                    value: "0".to_string(),
+                    count: 4
+                },
+                ValueCount {
+                    value: "1".to_string(),
+                    count: 4
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sample_and_nulls() {
+        let path = test_data_path("nulls.snappy.parquet");
+        let options = ProfileOptions {
+            sample_rows: 100,
+            top_k: 2,
+        };
+        let profile = FileProfile::from_file(&path, options).unwrap();
+        assert_eq!(profile.total_rows, 1000);
+        // 25 rows from each of the 4 row groups.
+        assert_eq!(profile.sampled_rows, 100);
+
+        let b1 = column(&profile, "b1");
+        assert_eq!(b1.sampled, 100);
+        assert!(b1.nulls > 0);
+        assert_eq!(b1.null_percent, b1.nulls as f64 / b1.sampled as f64 * 100.0);
+        assert!(column(&profile, "s.b").top_values.len() <= 2);
+    }
+
+    #[test]
+    fn test_text_and_html_output() {
+        let path = test_data_path("alltypes_plain.parquet");
+        let profile = FileProfile::from_file(&path, ProfileOptions::default()).unwrap();
+
+        let text = profile.to_string();
+        let mut lines = text.lines();
+        assert!(lines.next().unwrap().ends_with("sampled 8 of 8 rows"));
+        assert!(lines.next().unwrap().starts_with("Column"));
+        assert_eq!(lines.count(), 11);
+
+        let html = profile.to_html();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert_eq!(html.matches("<tr>").count(), 12);
+        assert!(html.contains("<td>string_col</td>"));
+    }
+
+    #[test]
+    fn test_float_specials() {
+        use arrow::array::{ArrayRef, Float32Array, Float64Array, RecordBatch};
+        use std::sync::Arc;
+
+        let x = Float64Array::from(vec![1.0, f64::NAN, 3.0, -0.0]);
+        let y = Float32Array::from(vec![
+            Some(f32::INFINITY),
+            Some(f32::NEG_INFINITY),
+            None,
+            Some(2.0),
+        ]);
+        let batch = RecordBatch::try_from_iter(vec![
+            ("x", Arc::new(x) as ArrayRef),
+            ("y", Arc::new(y) as ArrayRef),
+        ])
+        .unwrap();
+        let path = write_test_file("profile-floats", &batch, None);
+        let profile =
+            FileProfile::from_file(&path.display().to_string(), ProfileOptions::default()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // The NaN is counted but kept out of the other statistics.
+        let x = column(&profile, "x");
+        assert_eq!(
+            x.specials,
+            Some(FloatSpecials {
+                nan: 1,
+                neg_zero: 1,
+                ..Default::default()
+            })
+        );
+        assert!((x.mean.unwrap() - 4.0 / 3.0).abs() < 1e-9);
+        assert_eq!(x.max.as_deref(), Some("3.0000"));
+        assert_eq!(format_specials(&x.specials.unwrap()), "1 NaN, 1 -0.0");
+
+        let y = column(&profile, "y");
+        assert_eq!(format_specials(&y.specials.unwrap()), "1 +Inf, 1 -Inf");
+        assert_eq!(profile.nan_columns(), ["x"]);
+
+        let text = profile.to_string();
+        assert!(text.contains("\nNaN in x. NaN is unordered"), "{text}");
+        assert!(profile.to_html().contains("<p>NaN in x."));
+    }
+
+    #[test]
+    fn test_entropy_of_random_and_repeated_bytes() {
+        use arrow::array::{ArrayRef, BinaryArray, RecordBatch, StringArray};
+        use std::sync::Arc;
+
+        // Bytes from a xorshift generator stand in for encrypted blobs.
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let blobs: Vec<Vec<u8>> = (0..64)
+            .map(|_| {
+                (0..256)
+                    .map(|_| {
+                        state ^= state << 13;
+                        state ^= state >> 7;
+                        state ^= state << 17;
+                        state as u8
+                    })
+                    .collect()
+            })
+            .collect();
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "blob",
+                Arc::new(BinaryArray::from_iter_values(&blobs)) as ArrayRef,
+            ),
+            (
+                "status",
+                Arc::new(StringArray::from(vec!["ok"; 64])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+        let path = write_test_file("profile-entropy", &batch, None);
+        let profile =
+            FileProfile::from_file(&path.display().to_string(), ProfileOptions::default()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let blob = column(&profile, "blob");
+        assert!(blob.entropy.unwrap() > RANDOM_ENTROPY, "{blob:?}");
+        assert!(blob.ratio.unwrap() < 1.1, "{blob:?}");
+        // "ok" over and over: two byte values, equally often.
+        let status = column(&profile, "status");
+        assert!((status.entropy.unwrap() - 1.0).abs() < 1e-9);
+        assert_eq!(format_entropy(status.entropy), "1.00 bits/byte");
+        assert_eq!(byte_entropy(&[0; 256]), None);
+    }
+
+    #[test]
+    fn test_encoding_hint_of_sequential_values() {
+        use arrow::array::{ArrayRef, Int64Array, RecordBatch, StringArray};
+        use parquet::basic::Encoding;
+        use std::sync::Arc;
+
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "ts",
+                Arc::new(Int64Array::from_iter_values(
+                    (0..1000).map(|i| 1_700_000_000_000_000 + i * 3),
+                )) as ArrayRef,
+            ),
+            (
+                "name",
+                Arc::new(StringArray::from(vec!["a"; 1000])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+        let path = write_test_file("profile-encoding", &batch, None);
+        let profile =
+            FileProfile::from_file(&path.display().to_string(), ProfileOptions::default()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let hint = column(&profile, "ts").encoding_hint.clone().unwrap();
+        assert_eq!(hint.avg_run_length, 1.0);
+        assert_eq!(hint.avg_delta, 3.0);
+        assert_eq!(
+            hint.suggestion.map(|s| s.encoding),
+            Some(Encoding::DELTA_BINARY_PACKED)
+        );
+        assert_eq!(column(&profile, "name").encoding_hint, None);
+        assert!(
+            profile
+                .to_string()
+                .contains("DELTA_BINARY_PACKED: deltas need")
+        );
+    }
+
+    #[test]
+    fn test_list_lengths_and_map_keys() {
+        use arrow::array::{
+            ArrayRef, Int32Builder, ListArray, MapBuilder, RecordBatch, StringBuilder,
+        };
+        use arrow::datatypes::Int32Type;
+        use std::sync::Arc;
+
+        let tags = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), Some(2)]),
+            Some(vec![]),
+            None,
+            Some(vec![Some(3), None, Some(5)]),
+        ]);
+        let mut attrs = MapBuilder::new(None, StringBuilder::new(), Int32Builder::new());
+        for keys in [&["a", "b"][..], &["a"], &[], &["c", "a", "d"]] {
+            for key in keys {
+                attrs.keys().append_value(key);
+                attrs.values().append_value(1);
+            }
+            attrs.append(true).unwrap();
+        }
+        let batch = RecordBatch::try_from_iter(vec![
+            ("tags", Arc::new(tags) as ArrayRef),
+            ("attrs", Arc::new(attrs.finish()) as ArrayRef),
+        ])
+        .unwrap();
+        let path = write_test_file("profile-nested", &batch, None);
+        let profile =
+            FileProfile::from_file(&path.display().to_string(), ProfileOptions::default()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // The null list isn't counted; the empty one is.
+        let tags = profile.nested("tags").unwrap();
+        assert_eq!(tags.kind, "List");
+        assert_eq!(tags.count, 3);
+        assert_eq!((tags.min_length, tags.max_length), (Some(0), Some(3)));
+        assert_eq!(tags.avg_length, Some(5.0 / 3.0));
+        assert_eq!(tags.distinct_keys, None);
+        assert_eq!(format_lengths(tags), "0 / 1.67 / 3");
+
+        let attrs = profile.nested("attrs").unwrap();
+        assert_eq!(attrs.kind, "Map");
+        assert_eq!(attrs.count, 4);
+        assert_eq!((attrs.min_length, attrs.max_length), (Some(0), Some(3)));
+        assert_eq!(attrs.distinct_keys, Some(4));
+        assert_eq!(profile.nested.len(), 2);
+        assert_eq!(
+            profile
+                .enclosing_nested("attrs.entries.values")
+                .map(|n| &n.column),
+            Some(&"attrs".to_string())
+        );
+
+        let text = profile.to_string();
+        assert!(text.contains("Distinct keys"), "{text}");
+        assert!(profile.to_html().contains("<h2>Lists and maps</h2>"));
+    }
+}
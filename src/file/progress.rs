@@ -0,0 +1,86 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Progress of a scan over a file, in compressed bytes, shared between the
+/// thread doing the scan and whatever shows it. Cancelling asks the scan to
+/// stop at the next chunk or batch; it then fails with [`CANCELLED`].
+#[derive(Debug, Clone, Default)]
+pub struct ScanProgress {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    done: AtomicU64,
+    total: AtomicU64,
+    cancelled: AtomicBool,
+}
+
+/// Error of a scan that stopped because it was cancelled.
+pub const CANCELLED: &str = "cancelled";
+
+impl ScanProgress {
+    pub fn new(total: u64) -> Self {
+        let progress = Self::default();
+        progress.inner.total.store(total, Ordering::Relaxed);
+        progress
+    }
+
+    pub fn advance(&self, bytes: u64) {
+        self.inner.done.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn done(&self) -> u64 {
+        self.inner.done.load(Ordering::Relaxed)
+    }
+
+    pub fn total(&self) -> u64 {
+        self.inner.total.load(Ordering::Relaxed)
+    }
+
+    /// Share of the scan done, from 0 to 1.
+    pub fn ratio(&self) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => (self.done() as f64 / total as f64).min(1.0),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// `Err(CANCELLED)` once the scan was cancelled, for use with `?`.
+    pub fn check(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err(CANCELLED.to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_is_shared_between_clones() {
+        let progress = ScanProgress::new(200);
+        let worker = progress.clone();
+        worker.advance(50);
+        assert_eq!(progress.done(), 50);
+        assert_eq!(progress.ratio(), 0.25);
+        worker.advance(500);
+        assert_eq!(progress.ratio(), 1.0);
+
+        assert_eq!(worker.check(), Ok(()));
+        progress.cancel();
+        assert_eq!(worker.check(), Err(CANCELLED.to_string()));
+        assert_eq!(ScanProgress::default().ratio(), 0.0);
+    }
+}
@@ -0,0 +1,808 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use parquet::basic::{LogicalType, TimeUnit, Type as PhysicalType};
+use parquet::file::metadata::{ParquetMetaData, RowGroupMetaData};
+use parquet::file::page_index::column_index::ColumnIndexMetaData;
+use parquet::file::reader::FileReader;
+use parquet::schema::types::{ColumnDescriptor, SchemaDescriptor};
+use serde::Serialize;
+
+use crate::file::error::FileIOError;
+use crate::file::logical::{f16_to_f32, parse_uuid};
+use crate::file::parquet_ctx::open_reader;
+
+/// A literal as typed in a predicate; it is only given a type once it is
+/// compared with a column.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Number(String),
+    Str(String),
+    Bool(bool),
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Literal::Number(n) => write!(f, "{n}"),
+            Literal::Str(s) => write!(f, "'{s}'"),
+            Literal::Bool(b) => write!(f, "{b}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    NotEq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl fmt::Display for CompareOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self {
+            CompareOp::Eq => "=",
+            CompareOp::NotEq => "!=",
+            CompareOp::Lt => "<",
+            CompareOp::Le => "<=",
+            CompareOp::Gt => ">",
+            CompareOp::Ge => ">=",
+        };
+        write!(f, "{op}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Clause {
+    Compare(String, CompareOp, Literal),
+    Between(String, Literal, Literal),
+    IsNull(String),
+    IsNotNull(String),
+}
+
+impl Clause {
+    pub fn column(&self) -> &str {
+        match self {
+            Clause::Compare(column, ..)
+            | Clause::Between(column, ..)
+            | Clause::IsNull(column)
+            | Clause::IsNotNull(column) => column,
+        }
+    }
+}
+
+impl fmt::Display for Clause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Clause::Compare(column, op, value) => write!(f, "{column} {op} {value}"),
+            Clause::Between(column, low, high) => {
+                write!(f, "{column} BETWEEN {low} AND {high}")
+            }
+            Clause::IsNull(column) => write!(f, "{column} IS NULL"),
+            Clause::IsNotNull(column) => write!(f, "{column} IS NOT NULL"),
+        }
+    }
+}
+
+/// A conjunction of simple clauses on single columns, the part of a WHERE
+/// clause that min/max statistics can prune on, e.g.
+/// `event_time BETWEEN '2024-01-01' AND '2024-01-02' AND status = 'ok'`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Predicate {
+    pub clauses: Vec<Clause>,
+}
+
+impl fmt::Display for Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let clauses: Vec<String> = self.clauses.iter().map(ToString::to_string).collect();
+        write!(f, "{}", clauses.join(" AND "))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Str(String),
+    Op(CompareOp),
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '\'' | '"' => {
+                chars.next();
+                let text: String = chars.by_ref().take_while(|&ch| ch != c).collect();
+                tokens.push(Token::Str(text));
+            }
+            '=' | '!' | '<' | '>' => {
+                chars.next();
+                let next = chars.peek().copied();
+                let op = match (c, next) {
+                    ('=', Some('=')) | ('!', Some('=')) | ('<', Some('=')) | ('>', Some('=')) => {
+                        chars.next();
+                        match c {
+                            '=' => CompareOp::Eq,
+                            '!' => CompareOp::NotEq,
+                            '<' => CompareOp::Le,
+                            _ => CompareOp::Ge,
+                        }
+                    }
+                    ('<', Some('>')) => {
+                        chars.next();
+                        CompareOp::NotEq
+                    }
+                    ('=', _) => CompareOp::Eq,
+                    ('<', _) => CompareOp::Lt,
+                    ('>', _) => CompareOp::Gt,
+                    _ => return Err("expected '!='".to_string()),
+                };
+                tokens.push(Token::Op(op));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&ch) = chars.peek() {
+                    if ch.is_whitespace() || "'\"=!<>".contains(ch) {
+                        break;
+                    }
+                    word.push(ch);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn is_keyword(token: Option<&Token>, keyword: &str) -> bool {
+    matches!(token, Some(Token::Word(w)) if w.eq_ignore_ascii_case(keyword))
+}
+
+fn literal(token: Option<Token>) -> Result<Literal, String> {
+    match token {
+        Some(Token::Str(s)) => Ok(Literal::Str(s)),
+        Some(Token::Word(w)) if w.eq_ignore_ascii_case("true") => Ok(Literal::Bool(true)),
+        Some(Token::Word(w)) if w.eq_ignore_ascii_case("false") => Ok(Literal::Bool(false)),
+        Some(Token::Word(w)) if w.parse::<f64>().is_ok() => Ok(Literal::Number(w)),
+        Some(Token::Word(w)) => Err(format!("'{w}' is not a number; quote strings")),
+        _ => Err("expected a value".to_string()),
+    }
+}
+
+impl FromStr for Predicate {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut tokens = tokenize(input)?.into_iter().peekable();
+        let mut clauses = Vec::new();
+        loop {
+            let column = match tokens.next() {
+                Some(Token::Word(column)) => column,
+                _ => return Err("expected a column name".to_string()),
+            };
+            let clause = match tokens.next() {
+                Some(Token::Op(op)) => Clause::Compare(column, op, literal(tokens.next())?),
+                Some(Token::Word(w)) if w.eq_ignore_ascii_case("between") => {
+                    let low = literal(tokens.next())?;
+                    if !is_keyword(tokens.next().as_ref(), "and") {
+                        return Err("expected AND in BETWEEN".to_string());
+                    }
+                    Clause::Between(column, low, literal(tokens.next())?)
+                }
+                Some(Token::Word(w)) if w.eq_ignore_ascii_case("is") => {
+                    let negated = is_keyword(tokens.peek(), "not");
+                    if negated {
+                        tokens.next();
+                    }
+                    if !is_keyword(tokens.next().as_ref(), "null") {
+                        return Err("expected NULL after IS".to_string());
+                    }
+                    if negated {
+                        Clause::IsNotNull(column)
+                    } else {
+                        Clause::IsNull(column)
+                    }
+                }
+                _ => return Err(format!("expected an operator after '{column}'")),
+            };
+            clauses.push(clause);
+
+            match tokens.next() {
+                None => break,
+                Some(Token::Word(w)) if w.eq_ignore_ascii_case("and") || w == "&&" => {}
+                Some(_) => return Err("clauses must be joined with AND".to_string()),
+            }
+        }
+        Ok(Predicate { clauses })
+    }
+}
+
+/// A statistics value or literal, in a form that compares the way the
+/// column's sort order does.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Int(i128),
+    Float(f64),
+    Bytes(Vec<u8>),
+    Bool(bool),
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Value::Int(a), Value::Int(b)) => a.partial_cmp(b),
+            (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+            (Value::Int(a), Value::Float(b)) => (*a as f64).partial_cmp(b),
+            (Value::Float(a), Value::Int(b)) => a.partial_cmp(&(*b as f64)),
+            (Value::Bytes(a), Value::Bytes(b)) => a.partial_cmp(b),
+            (Value::Bool(a), Value::Bool(b)) => a.partial_cmp(b),
+            _ => None,
+        }
+    }
+}
+
+/// Days from 1970-01-01 to the given civil date (proleptic Gregorian).
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let month = month as i64;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Parse `YYYY-MM-DD` with an optional `[ T]HH:MM[:SS]` time into seconds
+/// since the epoch, UTC.
+fn parse_datetime(text: &str) -> Option<i64> {
+    let (date, time) = match text.split_once([' ', 'T']) {
+        Some((date, time)) => (date, Some(time.trim_end_matches('Z'))),
+        None => (text, None),
+    };
+    let mut parts = date.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let mut seconds = days_from_civil(year, month, day) * 86_400;
+    if let Some(time) = time {
+        let mut parts = time.splitn(3, ':');
+        let hours: i64 = parts.next()?.parse().ok()?;
+        let minutes: i64 = parts.next()?.parse().ok()?;
+        let secs: f64 = parts.next().map_or(Some(0.0), |s| s.parse().ok())?;
+        seconds += hours * 3600 + minutes * 60 + secs as i64;
+    }
+    Some(seconds)
+}
+
+pub(crate) fn decimal_scale(column: &ColumnDescriptor) -> Option<i32> {
+    match column.logical_type_ref() {
+        Some(LogicalType::Decimal(decimal)) => Some(decimal.scale),
+        _ => None,
+    }
+}
+
+/// Give `literal` the type of `column`, e.g. turn `'2024-01-01'` into days
+/// for a DATE column or scale `1.5` for a DECIMAL(_, 2) column.
+pub(crate) fn bind_literal(literal: &Literal, column: &ColumnDescriptor) -> Result<Value, String> {
+    let mismatch = || {
+        format!(
+            "{literal} cannot be compared with {} ({:?})",
+            column.path(),
+            column.physical_type()
+        )
+    };
+    let number = |n: &str| n.parse::<f64>().map_err(|_| mismatch());
+
+    if let Some(scale) = decimal_scale(column) {
+        let n = match literal {
+            Literal::Number(n) | Literal::Str(n) => number(n)?,
+            Literal::Bool(_) => return Err(mismatch()),
+        };
+        return Ok(Value::Int((n * 10f64.powi(scale)).round() as i128));
+    }
+
+    match (column.physical_type(), literal) {
+        (PhysicalType::BOOLEAN, Literal::Bool(b)) => Ok(Value::Bool(*b)),
+        (PhysicalType::INT32 | PhysicalType::INT64, Literal::Number(n)) => {
+            match n.parse::<i128>() {
+                Ok(i) => Ok(Value::Int(i)),
+                Err(_) => Ok(Value::Float(number(n)?)),
+            }
+        }
+        (PhysicalType::INT32 | PhysicalType::INT64, Literal::Str(s)) => {
+            let seconds = parse_datetime(s).ok_or_else(mismatch)?;
+            match column.logical_type_ref() {
+                Some(LogicalType::Date) => Ok(Value::Int((seconds.div_euclid(86_400)) as i128)),
+                Some(LogicalType::Timestamp(timestamp)) => Ok(Value::Int(
+                    seconds as i128
+                        * match timestamp.unit {
+                            TimeUnit::MILLIS => 1_000,
+                            TimeUnit::MICROS => 1_000_000,
+                            TimeUnit::NANOS => 1_000_000_000,
+                        },
+                )),
+                _ => Err(mismatch()),
+            }
+        }
+        (PhysicalType::FLOAT | PhysicalType::DOUBLE, Literal::Number(n)) => {
+            Ok(Value::Float(number(n)?))
+        }
+        (PhysicalType::FIXED_LEN_BYTE_ARRAY, Literal::Number(n))
+            if matches!(column.logical_type_ref(), Some(LogicalType::Float16)) =>
+        {
+            Ok(Value::Float(number(n)?))
+        }
+        (PhysicalType::FIXED_LEN_BYTE_ARRAY, Literal::Str(s))
+            if matches!(column.logical_type_ref(), Some(LogicalType::Uuid)) =>
+        {
+            parse_uuid(s).map(Value::Bytes).ok_or_else(mismatch)
+        }
+        (PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY, Literal::Str(s)) => {
+            Ok(Value::Bytes(s.as_bytes().to_vec()))
+        }
+        _ => Err(mismatch()),
+    }
+}
+
+/// Decode a plain-encoded statistics value of `column`.
+fn decode_value(bytes: &[u8], column: &ColumnDescriptor) -> Option<Value> {
+    if decimal_scale(column).is_some()
+        && matches!(
+            column.physical_type(),
+            PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY
+        )
+    {
+        return decode_decimal_bytes(bytes);
+    }
+    if let Some(LogicalType::Float16) = column.logical_type_ref() {
+        return Some(Value::Float(
+            f16_to_f32(u16::from_le_bytes(bytes.try_into().ok()?)) as f64,
+        ));
+    }
+    Some(match column.physical_type() {
+        PhysicalType::BOOLEAN => Value::Bool(*bytes.first()? != 0),
+        PhysicalType::INT32 => Value::Int(i32::from_le_bytes(bytes.try_into().ok()?) as i128),
+        PhysicalType::INT64 => Value::Int(i64::from_le_bytes(bytes.try_into().ok()?) as i128),
+        PhysicalType::FLOAT => Value::Float(f32::from_le_bytes(bytes.try_into().ok()?) as f64),
+        PhysicalType::DOUBLE => Value::Float(f64::from_le_bytes(bytes.try_into().ok()?)),
+        PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY => {
+            Value::Bytes(bytes.to_vec())
+        }
+        PhysicalType::INT96 => return None,
+    })
+}
+
+/// Big-endian two's complement, as decimals are stored in byte arrays.
+fn decode_decimal_bytes(bytes: &[u8]) -> Option<Value> {
+    if bytes.is_empty() || bytes.len() > 16 {
+        return None;
+    }
+    let fill = if bytes[0] & 0x80 != 0 { 0xff } else { 0 };
+    let mut buf = [fill; 16];
+    buf[16 - bytes.len()..].copy_from_slice(bytes);
+    Some(Value::Int(i128::from_be_bytes(buf)))
+}
+
+/// What min/max statistics say about a row group or page.
+struct Bounds {
+    min: Option<Value>,
+    max: Option<Value>,
+    null_count: Option<u64>,
+    /// Every value is null.
+    all_null: bool,
+}
+
+impl Bounds {
+    /// Whether the clause can be true for some row; `true` when unsure.
+    fn may_match(&self, clause: &Clause, bind: impl Fn(&Literal) -> Option<Value>) -> bool {
+        let cmp = |a: &Option<Value>, b: &Option<Value>| match (a, b) {
+            (Some(a), Some(b)) => a.partial_cmp(b),
+            _ => None,
+        };
+        match clause {
+            Clause::IsNull(_) => self.null_count != Some(0),
+            Clause::IsNotNull(_) => !self.all_null,
+            // Comparisons never match nulls.
+            _ if self.all_null => false,
+            Clause::Compare(_, op, literal) => {
+                let value = bind(literal);
+                let vs_min = cmp(&self.min, &value);
+                let vs_max = cmp(&self.max, &value);
+                match op {
+                    CompareOp::Eq => {
+                        vs_min != Some(Ordering::Greater) && vs_max != Some(Ordering::Less)
+                    }
+                    CompareOp::NotEq => {
+                        !(vs_min == Some(Ordering::Equal) && vs_max == Some(Ordering::Equal))
+                    }
+                    CompareOp::Lt => vs_min == Some(Ordering::Less) || vs_min.is_none(),
+                    CompareOp::Le => vs_min != Some(Ordering::Greater),
+                    CompareOp::Gt => vs_max == Some(Ordering::Greater) || vs_max.is_none(),
+                    CompareOp::Ge => vs_max != Some(Ordering::Less),
+                }
+            }
+            Clause::Between(_, low, high) => {
+                cmp(&self.max, &bind(low)) != Some(Ordering::Less)
+                    && cmp(&self.min, &bind(high)) != Some(Ordering::Greater)
+            }
+        }
+    }
+}
+
+/// Whether a row group's min/max statistics allow `column = value`.
+pub(crate) fn row_group_may_equal(rg: &RowGroupMetaData, col_idx: usize, value: &Value) -> bool {
+    let bounds = row_group_bounds(rg, col_idx);
+    !bounds.all_null
+        && bounds.min.as_ref().and_then(|min| min.partial_cmp(value)) != Some(Ordering::Greater)
+        && bounds.max.as_ref().and_then(|max| max.partial_cmp(value)) != Some(Ordering::Less)
+}
+
+fn row_group_bounds(rg: &RowGroupMetaData, col_idx: usize) -> Bounds {
+    let chunk = rg.column(col_idx);
+    let descr = chunk.column_descr();
+    match chunk.statistics() {
+        Some(stats) => {
+            let null_count = stats.null_count_opt();
+            Bounds {
+                min: stats.min_bytes_opt().and_then(|b| decode_value(b, descr)),
+                max: stats.max_bytes_opt().and_then(|b| decode_value(b, descr)),
+                null_count,
+                all_null: descr.max_rep_level() == 0
+                    && null_count.is_some_and(|n| n as i64 == rg.num_rows() && n > 0),
+            }
+        }
+        None => Bounds {
+            min: None,
+            max: None,
+            null_count: None,
+            all_null: false,
+        },
+    }
+}
+
+fn page_bounds(index: &ColumnIndexMetaData, page: usize, column: &ColumnDescriptor) -> Bounds {
+    macro_rules! primitive {
+        ($index:expr, $convert:expr) => {
+            (
+                $index.min_value(page).map($convert),
+                $index.max_value(page).map($convert),
+            )
+        };
+    }
+    let (min, max) = match index {
+        ColumnIndexMetaData::BOOLEAN(i) => primitive!(i, |v: &bool| Value::Bool(*v)),
+        ColumnIndexMetaData::INT32(i) => primitive!(i, |v: &i32| Value::Int(*v as i128)),
+        ColumnIndexMetaData::INT64(i) => primitive!(i, |v: &i64| Value::Int(*v as i128)),
+        ColumnIndexMetaData::FLOAT(i) => primitive!(i, |v: &f32| Value::Float(*v as f64)),
+        ColumnIndexMetaData::DOUBLE(i) => primitive!(i, |v: &f64| Value::Float(*v)),
+        ColumnIndexMetaData::BYTE_ARRAY(i) | ColumnIndexMetaData::FIXED_LEN_BYTE_ARRAY(i) => (
+            i.min_value(page).and_then(|b| decode_value(b, column)),
+            i.max_value(page).and_then(|b| decode_value(b, column)),
+        ),
+        ColumnIndexMetaData::INT96(_) | ColumnIndexMetaData::NONE => (None, None),
+    };
+    Bounds {
+        min,
+        max,
+        null_count: index.null_count(page).map(|n| n.max(0) as u64),
+        all_null: index.is_null_page(page),
+    }
+}
+
+/// Half-open row ranges, sorted and disjoint.
+type RowRanges = Vec<(u64, u64)>;
+
+fn intersect(a: &RowRanges, b: &RowRanges) -> RowRanges {
+    let mut out = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        let start = a[i].0.max(b[j].0);
+        let end = a[i].1.min(b[j].1);
+        if start < end {
+            out.push((start, end));
+        }
+        if a[i].1 < b[j].1 {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    out
+}
+
+/// Pages a reader would still fetch in a row group that survived pruning.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PagePruning {
+    /// Data pages across all columns of the row group.
+    pub total: usize,
+    pub scanned: usize,
+    pub skipped_bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RowGroupPruning {
+    pub idx: usize,
+    pub rows: i64,
+    pub compressed_size: u64,
+    /// Statistics rule out every row, so the row group is never read.
+    pub skipped: bool,
+    /// Page level pruning, when the file has a page index.
+    pub pages: Option<PagePruning>,
+}
+
+impl RowGroupPruning {
+    pub fn skipped_bytes(&self) -> u64 {
+        if self.skipped {
+            self.compressed_size
+        } else {
+            self.pages.as_ref().map_or(0, |p| p.skipped_bytes)
+        }
+    }
+}
+
+/// Find a leaf column by dotted path, or by its name when that is unique.
+pub(crate) fn resolve_column(schema: &SchemaDescriptor, name: &str) -> Result<usize, String> {
+    let columns = schema.columns();
+    if let Some(idx) = columns.iter().position(|c| c.path().string() == name) {
+        return Ok(idx);
+    }
+    let by_name: Vec<usize> = (0..columns.len())
+        .filter(|&idx| columns[idx].name() == name)
+        .collect();
+    match by_name.as_slice() {
+        [idx] => Ok(*idx),
+        [] => Err(format!("unknown column '{name}'")),
+        _ => Err(format!("'{name}' is ambiguous; use the dotted path")),
+    }
+}
+
+/// What a query engine's min/max pruning would read for a predicate.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PruningReport {
+    pub predicate: String,
+    pub row_groups: Vec<RowGroupPruning>,
+}
+
+impl PruningReport {
+    /// Open `path` with its page index and simulate pruning with `predicate`.
+    pub fn from_file(path: &str, predicate: &Predicate) -> Result<Self, FileIOError> {
+        let (reader, _) = open_reader(path)?;
+        let reader = reader
+            .with_page_index()
+            .map_err(|e| FileIOError::InvalidParquet {
+                path: path.into(),
+                details: e.to_string(),
+            })?;
+        Self::from_metadata(reader.metadata(), predicate)
+            .map_err(|details| FileIOError::MetadataError { details })
+    }
+
+    pub fn from_metadata(md: &ParquetMetaData, predicate: &Predicate) -> Result<Self, String> {
+        let schema = md.file_metadata().schema_descr();
+
+        // Resolve columns and check literals up front so typos are reported
+        // rather than silently scanning everything.
+        let mut bound = Vec::new();
+        for clause in &predicate.clauses {
+            let col_idx = resolve_column(schema, clause.column())?;
+            let descr = schema.column(col_idx);
+            match clause {
+                Clause::Compare(_, _, literal) => {
+                    bind_literal(literal, &descr)?;
+                }
+                Clause::Between(_, low, high) => {
+                    bind_literal(low, &descr)?;
+                    bind_literal(high, &descr)?;
+                }
+                Clause::IsNull(_) | Clause::IsNotNull(_) => {}
+            }
+            bound.push((clause, col_idx, descr));
+        }
+
+        let mut row_groups = Vec::new();
+        for (rg_idx, rg) in md.row_groups().iter().enumerate() {
+            let rows = rg.num_rows().max(0) as u64;
+            let skipped = bound.iter().any(|(clause, col_idx, descr)| {
+                !row_group_bounds(rg, *col_idx)
+                    .may_match(clause, |literal| bind_literal(literal, descr).ok())
+            });
+
+            let pages = match (md.column_index(), md.offset_index()) {
+                (Some(column_index), Some(offset_index)) if !skipped => {
+                    let column_index = &column_index[rg_idx];
+                    let offset_index = &offset_index[rg_idx];
+                    let page_rows = |col: usize, page: usize| {
+                        let locations = offset_index[col].page_locations();
+                        let start = locations[page].first_row_index.max(0) as u64;
+                        let end = locations
+                            .get(page + 1)
+                            .map_or(rows, |next| next.first_row_index.max(0) as u64);
+                        (start, end)
+                    };
+
+                    let mut selected: RowRanges = vec![(0, rows)];
+                    for (clause, col_idx, descr) in &bound {
+                        let Some(index) = column_index.get(*col_idx) else {
+                            continue;
+                        };
+                        if matches!(index, ColumnIndexMetaData::NONE) {
+                            continue;
+                        }
+                        let matching: RowRanges = (0..index.num_pages() as usize)
+                            .filter(|&page| {
+                                page_bounds(index, page, descr)
+                                    .may_match(clause, |literal| bind_literal(literal, descr).ok())
+                            })
+                            .map(|page| page_rows(*col_idx, page))
+                            .collect();
+                        selected = intersect(&selected, &matching);
+                    }
+
+                    let mut pruning = PagePruning {
+                        total: 0,
+                        scanned: 0,
+                        skipped_bytes: 0,
+                    };
+                    for (col, index) in offset_index.iter().enumerate() {
+                        for (page, location) in index.page_locations().iter().enumerate() {
+                            let (start, end) = page_rows(col, page);
+                            pruning.total += 1;
+                            if selected.iter().any(|&(s, e)| s < end && start < e) {
+                                pruning.scanned += 1;
+                            } else {
+                                pruning.skipped_bytes +=
+                                    location.compressed_page_size.max(0) as u64;
+                            }
+                        }
+                    }
+                    Some(pruning)
+                }
+                _ => None,
+            };
+
+            row_groups.push(RowGroupPruning {
+                idx: rg_idx,
+                rows: rg.num_rows(),
+                compressed_size: rg.compressed_size().max(0) as u64,
+                skipped,
+                pages,
+            });
+        }
+
+        Ok(Self {
+            predicate: predicate.to_string(),
+            row_groups,
+        })
+    }
+
+    /// Row groups a reader would still open.
+    pub fn scanned(&self) -> Vec<usize> {
+        self.row_groups
+            .iter()
+            .filter(|rg| !rg.skipped)
+            .map(|rg| rg.idx)
+            .collect()
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.row_groups.iter().map(|rg| rg.compressed_size).sum()
+    }
+
+    pub fn skipped_bytes(&self) -> u64 {
+        self.row_groups
+            .iter()
+            .map(RowGroupPruning::skipped_bytes)
+            .sum()
+    }
+
+    /// One line summary, e.g. "scans 2 of 4 row groups, skips 1.2 MiB (48%)".
+    pub fn summary(&self) -> String {
+        let total = self.total_bytes();
+        let skipped = self.skipped_bytes();
+        let percent = if total > 0 {
+            skipped as f64 / total as f64 * 100.0
+        } else {
+            0.0
+        };
+        format!(
+            "scans {} of {} row groups, skips {} ({percent:.0}%)",
+            self.scanned().len(),
+            self.row_groups.len(),
+            crate::file::utils::human_readable_bytes(skipped)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_data_path;
+
+    fn report(file: &str, predicate: &str) -> Result<PruningReport, String> {
+        let predicate: Predicate = predicate.parse()?;
+        PruningReport::from_file(&test_data_path(file), &predicate).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_parse_predicates() {
+        let predicate: Predicate =
+            "event_time BETWEEN '2024-01-01' AND '2024-01-02' and id >= 10 AND name IS NOT NULL"
+                .parse()
+                .unwrap();
+        assert_eq!(predicate.clauses.len(), 3);
+        assert_eq!(
+            predicate.to_string(),
+            "event_time BETWEEN '2024-01-01' AND '2024-01-02' AND id >= 10 AND name IS NOT NULL"
+        );
+        assert_eq!(
+            "x<>3".parse::<Predicate>().unwrap().clauses[0],
+            Clause::Compare("x".into(), CompareOp::NotEq, Literal::Number("3".into()))
+        );
+
+        assert!("".parse::<Predicate>().is_err());
+        assert!("id >".parse::<Predicate>().is_err());
+        assert!("id = abc".parse::<Predicate>().is_err());
+        assert!("id = 1 OR id = 2".parse::<Predicate>().is_err());
+        assert!("id BETWEEN 1 2".parse::<Predicate>().is_err());
+    }
+
+    #[test]
+    fn test_parse_datetime() {
+        assert_eq!(parse_datetime("1970-01-01"), Some(0));
+        assert_eq!(parse_datetime("2024-01-02"), Some(19_724 * 86_400));
+        assert_eq!(parse_datetime("1969-12-31 23:59:59"), Some(-1));
+        assert_eq!(parse_datetime("2024-13-01"), None);
+    }
+
+    #[test]
+    fn test_decimal_bytes() {
+        assert_eq!(decode_decimal_bytes(&[0x01, 0x00]), Some(Value::Int(256)));
+        assert_eq!(decode_decimal_bytes(&[0xff, 0xfe]), Some(Value::Int(-2)));
+    }
+
+    #[test]
+    fn test_row_groups_are_pruned_on_min_max() {
+        // b1 runs from 1 to 998 across four row groups of 250 rows.
+        let tail = report("nulls.snappy.parquet", "b1 > 900").unwrap();
+        assert_eq!(tail.scanned(), vec![3]);
+        let middle = report("nulls.snappy.parquet", "b1 BETWEEN 300 AND 400").unwrap();
+        assert_eq!(middle.scanned(), vec![1]);
+        assert!(middle.skipped_bytes() > middle.row_groups[0].compressed_size);
+        // The file has a page index, so surviving row groups report pages.
+        assert!(middle.row_groups[1].pages.is_some());
+        assert!(middle.row_groups[0].pages.is_none());
+        assert!(middle.summary().starts_with("scans 1 of 4 row groups"));
+
+        let everything = report("nulls.snappy.parquet", "b1 >= 0").unwrap();
+        assert_eq!(everything.scanned().len(), 4);
+        let nothing = report("nulls.snappy.parquet", "b1 > 1000").unwrap();
+        assert!(nothing.scanned().is_empty());
+        assert_eq!(nothing.skipped_bytes(), nothing.total_bytes());
+    }
+
+    #[test]
+    fn test_nested_columns_and_strings() {
+        let nothing = report("nulls.snappy.parquet", "s.b = 'zzz'").unwrap();
+        assert!(nothing.scanned().is_empty());
+        let by_name = report("nulls.snappy.parquet", "b BETWEEN 'v0' AND 'v9'").unwrap();
+        assert_eq!(by_name.scanned().len(), 4);
+    }
+
+    #[test]
+    fn test_errors_are_reported() {
+        assert!(report("nulls.snappy.parquet", "missing = 1").is_err());
+        assert!(report("nulls.snappy.parquet", "b1 = 'abc'").is_err());
+    }
+}
@@ -0,0 +1,569 @@
+use std::path::Path;
+
+use crate::file::analyzer::{AnalysisTable, Analyzers};
+use crate::file::lint::Finding;
+use crate::file::parquet_ctx::ParquetCtx;
+use crate::file::schema::SchemaInfo;
+use crate::file::utils::{commas, html_escape, human_readable_bytes, truncate_str};
+
+/// Longest min/max or key-value metadata value shown before truncating.
+const MAX_VALUE_CHARS: usize = 60;
+
+const CHART_WIDTH: f64 = 720.0;
+const CHART_HEIGHT: f64 = 180.0;
+/// Space left of the plot for the axis labels, and below it for the row
+/// group numbers.
+const CHART_LEFT: f64 = 80.0;
+const CHART_BOTTOM: f64 = 20.0;
+
+/// Headers and rows of one table of the report.
+struct Table<'a> {
+    headers: Vec<&'a str>,
+    rows: Vec<Vec<String>>,
+    /// Columns that hold numbers and are right-aligned.
+    numeric: Vec<usize>,
+}
+
+/// A self-contained summary of a file, for sharing: metadata, schema,
+/// per-column statistics, row group charts and the findings and tables of
+/// [`Analyzers`].
+pub struct Report<'a> {
+    ctx: &'a ParquetCtx,
+    findings: Vec<Finding>,
+    tables: Vec<AnalysisTable>,
+}
+
+impl<'a> Report<'a> {
+    /// A report with the findings of the built-in analyzers.
+    pub fn new(ctx: &'a ParquetCtx) -> Self {
+        Self::with_analyzers(ctx, &Analyzers::default())
+    }
+
+    pub fn with_analyzers(ctx: &'a ParquetCtx, analyzers: &Analyzers) -> Self {
+        let analysis = analyzers.run(ctx);
+        Self {
+            ctx,
+            findings: analysis.findings,
+            tables: analysis.tables,
+        }
+    }
+
+    pub fn findings(&self) -> &[Finding] {
+        &self.findings
+    }
+
+    /// The tables of the analyzers, as report tables.
+    fn analysis_tables(&self) -> Vec<(&str, Table<'_>)> {
+        self.tables
+            .iter()
+            .map(|table| {
+                let report_table = Table {
+                    headers: table.headers.iter().map(String::as_str).collect(),
+                    rows: table.rows.clone(),
+                    numeric: table.numeric.clone(),
+                };
+                (table.title.as_str(), report_table)
+            })
+            .collect()
+    }
+
+    fn file_name(&self) -> String {
+        Path::new(&self.ctx.file_path)
+            .file_name()
+            .map_or(self.ctx.file_path.clone(), |name| {
+                name.to_string_lossy().into_owned()
+            })
+    }
+
+    fn overview(&self) -> Table<'static> {
+        let md = &self.ctx.metadata;
+        let rows = [
+            ("Path", self.ctx.file_path.clone()),
+            ("File size", human_readable_bytes(self.ctx.file_size)),
+            ("Rows", commas(md.num_rows as u64)),
+            ("Columns", md.num_columns.to_string()),
+            ("Row groups", md.num_row_groups.to_string()),
+            ("Uncompressed", human_readable_bytes(md.raw_size)),
+            ("Compressed", human_readable_bytes(md.compressed_size)),
+            ("Ratio", format!("{:.2}x", md.compression_ratio)),
+            ("Codecs", md.codecs.clone()),
+            ("Encodings", md.encodings.clone()),
+            ("Created by", md.created_by.clone()),
+            ("Format version", md.format_version.clone()),
+        ];
+        Table {
+            headers: vec!["Property", "Value"],
+            rows: rows
+                .into_iter()
+                .map(|(key, value)| vec![key.to_string(), value])
+                .collect(),
+            numeric: vec![],
+        }
+    }
+
+    fn key_value_metadata(&self) -> Table<'static> {
+        Table {
+            headers: vec!["Key", "Value"],
+            rows: self
+                .ctx
+                .metadata
+                .key_value_metadata
+                .iter()
+                .map(|(key, value)| vec![key.clone(), truncate_str(value, MAX_VALUE_CHARS)])
+                .collect(),
+            numeric: vec![],
+        }
+    }
+
+    /// The schema tree with each leaf's type and repetition.
+    fn schema_lines(&self) -> Vec<String> {
+        let width = self
+            .ctx
+            .schema
+            .columns
+            .iter()
+            .map(|column| match column {
+                SchemaInfo::Root { display, .. }
+                | SchemaInfo::Group { display, .. }
+                | SchemaInfo::Primitive { display, .. } => display.chars().count(),
+            })
+            .max()
+            .unwrap_or(0);
+        self.ctx
+            .schema
+            .columns
+            .iter()
+            .map(|column| match column {
+                SchemaInfo::Root { display, .. } => display.clone(),
+                SchemaInfo::Group {
+                    display,
+                    repetition,
+                    logical,
+                    ..
+                } if !logical.is_empty() => format!("{display:<width$}  {repetition} ({logical})"),
+                SchemaInfo::Group {
+                    display,
+                    repetition,
+                    ..
+                } => format!("{display:<width$}  {repetition}"),
+                SchemaInfo::Primitive { display, info, .. } => {
+                    let logical = if info.logical.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" ({})", info.logical)
+                    };
+                    format!(
+                        "{display:<width$}  {} {}{logical}",
+                        info.repetition, info.physical
+                    )
+                }
+            })
+            .map(|line| line.trim_end().to_string())
+            .collect()
+    }
+
+    fn column_stats(&self) -> Table<'static> {
+        let rows = self
+            .ctx
+            .schema
+            .columns
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, column)| match column {
+                SchemaInfo::Primitive { info, stats, .. } => {
+                    let ratio = if stats.total_compressed_size > 0 {
+                        stats.total_uncompressed_size as f64 / stats.total_compressed_size as f64
+                    } else {
+                        0.0
+                    };
+                    let value = |v: &Option<String>| {
+                        v.as_deref()
+                            .map_or("-".to_string(), |v| truncate_str(v, MAX_VALUE_CHARS))
+                    };
+                    let kind = if info.logical.is_empty() {
+                        info.physical.clone()
+                    } else {
+                        format!("{} ({})", info.physical, info.logical)
+                    };
+                    Some(vec![
+                        self.ctx.schema.column_path(idx),
+                        kind,
+                        info.codec.clone(),
+                        info.encoding.clone(),
+                        human_readable_bytes(stats.total_compressed_size),
+                        human_readable_bytes(stats.total_uncompressed_size),
+                        format!("{ratio:.2}x"),
+                        commas(stats.nulls),
+                        stats.distinct.map_or("-".to_string(), commas),
+                        value(&stats.min),
+                        value(&stats.max),
+                    ])
+                }
+                _ => None,
+            })
+            .collect();
+        Table {
+            headers: vec![
+                "Column",
+                "Type",
+                "Codec",
+                "Encodings",
+                "Compressed",
+                "Uncompressed",
+                "Ratio",
+                "Nulls",
+                "Distinct",
+                "Min",
+                "Max",
+            ],
+            rows,
+            numeric: vec![4, 5, 6, 7, 8],
+        }
+    }
+
+    fn row_group_table(&self) -> Table<'static> {
+        Table {
+            headers: vec!["Row group", "Rows", "Compressed", "Uncompressed", "Ratio"],
+            rows: self
+                .ctx
+                .row_groups
+                .row_groups
+                .iter()
+                .map(|rg| {
+                    vec![
+                        (rg.idx + 1).to_string(),
+                        commas(rg.rows.max(0) as u64),
+                        human_readable_bytes(rg.compressed_size.max(0) as u64),
+                        human_readable_bytes(rg.uncompressed_size.max(0) as u64),
+                        format!("{:.2}x", rg.compression_ratio),
+                    ]
+                })
+                .collect(),
+            numeric: vec![0, 1, 2, 3, 4],
+        }
+    }
+
+    fn charts(&self) -> Vec<String> {
+        let row_groups = &self.ctx.row_groups.row_groups;
+        vec![
+            svg_bar_chart(
+                "Rows per row group",
+                &row_groups
+                    .iter()
+                    .map(|rg| rg.rows as f64)
+                    .collect::<Vec<_>>(),
+                |v| commas(v as u64),
+            ),
+            svg_bar_chart(
+                "Compressed size per row group",
+                &row_groups
+                    .iter()
+                    .map(|rg| rg.compressed_size as f64)
+                    .collect::<Vec<_>>(),
+                |v| human_readable_bytes(v as u64),
+            ),
+        ]
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!("# {}\n\n", markdown_escape(&self.file_name()));
+        md.push_str(&markdown_table(&self.overview()));
+
+        md.push_str("\n## Findings\n\n");
+        if self.findings.is_empty() {
+            md.push_str("No findings.\n");
+        }
+        for finding in &self.findings {
+            md.push_str(&format!(
+                "- **{}** `{}`: {}\n",
+                finding.severity,
+                finding.rule,
+                markdown_escape(&finding.message)
+            ));
+        }
+        for (title, table) in self.analysis_tables() {
+            md.push_str(&format!("\n### {}\n\n", markdown_escape(title)));
+            md.push_str(&markdown_table(&table));
+        }
+
+        md.push_str("\n## Schema\n\n```\n");
+        for line in self.schema_lines() {
+            md.push_str(&line);
+            md.push('\n');
+        }
+        md.push_str("```\n\n## Column statistics\n\n");
+        md.push_str(&markdown_table(&self.column_stats()));
+
+        md.push_str("\n## Row groups\n\n");
+        for chart in self.charts() {
+            md.push_str(&chart);
+            md.push_str("\n\n");
+        }
+        md.push_str(&markdown_table(&self.row_group_table()));
+
+        let key_values = self.key_value_metadata();
+        if !key_values.rows.is_empty() {
+            md.push_str("\n## Key-value metadata\n\n");
+            md.push_str(&markdown_table(&key_values));
+        }
+        md
+    }
+
+    pub fn to_html(&self) -> String {
+        let title = html_escape(&self.file_name());
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str(&format!("<title>{title}</title>\n"));
+        html.push_str(
+            "<style>\n\
+             body { font-family: sans-serif; margin: 2em; }\n\
+             table { border-collapse: collapse; margin-bottom: 1em; }\n\
+             th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: left; vertical-align: top; }\n\
+             th { background: #f0f0f0; }\n\
+             td.num { text-align: right; }\n\
+             li.error { color: #b00020; }\n\
+             li.warning { color: #a65e00; }\n\
+             svg { display: block; margin-bottom: 1em; }\n\
+             </style>\n</head>\n<body>\n",
+        );
+        html.push_str(&format!("<h1>{title}</h1>\n"));
+        html.push_str(&html_table(&self.overview()));
+
+        html.push_str("<h2>Findings</h2>\n");
+        if self.findings.is_empty() {
+            html.push_str("<p>No findings.</p>\n");
+        } else {
+            html.push_str("<ul>\n");
+            for finding in &self.findings {
+                html.push_str(&format!(
+                    "<li class=\"{0}\"><b>{0}</b> <code>{1}</code>: {2}</li>\n",
+                    finding.severity,
+                    finding.rule,
+                    html_escape(&finding.message)
+                ));
+            }
+            html.push_str("</ul>\n");
+        }
+        for (title, table) in self.analysis_tables() {
+            html.push_str(&format!("<h3>{}</h3>\n", html_escape(title)));
+            html.push_str(&html_table(&table));
+        }
+
+        html.push_str("<h2>Schema</h2>\n<pre>");
+        html.push_str(&html_escape(&self.schema_lines().join("\n")));
+        html.push_str("</pre>\n<h2>Column statistics</h2>\n");
+        html.push_str(&html_table(&self.column_stats()));
+
+        html.push_str("<h2>Row groups</h2>\n");
+        for chart in self.charts() {
+            html.push_str(&chart);
+            html.push('\n');
+        }
+        html.push_str(&html_table(&self.row_group_table()));
+
+        let key_values = self.key_value_metadata();
+        if !key_values.rows.is_empty() {
+            html.push_str("<h2>Key-value metadata</h2>\n");
+            html.push_str(&html_table(&key_values));
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+fn markdown_escape(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+fn markdown_table(table: &Table) -> String {
+    let mut md = format!("| {} |\n|", table.headers.join(" | "));
+    for idx in 0..table.headers.len() {
+        md.push_str(if table.numeric.contains(&idx) {
+            " ---: |"
+        } else {
+            " --- |"
+        });
+    }
+    md.push('\n');
+    for row in &table.rows {
+        let cells: Vec<String> = row.iter().map(|cell| markdown_escape(cell)).collect();
+        md.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    md
+}
+
+fn html_table(table: &Table) -> String {
+    let mut html = String::from("<table>\n<tr>");
+    for header in &table.headers {
+        html.push_str(&format!("<th>{}</th>", html_escape(header)));
+    }
+    html.push_str("</tr>\n");
+    for row in &table.rows {
+        html.push_str("<tr>");
+        for (idx, cell) in row.iter().enumerate() {
+            let class = if table.numeric.contains(&idx) {
+                " class=\"num\""
+            } else {
+                ""
+            };
+            html.push_str(&format!("<td{class}>{}</td>", html_escape(cell)));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+    html
+}
+
+/// A bar per row group, labelled with `label` on hover and on the axis.
+fn svg_bar_chart(title: &str, values: &[f64], label: impl Fn(f64) -> String) -> String {
+    let plot_width = CHART_WIDTH - CHART_LEFT;
+    let plot_height = CHART_HEIGHT - CHART_BOTTOM;
+    let max = values.iter().copied().fold(0.0, f64::max);
+    let slot = plot_width / values.len().max(1) as f64;
+    // Leave a gap between bars while they are wide enough to see it.
+    let bar = if slot > 4.0 { slot * 0.8 } else { slot };
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{CHART_WIDTH}\" height=\"{}\" \
+         font-family=\"sans-serif\" font-size=\"11\">\n\
+         <text x=\"0\" y=\"12\" font-size=\"13\" font-weight=\"bold\">{}</text>\n",
+        CHART_HEIGHT + 20.0,
+        html_escape(title)
+    );
+    let top = 20.0;
+    svg.push_str(&format!(
+        "<text x=\"{0}\" y=\"{1}\" text-anchor=\"end\">{2}</text>\n\
+         <text x=\"{0}\" y=\"{3}\" text-anchor=\"end\">0</text>\n\
+         <line x1=\"{4}\" y1=\"{3}\" x2=\"{5}\" y2=\"{3}\" stroke=\"#999\"/>\n",
+        CHART_LEFT - 6.0,
+        top + 10.0,
+        html_escape(&label(max)),
+        top + plot_height,
+        CHART_LEFT,
+        CHART_WIDTH,
+    ));
+    for (idx, &value) in values.iter().enumerate() {
+        let height = if max > 0.0 {
+            value / max * plot_height
+        } else {
+            0.0
+        };
+        svg.push_str(&format!(
+            "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{bar:.1}\" height=\"{height:.1}\" fill=\"#4c78a8\">\
+             <title>Row group {}: {}</title></rect>\n",
+            CHART_LEFT + idx as f64 * slot,
+            top + plot_height - height,
+            idx + 1,
+            html_escape(&label(value))
+        ));
+    }
+    if let Some(last) = values.len().checked_sub(1) {
+        svg.push_str(&format!(
+            "<text x=\"{CHART_LEFT}\" y=\"{0}\">1</text>\n\
+             <text x=\"{CHART_WIDTH}\" y=\"{0}\" text-anchor=\"end\">{1}</text>\n",
+            top + plot_height + 14.0,
+            last + 1
+        ));
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_data_path;
+
+    #[test]
+    fn test_markdown_report() {
+        let ctx = ParquetCtx::from_file(&test_data_path("alltypes_plain.parquet")).unwrap();
+        let report = Report::new(&ctx);
+        let md = report.to_markdown();
+        assert!(md.starts_with("# alltypes_plain.parquet\n"));
+        for section in [
+            "## Findings",
+            "## Schema",
+            "## Column statistics",
+            "## Row groups",
+        ] {
+            assert!(md.contains(section), "missing {section}");
+        }
+        assert!(md.contains("| Rows | 8 |"));
+        assert!(md.contains("- **warning** `uncompressed`"));
+        assert!(md.contains("| id | INT32 |"));
+        assert_eq!(md.matches("<svg").count(), 2);
+    }
+
+    /// Counts the row groups, with a table of their rows.
+    struct RowGroupCount;
+
+    impl crate::file::analyzer::Analyzer for RowGroupCount {
+        fn name(&self) -> &str {
+            "row-group-count"
+        }
+
+        fn analyze(&self, ctx: &ParquetCtx) -> Result<crate::file::analyzer::Analysis, String> {
+            let row_groups = &ctx.row_groups.row_groups;
+            Ok(crate::file::analyzer::Analysis {
+                findings: vec![Finding::new(
+                    crate::file::lint::Severity::Info,
+                    "row-group-count",
+                    format!("{} row groups", row_groups.len()),
+                )],
+                tables: vec![AnalysisTable {
+                    title: "Rows <per> group".to_string(),
+                    headers: vec!["Row group".to_string(), "Rows".to_string()],
+                    rows: row_groups
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, rg)| vec![(idx + 1).to_string(), rg.rows.to_string()])
+                        .collect(),
+                    numeric: vec![0, 1],
+                }],
+            })
+        }
+    }
+
+    #[test]
+    fn test_report_with_analyzers() {
+        let ctx = ParquetCtx::from_file(&test_data_path("alltypes_plain.parquet")).unwrap();
+        let analyzers = Analyzers::empty().with(RowGroupCount);
+        let report = Report::with_analyzers(&ctx, &analyzers);
+        assert_eq!(report.findings().len(), 1);
+        let md = report.to_markdown();
+        assert!(md.contains("- **info** `row-group-count`: 1 row groups\n"));
+        assert!(md.contains(
+            "\n### Rows <per> group\n\n| Row group | Rows |\n| ---: | ---: |\n| 1 | 8 |\n"
+        ));
+        assert!(!md.contains("`uncompressed`"));
+        let html = report.to_html();
+        assert!(html.contains("<h3>Rows &lt;per&gt; group</h3>\n<table>"));
+    }
+
+    #[test]
+    fn test_html_report() {
+        let ctx = ParquetCtx::from_file(&test_data_path("nulls.snappy.parquet")).unwrap();
+        let report = Report::new(&ctx);
+        let html = report.to_html();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.ends_with("</html>\n"));
+        assert!(
+            html.contains("<li class=\"warning\"><b>warning</b> <code>small-row-groups</code>")
+        );
+        // One bar per row group in each chart.
+        let bars = ctx.row_groups.num_row_groups() * 2;
+        assert_eq!(html.matches("<rect").count(), bars);
+        assert!(html.contains("<title>Row group 1: "));
+    }
+
+    #[test]
+    fn test_svg_bar_chart() {
+        let svg = svg_bar_chart("Sizes <x>", &[10.0, 5.0, 0.0], |v| format!("{v}"));
+        assert!(svg.contains("Sizes &lt;x&gt;"));
+        assert_eq!(svg.matches("<rect").count(), 3);
+        // The largest bar fills the plot, an empty one has no height.
+        assert!(svg.contains(&format!("height=\"{:.1}\"", CHART_HEIGHT - CHART_BOTTOM)));
+        assert!(svg.contains("height=\"0.0\""));
+        assert!(svg_bar_chart("Empty", &[], |v| format!("{v}")).ends_with("</svg>"));
+    }
+}
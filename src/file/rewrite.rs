@@ -0,0 +1,741 @@
+use std::fmt;
+use std::fs::File;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use arrow::record_batch::RecordBatchReader;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::{ArrowWriter, ProjectionMask};
+use parquet::file::metadata::{KeyValue, ParquetMetaData};
+use parquet::file::properties::WriterProperties;
+use parquet::schema::types::SchemaDescriptor;
+use serde::Serialize;
+
+use crate::file::row_group_filter::{parse_bytes, parse_number};
+use crate::file::utils::{commas, human_readable_bytes, same_file};
+
+/// Rows decoded per batch while rewriting.
+const READ_BATCH: usize = 8192;
+
+/// Key under which arrow stores its schema; the writer adds its own.
+const ARROW_SCHEMA_KEY: &str = "ARROW:schema";
+
+/// How big the row groups of a rewritten file should be.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RowGroupTarget {
+    /// Compressed bytes, estimated from the current compression.
+    Bytes(u64),
+    Rows(u64),
+}
+
+impl FromStr for RowGroupTarget {
+    type Err = String;
+
+    /// `128MiB`, `1.5 GB` or `500000 rows`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let target = match s.trim().strip_suffix("rows") {
+            Some(rows) => parse_number(rows.trim()).map(|n| RowGroupTarget::Rows(n as u64)),
+            None => parse_bytes(s).map(|n| RowGroupTarget::Bytes(n as u64)),
+        };
+        target
+            .filter(|target| !matches!(target, RowGroupTarget::Bytes(0) | RowGroupTarget::Rows(0)))
+            .ok_or_else(|| {
+                format!("'{s}' is not a size like 128MiB or a row count like 500000rows")
+            })
+    }
+}
+
+impl fmt::Display for RowGroupTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RowGroupTarget::Bytes(bytes) => f.write_str(&human_readable_bytes(*bytes)),
+            RowGroupTarget::Rows(rows) => write!(f, "{} rows", commas(*rows)),
+        }
+    }
+}
+
+/// Columns to keep in a rewritten file. Names are dotted leaf paths such as
+/// `address.city`, or a group such as `address` for all of its leaves.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnProjection {
+    #[default]
+    All,
+    /// Only these columns, with the top-level columns in the order given.
+    Select(Vec<String>),
+    Drop(Vec<String>),
+}
+
+impl ColumnProjection {
+    /// Leaf columns kept, in file order.
+    pub(crate) fn leaves(&self, schema: &SchemaDescriptor) -> Result<Vec<usize>, String> {
+        let named = |names: &[String]| -> Result<Vec<usize>, String> {
+            let mut leaves = Vec::new();
+            for name in names {
+                let matching = matching_leaves(schema, name);
+                if matching.is_empty() {
+                    return Err(format!("no column '{name}'"));
+                }
+                leaves.extend(matching);
+            }
+            leaves.sort_unstable();
+            leaves.dedup();
+            Ok(leaves)
+        };
+        let leaves: Vec<usize> = match self {
+            ColumnProjection::All => (0..schema.num_columns()).collect(),
+            ColumnProjection::Select(names) => named(names)?,
+            ColumnProjection::Drop(names) => {
+                let dropped = named(names)?;
+                (0..schema.num_columns())
+                    .filter(|leaf| !dropped.contains(leaf))
+                    .collect()
+            }
+        };
+        if leaves.is_empty() {
+            return Err("no columns left to write".to_string());
+        }
+        Ok(leaves)
+    }
+
+    /// Top-level columns in the order `--select` names them; `None` keeps
+    /// the file's order.
+    pub(crate) fn root_order(&self, schema: &SchemaDescriptor) -> Option<Vec<String>> {
+        let ColumnProjection::Select(names) = self else {
+            return None;
+        };
+        let mut roots: Vec<String> = Vec::new();
+        for leaf in names.iter().flat_map(|name| matching_leaves(schema, name)) {
+            let root = schema.get_column_root(leaf).name().to_string();
+            if !roots.contains(&root) {
+                roots.push(root);
+            }
+        }
+        Some(roots)
+    }
+}
+
+/// Leaves whose path is `name` or lies under it.
+fn matching_leaves(schema: &SchemaDescriptor, name: &str) -> Vec<usize> {
+    let prefix = format!("{name}.");
+    schema
+        .columns()
+        .iter()
+        .enumerate()
+        .filter(|(_, column)| {
+            let path = column.path().string();
+            path == name || path.starts_with(&prefix)
+        })
+        .map(|(leaf, _)| leaf)
+        .collect()
+}
+
+/// Rows of a current row group that go into a planned one.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SourceRows {
+    /// Numbered from 0.
+    pub row_group: usize,
+    /// First row taken, within the row group.
+    pub first_row: u64,
+    pub rows: u64,
+    /// Whether these are all the row group's rows.
+    pub whole: bool,
+}
+
+/// One row group of the rewritten file.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlannedRowGroup {
+    pub rows: u64,
+    /// Compressed size, pro rata from the row groups it is made of.
+    pub estimated_bytes: u64,
+    pub sources: Vec<SourceRows>,
+}
+
+/// How the row groups of a file would be merged and split to get close to
+/// a target size: the fewest row groups within the target, of about equal
+/// size. Without a target the row groups are kept. Rows keep their order.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RowGroupPlan {
+    pub path: String,
+    pub target: Option<RowGroupTarget>,
+    pub columns: ColumnProjection,
+    /// Leaf column paths written, and those left out.
+    pub kept: Vec<String>,
+    pub dropped: Vec<String>,
+    /// Rows and compressed bytes of the current row groups, counting only
+    /// the kept columns.
+    pub current: Vec<(u64, u64)>,
+    pub planned: Vec<PlannedRowGroup>,
+}
+
+impl RowGroupPlan {
+    pub fn new(
+        path: &str,
+        md: &ParquetMetaData,
+        target: Option<RowGroupTarget>,
+        columns: ColumnProjection,
+    ) -> Result<Self, String> {
+        let schema = md.file_metadata().schema_descr();
+        let leaves = columns.leaves(schema)?;
+        let (mut kept, mut dropped) = (Vec::new(), Vec::new());
+        for (leaf, column) in schema.columns().iter().enumerate() {
+            if leaves.contains(&leaf) {
+                kept.push(column.path().string());
+            } else {
+                dropped.push(column.path().string());
+            }
+        }
+        let current: Vec<(u64, u64)> = md
+            .row_groups()
+            .iter()
+            .map(|rg| {
+                let bytes: i64 = leaves
+                    .iter()
+                    .map(|&leaf| rg.column(leaf).compressed_size())
+                    .sum();
+                (rg.num_rows().max(0) as u64, bytes.max(0) as u64)
+            })
+            .collect();
+        let planned = match target {
+            Some(target) => balance(&current, target),
+            None => current
+                .iter()
+                .enumerate()
+                .filter(|&(_, &(rows, _))| rows > 0)
+                .map(|(row_group, &(rows, bytes))| PlannedRowGroup {
+                    rows,
+                    estimated_bytes: bytes,
+                    sources: vec![SourceRows {
+                        row_group,
+                        first_row: 0,
+                        rows,
+                        whole: true,
+                    }],
+                })
+                .collect(),
+        };
+        Ok(RowGroupPlan {
+            path: path.to_string(),
+            target,
+            columns,
+            kept,
+            dropped,
+            current,
+            planned,
+        })
+    }
+
+    /// Current row groups spread over more than one planned row group.
+    pub fn splits(&self) -> usize {
+        (0..self.current.len())
+            .filter(|&rg| {
+                self.planned
+                    .iter()
+                    .filter(|group| group.sources.iter().any(|s| s.row_group == rg))
+                    .count()
+                    > 1
+            })
+            .count()
+    }
+
+    /// Planned row groups made from more than one current row group.
+    pub fn merges(&self) -> usize {
+        self.planned
+            .iter()
+            .filter(|group| group.sources.len() > 1)
+            .count()
+    }
+
+    /// Whether the rewrite would keep the row groups as they are.
+    pub fn is_unchanged(&self) -> bool {
+        self.planned.len() == self.current.len()
+            && self
+                .planned
+                .iter()
+                .all(|group| matches!(group.sources.as_slice(), [source] if source.whole))
+    }
+
+    /// Write the kept columns of `path` to `output` in the planned row
+    /// groups, keeping the codec of each column and the key-value metadata.
+    /// Returns the rows written.
+    pub fn execute(&self, output: &str) -> Result<u64, String> {
+        if same_file(&self.path, output) {
+            return Err("refusing to overwrite the input file".to_string());
+        }
+        let file = File::open(&self.path).map_err(|e| e.to_string())?;
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file).map_err(|e| e.to_string())?;
+        let md = builder.metadata().clone();
+        let parquet_schema = md.file_metadata().schema_descr();
+        let mask = ProjectionMask::leaves(parquet_schema, self.columns.leaves(parquet_schema)?);
+        let reader = builder
+            .with_projection(mask)
+            .with_batch_size(READ_BATCH)
+            .build()
+            .map_err(|e| e.to_string())?;
+        let schema = reader.schema();
+        let order: Vec<usize> = match self.columns.root_order(parquet_schema) {
+            Some(roots) => roots
+                .iter()
+                .filter_map(|root| schema.index_of(root).ok())
+                .collect(),
+            None => (0..schema.fields().len()).collect(),
+        };
+        let schema = Arc::new(schema.project(&order).map_err(|e| e.to_string())?);
+
+        let mut props = WriterProperties::builder()
+            .set_max_row_group_row_count(None)
+            .set_key_value_metadata(md.file_metadata().key_value_metadata().map(|kv| {
+                kv.iter()
+                    .filter(|kv| kv.key != ARROW_SCHEMA_KEY)
+                    .cloned()
+                    .collect::<Vec<KeyValue>>()
+            }));
+        if let Some(row_group) = md.row_groups().first() {
+            for column in row_group.columns() {
+                props = props
+                    .set_column_compression(column.column_path().clone(), column.compression());
+            }
+        }
+        let out = File::create(output).map_err(|e| e.to_string())?;
+        let mut writer =
+            ArrowWriter::try_new(out, schema, Some(props.build())).map_err(|e| e.to_string())?;
+
+        let mut sizes = self.planned.iter().map(|group| group.rows);
+        let mut room = sizes.next().unwrap_or(u64::MAX);
+        let mut written = 0;
+        for batch in reader {
+            let mut batch = batch
+                .and_then(|batch| batch.project(&order))
+                .map_err(|e| e.to_string())?;
+            while batch.num_rows() > 0 {
+                let take = (batch.num_rows() as u64).min(room) as usize;
+                writer
+                    .write(&batch.slice(0, take))
+                    .map_err(|e| e.to_string())?;
+                batch = batch.slice(take, batch.num_rows() - take);
+                written += take as u64;
+                room -= take as u64;
+                if room == 0 {
+                    writer.flush().map_err(|e| e.to_string())?;
+                    room = sizes.next().unwrap_or(u64::MAX);
+                }
+            }
+        }
+        writer.close().map_err(|e| e.to_string())?;
+        Ok(written)
+    }
+}
+
+/// As few row groups as stay within the target, evened out so the last one
+/// isn't a runt.
+fn balance(current: &[(u64, u64)], target: RowGroupTarget) -> Vec<PlannedRowGroup> {
+    let total_rows: u64 = current.iter().map(|&(rows, _)| rows).sum();
+    let total_bytes: u64 = current.iter().map(|&(_, bytes)| bytes).sum();
+    let count = match target {
+        RowGroupTarget::Rows(target) => total_rows.div_ceil(target),
+        RowGroupTarget::Bytes(target) => total_bytes.div_ceil(target),
+    }
+    .clamp(1, total_rows.max(1));
+
+    let mut planned = Vec::new();
+    // Estimated bytes of `planned`.
+    let mut planned_bytes = 0;
+    let mut group = PlannedRowGroup {
+        rows: 0,
+        estimated_bytes: 0,
+        sources: vec![],
+    };
+    for (row_group, &(rows, bytes)) in current.iter().enumerate() {
+        let bytes_per_row = bytes as f64 / rows.max(1) as f64;
+        let mut first_row = 0;
+        while first_row < rows {
+            let index = planned.len() as u64;
+            // Rows that still fit in the group being planned; the last
+            // one takes whatever is left.
+            let room = if index + 1 == count {
+                u64::MAX
+            } else {
+                match target {
+                    RowGroupTarget::Rows(_) => {
+                        let rows = total_rows / count + u64::from(index < total_rows % count);
+                        rows.saturating_sub(group.rows)
+                    }
+                    RowGroupTarget::Bytes(_) => {
+                        let end = total_bytes as f64 * (index + 1) as f64 / count as f64;
+                        let done = (planned_bytes + group.estimated_bytes) as f64;
+                        ((end - done) / bytes_per_row.max(f64::MIN_POSITIVE)).round() as u64
+                    }
+                }
+                // At least one row, so a single huge row can't stall
+                // the plan.
+                .max(1)
+            };
+            let take = room.min(rows - first_row);
+            group.rows += take;
+            group.estimated_bytes += (take as f64 * bytes_per_row).round() as u64;
+            group.sources.push(SourceRows {
+                row_group,
+                first_row,
+                rows: take,
+                whole: take == rows,
+            });
+            first_row += take;
+            if take == room {
+                planned_bytes += group.estimated_bytes;
+                planned.push(std::mem::replace(
+                    &mut group,
+                    PlannedRowGroup {
+                        rows: 0,
+                        estimated_bytes: 0,
+                        sources: vec![],
+                    },
+                ));
+            }
+        }
+    }
+    if group.rows > 0 {
+        planned.push(group);
+    }
+    planned
+}
+
+/// `3`, or `3 rows 0..1,000` when only part of it is taken.
+fn format_source(source: &SourceRows) -> String {
+    if source.whole {
+        source.row_group.to_string()
+    } else {
+        format!(
+            "{} rows {}..{}",
+            source.row_group,
+            commas(source.first_row),
+            commas(source.first_row + source.rows)
+        )
+    }
+}
+
+/// Sources with runs of whole row groups shortened to `0-3`.
+fn format_sources(sources: &[SourceRows]) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    let mut run: Option<(usize, usize)> = None;
+    let close = |run: &mut Option<(usize, usize)>, parts: &mut Vec<String>| match run.take() {
+        Some((first, last)) if first == last => parts.push(first.to_string()),
+        Some((first, last)) => parts.push(format!("{first}-{last}")),
+        None => {}
+    };
+    for source in sources {
+        match run {
+            Some((_, last)) if source.whole && source.row_group == last + 1 => {
+                run = run.map(|(first, _)| (first, source.row_group));
+            }
+            _ if source.whole => {
+                close(&mut run, &mut parts);
+                run = Some((source.row_group, source.row_group));
+            }
+            _ => {
+                close(&mut run, &mut parts);
+                parts.push(format_source(source));
+            }
+        }
+    }
+    close(&mut run, &mut parts);
+    parts.join(", ")
+}
+
+impl fmt::Display for RowGroupPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.target {
+            Some(target) => writeln!(
+                f,
+                "{}: {} row groups -> {} for a target of {} ({} merged, {} split)",
+                self.path,
+                self.current.len(),
+                self.planned.len(),
+                target,
+                self.merges(),
+                self.splits()
+            )?,
+            None => writeln!(
+                f,
+                "{}: {} row groups, kept as they are",
+                self.path,
+                self.current.len()
+            )?,
+        }
+        if !self.dropped.is_empty() {
+            writeln!(
+                f,
+                "keeping {} of {} columns, dropping {}",
+                self.kept.len(),
+                self.kept.len() + self.dropped.len(),
+                self.dropped.join(", ")
+            )?;
+        }
+        if self.is_unchanged() {
+            if self.target.is_some() {
+                writeln!(f, "the row groups already match the target")?;
+            }
+            if self.dropped.is_empty() {
+                return Ok(());
+            }
+        }
+        let rows: Vec<[String; 4]> = self
+            .planned
+            .iter()
+            .enumerate()
+            .map(|(idx, group)| {
+                [
+                    idx.to_string(),
+                    commas(group.rows),
+                    human_readable_bytes(group.estimated_bytes),
+                    format_sources(&group.sources),
+                ]
+            })
+            .collect();
+        let headers = ["New", "Rows", "Est. size", "From"];
+        let widths: Vec<usize> = (0..3)
+            .map(|idx| {
+                rows.iter()
+                    .map(|row| row[idx].chars().count())
+                    .chain([headers[idx].len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        writeln!(f)?;
+        let headers = headers.map(str::to_string);
+        for row in std::iter::once(&headers).chain(&rows) {
+            writeln!(
+                f,
+                "{:<new$}  {:>rows$}  {:>size$}  {}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                new = widths[0],
+                rows = widths[1],
+                size = widths[2]
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::parquet_ctx::open_reader;
+    use crate::file::write_test_file;
+    use arrow::array::{ArrayRef, Int64Array, RecordBatch, StringArray, StructArray};
+    use arrow::datatypes::{DataType, Field};
+    use parquet::file::reader::FileReader;
+
+    /// 1,000 rows of `id` in row groups of 100.
+    fn write_ids(name: &str) -> String {
+        let batch = RecordBatch::try_from_iter([(
+            "id",
+            Arc::new(Int64Array::from_iter_values(0..1000)) as ArrayRef,
+        )])
+        .unwrap();
+        let props = WriterProperties::builder()
+            .set_max_row_group_row_count(Some(100))
+            .set_key_value_metadata(Some(vec![KeyValue::new(
+                "origin".to_string(),
+                "test".to_string(),
+            )]))
+            .build();
+        write_test_file(&format!("rewrite-{name}"), &batch, Some(props))
+            .display()
+            .to_string()
+    }
+
+    fn plan_for(path: &str, md: &ParquetMetaData, target: RowGroupTarget) -> RowGroupPlan {
+        RowGroupPlan::new(path, md, Some(target), ColumnProjection::All).unwrap()
+    }
+
+    #[test]
+    fn test_select_and_drop_columns() {
+        let address = StructArray::from(vec![
+            (
+                Arc::new(Field::new("city", DataType::Utf8, false)),
+                Arc::new(StringArray::from_iter_values(
+                    (0..300).map(|i| format!("c{i}")),
+                )) as ArrayRef,
+            ),
+            (
+                Arc::new(Field::new("zip", DataType::Int64, false)),
+                Arc::new(Int64Array::from_iter_values(0..300)) as ArrayRef,
+            ),
+        ]);
+        let batch = RecordBatch::try_from_iter([
+            (
+                "id",
+                Arc::new(Int64Array::from_iter_values(0..300)) as ArrayRef,
+            ),
+            (
+                "payload",
+                Arc::new(Int64Array::from_iter_values(0..300)) as ArrayRef,
+            ),
+            ("address", Arc::new(address) as ArrayRef),
+        ])
+        .unwrap();
+        let props = WriterProperties::builder()
+            .set_max_row_group_row_count(Some(100))
+            .build();
+        let path = write_test_file("rewrite-columns", &batch, Some(props))
+            .display()
+            .to_string();
+        let (reader, _) = open_reader(&path).unwrap();
+        let md = reader.metadata_ptr();
+        let columns = |projection| RowGroupPlan::new(&path, &md, None, projection);
+
+        let select = columns(ColumnProjection::Select(vec![
+            "address.city".to_string(),
+            "id".to_string(),
+        ]))
+        .unwrap();
+        assert_eq!(select.kept, ["id", "address.city"]);
+        assert_eq!(select.dropped, ["payload", "address.zip"]);
+        assert!(select.is_unchanged());
+        assert!(
+            select
+                .to_string()
+                .contains("keeping 2 of 4 columns, dropping payload, address.zip\n")
+        );
+
+        let drop = columns(ColumnProjection::Drop(vec!["address".to_string()])).unwrap();
+        assert_eq!(drop.kept, ["id", "payload"]);
+        assert_eq!(
+            columns(ColumnProjection::Select(vec!["nope".to_string()])),
+            Err("no column 'nope'".to_string())
+        );
+        assert_eq!(
+            columns(ColumnProjection::Drop(vec![
+                "id".to_string(),
+                "payload".to_string(),
+                "address".to_string()
+            ])),
+            Err("no columns left to write".to_string())
+        );
+
+        let output = path.replace(".parquet", "-out.parquet");
+        let written = select.execute(&output);
+        let rewritten = File::open(&output)
+            .map_err(|e| e.to_string())
+            .and_then(|file| {
+                ParquetRecordBatchReaderBuilder::try_new(file).map_err(|e| e.to_string())
+            });
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&output).unwrap();
+
+        assert_eq!(written, Ok(300));
+        let rewritten = rewritten.unwrap();
+        // The top-level columns follow the order of the selection.
+        let fields: Vec<String> = rewritten
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.to_string())
+            .collect();
+        assert_eq!(fields.len(), 2);
+        assert!(fields[0].contains("\"address\"") && !fields[0].contains("zip"));
+        assert!(fields[1].contains("\"id\""));
+        assert_eq!(
+            rewritten
+                .metadata()
+                .row_groups()
+                .iter()
+                .map(|rg| rg.num_rows())
+                .collect::<Vec<_>>(),
+            [100, 100, 100]
+        );
+    }
+
+    #[test]
+    fn test_parse_target() {
+        assert_eq!("128MiB".parse(), Ok(RowGroupTarget::Bytes(128 << 20)));
+        assert_eq!("1.5 kb".parse(), Ok(RowGroupTarget::Bytes(1536)));
+        assert_eq!("500_000 rows".parse(), Ok(RowGroupTarget::Rows(500_000)));
+        assert_eq!("250rows".parse(), Ok(RowGroupTarget::Rows(250)));
+        assert!("0 rows".parse::<RowGroupTarget>().is_err());
+        assert!("big".parse::<RowGroupTarget>().is_err());
+    }
+
+    #[test]
+    fn test_plan_and_execute() {
+        let path = write_ids("plan");
+        let (reader, _) = open_reader(&path).unwrap();
+        let md = reader.metadata_ptr();
+
+        // 100-row groups into 250-row ones: two whole groups and half of
+        // the next, then the other half and so on.
+        let plan = plan_for(&path, &md, RowGroupTarget::Rows(250));
+        assert_eq!(
+            plan.planned.iter().map(|g| g.rows).collect::<Vec<_>>(),
+            [250, 250, 250, 250]
+        );
+        assert_eq!(
+            format_sources(&plan.planned[0].sources),
+            "0-1, 2 rows 0..50"
+        );
+        assert_eq!(
+            format_sources(&plan.planned[1].sources),
+            "2 rows 50..100, 3-4"
+        );
+        assert_eq!((plan.merges(), plan.splits()), (4, 2));
+        assert!(!plan.is_unchanged());
+        assert!(plan.to_string().contains("\nNew  Rows"));
+
+        // 300 rows would leave a runt of 100, so the rows are evened out.
+        let even = plan_for(&path, &md, RowGroupTarget::Rows(300));
+        assert_eq!(
+            even.planned.iter().map(|g| g.rows).collect::<Vec<_>>(),
+            [250, 250, 250, 250]
+        );
+
+        // Splitting into 40-row groups.
+        let split = plan_for(&path, &md, RowGroupTarget::Rows(40));
+        assert_eq!(split.planned.len(), 25);
+        assert_eq!(split.splits(), 10);
+        assert!(plan_for(&path, &md, RowGroupTarget::Rows(100)).is_unchanged());
+
+        // Half the file's bytes makes two row groups of about 500 rows.
+        let total: i64 = md.row_groups().iter().map(|rg| rg.compressed_size()).sum();
+        let by_size = plan_for(
+            &path,
+            &md,
+            RowGroupTarget::Bytes((total as u64).div_ceil(2)),
+        );
+        assert_eq!(by_size.planned.len(), 2);
+        assert!(by_size.planned[0].rows.abs_diff(500) < 20);
+
+        let output = path.replace(".parquet", "-out.parquet");
+        let written = plan.execute(&output);
+        let overwrite = plan.execute(&path);
+        let rewritten = open_reader(&output).map(|(reader, _)| reader);
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&output).unwrap();
+
+        assert_eq!(written, Ok(1000));
+        assert_eq!(
+            overwrite,
+            Err("refusing to overwrite the input file".to_string())
+        );
+        let rewritten = rewritten.unwrap();
+        let md = rewritten.metadata();
+        assert_eq!(
+            md.row_groups()
+                .iter()
+                .map(|rg| rg.num_rows())
+                .collect::<Vec<_>>(),
+            [250, 250, 250, 250]
+        );
+        assert!(
+            md.file_metadata()
+                .key_value_metadata()
+                .unwrap()
+                .iter()
+                .any(|kv| kv.key == "origin")
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use std::fmt;
+
+use parquet::arrow::ProjectionMask;
+use parquet::arrow::arrow_reader::{ArrowReaderMetadata, ParquetRecordBatchReaderBuilder};
+use parquet::file::reader::FileReader;
+use serde::Serialize;
+
+use crate::file::error::FileIOError;
+use crate::file::parquet_ctx::open_reader;
+use crate::file::utils::commas;
+
+/// Rows actually decoded from one column chunk, or why decoding stopped.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ColumnRowCount {
+    pub column: String,
+    pub decoded: Result<u64, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RowGroupRowCount {
+    /// `num_rows` from the row group metadata.
+    pub declared: i64,
+    pub columns: Vec<ColumnRowCount>,
+}
+
+/// The three places a file states how many rows it has: the file footer, the
+/// row groups, and the data pages themselves. A writer bug or a truncated
+/// file shows up as a disagreement between them.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RowCountReport {
+    pub path: String,
+    /// `num_rows` from the file metadata.
+    pub file_rows: i64,
+    pub row_groups: Vec<RowGroupRowCount>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RowCountMismatch {
+    /// The footer disagrees with the sum of the row groups.
+    FileTotal { file_rows: i64, row_group_rows: i64 },
+    /// A column chunk decoded to a different number of rows than declared.
+    Column {
+        row_group: usize,
+        column: String,
+        declared: i64,
+        decoded: u64,
+    },
+    /// A column chunk could not be decoded to the end.
+    Unreadable {
+        row_group: usize,
+        column: String,
+        error: String,
+    },
+}
+
+impl fmt::Display for RowCountMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let signed = |n: i64| {
+            let sign = if n < 0 { "-" } else { "" };
+            format!("{sign}{}", commas(n.unsigned_abs()))
+        };
+        match self {
+            RowCountMismatch::FileTotal {
+                file_rows,
+                row_group_rows,
+            } => write!(
+                f,
+                "file metadata says {} rows, row groups add up to {}",
+                signed(*file_rows),
+                signed(*row_group_rows)
+            ),
+            RowCountMismatch::Column {
+                row_group,
+                column,
+                declared,
+                decoded,
+            } => write!(
+                f,
+                "row group {row_group}, {column}: declared {}, decoded {}",
+                signed(*declared),
+                commas(*decoded)
+            ),
+            RowCountMismatch::Unreadable {
+                row_group,
+                column,
+                error,
+            } => write!(f, "row group {row_group}, {column}: {error}"),
+        }
+    }
+}
+
+impl RowCountReport {
+    /// Decode every column chunk of the file and count its rows. This reads
+    /// the whole file, so it is much slower than anything else parqeye does.
+    pub fn from_file(path: &str) -> Result<Self, FileIOError> {
+        let (reader, _) = open_reader(path)?;
+        let file_rows = reader.metadata().file_metadata().num_rows();
+
+        let file = reader.file();
+        let invalid = |e: parquet::errors::ParquetError| FileIOError::InvalidParquet {
+            path: path.into(),
+            details: e.to_string(),
+        };
+        let metadata = ArrowReaderMetadata::try_new(reader.metadata_ptr(), Default::default())
+            .map_err(invalid)?;
+        let schema = metadata.metadata().file_metadata().schema_descr_ptr();
+
+        let mut row_groups = Vec::new();
+        for (rg_idx, rg) in metadata.metadata().row_groups().iter().enumerate() {
+            let mut columns = Vec::new();
+            for (col_idx, col) in schema.columns().iter().enumerate() {
+                let decoded = file
+                    .try_clone()
+                    .map_err(|e| e.to_string())
+                    .and_then(|file| {
+                        let reader = ParquetRecordBatchReaderBuilder::new_with_metadata(
+                            file,
+                            metadata.clone(),
+                        )
+                        .with_row_groups(vec![rg_idx])
+                        .with_projection(ProjectionMask::leaves(&schema, [col_idx]))
+                        .build()
+                        .map_err(|e| e.to_string())?;
+                        reader.into_iter().try_fold(0u64, |rows, batch| {
+                            batch
+                                .map(|b| rows + b.num_rows() as u64)
+                                .map_err(|e| e.to_string())
+                        })
+                    });
+                columns.push(ColumnRowCount {
+                    column: col.path().string(),
+                    decoded,
+                });
+            }
+            row_groups.push(RowGroupRowCount {
+                declared: rg.num_rows(),
+                columns,
+            });
+        }
+
+        Ok(Self {
+            path: path.to_string(),
+            file_rows,
+            row_groups,
+        })
+    }
+
+    pub fn row_group_rows(&self) -> i64 {
+        self.row_groups.iter().map(|rg| rg.declared).sum()
+    }
+
+    pub fn mismatches(&self) -> Vec<RowCountMismatch> {
+        let mut mismatches = Vec::new();
+        if self.file_rows != self.row_group_rows() {
+            mismatches.push(RowCountMismatch::FileTotal {
+                file_rows: self.file_rows,
+                row_group_rows: self.row_group_rows(),
+            });
+        }
+        for (row_group, rg) in self.row_groups.iter().enumerate() {
+            for col in &rg.columns {
+                match &col.decoded {
+                    Ok(decoded) if *decoded as i64 == rg.declared => {}
+                    Ok(decoded) => mismatches.push(RowCountMismatch::Column {
+                        row_group,
+                        column: col.column.clone(),
+                        declared: rg.declared,
+                        decoded: *decoded,
+                    }),
+                    Err(error) => mismatches.push(RowCountMismatch::Unreadable {
+                        row_group,
+                        column: col.column.clone(),
+                        error: error.clone(),
+                    }),
+                }
+            }
+        }
+        mismatches
+    }
+}
+
+impl fmt::Display for RowCountReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mismatches = self.mismatches();
+        if mismatches.is_empty() {
+            return write!(
+                f,
+                "{}: {} rows in {} row group{}, all counts agree",
+                self.path,
+                commas(self.file_rows.max(0) as u64),
+                self.row_groups.len(),
+                if self.row_groups.len() == 1 { "" } else { "s" }
+            );
+        }
+        write!(f, "{}: {} mismatches", self.path, mismatches.len())?;
+        for mismatch in mismatches {
+            write!(f, "\n  {mismatch}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_data_path;
+
+    #[test]
+    fn test_counts_agree_for_valid_files() {
+        for name in ["alltypes_plain.parquet", "nulls.snappy.parquet"] {
+            let report = RowCountReport::from_file(&test_data_path(name)).unwrap();
+            assert_eq!(report.mismatches(), vec![], "{report}");
+            assert_eq!(report.row_group_rows(), report.file_rows);
+        }
+    }
+
+    #[test]
+    fn test_nested_columns_count_top_level_rows() {
+        let report = RowCountReport::from_file(&test_data_path("nulls.snappy.parquet")).unwrap();
+        assert_eq!(report.row_groups.len(), 4);
+        let list = report.row_groups[0]
+            .columns
+            .iter()
+            .find(|c| c.column.starts_with("l."))
+            .unwrap();
+        assert_eq!(list.decoded, Ok(report.row_groups[0].declared as u64));
+    }
+
+    #[test]
+    fn test_mismatches_are_reported() {
+        let report = RowCountReport {
+            path: "broken.parquet".to_string(),
+            file_rows: 300,
+            row_groups: vec![
+                RowGroupRowCount {
+                    declared: 100,
+                    columns: vec![
+                        ColumnRowCount {
+                            column: "id".to_string(),
+                            decoded: Ok(100),
+                        },
+                        ColumnRowCount {
+                            column: "name".to_string(),
+                            decoded: Ok(90),
+                        },
+                    ],
+                },
+                RowGroupRowCount {
+                    declared: 100,
+                    columns: vec![ColumnRowCount {
+                        column: "id".to_string(),
+                        decoded: Err("unexpected end of file".to_string()),
+                    }],
+                },
+            ],
+        };
+        let lines: Vec<String> = report
+            .mismatches()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                "file metadata says 300 rows, row groups add up to 200",
+                "row group 0, name: declared 100, decoded 90",
+                "row group 1, id: unexpected end of file",
+            ]
+        );
+        assert!(
+            report
+                .to_string()
+                .starts_with("broken.parquet: 3 mismatches")
+        );
+    }
+}
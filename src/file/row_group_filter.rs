@@ -0,0 +1,243 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::file::row_groups::RowGroupStats;
+
+/// Row group property a filter condition compares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Rows,
+    /// Compressed size in bytes.
+    Size,
+    /// Uncompressed size in bytes.
+    Uncompressed,
+    /// Uncompressed / compressed size.
+    Ratio,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+/// A single `field op value` comparison, e.g. `size > 512MB`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    pub field: Field,
+    pub op: Op,
+    pub value: f64,
+}
+
+/// Conditions a row group must all satisfy, e.g. `rows < 10000 and ratio < 1.1`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowGroupFilter {
+    pub conditions: Vec<Condition>,
+    source: String,
+}
+
+impl RowGroupFilter {
+    pub fn matches(&self, row_group: &RowGroupStats) -> bool {
+        self.conditions.iter().all(|c| c.matches(row_group))
+    }
+
+    /// Indices of the row groups that match, in file order.
+    pub fn matching(&self, row_groups: &[RowGroupStats]) -> Vec<usize> {
+        row_groups
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, rg)| self.matches(rg).then_some(idx))
+            .collect()
+    }
+}
+
+impl fmt::Display for RowGroupFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl Condition {
+    pub fn matches(&self, row_group: &RowGroupStats) -> bool {
+        let actual = match self.field {
+            Field::Rows => row_group.rows as f64,
+            Field::Size => row_group.compressed_size as f64,
+            Field::Uncompressed => row_group.uncompressed_size as f64,
+            Field::Ratio => row_group.compression_ratio,
+        };
+        match self.op {
+            Op::Lt => actual < self.value,
+            Op::Le => actual <= self.value,
+            Op::Gt => actual > self.value,
+            Op::Ge => actual >= self.value,
+            Op::Eq => actual == self.value,
+            Op::Ne => actual != self.value,
+        }
+    }
+}
+
+impl FromStr for RowGroupFilter {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let source = input.split_whitespace().collect::<Vec<_>>().join(" ");
+        if source.is_empty() {
+            return Err("empty filter".to_string());
+        }
+        let conditions = source
+            .split("&&")
+            .flat_map(|part| part.split(" and "))
+            .map(str::parse)
+            .collect::<Result<Vec<Condition>, _>>()?;
+        Ok(Self { conditions, source })
+    }
+}
+
+impl FromStr for Condition {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        // Two-character operators first so `<=` is not read as `<`.
+        const OPS: [(&str, Op); 7] = [
+            ("<=", Op::Le),
+            (">=", Op::Ge),
+            ("==", Op::Eq),
+            ("!=", Op::Ne),
+            ("<", Op::Lt),
+            (">", Op::Gt),
+            ("=", Op::Eq),
+        ];
+        let (at, symbol, op) = OPS
+            .iter()
+            .filter_map(|(symbol, op)| input.find(symbol).map(|at| (at, *symbol, *op)))
+            .min_by_key(|(at, ..)| *at)
+            .ok_or_else(|| format!("'{input}' has no comparison (use <, <=, >, >=, = or !=)"))?;
+
+        let (name, value) = (input[..at].trim(), input[at + symbol.len()..].trim());
+        let field = match name.to_ascii_lowercase().as_str() {
+            "rows" => Field::Rows,
+            "size" | "compressed" => Field::Size,
+            "uncompressed" => Field::Uncompressed,
+            "ratio" => Field::Ratio,
+            other => {
+                return Err(format!(
+                    "unknown field '{other}' (use rows, size, uncompressed or ratio)"
+                ));
+            }
+        };
+        let value = match field {
+            Field::Size | Field::Uncompressed => parse_bytes(value),
+            Field::Rows | Field::Ratio => parse_number(value),
+        }
+        .ok_or_else(|| format!("'{value}' is not a valid {name} value"))?;
+
+        Ok(Self { field, op, value })
+    }
+}
+
+/// Parse a number, allowing `_` and `,` separators.
+pub(crate) fn parse_number(value: &str) -> Option<f64> {
+    value
+        .replace(['_', ','], "")
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite())
+}
+
+/// Parse a byte size like `512MB`, `1.5 GiB` or `4096`. Units are binary,
+/// matching how sizes are displayed.
+pub(crate) fn parse_bytes(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| c.is_ascii_alphabetic())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let multiplier: f64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1.0,
+        "k" | "kb" | "kib" => 1024.0,
+        "m" | "mb" | "mib" => 1024.0 * 1024.0,
+        "g" | "gb" | "gib" => 1024.0 * 1024.0 * 1024.0,
+        "t" | "tb" | "tib" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    parse_number(number.trim()).map(|n| n * multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row_group(rows: i64, compressed_size: i64, uncompressed_size: i64) -> RowGroupStats {
+        RowGroupStats {
+            idx: 0,
+            rows,
+            compressed_size,
+            uncompressed_size,
+            compression_ratio: uncompressed_size as f64 / compressed_size as f64,
+            column_metadata: vec![],
+            sorting_columns: vec![],
+        }
+    }
+
+    #[test]
+    fn test_parse_conditions() {
+        let filter: RowGroupFilter = "rows < 10_000 and ratio<=1.1 && size > 512MB"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            filter.conditions,
+            vec![
+                Condition {
+                    field: Field::Rows,
+                    op: Op::Lt,
+                    value: 10_000.0
+                },
+                Condition {
+                    field: Field::Ratio,
+                    op: Op::Le,
+                    value: 1.1
+                },
+                Condition {
+                    field: Field::Size,
+                    op: Op::Gt,
+                    value: 512.0 * 1024.0 * 1024.0
+                },
+            ]
+        );
+        assert_eq!(
+            filter.to_string(),
+            "rows < 10_000 and ratio<=1.1 && size > 512MB"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!("".parse::<RowGroupFilter>().is_err());
+        assert!("rows".parse::<RowGroupFilter>().is_err());
+        assert!("columns > 3".parse::<RowGroupFilter>().is_err());
+        assert!("size > 3 parsecs".parse::<RowGroupFilter>().is_err());
+        assert!("rows < lots".parse::<RowGroupFilter>().is_err());
+    }
+
+    #[test]
+    fn test_matching() {
+        let row_groups = vec![
+            row_group(100, 1000, 1050),
+            row_group(50_000, 1000, 4000),
+            row_group(200, 2048, 8192),
+        ];
+        let small: RowGroupFilter = "rows < 10000".parse().unwrap();
+        assert_eq!(small.matching(&row_groups), vec![0, 2]);
+
+        let poorly_compressed: RowGroupFilter = "ratio < 1.1".parse().unwrap();
+        assert_eq!(poorly_compressed.matching(&row_groups), vec![0]);
+
+        let both: RowGroupFilter = "rows < 10000 and size >= 2KB".parse().unwrap();
+        assert_eq!(both.matching(&row_groups), vec![2]);
+    }
+}
@@ -1,17 +1,28 @@
-use parquet::basic::{Encoding, PageType};
+use parquet::basic::{Encoding, LogicalType, PageType};
 use parquet::column::page::{Page, PageReader};
 use parquet::file::metadata::{ColumnChunkMetaData, RowGroupMetaData};
 use parquet::file::reader::FileReader;
-use parquet::file::reader::{ChunkReader, SerializedFileReader};
+
+use crate::file::geo::GeoStats;
+use crate::file::logical;
+use crate::file::page_header::PageHeaders;
+use crate::file::parquet_ctx::SharedReader;
 use parquet::file::statistics::Statistics;
 
 use itertools::Itertools;
+use rayon::prelude::*;
+use serde::Serialize;
 use std::iter::Iterator;
 
+#[derive(Serialize)]
 pub struct RowGroupPageInfo {
     pub page_infos: Vec<PageInfo>,
+    /// Why scanning stopped early, e.g. an unsupported encoding or a
+    /// truncated file. `page_infos` holds the pages read before it.
+    pub error: Option<String>,
 }
 
+#[derive(Serialize)]
 pub struct HasStats {
     pub has_stats: bool,
     pub has_dictionary_page: bool,
@@ -19,13 +30,62 @@ pub struct HasStats {
     pub has_page_encoding_stats: bool,
 }
 
+#[derive(Serialize)]
 pub struct PageInfo {
     pub page_type: String,
     pub size: usize,
+    /// Values in the page, including nulls.
     pub rows: usize,
     pub encoding: String,
+    /// Fields only V2 data pages have.
+    pub v2: Option<DataPageV2Info>,
+}
+
+/// What a V2 data page header records beyond a V1 one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DataPageV2Info {
+    pub num_nulls: u32,
+    pub num_rows: u32,
+    /// Lengths of the definition and repetition levels, which V2 pages
+    /// store uncompressed ahead of the values.
+    pub def_levels_byte_len: u32,
+    pub rep_levels_byte_len: u32,
+    /// Whether the values are compressed. Writers may leave a page
+    /// uncompressed when compressing it doesn't pay off.
+    pub is_compressed: bool,
 }
 
+impl PageInfo {
+    pub fn is_data_page(&self) -> bool {
+        self.page_type.starts_with("Data Page")
+    }
+}
+
+/// Data pages of a column chunk, counted by encoding.
+#[derive(Serialize)]
+pub struct EncodingBreakdown {
+    /// (encoding, data pages), in order of first appearance.
+    pub data_pages: Vec<(String, usize)>,
+    /// Some data pages are dictionary encoded and others are not, i.e. the
+    /// writer gave up on the dictionary part way through the chunk.
+    pub dictionary_fallback: bool,
+    /// How the encodings the footer lists for the chunk differ from those
+    /// its pages use; `None` if they agree or the pages couldn't be read.
+    pub footer_mismatch: Option<EncodingMismatch>,
+}
+
+/// Encodings the footer lists for a column chunk against the ones its pages
+/// use. Readers trust the footer, e.g. to filter on the dictionary only when
+/// every data page is dictionary encoded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EncodingMismatch {
+    /// Listed in the footer, used by no page.
+    pub unused: Vec<String>,
+    /// Used by pages, missing from the footer.
+    pub undeclared: Vec<String>,
+}
+
+#[derive(Serialize)]
 pub struct RowGroupColumnStats {
     pub min: Option<String>,
     pub max: Option<String>,
@@ -33,103 +93,197 @@ pub struct RowGroupColumnStats {
     pub distinct_count: Option<u64>,
 }
 
+#[derive(Serialize)]
 pub struct RowGroupColumnMetadata {
     pub file_offset: u64,
+    /// (start, length) of the chunk in the file, dictionary page included.
+    /// This is the range a reader fetches to scan the column.
+    pub byte_range: (u64, u64),
     pub column_path: String,
     pub has_stats: HasStats,
     pub statistics: Option<RowGroupColumnStats>,
+    /// Bounding box and geometry types of a GEOMETRY or GEOGRAPHY chunk.
+    pub geo_statistics: Option<GeoStats>,
     pub total_compressed_size: i64,
     pub total_uncompressed_size: i64,
     pub compression_type: String,
     pub pages: RowGroupPageInfo,
+    /// The pages' thrift headers, decoded from the file.
+    pub page_headers: PageHeaders,
+    pub encodings: EncodingBreakdown,
+}
+
+/// A column chunk's dictionary page, as its header describes it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DictionaryPageSize {
+    pub compressed_size: u64,
+    pub uncompressed_size: u64,
+    /// Distinct values in the dictionary.
+    pub entries: u64,
 }
 
-pub struct RowGroupAvgMedianStats {
-    pub avg_compressed_size: f64,
-    pub median_compressed_size: f64,
-    pub avg_uncompressed_size: f64,
-    pub median_uncompressed_size: f64,
-    pub avg_rows_per_rg: f64,
-    pub median_rows_per_rg: f64,
-    pub avg_compression_ratio: f64,
-    pub median_compression_ratio: f64,
+/// Distribution of one row group property across all row groups.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Aggregate {
+    pub min: f64,
+    pub avg: f64,
+    pub median: f64,
+    pub p95: f64,
+    pub max: f64,
 }
 
-impl RowGroupAvgMedianStats {
+impl Aggregate {
+    pub fn new(values: impl IntoIterator<Item = f64>) -> Self {
+        let sorted: Vec<f64> = values
+            .into_iter()
+            .sorted_by(|a, b| a.total_cmp(b))
+            .collect();
+        let n = sorted.len();
+        if n == 0 {
+            return Self::default();
+        }
+        // Nearest-rank percentile
+        let p95_rank = (n as f64 * 0.95).ceil() as usize;
+        Self {
+            min: sorted[0],
+            avg: sorted.iter().sum::<f64>() / n as f64,
+            median: sorted[n / 2],
+            p95: sorted[p95_rank.clamp(1, n) - 1],
+            max: sorted[n - 1],
+        }
+    }
+}
+
+/// Min / avg / median / p95 / max of row counts, sizes and compression
+/// ratios across the file's row groups.
+#[derive(Serialize)]
+pub struct RowGroupAggregates {
+    pub rows: Aggregate,
+    pub compressed_size: Aggregate,
+    pub uncompressed_size: Aggregate,
+    pub compression_ratio: Aggregate,
+}
+
+impl RowGroupAggregates {
     pub fn new(row_groups_stats: &[RowGroupStats]) -> Self {
-        // TODO: parallelize or iterate row group stats once
         Self {
-            avg_compressed_size: row_groups_stats
-                .iter()
-                .map(|rg| rg.compressed_size)
-                .sum::<i64>() as f64
-                / row_groups_stats.len() as f64,
-            median_compressed_size: row_groups_stats
-                .iter()
-                .map(|rg| rg.compressed_size)
-                .sorted()
-                .nth(row_groups_stats.len() / 2)
-                .unwrap_or(0) as f64,
-            avg_uncompressed_size: row_groups_stats
-                .iter()
-                .map(|rg| rg.uncompressed_size)
-                .sum::<i64>() as f64
-                / row_groups_stats.len() as f64,
-            median_uncompressed_size: row_groups_stats
-                .iter()
-                .map(|rg| rg.uncompressed_size)
-                .sorted()
-                .nth(row_groups_stats.len() / 2)
-                .unwrap_or(0) as f64,
-            avg_rows_per_rg: row_groups_stats.iter().map(|rg| rg.rows).sum::<i64>() as f64
-                / row_groups_stats.len() as f64,
-            median_rows_per_rg: row_groups_stats
-                .iter()
-                .map(|rg| rg.rows)
-                .sorted()
-                .nth(row_groups_stats.len() / 2)
-                .unwrap_or(0) as f64,
-            avg_compression_ratio: row_groups_stats
-                .iter()
-                .map(|rg| rg.compression_ratio)
-                .sum::<f64>()
-                / row_groups_stats.len() as f64,
-            median_compression_ratio: row_groups_stats
-                .iter()
-                .map(|rg| rg.compression_ratio)
-                .sorted_by(|a, b| a.partial_cmp(b).unwrap())
-                .nth(row_groups_stats.len() / 2)
-                .unwrap_or(0.),
+            rows: Aggregate::new(row_groups_stats.iter().map(|rg| rg.rows as f64)),
+            compressed_size: Aggregate::new(
+                row_groups_stats.iter().map(|rg| rg.compressed_size as f64),
+            ),
+            uncompressed_size: Aggregate::new(
+                row_groups_stats
+                    .iter()
+                    .map(|rg| rg.uncompressed_size as f64),
+            ),
+            compression_ratio: Aggregate::new(
+                row_groups_stats.iter().map(|rg| rg.compression_ratio),
+            ),
         }
     }
 }
 
+/// How the compressed size of the row groups in the last quarter of the
+/// file compares with the first quarter, as a factor: well below 1 when a
+/// writer flushed smaller and smaller row groups, e.g. under memory
+/// pressure. Takes (byte offset, compressed size) pairs in any order; `None`
+/// for fewer than 4 row groups.
+pub fn size_drift(row_groups: &[(u64, i64)]) -> Option<f64> {
+    if row_groups.len() < 4 {
+        return None;
+    }
+    let sizes: Vec<f64> = row_groups
+        .iter()
+        .sorted_by_key(|(offset, _)| *offset)
+        .map(|(_, size)| *size as f64)
+        .collect();
+    let quarter = sizes.len() / 4;
+    let mean = |sizes: &[f64]| sizes.iter().sum::<f64>() / sizes.len() as f64;
+    let first = mean(&sizes[..quarter]);
+    (first > 0.0).then(|| mean(&sizes[sizes.len() - quarter..]) / first)
+}
+
+/// Gap below which object store readers merge neighbouring range requests
+/// into one (the `object_store` crate's default).
+pub const RANGE_COALESCE_GAP: u64 = 1024 * 1024;
+
+/// Merge (start, length) ranges that overlap or are at most `max_gap` bytes
+/// apart, the way a remote reader batches its requests.
+pub fn coalesce_ranges(ranges: &[(u64, u64)], max_gap: u64) -> Vec<(u64, u64)> {
+    let mut merged: Vec<(u64, u64)> = Vec::new();
+    for &(start, len) in ranges.iter().sorted() {
+        match merged.last_mut() {
+            Some((prev_start, prev_len)) if start <= *prev_start + *prev_len + max_gap => {
+                let end = (*prev_start + *prev_len).max(start + len);
+                *prev_len = end - *prev_start;
+            }
+            _ => merged.push((start, len)),
+        }
+    }
+    merged
+}
+
+/// HTTP `Range` header value for (start, length); the end is inclusive.
+pub fn http_range(start: u64, len: u64) -> String {
+    format!("bytes={}-{}", start, (start + len).saturating_sub(1))
+}
+
+#[derive(Serialize)]
 pub struct RowGroups {
     pub row_groups: Vec<RowGroupStats>,
-    pub avg_median_stats: RowGroupAvgMedianStats,
+    pub aggregates: RowGroupAggregates,
 }
 
 impl RowGroups {
-    pub fn from_file_reader<R: ChunkReader + 'static>(
-        reader: &SerializedFileReader<R>,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn from_file_reader(reader: &SharedReader) -> Result<Self, Box<dyn std::error::Error>> {
+        // Reading the pages of every chunk dominates opening large files,
+        // so the row groups, and their chunks below, are read in parallel.
         let row_groups = (0..reader.metadata().num_row_groups())
+            .into_par_iter()
             .map(|idx| RowGroupStats::from_file_reader(reader, idx))
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e as Box<dyn std::error::Error>)?;
 
-        let avg_median_stats = RowGroupAvgMedianStats::new(&row_groups);
+        let aggregates = RowGroupAggregates::new(&row_groups);
 
         Ok(Self {
             row_groups,
-            avg_median_stats,
+            aggregates,
         })
     }
 
     pub fn num_row_groups(&self) -> usize {
         self.row_groups.len()
     }
+
+    /// Index in the file of the first row of row group `row_group`.
+    pub fn first_row(&self, row_group: usize) -> usize {
+        self.row_groups[..row_group.min(self.row_groups.len())]
+            .iter()
+            .map(|rg| rg.rows as usize)
+            .sum()
+    }
+
+    /// The row group holding row `row` of the file.
+    pub fn row_group_of_row(&self, row: usize) -> Option<usize> {
+        let mut end = 0;
+        self.row_groups.iter().position(|rg| {
+            end += rg.rows as usize;
+            row < end
+        })
+    }
+
+    /// Column chunks whose pages could not all be read.
+    pub fn page_errors(&self) -> usize {
+        self.row_groups
+            .iter()
+            .flat_map(|rg| &rg.column_metadata)
+            .filter(|c| c.pages.error.is_some())
+            .count()
+    }
 }
 
+#[derive(Serialize)]
 pub struct RowGroupStats {
     pub idx: usize,
     pub rows: i64,
@@ -137,22 +291,87 @@ pub struct RowGroupStats {
     pub uncompressed_size: i64,
     pub compression_ratio: f64,
     pub column_metadata: Vec<RowGroupColumnMetadata>,
+    /// The sort order the writer declared for the row group's rows, most
+    /// significant column first. Empty when none was declared.
+    pub sorting_columns: Vec<SortingColumnInfo>,
+}
+
+/// One column of a row group's declared sort order.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SortingColumnInfo {
+    /// Dotted path of the leaf column.
+    pub column_path: String,
+    pub descending: bool,
+    pub nulls_first: bool,
+}
+
+impl std::fmt::Display for SortingColumnInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            self.column_path,
+            sort_direction(self.descending, self.nulls_first)
+        )
+    }
+}
+
+/// `↑` or `↓`, followed by where nulls go when they come first. Nulls last
+/// is what most writers do and is left out.
+pub fn sort_direction(descending: bool, nulls_first: bool) -> String {
+    let arrow = if descending { "↓" } else { "↑" };
+    if nulls_first {
+        format!("{arrow} nulls first")
+    } else {
+        arrow.to_string()
+    }
 }
 
 impl RowGroupStats {
-    pub fn from_file_reader<R: ChunkReader + 'static>(
-        reader: &SerializedFileReader<R>,
+    /// (start, length) spanning all of the row group's column chunks.
+    pub fn byte_range(&self) -> (u64, u64) {
+        let start = self
+            .column_metadata
+            .iter()
+            .map(|c| c.byte_range.0)
+            .min()
+            .unwrap_or(0);
+        let end = self
+            .column_metadata
+            .iter()
+            .map(|c| c.byte_range.0 + c.byte_range.1)
+            .max()
+            .unwrap_or(0);
+        (start, end.saturating_sub(start))
+    }
+
+    pub fn from_file_reader(
+        reader: &SharedReader,
         idx: usize,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
         let rg_md: &RowGroupMetaData = reader.metadata().row_group(idx);
         let compressed_size = rg_md.columns().iter().map(|c| c.compressed_size()).sum();
         let uncompressed_size = rg_md.columns().iter().map(|c| c.uncompressed_size()).sum();
         let compression_ratio = uncompressed_size as f64 / compressed_size as f64;
 
         let column_metadata = (0..rg_md.num_columns())
+            .into_par_iter()
             .map(|col_idx| RowGroupColumnMetadata::from_file_reader(reader, idx, col_idx))
             .collect::<Result<Vec<_>, _>>()?;
 
+        let schema = reader.metadata().file_metadata().schema_descr();
+        let sorting_columns = rg_md
+            .sorting_columns()
+            .into_iter()
+            .flatten()
+            .filter(|sort| (sort.column_idx as usize) < schema.num_columns())
+            .map(|sort| SortingColumnInfo {
+                column_path: schema.column(sort.column_idx as usize).path().string(),
+                descending: sort.descending,
+                nulls_first: sort.nulls_first,
+            })
+            .collect();
+
         Ok(RowGroupStats {
             idx,
             rows: rg_md.num_rows(),
@@ -160,28 +379,83 @@ impl RowGroupStats {
             uncompressed_size,
             compression_ratio,
             column_metadata,
+            sorting_columns,
         })
     }
 }
 
 impl RowGroupColumnMetadata {
-    pub fn from_file_reader<R: ChunkReader + 'static>(
-        reader: &SerializedFileReader<R>,
+    /// The chunk's dictionary page, if its headers could be read and it has
+    /// one.
+    pub fn dictionary_page(&self) -> Option<DictionaryPageSize> {
+        self.page_headers
+            .headers
+            .iter()
+            .find(|header| header.page_type == "Dictionary Page")
+            .map(|header| DictionaryPageSize {
+                compressed_size: header.compressed_size.max(0) as u64,
+                uncompressed_size: header.uncompressed_size.max(0) as u64,
+                entries: header.num_values.unwrap_or(0).max(0) as u64,
+            })
+    }
+
+    pub fn from_file_reader(
+        reader: &SharedReader,
         rg_idx: usize,
         col_idx: usize,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
         let rg_md = reader.metadata().row_group(rg_idx);
         let column_chunk: &ColumnChunkMetaData = rg_md.column(col_idx);
 
-        let mut page_reader = reader
-            .get_row_group(rg_idx)?
-            .get_column_page_reader(col_idx)?;
-        let pages = Self::make_page_info(&mut page_reader)?;
+        let (pages, data_page_encodings, dictionary_encoding) = match reader
+            .get_row_group(rg_idx)
+            .and_then(|rg| rg.get_column_page_reader(col_idx))
+        {
+            Ok(mut page_reader) => Self::make_page_info(&mut page_reader),
+            Err(e) => (
+                RowGroupPageInfo {
+                    page_infos: vec![],
+                    error: Some(e.to_string()),
+                },
+                vec![],
+                None,
+            ),
+        };
+        let footer_mismatch = match pages.error {
+            Some(_) => None,
+            None => EncodingMismatch::new(
+                &column_chunk.encodings().collect_vec(),
+                data_page_encodings
+                    .iter()
+                    .copied()
+                    .chain(dictionary_encoding),
+            ),
+        };
+
+        // Prefer the writer's page encoding stats; the reader only keeps them
+        // when asked to, so otherwise count the pages we just read.
+        let encodings = match column_chunk.page_encoding_stats() {
+            Some(stats) if !stats.is_empty() => EncodingBreakdown::from_counts(
+                stats
+                    .iter()
+                    .filter(|s| is_data_page(s.page_type))
+                    .map(|s| (s.encoding, s.count.max(0) as usize)),
+            ),
+            _ => EncodingBreakdown::from_counts(data_page_encodings.into_iter().map(|e| (e, 1))),
+        };
+        let encodings = EncodingBreakdown {
+            footer_mismatch,
+            ..encodings
+        };
 
-        let statistics = RowGroupColumnStats::new(column_chunk.statistics());
+        let statistics = RowGroupColumnStats::new(
+            column_chunk.statistics(),
+            column_chunk.column_descr().logical_type_ref(),
+        );
 
         Ok(RowGroupColumnMetadata {
             file_offset: column_chunk.file_offset() as u64,
+            byte_range: column_chunk.byte_range(),
             column_path: column_chunk.column_descr().path().to_string(),
             has_stats: HasStats {
                 has_stats: column_chunk.statistics().is_some(),
@@ -191,27 +465,161 @@ impl RowGroupColumnMetadata {
                     && !column_chunk.page_encoding_stats().unwrap().is_empty(),
             },
             statistics,
+            geo_statistics: column_chunk.geo_statistics().map(GeoStats::new),
             total_compressed_size: column_chunk.compressed_size(),
             total_uncompressed_size: column_chunk.uncompressed_size(),
             compression_type: column_chunk.compression().to_string(),
             pages,
+            page_headers: PageHeaders::read(reader.source(), column_chunk),
+            encodings,
         })
     }
 
+    /// Read every page of the chunk, also returning the encodings of its data
+    /// pages in order and that of its dictionary page. Stops at the first
+    /// page that fails to read.
     fn make_page_info(
         page_reader: &mut Box<dyn PageReader>,
-    ) -> Result<RowGroupPageInfo, Box<dyn std::error::Error>> {
+    ) -> (RowGroupPageInfo, Vec<Encoding>, Option<Encoding>) {
         let mut page_info = Vec::new();
-        while let Ok(page) = page_reader.get_next_page() {
-            if let Some(page) = page {
-                page_info.push(PageInfo::from(&page));
+        let mut data_page_encodings = Vec::new();
+        let mut dictionary_encoding = None;
+        let mut error = None;
+        loop {
+            match page_reader.get_next_page() {
+                Ok(Some(page)) => {
+                    if is_data_page(page.page_type()) {
+                        data_page_encodings.push(page.encoding());
+                    } else if page.page_type() == PageType::DICTIONARY_PAGE {
+                        dictionary_encoding = Some(page.encoding());
+                    }
+                    page_info.push(PageInfo::from(&page));
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    error = Some(e.to_string());
+                    break;
+                }
+            }
+        }
+        (
+            RowGroupPageInfo {
+                page_infos: page_info,
+                error,
+            },
+            data_page_encodings,
+            dictionary_encoding,
+        )
+    }
+}
+
+impl EncodingBreakdown {
+    pub fn from_counts(counts: impl IntoIterator<Item = (Encoding, usize)>) -> Self {
+        let mut data_pages: Vec<(String, usize)> = Vec::new();
+        let (mut dictionary, mut other) = (false, false);
+        for (encoding, count) in counts {
+            if count == 0 {
+                continue;
+            }
+            if is_dictionary_encoding(encoding) {
+                dictionary = true;
             } else {
-                break;
+                other = true;
+            }
+            let name = encoding_name(encoding);
+            match data_pages.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, pages)) => *pages += count,
+                None => data_pages.push((name, count)),
             }
         }
-        Ok(RowGroupPageInfo {
-            page_infos: page_info,
-        })
+        Self {
+            data_pages,
+            dictionary_fallback: dictionary && other,
+            footer_mismatch: None,
+        }
+    }
+
+    pub fn total_pages(&self) -> usize {
+        self.data_pages.iter().map(|(_, pages)| pages).sum()
+    }
+}
+
+impl EncodingMismatch {
+    /// Compare the encodings `declared` in the footer with the value
+    /// encodings of the pages. The two dictionary encodings count as one,
+    /// and the level encodings RLE and BIT_PACKED are never unused, as
+    /// writers list them for columns without levels too.
+    pub fn new(declared: &[Encoding], used: impl IntoIterator<Item = Encoding>) -> Option<Self> {
+        let same = |a: Encoding, b: Encoding| {
+            a == b || (is_dictionary_encoding(a) && is_dictionary_encoding(b))
+        };
+        let used: Vec<Encoding> = used.into_iter().unique().collect();
+        let unused = declared
+            .iter()
+            .copied()
+            .filter(|&e| !is_level_encoding(e))
+            .filter(|&e| !used.iter().any(|&u| same(e, u)))
+            .map(encoding_name)
+            .unique()
+            .collect();
+        let undeclared = used
+            .iter()
+            .copied()
+            .filter(|&u| !declared.iter().any(|&e| same(e, u)))
+            .map(encoding_name)
+            .unique()
+            .collect();
+        let mismatch = Self { unused, undeclared };
+        (!mismatch.unused.is_empty() || !mismatch.undeclared.is_empty()).then_some(mismatch)
+    }
+}
+
+impl std::fmt::Display for EncodingMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if !self.unused.is_empty() {
+            parts.push(format!(
+                "footer lists {} but no page uses it",
+                self.unused.join(", ")
+            ));
+        }
+        if !self.undeclared.is_empty() {
+            parts.push(format!(
+                "pages use {} but the footer doesn't list it",
+                self.undeclared.join(", ")
+            ));
+        }
+        f.write_str(&parts.join("; "))
+    }
+}
+
+fn is_data_page(page_type: PageType) -> bool {
+    matches!(page_type, PageType::DATA_PAGE | PageType::DATA_PAGE_V2)
+}
+
+#[allow(deprecated)]
+fn is_level_encoding(encoding: Encoding) -> bool {
+    matches!(encoding, Encoding::RLE | Encoding::BIT_PACKED)
+}
+
+fn is_dictionary_encoding(encoding: Encoding) -> bool {
+    matches!(
+        encoding,
+        Encoding::RLE_DICTIONARY | Encoding::PLAIN_DICTIONARY
+    )
+}
+
+fn encoding_name(encoding: Encoding) -> String {
+    match encoding {
+        Encoding::PLAIN => "Plain".to_string(),
+        Encoding::PLAIN_DICTIONARY => "Plain Dictionary".to_string(),
+        Encoding::RLE => "RLE".to_string(),
+        Encoding::DELTA_BINARY_PACKED => "Delta Binary Packed".to_string(),
+        Encoding::DELTA_LENGTH_BYTE_ARRAY => "Delta Length Byte Array".to_string(),
+        Encoding::DELTA_BYTE_ARRAY => "Delta Byte Array".to_string(),
+        Encoding::RLE_DICTIONARY => "RLE Dictionary".to_string(),
+        Encoding::BYTE_STREAM_SPLIT => "Byte Stream Split".to_string(),
+        _ => format!("{encoding:?}"), // Handle any other encoding types
     }
 }
 
@@ -225,23 +633,30 @@ impl From<&Page> for PageInfo {
             PageType::DATA_PAGE_V2 => "Data Page V2".to_string(),
         };
 
-        let encoding: String = match page.encoding() {
-            Encoding::PLAIN => "Plain".to_string(),
-            Encoding::PLAIN_DICTIONARY => "Plain Dictionary".to_string(),
-            Encoding::RLE => "RLE".to_string(),
-            Encoding::DELTA_BINARY_PACKED => "Delta Binary Packed".to_string(),
-            Encoding::DELTA_LENGTH_BYTE_ARRAY => "Delta Length Byte Array".to_string(),
-            Encoding::DELTA_BYTE_ARRAY => "Delta Byte Array".to_string(),
-            Encoding::RLE_DICTIONARY => "RLE Dictionary".to_string(),
-            Encoding::BYTE_STREAM_SPLIT => "Byte Stream Split".to_string(),
-            _ => format!("{:?}", page.encoding()), // Handle any other encoding types
+        let v2 = match *page {
+            Page::DataPageV2 {
+                num_nulls,
+                num_rows,
+                def_levels_byte_len,
+                rep_levels_byte_len,
+                is_compressed,
+                ..
+            } => Some(DataPageV2Info {
+                num_nulls,
+                num_rows,
+                def_levels_byte_len,
+                rep_levels_byte_len,
+                is_compressed,
+            }),
+            _ => None,
         };
 
         PageInfo {
             page_type,
             size: page.buffer().len(),
             rows: page.num_values() as usize,
-            encoding,
+            encoding: encoding_name(page.encoding()),
+            v2,
         }
     }
 }
@@ -270,12 +685,197 @@ macro_rules! extract_stat_value {
 }
 
 impl RowGroupColumnStats {
-    fn new(stats: Option<&Statistics>) -> Option<Self> {
+    fn new(stats: Option<&Statistics>, logical: Option<&LogicalType>) -> Option<Self> {
+        // FLOAT16, UUID, JSON and BSON are byte arrays that only read well
+        // decoded by their logical type.
+        let decode = |bytes: Option<&[u8]>| {
+            logical.and_then(|logical_type| logical::format_value(bytes?, logical_type))
+        };
         stats.map(|stats| Self {
-            min: extract_stat_value!(stats, min_opt),
-            max: extract_stat_value!(stats, max_opt),
+            min: decode(stats.min_bytes_opt()).or_else(|| extract_stat_value!(stats, min_opt)),
+            max: decode(stats.max_bytes_opt()).or_else(|| extract_stat_value!(stats, max_opt)),
             null_count: stats.null_count_opt(),
             distinct_count: stats.distinct_count_opt(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::parquet_ctx::open_reader;
+    use crate::file::test_data_path;
+
+    #[test]
+    fn test_aggregate() {
+        let aggregate = Aggregate::new((1..=20).map(f64::from));
+        assert_eq!(aggregate.min, 1.0);
+        assert_eq!(aggregate.avg, 10.5);
+        assert_eq!(aggregate.median, 11.0);
+        assert_eq!(aggregate.p95, 19.0);
+        assert_eq!(aggregate.max, 20.0);
+
+        let single = Aggregate::new([4.0]);
+        assert_eq!((single.median, single.p95), (4.0, 4.0));
+        assert_eq!(Aggregate::new([]), Aggregate::default());
+    }
+
+    #[test]
+    fn test_size_drift() {
+        let shrinking: Vec<(u64, i64)> = (0..8).map(|i| (i * 100, 800 - i as i64 * 100)).collect();
+        assert_eq!(size_drift(&shrinking), Some(150.0 / 750.0));
+        // Order of the footer doesn't matter, only the position in the file.
+        let reversed: Vec<(u64, i64)> = shrinking.iter().rev().copied().collect();
+        assert_eq!(size_drift(&reversed), size_drift(&shrinking));
+        assert_eq!(
+            size_drift(&[(0, 10), (10, 10), (20, 10), (30, 10)]),
+            Some(1.0)
+        );
+        assert_eq!(size_drift(&shrinking[..3]), None);
+    }
+
+    #[test]
+    fn test_coalesce_ranges() {
+        let ranges = [(100, 50), (0, 100), (1000, 10), (160, 20)];
+        assert_eq!(
+            coalesce_ranges(&ranges, 0),
+            vec![(0, 150), (160, 20), (1000, 10)]
+        );
+        assert_eq!(coalesce_ranges(&ranges, 10), vec![(0, 180), (1000, 10)]);
+        assert_eq!(coalesce_ranges(&ranges, 1024), vec![(0, 1010)]);
+        assert_eq!(http_range(4, 100), "bytes=4-103");
+    }
+
+    #[test]
+    fn test_row_group_byte_range_spans_its_chunks() {
+        let path = test_data_path("nulls.snappy.parquet");
+        let (reader, _) = open_reader(&path).unwrap();
+        let rg = RowGroupStats::from_file_reader(&reader, 1).unwrap();
+        let (start, len) = rg.byte_range();
+        for column in &rg.column_metadata {
+            let (col_start, col_len) = column.byte_range;
+            assert!(col_start >= start && col_start + col_len <= start + len);
+        }
+        assert_eq!(len, rg.compressed_size as u64);
+    }
+
+    #[test]
+    fn test_row_groups_read_in_parallel_keep_file_order() {
+        let path = test_data_path("nulls.snappy.parquet");
+        let (reader, _) = open_reader(&path).unwrap();
+        let row_groups = RowGroups::from_file_reader(&reader).unwrap();
+        let columns = reader
+            .metadata()
+            .file_metadata()
+            .schema_descr()
+            .columns()
+            .to_vec();
+        for (idx, rg) in row_groups.row_groups.iter().enumerate() {
+            assert_eq!(rg.idx, idx);
+            let paths: Vec<String> = rg
+                .column_metadata
+                .iter()
+                .map(|c| c.column_path.clone())
+                .collect();
+            let expected: Vec<String> = columns.iter().map(|c| c.path().to_string()).collect();
+            assert_eq!(paths, expected);
+        }
+    }
+
+    #[test]
+    fn test_dictionary_page() {
+        let path = test_data_path("nulls.snappy.parquet");
+        let (reader, _) = open_reader(&path).unwrap();
+        let rg = RowGroupStats::from_file_reader(&reader, 0).unwrap();
+        for column in &rg.column_metadata {
+            let dictionary = column.dictionary_page();
+            assert_eq!(
+                dictionary.is_some(),
+                column.has_stats.has_dictionary_page,
+                "{}",
+                column.column_path
+            );
+            if let Some(dictionary) = dictionary {
+                assert!(dictionary.entries > 0);
+                assert!(dictionary.compressed_size > 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_row_group_of_row() {
+        let path = test_data_path("nulls.snappy.parquet");
+        let (reader, _) = open_reader(&path).unwrap();
+        let row_groups = RowGroups::from_file_reader(&reader).unwrap();
+        let last = row_groups.num_row_groups() - 1;
+        let start = row_groups.first_row(last);
+        assert_eq!(row_groups.first_row(0), 0);
+        assert_eq!(row_groups.row_group_of_row(0), Some(0));
+        assert_eq!(row_groups.row_group_of_row(start), Some(last));
+        let end = start + row_groups.row_groups[last].rows as usize;
+        assert_eq!(row_groups.row_group_of_row(end - 1), Some(last));
+        assert_eq!(row_groups.row_group_of_row(end), None);
+    }
+
+    #[test]
+    fn test_encoding_breakdown_merges_counts() {
+        let breakdown = EncodingBreakdown::from_counts([
+            (Encoding::RLE_DICTIONARY, 2),
+            (Encoding::RLE_DICTIONARY, 1),
+            (Encoding::PLAIN, 0),
+        ]);
+        assert_eq!(
+            breakdown.data_pages,
+            vec![("RLE Dictionary".to_string(), 3)]
+        );
+        assert_eq!(breakdown.total_pages(), 3);
+        assert!(!breakdown.dictionary_fallback);
+    }
+
+    #[test]
+    fn test_encoding_breakdown_flags_dictionary_fallback() {
+        let breakdown =
+            EncodingBreakdown::from_counts([(Encoding::RLE_DICTIONARY, 4), (Encoding::PLAIN, 2)]);
+        assert!(breakdown.dictionary_fallback);
+        assert_eq!(breakdown.data_pages[1], ("Plain".to_string(), 2));
+
+        let plain_only = EncodingBreakdown::from_counts([(Encoding::PLAIN, 5)]);
+        assert!(!plain_only.dictionary_fallback);
+    }
+
+    #[test]
+    fn test_encoding_mismatch() {
+        // Dictionary page and data pages as declared, levels listed too.
+        let declared = [Encoding::PLAIN, Encoding::RLE_DICTIONARY, Encoding::RLE];
+        assert_eq!(
+            EncodingMismatch::new(&declared, [Encoding::PLAIN, Encoding::RLE_DICTIONARY]),
+            None
+        );
+        // v1 writers name both dictionary encodings PLAIN_DICTIONARY.
+        assert_eq!(
+            EncodingMismatch::new(
+                &[Encoding::PLAIN_DICTIONARY, Encoding::RLE],
+                [Encoding::RLE_DICTIONARY]
+            ),
+            None
+        );
+
+        let mismatch = EncodingMismatch::new(&declared, [Encoding::PLAIN]).unwrap();
+        assert_eq!(mismatch.unused, vec!["RLE Dictionary".to_string()]);
+        assert!(mismatch.undeclared.is_empty());
+        assert_eq!(
+            mismatch.to_string(),
+            "footer lists RLE Dictionary but no page uses it"
+        );
+
+        let mismatch = EncodingMismatch::new(
+            &[Encoding::RLE_DICTIONARY],
+            [Encoding::RLE_DICTIONARY, Encoding::DELTA_BINARY_PACKED],
+        )
+        .unwrap();
+        assert_eq!(
+            mismatch.to_string(),
+            "pages use Delta Binary Packed but the footer doesn't list it"
+        );
+    }
+}
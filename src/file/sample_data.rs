@@ -1,25 +1,374 @@
+use arrow::array::{Array, ArrayRef, AsArray};
+use arrow::datatypes::DataType as ArrowDataType;
+use arrow::util::display::{ArrayFormatter, FormatOptions};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Local, Utc};
+use parquet::arrow::arrow_reader::{ParquetRecordBatchReaderBuilder, RowSelection, RowSelector};
+use parquet::basic::LogicalType;
+use parquet::file::metadata::ParquetMetaDataReader;
+use parquet::schema::types::SchemaDescriptor;
 use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as Json;
+
+use crate::file::json_path::JsonColumn;
+use crate::file::logical::{format_value, json_one_line};
+use crate::file::variant;
+
+/// How timestamps are shown in the rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DateStyle {
+    /// As stored, e.g. `2024-01-01 12:00:00`.
+    #[default]
+    Utc,
+    /// Converted to the local time zone, with its offset.
+    Local,
+    /// The raw integer in the column's unit since the Unix epoch.
+    Epoch,
+}
+
+/// Which rows of the file the preview shows. Written `head`, `tail`,
+/// `every`, `every:<n>`, `random`, `random:<seed>` or `row_groups`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Sampling {
+    /// The first rows.
+    #[default]
+    Head,
+    /// The last rows.
+    Tail,
+    /// Every `n`th row from the first; `0` spaces the rows out over the
+    /// whole file.
+    Every(usize),
+    /// Rows picked at random; the same seed picks the same rows.
+    Random(u64),
+    /// The same number of rows from the start of each row group.
+    RowGroups,
+}
+
+impl Sampling {
+    /// The modes the Visualize tab's toggle steps through.
+    pub const CYCLE: [Sampling; 5] = [
+        Sampling::Head,
+        Sampling::Tail,
+        Sampling::Every(0),
+        Sampling::Random(0),
+        Sampling::RowGroups,
+    ];
+
+    /// The mode after this one in [`Self::CYCLE`].
+    pub fn next(self) -> Self {
+        let at = Self::CYCLE
+            .iter()
+            .position(|mode| std::mem::discriminant(mode) == std::mem::discriminant(&self))
+            .unwrap_or(0);
+        Self::CYCLE[(at + 1) % Self::CYCLE.len()]
+    }
+
+    /// Positions in the file of the rows to show, in order and at most
+    /// `max_rows` of them, given the number of rows in each row group.
+    pub fn positions(self, row_group_rows: &[usize], max_rows: usize) -> Vec<usize> {
+        let num_rows: usize = row_group_rows.iter().sum();
+        let count = max_rows.min(num_rows);
+        match self {
+            Sampling::Head => (0..count).collect(),
+            Sampling::Tail => (num_rows - count..num_rows).collect(),
+            Sampling::Every(n) => {
+                let step = match n {
+                    0 => num_rows.div_ceil(max_rows.max(1)).max(1),
+                    n => n,
+                };
+                (0..num_rows).step_by(step).take(max_rows).collect()
+            }
+            Sampling::Random(seed) => random_rows(num_rows, count, seed),
+            Sampling::RowGroups => {
+                let per_group = (max_rows / row_group_rows.len().max(1)).max(1);
+                let mut positions = Vec::new();
+                let mut start = 0;
+                for &rows in row_group_rows {
+                    positions.extend(start..start + rows.min(per_group));
+                    start += rows;
+                }
+                positions.truncate(max_rows);
+                positions
+            }
+        }
+    }
+}
+
+impl fmt::Display for Sampling {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Sampling::Head => write!(f, "head"),
+            Sampling::Tail => write!(f, "tail"),
+            Sampling::Every(0) => write!(f, "every"),
+            Sampling::Every(n) => write!(f, "every:{n}"),
+            Sampling::Random(seed) => write!(f, "random:{seed}"),
+            Sampling::RowGroups => write!(f, "row_groups"),
+        }
+    }
+}
+
+impl FromStr for Sampling {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (mode, arg) = match text.split_once(':') {
+            Some((mode, arg)) => (mode, Some(arg)),
+            None => (text, None),
+        };
+        let invalid = || format!("invalid sampling '{text}'");
+        match (mode, arg) {
+            ("head", None) => Ok(Sampling::Head),
+            ("tail", None) => Ok(Sampling::Tail),
+            ("every", None) => Ok(Sampling::Every(0)),
+            ("every", Some(n)) => match n.parse() {
+                Ok(0) | Err(_) => Err(format!("{}: expected every:<n> with n >= 1", invalid())),
+                Ok(n) => Ok(Sampling::Every(n)),
+            },
+            ("random", None) => Ok(Sampling::Random(0)),
+            ("random", Some(seed)) => seed
+                .parse()
+                .map(Sampling::Random)
+                .map_err(|_| format!("{}: expected random:<seed>", invalid())),
+            ("row_groups" | "rowgroups", None) => Ok(Sampling::RowGroups),
+            _ => Err(format!(
+                "{}, expected head, tail, every[:n], random[:seed] or row_groups",
+                invalid()
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for Sampling {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        text.parse()
+    }
+}
 
+impl From<Sampling> for String {
+    fn from(sampling: Sampling) -> Self {
+        sampling.to_string()
+    }
+}
+
+/// The splitmix64 pseudo-random stream: fast, and the same seed always
+/// gives the same numbers.
 #[derive(Debug, Clone)]
+pub(crate) struct SplitMix64(u64);
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`.
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// `count` distinct positions below `num_rows`, in order, picked with
+/// Floyd's algorithm from a splitmix64 stream seeded with `seed`.
+fn random_rows(num_rows: usize, count: usize, seed: u64) -> Vec<usize> {
+    let mut rng = SplitMix64::new(seed);
+    let mut picked = BTreeSet::new();
+    for last in num_rows - count..num_rows {
+        let pick = (rng.next_u64() % (last as u64 + 1)) as usize;
+        if !picked.insert(pick) {
+            picked.insert(last);
+        }
+    }
+    picked.into_iter().collect()
+}
+
+/// How many rows to load at a time, which ones and how to show them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreviewOptions {
+    pub max_rows: usize,
+    pub dates: DateStyle,
+    pub sampling: Sampling,
+}
+
+impl Default for PreviewOptions {
+    fn default() -> Self {
+        Self {
+            max_rows: ParquetSampleData::MAX_ROWS,
+            dates: DateStyle::default(),
+            sampling: Sampling::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct ParquetSampleData {
     pub flattened_columns: Vec<String>,
     pub rows: Vec<Vec<String>>,
     pub total_columns: usize,
     pub total_rows: usize,
+    /// Position in the file of `rows[0]`; non-zero after jumping to a row.
+    pub first_row: usize,
+    /// Position in the file of each row when they don't follow each other,
+    /// e.g. for a random sample.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub positions: Option<Vec<usize>>,
 }
 
 // TODO: in future create a independent crate that does the parsing,
 // the polars crate is large and doesn't support complex nested types.
 impl ParquetSampleData {
+    /// Number of rows loaded at a time, unless `PreviewOptions` says
+    /// otherwise.
+    pub const MAX_ROWS: usize = 200;
+
+    /// No rows or columns; stands in when the data could not be read.
+    pub fn empty() -> Self {
+        Self {
+            flattened_columns: vec![],
+            rows: vec![],
+            total_columns: 0,
+            total_rows: 0,
+            first_row: 0,
+            positions: None,
+        }
+    }
+
+    /// Position in the file of `rows[row]`.
+    pub fn position(&self, row: usize) -> usize {
+        match &self.positions {
+            Some(positions) => positions.get(row).copied().unwrap_or(self.first_row + row),
+            None => self.first_row + row,
+        }
+    }
+
+    /// Index into `rows` of the row at `position` in the file, if it is
+    /// loaded.
+    pub fn row_at(&self, position: usize) -> Option<usize> {
+        match &self.positions {
+            Some(positions) => positions.binary_search(&position).ok(),
+            None => (position >= self.first_row && position < self.first_row + self.total_rows)
+                .then(|| position - self.first_row),
+        }
+    }
+
+    /// Append `json` as a column extracted from the rows loaded, `NULL`
+    /// where the source cell holds no JSON or the path isn't in it. Returns
+    /// how many rows had a value.
+    pub fn add_json_column(&mut self, json: &JsonColumn) -> Result<usize, String> {
+        let source = self
+            .flattened_columns
+            .iter()
+            .position(|name| *name == json.column)
+            .ok_or_else(|| format!("no column '{}' in the preview", json.column))?;
+        let mut found = 0;
+        for row in &mut self.rows {
+            let value = row.get(source).and_then(|cell| json.extract(cell));
+            found += usize::from(value.is_some());
+            row.push(value.unwrap_or_else(|| "NULL".to_string()));
+        }
+        self.flattened_columns.push(json.name());
+        self.total_columns += 1;
+        Ok(found)
+    }
+
+    /// Drop the columns after the first `len`, e.g. extracted JSON columns.
+    pub fn truncate_columns(&mut self, len: usize) {
+        self.flattened_columns.truncate(len);
+        for row in &mut self.rows {
+            row.truncate(len);
+        }
+        self.total_columns = self.flattened_columns.len();
+    }
+
     pub fn read_sample_data(
         file_path: &str,
     ) -> Result<ParquetSampleData, Box<dyn std::error::Error>> {
-        const MAX_ROWS: usize = 200;
+        Self::read_window(file_path, 0)
+    }
+
+    /// Read up to `MAX_ROWS` rows starting at row `first_row` of the file.
+    pub fn read_window(
+        file_path: &str,
+        first_row: usize,
+    ) -> Result<ParquetSampleData, Box<dyn std::error::Error>> {
+        Self::read_window_with(file_path, first_row, &PreviewOptions::default())
+    }
+
+    /// Read the rows `options.sampling` picks, up to `options.max_rows`.
+    pub fn read_sample(
+        file_path: &str,
+        options: &PreviewOptions,
+    ) -> Result<ParquetSampleData, Box<dyn std::error::Error>> {
+        if options.sampling == Sampling::Head {
+            return Self::read_window_with(file_path, 0, options);
+        }
+        let metadata =
+            ParquetMetaDataReader::new().parse_and_finish(&std::fs::File::open(file_path)?)?;
+        let row_group_rows: Vec<usize> = metadata
+            .row_groups()
+            .iter()
+            .map(|rg| rg.num_rows() as usize)
+            .collect();
+        let positions = options
+            .sampling
+            .positions(&row_group_rows, options.max_rows);
+        match positions.first() {
+            None => Self::read_window_with(file_path, 0, options),
+            Some(&first) if positions.windows(2).all(|pair| pair[1] == pair[0] + 1) => {
+                Self::read_window_with(file_path, first, options)
+            }
+            Some(_) => Self::read_positions(file_path, positions),
+        }
+    }
+
+    /// Read up to `options.max_rows` rows starting at row `first_row`.
+    pub fn read_window_with(
+        file_path: &str,
+        first_row: usize,
+        options: &PreviewOptions,
+    ) -> Result<ParquetSampleData, Box<dyn std::error::Error>> {
+        // polars reads FLOAT16, UUID, JSON and BSON columns as plain binary,
+        // and VARIANT columns as the struct they are stored as.
+        let logical_types = std::fs::File::open(file_path)
+            .ok()
+            .and_then(|file| ParquetMetaDataReader::new().parse_and_finish(&file).ok())
+            .map(|md| column_logical_types(md.file_metadata().schema_descr()))
+            .unwrap_or_default();
+        if logical_types
+            .iter()
+            .any(|logical| matches!(logical, Some(LogicalType::Variant(_))))
+        {
+            return Self::read_window_arrow(file_path, first_row, options);
+        }
 
         // Read parquet file using polars LazyFrame
-        let df = LazyFrame::scan_parquet(PlPath::new(file_path), Default::default())?
-            .limit(MAX_ROWS as u32)
-            .collect()?;
+        let df = match LazyFrame::scan_parquet(PlPath::new(file_path), Default::default()).and_then(
+            |frame| {
+                frame
+                    .slice(first_row as i64, options.max_rows as u32)
+                    .collect()
+            },
+        ) {
+            Ok(df) => df,
+            Err(error) => {
+                return Self::read_window_arrow(file_path, first_row, options)
+                    .map_err(|_| error.into());
+            }
+        };
 
         // Flatten struct columns
         let df = Self::flatten_struct_columns(df)?;
@@ -37,9 +386,10 @@ impl ParquetSampleData {
         let mut rows = Vec::new();
         for row_idx in 0..df.height() {
             let mut row = Vec::new();
-            for col in df.get_columns() {
+            for (col_idx, col) in df.get_columns().iter().enumerate() {
                 let series = col.as_materialized_series();
-                let value = Self::get_value_as_string(series, row_idx);
+                let logical = logical_types.get(col_idx).and_then(Option::as_ref);
+                let value = Self::get_value_as_string(series, row_idx, options.dates, logical);
                 row.push(value);
             }
             rows.push(row);
@@ -50,6 +400,110 @@ impl ParquetSampleData {
             flattened_columns,
             rows,
             total_rows: df.height(),
+            first_row,
+            positions: None,
+        })
+    }
+
+    /// Read the rows with arrow-rs instead, for files polars rejects: its
+    /// footer parser predates some logical types, e.g. GEOMETRY and
+    /// GEOGRAPHY, and for VARIANT columns, which it can't reassemble.
+    /// Geospatial values are shown as WKT and variants as one-line JSON;
+    /// timestamps are shown as stored whatever `options.dates` says.
+    fn read_window_arrow(
+        file_path: &str,
+        first_row: usize,
+        options: &PreviewOptions,
+    ) -> Result<ParquetSampleData, Box<dyn std::error::Error>> {
+        let selection = RowSelection::from(vec![
+            RowSelector::skip(first_row),
+            RowSelector::select(options.max_rows),
+        ]);
+        Self::read_arrow(file_path, selection, first_row, None)
+    }
+
+    /// Read the rows at `positions`, which are in order, with arrow-rs so
+    /// the pages in between can be skipped.
+    fn read_positions(
+        file_path: &str,
+        positions: Vec<usize>,
+    ) -> Result<ParquetSampleData, Box<dyn std::error::Error>> {
+        let mut selectors = Vec::new();
+        let mut next = 0;
+        for &position in &positions {
+            if position > next {
+                selectors.push(RowSelector::skip(position - next));
+            }
+            selectors.push(RowSelector::select(1));
+            next = position + 1;
+        }
+        let first_row = positions.first().copied().unwrap_or_default();
+        Self::read_arrow(
+            file_path,
+            RowSelection::from(selectors),
+            first_row,
+            Some(positions),
+        )
+    }
+
+    fn read_arrow(
+        file_path: &str,
+        selection: RowSelection,
+        first_row: usize,
+        positions: Option<Vec<usize>>,
+    ) -> Result<ParquetSampleData, Box<dyn std::error::Error>> {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(file_path)?)?;
+        let flattened_columns: Vec<String> = builder
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect();
+        let logical_types = column_logical_types(builder.parquet_schema());
+        let reader = builder
+            .with_row_selection(selection)
+            .with_batch_size(ParquetSampleData::MAX_ROWS)
+            .build()?;
+
+        let format = FormatOptions::default().with_null("NULL");
+        let mut rows = Vec::new();
+        for batch in reader {
+            let batch = batch?;
+            let formatters = batch
+                .columns()
+                .iter()
+                .map(|column| ArrayFormatter::try_new(column.as_ref(), &format))
+                .collect::<Result<Vec<_>, _>>()?;
+            for row_idx in 0..batch.num_rows() {
+                let row = batch
+                    .columns()
+                    .iter()
+                    .zip(&formatters)
+                    .zip(&logical_types)
+                    .map(|((column, formatter), logical)| {
+                        match logical {
+                            Some(LogicalType::Variant(_)) => variant_text(column.as_ref(), row_idx),
+                            Some(logical) => value_bytes(column.as_ref(), row_idx)
+                                .and_then(|bytes| format_value(bytes, logical)),
+                            None => None,
+                        }
+                        .or_else(|| {
+                            array_json(column.as_ref(), row_idx).map(|json| json_one_line(&json))
+                        })
+                        .unwrap_or_else(|| formatter.value(row_idx).to_string())
+                    })
+                    .collect();
+                rows.push(row);
+            }
+        }
+
+        Ok(ParquetSampleData {
+            total_columns: flattened_columns.len(),
+            flattened_columns,
+            total_rows: rows.len(),
+            rows,
+            first_row,
+            positions,
         })
     }
 
@@ -60,17 +514,279 @@ impl ParquetSampleData {
         Ok(df)
     }
 
-    fn get_value_as_string(col: &Series, row_idx: usize) -> String {
+    fn get_value_as_string(
+        col: &Series,
+        row_idx: usize,
+        dates: DateStyle,
+        logical: Option<&LogicalType>,
+    ) -> String {
         // Use get() which returns AnyValue and handle it
         match col.get(row_idx) {
             Ok(any_value) => {
                 if any_value.is_null() {
-                    "NULL".to_string()
-                } else {
-                    format!("{any_value}")
+                    return "NULL".to_string();
+                }
+                if let Some(json) = any_value_json(&any_value) {
+                    return json_one_line(&json);
+                }
+                let bytes = match &any_value {
+                    AnyValue::Binary(bytes) => Some(*bytes),
+                    AnyValue::BinaryOwned(bytes) => Some(bytes.as_slice()),
+                    AnyValue::String(text) => Some(text.as_bytes()),
+                    AnyValue::StringOwned(text) => Some(text.as_bytes()),
+                    _ => None,
+                };
+                if let Some(text) = logical
+                    .zip(bytes)
+                    .and_then(|(logical, bytes)| format_value(bytes, logical))
+                {
+                    return text;
+                }
+                match (&any_value, dates) {
+                    (
+                        AnyValue::Datetime(v, ..) | AnyValue::DatetimeOwned(v, ..),
+                        DateStyle::Epoch,
+                    ) => v.to_string(),
+                    (
+                        AnyValue::Datetime(v, unit, _) | AnyValue::DatetimeOwned(v, unit, _),
+                        DateStyle::Local,
+                    ) => match timestamp_to_utc(*v, *unit) {
+                        Some(utc) => utc
+                            .with_timezone(&Local)
+                            .format("%Y-%m-%d %H:%M:%S%.f %:z")
+                            .to_string(),
+                        None => format!("{any_value}"),
+                    },
+                    _ => format!("{any_value}"),
                 }
             }
             Err(_) => "NULL".to_string(),
         }
     }
 }
+
+/// Logical type of each top-level column, `None` for columns without one.
+fn column_logical_types(schema: &SchemaDescriptor) -> Vec<Option<LogicalType>> {
+    schema
+        .root_schema()
+        .get_fields()
+        .iter()
+        .map(|field| field.get_basic_info().logical_type_ref().cloned())
+        .collect()
+}
+
+/// A VARIANT column reassembled from its shredded parts, as one-line JSON.
+fn variant_text(column: &dyn Array, row_idx: usize) -> Option<String> {
+    let column = column.as_struct_opt()?;
+    Some(match variant::reassemble(column, row_idx) {
+        Ok(Some(value)) => value.to_string(),
+        Ok(None) => "NULL".to_string(),
+        Err(error) => format!("invalid variant: {error}"),
+    })
+}
+
+/// A non-null list, array or struct value as JSON, so that nested values
+/// show their field names. polars reads maps as lists of key/value structs,
+/// which become objects.
+fn any_value_json(value: &AnyValue) -> Option<Json> {
+    match value {
+        AnyValue::List(_)
+        | AnyValue::Array(..)
+        | AnyValue::Struct(..)
+        | AnyValue::StructOwned(_)
+            if !value.is_null() =>
+        {
+            Some(any_value_to_json(value))
+        }
+        _ => None,
+    }
+}
+
+fn any_value_to_json(value: &AnyValue) -> Json {
+    match value {
+        AnyValue::Null => Json::Null,
+        AnyValue::Boolean(v) => Json::Bool(*v),
+        AnyValue::Int8(v) => Json::from(*v),
+        AnyValue::Int16(v) => Json::from(*v),
+        AnyValue::Int32(v) => Json::from(*v),
+        AnyValue::Int64(v) => Json::from(*v),
+        AnyValue::UInt8(v) => Json::from(*v),
+        AnyValue::UInt16(v) => Json::from(*v),
+        AnyValue::UInt32(v) => Json::from(*v),
+        AnyValue::UInt64(v) => Json::from(*v),
+        AnyValue::Float32(v) => float_json(f64::from(*v)),
+        AnyValue::Float64(v) => float_json(*v),
+        AnyValue::String(text) => Json::String(text.to_string()),
+        AnyValue::StringOwned(text) => Json::String(text.to_string()),
+        AnyValue::List(items) | AnyValue::Array(items, _) => {
+            let values = items.iter().map(|item| any_value_to_json(&item)).collect();
+            match items.dtype() {
+                DataType::Struct(fields)
+                    if is_map_entry(fields.iter().map(|field| field.name().as_str())) =>
+                {
+                    map_json(values)
+                }
+                _ => Json::Array(values),
+            }
+        }
+        AnyValue::Struct(_, _, fields) => Json::Object(
+            fields
+                .iter()
+                .zip(value._iter_struct_av())
+                .map(|(field, value)| (field.name().to_string(), any_value_to_json(&value)))
+                .collect(),
+        ),
+        AnyValue::StructOwned(payload) => Json::Object(
+            payload
+                .1
+                .iter()
+                .zip(&payload.0)
+                .map(|(field, value)| (field.name().to_string(), any_value_to_json(value)))
+                .collect(),
+        ),
+        other => Json::String(other.to_string()),
+    }
+}
+
+/// A non-null list, map or struct value as JSON, for the arrow-rs reader.
+fn array_json(column: &dyn Array, row_idx: usize) -> Option<Json> {
+    match column.data_type() {
+        ArrowDataType::List(_)
+        | ArrowDataType::LargeList(_)
+        | ArrowDataType::FixedSizeList(..)
+        | ArrowDataType::Map(..)
+        | ArrowDataType::Struct(_)
+            if column.is_valid(row_idx) =>
+        {
+            Some(array_to_json(column, row_idx))
+        }
+        _ => None,
+    }
+}
+
+fn array_to_json(column: &dyn Array, row_idx: usize) -> Json {
+    if column.is_null(row_idx) {
+        return Json::Null;
+    }
+    let items = |items: ArrayRef| {
+        Json::Array(
+            (0..items.len())
+                .map(|i| array_to_json(items.as_ref(), i))
+                .collect(),
+        )
+    };
+    if let Some(lists) = column.as_list_opt::<i32>() {
+        return items(lists.value(row_idx));
+    }
+    if let Some(lists) = column.as_list_opt::<i64>() {
+        return items(lists.value(row_idx));
+    }
+    if let Some(lists) = column.as_fixed_size_list_opt() {
+        return items(lists.value(row_idx));
+    }
+    if let Some(maps) = column.as_map_opt() {
+        let entries = maps.value(row_idx);
+        return map_json(
+            (0..entries.len())
+                .map(|i| {
+                    Json::Object(serde_json::Map::from_iter([
+                        (
+                            "key".to_string(),
+                            array_to_json(entries.column(0).as_ref(), i),
+                        ),
+                        (
+                            "value".to_string(),
+                            array_to_json(entries.column(1).as_ref(), i),
+                        ),
+                    ]))
+                })
+                .collect(),
+        );
+    }
+    if let Some(structs) = column.as_struct_opt() {
+        return Json::Object(
+            structs
+                .column_names()
+                .into_iter()
+                .zip(structs.columns())
+                .map(|(name, field)| (name.to_string(), array_to_json(field.as_ref(), row_idx)))
+                .collect(),
+        );
+    }
+    let text = ArrayFormatter::try_new(column, &FormatOptions::default())
+        .map(|formatter| formatter.value(row_idx).to_string())
+        .unwrap_or_default();
+    match column.data_type() {
+        ArrowDataType::Boolean => Json::Bool(text == "true"),
+        data_type if data_type.is_integer() || data_type.is_floating() => {
+            serde_json::from_str(&text).unwrap_or(Json::String(text))
+        }
+        _ => Json::String(text),
+    }
+}
+
+/// Whether a struct with these fields is a map entry.
+fn is_map_entry<'a>(mut fields: impl Iterator<Item = &'a str>) -> bool {
+    matches!(
+        (fields.next(), fields.next(), fields.next()),
+        (Some("key" | "keys"), Some("value" | "values"), None)
+    )
+}
+
+/// Map entries, each an object with a key and a value, as one object. Keys
+/// that aren't strings are written as JSON.
+fn map_json(entries: Vec<Json>) -> Json {
+    Json::Object(
+        entries
+            .into_iter()
+            .filter_map(|entry| {
+                let Json::Object(entry) = entry else {
+                    return None;
+                };
+                let mut fields = entry.into_iter().map(|(_, field)| field);
+                let key = match fields.next()? {
+                    Json::String(key) => key,
+                    key => json_one_line(&key),
+                };
+                Some((key, fields.next().unwrap_or(Json::Null)))
+            })
+            .collect(),
+    )
+}
+
+fn float_json(value: f64) -> Json {
+    serde_json::Number::from_f64(value)
+        .map_or_else(|| Json::String(value.to_string()), Json::Number)
+}
+
+/// The bytes of a non-null binary or string value.
+pub(crate) fn value_bytes(column: &dyn Array, row_idx: usize) -> Option<&[u8]> {
+    if column.is_null(row_idx) {
+        return None;
+    }
+    if let Some(values) = column.as_binary_opt::<i32>() {
+        Some(values.value(row_idx))
+    } else if let Some(values) = column.as_binary_opt::<i64>() {
+        Some(values.value(row_idx))
+    } else if let Some(values) = column.as_binary_view_opt() {
+        Some(values.value(row_idx))
+    } else if let Some(values) = column.as_fixed_size_binary_opt() {
+        Some(values.value(row_idx))
+    } else if let Some(values) = column.as_string_opt::<i32>() {
+        Some(values.value(row_idx).as_bytes())
+    } else if let Some(values) = column.as_string_opt::<i64>() {
+        Some(values.value(row_idx).as_bytes())
+    } else {
+        column
+            .as_string_view_opt()
+            .map(|values| values.value(row_idx).as_bytes())
+    }
+}
+
+fn timestamp_to_utc(value: i64, unit: TimeUnit) -> Option<DateTime<Utc>> {
+    match unit {
+        TimeUnit::Nanoseconds => Some(DateTime::from_timestamp_nanos(value)),
+        TimeUnit::Microseconds => DateTime::from_timestamp_micros(value),
+        TimeUnit::Milliseconds => DateTime::from_timestamp_millis(value),
+    }
+}
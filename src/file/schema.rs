@@ -1,26 +1,55 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 
-use parquet::basic::{LogicalType, TimeUnit, Type as PhysicalType};
-use parquet::file::metadata::ParquetMetaData;
+use chrono::{DateTime, Utc};
+
+use parquet::basic::{ColumnOrder, LogicalType, SortOrder, TimeUnit, Type as PhysicalType};
+use parquet::file::metadata::{ColumnChunkMetaData, ParquetMetaData};
 use parquet::schema::types::Type as ParquetType;
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::file::geo::{self, GeoBounds};
+use crate::file::logical;
+use crate::file::row_groups::sort_direction;
+use crate::file::utils::display_width;
+use crate::file::variant::{self, Shredding};
+
+#[cfg(feature = "tui")]
+use crate::components::size_breakdown::percent_of;
+#[cfg(feature = "tui")]
+use crate::file::utils::{format_size, is_suspicious_time, relative_time, relative_times};
+#[cfg(feature = "tui")]
+use crate::theme::Theme;
+#[cfg(feature = "tui")]
 use ratatui::{
-    style::{Color, Stylize},
+    style::{Style, Stylize},
     widgets::{Cell, Row},
 };
+#[cfg(feature = "tui")]
+use std::collections::BTreeMap;
 
-use crate::file::utils::format_size;
-
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ColumnStats {
     pub min: Option<String>,
     pub max: Option<String>,
+    /// `min` and `max` as instants, for TIMESTAMP and DATE columns.
+    #[serde(skip)]
+    pub min_time: Option<DateTime<Utc>>,
+    #[serde(skip)]
+    pub max_time: Option<DateTime<Utc>>,
     pub nulls: u64,
     pub distinct: Option<u64>,
     pub total_compressed_size: u64,
     pub total_uncompressed_size: u64,
+    /// Estimated size once decoded into Arrow arrays, see
+    /// [`arrow_memory_estimate`].
+    pub arrow_memory: u64,
+    /// Bounding box of a GEOMETRY or GEOGRAPHY column over the chunks that
+    /// have one. Such columns carry it instead of min/max.
+    pub geo_bounds: Option<GeoBounds>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize)]
 pub struct ColumnSchemaInfo {
     pub name: String,
     pub repetition: String,
@@ -30,28 +59,203 @@ pub struct ColumnSchemaInfo {
     pub converted_type: String,
     pub encoding: String,
     pub dictionary_values: Option<Vec<String>>,
+    /// The footer's column order, which says how min/max were compared.
+    /// Files written before column orders existed compare bytes signed.
+    pub column_order: String,
+    pub sort_key: Option<SortKey>,
+    /// The writer's field id, which Iceberg and Delta column mapping resolve
+    /// columns by instead of their names.
+    pub field_id: Option<i32>,
+}
+
+/// Where a leaf column appears in the sort order row groups declare.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct SortKey {
+    /// 0-based, the most significant sorting column first.
+    pub position: usize,
+    pub descending: bool,
+    pub nulls_first: bool,
+    /// Row groups declaring the column as a sorting column, out of
+    /// `total_row_groups`.
+    pub row_groups: usize,
+    pub total_row_groups: usize,
+}
+
+impl std::fmt::Display for SortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "#{} {}",
+            self.position + 1,
+            sort_direction(self.descending, self.nulls_first)
+        )?;
+        if self.row_groups < self.total_row_groups {
+            write!(f, " ({}/{} RGs)", self.row_groups, self.total_row_groups)?;
+        }
+        Ok(())
+    }
 }
 
-#[derive(Clone)]
+/// One node of the schema in depth-first order. `display` is the node's
+/// line in the schema tree and is left out when serialized.
+#[derive(Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
 pub enum SchemaInfo {
     Root {
         name: String,
+        #[serde(skip)]
         display: String,
     },
     Primitive {
         name: String,
+        #[serde(skip)]
         display: String,
+        depth: usize,
         info: Box<ColumnSchemaInfo>,
         stats: ColumnStats,
     },
     Group {
         name: String,
+        #[serde(skip)]
         display: String,
+        depth: usize,
         repetition: String,
+        field_id: Option<i32>,
+        /// E.g. `List`, `Map` or `Variant`; empty for plain structs.
+        logical: String,
+        /// Set on the shredded positions of VARIANT columns.
+        shredding: Option<Shredding>,
     },
 }
 
+impl SchemaInfo {
+    /// Nesting depth; the root is 0 and its direct children 1.
+    pub fn depth(&self) -> usize {
+        match self {
+            SchemaInfo::Root { .. } => 0,
+            SchemaInfo::Primitive { depth, .. } | SchemaInfo::Group { depth, .. } => *depth,
+        }
+    }
+
+    /// The node's field id, if the writer set one. The root never has one.
+    pub fn field_id(&self) -> Option<i32> {
+        match self {
+            SchemaInfo::Root { .. } => None,
+            SchemaInfo::Primitive { info, .. } => info.field_id,
+            SchemaInfo::Group { field_id, .. } => *field_id,
+        }
+    }
+}
+
+/// Dotted path of `columns[index]` built from its ancestors' names.
+pub fn column_path(columns: &[SchemaInfo], index: usize) -> String {
+    let mut names = vec![];
+    let mut depth = usize::MAX;
+    for column in columns[..=index].iter().rev() {
+        match column {
+            SchemaInfo::Primitive { name, .. } | SchemaInfo::Group { name, .. }
+                if column.depth() < depth =>
+            {
+                names.push(name.as_str());
+                depth = column.depth();
+            }
+            _ => {}
+        }
+    }
+    names.reverse();
+    names.join(".")
+}
+
+/// Shape of the schema tree (depth and kind of every node), enough to
+/// navigate it with some groups collapsed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaOutline {
+    depths: Vec<usize>,
+    leaves: Vec<bool>,
+}
+
+/// Built from `(depth, is_leaf)` per node, in tree order.
+impl FromIterator<(usize, bool)> for SchemaOutline {
+    fn from_iter<I: IntoIterator<Item = (usize, bool)>>(nodes: I) -> Self {
+        let (depths, leaves) = nodes.into_iter().unzip();
+        SchemaOutline { depths, leaves }
+    }
+}
+
+impl SchemaOutline {
+    pub fn len(&self) -> usize {
+        self.depths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.depths.is_empty()
+    }
+
+    pub fn is_group(&self, index: usize) -> bool {
+        index > 0 && self.leaves.get(index) == Some(&false)
+    }
+
+    /// Closest enclosing node (the root for top-level columns).
+    pub fn parent(&self, index: usize) -> Option<usize> {
+        let depth = *self.depths.get(index)?;
+        (0..index).rev().find(|&idx| self.depths[idx] < depth)
+    }
+
+    /// One past the last descendant of the node at `index`.
+    pub fn subtree_end(&self, index: usize) -> usize {
+        let depth = self.depths[index];
+        (index + 1..self.depths.len())
+            .find(|&idx| self.depths[idx] <= depth)
+            .unwrap_or(self.depths.len())
+    }
+
+    /// Nodes not hidden inside a collapsed group, in tree order (the root
+    /// included).
+    pub fn visible_nodes(&self, collapsed: &BTreeSet<usize>) -> Vec<usize> {
+        let mut visible = Vec::with_capacity(self.depths.len());
+        let mut idx = 0;
+        while idx < self.depths.len() {
+            visible.push(idx);
+            idx = if self.is_group(idx) && collapsed.contains(&idx) {
+                self.subtree_end(idx)
+            } else {
+                idx + 1
+            };
+        }
+        visible
+    }
+
+    /// The root followed by every leaf, for the flat view of the schema.
+    pub fn flat_nodes(&self) -> Vec<usize> {
+        (0..self.depths.len())
+            .filter(|&idx| idx == 0 || self.leaves[idx])
+            .collect()
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        self.leaves.iter().filter(|&&leaf| leaf).count()
+    }
+
+    /// Position of the node at `index` among the leaf columns, if it is a leaf.
+    pub fn leaf_position(&self, index: usize) -> Option<usize> {
+        (index > 0 && *self.leaves.get(index)?)
+            .then(|| self.leaves[..index].iter().filter(|&&leaf| leaf).count())
+    }
+
+    /// Node index of the 1-based `selected` leaf (0 means no selection).
+    pub fn leaf_node(&self, selected: usize) -> Option<usize> {
+        let leaf = selected.checked_sub(1)?;
+        self.leaves
+            .iter()
+            .enumerate()
+            .filter(|(_, is_leaf)| **is_leaf)
+            .nth(leaf)
+            .map(|(idx, _)| idx)
+    }
+}
+
 // TODO: Add Dictionary Values
+#[derive(Serialize)]
 pub struct FileSchema {
     pub columns: Vec<SchemaInfo>,
 }
@@ -62,24 +266,13 @@ impl FileSchema {
             md.file_metadata().schema_descr();
         let root = schema_descr.root_schema();
 
-        // Pre-compute codec + encoding summary for every leaf column
-        let mut summaries: Vec<(String, String)> = Vec::new();
-        for (col_idx, _) in schema_descr.columns().iter().enumerate() {
-            // use std::collections::BTreeSet;
-            let mut codecs: HashSet<String> = HashSet::new();
-            let mut encs: HashSet<String> = HashSet::new();
-
-            md.row_groups().iter().for_each(|rg| {
-                let col_chunk = rg.column(col_idx);
-                codecs.insert(format!("{:?}", col_chunk.compression()));
-                encs.extend(col_chunk.encodings().map(|enc| format!("{enc:?}")));
-            });
-
-            let codec_summary = codecs.into_iter().collect::<Vec<_>>().join(", ");
-            let enc_summary = encs.into_iter().collect::<Vec<_>>().join(", ");
-
-            summaries.push((codec_summary, enc_summary));
-        }
+        // One pass over the row groups per leaf column, the columns in
+        // parallel: files with thousands of row groups make this the bulk of
+        // opening them.
+        let leaves: Vec<LeafSummary> = (0..schema_descr.num_columns())
+            .into_par_iter()
+            .map(|col_idx| summarize_column(md, col_idx))
+            .collect();
 
         let mut lines: Vec<SchemaInfo> = Vec::new();
         lines.push(SchemaInfo::Root {
@@ -89,7 +282,7 @@ impl FileSchema {
 
         let children = root.get_fields();
         let count = children.len();
-        let mut leaf_idx: usize = 0;
+        let mut leaves = leaves.into_iter();
 
         for (idx, child) in children.iter().enumerate() {
             traverse(
@@ -97,15 +290,110 @@ impl FileSchema {
                 "   ".to_string(),
                 idx == count - 1,
                 &mut lines,
-                &mut leaf_idx,
-                &summaries,
-                md,
+                &mut leaves,
             );
         }
 
+        let shredded = variant::shredding(md);
+        if !shredded.is_empty() {
+            for idx in 0..lines.len() {
+                let path = column_path(&lines, idx);
+                if let SchemaInfo::Group { shredding, .. } = &mut lines[idx] {
+                    *shredding = shredded.get(&path).copied();
+                }
+            }
+        }
+
         Ok(FileSchema { columns: lines })
     }
 
+    pub fn outline(&self) -> SchemaOutline {
+        self.columns
+            .iter()
+            .map(|c| (c.depth(), matches!(c, SchemaInfo::Primitive { .. })))
+            .collect()
+    }
+
+    /// Node indices shown when the groups in `collapsed` are folded.
+    pub fn visible_nodes(&self, collapsed: &BTreeSet<usize>) -> Vec<usize> {
+        self.outline().visible_nodes(collapsed)
+    }
+
+    /// Dotted path of the node at `index` from the top of the schema (e.g.
+    /// `a.b.c`), leaving out the root.
+    pub fn column_path(&self, index: usize) -> String {
+        column_path(&self.columns, index)
+    }
+
+    /// Width of the flat view: the longest leaf path plus its indent.
+    pub fn flat_width(&self) -> usize {
+        (1..self.columns.len())
+            .filter(|&idx| matches!(self.columns[idx], SchemaInfo::Primitive { .. }))
+            .map(|idx| display_width(&self.column_path(idx)) + 3)
+            .max()
+            .unwrap_or(0)
+            .max(24)
+    }
+
+    /// The `n` leaf columns with the largest compressed size, largest first,
+    /// as (dotted path, compressed bytes).
+    pub fn largest_columns(&self, n: usize) -> Vec<(String, u64)> {
+        let mut sizes: Vec<(String, u64)> = self
+            .columns
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, c)| match c {
+                SchemaInfo::Primitive { stats, .. } => {
+                    Some((self.column_path(idx), stats.total_compressed_size))
+                }
+                _ => None,
+            })
+            .collect();
+        sizes.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+        sizes.truncate(n);
+        sizes
+    }
+
+    /// Compressed and uncompressed bytes of every leaf under the node at
+    /// `index` (the node itself if it is a leaf, the whole file for the root).
+    pub fn subtree_size(&self, index: usize) -> (u64, u64) {
+        let Some(node) = self.columns.get(index) else {
+            return (0, 0);
+        };
+        let depth = node.depth();
+        std::iter::once(node)
+            .chain(
+                self.columns[index + 1..]
+                    .iter()
+                    .take_while(|c| c.depth() > depth),
+            )
+            .filter_map(|c| match c {
+                SchemaInfo::Primitive { stats, .. } => {
+                    Some((stats.total_compressed_size, stats.total_uncompressed_size))
+                }
+                _ => None,
+            })
+            .fold((0, 0), |(compressed, uncompressed), (c, u)| {
+                (compressed + c, uncompressed + u)
+            })
+    }
+
+    /// Node indices of the leaf columns, largest compressed size first (the
+    /// schema order among equal sizes).
+    pub fn leaves_by_size(&self) -> Vec<usize> {
+        let mut leaves: Vec<(usize, u64)> = self
+            .columns
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, c)| match c {
+                SchemaInfo::Primitive { stats, .. } => Some((idx, stats.total_compressed_size)),
+                _ => None,
+            })
+            .collect();
+        leaves.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+        leaves.into_iter().map(|(idx, _)| idx).collect()
+    }
+
     pub fn column_group_name(&self, index: usize) -> String {
         match self.columns.get(index).unwrap() {
             SchemaInfo::Primitive { name, .. } => name.clone(),
@@ -121,13 +409,41 @@ impl FileSchema {
             .count()
     }
 
+    /// Position of the schema node at `index` among the leaf columns, if it is
+    /// a leaf.
+    pub fn leaf_position(&self, index: usize) -> Option<usize> {
+        matches!(self.columns.get(index)?, SchemaInfo::Primitive { .. }).then(|| {
+            self.columns[..index]
+                .iter()
+                .filter(|c| matches!(c, SchemaInfo::Primitive { .. }))
+                .count()
+        })
+    }
+
+    /// Schema node index of the 1-based `selected` leaf (0 means no selection).
+    pub fn leaf_schema_index(&self, selected: usize) -> Option<usize> {
+        let leaf = selected.checked_sub(1)?;
+        self.columns
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| matches!(c, SchemaInfo::Primitive { .. }))
+            .nth(leaf)
+            .map(|(idx, _)| idx)
+    }
+
+    /// Leaf position of the first leaf at or after schema node `index`.
+    pub fn first_leaf_from(&self, index: usize) -> Option<usize> {
+        (index..self.columns.len()).find_map(|idx| self.leaf_position(idx))
+    }
+
     pub fn tree_width(&self) -> usize {
         self.columns
             .iter()
             .map(|c| match c {
                 SchemaInfo::Root { display, .. } => display.len(),
                 SchemaInfo::Primitive { display, .. } => display.len(),
-                SchemaInfo::Group { display, .. } => display.len(),
+                // Room for the expand / collapse marker.
+                SchemaInfo::Group { display, .. } => display.len() + 2,
             })
             .max()
             .unwrap_or(0)
@@ -145,12 +461,58 @@ impl FileSchema {
             .collect()
     }
 
-    pub fn generate_table_rows(&self, selected_index: Option<usize>) -> Vec<Row<'_>> {
+    /// Dotted paths of the leaf columns, in column chunk order.
+    pub fn leaf_paths(&self) -> Vec<String> {
+        (0..self.columns.len())
+            .filter(|&idx| matches!(self.columns[idx], SchemaInfo::Primitive { .. }))
+            .map(|idx| self.column_path(idx))
+            .collect()
+    }
+
+    /// Width the ` #id` field id labels add to the schema tree.
+    pub fn field_id_width(&self) -> usize {
+        self.columns
+            .iter()
+            .filter_map(SchemaInfo::field_id)
+            .map(|id| display_width(&field_id_label(id)))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Leaf columns of the declared sort order with their paths, most
+    /// significant first.
+    pub fn sort_keys(&self) -> Vec<(String, SortKey)> {
+        let mut keys: Vec<(String, SortKey)> = (1..self.columns.len())
+            .filter_map(|idx| match &self.columns[idx] {
+                SchemaInfo::Primitive { info, .. } => {
+                    info.sort_key.map(|key| (self.column_path(idx), key))
+                }
+                _ => None,
+            })
+            .collect();
+        keys.sort_by_key(|(_, key)| key.position);
+        keys
+    }
+
+    /// Index of the node whose dotted path is `path`, group or leaf.
+    pub fn find_path(&self, path: &str) -> Option<usize> {
+        (1..self.columns.len()).find(|&idx| self.column_path(idx) == path)
+    }
+}
+
+#[cfg(feature = "tui")]
+impl FileSchema {
+    pub fn generate_table_rows(
+        &self,
+        selected_index: Option<usize>,
+        theme: &Theme,
+    ) -> Vec<Row<'_>> {
         let mut primitive_index = 1; // Start counting primitives from 1 (like app does)
 
         self.columns
             .iter()
-            .filter_map(|col| {
+            .enumerate()
+            .filter_map(|(node, col)| {
                 if let SchemaInfo::Primitive { info, stats, .. } = col {
                     let compression_ratio = if stats.total_uncompressed_size > 0 {
                         format!(
@@ -165,32 +527,58 @@ impl FileSchema {
                     let is_selected = selected_index == Some(primitive_index);
 
                     let mut row = Row::new([
+                        Cell::from(field_id_text(info.field_id)),
                         Cell::from(info.repetition.clone()),
                         Cell::from(info.physical.clone()),
+                        Cell::from(logical_text(info)),
                         Cell::from(format_size(stats.total_compressed_size)),
+                        Cell::from(self.file_share_text(stats.total_compressed_size)),
                         Cell::from(format_size(stats.total_uncompressed_size)),
-                        Cell::from(compression_ratio),
+                        Cell::from(format_size(stats.arrow_memory)),
+                        Cell::from(compression_ratio).style(ratio_style(
+                            stats.total_compressed_size,
+                            stats.total_uncompressed_size,
+                            theme,
+                        )),
                         Cell::from(info.encoding.clone()),
                         Cell::from(info.codec.clone()),
-                        Cell::from(stats.min.clone().unwrap_or_else(|| "NULL".to_string())),
-                        Cell::from(stats.max.clone().unwrap_or_else(|| "NULL".to_string())),
+                        Cell::from(min_text(stats)).style(time_style(stats.min_time, theme)),
+                        Cell::from(max_text(stats)).style(time_style(stats.max_time, theme)),
                         Cell::from(stats.nulls.to_string()),
+                        Cell::from(distinct_text(stats, None)),
+                        Cell::from(info.sort_key.map_or("-".to_string(), |key| key.to_string())),
+                        Cell::from(info.column_order.clone()),
                     ]);
 
                     if is_selected {
-                        row = row.style(
-                            ratatui::style::Style::default()
-                                .bg(Color::Yellow)
-                                .fg(Color::Black),
-                        );
+                        row = row.style(theme.selection_style());
                     }
 
                     primitive_index += 1;
                     Some(row)
-                } else if let SchemaInfo::Group { repetition, .. } = col {
+                } else if let SchemaInfo::Group {
+                    repetition,
+                    field_id,
+                    logical,
+                    shredding,
+                    ..
+                } = col
+                {
+                    let (compressed, uncompressed) = self.subtree_size(node);
                     let row = Row::new(vec![
-                        Cell::from(repetition.clone().green()),
-                        Cell::from("group".green()),
+                        Cell::from(field_id_text(*field_id)),
+                        Cell::from(repetition.clone().fg(theme.group)),
+                        Cell::from("group".fg(theme.group)),
+                        Cell::from(group_logical_text(logical, *shredding)),
+                        Cell::from(format_size(compressed)),
+                        Cell::from(self.file_share_text(compressed)),
+                        Cell::from(format_size(uncompressed)),
+                        Cell::from(""),
+                        Cell::from(ratio_text(compressed, uncompressed)).style(ratio_style(
+                            compressed,
+                            uncompressed,
+                            theme,
+                        )),
                     ]);
                     Some(row)
                 } else {
@@ -205,6 +593,7 @@ impl FileSchema {
         selected_index: usize,
         start_col: usize,
         num_cols: usize,
+        theme: &Theme,
     ) -> (Vec<Row<'_>>, Vec<usize>) {
         self.generate_table_rows_with_scroll(
             selected_index,
@@ -212,6 +601,7 @@ impl FileSchema {
             num_cols,
             0,
             self.columns.len(),
+            theme,
         )
     }
 
@@ -222,17 +612,38 @@ impl FileSchema {
         num_cols: usize,
         start_row: usize,
         num_rows: usize,
+        theme: &Theme,
+    ) -> (Vec<Row<'_>>, Vec<usize>) {
+        let nodes: Vec<usize> = (start_row + 1..self.columns.len()).take(num_rows).collect();
+        self.generate_table_rows_for_nodes(
+            self.leaf_schema_index(selected_index),
+            start_col,
+            num_cols,
+            &nodes,
+            &BTreeMap::new(),
+            theme,
+        )
+    }
+
+    /// Table rows for the schema nodes in `nodes` (e.g. the visible window of
+    /// the tree), highlighting `selected_node`. `distinct_estimates` fills in
+    /// the Distinct column, by leaf position, where the footer has no count.
+    pub fn generate_table_rows_for_nodes(
+        &self,
+        selected_node: Option<usize>,
+        start_col: usize,
+        num_cols: usize,
+        nodes: &[usize],
+        distinct_estimates: &BTreeMap<usize, u64>,
+        theme: &Theme,
     ) -> (Vec<Row<'_>>, Vec<usize>) {
-        let mut primitive_index = 1; // Start counting primitives from 1 (like app does)
         let mut column_widths = vec![0usize; num_cols];
 
-        let rows = self
-            .columns
+        let rows = nodes
             .iter()
-            .enumerate()
-            .skip(start_row + 1)
-            .take(num_rows)
-            .filter_map(|(_col_idx, col)| {
+            .filter_map(|&node| {
+                let col = self.columns.get(node)?;
+                let is_selected = selected_node == Some(node);
                 if let SchemaInfo::Primitive { info, stats, .. } = col {
                     let compression_ratio = if stats.total_uncompressed_size > 0 {
                         format!(
@@ -244,21 +655,29 @@ impl FileSchema {
                         "N/A".to_string()
                     };
 
-                    let is_selected =
-                        selected_index > 0 && (selected_index - start_row) == primitive_index;
-
                     // Create all cells first
                     let all_cells = vec![
+                        field_id_text(info.field_id),
                         info.repetition.clone(),
                         info.physical.clone(),
+                        logical_text(info),
                         format_size(stats.total_compressed_size),
+                        self.file_share_text(stats.total_compressed_size),
                         format_size(stats.total_uncompressed_size),
+                        format_size(stats.arrow_memory),
                         compression_ratio,
                         info.encoding.clone(),
                         info.codec.clone(),
-                        stats.min.clone().unwrap_or_else(|| "NULL".to_string()),
-                        stats.max.clone().unwrap_or_else(|| "NULL".to_string()),
+                        min_text(stats),
+                        max_text(stats),
                         stats.nulls.to_string(),
+                        distinct_text(
+                            stats,
+                            self.leaf_position(node)
+                                .and_then(|leaf| distinct_estimates.get(&leaf).copied()),
+                        ),
+                        info.sort_key.map_or("-".to_string(), |key| key.to_string()),
+                        info.column_order.clone(),
                     ];
 
                     // Select only the visible columns and track their content lengths
@@ -270,29 +689,51 @@ impl FileSchema {
 
                     // Update column widths with the maximum seen so far
                     for (col_idx, content) in visible_cell_contents.iter().enumerate() {
-                        column_widths[col_idx] = column_widths[col_idx].max(content.len());
+                        column_widths[col_idx] = column_widths[col_idx].max(display_width(content));
                     }
 
                     // Create cells from the content
-                    let visible_cells: Vec<_> =
-                        visible_cell_contents.into_iter().map(Cell::from).collect();
+                    let visible_cells: Vec<_> = visible_cell_contents
+                        .into_iter()
+                        .enumerate()
+                        .map(|(idx, content)| match start_col + idx {
+                            8 => Cell::from(content).style(ratio_style(
+                                stats.total_compressed_size,
+                                stats.total_uncompressed_size,
+                                theme,
+                            )),
+                            11 => Cell::from(content).style(time_style(stats.min_time, theme)),
+                            12 => Cell::from(content).style(time_style(stats.max_time, theme)),
+                            _ => Cell::from(content),
+                        })
+                        .collect();
 
                     let mut row = Row::new(visible_cells);
 
                     if is_selected {
-                        row = row.style(
-                            ratatui::style::Style::default()
-                                .bg(Color::Yellow)
-                                .fg(Color::Black),
-                        );
+                        row = row.style(theme.selection_style());
                     }
 
-                    primitive_index += 1;
                     Some(row)
-                } else if let SchemaInfo::Group { repetition, .. } = col {
+                } else if let SchemaInfo::Group {
+                    repetition,
+                    field_id,
+                    logical,
+                    shredding,
+                    ..
+                } = col
+                {
+                    let (compressed, uncompressed) = self.subtree_size(node);
                     let all_cells = vec![
+                        field_id_text(*field_id),
                         repetition.clone(),
                         "group".to_string(),
+                        group_logical_text(logical, *shredding),
+                        format_size(compressed),
+                        self.file_share_text(compressed),
+                        format_size(uncompressed),
+                        "".to_string(),
+                        ratio_text(compressed, uncompressed),
                         "".to_string(),
                         "".to_string(),
                         "".to_string(),
@@ -311,22 +752,28 @@ impl FileSchema {
 
                     // Update column widths with the maximum seen so far
                     for (col_idx, content) in visible_cell_contents.iter().enumerate() {
-                        column_widths[col_idx] = column_widths[col_idx].max(content.len());
+                        column_widths[col_idx] = column_widths[col_idx].max(display_width(content));
                     }
 
                     let visible_cells: Vec<_> = visible_cell_contents
                         .into_iter()
                         .enumerate()
-                        .map(|(idx, content)| {
-                            if idx == 0 || idx == 1 {
-                                Cell::from(content.green())
-                            } else {
-                                Cell::from(content)
-                            }
+                        .map(|(idx, content)| match start_col + idx {
+                            // Repetition and the "group" type.
+                            1 | 2 => Cell::from(content.fg(theme.group)),
+                            8 => Cell::from(content).style(ratio_style(
+                                compressed,
+                                uncompressed,
+                                theme,
+                            )),
+                            _ => Cell::from(content),
                         })
                         .collect();
 
-                    let row = Row::new(visible_cells);
+                    let mut row = Row::new(visible_cells);
+                    if is_selected {
+                        row = row.style(theme.selection_style());
+                    }
                     Some(row)
                 } else {
                     None
@@ -336,6 +783,107 @@ impl FileSchema {
 
         (rows, column_widths)
     }
+
+    /// Share of the file's compressed size taken by `compressed` bytes,
+    /// e.g. `70.2%`.
+    fn file_share_text(&self, compressed: u64) -> String {
+        let (total, _) = self.subtree_size(0);
+        format!("{:.1}%", percent_of(compressed, total))
+    }
+}
+
+/// A compression ratio colored by how well it compresses.
+#[cfg(feature = "tui")]
+fn ratio_style(compressed: u64, uncompressed: u64, theme: &Theme) -> Style {
+    if compressed > 0 {
+        theme.ratio_style(uncompressed as f64 / compressed as f64)
+    } else {
+        Style::default()
+    }
+}
+
+/// Uncompressed over compressed size, e.g. `2.50x`.
+#[cfg(feature = "tui")]
+fn ratio_text(compressed: u64, uncompressed: u64) -> String {
+    if compressed > 0 {
+        format!("{:.2}x", uncompressed as f64 / compressed as f64)
+    } else {
+        "N/A".to_string()
+    }
+}
+
+#[cfg(feature = "tui")]
+fn logical_text(info: &ColumnSchemaInfo) -> String {
+    if info.logical.is_empty() {
+        "-".to_string()
+    } else {
+        info.logical.clone()
+    }
+}
+
+/// A group's logical type, followed by how much of it was shredded when it
+/// is part of a VARIANT column.
+#[cfg(feature = "tui")]
+fn group_logical_text(logical: &str, shredding: Option<Shredding>) -> String {
+    match shredding {
+        Some(shredding) if logical.is_empty() => shredding.to_string(),
+        Some(shredding) => format!("{logical}, {shredding}"),
+        None => logical.to_string(),
+    }
+}
+
+/// Min, or the lower corner of the bounding box of a geospatial column.
+#[cfg(feature = "tui")]
+fn min_text(stats: &ColumnStats) -> String {
+    match (&stats.min, stats.geo_bounds) {
+        (Some(min), _) => time_text(min, stats.min_time),
+        (None, Some(bounds)) => bounds.min_text(),
+        (None, None) => "NULL".to_string(),
+    }
+}
+
+/// Max, or the upper corner of the bounding box of a geospatial column.
+#[cfg(feature = "tui")]
+fn max_text(stats: &ColumnStats) -> String {
+    match (&stats.max, stats.geo_bounds) {
+        (Some(max), _) => time_text(max, stats.max_time),
+        (None, Some(bounds)) => bounds.max_text(),
+        (None, None) => "NULL".to_string(),
+    }
+}
+
+/// A timestamp min or max, followed by how long ago it is if
+/// `relative_times` is set.
+#[cfg(feature = "tui")]
+fn time_text(text: &str, time: Option<DateTime<Utc>>) -> String {
+    match time {
+        Some(time) if relative_times() => format!("{text} ({})", relative_time(time, Utc::now())),
+        _ => text.to_string(),
+    }
+}
+
+/// Timestamps in the future or at the Unix epoch stand out as likely bugs.
+#[cfg(feature = "tui")]
+fn time_style(time: Option<DateTime<Utc>>, theme: &Theme) -> Style {
+    match time {
+        Some(time) if is_suspicious_time(time, Utc::now()) => Style::default().fg(theme.bad),
+        _ => Style::default(),
+    }
+}
+
+#[cfg(feature = "tui")]
+fn field_id_text(field_id: Option<i32>) -> String {
+    field_id.map_or("-".to_string(), |id| id.to_string())
+}
+
+/// The footer's distinct count, else an estimate marked with `≈`.
+#[cfg(feature = "tui")]
+fn distinct_text(stats: &ColumnStats, estimate: Option<u64>) -> String {
+    match (stats.distinct, estimate) {
+        (Some(count), _) => count.to_string(),
+        (None, Some(estimate)) => format!("≈{estimate}"),
+        (None, None) => "-".to_string(),
+    }
 }
 
 fn traverse(
@@ -343,46 +891,53 @@ fn traverse(
     prefix: String,
     is_last: bool,
     lines: &mut Vec<SchemaInfo>,
-    leaf_idx: &mut usize,
-    summaries: &Vec<(String, String)>,
-    md: &ParquetMetaData,
+    leaves: &mut impl Iterator<Item = LeafSummary>,
 ) {
     let connector: &'static str = if is_last { "└─" } else { "├─" };
     let line = format!("{}{} {}", prefix, connector, node.name());
+    // Every level of nesting adds a three-character indent.
+    let depth = prefix.chars().count() / 3;
 
     if node.is_primitive() {
         let repetition = format!("{:?}", node.get_basic_info().repetition());
         let physical = format!("{:?}", node.get_physical_type());
-        let logical = match node.get_basic_info().logical_type_ref() {
-            Some(logical_type) => logical_type_to_string(logical_type),
-            None => String::new(),
-        };
 
-        let (codec_sum, enc_sum) = &summaries[*leaf_idx];
-        let stats = aggregate_column_stats(md, *leaf_idx, node.get_physical_type());
+        let leaf = leaves
+            .next()
+            .expect("one summary per leaf column of the schema");
         let info = ColumnSchemaInfo {
             name: node.name().to_string(),
             repetition: repetition.clone(),
             physical: physical.clone(),
-            logical: logical.clone(),
-            codec: codec_sum.clone(),
-            encoding: enc_sum.clone(),
+            logical: leaf.logical,
+            codec: leaf.codecs,
+            encoding: leaf.encodings,
             converted_type: node.get_basic_info().converted_type().to_string(),
             dictionary_values: None,
+            column_order: leaf.column_order,
+            sort_key: leaf.sort_key,
+            field_id: field_id(node),
         };
         lines.push(SchemaInfo::Primitive {
             name: node.name().to_string(),
             display: line,
+            depth,
             info: Box::new(info),
-            stats,
+            stats: leaf.stats,
         });
-
-        *leaf_idx += 1;
     } else {
         lines.push(SchemaInfo::Group {
             name: node.name().to_string(),
             display: line,
+            depth,
             repetition: format!("{:?}", node.get_basic_info().repetition()),
+            field_id: field_id(node),
+            logical: node
+                .get_basic_info()
+                .logical_type_ref()
+                .map(logical_type_to_string)
+                .unwrap_or_default(),
+            shredding: None,
         });
     }
 
@@ -391,86 +946,194 @@ fn traverse(
         let count = fields.len();
         for (idx, child) in fields.iter().enumerate() {
             let next_prefix = format!("{}{}", prefix, if is_last { "   " } else { "│  " });
-            traverse(
-                child.as_ref(),
-                next_prefix,
-                idx == count - 1,
-                lines,
-                leaf_idx,
-                summaries,
-                md,
-            );
+            traverse(child.as_ref(), next_prefix, idx == count - 1, lines, leaves);
         }
     }
 }
 
-/// Efficiently aggregate column statistics across all row groups
-fn aggregate_column_stats(
-    md: &ParquetMetaData,
-    col_idx: usize,
-    physical: PhysicalType,
-) -> ColumnStats {
-    let (min_bytes, max_bytes, nulls, distinct, total_compressed_size, total_uncompressed_size) =
-        md.row_groups().iter().fold(
-            (
-                None::<Vec<u8>>,
-                None::<Vec<u8>>,
-                0u64,
-                None::<u64>,
-                0u64,
-                0u64,
-            ),
-            |(
-                mut min_bytes,
-                mut max_bytes,
-                mut nulls,
-                mut distinct,
-                mut compressed,
-                mut uncompressed,
-            ),
-             rg| {
-                let col_meta = rg.column(col_idx);
-                if let Some(stats) = col_meta.statistics() {
-                    nulls += stats.null_count_opt().unwrap_or(0);
-                    distinct =
-                        Some(distinct.unwrap_or(0) + stats.distinct_count_opt().unwrap_or(0));
-
-                    if let Some(min_b) = stats.min_bytes_opt()
-                        && min_bytes.as_ref().is_none_or(|mb| min_b < &mb[..])
-                    {
-                        min_bytes = Some(min_b.to_vec());
-                    }
-                    if let Some(max_b) = stats.max_bytes_opt()
-                        && max_bytes.as_ref().is_none_or(|mb| max_b > &mb[..])
-                    {
-                        max_bytes = Some(max_b.to_vec());
-                    }
-                }
-                compressed += col_meta.compressed_size() as u64;
-                uncompressed += col_meta.uncompressed_size() as u64;
-                (
-                    min_bytes,
-                    max_bytes,
-                    nulls,
-                    distinct,
-                    compressed,
-                    uncompressed,
-                )
-            },
-        );
+/// Field id label appended to a node in the schema tree.
+pub fn field_id_label(id: i32) -> String {
+    format!(" #{id}")
+}
 
-    ColumnStats {
-        min: min_bytes.as_deref().map(|b| decode_value(b, physical)),
-        max: max_bytes.as_deref().map(|b| decode_value(b, physical)),
-        nulls,
-        distinct,
-        total_compressed_size,
-        total_uncompressed_size,
+fn field_id(node: &ParquetType) -> Option<i32> {
+    let info = node.get_basic_info();
+    info.has_id().then(|| info.id())
+}
+
+/// What the footer says about one leaf column over all row groups.
+struct LeafSummary {
+    /// Codecs and encodings used by any of its chunks, comma separated.
+    codecs: String,
+    encodings: String,
+    stats: ColumnStats,
+    column_order: String,
+    sort_key: Option<SortKey>,
+    /// Logical type, with CRS references of geospatial types resolved.
+    logical: String,
+}
+
+/// Aggregate the chunks of leaf column `col_idx` in a single pass over the
+/// row groups.
+fn summarize_column(md: &ParquetMetaData, col_idx: usize) -> LeafSummary {
+    let mut codecs: HashSet<String> = HashSet::new();
+    let mut encodings: HashSet<String> = HashSet::new();
+    let mut min_bytes: Option<&[u8]> = None;
+    let mut max_bytes: Option<&[u8]> = None;
+    let mut nulls = 0u64;
+    let mut distinct: Option<u64> = None;
+    let mut compressed = 0u64;
+    let mut uncompressed = 0u64;
+    let mut arrow_memory = 0u64;
+    let mut sort_key: Option<SortKey> = None;
+    let mut geo_bounds: Option<GeoBounds> = None;
+
+    for rg in md.row_groups() {
+        // The position and direction are taken from the first row group
+        // declaring the column; writers use one sort order per file.
+        if let Some((position, sort)) = rg.sorting_columns().and_then(|sorts| {
+            sorts
+                .iter()
+                .enumerate()
+                .find(|(_, sort)| sort.column_idx as usize == col_idx)
+        }) {
+            let key = sort_key.get_or_insert(SortKey {
+                position,
+                descending: sort.descending,
+                nulls_first: sort.nulls_first,
+                row_groups: 0,
+                total_row_groups: md.num_row_groups(),
+            });
+            key.row_groups += 1;
+        }
+        let col_meta = rg.column(col_idx);
+        codecs.insert(format!("{:?}", col_meta.compression()));
+        encodings.extend(col_meta.encodings().map(|enc| format!("{enc:?}")));
+        if let Some(stats) = col_meta.statistics() {
+            nulls += stats.null_count_opt().unwrap_or(0);
+            if let Some(count) = stats.distinct_count_opt() {
+                distinct = Some(distinct.unwrap_or(0) + count);
+            }
+            if let Some(min_b) = stats.min_bytes_opt()
+                && min_bytes.is_none_or(|mb| min_b < mb)
+            {
+                min_bytes = Some(min_b);
+            }
+            if let Some(max_b) = stats.max_bytes_opt()
+                && max_bytes.is_none_or(|mb| max_b > mb)
+            {
+                max_bytes = Some(max_b);
+            }
+        }
+        if let Some(bounds) = col_meta
+            .geo_statistics()
+            .and_then(|stats| stats.bounding_box())
+            .map(GeoBounds::from)
+        {
+            geo_bounds = Some(geo_bounds.map_or(bounds, |merged| merged.merge(bounds)));
+        }
+        compressed += col_meta.compressed_size() as u64;
+        uncompressed += col_meta.uncompressed_size() as u64;
+        arrow_memory += arrow_memory_estimate(col_meta, rg.num_rows().max(0) as u64);
+    }
+
+    let descr = md.file_metadata().schema_descr().column(col_idx);
+    let physical = descr.physical_type();
+    let logical = match descr.logical_type_ref() {
+        Some(logical_type) => geo::describe_type(
+            logical_type,
+            md.file_metadata().key_value_metadata().map(Vec::as_slice),
+        )
+        .unwrap_or_else(|| logical_type_to_string(logical_type)),
+        None => String::new(),
+    };
+    let column_order = column_order_name(md.file_metadata().column_order(col_idx));
+    LeafSummary {
+        logical,
+        column_order,
+        sort_key,
+        codecs: codecs.into_iter().collect::<Vec<_>>().join(", "),
+        encodings: encodings.into_iter().collect::<Vec<_>>().join(", "),
+        stats: ColumnStats {
+            min: min_bytes.map(|b| decode_logical_value(b, physical, descr.logical_type_ref())),
+            max: max_bytes.map(|b| decode_logical_value(b, physical, descr.logical_type_ref())),
+            min_time: min_bytes
+                .zip(descr.logical_type_ref())
+                .and_then(|(b, logical_type)| logical::decode_time(b, logical_type)),
+            max_time: max_bytes
+                .zip(descr.logical_type_ref())
+                .and_then(|(b, logical_type)| logical::decode_time(b, logical_type)),
+            nulls,
+            distinct,
+            total_compressed_size: compressed,
+            total_uncompressed_size: uncompressed,
+            arrow_memory,
+            geo_bounds,
+        },
+    }
+}
+
+fn column_order_name(order: ColumnOrder) -> String {
+    match order {
+        ColumnOrder::TYPE_DEFINED_ORDER(SortOrder::SIGNED) => "Signed".to_string(),
+        ColumnOrder::TYPE_DEFINED_ORDER(SortOrder::UNSIGNED) => "Unsigned".to_string(),
+        ColumnOrder::TYPE_DEFINED_ORDER(SortOrder::UNDEFINED) => "Undefined".to_string(),
+        ColumnOrder::UNDEFINED => "Legacy (signed)".to_string(),
+        ColumnOrder::UNKNOWN => "Unknown".to_string(),
     }
 }
 
+/// Rough in-memory size of a column chunk decoded into an Arrow array: a
+/// fixed-width slot per value (nulls included), a validity bitmap for
+/// nullable columns and offsets for lists and strings. String data comes
+/// from the writer's unencoded size statistics when present and falls back
+/// to the uncompressed size, which undercounts dictionary-encoded chunks.
+/// Lists are counted from the repetition level histogram when present, and
+/// otherwise as one per row of the row group's `rows` at each nesting level.
+pub fn arrow_memory_estimate(col: &ColumnChunkMetaData, rows: u64) -> u64 {
+    let descr = col.column_descr();
+    let values = col.num_values().max(0) as u64;
+
+    let data = match descr.physical_type() {
+        PhysicalType::BOOLEAN => values.div_ceil(8),
+        PhysicalType::INT32 | PhysicalType::FLOAT => values * 4,
+        // INT96 is read as a nanosecond timestamp.
+        PhysicalType::INT64 | PhysicalType::DOUBLE | PhysicalType::INT96 => values * 8,
+        PhysicalType::FIXED_LEN_BYTE_ARRAY => values * descr.type_length().max(0) as u64,
+        PhysicalType::BYTE_ARRAY => {
+            let bytes = col
+                .unencoded_byte_array_data_bytes()
+                .map(|b| b.max(0) as u64)
+                .unwrap_or(col.uncompressed_size().max(0) as u64);
+            (values + 1) * 4 + bytes
+        }
+    };
+    let validity = if descr.max_def_level() > 0 {
+        values.div_ceil(8)
+    } else {
+        0
+    };
+    // A list at nesting level `k` starts at each value whose repetition
+    // level is below `k`, and every level adds one closing offset.
+    let max_rep = descr.max_rep_level().max(0) as usize;
+    let lists: u64 = match col.repetition_level_histogram() {
+        Some(histogram) => (1..=max_rep)
+            .map(|level| {
+                let starts = histogram.values().iter().take(level);
+                starts.map(|&n| n.max(0) as u64).sum::<u64>()
+            })
+            .sum(),
+        None => rows * max_rep as u64,
+    };
+    let list_offsets = match max_rep {
+        0 => 0,
+        _ => (lists + max_rep as u64) * 4,
+    };
+    data + validity + list_offsets
+}
+
 /// Decode raw statistics bytes into a readable value based on the physical type
-fn decode_value(bytes: &[u8], physical: PhysicalType) -> String {
+pub(crate) fn decode_value(bytes: &[u8], physical: PhysicalType) -> String {
     match physical {
         PhysicalType::INT32 if bytes.len() == 4 => {
             i32::from_le_bytes(bytes.try_into().unwrap()).to_string()
@@ -503,7 +1166,19 @@ fn decode_value(bytes: &[u8], physical: PhysicalType) -> String {
     }
 }
 
-fn logical_type_to_string(logical_type: &LogicalType) -> String {
+/// Like `decode_value`, but shows values of FLOAT16, UUID, JSON and BSON
+/// columns by their logical type rather than as text or hex.
+pub(crate) fn decode_logical_value(
+    bytes: &[u8],
+    physical: PhysicalType,
+    logical: Option<&LogicalType>,
+) -> String {
+    logical
+        .and_then(|logical_type| logical::format_value(bytes, logical_type))
+        .unwrap_or_else(|| decode_value(bytes, physical))
+}
+
+pub(crate) fn logical_type_to_string(logical_type: &LogicalType) -> String {
     match logical_type {
         LogicalType::Decimal(t) => format!("Decimal({},{})", t.scale, t.precision),
         LogicalType::Integer(t) => format!(
@@ -563,6 +1238,10 @@ fn logical_type_to_string(logical_type: &LogicalType) -> String {
                 }
             ),
         },
+        LogicalType::Geometry(_) | LogicalType::Geography(_) => {
+            geo::describe_type(logical_type, None).unwrap_or_default()
+        }
+        LogicalType::Variant(_) => "Variant".to_string(),
         _ => format!("{logical_type:?}"),
     }
 }
@@ -570,6 +1249,7 @@ fn logical_type_to_string(logical_type: &LogicalType) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::file::{test_data_path, write_test_file};
     use parquet::file::reader::{FileReader, SerializedFileReader};
     use std::fs::File;
 
@@ -649,6 +1329,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_column_stats_add_up_over_row_groups() {
+        let path = test_data_path("nulls.snappy.parquet");
+        let reader = SerializedFileReader::try_from(File::open(path).unwrap()).unwrap();
+        let md = reader.metadata();
+        let file_schema = FileSchema::from_metadata(md).unwrap();
+
+        let leaves = file_schema.columns.iter().filter_map(|col| match col {
+            SchemaInfo::Primitive { stats, .. } => Some(stats),
+            _ => None,
+        });
+        let mut count = 0;
+        for (col_idx, stats) in leaves.enumerate() {
+            let chunks = || md.row_groups().iter().map(|rg| rg.column(col_idx));
+            let compressed: i64 = chunks().map(|c| c.compressed_size()).sum();
+            let nulls: u64 = chunks()
+                .filter_map(|c| c.statistics().and_then(|s| s.null_count_opt()))
+                .sum();
+            assert_eq!(stats.total_compressed_size, compressed as u64);
+            assert_eq!(stats.nulls, nulls);
+            count += 1;
+        }
+        assert_eq!(count, md.file_metadata().schema_descr().num_columns());
+    }
+
     #[test]
     fn test_column_schema_info() {
         let file_schema = load_alltypes_schema();
@@ -683,26 +1388,48 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "tui")]
     fn test_generate_table_rows() {
         let file_schema = load_alltypes_schema();
 
         // Generate rows with no selection
-        let rows = file_schema.generate_table_rows(None);
+        let rows = file_schema.generate_table_rows(None, &Theme::default());
 
         // Should have 11 primitive columns
         assert_eq!(11, rows.len());
 
         // Generate rows with selection
-        let rows_selected = file_schema.generate_table_rows(Some(1));
+        let rows_selected = file_schema.generate_table_rows(Some(1), &Theme::default());
         assert_eq!(11, rows_selected.len());
     }
 
     #[test]
+    #[cfg(feature = "tui")]
+    fn test_distinct_text() {
+        let file_schema = load_alltypes_schema();
+        let SchemaInfo::Primitive { stats, .. } = &file_schema.columns[1] else {
+            panic!("expected a leaf");
+        };
+        // alltypes_plain has no distinct counts in its footer.
+        assert_eq!(stats.distinct, None);
+        assert_eq!(distinct_text(stats, None), "-");
+        assert_eq!(distinct_text(stats, Some(8)), "≈8");
+
+        let counted = ColumnStats {
+            distinct: Some(3),
+            ..stats.clone()
+        };
+        assert_eq!(distinct_text(&counted, Some(8)), "3");
+    }
+
+    #[test]
+    #[cfg(feature = "tui")]
     fn test_generate_table_rows_with_columns() {
         let file_schema = load_alltypes_schema();
 
         // Test with different column ranges
-        let (rows, widths) = file_schema.generate_table_rows_with_columns(1, 0, 5);
+        let (rows, widths) =
+            file_schema.generate_table_rows_with_columns(1, 0, 5, &Theme::default());
 
         // Should have rows for primitive columns
         assert!(!rows.is_empty());
@@ -717,6 +1444,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "tui")]
     fn test_generate_table_rows_with_scroll() {
         let file_schema = load_alltypes_schema();
 
@@ -727,6 +1455,7 @@ mod tests {
             10, // num_cols
             0,  // start_row
             5,  // num_rows (limit to 5)
+            &Theme::default(),
         );
 
         // Should have at most 5 rows
@@ -736,6 +1465,113 @@ mod tests {
         assert_eq!(10, widths.len());
     }
 
+    #[test]
+    fn test_leaf_position_round_trip() {
+        let file_schema = load_alltypes_schema();
+
+        // Root is not a leaf; the first leaf follows it.
+        assert_eq!(None, file_schema.leaf_position(0));
+        assert_eq!(Some(0), file_schema.leaf_position(1));
+        assert_eq!(Some(1), file_schema.leaf_schema_index(1));
+        assert_eq!(None, file_schema.leaf_schema_index(0));
+        assert_eq!(Some(0), file_schema.first_leaf_from(0));
+
+        for leaf in 0..file_schema.column_size() {
+            let idx = file_schema.leaf_schema_index(leaf + 1).unwrap();
+            assert_eq!(Some(leaf), file_schema.leaf_position(idx));
+        }
+    }
+
+    // root
+    // ├─ a
+    // ├─ g ▾
+    // │  ├─ b
+    // │  └─ h ▾
+    // │     └─ c
+    // └─ d
+    fn nested_outline() -> SchemaOutline {
+        [
+            (0, false),
+            (1, true),
+            (1, false),
+            (2, true),
+            (2, false),
+            (3, true),
+            (1, true),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn test_outline_structure() {
+        let outline = nested_outline();
+        assert!(outline.is_group(2) && outline.is_group(4));
+        assert!(!outline.is_group(0) && !outline.is_group(1));
+        assert_eq!(outline.parent(5), Some(4));
+        assert_eq!(outline.parent(6), Some(0));
+        assert_eq!(outline.subtree_end(2), 6);
+        assert_eq!(outline.subtree_end(4), 6);
+        assert_eq!(outline.leaf_position(5), Some(2));
+        assert_eq!(outline.leaf_node(4), Some(6));
+    }
+
+    #[test]
+    fn test_visible_nodes_hide_collapsed_subtrees() {
+        let outline = nested_outline();
+        assert_eq!(
+            outline.visible_nodes(&BTreeSet::new()),
+            vec![0, 1, 2, 3, 4, 5, 6]
+        );
+        assert_eq!(outline.visible_nodes(&[4].into()), vec![0, 1, 2, 3, 4, 6]);
+        // Folding an outer group hides nested groups whatever their state.
+        assert_eq!(outline.visible_nodes(&[2, 4].into()), vec![0, 1, 2, 6]);
+    }
+
+    #[test]
+    fn test_flat_nodes_are_root_and_leaves() {
+        assert_eq!(nested_outline().flat_nodes(), vec![0, 1, 3, 5, 6]);
+    }
+
+    #[test]
+    fn test_column_path_joins_ancestor_names() {
+        let path = test_data_path("nulls.snappy.parquet");
+        let reader = SerializedFileReader::try_from(File::open(path).unwrap()).unwrap();
+        let file_schema = FileSchema::from_metadata(reader.metadata()).unwrap();
+        let descr = reader.metadata().file_metadata().schema_descr();
+
+        let leaf_paths: Vec<String> = (0..file_schema.columns.len())
+            .filter(|&idx| file_schema.leaf_position(idx).is_some())
+            .map(|idx| file_schema.column_path(idx))
+            .collect();
+        let expected: Vec<String> = descr.columns().iter().map(|c| c.path().string()).collect();
+        assert_eq!(leaf_paths, expected);
+        assert!(file_schema.flat_width() >= expected.iter().map(|p| p.len()).max().unwrap());
+    }
+
+    #[test]
+    fn test_largest_columns_sorted_by_compressed_size() {
+        let file_schema = load_alltypes_schema();
+        let top = file_schema.largest_columns(3);
+        assert_eq!(3, top.len());
+        assert!(top.windows(2).all(|w| w[0].1 >= w[1].1));
+
+        let all = file_schema.largest_columns(usize::MAX);
+        assert_eq!(file_schema.column_size(), all.len());
+        assert_eq!(all[..3], top[..]);
+    }
+
+    #[test]
+    fn test_depth_follows_nesting() {
+        let file_schema = load_alltypes_schema();
+        assert_eq!(file_schema.columns[0].depth(), 0);
+        assert!(file_schema.columns[1..].iter().all(|c| c.depth() == 1));
+        assert_eq!(
+            file_schema.visible_nodes(&BTreeSet::new()).len(),
+            file_schema.columns.len()
+        );
+    }
+
     #[test]
     fn test_schema_info_types() {
         let file_schema = load_alltypes_schema();
@@ -842,6 +1678,52 @@ mod tests {
         assert_eq!(value, "FFFEFD");
     }
 
+    #[test]
+    fn test_arrow_memory_estimate() {
+        let path = test_data_path("alltypes_plain.parquet");
+        let reader = SerializedFileReader::try_from(File::open(path).unwrap()).unwrap();
+        let rg = reader.metadata().row_group(0);
+        let by_name = |name: &str| {
+            let idx = (0..rg.num_columns())
+                .find(|&i| rg.column(i).column_path().string() == name)
+                .unwrap();
+            arrow_memory_estimate(rg.column(idx), 8)
+        };
+        // 8 optional values: one validity byte plus the values themselves.
+        assert_eq!(by_name("id"), 1 + 8 * 4);
+        assert_eq!(by_name("bigint_col"), 1 + 8 * 8);
+        assert_eq!(by_name("bool_col"), 1 + 1);
+        // Strings add 9 offsets to the data.
+        assert!(by_name("string_col") > 1 + 9 * 4);
+
+        let schema = load_alltypes_schema();
+        assert!(schema.columns.iter().all(|c| match c {
+            SchemaInfo::Primitive { stats, .. } => stats.arrow_memory > 0,
+            _ => true,
+        }));
+    }
+
+    #[test]
+    fn test_arrow_memory_estimate_of_lists() {
+        use arrow::array::{ListArray, RecordBatch};
+        use arrow::datatypes::Int32Type;
+        use std::sync::Arc;
+
+        // 100 lists of 10 values each.
+        let lists = ListArray::from_iter_primitive::<Int32Type, _, _>(
+            (0..100).map(|i| Some((0..10).map(move |j| Some(i * 10 + j)))),
+        );
+        let batch = RecordBatch::try_from_iter([("list", Arc::new(lists) as _)]).unwrap();
+        let path = write_test_file("list-memory", &batch, None);
+        let reader = SerializedFileReader::try_from(File::open(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let col = reader.metadata().row_group(0).column(0);
+        assert!(col.repetition_level_histogram().is_some());
+        // 1,000 values with their validity bits, and 101 offsets rather
+        // than one per value.
+        assert_eq!(arrow_memory_estimate(col, 100), 1000 * 4 + 125 + 101 * 4);
+    }
+
     #[test]
     fn test_logical_type_to_string() {
         // Test Decimal
@@ -855,4 +1737,177 @@ mod tests {
         let unsigned = LogicalType::integer(16, false);
         assert_eq!(logical_type_to_string(&unsigned), "Integer(16,unsign)");
     }
+
+    #[test]
+    fn test_sorting_columns_become_sort_keys() {
+        use crate::file::parquet_ctx::open_reader;
+        use crate::file::row_groups::RowGroups;
+        use arrow::array::{Int64Array, RecordBatch, StringArray};
+        use parquet::file::metadata::SortingColumn;
+        use parquet::file::properties::WriterProperties;
+        use std::sync::Arc;
+
+        // Sorted by `name` descending, then `id` with nulls first.
+        let batch = RecordBatch::try_from_iter([
+            ("id", Arc::new(Int64Array::from(vec![1, 2, 3])) as _),
+            (
+                "name",
+                Arc::new(StringArray::from(vec!["c", "b", "a"])) as _,
+            ),
+        ])
+        .unwrap();
+        let props = WriterProperties::builder()
+            .set_sorting_columns(Some(vec![
+                SortingColumn {
+                    column_idx: 1,
+                    descending: true,
+                    nulls_first: false,
+                },
+                SortingColumn {
+                    column_idx: 0,
+                    descending: false,
+                    nulls_first: true,
+                },
+            ]))
+            .build();
+        let path = write_test_file("sorted", &batch, Some(props));
+        let (reader, _) = open_reader(&path.display().to_string()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let schema = FileSchema::from_metadata(reader.metadata()).unwrap();
+        let keys: Vec<(String, String)> = schema
+            .sort_keys()
+            .into_iter()
+            .map(|(path, key)| (path, key.to_string()))
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                ("name".to_string(), "#1 ↓".to_string()),
+                ("id".to_string(), "#2 ↑ nulls first".to_string()),
+            ]
+        );
+        let SchemaInfo::Primitive { info, .. } = &schema.columns[1] else {
+            panic!("id is a leaf");
+        };
+        assert_eq!(info.column_order, "Signed");
+
+        let row_groups = RowGroups::from_file_reader(&reader).unwrap();
+        let sorted_by: Vec<String> = row_groups.row_groups[0]
+            .sorting_columns
+            .iter()
+            .map(|sort| sort.to_string())
+            .collect();
+        assert_eq!(sorted_by, ["name ↓", "id ↑ nulls first"]);
+    }
+
+    #[test]
+    fn test_field_ids_of_leaves_and_groups() {
+        use crate::file::parquet_ctx::open_reader;
+        use arrow::array::{Array, ArrayRef, Int64Array, RecordBatch, StructArray};
+        use arrow::datatypes::{DataType, Field};
+        use parquet::arrow::PARQUET_FIELD_ID_META_KEY;
+        use std::collections::HashMap;
+        use std::sync::Arc;
+
+        let with_id = |field: Field, id: i32| {
+            field.with_metadata(HashMap::from([(
+                PARQUET_FIELD_ID_META_KEY.to_string(),
+                id.to_string(),
+            )]))
+        };
+        // `id` has field id 1, the `point` group 2 and its `x` 3; `y` has none.
+        let x = with_id(Field::new("x", DataType::Int64, false), 3);
+        let y = Field::new("y", DataType::Int64, false);
+        let point = StructArray::from(vec![
+            (Arc::new(x), Arc::new(Int64Array::from(vec![1])) as ArrayRef),
+            (Arc::new(y), Arc::new(Int64Array::from(vec![2])) as ArrayRef),
+        ]);
+        let id = with_id(Field::new("id", DataType::Int64, false), 1);
+        let point_field = with_id(Field::new("point", point.data_type().clone(), false), 2);
+        let batch = RecordBatch::try_new(
+            Arc::new(arrow::datatypes::Schema::new(vec![id, point_field])),
+            vec![Arc::new(Int64Array::from(vec![7])), Arc::new(point)],
+        )
+        .unwrap();
+        let path = write_test_file("field-ids", &batch, None);
+        let (reader, _) = open_reader(&path.display().to_string()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let schema = FileSchema::from_metadata(reader.metadata()).unwrap();
+        let field_ids: Vec<(String, Option<i32>)> = (1..schema.columns.len())
+            .map(|idx| (schema.column_path(idx), schema.columns[idx].field_id()))
+            .collect();
+        assert_eq!(
+            field_ids,
+            [
+                ("id".to_string(), Some(1)),
+                ("point".to_string(), Some(2)),
+                ("point.x".to_string(), Some(3)),
+                ("point.y".to_string(), None),
+            ]
+        );
+        assert_eq!(schema.columns[0].field_id(), None);
+        assert_eq!(schema.field_id_width(), " #3".len());
+    }
+
+    #[test]
+    fn test_subtree_size_adds_up_the_leaves() {
+        use crate::file::parquet_ctx::open_reader;
+        use arrow::array::{ArrayRef, Int64Array, RecordBatch, StringArray, StructArray};
+        use arrow::datatypes::{DataType, Field};
+        use std::sync::Arc;
+
+        let payload = StructArray::from(vec![
+            (
+                Arc::new(Field::new("body", DataType::Utf8, false)),
+                Arc::new(StringArray::from(vec!["x".repeat(4096); 8])) as ArrayRef,
+            ),
+            (
+                Arc::new(Field::new("size", DataType::Int64, false)),
+                Arc::new(Int64Array::from_iter_values(0..8)) as ArrayRef,
+            ),
+        ]);
+        let batch = RecordBatch::try_from_iter([
+            (
+                "id",
+                Arc::new(Int64Array::from_iter_values(0..8)) as ArrayRef,
+            ),
+            ("payload", Arc::new(payload) as ArrayRef),
+        ])
+        .unwrap();
+        let path = write_test_file("subtree", &batch, None);
+        let (reader, _) = open_reader(&path.display().to_string()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let schema = FileSchema::from_metadata(reader.metadata()).unwrap();
+        let payload = schema.find_path("payload").unwrap();
+        let leaves = [
+            schema.find_path("payload.body"),
+            schema.find_path("payload.size"),
+        ];
+        let (compressed, uncompressed) = leaves
+            .iter()
+            .map(|leaf| schema.subtree_size(leaf.unwrap()))
+            .fold((0, 0), |(c, u), (leaf_c, leaf_u)| (c + leaf_c, u + leaf_u));
+        assert!(compressed > 0);
+        assert_eq!(schema.subtree_size(payload), (compressed, uncompressed));
+        let id = schema.subtree_size(schema.find_path("id").unwrap());
+        assert_eq!(
+            schema.subtree_size(0),
+            (compressed + id.0, uncompressed + id.1)
+        );
+    }
+
+    #[test]
+    fn test_partial_sort_key_counts_row_groups() {
+        let key = SortKey {
+            position: 0,
+            descending: false,
+            nulls_first: false,
+            row_groups: 2,
+            total_row_groups: 5,
+        };
+        assert_eq!(key.to_string(), "#1 ↑ (2/5 RGs)");
+    }
 }
@@ -0,0 +1,362 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::Path;
+
+use parquet::file::reader::FileReader;
+use parquet::schema::types::SchemaDescriptor;
+use serde::Serialize;
+
+use crate::file::error::FileIOError;
+use crate::file::parquet_ctx::open_reader;
+use crate::file::schema::logical_type_to_string;
+
+/// What a leaf column looks like in one file. Two files are compatible when
+/// every column has the same shape in both.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct ColumnShape {
+    pub physical: String,
+    pub logical: String,
+    pub repetition: String,
+    pub field_id: Option<i32>,
+}
+
+impl ColumnShape {
+    fn type_name(&self) -> String {
+        if self.logical.is_empty() {
+            self.physical.clone()
+        } else {
+            format!("{} ({})", self.physical, self.logical)
+        }
+    }
+}
+
+/// Leaf columns of a schema by dotted path.
+pub type SchemaShape = BTreeMap<String, ColumnShape>;
+
+pub fn schema_shape(schema: &SchemaDescriptor) -> SchemaShape {
+    schema
+        .columns()
+        .iter()
+        .map(|col| {
+            let info = col.self_type().get_basic_info();
+            let shape = ColumnShape {
+                physical: format!("{:?}", col.physical_type()),
+                logical: info
+                    .logical_type_ref()
+                    .map(logical_type_to_string)
+                    .unwrap_or_default(),
+                repetition: format!("{:?}", info.repetition()),
+                field_id: info.has_id().then(|| info.id()),
+            };
+            (col.path().string(), shape)
+        })
+        .collect()
+}
+
+/// How one file's schema differs from the reference schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Deviation {
+    MissingColumn {
+        column: String,
+    },
+    ExtraColumn {
+        column: String,
+    },
+    TypeChanged {
+        column: String,
+        expected: String,
+        found: String,
+    },
+    RepetitionChanged {
+        column: String,
+        expected: String,
+        found: String,
+    },
+    FieldIdChanged {
+        column: String,
+        expected: Option<i32>,
+        found: Option<i32>,
+    },
+}
+
+impl fmt::Display for Deviation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let id = |id: &Option<i32>| id.map_or("none".to_string(), |id| id.to_string());
+        match self {
+            Deviation::MissingColumn { column } => write!(f, "missing column {column}"),
+            Deviation::ExtraColumn { column } => write!(f, "extra column {column}"),
+            Deviation::TypeChanged {
+                column,
+                expected,
+                found,
+            } => write!(f, "{column}: type {found}, expected {expected}"),
+            Deviation::RepetitionChanged {
+                column,
+                expected,
+                found,
+            } => write!(f, "{column}: repetition {found}, expected {expected}"),
+            Deviation::FieldIdChanged {
+                column,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{column}: field id {}, expected {}",
+                id(found),
+                id(expected)
+            ),
+        }
+    }
+}
+
+/// Compare `found` against the `reference` schema.
+pub fn compare_shapes(reference: &SchemaShape, found: &SchemaShape) -> Vec<Deviation> {
+    let mut deviations = Vec::new();
+    for (column, expected) in reference {
+        let Some(actual) = found.get(column) else {
+            deviations.push(Deviation::MissingColumn {
+                column: column.clone(),
+            });
+            continue;
+        };
+        if (&expected.physical, &expected.logical) != (&actual.physical, &actual.logical) {
+            deviations.push(Deviation::TypeChanged {
+                column: column.clone(),
+                expected: expected.type_name(),
+                found: actual.type_name(),
+            });
+        }
+        if expected.repetition != actual.repetition {
+            deviations.push(Deviation::RepetitionChanged {
+                column: column.clone(),
+                expected: expected.repetition.clone(),
+                found: actual.repetition.clone(),
+            });
+        }
+        if expected.field_id != actual.field_id {
+            deviations.push(Deviation::FieldIdChanged {
+                column: column.clone(),
+                expected: expected.field_id,
+                found: actual.field_id,
+            });
+        }
+    }
+    for column in found.keys().filter(|c| !reference.contains_key(*c)) {
+        deviations.push(Deviation::ExtraColumn {
+            column: column.clone(),
+        });
+    }
+    deviations
+}
+
+/// Result of checking that a set of files share one schema.
+#[derive(Debug, Serialize)]
+pub struct SchemaCheck {
+    pub files: usize,
+    /// The file whose schema the others are compared against: the first file
+    /// with the most common schema.
+    pub reference: Option<String>,
+    /// Files that differ from the reference, with how they differ.
+    pub deviations: Vec<(String, Vec<Deviation>)>,
+    /// Files that could not be read at all.
+    pub errors: Vec<(String, String)>,
+}
+
+impl SchemaCheck {
+    /// Read the footer of every file and compare their schemas.
+    pub fn run(paths: &[String]) -> Self {
+        let shapes = paths
+            .iter()
+            .map(|path| {
+                let shape = open_reader(path).map(|(reader, _)| {
+                    schema_shape(reader.metadata().file_metadata().schema_descr())
+                });
+                (path.clone(), shape)
+            })
+            .collect();
+        Self::from_shapes(shapes)
+    }
+
+    pub fn from_shapes(shapes: Vec<(String, Result<SchemaShape, FileIOError>)>) -> Self {
+        let files = shapes.len();
+        let mut errors = Vec::new();
+        let mut readable = Vec::new();
+        for (path, shape) in shapes {
+            match shape {
+                Ok(shape) => readable.push((path, shape)),
+                Err(e) => errors.push((path, e.to_string().replace('\n', " "))),
+            }
+        }
+
+        // Most common schema wins; on a tie, the one seen first.
+        let mut counts: HashMap<&SchemaShape, (usize, usize)> = HashMap::new();
+        for (idx, (_, shape)) in readable.iter().enumerate() {
+            counts.entry(shape).or_insert((0, idx)).0 += 1;
+        }
+        let reference = counts
+            .into_values()
+            .max_by_key(|&(count, first)| (count, std::cmp::Reverse(first)))
+            .map(|(_, first)| first);
+
+        let deviations = match reference {
+            Some(idx) => readable
+                .iter()
+                .map(|(path, shape)| (path.clone(), compare_shapes(&readable[idx].1, shape)))
+                .filter(|(_, deviations)| !deviations.is_empty())
+                .collect(),
+            None => Vec::new(),
+        };
+
+        Self {
+            files,
+            reference: reference.map(|idx| readable[idx].0.clone()),
+            deviations,
+            errors,
+        }
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        self.deviations.is_empty() && self.errors.is_empty()
+    }
+}
+
+impl fmt::Display for SchemaCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (path, deviations) in &self.deviations {
+            writeln!(f, "{path}")?;
+            for deviation in deviations {
+                writeln!(f, "  {deviation}")?;
+            }
+        }
+        for (path, error) in &self.errors {
+            writeln!(f, "{path}")?;
+            writeln!(f, "  error: {error}")?;
+        }
+        let reference = self.reference.as_deref().unwrap_or("—");
+        if self.is_consistent() {
+            write!(f, "{} files share the schema of {reference}", self.files)
+        } else {
+            write!(
+                f,
+                "{} of {} files differ from the schema of {reference}, {} could not be read",
+                self.deviations.len(),
+                self.files,
+                self.errors.len()
+            )
+        }
+    }
+}
+
+/// Replace every directory in `paths` with the `.parquet` files below it,
+/// sorted, so a whole dataset can be passed as one argument.
+pub fn expand_paths(paths: &[String]) -> std::io::Result<Vec<String>> {
+    fn walk(dir: &Path, out: &mut Vec<String>) -> std::io::Result<()> {
+        let mut entries: Vec<_> = std::fs::read_dir(dir)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<_, _>>()?;
+        entries.sort();
+        for path in entries {
+            if path.is_dir() {
+                walk(&path, out)?;
+            } else if path.extension().is_some_and(|ext| ext == "parquet") {
+                out.push(path.display().to_string());
+            }
+        }
+        Ok(())
+    }
+
+    let mut out = Vec::new();
+    for path in paths {
+        if Path::new(path).is_dir() {
+            walk(Path::new(path), &mut out)?;
+        } else {
+            out.push(path.clone());
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_data_path;
+
+    fn shape(physical: &str, field_id: Option<i32>) -> ColumnShape {
+        ColumnShape {
+            physical: physical.to_string(),
+            logical: String::new(),
+            repetition: "OPTIONAL".to_string(),
+            field_id,
+        }
+    }
+
+    #[test]
+    fn test_compare_shapes_reports_each_kind() {
+        let reference = SchemaShape::from([
+            ("id".to_string(), shape("INT64", Some(1))),
+            ("name".to_string(), shape("BYTE_ARRAY", Some(2))),
+            ("ts".to_string(), shape("INT64", Some(3))),
+        ]);
+        let found = SchemaShape::from([
+            ("id".to_string(), shape("INT32", Some(1))),
+            ("name".to_string(), shape("BYTE_ARRAY", Some(5))),
+            ("extra".to_string(), shape("BOOLEAN", None)),
+        ]);
+        let deviations: Vec<String> = compare_shapes(&reference, &found)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            deviations,
+            [
+                "id: type INT32, expected INT64",
+                "name: field id 5, expected 2",
+                "missing column ts",
+                "extra column extra",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_most_common_schema_is_the_reference() {
+        let common = SchemaShape::from([("id".to_string(), shape("INT64", None))]);
+        let odd = SchemaShape::from([("id".to_string(), shape("INT32", None))]);
+        let check = SchemaCheck::from_shapes(vec![
+            ("a".to_string(), Ok(odd)),
+            ("b".to_string(), Ok(common.clone())),
+            ("c".to_string(), Ok(common)),
+        ]);
+        assert_eq!(check.reference.as_deref(), Some("b"));
+        assert_eq!(check.deviations.len(), 1);
+        assert_eq!(check.deviations[0].0, "a");
+        assert!(!check.is_consistent());
+    }
+
+    #[test]
+    fn test_check_real_files() {
+        let same = SchemaCheck::run(&[
+            test_data_path("alltypes_plain.parquet"),
+            test_data_path("alltypes_dictionary.parquet"),
+        ]);
+        assert!(same.is_consistent(), "{same}");
+
+        let mixed = SchemaCheck::run(&[
+            test_data_path("alltypes_plain.parquet"),
+            test_data_path("nulls.snappy.parquet"),
+            test_data_path("missing.parquet"),
+        ]);
+        assert!(!mixed.is_consistent());
+        assert_eq!(mixed.errors.len(), 1);
+        assert!(mixed.to_string().contains("missing column"));
+    }
+
+    #[test]
+    fn test_expand_paths_walks_directories() {
+        let dir = crate::file::parquet_test_data();
+        let files = expand_paths(std::slice::from_ref(&dir)).unwrap();
+        assert!(files.iter().all(|f| f.ends_with(".parquet")));
+        assert!(files.contains(&test_data_path("alltypes_plain.parquet")));
+        assert!(files.is_sorted());
+    }
+}
@@ -0,0 +1,237 @@
+use std::fmt;
+use std::fs::File;
+use std::ops::Range;
+use std::str::FromStr;
+
+use arrow::util::display::{ArrayFormatter, FormatOptions};
+use parquet::arrow::arrow_reader::{ParquetRecordBatchReaderBuilder, RowSelection, RowSelector};
+use regex::Regex;
+
+use crate::file::progress::ScanProgress;
+
+/// Rows decoded per batch while searching the file.
+const READ_BATCH: usize = 8192;
+
+/// What to look for in preview values: text, matched anywhere in a value
+/// whatever its case, or a regular expression between slashes, e.g.
+/// `/^2024-0[1-3]/`.
+#[derive(Debug, Clone)]
+pub struct ValuePattern {
+    source: String,
+    regex: Regex,
+}
+
+impl ValuePattern {
+    pub fn is_match(&self, value: &str) -> bool {
+        self.regex.is_match(value)
+    }
+
+    /// Byte ranges of the matches in `value`, for highlighting.
+    pub fn ranges(&self, value: &str) -> Vec<Range<usize>> {
+        self.regex
+            .find_iter(value)
+            .filter(|m| !m.is_empty())
+            .map(|m| m.range())
+            .collect()
+    }
+
+    /// Whether any value of `row` matches.
+    pub fn matches_row<S: AsRef<str>>(&self, row: &[S]) -> bool {
+        row.iter().any(|value| self.is_match(value.as_ref()))
+    }
+
+    /// The first row after `from` (before it when going back) with a
+    /// matching value, wrapping around the ends and coming back to `from`
+    /// last.
+    pub fn find_row<S: AsRef<str>>(
+        &self,
+        rows: &[Vec<S>],
+        from: usize,
+        forward: bool,
+    ) -> Option<usize> {
+        let len = rows.len();
+        (1..=len)
+            .map(|step| {
+                if forward {
+                    (from + step) % len
+                } else {
+                    (from + len * step - step) % len
+                }
+            })
+            .find(|&row| self.matches_row(&rows[row]))
+    }
+
+    /// Scan the rows of the file from row `from`, numbered from 0, to the
+    /// end and return the first with a matching value. Values are formatted
+    /// by arrow, so dates and nested values may be written a little
+    /// differently from the preview. Reports the compressed bytes of the
+    /// row groups read to `progress` and stops if it is cancelled.
+    pub fn find_in_file(
+        &self,
+        path: &str,
+        from: usize,
+        progress: &ScanProgress,
+    ) -> Result<Option<usize>, String> {
+        let file = File::open(path).map_err(|e| format!("{path}: {e}"))?;
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file).map_err(|e| e.to_string())?;
+        let metadata = builder.metadata().clone();
+        let format = FormatOptions::default().with_null("NULL");
+
+        let mut first_row = 0;
+        for (index, row_group) in metadata.row_groups().iter().enumerate() {
+            let rows = row_group.num_rows().max(0) as usize;
+            let end = first_row + rows;
+            if end <= from {
+                first_row = end;
+                continue;
+            }
+            progress.check()?;
+            let skip = from.saturating_sub(first_row);
+            let file = File::open(path).map_err(|e| format!("{path}: {e}"))?;
+            let reader = ParquetRecordBatchReaderBuilder::try_new(file)
+                .and_then(|builder| {
+                    builder
+                        .with_row_groups(vec![index])
+                        .with_row_selection(RowSelection::from(vec![
+                            RowSelector::skip(skip),
+                            RowSelector::select(rows - skip),
+                        ]))
+                        .with_batch_size(READ_BATCH)
+                        .build()
+                })
+                .map_err(|e| e.to_string())?;
+
+            let mut row = first_row + skip;
+            for batch in reader {
+                progress.check()?;
+                let batch = batch.map_err(|e| e.to_string())?;
+                let formatters = batch
+                    .columns()
+                    .iter()
+                    .map(|column| ArrayFormatter::try_new(column.as_ref(), &format))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| e.to_string())?;
+                let found = (0..batch.num_rows()).find(|&i| {
+                    formatters
+                        .iter()
+                        .any(|formatter| self.is_match(&formatter.value(i).to_string()))
+                });
+                if let Some(i) = found {
+                    return Ok(Some(row + i));
+                }
+                row += batch.num_rows();
+            }
+            progress.advance(row_group.compressed_size().max(0) as u64);
+            first_row = end;
+        }
+        Ok(None)
+    }
+}
+
+impl FromStr for ValuePattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err("usage: search <text> | /<regex>/".to_string());
+        }
+        let regex = match s.strip_prefix('/').and_then(|s| s.strip_suffix('/')) {
+            Some(pattern) => Regex::new(pattern).map_err(|e| format!("invalid regex: {e}"))?,
+            None => Regex::new(&format!("(?i){}", regex::escape(s))).map_err(|e| e.to_string())?,
+        };
+        Ok(Self {
+            source: s.to_string(),
+            regex,
+        })
+    }
+}
+
+impl PartialEq for ValuePattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl fmt::Display for ValuePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::sample_data::ParquetSampleData;
+    use crate::file::test_data_path;
+
+    #[test]
+    fn test_parse() {
+        let text: ValuePattern = "Foo Bar".parse().unwrap();
+        assert!(text.is_match("a foo bar"));
+        assert!(!text.is_match("foobar"));
+        assert_eq!(text.to_string(), "Foo Bar");
+
+        // Text is taken literally.
+        let dots: ValuePattern = "1.5".parse().unwrap();
+        assert!(!dots.is_match("125"));
+
+        let regex: ValuePattern = "/^a.c$/".parse().unwrap();
+        assert!(regex.is_match("abc"));
+        assert!(!regex.is_match("ABC"));
+        assert!(!regex.is_match("xabc"));
+
+        assert!("/(/".parse::<ValuePattern>().is_err());
+        assert!("".parse::<ValuePattern>().is_err());
+        // A lone slash is text.
+        assert!("/".parse::<ValuePattern>().unwrap().is_match("a/b"));
+    }
+
+    #[test]
+    fn test_ranges() {
+        let pattern: ValuePattern = "ab".parse().unwrap();
+        assert_eq!(pattern.ranges("abxAB"), vec![0..2, 3..5]);
+        let empty: ValuePattern = "/x*/".parse().unwrap();
+        assert!(empty.ranges("abc").is_empty());
+    }
+
+    #[test]
+    fn test_find_row_wraps() {
+        let rows = vec![
+            vec!["a", "1"],
+            vec!["b", "2"],
+            vec!["a", "3"],
+            vec!["c", "4"],
+        ];
+        let pattern: ValuePattern = "a".parse().unwrap();
+        assert_eq!(pattern.find_row(&rows, 0, true), Some(2));
+        assert_eq!(pattern.find_row(&rows, 2, true), Some(0));
+        assert_eq!(pattern.find_row(&rows, 0, false), Some(2));
+        assert_eq!(pattern.find_row(&rows, 3, false), Some(2));
+        // The only match is the row searched from.
+        let only: ValuePattern = "4".parse().unwrap();
+        assert_eq!(only.find_row(&rows, 3, true), Some(3));
+        let none: ValuePattern = "z".parse().unwrap();
+        assert_eq!(none.find_row(&rows, 0, true), None);
+        assert_eq!(none.find_row(&Vec::<Vec<&str>>::new(), 0, true), None);
+    }
+
+    #[test]
+    fn test_find_in_file() {
+        let path = test_data_path("alltypes_plain.parquet");
+        let progress = ScanProgress::default();
+        // Only `id` holds a 6.
+        let pattern: ValuePattern = "/^6$/".parse().unwrap();
+        let preview = ParquetSampleData::read_sample_data(&path).unwrap();
+        let row = pattern.find_row(&preview.rows, preview.total_rows - 1, true);
+        assert!(row.is_some());
+        assert_eq!(pattern.find_in_file(&path, 0, &progress), Ok(row));
+        let after = row.unwrap() + 1;
+        assert_eq!(pattern.find_in_file(&path, after, &progress), Ok(None));
+        assert!(progress.done() > 0);
+
+        let cancelled = ScanProgress::default();
+        cancelled.cancel();
+        assert!(pattern.find_in_file(&path, 0, &cancelled).is_err());
+        assert!(pattern.find_in_file("nope.parquet", 0, &progress).is_err());
+    }
+}
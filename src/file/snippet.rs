@@ -0,0 +1,282 @@
+use std::str::FromStr;
+
+use crate::file::schema::{ColumnSchemaInfo, FileSchema, SchemaInfo};
+
+/// What to copy about a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SnippetFormat {
+    /// Path, index and types on one line:
+    /// `a.b  INT64 (Timestamp(utc, micros))  column index 3`.
+    #[default]
+    Summary,
+    /// The dotted path, `a.b`.
+    Path,
+    /// The 0-based leaf column index, as parquet tools number columns.
+    Index,
+    /// The physical type and the logical type, if any.
+    Type,
+    /// A Spark SQL column definition, `` `a`.`b` TIMESTAMP ``.
+    Sql,
+    /// A PySpark field, `StructField("b", TimestampType(), True)`.
+    Spark,
+}
+
+impl FromStr for SnippetFormat {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "path" => Ok(Self::Path),
+            "index" => Ok(Self::Index),
+            "type" => Ok(Self::Type),
+            "sql" => Ok(Self::Sql),
+            "spark" => Ok(Self::Spark),
+            other => Err(format!(
+                "copy: unknown format '{other}', expected path, index, type, sql or spark"
+            )),
+        }
+    }
+}
+
+/// The leaf column at dotted `path` and leaf `index` as `format`.
+pub fn column_snippet(
+    info: &ColumnSchemaInfo,
+    path: &str,
+    index: usize,
+    format: SnippetFormat,
+) -> String {
+    let sql = sql_type(info);
+    let repeated = info.repetition == "REPEATED";
+    let nullable = info.repetition == "OPTIONAL";
+    match format {
+        SnippetFormat::Summary => format!("{path}  {}  column index {index}", type_text(info)),
+        SnippetFormat::Path => path.to_string(),
+        SnippetFormat::Index => index.to_string(),
+        SnippetFormat::Type => type_text(info),
+        SnippetFormat::Sql => {
+            let name = path
+                .split('.')
+                .map(|part| format!("`{}`", part.replace('`', "``")))
+                .collect::<Vec<_>>()
+                .join(".");
+            let sql = if repeated {
+                format!("ARRAY<{sql}>")
+            } else {
+                sql
+            };
+            let not_null = if nullable { "" } else { " NOT NULL" };
+            format!("{name} {sql}{not_null}")
+        }
+        SnippetFormat::Spark => {
+            let spark = spark_type(&sql);
+            let spark = if repeated {
+                format!("ArrayType({spark}, False)")
+            } else {
+                spark
+            };
+            let nullable = if nullable { "True" } else { "False" };
+            format!("StructField({:?}, {spark}, {nullable})", info.name)
+        }
+    }
+}
+
+/// A Spark SQL `CREATE TABLE` with a line per leaf column, nested ones by
+/// their dotted path as in [`SnippetFormat::Sql`].
+pub fn schema_ddl(schema: &FileSchema, table: &str) -> String {
+    let columns: Vec<String> = (0..schema.columns.len())
+        .filter_map(
+            |node| match (&schema.columns[node], schema.leaf_position(node)) {
+                (SchemaInfo::Primitive { info, .. }, Some(index)) => {
+                    let path = schema.column_path(node);
+                    Some(format!(
+                        "  {}",
+                        column_snippet(info, &path, index, SnippetFormat::Sql)
+                    ))
+                }
+                _ => None,
+            },
+        )
+        .collect();
+    format!(
+        "CREATE TABLE `{}` (\n{}\n);\n",
+        table.replace('`', "``"),
+        columns.join(",\n")
+    )
+}
+
+/// `INT64 (Timestamp(utc, micros))`, or just the physical type.
+fn type_text(info: &ColumnSchemaInfo) -> String {
+    if info.logical.is_empty() {
+        info.physical.clone()
+    } else {
+        format!("{} ({})", info.physical, info.logical)
+    }
+}
+
+/// The Spark SQL type a reader maps the column to.
+fn sql_type(info: &ColumnSchemaInfo) -> String {
+    let logical = info.logical.as_str();
+    if let Some(args) = logical
+        .strip_prefix("Decimal(")
+        .and_then(|args| args.strip_suffix(')'))
+        && let Some((scale, precision)) = args.split_once(',')
+    {
+        return format!("DECIMAL({precision},{scale})");
+    }
+    if let Some(args) = logical.strip_prefix("Integer(") {
+        let signed = !args.contains("unsign");
+        // Unsigned integers need the next wider type.
+        return match (args.split(',').next().unwrap_or_default(), signed) {
+            ("8", true) => "TINYINT",
+            ("16", true) | ("8", false) => "SMALLINT",
+            ("32", true) | ("16", false) => "INT",
+            ("64", true) | ("32", false) => "BIGINT",
+            _ => "DECIMAL(20,0)",
+        }
+        .to_string();
+    }
+    let sql = match logical {
+        "Date" => "DATE",
+        _ if logical.starts_with("Timestamp(utc") => "TIMESTAMP",
+        _ if logical.starts_with("Timestamp(local") => "TIMESTAMP_NTZ",
+        "String" | "Enum" | "Json" => "STRING",
+        "Float16" => "FLOAT",
+        _ => match (info.converted_type.as_str(), info.physical.as_str()) {
+            ("UTF8" | "ENUM" | "JSON", _) => "STRING",
+            (_, "BOOLEAN") => "BOOLEAN",
+            (_, "INT32") => "INT",
+            (_, "INT64") => "BIGINT",
+            (_, "INT96") => "TIMESTAMP",
+            (_, "FLOAT") => "FLOAT",
+            (_, "DOUBLE") => "DOUBLE",
+            _ => "BINARY",
+        },
+    };
+    sql.to_string()
+}
+
+/// The PySpark type for a Spark SQL type from [`sql_type`].
+fn spark_type(sql: &str) -> String {
+    if let Some(args) = sql
+        .strip_prefix("DECIMAL(")
+        .and_then(|args| args.strip_suffix(')'))
+    {
+        return format!("DecimalType({})", args.replace(',', ", "));
+    }
+    let name = match sql {
+        "BOOLEAN" => "BooleanType",
+        "TINYINT" => "ByteType",
+        "SMALLINT" => "ShortType",
+        "INT" => "IntegerType",
+        "BIGINT" => "LongType",
+        "FLOAT" => "FloatType",
+        "DOUBLE" => "DoubleType",
+        "STRING" => "StringType",
+        "DATE" => "DateType",
+        "TIMESTAMP" => "TimestampType",
+        "TIMESTAMP_NTZ" => "TimestampNTZType",
+        _ => "BinaryType",
+    };
+    format!("{name}()")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_data_path;
+
+    fn info(name: &str, repetition: &str, physical: &str, logical: &str) -> ColumnSchemaInfo {
+        ColumnSchemaInfo {
+            name: name.to_string(),
+            repetition: repetition.to_string(),
+            physical: physical.to_string(),
+            logical: logical.to_string(),
+            codec: String::new(),
+            converted_type: "NONE".to_string(),
+            encoding: String::new(),
+            dictionary_values: None,
+            column_order: String::new(),
+            sort_key: None,
+            field_id: None,
+        }
+    }
+
+    #[test]
+    fn test_formats() {
+        let ts = info("ts", "OPTIONAL", "INT64", "Timestamp(utc, micros)");
+        let snippet = |format| column_snippet(&ts, "event.ts", 3, format);
+        assert_eq!(
+            snippet(SnippetFormat::Summary),
+            "event.ts  INT64 (Timestamp(utc, micros))  column index 3"
+        );
+        assert_eq!(snippet(SnippetFormat::Path), "event.ts");
+        assert_eq!(snippet(SnippetFormat::Index), "3");
+        assert_eq!(
+            snippet(SnippetFormat::Type),
+            "INT64 (Timestamp(utc, micros))"
+        );
+        assert_eq!(snippet(SnippetFormat::Sql), "`event`.`ts` TIMESTAMP");
+        assert_eq!(
+            snippet(SnippetFormat::Spark),
+            r#"StructField("ts", TimestampType(), True)"#
+        );
+        assert_eq!("sql".parse(), Ok(SnippetFormat::Sql));
+        assert!("yaml".parse::<SnippetFormat>().is_err());
+    }
+
+    #[test]
+    fn test_types() {
+        let sql = |repetition, physical, logical| {
+            let column = info("c", repetition, physical, logical);
+            (
+                column_snippet(&column, "c", 0, SnippetFormat::Sql),
+                column_snippet(&column, "c", 0, SnippetFormat::Spark),
+            )
+        };
+        assert_eq!(
+            sql("REQUIRED", "FIXED_LEN_BYTE_ARRAY", "Decimal(2,10)"),
+            (
+                "`c` DECIMAL(10,2) NOT NULL".to_string(),
+                r#"StructField("c", DecimalType(10, 2), False)"#.to_string()
+            )
+        );
+        assert_eq!(sql("OPTIONAL", "INT32", "Integer(8,sign)").0, "`c` TINYINT");
+        assert_eq!(
+            sql("OPTIONAL", "INT32", "Integer(16,sign)").0,
+            "`c` SMALLINT"
+        );
+        assert_eq!(sql("OPTIONAL", "INT32", "Integer(16,unsign)").0, "`c` INT");
+        assert_eq!(
+            sql("OPTIONAL", "INT64", "Integer(64,unsign)").0,
+            "`c` DECIMAL(20,0)"
+        );
+        assert_eq!(sql("OPTIONAL", "INT32", "Date").0, "`c` DATE");
+        assert_eq!(
+            sql("OPTIONAL", "INT64", "Timestamp(local, nanos)").0,
+            "`c` TIMESTAMP_NTZ"
+        );
+        assert_eq!(sql("OPTIONAL", "BYTE_ARRAY", "String").0, "`c` STRING");
+        assert_eq!(sql("OPTIONAL", "BYTE_ARRAY", "").0, "`c` BINARY");
+        assert_eq!(sql("OPTIONAL", "INT96", "").0, "`c` TIMESTAMP");
+        assert_eq!(
+            sql("REPEATED", "INT32", ""),
+            (
+                "`c` ARRAY<INT> NOT NULL".to_string(),
+                r#"StructField("c", ArrayType(IntegerType(), False), False)"#.to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_schema_ddl() {
+        let path = test_data_path("alltypes_plain.parquet");
+        let ctx = crate::file::parquet_ctx::ParquetCtx::from_file(&path).unwrap();
+        let ddl = schema_ddl(&ctx.schema, "all`types");
+        assert!(
+            ddl.starts_with("CREATE TABLE `all``types` (\n  `id` INT,\n  `bool_col` BOOLEAN,\n")
+        );
+        assert!(ddl.contains("\n  `timestamp_col` TIMESTAMP"));
+        assert!(ddl.ends_with("\n);\n"));
+        assert_eq!(ddl.lines().count(), 13);
+    }
+}
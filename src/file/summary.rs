@@ -0,0 +1,184 @@
+use std::collections::BTreeSet;
+
+use itertools::Itertools;
+use parquet::file::reader::FileReader;
+use parquet::schema::printer::print_schema;
+use serde::Serialize;
+
+use crate::file::error::FileIOError;
+use crate::file::parquet_ctx::open_reader;
+use crate::file::utils::{commas, human_readable_bytes};
+
+/// One file's headline numbers, read from the footer only so that
+/// summarizing thousands of files stays fast.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileSummary {
+    pub path: String,
+    pub rows: u64,
+    /// Size of the file on disk, in bytes.
+    pub size: u64,
+    pub row_groups: usize,
+    /// Compression codecs used by any column chunk, sorted.
+    pub codecs: Vec<String>,
+    pub created_by: String,
+    /// Hash of the schema; files with the same schema have the same hash.
+    pub schema_hash: String,
+}
+
+impl FileSummary {
+    pub fn from_file(path: &str) -> Result<Self, FileIOError> {
+        let (reader, size) = open_reader(path)?;
+        let md = reader.metadata();
+
+        let codecs: BTreeSet<String> = md
+            .row_groups()
+            .iter()
+            .flat_map(|rg| rg.columns())
+            .map(|col| format!("{:?}", col.compression()))
+            .collect();
+
+        let mut schema = Vec::new();
+        print_schema(&mut schema, md.file_metadata().schema());
+
+        Ok(Self {
+            path: path.to_string(),
+            rows: md.row_groups().iter().map(|rg| rg.num_rows()).sum::<i64>() as u64,
+            size,
+            row_groups: md.num_row_groups(),
+            codecs: codecs.into_iter().collect(),
+            created_by: md.file_metadata().created_by().unwrap_or("—").to_string(),
+            schema_hash: format!("{:016x}", fnv1a(&schema)),
+        })
+    }
+}
+
+/// 64-bit FNV-1a; stable across runs and platforms, unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Format summaries (or the errors reading them) as an aligned text table,
+/// one row per file. Files whose schema differs from the most common one are
+/// marked with `*`.
+pub fn format_summary_table(summaries: &[(String, Result<FileSummary, FileIOError>)]) -> String {
+    let common_hash = summaries
+        .iter()
+        .filter_map(|(_, s)| s.as_ref().ok())
+        .map(|s| s.schema_hash.as_str())
+        .counts()
+        .into_iter()
+        .max_by_key(|(hash, count)| (*count, std::cmp::Reverse(*hash)))
+        .map(|(hash, _)| hash);
+
+    let header = [
+        "File",
+        "Rows",
+        "Size",
+        "Row groups",
+        "Codecs",
+        "Created by",
+        "Schema",
+    ]
+    .map(String::from)
+    .to_vec();
+    let mut rows = vec![header];
+    for (path, summary) in summaries {
+        rows.push(match summary {
+            Ok(s) => {
+                let odd = common_hash.is_some_and(|hash| hash != s.schema_hash);
+                vec![
+                    path.clone(),
+                    commas(s.rows),
+                    human_readable_bytes(s.size),
+                    commas(s.row_groups as u64),
+                    s.codecs.join(","),
+                    s.created_by.clone(),
+                    format!("{}{}", &s.schema_hash[..8], if odd { " *" } else { "" }),
+                ]
+            }
+            Err(e) => vec![path.clone(), format!("error: {e}").replace('\n', " ")],
+        });
+    }
+
+    let columns = rows[0].len();
+    let widths: Vec<usize> = (0..columns)
+        .map(|col| {
+            rows.iter()
+                .filter(|row| row.len() == columns || col == 0)
+                .filter_map(|row| row.get(col))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut out = String::new();
+    for row in &rows {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(col, (cell, width))| {
+                // Numbers read better right-aligned.
+                if (1..=3).contains(&col) && row.len() == columns {
+                    format!("{cell:>width$}")
+                } else {
+                    format!("{cell:<width$}")
+                }
+            })
+            .join("  ");
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_data_path;
+
+    #[test]
+    fn test_summary_reads_the_footer() {
+        let summary = FileSummary::from_file(&test_data_path("alltypes_plain.parquet")).unwrap();
+        assert_eq!(summary.rows, 8);
+        assert_eq!(summary.row_groups, 1);
+        assert!(summary.size > 0);
+        assert_eq!(summary.schema_hash.len(), 16);
+    }
+
+    #[test]
+    fn test_same_schema_same_hash() {
+        let plain = FileSummary::from_file(&test_data_path("alltypes_plain.parquet")).unwrap();
+        let dictionary =
+            FileSummary::from_file(&test_data_path("alltypes_dictionary.parquet")).unwrap();
+        let nulls = FileSummary::from_file(&test_data_path("nulls.snappy.parquet")).unwrap();
+        assert_eq!(plain.schema_hash, dictionary.schema_hash);
+        assert_ne!(plain.schema_hash, nulls.schema_hash);
+    }
+
+    #[test]
+    fn test_table_marks_the_odd_schema_out() {
+        let summaries: Vec<_> = [
+            "alltypes_plain.parquet",
+            "alltypes_dictionary.parquet",
+            "nulls.snappy.parquet",
+            "missing.parquet",
+        ]
+        .iter()
+        .map(|name| {
+            let path = test_data_path(name);
+            (name.to_string(), FileSummary::from_file(&path))
+        })
+        .collect();
+        let table = format_summary_table(&summaries);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].starts_with("File"));
+        assert!(!lines[1].ends_with('*'));
+        assert!(lines[3].starts_with("nulls.snappy.parquet") && lines[3].ends_with('*'));
+        assert!(lines[4].contains("error: File not found"));
+    }
+}
@@ -0,0 +1,334 @@
+use std::fmt;
+
+use itertools::Itertools;
+use parquet::basic::{ConvertedType, LogicalType, TimeUnit, Type as PhysicalType};
+use parquet::file::metadata::ParquetMetaData;
+use serde::Serialize;
+
+use crate::file::error::FileIOError;
+use crate::file::parquet_ctx::open_reader;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemporalKind {
+    Timestamp,
+    Time,
+    Date,
+}
+
+impl fmt::Display for TemporalKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TemporalKind::Timestamp => "timestamp",
+            TemporalKind::Time => "time",
+            TemporalKind::Date => "date",
+        })
+    }
+}
+
+/// How one leaf column stores dates or times.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TemporalColumn {
+    pub path: String,
+    pub kind: TemporalKind,
+    pub physical: String,
+    /// `millis`, `micros` or `nanos`; `None` for dates.
+    pub unit: Option<&'static str>,
+    /// The `isAdjustedToUTC` flag; `None` for dates and for INT96, which
+    /// records no flag.
+    pub utc: Option<bool>,
+    /// Stored in the deprecated 12-byte INT96 form, nanoseconds by
+    /// convention.
+    pub int96: bool,
+}
+
+/// Every temporal column of a file, for spotting columns that disagree on
+/// unit, UTC adjustment or INT96.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TemporalAudit {
+    pub path: String,
+    pub columns: Vec<TemporalColumn>,
+    /// Conventions that differ between timestamp columns.
+    pub mixed: Vec<String>,
+}
+
+impl TemporalAudit {
+    pub fn from_file(path: &str) -> Result<Self, FileIOError> {
+        let (reader, _) = open_reader(path)?;
+        Ok(Self::from_metadata(path, &reader.metadata_ptr()))
+    }
+
+    pub fn from_metadata(path: &str, md: &ParquetMetaData) -> Self {
+        let schema = md.file_metadata().schema_descr();
+        let columns: Vec<TemporalColumn> = schema
+            .columns()
+            .iter()
+            .filter_map(|column| {
+                let physical = column.physical_type();
+                let (kind, unit, utc) = match (column.logical_type_ref(), column.converted_type()) {
+                    (Some(LogicalType::Timestamp(t)), _) => (
+                        TemporalKind::Timestamp,
+                        Some(unit_name(&t.unit)),
+                        Some(t.is_adjusted_to_u_t_c),
+                    ),
+                    (Some(LogicalType::Time(t)), _) => (
+                        TemporalKind::Time,
+                        Some(unit_name(&t.unit)),
+                        Some(t.is_adjusted_to_u_t_c),
+                    ),
+                    (Some(LogicalType::Date), _) => (TemporalKind::Date, None, None),
+                    // Legacy converted types are always adjusted to UTC.
+                    (None, ConvertedType::TIMESTAMP_MILLIS) => {
+                        (TemporalKind::Timestamp, Some("millis"), Some(true))
+                    }
+                    (None, ConvertedType::TIMESTAMP_MICROS) => {
+                        (TemporalKind::Timestamp, Some("micros"), Some(true))
+                    }
+                    (None, ConvertedType::TIME_MILLIS) => {
+                        (TemporalKind::Time, Some("millis"), Some(true))
+                    }
+                    (None, ConvertedType::TIME_MICROS) => {
+                        (TemporalKind::Time, Some("micros"), Some(true))
+                    }
+                    (None, ConvertedType::DATE) => (TemporalKind::Date, None, None),
+                    _ if physical == PhysicalType::INT96 => {
+                        (TemporalKind::Timestamp, Some("nanos"), None)
+                    }
+                    _ => return None,
+                };
+                Some(TemporalColumn {
+                    path: column.path().string(),
+                    kind,
+                    physical: physical.to_string(),
+                    unit,
+                    utc,
+                    int96: physical == PhysicalType::INT96,
+                })
+            })
+            .collect();
+        let mixed = mixed_conventions(&columns);
+        TemporalAudit {
+            path: path.to_string(),
+            columns,
+            mixed,
+        }
+    }
+}
+
+fn unit_name(unit: &TimeUnit) -> &'static str {
+    match unit {
+        TimeUnit::MILLIS => "millis",
+        TimeUnit::MICROS => "micros",
+        TimeUnit::NANOS => "nanos",
+    }
+}
+
+/// One sentence per convention the timestamp columns disagree on.
+fn mixed_conventions(columns: &[TemporalColumn]) -> Vec<String> {
+    let timestamps: Vec<&TemporalColumn> = columns
+        .iter()
+        .filter(|c| c.kind == TemporalKind::Timestamp)
+        .collect();
+    let paths = |pred: &dyn Fn(&TemporalColumn) -> bool| {
+        timestamps
+            .iter()
+            .filter(|c| pred(c))
+            .map(|c| c.path.as_str())
+            .join(", ")
+    };
+    let mut mixed = Vec::new();
+
+    let units: Vec<&str> = timestamps.iter().filter_map(|c| c.unit).unique().collect();
+    if units.len() > 1 {
+        let groups = units
+            .iter()
+            .map(|&unit| format!("{unit} ({})", paths(&|c| c.unit == Some(unit))))
+            .join(", ");
+        mixed.push(format!("timestamps in different units: {groups}"));
+    }
+
+    if timestamps.iter().any(|c| c.utc == Some(true))
+        && timestamps.iter().any(|c| c.utc == Some(false))
+    {
+        mixed.push(format!(
+            "timestamps adjusted to UTC ({}) next to local ones ({})",
+            paths(&|c| c.utc == Some(true)),
+            paths(&|c| c.utc == Some(false))
+        ));
+    }
+
+    if timestamps.iter().any(|c| c.int96) && timestamps.iter().any(|c| !c.int96) {
+        mixed.push(format!(
+            "INT96 timestamps ({}) next to INT64 ones ({})",
+            paths(&|c| c.int96),
+            paths(&|c| !c.int96)
+        ));
+    }
+    mixed
+}
+
+impl fmt::Display for TemporalAudit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.columns.is_empty() {
+            return writeln!(f, "{}: no temporal columns", self.path);
+        }
+        writeln!(
+            f,
+            "{}: {} temporal column{}",
+            self.path,
+            self.columns.len(),
+            if self.columns.len() == 1 { "" } else { "s" }
+        )?;
+        let flag = |value: Option<bool>| match value {
+            Some(true) => "yes",
+            Some(false) => "no",
+            None => "-",
+        };
+        let rows: Vec<[String; 6]> = self
+            .columns
+            .iter()
+            .map(|c| {
+                [
+                    c.path.clone(),
+                    c.kind.to_string(),
+                    c.physical.clone(),
+                    c.unit.unwrap_or("-").to_string(),
+                    flag(c.utc).to_string(),
+                    flag(Some(c.int96)).to_string(),
+                ]
+            })
+            .collect();
+        let headers = ["Column", "Type", "Physical", "Unit", "UTC", "INT96"].map(str::to_string);
+        let widths: Vec<usize> = (0..5)
+            .map(|idx| {
+                rows.iter()
+                    .chain([&headers])
+                    .map(|row| row[idx].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        writeln!(f)?;
+        for row in std::iter::once(&headers).chain(&rows) {
+            let line = row[..5]
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{cell:<width$}"))
+                .chain([row[5].clone()])
+                .join("  ");
+            writeln!(f, "{line}")?;
+        }
+        writeln!(f)?;
+        if self.mixed.is_empty() {
+            return writeln!(f, "timestamp conventions agree");
+        }
+        for mixed in &self.mixed {
+            writeln!(f, "mixed: {mixed}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::{test_data_path, write_test_file};
+    use arrow::array::{
+        ArrayRef, Date32Array, RecordBatch, Time32MillisecondArray, TimestampMicrosecondArray,
+        TimestampMillisecondArray,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn test_int96_only() {
+        let audit = TemporalAudit::from_file(&test_data_path("alltypes_plain.parquet")).unwrap();
+        assert_eq!(
+            audit.columns,
+            [TemporalColumn {
+                path: "timestamp_col".to_string(),
+                kind: TemporalKind::Timestamp,
+                physical: "INT96".to_string(),
+                unit: Some("nanos"),
+                utc: None,
+                int96: true,
+            }]
+        );
+        assert!(audit.mixed.is_empty());
+    }
+
+    #[test]
+    fn test_int96_next_to_int64() {
+        let column = |path: &str, int96: bool| TemporalColumn {
+            path: path.to_string(),
+            kind: TemporalKind::Timestamp,
+            physical: if int96 { "INT96" } else { "INT64" }.to_string(),
+            unit: Some("nanos"),
+            utc: (!int96).then_some(true),
+            int96,
+        };
+        assert_eq!(
+            mixed_conventions(&[column("a", true), column("b", false), column("c", true)]),
+            ["INT96 timestamps (a, c) next to INT64 ones (b)"]
+        );
+        assert!(mixed_conventions(&[column("a", true), column("c", true)]).is_empty());
+    }
+
+    #[test]
+    fn test_mixed_conventions() {
+        let batch = RecordBatch::try_from_iter([
+            (
+                "created",
+                Arc::new(TimestampMillisecondArray::from(vec![0]).with_timezone("UTC")) as ArrayRef,
+            ),
+            (
+                "updated",
+                Arc::new(TimestampMicrosecondArray::from(vec![0])) as ArrayRef,
+            ),
+            ("day", Arc::new(Date32Array::from(vec![0])) as ArrayRef),
+            (
+                "at",
+                Arc::new(Time32MillisecondArray::from(vec![0])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+        let path = write_test_file("temporal", &batch, None);
+        let audit = TemporalAudit::from_file(&path.display().to_string());
+        std::fs::remove_file(&path).unwrap();
+        let audit = audit.unwrap();
+
+        let summary: Vec<_> = audit
+            .columns
+            .iter()
+            .map(|c| (c.path.as_str(), c.kind, c.unit, c.utc))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (
+                    "created",
+                    TemporalKind::Timestamp,
+                    Some("millis"),
+                    Some(true)
+                ),
+                (
+                    "updated",
+                    TemporalKind::Timestamp,
+                    Some("micros"),
+                    Some(false)
+                ),
+                ("day", TemporalKind::Date, None, None),
+                ("at", TemporalKind::Time, Some("millis"), Some(false)),
+            ]
+        );
+        assert_eq!(
+            audit.mixed,
+            [
+                "timestamps in different units: millis (created), micros (updated)",
+                "timestamps adjusted to UTC (created) next to local ones (updated)",
+            ]
+        );
+        let text = audit.to_string();
+        assert!(text.contains("\nColumn   Type       Physical  Unit    UTC  INT96\n"));
+        assert!(text.contains("\ncreated  timestamp  INT64     millis  yes  no\n"));
+    }
+}
@@ -0,0 +1,431 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use parquet::basic::{ConvertedType, LogicalType, PageType, Type as PhysicalType};
+use parquet::column::page::{Page, PageMetadata, PageReader};
+use parquet::column::reader::ColumnReaderImpl;
+use parquet::data_type::{ByteArray, ByteArrayType};
+use parquet::errors::Result as ParquetResult;
+use parquet::file::reader::FileReader;
+use serde::Serialize;
+
+use crate::file::error::FileIOError;
+use crate::file::parquet_ctx::open_reader;
+use crate::file::progress::ScanProgress;
+use crate::file::utils::commas;
+
+/// Levels decoded per batch.
+const READ_BATCH: usize = 8192;
+
+/// Bytes of an offending value kept for the report.
+const EXAMPLE_BYTES: usize = 64;
+
+/// Where the first invalid value of a column is, and what it looks like.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InvalidString {
+    /// Numbered from 0.
+    pub row_group: usize,
+    /// Index of the page within its column chunk, dictionary page included,
+    /// as the Pages view lists them.
+    pub page: usize,
+    /// Row in the file, numbered from 0.
+    pub row: u64,
+    /// The valid parts as text, the invalid bytes as `\xNN`.
+    pub example: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Utf8Column {
+    pub path: String,
+    /// Non-null values checked.
+    pub values: u64,
+    pub invalid: u64,
+    pub first_invalid: Option<InvalidString>,
+}
+
+/// Whether the STRING columns of a file hold valid UTF-8. Readers such as
+/// arrow reject a file with invalid strings, while others pass the bytes
+/// on, so a bad writer tends to surface far from its cause.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Utf8Report {
+    pub path: String,
+    pub columns: Vec<Utf8Column>,
+}
+
+impl Utf8Report {
+    pub fn from_file(path: &str) -> Result<Self, FileIOError> {
+        Self::from_file_with_progress(path, &ScanProgress::default()).map_err(|details| {
+            FileIOError::InvalidParquet {
+                path: path.into(),
+                details,
+            }
+        })
+    }
+
+    /// Like [`Utf8Report::from_file`], reporting the compressed bytes of the
+    /// row groups read to `progress` and stopping if it is cancelled.
+    pub fn from_file_with_progress(path: &str, progress: &ScanProgress) -> Result<Self, String> {
+        let (reader, _) = open_reader(path).map_err(|e| e.to_string())?;
+        let md = reader.metadata_ptr();
+        let schema = md.file_metadata().schema_descr();
+        let leaves: Vec<usize> = (0..schema.num_columns())
+            .filter(|&i| {
+                let column = schema.column(i);
+                column.physical_type() == PhysicalType::BYTE_ARRAY
+                    && (matches!(column.logical_type_ref(), Some(LogicalType::String))
+                        || column.converted_type() == ConvertedType::UTF8)
+            })
+            .collect();
+        let mut columns: Vec<Utf8Column> = leaves
+            .iter()
+            .map(|&i| Utf8Column {
+                path: schema.column(i).path().string(),
+                values: 0,
+                invalid: 0,
+                first_invalid: None,
+            })
+            .collect();
+
+        let mut first_row = 0;
+        for rg in 0..md.num_row_groups() {
+            progress.check()?;
+            let row_group = reader.get_row_group(rg).map_err(|e| e.to_string())?;
+            for (column, &leaf) in columns.iter_mut().zip(&leaves) {
+                let pages = CountingPages::new(
+                    row_group
+                        .get_column_page_reader(leaf)
+                        .map_err(|e| e.to_string())?,
+                );
+                let data_pages = Arc::clone(&pages.data_pages);
+                let descr = schema.column(leaf);
+                let (max_def, max_rep) = (descr.max_def_level(), descr.max_rep_level());
+                let mut values_reader =
+                    ColumnReaderImpl::<ByteArrayType>::new(descr, Box::new(pages));
+
+                // Levels of the chunk decoded before this batch, and rows
+                // started so far.
+                let (mut levels_before, mut records) = (0, 0);
+                loop {
+                    let (mut defs, mut reps, mut values) = (vec![], vec![], vec![]);
+                    let (_, _, levels) = values_reader
+                        .read_records(
+                            READ_BATCH,
+                            (max_def > 0).then_some(&mut defs),
+                            (max_rep > 0).then_some(&mut reps),
+                            &mut values,
+                        )
+                        .map_err(|e| e.to_string())?;
+                    let levels = if max_def > 0 || max_rep > 0 {
+                        levels
+                    } else {
+                        values.len()
+                    };
+                    if levels == 0 {
+                        break;
+                    }
+                    let mut values = values.iter();
+                    for level in 0..levels {
+                        if max_rep == 0 || reps[level] == 0 {
+                            records += 1;
+                        }
+                        if max_def > 0 && defs[level] < max_def {
+                            continue;
+                        }
+                        let Some(value) = values.next() else { break };
+                        column.values += 1;
+                        if std::str::from_utf8(value.data()).is_ok() {
+                            continue;
+                        }
+                        column.invalid += 1;
+                        column.first_invalid.get_or_insert_with(|| InvalidString {
+                            row_group: rg,
+                            page: page_of(&data_pages.lock().unwrap(), levels_before + level),
+                            row: first_row + records - 1,
+                            example: escape(value),
+                        });
+                    }
+                    levels_before += levels;
+                }
+            }
+            first_row += md.row_group(rg).num_rows().max(0) as u64;
+            progress.advance(md.row_group(rg).compressed_size().max(0) as u64);
+        }
+
+        Ok(Utf8Report {
+            path: path.to_string(),
+            columns,
+        })
+    }
+
+    pub fn invalid_values(&self) -> u64 {
+        self.columns.iter().map(|c| c.invalid).sum()
+    }
+
+    /// One line for the status bar.
+    pub fn summary(&self) -> String {
+        if self.columns.is_empty() {
+            return "no STRING columns".to_string();
+        }
+        let bad: Vec<&Utf8Column> = self.columns.iter().filter(|c| c.invalid > 0).collect();
+        match bad.as_slice() {
+            [] => format!(
+                "all {} values of {} STRING column{} are valid UTF-8",
+                commas(self.columns.iter().map(|c| c.values).sum()),
+                self.columns.len(),
+                if self.columns.len() == 1 { "" } else { "s" }
+            ),
+            [column, ..] => {
+                let mut summary = format!(
+                    "{} invalid UTF-8 values in {} of {} STRING columns",
+                    commas(self.invalid_values()),
+                    bad.len(),
+                    self.columns.len()
+                );
+                if let Some(first) = &column.first_invalid {
+                    summary.push_str(&format!(
+                        ", first in {} at row {}: {}",
+                        column.path,
+                        commas(first.row + 1),
+                        first.example
+                    ));
+                }
+                summary
+            }
+        }
+    }
+}
+
+/// Index of the page holding level `level`, from the `(page index, levels)`
+/// of each data page read so far.
+fn page_of(data_pages: &[(usize, usize)], level: usize) -> usize {
+    let mut end = 0;
+    for &(page, levels) in data_pages {
+        end += levels;
+        if level < end {
+            return page;
+        }
+    }
+    data_pages.last().map_or(0, |&(page, _)| page)
+}
+
+/// The value with its valid parts as text and its invalid bytes as `\xNN`,
+/// cut at [`EXAMPLE_BYTES`].
+fn escape(value: &ByteArray) -> String {
+    let bytes = value.data();
+    let mut text = String::new();
+    for chunk in bytes[..bytes.len().min(EXAMPLE_BYTES)].utf8_chunks() {
+        text.extend(chunk.valid().escape_debug());
+        for byte in chunk.invalid() {
+            text.push_str(&format!("\\x{byte:02X}"));
+        }
+    }
+    if bytes.len() > EXAMPLE_BYTES {
+        text.push('…');
+    }
+    text
+}
+
+/// Passes pages through, noting the index and level count of each data
+/// page so a decoded value can be traced back to its page.
+struct CountingPages {
+    inner: Box<dyn PageReader>,
+    next_page: usize,
+    data_pages: Arc<Mutex<Vec<(usize, usize)>>>,
+}
+
+impl CountingPages {
+    fn new(inner: Box<dyn PageReader>) -> Self {
+        Self {
+            inner,
+            next_page: 0,
+            data_pages: Arc::default(),
+        }
+    }
+
+    fn record(&mut self, page: &Option<Page>) {
+        if let Some(page) = page {
+            if matches!(
+                page.page_type(),
+                PageType::DATA_PAGE | PageType::DATA_PAGE_V2
+            ) {
+                self.data_pages
+                    .lock()
+                    .unwrap()
+                    .push((self.next_page, page.num_values() as usize));
+            }
+            self.next_page += 1;
+        }
+    }
+}
+
+impl Iterator for CountingPages {
+    type Item = ParquetResult<Page>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.get_next_page().transpose()
+    }
+}
+
+impl PageReader for CountingPages {
+    fn get_next_page(&mut self) -> ParquetResult<Option<Page>> {
+        let page = self.inner.get_next_page()?;
+        self.record(&page);
+        Ok(page)
+    }
+
+    fn peek_next_page(&mut self) -> ParquetResult<Option<PageMetadata>> {
+        self.inner.peek_next_page()
+    }
+
+    fn skip_next_page(&mut self) -> ParquetResult<()> {
+        self.next_page += 1;
+        self.inner.skip_next_page()
+    }
+
+    fn at_record_boundary(&mut self) -> ParquetResult<bool> {
+        self.inner.at_record_boundary()
+    }
+}
+
+impl fmt::Display for Utf8Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}: {}", self.path, self.summary())?;
+        let bad: Vec<&Utf8Column> = self.columns.iter().filter(|c| c.invalid > 0).collect();
+        if bad.is_empty() {
+            return Ok(());
+        }
+        let rows: Vec<[String; 5]> = bad
+            .iter()
+            .map(|column| {
+                let first = column.first_invalid.as_ref();
+                [
+                    column.path.clone(),
+                    format!("{} of {}", commas(column.invalid), commas(column.values)),
+                    first.map_or("-".into(), |f| format!("{}/{}", f.row_group, f.page)),
+                    first.map_or("-".into(), |f| commas(f.row + 1)),
+                    first.map_or("-".into(), |f| f.example.clone()),
+                ]
+            })
+            .collect();
+        let headers = ["Column", "Invalid", "Row group/page", "Row", "First value"];
+        let widths: Vec<usize> = (0..4)
+            .map(|idx| {
+                rows.iter()
+                    .map(|row| row[idx].chars().count())
+                    .chain([headers[idx].len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        writeln!(f)?;
+        let headers = headers.map(str::to_string);
+        for row in std::iter::once(&headers).chain(&rows) {
+            writeln!(
+                f,
+                "{:<path$}  {:<invalid$}  {:<page$}  {:<row$}  {}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                row[4],
+                path = widths[0],
+                invalid = widths[1],
+                page = widths[2],
+                row = widths[3]
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_data_path;
+    use parquet::data_type::ByteArrayType;
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::parser::parse_message_type;
+
+    #[test]
+    fn test_valid_strings() {
+        let report = Utf8Report::from_file(&test_data_path("alltypes_plain.parquet")).unwrap();
+        // Its string columns are plain BYTE_ARRAY without a STRING type.
+        assert!(report.columns.is_empty());
+        assert_eq!(report.summary(), "no STRING columns");
+    }
+
+    #[test]
+    fn test_invalid_strings() {
+        // Two row groups of 10 rows, pages of 4 rows. `name` is invalid in
+        // rows 15 and 18, `note` is every other row and always valid.
+        let path =
+            std::env::temp_dir().join(format!("parqeye-utf8-{}.parquet", std::process::id()));
+        let schema = parse_message_type(
+            "message m { required binary name (STRING); optional binary note (UTF8); }",
+        )
+        .unwrap();
+        let props = WriterProperties::builder()
+            .set_dictionary_enabled(false)
+            .set_write_batch_size(4)
+            .set_data_page_row_count_limit(4)
+            .build();
+        let file = std::fs::File::create(&path).unwrap();
+        let mut writer =
+            SerializedFileWriter::new(file, Arc::new(schema), Arc::new(props)).unwrap();
+        for rg in 0..2 {
+            let names: Vec<ByteArray> = (0..10)
+                .map(|i| match rg * 10 + i {
+                    15 => ByteArray::from(b"caf\xE9".to_vec()),
+                    18 => ByteArray::from(vec![0xFF]),
+                    row => ByteArray::from(format!("row {row}").as_str()),
+                })
+                .collect();
+            let notes: Vec<ByteArray> = (0..5).map(|_| ByteArray::from("ok")).collect();
+            let defs: Vec<i16> = (0..10).map(|i| (i % 2 == 0) as i16).collect();
+            let mut row_group = writer.next_row_group().unwrap();
+            let mut column = row_group.next_column().unwrap().unwrap();
+            column
+                .typed::<ByteArrayType>()
+                .write_batch(&names, None, None)
+                .unwrap();
+            column.close().unwrap();
+            let mut column = row_group.next_column().unwrap().unwrap();
+            column
+                .typed::<ByteArrayType>()
+                .write_batch(&notes, Some(&defs), None)
+                .unwrap();
+            column.close().unwrap();
+            row_group.close().unwrap();
+        }
+        writer.close().unwrap();
+        let report = Utf8Report::from_file(&path.display().to_string());
+        std::fs::remove_file(&path).unwrap();
+        let report = report.unwrap();
+
+        assert_eq!(report.invalid_values(), 2);
+        let [name, note] = report.columns.as_slice() else {
+            panic!("expected two columns");
+        };
+        assert_eq!((name.values, name.invalid), (20, 2));
+        assert_eq!(
+            name.first_invalid,
+            Some(InvalidString {
+                row_group: 1,
+                page: 1,
+                row: 15,
+                example: "caf\\xE9".to_string(),
+            })
+        );
+        assert_eq!((note.values, note.invalid), (10, 0));
+        assert_eq!(
+            report.summary(),
+            "2 invalid UTF-8 values in 1 of 2 STRING columns, first in name at row 16: caf\\xE9"
+        );
+        assert!(
+            report
+                .to_string()
+                .contains("\nname    2 of 20  1/1             16   caf\\xE9\n")
+        );
+    }
+}
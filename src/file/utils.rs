@@ -1,16 +1,124 @@
-/// Convert a byte count into a human-readable string (e.g. "2.3 MB").
+use chrono::{DateTime, Datelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Whether sizes are shown in powers of 1024 (KiB, MiB) or 1000 (KB, MB).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ByteUnits {
+    #[default]
+    Binary,
+    Decimal,
+}
+
+static DECIMAL_BYTES: AtomicBool = AtomicBool::new(false);
+
+/// Units used by [`human_readable_bytes`] from now on, for the whole process.
+pub fn set_byte_units(units: ByteUnits) {
+    DECIMAL_BYTES.store(units == ByteUnits::Decimal, Ordering::Relaxed);
+}
+
+static RELATIVE_TIMES: AtomicBool = AtomicBool::new(false);
+
+/// Whether timestamp and date min/max values are followed by how long ago
+/// they are, from now on, for the whole process.
+pub fn set_relative_times(relative: bool) {
+    RELATIVE_TIMES.store(relative, Ordering::Relaxed);
+}
+
+pub fn relative_times() -> bool {
+    RELATIVE_TIMES.load(Ordering::Relaxed)
+}
+
+/// How well a column compresses, from its uncompressed over compressed size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RatioGrade {
+    Poor,
+    Fair,
+    Good,
+}
+
+// Bits of the f64 thresholds: below the first a ratio is poor, above the
+// second good.
+static POOR_RATIO: AtomicU64 = AtomicU64::new(1.1f64.to_bits());
+static GOOD_RATIO: AtomicU64 = AtomicU64::new(3.0f64.to_bits());
+
+/// Ratios graded poor and good by [`ratio_grade`] from now on, for the
+/// whole process.
+pub fn set_ratio_thresholds(poor: f64, good: f64) {
+    POOR_RATIO.store(poor.to_bits(), Ordering::Relaxed);
+    GOOD_RATIO.store(good.to_bits(), Ordering::Relaxed);
+}
+
+pub fn ratio_grade(ratio: f64) -> RatioGrade {
+    if ratio < f64::from_bits(POOR_RATIO.load(Ordering::Relaxed)) {
+        RatioGrade::Poor
+    } else if ratio > f64::from_bits(GOOD_RATIO.load(Ordering::Relaxed)) {
+        RatioGrade::Good
+    } else {
+        RatioGrade::Fair
+    }
+}
+
+/// `then` as seen from `now`: `3 days ago`, `in 5 hours`, or just the year
+/// for more than a year ahead, e.g. `in 2027`.
+pub fn relative_time(then: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let seconds = (now - then).num_seconds();
+    let ago = seconds >= 0;
+    let seconds = seconds.unsigned_abs();
+    if !ago && seconds >= 365 * 86_400 {
+        return format!("in {}", then.year());
+    }
+    let (n, unit) = match seconds {
+        0..60 => return "just now".to_string(),
+        60..3_600 => (seconds / 60, "minute"),
+        3_600..86_400 => (seconds / 3_600, "hour"),
+        86_400..2_592_000 => (seconds / 86_400, "day"),
+        2_592_000..31_536_000 => (seconds / 2_592_000, "month"),
+        _ => (seconds / 31_536_000, "year"),
+    };
+    let plural = if n == 1 { "" } else { "s" };
+    if ago {
+        format!("{n} {unit}{plural} ago")
+    } else {
+        format!("in {n} {unit}{plural}")
+    }
+}
+
+/// Timestamps after `now` or at exactly the Unix epoch are more often a bug
+/// (a wrong unit, a zero default) than real data.
+pub fn is_suspicious_time(time: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    time > now || time == DateTime::UNIX_EPOCH
+}
+
+/// Convert a byte count into a human-readable string (e.g. "2.30 MiB"), in
+/// the units chosen with [`set_byte_units`].
 pub fn human_readable_bytes(bytes: u64) -> String {
-    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let units = if DECIMAL_BYTES.load(Ordering::Relaxed) {
+        ByteUnits::Decimal
+    } else {
+        ByteUnits::Binary
+    };
+    format_bytes(bytes, units)
+}
+
+pub fn format_bytes(bytes: u64, units: ByteUnits) -> String {
+    let (base, names) = match units {
+        ByteUnits::Binary => (1024.0, ["B", "KiB", "MiB", "GiB", "TiB"]),
+        ByteUnits::Decimal => (1000.0, ["B", "KB", "MB", "GB", "TB"]),
+    };
     let mut size = bytes as f64;
     let mut unit = 0;
-    while size >= 1024.0 && unit < UNITS.len() - 1 {
-        size /= 1024.0;
+    while size >= base && unit < names.len() - 1 {
+        size /= base;
         unit += 1;
     }
     if unit == 0 {
-        format!("{:.0} {}", size, UNITS[unit])
+        format!("{:.0} {}", size, names[unit])
     } else {
-        format!("{:.2} {}", size, UNITS[unit])
+        format!("{:.2} {}", size, names[unit])
     }
 }
 
@@ -30,13 +138,84 @@ pub fn human_readable_count(n: u64) -> String {
     }
 }
 
+/// Terminal columns `s` takes: two for CJK and most emoji, none for
+/// combining marks.
+pub fn display_width(s: &str) -> usize {
+    s.width()
+}
+
+/// Cut `s` to at most `width` columns, ending with `…` when anything was
+/// cut. Graphemes (a letter with its accents, an emoji with its modifiers)
+/// are kept whole, so the result may be a column short.
 pub fn truncate_str(s: &str, width: usize) -> String {
-    if s.chars().count() > width {
-        let truncated: String = s.chars().take(width - 1).collect();
-        format!("{truncated}…")
-    } else {
-        s.to_string()
+    if display_width(s) <= width {
+        return s.to_string();
+    }
+    let mut truncated = String::new();
+    let mut used = 0;
+    for grapheme in s.graphemes(true) {
+        let grapheme_width = grapheme.width();
+        if used + grapheme_width + 1 > width {
+            break;
+        }
+        truncated.push_str(grapheme);
+        used += grapheme_width;
     }
+    if width > 0 {
+        truncated.push('…');
+    }
+    truncated
+}
+
+/// Like [`truncate_str`] but cuts from the front, starting with `…`, so
+/// the end of `s` stays visible.
+pub fn truncate_start(s: &str, width: usize) -> String {
+    if display_width(s) <= width {
+        return s.to_string();
+    }
+    let mut kept = Vec::new();
+    let mut used = 0;
+    for grapheme in s.graphemes(true).rev() {
+        let grapheme_width = grapheme.width();
+        if used + grapheme_width + 1 > width {
+            break;
+        }
+        kept.push(grapheme);
+        used += grapheme_width;
+    }
+    if width == 0 {
+        return String::new();
+    }
+    kept.reverse();
+    format!("…{}", kept.concat())
+}
+
+/// Break `s` into lines at most `width` columns wide, and at its own line
+/// breaks. A grapheme wider than `width` still gets a line of its own.
+pub fn wrap_str(s: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for text in s.split('\n') {
+        let mut line = String::new();
+        let mut used = 0;
+        for grapheme in text.graphemes(true) {
+            let grapheme_width = grapheme.width();
+            if used + grapheme_width > width && !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+                used = 0;
+            }
+            line.push_str(grapheme);
+            used += grapheme_width;
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// `s` followed by spaces up to `width` columns, like `{s:<width$}` but
+/// counting columns rather than chars.
+pub fn pad_str(s: &str, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(s));
+    format!("{s}{}", " ".repeat(padding))
 }
 
 pub fn commas(n: u64) -> String {
@@ -51,6 +230,21 @@ pub fn commas(n: u64) -> String {
     out.chars().rev().collect()
 }
 
+/// Whether two paths name the same existing file, so a command writing
+/// `output` would clobber its input.
+pub fn same_file(path: &str, output: &str) -> bool {
+    let canonical = |p: &str| std::path::Path::new(p).canonicalize();
+    canonical(output).is_ok_and(|out| canonical(path).is_ok_and(|p| p == out))
+}
+
+/// Escape text for use in HTML element content or attribute values.
+pub fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
 /// Format byte size into human-readable format
 pub fn format_size(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
@@ -85,6 +279,51 @@ mod tests {
         assert_eq!(human_readable_bytes(2500 * 1024 * 1024), "2.44 GiB");
     }
 
+    #[test]
+    fn test_format_decimal_bytes() {
+        assert_eq!(format_bytes(999, ByteUnits::Decimal), "999 B");
+        assert_eq!(format_bytes(1000, ByteUnits::Decimal), "1.00 KB");
+        assert_eq!(format_bytes(1024, ByteUnits::Decimal), "1.02 KB");
+        assert_eq!(format_bytes(2_500_000_000, ByteUnits::Decimal), "2.50 GB");
+        assert_eq!(format_bytes(1536, ByteUnits::Binary), "1.50 KiB");
+    }
+
+    #[test]
+    fn test_relative_time() {
+        let now = DateTime::parse_from_rfc3339("2026-10-15T12:00:00Z")
+            .unwrap()
+            .to_utc();
+        let at = |text: &str| DateTime::parse_from_rfc3339(text).unwrap().to_utc();
+        assert_eq!(relative_time(now, now), "just now");
+        assert_eq!(
+            relative_time(at("2026-10-15T11:59:00Z"), now),
+            "1 minute ago"
+        );
+        assert_eq!(relative_time(at("2026-10-12T09:00:00Z"), now), "3 days ago");
+        assert_eq!(
+            relative_time(at("2026-07-01T00:00:00Z"), now),
+            "3 months ago"
+        );
+        assert_eq!(
+            relative_time(at("2020-01-01T00:00:00Z"), now),
+            "6 years ago"
+        );
+        assert_eq!(relative_time(at("2026-10-15T17:00:00Z"), now), "in 5 hours");
+        assert_eq!(relative_time(at("2027-12-01T00:00:00Z"), now), "in 2027");
+
+        assert!(is_suspicious_time(DateTime::UNIX_EPOCH, now));
+        assert!(is_suspicious_time(at("2027-12-01T00:00:00Z"), now));
+        assert!(!is_suspicious_time(at("2020-01-01T00:00:00Z"), now));
+    }
+
+    #[test]
+    fn test_ratio_grade_by_default_thresholds() {
+        assert_eq!(ratio_grade(1.0), RatioGrade::Poor);
+        assert_eq!(ratio_grade(1.1), RatioGrade::Fair);
+        assert_eq!(ratio_grade(3.0), RatioGrade::Fair);
+        assert_eq!(ratio_grade(5.2), RatioGrade::Good);
+    }
+
     #[test]
     fn test_human_readable_count() {
         assert_eq!(human_readable_count(0), "0");
@@ -108,6 +347,40 @@ mod tests {
         assert_eq!(truncate_str("a", 1), "a");
         assert_eq!(truncate_str("ab", 1), "…");
         assert_eq!(truncate_str("hello", 3), "he…");
+        // Wide characters take two columns and are never split.
+        assert_eq!(truncate_str("東京都庁", 8), "東京都庁");
+        assert_eq!(truncate_str("東京都庁", 6), "東京…");
+        assert_eq!(truncate_str("東京都庁", 5), "東京…");
+        assert_eq!(truncate_str("東京都庁", 4), "東…");
+        // A family emoji is one grapheme of several chars.
+        assert_eq!(truncate_str("👨‍👩‍👧 family", 3), "👨‍👩‍👧…");
+        assert_eq!(truncate_str("e\u{301}te\u{301}", 2), "e\u{301}…");
+    }
+
+    #[test]
+    fn test_truncate_start() {
+        assert_eq!(truncate_start("a.b.c", 5), "a.b.c");
+        assert_eq!(truncate_start("a.b.c", 3), "….c");
+        assert_eq!(truncate_start("組.名前", 5), "…名前");
+        assert_eq!(truncate_start("組.名前", 4), "…前");
+    }
+
+    #[test]
+    fn test_wrap_str() {
+        assert_eq!(wrap_str("", 4), vec![""]);
+        assert_eq!(wrap_str("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(wrap_str("東京都庁", 5), vec!["東京", "都庁"]);
+        assert_eq!(wrap_str("ab\ncd", 4), vec!["ab", "cd"]);
+        assert_eq!(wrap_str("表", 1), vec!["表"]);
+    }
+
+    #[test]
+    fn test_display_width_and_padding() {
+        assert_eq!(display_width("abc"), 3);
+        assert_eq!(display_width("表"), 2);
+        assert_eq!(display_width("e\u{301}"), 1);
+        assert_eq!(pad_str("表", 4), "表  ");
+        assert_eq!(pad_str("abcdef", 4), "abcdef");
     }
 
     #[test]
@@ -148,8 +421,10 @@ mod tests {
 
     #[test]
     fn test_unicode_truncation() {
-        // Test with unicode characters
-        assert_eq!(truncate_str("hello 🌍 world", 10), "hello 🌍 w…");
-        assert_eq!(truncate_str("日本語", 2), "日…");
+        // Test with unicode characters, which can take two columns
+        assert_eq!(truncate_str("hello 🌍 world", 10), "hello 🌍 …");
+        assert_eq!(truncate_str("hello 🌍 world", 11), "hello 🌍 w…");
+        assert_eq!(truncate_str("日本語", 3), "日…");
+        assert_eq!(truncate_str("日本語", 2), "…");
     }
 }
@@ -0,0 +1,526 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use arrow::array::{Array, AsArray, StructArray};
+use arrow::datatypes::DataType;
+use arrow::util::display::{ArrayFormatter, FormatOptions};
+use chrono::{DateTime, NaiveTime};
+use parquet::basic::LogicalType;
+use parquet::file::metadata::ParquetMetaData;
+use parquet::schema::types::Type as ParquetType;
+use serde::Serialize;
+
+use crate::file::logical::{base64, format_uuid};
+
+/// Variant values nested deeper than this are rejected rather than
+/// recursed into.
+const MAX_DEPTH: usize = 64;
+
+/// How the values at one shredded position of a VARIANT column were
+/// stored, counted from the footer's null counts: in the typed column, or
+/// left variant-encoded in `value` (e.g. because they have another type).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Shredding {
+    pub typed: u64,
+    pub fallback: u64,
+}
+
+impl Shredding {
+    /// Share of the present values that were shredded, `None` when there
+    /// are none.
+    pub fn fraction(&self) -> Option<f64> {
+        let total = self.typed + self.fallback;
+        (total > 0).then(|| self.typed as f64 / total as f64)
+    }
+}
+
+impl fmt::Display for Shredding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.fraction() {
+            Some(fraction) => write!(f, "{:.0}% shredded", fraction * 100.0),
+            None => write!(f, "no values"),
+        }
+    }
+}
+
+/// Shredding of every group of a VARIANT column that has both a `value`
+/// and a leaf `typed_value`, by the group's dotted path: the column itself
+/// when it is shredded as one type, otherwise the fields of its shredded
+/// objects and the elements of its shredded arrays. Positions whose chunks
+/// lack null counts are left out.
+pub fn shredding(md: &ParquetMetaData) -> HashMap<String, Shredding> {
+    let mut found = HashMap::new();
+    let mut next_leaf = 0;
+    let mut path = Vec::new();
+    for field in md.file_metadata().schema_descr().root_schema().get_fields() {
+        path.push(field.name().to_string());
+        walk(field, md, false, &mut path, &mut next_leaf, &mut found);
+        path.pop();
+    }
+    found
+}
+
+fn walk(
+    node: &ParquetType,
+    md: &ParquetMetaData,
+    in_variant: bool,
+    path: &mut Vec<String>,
+    next_leaf: &mut usize,
+    found: &mut HashMap<String, Shredding>,
+) {
+    if node.is_primitive() {
+        *next_leaf += 1;
+        return;
+    }
+    let in_variant = in_variant
+        || matches!(
+            node.get_basic_info().logical_type_ref(),
+            Some(LogicalType::Variant(_))
+        );
+    let (mut typed, mut value) = (None, None);
+    for child in node.get_fields() {
+        if child.is_primitive() {
+            match child.name() {
+                "typed_value" => typed = Some(*next_leaf),
+                "value" => value = Some(*next_leaf),
+                _ => {}
+            }
+        }
+        path.push(child.name().to_string());
+        walk(child, md, in_variant, path, next_leaf, found);
+        path.pop();
+    }
+    if let (true, Some(typed), Some(value)) = (in_variant, typed, value)
+        && let (Some(typed), Some(fallback)) = (present(md, typed), present(md, value))
+    {
+        found.insert(path.join("."), Shredding { typed, fallback });
+    }
+}
+
+/// Non-null values of a leaf column over all row groups.
+fn present(md: &ParquetMetaData, col_idx: usize) -> Option<u64> {
+    md.row_groups()
+        .iter()
+        .map(|rg| {
+            let column = rg.column(col_idx);
+            let nulls = column.statistics()?.null_count_opt()?;
+            Some((column.num_values().max(0) as u64).saturating_sub(nulls))
+        })
+        .sum()
+}
+
+/// A decoded variant value. Numbers keep their text so that decimals print
+/// exactly; dates, timestamps, UUIDs and binary become strings.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Variant {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Variant>),
+    Object(Vec<(String, Variant)>),
+}
+
+/// One-line JSON, spaced like [`crate::file::logical::json_one_line`].
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Variant::Null => write!(f, "null"),
+            Variant::Bool(b) => write!(f, "{b}"),
+            Variant::Number(n) => write!(f, "{n}"),
+            Variant::String(s) => write!(f, "{}", serde_json::Value::from(s.as_str())),
+            Variant::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+            Variant::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (name, item)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {item}", serde_json::Value::from(name.as_str()))?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+/// The field names of a variant's metadata.
+pub struct VariantMetadata<'a> {
+    names: Vec<&'a [u8]>,
+}
+
+impl<'a> VariantMetadata<'a> {
+    pub fn new(bytes: &'a [u8]) -> Result<Self, String> {
+        let header = *bytes.first().ok_or("empty variant metadata")?;
+        if header & 0x0f != 1 {
+            return Err(format!("unsupported variant version {}", header & 0x0f));
+        }
+        let offset_size = usize::from(header >> 6) + 1;
+        let count = read_uint(bytes, 1, offset_size)?;
+        let offsets_start = 1 + offset_size;
+        let strings = offsets_start + (count + 1) * offset_size;
+        let offset = |i: usize| read_uint(bytes, offsets_start + i * offset_size, offset_size);
+        let names = (0..count)
+            .map(|i| {
+                let (start, end) = (strings + offset(i)?, strings + offset(i + 1)?);
+                bytes
+                    .get(start..end)
+                    .ok_or_else(|| "variant metadata name out of bounds".to_string())
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { names })
+    }
+
+    fn name(&self, id: usize) -> Result<String, String> {
+        self.names
+            .get(id)
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .ok_or_else(|| format!("variant field id {id} not in metadata"))
+    }
+}
+
+/// Decode a variant-encoded value.
+pub fn decode(metadata: &VariantMetadata, bytes: &[u8]) -> Result<Variant, String> {
+    decode_at(metadata, bytes, 0)
+}
+
+fn decode_at(metadata: &VariantMetadata, bytes: &[u8], depth: usize) -> Result<Variant, String> {
+    if depth > MAX_DEPTH {
+        return Err("variant nested too deeply".to_string());
+    }
+    let header = *bytes.first().ok_or("empty variant value")?;
+    let value_header = header >> 2;
+    match header & 0x03 {
+        0 => primitive(value_header, &bytes[1..]),
+        1 => Ok(Variant::String(
+            String::from_utf8_lossy(take(bytes, 1, usize::from(value_header))?).into_owned(),
+        )),
+        2 => {
+            let offset_size = usize::from(value_header & 0x03) + 1;
+            let id_size = usize::from((value_header >> 2) & 0x03) + 1;
+            let count_size = if value_header & 0x10 != 0 { 4 } else { 1 };
+            let count = read_uint(bytes, 1, count_size)?;
+            let ids = 1 + count_size;
+            let offsets = ids + count * id_size;
+            let data = offsets + (count + 1) * offset_size;
+            let fields = (0..count)
+                .map(|i| {
+                    let name = metadata.name(read_uint(bytes, ids + i * id_size, id_size)?)?;
+                    let start = data + read_uint(bytes, offsets + i * offset_size, offset_size)?;
+                    let value = bytes.get(start..).ok_or("variant field out of bounds")?;
+                    Ok((name, decode_at(metadata, value, depth + 1)?))
+                })
+                .collect::<Result<_, String>>()?;
+            Ok(Variant::Object(fields))
+        }
+        _ => {
+            let offset_size = usize::from(value_header & 0x03) + 1;
+            let count_size = if value_header & 0x04 != 0 { 4 } else { 1 };
+            let count = read_uint(bytes, 1, count_size)?;
+            let offsets = 1 + count_size;
+            let data = offsets + (count + 1) * offset_size;
+            let items = (0..count)
+                .map(|i| {
+                    let start = data + read_uint(bytes, offsets + i * offset_size, offset_size)?;
+                    let value = bytes.get(start..).ok_or("variant element out of bounds")?;
+                    decode_at(metadata, value, depth + 1)
+                })
+                .collect::<Result<_, String>>()?;
+            Ok(Variant::Array(items))
+        }
+    }
+}
+
+fn primitive(type_id: u8, bytes: &[u8]) -> Result<Variant, String> {
+    let fixed = |len: usize| take(bytes, 0, len);
+    let number = |n: String| Ok(Variant::Number(n));
+    match type_id {
+        0 => Ok(Variant::Null),
+        1 => Ok(Variant::Bool(true)),
+        2 => Ok(Variant::Bool(false)),
+        3 => number((fixed(1)?[0] as i8).to_string()),
+        4 => number(i16::from_le_bytes(fixed(2)?.try_into().unwrap()).to_string()),
+        5 => number(i32::from_le_bytes(fixed(4)?.try_into().unwrap()).to_string()),
+        6 => number(i64::from_le_bytes(fixed(8)?.try_into().unwrap()).to_string()),
+        7 => number(f64::from_le_bytes(fixed(8)?.try_into().unwrap()).to_string()),
+        8..=10 => {
+            let width = [4, 8, 16][usize::from(type_id - 8)];
+            let scale = fixed(1)?[0];
+            let raw = take(bytes, 1, width)?;
+            let fill = if raw[width - 1] & 0x80 != 0 { 0xff } else { 0 };
+            let mut buf = [fill; 16];
+            buf[..width].copy_from_slice(raw);
+            number(decimal_text(i128::from_le_bytes(buf), scale))
+        }
+        11 => {
+            let days = i32::from_le_bytes(fixed(4)?.try_into().unwrap());
+            let date = DateTime::from_timestamp(i64::from(days) * 86_400, 0)
+                .ok_or("variant date out of range")?;
+            Ok(Variant::String(date.format("%Y-%m-%d").to_string()))
+        }
+        12 | 13 | 18 | 19 => {
+            let ticks = i64::from_le_bytes(fixed(8)?.try_into().unwrap());
+            let (time, digits) = if type_id < 18 {
+                (DateTime::from_timestamp_micros(ticks), "%.6f")
+            } else {
+                (Some(DateTime::from_timestamp_nanos(ticks)), "%.9f")
+            };
+            let time = time.ok_or("variant timestamp out of range")?;
+            let zone = if matches!(type_id, 12 | 18) {
+                "+00:00"
+            } else {
+                ""
+            };
+            Ok(Variant::String(format!(
+                "{}{zone}",
+                time.format(&format!("%Y-%m-%d %H:%M:%S{digits}"))
+            )))
+        }
+        14 => number(f32::from_le_bytes(fixed(4)?.try_into().unwrap()).to_string()),
+        15 | 16 => {
+            let len = read_uint(bytes, 0, 4)?;
+            let data = take(bytes, 4, len)?;
+            Ok(Variant::String(if type_id == 15 {
+                base64(data)
+            } else {
+                String::from_utf8_lossy(data).into_owned()
+            }))
+        }
+        17 => {
+            let micros = i64::from_le_bytes(fixed(8)?.try_into().unwrap());
+            let time = u32::try_from(micros.div_euclid(1_000_000))
+                .ok()
+                .zip(u32::try_from(micros.rem_euclid(1_000_000) * 1_000).ok())
+                .and_then(|(secs, nanos)| {
+                    NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos)
+                })
+                .ok_or("variant time out of range")?;
+            Ok(Variant::String(time.format("%H:%M:%S%.6f").to_string()))
+        }
+        20 => Ok(Variant::String(format_uuid(fixed(16)?).unwrap())),
+        other => Err(format!("unsupported variant type {other}")),
+    }
+}
+
+/// `unscaled` with the decimal point `scale` digits from the right.
+fn decimal_text(unscaled: i128, scale: u8) -> String {
+    let digits = unscaled.unsigned_abs().to_string();
+    let scale = usize::from(scale);
+    let sign = if unscaled < 0 { "-" } else { "" };
+    if scale == 0 {
+        return format!("{sign}{digits}");
+    }
+    let digits = format!("{digits:0>width$}", width = scale + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - scale);
+    format!("{sign}{whole}.{fraction}")
+}
+
+fn take(bytes: &[u8], start: usize, len: usize) -> Result<&[u8], String> {
+    start
+        .checked_add(len)
+        .and_then(|end| bytes.get(start..end))
+        .ok_or_else(|| "truncated variant".to_string())
+}
+
+fn read_uint(bytes: &[u8], start: usize, size: usize) -> Result<usize, String> {
+    let mut buf = [0; 8];
+    buf[..size].copy_from_slice(take(bytes, start, size)?);
+    Ok(u64::from_le_bytes(buf) as usize)
+}
+
+/// Reassemble the variant in row `row` of a VARIANT column read by arrow-rs
+/// as a struct of `metadata`, `value` and, when shredded, `typed_value`.
+/// `None` when the variant is null.
+pub fn reassemble(column: &StructArray, row: usize) -> Result<Option<Variant>, String> {
+    if column.is_null(row) {
+        return Ok(None);
+    }
+    let metadata = column
+        .column_by_name("metadata")
+        .and_then(|metadata| binary(metadata.as_ref(), row))
+        .ok_or("variant without metadata")?;
+    let metadata = VariantMetadata::new(metadata)?;
+    Ok(Some(
+        shredded(&metadata, column, row, 0)?.unwrap_or(Variant::Null),
+    ))
+}
+
+/// The value at a shredded position: `typed_value` when set, merged with
+/// the unshredded fields of `value` for objects; `None` when both are null,
+/// as for a missing object field.
+fn shredded(
+    metadata: &VariantMetadata,
+    position: &StructArray,
+    row: usize,
+    depth: usize,
+) -> Result<Option<Variant>, String> {
+    if depth > MAX_DEPTH {
+        return Err("variant nested too deeply".to_string());
+    }
+    let value = match position
+        .column_by_name("value")
+        .and_then(|value| binary(value.as_ref(), row))
+    {
+        Some(bytes) => Some(decode(metadata, bytes)?),
+        None => None,
+    };
+    let Some(typed) = position
+        .column_by_name("typed_value")
+        .filter(|typed| typed.is_valid(row))
+    else {
+        return Ok(value);
+    };
+
+    if let Some(fields) = typed.as_struct_opt() {
+        let mut object = Vec::new();
+        for (name, field) in fields.column_names().into_iter().zip(fields.columns()) {
+            let field = field
+                .as_struct_opt()
+                .ok_or("shredded field is not a group")?;
+            if let Some(item) = shredded(metadata, field, row, depth + 1)? {
+                object.push((name.to_string(), item));
+            }
+        }
+        // A partially shredded object keeps its other fields in `value`.
+        if let Some(Variant::Object(rest)) = value {
+            object.extend(rest);
+        }
+        object.sort_by(|a, b| a.0.cmp(&b.0));
+        return Ok(Some(Variant::Object(object)));
+    }
+    if let Some(list) = typed.as_list_opt::<i32>() {
+        let elements = list.value(row);
+        let elements = elements
+            .as_struct_opt()
+            .ok_or("shredded element is not a group")?;
+        let items = (0..elements.len())
+            .map(|i| {
+                shredded(metadata, elements, i, depth + 1).map(|item| item.unwrap_or(Variant::Null))
+            })
+            .collect::<Result<_, _>>()?;
+        return Ok(Some(Variant::Array(items)));
+    }
+    typed_scalar(typed.as_ref(), row).map(Some)
+}
+
+fn typed_scalar(column: &dyn Array, row: usize) -> Result<Variant, String> {
+    if let Some(values) = column.as_boolean_opt() {
+        return Ok(Variant::Bool(values.value(row)));
+    }
+    if let Some(bytes) = binary(column, row) {
+        return Ok(Variant::String(match column.data_type() {
+            DataType::FixedSizeBinary(16) => format_uuid(bytes).unwrap(),
+            _ => base64(bytes),
+        }));
+    }
+    let text = ArrayFormatter::try_new(column, &FormatOptions::default())
+        .map_err(|error| error.to_string())?
+        .value(row)
+        .to_string();
+    Ok(match column.data_type() {
+        data_type if data_type.is_numeric() => Variant::Number(text),
+        _ => Variant::String(text),
+    })
+}
+
+/// The bytes of a non-null binary value.
+fn binary(column: &dyn Array, row: usize) -> Option<&[u8]> {
+    if column.is_null(row) {
+        return None;
+    }
+    if let Some(values) = column.as_binary_opt::<i32>() {
+        Some(values.value(row))
+    } else if let Some(values) = column.as_binary_opt::<i64>() {
+        Some(values.value(row))
+    } else if let Some(values) = column.as_binary_view_opt() {
+        Some(values.value(row))
+    } else {
+        column
+            .as_fixed_size_binary_opt()
+            .map(|values| values.value(row))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Metadata with the field names `a`, `b` and `c`.
+    const METADATA: [u8; 9] = [0x11, 3, 0, 1, 2, 3, b'a', b'b', b'c'];
+
+    fn decoded(bytes: &[u8]) -> String {
+        decode(&VariantMetadata::new(&METADATA).unwrap(), bytes)
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_decode_primitives() {
+        assert_eq!(decoded(&[0x00]), "null");
+        assert_eq!(decoded(&[0x04]), "true");
+        assert_eq!(decoded(&[0x0C, 0xfe]), "-2");
+        assert_eq!(decoded(&[0x14, 0xe8, 0x03, 0, 0]), "1000");
+        assert_eq!(decoded(&[0x1C, 0, 0, 0, 0, 0, 0, 0xf8, 0x3f]), "1.5");
+        // decimal4 with scale 2
+        assert_eq!(decoded(&[0x20, 2, 0x39, 0x30, 0, 0]), "123.45");
+        assert_eq!(decoded(&[0x20, 3, 0xfb, 0xff, 0xff, 0xff]), "-0.005");
+        // date, 2024-01-01
+        assert_eq!(decoded(&[0x2C, 0x0b, 0x4d, 0, 0]), "\"2024-01-01\"");
+        assert_eq!(decoded(&[0x0D, b'h', b'i', b'!']), "\"hi!\"");
+        assert_eq!(decoded(&[0x40, 3, 0, 0, 0, b'a', b'"', b'c']), r#""a\"c""#);
+    }
+
+    #[test]
+    fn test_decode_objects_and_arrays() {
+        // {"a": 1, "c": [true, "x"]}
+        let bytes = [
+            0x02, 2, 0, 2, 0, 2, 10, // header, count, ids, offsets
+            0x0C, 1, // a: int8 1
+            0x03, 2, 0, 1, 3, 0x04, 0x05, b'x', // c: [true, "x"]
+        ];
+        assert_eq!(decoded(&bytes), r#"{"a": 1, "c": [true, "x"]}"#);
+    }
+
+    #[test]
+    fn test_decode_rejects_malformed_values() {
+        let metadata = VariantMetadata::new(&METADATA).unwrap();
+        assert!(decode(&metadata, &[]).is_err());
+        assert!(decode(&metadata, &[0x18, 1, 2]).is_err());
+        // field id 7 is not in the metadata
+        assert!(decode(&metadata, &[0x02, 1, 7, 0, 1, 0x00]).is_err());
+        assert!(VariantMetadata::new(&[0x02, 0]).is_err());
+        // arrays nested past the limit
+        let mut deep = vec![0x00];
+        for _ in 0..=MAX_DEPTH {
+            let end = (deep.len() as u16).to_le_bytes().to_vec();
+            deep = [vec![0x07, 1, 0, 0], end, deep].concat();
+        }
+        assert!(decode(&metadata, &deep).is_err());
+    }
+
+    #[test]
+    fn test_shredding_fraction() {
+        let shredding = Shredding {
+            typed: 3,
+            fallback: 1,
+        };
+        assert_eq!(shredding.to_string(), "75% shredded");
+        assert_eq!(
+            Shredding {
+                typed: 0,
+                fallback: 0
+            }
+            .fraction(),
+            None
+        );
+    }
+}
@@ -0,0 +1,422 @@
+use serde_json::{Map, Value, json};
+
+use crate::file::page_values::{PREVIEW_PAGES, chunk_previews};
+use crate::file::parquet_ctx::ParquetCtx;
+use crate::file::schema::SchemaInfo;
+
+/// One of the app's views as plain rows of values, for printing from the
+/// command line as text, CSV, TSV or JSON. Sizes are in bytes and row
+/// groups are numbered from 0 so the output is easy to compute with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct View {
+    /// Column names, snake_case so they double as JSON keys.
+    pub columns: Vec<&'static str>,
+    /// Cells are numbers, strings or null.
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl View {
+    /// Every node of the schema below the root, in depth-first order.
+    pub fn schema(ctx: &ParquetCtx) -> Self {
+        let rows = ctx
+            .schema
+            .columns
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, column)| {
+                let path = ctx.schema.column_path(idx);
+                match column {
+                    SchemaInfo::Root { .. } => None,
+                    SchemaInfo::Group {
+                        depth,
+                        repetition,
+                        field_id,
+                        logical,
+                        ..
+                    } => Some(vec![
+                        json!(path),
+                        json!("group"),
+                        json!(depth),
+                        json!(repetition),
+                        Value::Null,
+                        non_empty(logical),
+                        Value::Null,
+                        json!(field_id),
+                    ]),
+                    SchemaInfo::Primitive { depth, info, .. } => Some(vec![
+                        json!(path),
+                        json!("leaf"),
+                        json!(depth),
+                        json!(info.repetition),
+                        json!(info.physical),
+                        non_empty(&info.logical),
+                        non_empty(&info.converted_type),
+                        json!(info.field_id),
+                    ]),
+                }
+            })
+            .collect();
+        Self {
+            columns: vec![
+                "path",
+                "kind",
+                "depth",
+                "repetition",
+                "physical",
+                "logical",
+                "converted_type",
+                "field_id",
+            ],
+            rows,
+        }
+    }
+
+    pub fn row_groups(ctx: &ParquetCtx) -> Self {
+        let rows = ctx
+            .row_groups
+            .row_groups
+            .iter()
+            .map(|rg| {
+                let (offset, length) = rg.byte_range();
+                vec![
+                    json!(rg.idx),
+                    json!(rg.rows),
+                    json!(rg.compressed_size),
+                    json!(rg.uncompressed_size),
+                    ratio(rg.compression_ratio),
+                    json!(offset),
+                    json!(length),
+                ]
+            })
+            .collect();
+        Self {
+            columns: vec![
+                "row_group",
+                "rows",
+                "compressed_bytes",
+                "uncompressed_bytes",
+                "compression_ratio",
+                "offset",
+                "length",
+            ],
+            rows,
+        }
+    }
+
+    /// Every page of every column chunk, dictionary pages included, with the
+    /// first values of up to [`PREVIEW_PAGES`] pages per chunk.
+    pub fn pages(ctx: &ParquetCtx) -> Self {
+        let paths = ctx.schema.leaf_paths();
+        let mut rows = vec![];
+        for rg in &ctx.row_groups.row_groups {
+            for (col, chunk) in rg.column_metadata.iter().enumerate() {
+                let previews =
+                    chunk_previews(&ctx.reader, rg.idx, col, PREVIEW_PAGES).unwrap_or_default();
+                for (page, info) in chunk.pages.page_infos.iter().enumerate() {
+                    rows.push(vec![
+                        json!(rg.idx),
+                        json!(paths.get(col)),
+                        json!(page),
+                        json!(info.page_type),
+                        json!(info.encoding),
+                        json!(info.size),
+                        json!(info.rows),
+                        json!(previews.get(page).and_then(|p| p.as_ref()?.as_ref().ok())),
+                    ]);
+                }
+            }
+        }
+        Self {
+            columns: vec![
+                "row_group",
+                "column",
+                "page",
+                "page_type",
+                "encoding",
+                "bytes",
+                "rows",
+                "first_values",
+            ],
+            rows,
+        }
+    }
+
+    /// Statistics of each leaf column over all row groups.
+    pub fn stats(ctx: &ParquetCtx) -> Self {
+        let rows = ctx
+            .schema
+            .columns
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, column)| match column {
+                SchemaInfo::Primitive { info, stats, .. } => Some(vec![
+                    json!(ctx.schema.column_path(idx)),
+                    json!(info.physical),
+                    non_empty(&info.logical),
+                    json!(info.codec),
+                    json!(info.encoding),
+                    json!(stats.total_compressed_size),
+                    json!(stats.total_uncompressed_size),
+                    json!(stats.nulls),
+                    json!(stats.distinct),
+                    json!(stats.min),
+                    json!(stats.max),
+                ]),
+                _ => None,
+            })
+            .collect();
+        Self {
+            columns: vec![
+                "column",
+                "physical",
+                "logical",
+                "codec",
+                "encodings",
+                "compressed_bytes",
+                "uncompressed_bytes",
+                "nulls",
+                "distinct",
+                "min",
+                "max",
+            ],
+            rows,
+        }
+    }
+
+    /// The file-level facts of the Metadata tab as key-value rows, the
+    /// key-value metadata after them under a `kv.` prefix.
+    pub fn metadata(ctx: &ParquetCtx) -> Self {
+        let md = &ctx.metadata;
+        let mut rows = vec![
+            vec![json!("format_version"), json!(md.format_version)],
+            vec![json!("created_by"), json!(md.created_by)],
+            vec![json!("rows"), json!(md.num_rows)],
+            vec![json!("columns"), json!(md.num_columns)],
+            vec![json!("row_groups"), json!(md.num_row_groups)],
+            vec![json!("file_bytes"), json!(ctx.file_size)],
+            vec![json!("compressed_bytes"), json!(md.compressed_size)],
+            vec![json!("uncompressed_bytes"), json!(md.raw_size)],
+            vec![json!("compression_ratio"), ratio(md.compression_ratio)],
+            vec![json!("codecs"), non_empty(&md.codecs)],
+            vec![json!("encodings"), non_empty(&md.encodings)],
+            vec![json!("avg_row_bytes"), json!(md.avg_row_size)],
+        ];
+        if let Some(footer) = &md.footer {
+            rows.push(vec![json!("footer_bytes"), json!(footer.footer_size)]);
+        }
+        rows.extend(
+            md.key_value_metadata
+                .iter()
+                .map(|(key, value)| vec![json!(format!("kv.{key}")), json!(value)]),
+        );
+        Self {
+            columns: vec!["key", "value"],
+            rows,
+        }
+    }
+
+    /// Aligned columns with a header line, nulls shown as `-`.
+    pub fn to_plain(&self) -> String {
+        let rows: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell {
+                        Value::Null => "-".to_string(),
+                        cell => cell_text(cell),
+                    })
+                    .collect()
+            })
+            .collect();
+        let widths: Vec<usize> = (0..self.columns.len())
+            .map(|idx| {
+                rows.iter()
+                    .map(|row| row[idx].chars().count())
+                    .chain([self.columns[idx].len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let header: Vec<String> = self.columns.iter().map(|c| c.to_string()).collect();
+        let mut out = String::new();
+        for row in std::iter::once(&header).chain(&rows) {
+            let line: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect();
+            out.push_str(line.join("  ").trim_end());
+            out.push('\n');
+        }
+        out
+    }
+
+    /// RFC 4180 CSV with a header line; nulls are empty fields.
+    pub fn to_csv(&self) -> String {
+        self.delimited(',', |text| {
+            if text.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", text.replace('"', "\"\""))
+            } else {
+                text
+            }
+        })
+    }
+
+    /// Tab-separated values with a header line. Tabs and line breaks inside
+    /// values become spaces.
+    pub fn to_tsv(&self) -> String {
+        self.delimited('\t', |text| text.replace(['\t', '\n', '\r'], " "))
+    }
+
+    fn delimited(&self, separator: char, escape: impl Fn(String) -> String) -> String {
+        let separator = separator.to_string();
+        let mut out = self.columns.join(&separator);
+        out.push('\n');
+        for row in &self.rows {
+            let cells: Vec<String> = row.iter().map(|cell| escape(cell_text(cell))).collect();
+            out.push_str(&cells.join(&separator));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// An array with one object per row, keyed by column name.
+    pub fn to_json(&self) -> Value {
+        Value::Array(
+            self.rows
+                .iter()
+                .map(|row| {
+                    let object: Map<String, Value> = self
+                        .columns
+                        .iter()
+                        .map(|c| c.to_string())
+                        .zip(row.iter().cloned())
+                        .collect();
+                    Value::Object(object)
+                })
+                .collect(),
+        )
+    }
+}
+
+fn cell_text(cell: &Value) -> String {
+    match cell {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Null for the empty and `NONE` placeholders the schema uses.
+fn non_empty(text: &str) -> Value {
+    if text.is_empty() || text == "NONE" {
+        Value::Null
+    } else {
+        json!(text)
+    }
+}
+
+/// Ratios rounded to 2 decimals; NaN (an empty row group) becomes null.
+fn ratio(value: f64) -> Value {
+    if value.is_finite() {
+        json!((value * 100.0).round() / 100.0)
+    } else {
+        Value::Null
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_data_path;
+
+    fn nulls_ctx() -> ParquetCtx {
+        ParquetCtx::from_file(&test_data_path("nulls.snappy.parquet")).unwrap()
+    }
+
+    #[test]
+    fn test_schema_view() {
+        let view = View::schema(&nulls_ctx());
+        let paths: Vec<&Value> = view.rows.iter().map(|row| &row[0]).collect();
+        assert_eq!(paths[0], "b1");
+        assert!(paths.contains(&&json!("s.a")));
+        assert_eq!(view.rows[0][6], Value::Null);
+        let group = view.rows.iter().find(|row| row[0] == "s").unwrap();
+        assert_eq!(group[1], "group");
+        assert_eq!(group[4], Value::Null);
+    }
+
+    #[test]
+    fn test_metadata_view() {
+        let ctx = nulls_ctx();
+        let view = View::metadata(&ctx);
+        let value = |key: &str| {
+            view.rows
+                .iter()
+                .find(|row| row[0] == key)
+                .map(|row| row[1].clone())
+                .unwrap()
+        };
+        assert_eq!(value("rows"), json!(ctx.metadata.num_rows));
+        assert_eq!(value("row_groups"), json!(ctx.row_groups.num_row_groups()));
+        assert_eq!(value("file_bytes"), json!(ctx.file_size));
+        assert!(view.rows.iter().all(|row| row.len() == view.columns.len()));
+    }
+
+    #[test]
+    fn test_row_groups_and_pages() {
+        let ctx = nulls_ctx();
+        let view = View::row_groups(&ctx);
+        assert_eq!(view.rows.len(), ctx.row_groups.num_row_groups());
+        assert_eq!(view.rows[0][0], 0);
+        assert_eq!(view.rows[0][1], 250);
+
+        let pages = View::pages(&ctx);
+        let expected: usize = ctx
+            .row_groups
+            .row_groups
+            .iter()
+            .flat_map(|rg| &rg.column_metadata)
+            .map(|c| c.pages.page_infos.len())
+            .sum();
+        assert_eq!(pages.rows.len(), expected);
+        assert_eq!(pages.rows[0][1], "b1");
+    }
+
+    #[test]
+    fn test_stats_view() {
+        let ctx = ParquetCtx::from_file(&test_data_path("alltypes_plain.parquet")).unwrap();
+        let view = View::stats(&ctx);
+        assert_eq!(view.rows.len(), 11);
+        assert_eq!(view.rows[0][0], "id");
+        assert_eq!(view.rows[0][1], "INT32");
+    }
+
+    #[test]
+    fn test_formats() {
+        let view = View {
+            columns: vec!["name", "size", "note"],
+            rows: vec![
+                vec![json!("a,b"), json!(10), Value::Null],
+                vec![json!("say \"hi\""), json!(2.5), json!("x\ty")],
+            ],
+        };
+        assert_eq!(
+            view.to_csv(),
+            "name,size,note\n\"a,b\",10,\n\"say \"\"hi\"\"\",2.5,x\ty\n"
+        );
+        assert_eq!(
+            view.to_tsv(),
+            "name\tsize\tnote\na,b\t10\t\nsay \"hi\"\t2.5\tx y\n"
+        );
+        assert_eq!(
+            view.to_plain(),
+            "name      size  note\na,b       10    -\nsay \"hi\"  2.5   x\ty\n"
+        );
+        assert_eq!(
+            view.to_json()[0],
+            json!({"name": "a,b", "size": 10, "note": null})
+        );
+    }
+}
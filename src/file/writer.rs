@@ -0,0 +1,288 @@
+use std::fmt;
+
+use parquet::basic::Type as PhysicalType;
+use parquet::file::metadata::ParquetMetaData;
+use serde::Serialize;
+
+/// Libraries and engines recognised from the `created_by` string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WriterKind {
+    ParquetMr,
+    ArrowCpp,
+    ArrowRs,
+    ArrowGo,
+    Fastparquet,
+    DuckDb,
+    Polars,
+    Impala,
+    ParquetNet,
+    Other,
+}
+
+impl WriterKind {
+    fn from_application(application: &str) -> Self {
+        match application.to_ascii_lowercase().as_str() {
+            "parquet-mr" | "parquet-java" => WriterKind::ParquetMr,
+            "parquet-cpp" | "parquet-cpp-arrow" => WriterKind::ArrowCpp,
+            "parquet-rs" => WriterKind::ArrowRs,
+            "parquet-go" => WriterKind::ArrowGo,
+            "fastparquet-python" | "fastparquet" => WriterKind::Fastparquet,
+            "duckdb" => WriterKind::DuckDb,
+            "polars" => WriterKind::Polars,
+            "impala" => WriterKind::Impala,
+            "parquet.net" => WriterKind::ParquetNet,
+            _ => WriterKind::Other,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WriterKind::ParquetMr => "parquet-mr",
+            WriterKind::ArrowCpp => "arrow-cpp",
+            WriterKind::ArrowRs => "arrow-rs",
+            WriterKind::ArrowGo => "arrow-go",
+            WriterKind::Fastparquet => "fastparquet",
+            WriterKind::DuckDb => "DuckDB",
+            WriterKind::Polars => "Polars",
+            WriterKind::Impala => "Impala",
+            WriterKind::ParquetNet => "Parquet.Net",
+            WriterKind::Other => "unknown",
+        }
+    }
+}
+
+/// The library that wrote the file, parsed from `created_by`, which the
+/// spec lays out as `<application> version <version> (build <hash>)`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Writer {
+    pub kind: WriterKind,
+    /// Application name as written, e.g. `parquet-cpp-arrow`.
+    pub application: String,
+    pub version: Option<String>,
+    pub build: Option<String>,
+    /// Engine on top of the library, from the key-value metadata, e.g.
+    /// `Spark 3.5.0`.
+    pub engine: Option<String>,
+}
+
+impl Writer {
+    /// Parse `created_by`; `None` when it is missing or empty.
+    pub fn parse(created_by: &str) -> Option<Self> {
+        let created_by = created_by.trim();
+        if created_by.is_empty() || created_by == "—" {
+            return None;
+        }
+        let (head, build) = match created_by.split_once("(build") {
+            Some((head, build)) => (
+                head.trim(),
+                Some(build.trim_end_matches(')').trim().to_string()).filter(|b| !b.is_empty()),
+            ),
+            None => (created_by, None),
+        };
+        let (application, version) = match head.split_once(" version ") {
+            Some((application, version)) => (
+                application.trim(),
+                version.split_whitespace().next().map(str::to_string),
+            ),
+            None => (head.split_whitespace().next().unwrap_or(head), None),
+        };
+        Some(Self {
+            kind: WriterKind::from_application(application),
+            application: application.to_string(),
+            version,
+            build,
+            engine: None,
+        })
+    }
+
+    /// Parse the writer of `md`, naming the engine when the key-value
+    /// metadata gives it away.
+    pub fn from_metadata(md: &ParquetMetaData) -> Option<Self> {
+        let mut writer = Self::parse(md.file_metadata().created_by()?)?;
+        let kv = md.file_metadata().key_value_metadata();
+        let value_of = |key: &str| {
+            kv.and_then(|kv| kv.iter().find(|pair| pair.key == key))
+                .and_then(|pair| pair.value.clone())
+        };
+        writer.engine = if let Some(version) = value_of("org.apache.spark.version") {
+            Some(format!("Spark {version}"))
+        } else if value_of("pandas").is_some() {
+            Some("pandas".to_string())
+        } else {
+            None
+        };
+        Some(writer)
+    }
+
+    /// `(major, minor, patch)` of the version, ignoring a leading `v` and
+    /// suffixes such as `-SNAPSHOT`.
+    pub fn semver(&self) -> Option<(u64, u64, u64)> {
+        let version = self.version.as_deref()?.trim_start_matches('v');
+        let mut parts = version
+            .split(['.', '-', '+'])
+            .map(|part| part.parse::<u64>());
+        let major = parts.next()?.ok()?;
+        let minor = parts.next().and_then(Result::ok).unwrap_or(0);
+        let patch = parts.next().and_then(Result::ok).unwrap_or(0);
+        Some((major, minor, patch))
+    }
+
+    fn older_than(&self, version: (u64, u64, u64)) -> bool {
+        self.semver().is_some_and(|v| v < version)
+    }
+
+    /// Known habits of this writer that show in `md`, e.g. statistics
+    /// readers should not trust. Only quirks the file actually exhibits are
+    /// listed.
+    pub fn quirks(&self, md: &ParquetMetaData) -> Vec<String> {
+        let schema = md.file_metadata().schema_descr();
+        let has_type = |physical: PhysicalType| {
+            schema
+                .columns()
+                .iter()
+                .any(|col| col.physical_type() == physical)
+        };
+        let chunks = || md.row_groups().iter().flat_map(|rg| rg.columns());
+        let has_page_index = chunks().any(|col| col.column_index_offset().is_some());
+
+        let mut quirks = vec![];
+        if self.kind == WriterKind::ParquetMr
+            && self.older_than((1, 8, 0))
+            && chunks().any(|col| {
+                col.statistics().is_some()
+                    && matches!(
+                        col.column_type(),
+                        PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY
+                    )
+            })
+        {
+            quirks.push(
+                "binary min/max from parquet-mr before 1.8.0 may be wrong (PARQUET-251)"
+                    .to_string(),
+            );
+        }
+        if has_type(PhysicalType::INT96) {
+            let why = match (self.kind, &self.engine) {
+                (_, Some(engine)) if engine.starts_with("Spark") => "Spark's default",
+                (WriterKind::Impala, _) => "Impala's default",
+                (WriterKind::ArrowCpp, _) => "opted into by arrow-cpp",
+                _ => "deprecated",
+            };
+            quirks.push(format!(
+                "INT96 timestamps ({why}); engines disagree on their time zone"
+            ));
+        }
+        if !has_page_index && md.num_row_groups() > 0 {
+            let why = match self.kind {
+                WriterKind::ParquetMr if self.older_than((1, 11, 0)) => {
+                    Some("parquet-mr writes one from 1.11.0")
+                }
+                WriterKind::ArrowCpp => Some("arrow-cpp only writes one when enabled"),
+                WriterKind::Fastparquet => Some("fastparquet does not write one"),
+                _ => None,
+            };
+            if let Some(why) = why {
+                quirks.push(format!("no page index: {why}"));
+            }
+        }
+        quirks
+    }
+}
+
+impl fmt::Display for Writer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            WriterKind::Other => f.write_str(&self.application)?,
+            kind => f.write_str(kind.name())?,
+        }
+        if let Some(version) = &self.version {
+            write!(f, " {version}")?;
+        }
+        if let Some(engine) = &self.engine {
+            write!(f, " via {engine}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_data_path;
+    use parquet::file::reader::FileReader;
+
+    #[test]
+    fn test_parse_created_by() {
+        let writer = Writer::parse(
+            "parquet-mr version 1.12.3 (build f8dced182c4c1fbdec6ccb3185537b5a01e6ed6b)",
+        )
+        .unwrap();
+        assert_eq!(writer.kind, WriterKind::ParquetMr);
+        assert_eq!(writer.version.as_deref(), Some("1.12.3"));
+        assert_eq!(
+            writer.build.as_deref(),
+            Some("f8dced182c4c1fbdec6ccb3185537b5a01e6ed6b")
+        );
+        assert_eq!(writer.semver(), Some((1, 12, 3)));
+        assert_eq!(writer.to_string(), "parquet-mr 1.12.3");
+
+        let cases = [
+            (
+                "parquet-cpp-arrow version 14.0.1",
+                WriterKind::ArrowCpp,
+                "arrow-cpp 14.0.1",
+            ),
+            (
+                "parquet-rs version 53.0.0",
+                WriterKind::ArrowRs,
+                "arrow-rs 53.0.0",
+            ),
+            (
+                "fastparquet-python version 2023.10.1 (build 0)",
+                WriterKind::Fastparquet,
+                "fastparquet 2023.10.1",
+            ),
+            (
+                "DuckDB version v1.1.0 (build fa5c2fe15f)",
+                WriterKind::DuckDb,
+                "DuckDB v1.1.0",
+            ),
+            ("Polars", WriterKind::Polars, "Polars"),
+            ("my-writer 0.1", WriterKind::Other, "my-writer"),
+        ];
+        for (created_by, kind, display) in cases {
+            let writer = Writer::parse(created_by).unwrap();
+            assert_eq!(writer.kind, kind, "{created_by}");
+            assert_eq!(writer.to_string(), display);
+        }
+        assert_eq!(
+            Writer::parse("DuckDB version v1.1.0").unwrap().semver(),
+            Some((1, 1, 0))
+        );
+        assert_eq!(
+            Writer::parse("impala version 1.3.0-INTERNAL")
+                .unwrap()
+                .semver(),
+            Some((1, 3, 0))
+        );
+        assert_eq!(Writer::parse(""), None);
+        assert_eq!(Writer::parse("—"), None);
+    }
+
+    #[test]
+    fn test_writer_of_file() {
+        let path = test_data_path("alltypes_plain.parquet");
+        let (reader, _) = crate::file::parquet_ctx::open_reader(&path).unwrap();
+        let writer = Writer::from_metadata(reader.metadata()).unwrap();
+        assert_eq!(writer.kind, WriterKind::Impala);
+        assert_eq!(writer.to_string(), "Impala 1.3.0-INTERNAL");
+        // Impala has no page index quirk of its own to report.
+        assert!(
+            writer
+                .quirks(reader.metadata())
+                .iter()
+                .all(|quirk| !quirk.starts_with("no page index"))
+        );
+    }
+}
@@ -0,0 +1,117 @@
+/// How many places the jump list remembers.
+pub const MAX_JUMPS: usize = 100;
+
+/// Places jumped away from, in order, as in vim: `back` returns to the
+/// previous one and `forward` retraces the steps taken back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JumpList<T> {
+    places: Vec<T>,
+    // Position in `places` while going back and forth; `places.len()`
+    // when not.
+    index: usize,
+}
+
+impl<T> Default for JumpList<T> {
+    fn default() -> Self {
+        Self {
+            places: Vec::new(),
+            index: 0,
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> JumpList<T> {
+    /// Remember `from`, the place a jump leaves. An earlier visit to the
+    /// same place moves to the end, and going forward starts over.
+    pub fn record(&mut self, from: T) {
+        self.places.retain(|place| *place != from);
+        self.places.push(from);
+        if self.places.len() > MAX_JUMPS {
+            self.places.remove(0);
+        }
+        self.index = self.places.len();
+    }
+
+    /// The place before `current`, if any. The first step back remembers
+    /// `current` so that `forward` can come back to it.
+    pub fn back(&mut self, current: T) -> Option<T> {
+        if self.index == self.places.len() {
+            if self.places.last() != Some(&current) {
+                self.record(current);
+            }
+            self.index = self.places.len() - 1;
+        }
+        self.index = self.index.checked_sub(1)?;
+        self.places.get(self.index).cloned()
+    }
+
+    /// The place after the current one, undoing a step `back`.
+    pub fn forward(&mut self) -> Option<T> {
+        if self.index + 1 >= self.places.len() {
+            return None;
+        }
+        self.index += 1;
+        self.places.get(self.index).cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.places.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.places.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_back_and_forward() {
+        let mut jumps = JumpList::default();
+        assert_eq!(jumps.back(1), None);
+        jumps.record(1);
+        jumps.record(2);
+        // At 3 now.
+        assert_eq!(jumps.back(3), Some(2));
+        assert_eq!(jumps.back(2), Some(1));
+        assert_eq!(jumps.back(1), None);
+        assert_eq!(jumps.forward(), Some(2));
+        assert_eq!(jumps.forward(), Some(3));
+        assert_eq!(jumps.forward(), None);
+        assert_eq!(jumps.len(), 3);
+    }
+
+    #[test]
+    fn test_record_after_going_back() {
+        let mut jumps = JumpList::default();
+        jumps.record(1);
+        jumps.record(2);
+        assert_eq!(jumps.back(3), Some(2));
+        assert_eq!(jumps.back(2), Some(1));
+        // Jumping from 1 moves it to the end.
+        jumps.record(1);
+        assert_eq!(jumps.forward(), None);
+        assert_eq!(jumps.back(4), Some(1));
+        assert_eq!(jumps.back(1), Some(3));
+        assert_eq!(jumps.back(3), Some(2));
+    }
+
+    #[test]
+    fn test_length_is_bounded() {
+        let mut jumps = JumpList::default();
+        for place in 0..MAX_JUMPS + 10 {
+            jumps.record(place);
+        }
+        assert_eq!(jumps.len(), MAX_JUMPS);
+        let mut oldest = None;
+        let mut current = MAX_JUMPS + 10;
+        while let Some(place) = jumps.back(current) {
+            oldest = Some(place);
+            current = place;
+        }
+        // Going back remembers the latest place too, pushing out one more.
+        assert_eq!(oldest, Some(11));
+    }
+}
@@ -0,0 +1,619 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::config::TabKind;
+
+/// Everything a key can be bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    Reset,
+    NextTab,
+    PrevTab,
+    Help,
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    PageLeft,
+    PageRight,
+    ShrinkTree,
+    GrowTree,
+    ToggleTree,
+    Goto,
+    Command,
+    ToggleGroup,
+    ToggleFlat,
+    ToggleLayout,
+    EstimateDistinct,
+    MarkRowGroup,
+    ToggleFieldIds,
+    Inspect,
+    ExpandRow,
+    WrapRow,
+    Copy,
+    Sample,
+    LastRows,
+    OpenExternal,
+    Search,
+    NextMatch,
+    PrevMatch,
+    Bookmark,
+    ToggleBookmarked,
+    ToggleSplit,
+    JumpBack,
+    JumpForward,
+    RecordMacro,
+    PlayMacro,
+}
+
+impl Action {
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::Reset => "Reset position / close popup",
+            Action::NextTab => "Next tab",
+            Action::PrevTab => "Previous tab",
+            Action::Help => "Toggle this help",
+            Action::Up => "Move up",
+            Action::Down => "Move down",
+            Action::Left => "Move left",
+            Action::Right => "Move right",
+            Action::PageUp => "Page up",
+            Action::PageDown => "Page down",
+            Action::PageLeft => "Page left",
+            Action::PageRight => "Page right",
+            Action::ShrinkTree => "Shrink schema tree",
+            Action::GrowTree => "Grow schema tree",
+            Action::ToggleTree => "Show / hide schema tree",
+            Action::Goto => "Go to row / row group",
+            Action::Command => "Open the command line, e.g. :tab schema",
+            Action::ToggleGroup => "Fold / unfold schema group",
+            Action::ToggleFlat => "Switch schema tree / flat paths",
+            Action::ToggleLayout => "Show / hide byte layout",
+            Action::EstimateDistinct => "Estimate distinct values",
+            Action::MarkRowGroup => "Mark row group to compare",
+            Action::ToggleFieldIds => "Show / hide field ids",
+            Action::Inspect => "Inspect the selected row",
+            Action::ExpandRow => "Expand nested values of the selected row",
+            Action::WrapRow => "Wrap the long values of the selected row",
+            Action::Copy => "Copy the selected column's path and type",
+            Action::Sample => "Change which rows are previewed",
+            Action::LastRows => "Jump to the last rows of the file",
+            Action::OpenExternal => "Open the value or schema in the pager / editor",
+            Action::Search => "Search the previewed values",
+            Action::NextMatch => "Next row matching the search",
+            Action::PrevMatch => "Previous row matching the search",
+            Action::Bookmark => "Bookmark the selected column",
+            Action::ToggleBookmarked => "Show only bookmarked columns / all columns",
+            Action::ToggleSplit => "Show / hide the data preview under the schema",
+            Action::JumpBack => "Back to where the last jump left",
+            Action::JumpForward => "Forward again after jumping back",
+            Action::RecordMacro => "Record keys into a register (a-z, 0-9) / stop",
+            Action::PlayMacro => "Replay the keys of a register (@ again: the last one)",
+        }
+    }
+}
+
+/// Keys for one action in `[keybindings]`: either `"j"` or `["j", "Down"]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    fn keys(&self) -> &[String] {
+        match self {
+            KeyList::One(key) => std::slice::from_ref(key),
+            KeyList::Many(keys) => keys,
+        }
+    }
+}
+
+/// Action name to the keys that trigger it. Listing an action replaces its
+/// default keys.
+pub type Bindings = BTreeMap<Action, KeyList>;
+
+/// `[keybindings]` section. Bindings at the top apply everywhere; those in
+/// `[keybindings.<tab>]` apply on top of them while that tab is active.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeybindingsConfig {
+    #[serde(flatten)]
+    pub global: Bindings,
+    #[serde(skip_serializing_if = "Bindings::is_empty")]
+    pub visualize: Bindings,
+    #[serde(skip_serializing_if = "Bindings::is_empty")]
+    pub metadata: Bindings,
+    #[serde(skip_serializing_if = "Bindings::is_empty")]
+    pub schema: Bindings,
+    #[serde(skip_serializing_if = "Bindings::is_empty")]
+    pub row_groups: Bindings,
+}
+
+impl KeybindingsConfig {
+    pub fn is_empty(&self) -> bool {
+        self.global.is_empty() && self.tabs().iter().all(|(_, b)| b.is_empty())
+    }
+
+    fn tabs(&self) -> [(TabKind, &Bindings); 4] {
+        [
+            (TabKind::Visualize, &self.visualize),
+            (TabKind::Metadata, &self.metadata),
+            (TabKind::Schema, &self.schema),
+            (TabKind::RowGroups, &self.row_groups),
+        ]
+    }
+}
+
+/// A key with the modifiers held down, e.g. `Ctrl+d`. Shift is folded into
+/// the character for character keys, so `D` and `Shift+d` are the same key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c.to_uppercase().next().unwrap_or(c))
+            }
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            KeyCode::BackTab => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            code => code,
+        };
+        Self { code, modifiers }
+    }
+}
+
+impl From<KeyCode> for Key {
+    fn from(code: KeyCode) -> Self {
+        Key::new(code, KeyModifiers::NONE)
+    }
+}
+
+impl From<KeyEvent> for Key {
+    fn from(event: KeyEvent) -> Self {
+        Key::new(event.code, event.modifiers)
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl+"),
+            (KeyModifiers::ALT, "Alt+"),
+            (KeyModifiers::SHIFT, "Shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(name)?;
+            }
+        }
+        write!(f, "{}", KeyName(self.code))
+    }
+}
+
+/// Resolved key to action bindings. Each key maps to at most one action.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    bindings: Vec<(Key, Action)>,
+    /// Keymaps of the tabs with a `[keybindings.<tab>]` section, by tab
+    /// position.
+    tabs: BTreeMap<usize, Keymap>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        // The defaults always parse.
+        Keymap::from_config(&KeybindingsConfig::default()).unwrap_or_else(|_| Keymap::global())
+    }
+}
+
+/// Default keys that only apply in one tab, e.g. `n` steps through search
+/// matches in Visualize while it estimates distinct values in Schema.
+fn tab_defaults(tab: TabKind) -> &'static [(KeyCode, Action)] {
+    match tab {
+        TabKind::Visualize => &[
+            (KeyCode::Char('n'), Action::NextMatch),
+            (KeyCode::Char('N'), Action::PrevMatch),
+        ],
+        _ => &[],
+    }
+}
+
+impl Keymap {
+    /// The default keys of every tab.
+    fn global() -> Self {
+        let mut keymap = Keymap {
+            bindings: vec![],
+            tabs: BTreeMap::new(),
+        };
+        for (key, action) in [
+            (KeyCode::Char('q'), Action::Quit),
+            (KeyCode::Char('Q'), Action::Quit),
+            (KeyCode::Esc, Action::Reset),
+            (KeyCode::Tab, Action::NextTab),
+            (KeyCode::BackTab, Action::PrevTab),
+            (KeyCode::Char('?'), Action::Help),
+            (KeyCode::Up, Action::Up),
+            (KeyCode::Down, Action::Down),
+            (KeyCode::Left, Action::Left),
+            (KeyCode::Right, Action::Right),
+            (KeyCode::Char('u'), Action::PageUp),
+            (KeyCode::Char('U'), Action::PageUp),
+            (KeyCode::Char('d'), Action::PageDown),
+            (KeyCode::Char('D'), Action::PageDown),
+            (KeyCode::Char('H'), Action::PageLeft),
+            (KeyCode::Char('L'), Action::PageRight),
+            (KeyCode::Char('<'), Action::ShrinkTree),
+            (KeyCode::Char('>'), Action::GrowTree),
+            (KeyCode::Char('t'), Action::ToggleTree),
+            (KeyCode::Char('T'), Action::ToggleTree),
+            (KeyCode::Char('g'), Action::Goto),
+            (KeyCode::Char(':'), Action::Command),
+            (KeyCode::Enter, Action::ToggleGroup),
+            (KeyCode::Char('f'), Action::ToggleFlat),
+            (KeyCode::Char('F'), Action::ToggleFlat),
+            (KeyCode::Char('o'), Action::ToggleLayout),
+            (KeyCode::Char('O'), Action::ToggleLayout),
+            (KeyCode::Char('n'), Action::EstimateDistinct),
+            (KeyCode::Char('N'), Action::EstimateDistinct),
+            (KeyCode::Char('m'), Action::MarkRowGroup),
+            (KeyCode::Char('M'), Action::MarkRowGroup),
+            (KeyCode::Char('i'), Action::ToggleFieldIds),
+            (KeyCode::Char('I'), Action::ToggleFieldIds),
+            (KeyCode::Char('v'), Action::Inspect),
+            (KeyCode::Char('V'), Action::Inspect),
+            (KeyCode::Char('e'), Action::ExpandRow),
+            (KeyCode::Char('E'), Action::ExpandRow),
+            (KeyCode::Char('w'), Action::WrapRow),
+            (KeyCode::Char('W'), Action::WrapRow),
+            (KeyCode::Char('y'), Action::Copy),
+            (KeyCode::Char('Y'), Action::Copy),
+            (KeyCode::Char('s'), Action::Sample),
+            (KeyCode::Char('S'), Action::Sample),
+            (KeyCode::Char('G'), Action::LastRows),
+            (KeyCode::End, Action::LastRows),
+            (KeyCode::Char('p'), Action::OpenExternal),
+            (KeyCode::Char('P'), Action::OpenExternal),
+            (KeyCode::Char('/'), Action::Search),
+            (KeyCode::Char('b'), Action::Bookmark),
+            (KeyCode::Char('B'), Action::ToggleBookmarked),
+            (KeyCode::Char('|'), Action::ToggleSplit),
+            (KeyCode::Char('@'), Action::PlayMacro),
+        ] {
+            keymap.bind(key.into(), action);
+        }
+        // Terminals that can't tell Ctrl+i from Tab send it as Tab.
+        keymap.bind(
+            Key::new(KeyCode::Char('o'), KeyModifiers::CONTROL),
+            Action::JumpBack,
+        );
+        keymap.bind(
+            Key::new(KeyCode::Char('i'), KeyModifiers::CONTROL),
+            Action::JumpForward,
+        );
+        // vim's `q`, which quits here.
+        keymap.bind(
+            Key::new(KeyCode::Char('q'), KeyModifiers::CONTROL),
+            Action::RecordMacro,
+        );
+        keymap
+    }
+
+    /// Defaults with the user's remaps applied on top. A tab's own default
+    /// keys give way to a global remap of the same action.
+    pub fn from_config(config: &KeybindingsConfig) -> Result<Self, String> {
+        let mut keymap = Keymap::global();
+        keymap.apply(&config.global)?;
+        for (tab, bindings) in config.tabs() {
+            let mut tab_keymap = keymap.clone();
+            for &(key, action) in tab_defaults(tab) {
+                if !config.global.contains_key(&action) {
+                    tab_keymap.bind(key.into(), action);
+                }
+            }
+            tab_keymap.apply(bindings)?;
+            if tab_keymap != keymap {
+                keymap.tabs.insert(tab.index(), tab_keymap);
+            }
+        }
+        Ok(keymap)
+    }
+
+    fn apply(&mut self, bindings: &Bindings) -> Result<(), String> {
+        for (action, keys) in bindings {
+            self.bindings.retain(|(_, a)| a != action);
+            for key in keys.keys() {
+                self.bind(parse_key(key)?, *action);
+            }
+        }
+        Ok(())
+    }
+
+    /// The bindings in effect while the tab at position `tab` is active.
+    pub fn in_tab(&self, tab: usize) -> &Keymap {
+        self.tabs.get(&tab).unwrap_or(self)
+    }
+
+    /// Bind `key` to `action`, taking it away from any other action.
+    pub fn bind(&mut self, key: Key, action: Action) {
+        self.bindings.retain(|(k, _)| *k != key);
+        self.bindings.push((key, action));
+    }
+
+    pub fn action_for(&self, key: impl Into<Key>) -> Option<Action> {
+        let key = key.into();
+        self.bindings
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, action)| *action)
+    }
+
+    pub fn keys_for(&self, action: Action) -> Vec<Key> {
+        self.bindings
+            .iter()
+            .filter(|(_, a)| *a == action)
+            .map(|(key, _)| *key)
+            .collect()
+    }
+
+    /// Keys bound to `action`, formatted for display (e.g. `q/Q`).
+    pub fn describe(&self, action: Action) -> String {
+        let keys = self.keys_for(action);
+        if keys.is_empty() {
+            return "unbound".to_string();
+        }
+        keys.into_iter()
+            .map(|key| key.to_string())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+/// Parse a key from the config: a single character or a named key such as
+/// `Down`, `PageUp`, `Esc` or `F5`, optionally after `Ctrl+`, `Alt+` or
+/// `Shift+` (all case-insensitive), e.g. `Ctrl+d`.
+pub fn parse_key(name: &str) -> Result<Key, String> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = name;
+    while let Some((prefix, key)) = rest.split_once('+')
+        && !key.is_empty()
+    {
+        modifiers |= match prefix.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => return Err(format!("unknown modifier '{prefix}' in '{name}'")),
+        };
+        rest = key;
+    }
+    let code = parse_key_code(rest).ok_or_else(|| format!("unknown key '{name}'"))?;
+    Ok(Key::new(code, modifiers))
+}
+
+fn parse_key_code(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        // Arrows as the help overlay shows them.
+        return Some(match c {
+            '↑' => KeyCode::Up,
+            '↓' => KeyCode::Down,
+            '←' => KeyCode::Left,
+            '→' => KeyCode::Right,
+            c => KeyCode::Char(c),
+        });
+    }
+    let key = match name.to_ascii_lowercase().as_str() {
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "enter" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "delete" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "space" => KeyCode::Char(' '),
+        other => match other.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+            Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+            _ => return None,
+        },
+    };
+    Some(key)
+}
+
+/// Display name of a key, as shown in the help overlay.
+pub struct KeyName(pub KeyCode);
+
+impl fmt::Display for KeyName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_bindings() {
+        let keymap = Keymap::default();
+        assert_eq!(keymap.action_for(KeyCode::Char('q')), Some(Action::Quit));
+        assert_eq!(keymap.action_for(KeyCode::Down), Some(Action::Down));
+        assert_eq!(keymap.action_for(KeyCode::Char('x')), None);
+        assert_eq!(keymap.describe(Action::Quit), "q/Q");
+        assert_eq!(keymap.describe(Action::Up), "↑");
+    }
+
+    #[test]
+    fn test_remap_replaces_default_keys() {
+        let mut config = KeybindingsConfig::default();
+        config.global.insert(
+            Action::Down,
+            KeyList::Many(vec!["j".to_string(), "Down".to_string()]),
+        );
+        config
+            .global
+            .insert(Action::Quit, KeyList::One("x".to_string()));
+        let keymap = Keymap::from_config(&config).unwrap();
+
+        assert_eq!(keymap.action_for(KeyCode::Char('j')), Some(Action::Down));
+        assert_eq!(keymap.action_for(KeyCode::Down), Some(Action::Down));
+        assert_eq!(keymap.action_for(KeyCode::Char('x')), Some(Action::Quit));
+        assert_eq!(keymap.action_for(KeyCode::Char('q')), None);
+        assert_eq!(keymap.describe(Action::Quit), "x");
+    }
+
+    #[test]
+    fn test_tab_defaults() {
+        let keymap = Keymap::default();
+        let visualize = keymap.in_tab(TabKind::Visualize.index());
+        let schema = keymap.in_tab(TabKind::Schema.index());
+        assert_eq!(visualize.describe(Action::NextMatch), "n");
+        assert_eq!(visualize.describe(Action::PrevMatch), "N");
+        assert_eq!(
+            schema.action_for(KeyCode::Char('n')),
+            Some(Action::EstimateDistinct)
+        );
+        assert_eq!(keymap.action_for(KeyCode::Char('/')), Some(Action::Search));
+
+        // A global remap replaces the tab's default key.
+        let config: KeybindingsConfig = toml::from_str("next_match = \"]\"\n").unwrap();
+        let keymap = Keymap::from_config(&config).unwrap();
+        let visualize = keymap.in_tab(TabKind::Visualize.index());
+        assert_eq!(visualize.describe(Action::NextMatch), "]");
+        assert_eq!(
+            visualize.action_for(KeyCode::Char('n')),
+            Some(Action::EstimateDistinct)
+        );
+    }
+
+    #[test]
+    fn test_remap_steals_key_from_other_action() {
+        let mut config = KeybindingsConfig::default();
+        config
+            .global
+            .insert(Action::PageDown, KeyList::One("t".to_string()));
+        let keymap = Keymap::from_config(&config).unwrap();
+        assert_eq!(
+            keymap.action_for(KeyCode::Char('t')),
+            Some(Action::PageDown)
+        );
+        assert_eq!(
+            keymap.keys_for(Action::ToggleTree),
+            vec![Key::from(KeyCode::Char('T'))]
+        );
+    }
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(parse_key("j"), Ok(KeyCode::Char('j').into()));
+        assert_eq!(parse_key("PageDown"), Ok(KeyCode::PageDown.into()));
+        assert_eq!(parse_key("esc"), Ok(KeyCode::Esc.into()));
+        assert_eq!(parse_key("F5"), Ok(KeyCode::F(5).into()));
+        assert_eq!(parse_key("space"), Ok(KeyCode::Char(' ').into()));
+        assert!(parse_key("F13").is_err());
+        assert!(parse_key("hyper").is_err());
+    }
+
+    #[test]
+    fn test_parse_key_with_modifiers() {
+        let ctrl_d = Key::new(KeyCode::Char('d'), KeyModifiers::CONTROL);
+        assert_eq!(parse_key("Ctrl+d"), Ok(ctrl_d));
+        assert_eq!(parse_key("control+d"), Ok(ctrl_d));
+        assert_eq!(
+            parse_key("Ctrl+Alt+Down"),
+            Ok(Key::new(
+                KeyCode::Down,
+                KeyModifiers::CONTROL | KeyModifiers::ALT
+            ))
+        );
+        assert_eq!(parse_key("Shift+d"), Ok(KeyCode::Char('D').into()));
+        assert_eq!(parse_key("Shift+Tab"), Ok(KeyCode::BackTab.into()));
+        assert_eq!(parse_key("+"), Ok(KeyCode::Char('+').into()));
+        assert_eq!(
+            parse_key("Ctrl++"),
+            Ok(Key::new(KeyCode::Char('+'), KeyModifiers::CONTROL))
+        );
+        assert!(parse_key("Hyper+d").is_err());
+        assert!(parse_key("Ctrl+hyper").is_err());
+        assert_eq!(ctrl_d.to_string(), "Ctrl+d");
+    }
+
+    #[test]
+    fn test_key_events_match_chords() {
+        let mut config = KeybindingsConfig::default();
+        config
+            .global
+            .insert(Action::PageDown, KeyList::One("Ctrl+d".to_string()));
+        let keymap = Keymap::from_config(&config).unwrap();
+
+        let ctrl_d = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL);
+        assert_eq!(keymap.action_for(ctrl_d), Some(Action::PageDown));
+        assert_eq!(keymap.action_for(KeyCode::Char('d')), None);
+        // Terminals report capitals with Shift held.
+        let shift_q = KeyEvent::new(KeyCode::Char('Q'), KeyModifiers::SHIFT);
+        assert_eq!(keymap.action_for(shift_q), Some(Action::Quit));
+        assert_eq!(keymap.describe(Action::PageDown), "Ctrl+d");
+    }
+
+    #[test]
+    fn test_tab_sections_apply_on_top_of_global() {
+        let config: KeybindingsConfig =
+            toml::from_str("up = \"k\"\n[schema]\ndown = \"j\"\nup = \"Ctrl+p\"\n").unwrap();
+        let keymap = Keymap::from_config(&config).unwrap();
+        let schema = keymap.in_tab(TabKind::Schema.index());
+        let visualize = keymap.in_tab(TabKind::Visualize.index());
+
+        assert_eq!(schema.action_for(KeyCode::Char('j')), Some(Action::Down));
+        assert_eq!(schema.action_for(KeyCode::Down), None);
+        assert_eq!(schema.describe(Action::Up), "Ctrl+p");
+        assert_eq!(schema.action_for(KeyCode::Char('q')), Some(Action::Quit));
+        assert_eq!(visualize.action_for(KeyCode::Char('j')), None);
+        assert_eq!(visualize.describe(Action::Up), "k");
+        assert_eq!(keymap.describe(Action::Up), "k");
+
+        assert_eq!(
+            visualize.action_for(KeyCode::Char('n')),
+            Some(Action::NextMatch)
+        );
+
+        let bad: KeybindingsConfig = toml::from_str("[metadata]\nquit = \"Hyper+q\"\n").unwrap();
+        assert!(Keymap::from_config(&bad).is_err());
+    }
+}
@@ -1,7 +1,56 @@
+//! Inspect Parquet files: metadata, schema, row groups, pages and sample rows.
+//!
+//! parqeye is mainly a terminal viewer, but the types it reads files into can
+//! be used on their own. [`file::parquet_ctx::ParquetCtx::from_file`] is the
+//! entry point; everything it returns implements `serde::Serialize`.
+//!
+//! ```no_run
+//! use parqeye::file::parquet_ctx::ParquetCtx;
+//!
+//! let ctx = ParquetCtx::from_file("data.parquet")?;
+//! println!("{} rows in {} row groups", ctx.metadata.num_rows, ctx.metadata.num_row_groups);
+//! for (path, size) in ctx.schema.largest_columns(3) {
+//!     println!("{path}: {size} bytes");
+//! }
+//! println!("{}", serde_json::to_string_pretty(&ctx.row_groups.aggregates)?);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! The stable library API is the [`file`] module. The terminal UI lives behind
+//! the default `tui` feature; build with `default-features = false` to leave
+//! out ratatui and crossterm.
+
+#[cfg(feature = "tui")]
 pub mod app;
+#[cfg(feature = "tui")]
+pub mod command;
+#[cfg(feature = "tui")]
 pub mod components;
+#[cfg(feature = "tui")]
+pub mod config;
+#[cfg(feature = "tui")]
+pub mod external;
 pub mod file;
+#[cfg(feature = "tui")]
+pub mod jumps;
+#[cfg(feature = "tui")]
+pub mod keymap;
+#[cfg(feature = "tui")]
+pub mod macros;
+#[cfg(feature = "tui")]
+pub mod mouse;
+#[cfg(feature = "tui")]
+pub mod script;
+#[cfg(feature = "tui")]
+pub mod session;
+#[cfg(feature = "tui")]
+pub mod status;
+#[cfg(feature = "tui")]
 pub mod tabs;
+#[cfg(feature = "tui")]
+pub mod theme;
+#[cfg(feature = "tui")]
 pub mod ui;
 
+#[cfg(feature = "tui")]
 pub use app::App;
@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::ConfigError;
+use crate::keymap::{Key, parse_key};
+
+/// Key sequences recorded into registers with `record_macro`, replayed with
+/// `play_macro`. Saved next to `config.toml`, so a macro recorded on one
+/// file replays on the next.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Macros {
+    /// Keys of each register, named as in `[keybindings]`.
+    #[serde(flatten)]
+    registers: BTreeMap<String, Vec<String>>,
+}
+
+/// Whether `c` names a register: a letter or a digit.
+pub fn is_register(c: char) -> bool {
+    c.is_ascii_alphanumeric()
+}
+
+impl Macros {
+    /// Where the macros are saved, e.g. `~/.config/parqeye/macros.toml`.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("parqeye").join("macros.toml"))
+    }
+
+    /// The saved macros, or none if nothing was saved yet.
+    pub fn load() -> Result<Self, ConfigError> {
+        match Self::default_path() {
+            Some(path) => Self::load_from(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self, ConfigError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let macros: Macros = toml::from_str(&contents).map_err(|e| ConfigError::Parse {
+            path: path.to_path_buf(),
+            details: e.to_string(),
+        })?;
+        for (register, keys) in &macros.registers {
+            let mut chars = register.chars();
+            if !matches!((chars.next(), chars.next()), (Some(c), None) if is_register(c)) {
+                return Err(ConfigError::Parse {
+                    path: path.to_path_buf(),
+                    details: format!("'{register}' is not a register (a-z, 0-9)"),
+                });
+            }
+            for key in keys {
+                parse_key(key).map_err(|details| ConfigError::Parse {
+                    path: path.to_path_buf(),
+                    details: format!("macro {register}: {details}"),
+                })?;
+            }
+        }
+        Ok(macros)
+    }
+
+    pub fn save(&self) -> Result<(), ConfigError> {
+        match Self::default_path() {
+            Some(path) => self.save_to(&path),
+            None => Ok(()),
+        }
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), ConfigError> {
+        let write_err = |source| ConfigError::Write {
+            path: path.to_path_buf(),
+            source,
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(write_err)?;
+        }
+        let contents = toml::to_string_pretty(self).map_err(|e| ConfigError::Parse {
+            path: path.to_path_buf(),
+            details: e.to_string(),
+        })?;
+        fs::write(path, contents).map_err(write_err)
+    }
+
+    /// The keys recorded into `register`, if any.
+    pub fn get(&self, register: char) -> Option<Vec<Key>> {
+        self.registers
+            .get(&register.to_string())?
+            .iter()
+            .map(|key| parse_key(key).ok())
+            .collect()
+    }
+
+    /// Record `keys` into `register`, replacing what it held. An empty
+    /// sequence clears it.
+    pub fn set(&mut self, register: char, keys: &[Key]) {
+        if keys.is_empty() {
+            self.registers.remove(&register.to_string());
+        } else {
+            self.registers.insert(
+                register.to_string(),
+                keys.iter().map(ToString::to_string).collect(),
+            );
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.registers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    #[test]
+    fn test_keys_round_trip() {
+        let keys = [
+            Key::from(KeyCode::Tab),
+            Key::from(KeyCode::BackTab),
+            Key::from(KeyCode::Char(':')),
+            Key::from(KeyCode::Char(' ')),
+            Key::from(KeyCode::Char('+')),
+            Key::from(KeyCode::Down),
+            Key::from(KeyCode::Enter),
+            Key::new(KeyCode::Char('o'), KeyModifiers::CONTROL),
+        ];
+        let mut macros = Macros::default();
+        macros.set('a', &keys);
+        assert_eq!(macros.get('a'), Some(keys.to_vec()));
+        assert_eq!(macros.get('b'), None);
+        macros.set('a', &[]);
+        assert!(macros.is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("parqeye-macros-{}", std::process::id()));
+        let path = dir.join("macros.toml");
+        assert_eq!(Macros::load_from(&path).unwrap(), Macros::default());
+
+        let mut macros = Macros::default();
+        macros.set('q', &[KeyCode::Tab.into(), KeyCode::Char('G').into()]);
+        macros.save_to(&path).unwrap();
+        assert_eq!(Macros::load_from(&path).unwrap(), macros);
+
+        fs::write(&path, "ab = [\"Tab\"]\n").unwrap();
+        assert!(Macros::load_from(&path).is_err());
+        fs::write(&path, "a = [\"Hyper+x\"]\n").unwrap();
+        assert!(Macros::load_from(&path).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
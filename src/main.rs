@@ -1,35 +1,994 @@
-use parqeye::app::App;
-use parqeye::file::error::FileIOError;
-use parqeye::file::parquet_ctx::ParquetCtx;
+use parqeye::app::{App, StartPosition};
+use parqeye::config::{AppConfig, TabKind};
+use parqeye::file::analyzer::Analyzers;
+use parqeye::file::bench::BenchReport;
+use parqeye::file::codecs::CodecReport;
+use parqeye::file::deletes::DeleteInfo;
+use parqeye::file::diff::FileDiff;
+use parqeye::file::duplicates::{DuplicateOptions, DuplicateReport};
+use parqeye::file::export::{ExportFormat, ExportOptions, export};
+use parqeye::file::extract::{RowGroupRange, extract_row_groups};
+use parqeye::file::generate::{ColumnSpec, GenerateOptions, generate, parse_codec};
+use parqeye::file::offsets::OffsetReport;
+use parqeye::file::parquet_ctx::{ParquetCtx, open_reader};
+use parqeye::file::profile::{FileProfile, ProfileOptions};
+use parqeye::file::report::Report;
+use parqeye::file::rewrite::{ColumnProjection, RowGroupPlan, RowGroupTarget};
+use parqeye::file::row_count::RowCountReport;
+use parqeye::file::schema_check::{SchemaCheck, expand_paths};
+use parqeye::file::summary::{FileSummary, format_summary_table};
+use parqeye::file::temporal::TemporalAudit;
+use parqeye::file::utf8::Utf8Report;
+use parqeye::file::utils::{commas, set_byte_units, set_ratio_thresholds, set_relative_times};
+use parqeye::file::views::View;
+use parqeye::macros::Macros;
+use parqeye::script::Script;
+use parqeye::session::Session;
+use parqeye::status::StatusMessage;
+use parqeye::theme::RenderMode;
 
-use clap::Parser;
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 
 #[derive(Parser)]
 #[command(
     author,
     version,
-    about = "Command line tool to visualize parquet files"
+    about = "Command line tool to visualize parquet files",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
 )]
 pub struct Opts {
+    #[command(flatten)]
+    pub view: ViewArgs,
+
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+}
+
+/// What to open in the app and where. `parqeye <path>` is short for
+/// `parqeye view <path>`.
+#[derive(Args)]
+pub struct ViewArgs {
     /// Path to the parquet file
-    pub path: String,
+    #[arg(required = true)]
+    pub path: Option<String>,
+
+    /// Tab to open on; defaults to the one the other position flags point
+    /// into
+    #[arg(long, value_enum)]
+    pub tab: Option<TabKind>,
+
+    /// Row group to select, numbered from 1
+    #[arg(long)]
+    pub row_group: Option<usize>,
+
+    /// Row to select in the Visualize tab, numbered from 1
+    #[arg(long)]
+    pub row: Option<usize>,
+
+    /// Column to select, as a dotted path such as `user.id`
+    #[arg(long)]
+    pub column: Option<String>,
+
+    /// Open with a preset, e.g. `size-debug` or `stats-audit`; `[presets]`
+    /// in the config adds more
+    #[arg(long)]
+    pub preset: Option<String>,
+
+    /// Draw without colors; also when NO_COLOR is set
+    #[arg(long)]
+    pub no_color: bool,
+
+    /// Draw borders, arrows and bars with ASCII characters only
+    #[arg(long)]
+    pub ascii: bool,
+
+    /// Count the rows deleted from a data file of an Iceberg table, by
+    /// reading the delete files under the table's `data` directory
+    #[arg(long)]
+    pub deletes: bool,
+}
+
+#[derive(Subcommand)]
+pub enum Commands {
+    /// Open the file in the app; the default when no command is given
+    View(ViewArgs),
+    /// Print one line per file with rows, size, row groups, codecs, writer
+    /// and schema hash. Files with an unusual schema are marked with `*`.
+    Summary {
+        /// Parquet files, or directories to search for them
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
+    /// Check that all files share one schema and list the files that
+    /// deviate. Exits non-zero if any do, for gating pipelines in CI.
+    Check {
+        /// Parquet files, or directories to search for them
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
+    /// Compare two files: rows, size, row groups, codecs, writer and
+    /// schema. Exits non-zero if their schemas differ.
+    Diff {
+        /// The file to compare against
+        left: String,
+        /// The file compared
+        right: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = DiffFormat::Text)]
+        format: DiffFormat,
+    },
+    /// Cross-check the row counts in the file metadata, the row groups and
+    /// the decoded data pages. Exits non-zero on any mismatch. Reads every
+    /// page, so it is slow on large files.
+    Rows {
+        /// Parquet files, or directories to search for them
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
+    /// Check that column chunks and pages don't overlap, come in order and
+    /// are where the footer's offsets say. Exits non-zero on any problem.
+    Offsets {
+        /// Parquet files, or directories to search for them
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
+    /// Profile each column from a sample of rows: null %, distinct estimate,
+    /// min/max, mean and stddev of numbers, NaN, infinity and -0.0 counts of
+    /// floats, and the most frequent values and average length of strings.
+    Profile {
+        /// Path to the parquet file
+        path: String,
+        /// Rows to read per column, spread over the row groups
+        #[arg(long, default_value_t = ProfileOptions::default().sample_rows)]
+        sample: usize,
+        /// Read every row instead of a sample
+        #[arg(long, conflicts_with = "sample")]
+        full: bool,
+        /// Most frequent values to list for string columns
+        #[arg(long, default_value_t = ProfileOptions::default().top_k)]
+        top: usize,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ProfileFormat::Text)]
+        format: ProfileFormat,
+        /// Write to this file instead of standard output
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Estimate the duplicate rate from a sample of rows, hashing the key
+    /// columns, and list the most repeated keys with the row groups they
+    /// are in. Exits non-zero if any key repeats.
+    Dups {
+        /// Path to the parquet file
+        path: String,
+        /// Top-level columns identifying a row, e.g. `--key id,date`;
+        /// defaults to every column
+        #[arg(long, value_delimiter = ',')]
+        key: Vec<String>,
+        /// Rows to read, spread over the row groups
+        #[arg(long, default_value_t = DuplicateOptions::default().sample_rows)]
+        sample: usize,
+        /// Most repeated keys to list
+        #[arg(long, default_value_t = DuplicateOptions::default().top_k)]
+        top: usize,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = DuplicatesFormat::Text)]
+        format: DuplicatesFormat,
+    },
+    /// List the timestamp, time and date columns with their unit, UTC
+    /// adjustment and whether they use INT96. Exits non-zero if timestamp
+    /// columns mix conventions.
+    Timestamps {
+        /// Path to the parquet file
+        path: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = TimestampsFormat::Text)]
+        format: TimestampsFormat,
+    },
+    /// Decode every STRING column and check it holds valid UTF-8, listing
+    /// the row group, page and row of the first invalid value. Exits
+    /// non-zero if any value is invalid.
+    Utf8 {
+        /// Path to the parquet file
+        path: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = Utf8Format::Text)]
+        format: Utf8Format,
+    },
+    /// Decode every column and time it, listing MB/s and rows/s per column
+    /// and per codec with the slowest column first. Reads the whole file.
+    Bench {
+        /// Path to the parquet file
+        path: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = BenchFormat::Text)]
+        format: BenchFormat,
+    },
+    /// Show each column's codec and compression ratio, estimate the level
+    /// of ZSTD, GZIP and BROTLI columns by re-encoding a sample, and
+    /// recommend a higher level where it would save space.
+    Codecs {
+        /// Path to the parquet file
+        path: String,
+        /// Level to project savings for; defaults to 9
+        #[arg(long)]
+        level: Option<u32>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = CodecsFormat::Text)]
+        format: CodecsFormat,
+    },
+    /// Write a self-contained report with the metadata, schema, column
+    /// statistics, row group charts and findings about the file's layout.
+    Report {
+        /// Path to the parquet file
+        path: String,
+        /// Write to this file instead of standard output
+        #[arg(short, long)]
+        output: Option<String>,
+        /// Output format; defaults to Markdown for `.md` outputs and HTML
+        /// otherwise
+        #[arg(long, value_enum)]
+        format: Option<ReportFormat>,
+    },
+    /// Run the steps of a TOML script on the file, such as selecting
+    /// columns, profiling them and writing a report, without opening the
+    /// app.
+    Exec {
+        /// The script, a list of `[[steps]]`
+        #[arg(long)]
+        script: String,
+        /// Path to the parquet file
+        path: String,
+    },
+    /// Plan how the row groups would be merged and split to reach a target
+    /// size, or which columns would be kept, and with `--output` write a copy
+    /// of the file that way.
+    Rewrite {
+        /// Path to the parquet file
+        path: String,
+        /// Target row group size: compressed bytes such as `128MiB`, or rows
+        /// such as `1000000rows`; without it the row groups are kept
+        #[arg(long, required_unless_present_any = ["select", "drop_columns"])]
+        row_group_size: Option<RowGroupTarget>,
+        /// Keep only these columns, e.g. `--select id,address.city`; the
+        /// top-level columns are written in this order
+        #[arg(long, value_delimiter = ',', conflicts_with = "drop_columns")]
+        select: Vec<String>,
+        /// Leave out these columns, e.g. `--drop-columns payload,address.zip`
+        #[arg(long, value_delimiter = ',')]
+        drop_columns: Vec<String>,
+        /// Write the rewritten file here; without it only the plan is
+        /// printed
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Copy one row group, or a range of them, into a new file without
+    /// decoding the pages, for a minimal reproduction of a problem.
+    Extract {
+        /// Row group to copy, numbered from 1 as in the app, or an
+        /// inclusive range such as `3-5`
+        #[arg(long)]
+        row_group: RowGroupRange,
+        /// Path to the parquet file
+        path: String,
+        /// Path of the new file
+        output: String,
+    },
+    /// Decode the rows and write them as CSV, JSON or JSON lines.
+    Export {
+        /// Path to the parquet file
+        path: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ExportFormatArg::Csv)]
+        format: ExportFormatArg,
+        /// Keep only these columns, e.g. `--select id,address.city`; the
+        /// top-level columns are written in this order
+        #[arg(long, value_delimiter = ',', conflicts_with = "drop_columns")]
+        select: Vec<String>,
+        /// Leave out these columns, e.g. `--drop-columns payload`
+        #[arg(long, value_delimiter = ',')]
+        drop_columns: Vec<String>,
+        /// Rows to write from the start of the file
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Write to this file instead of standard output
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Write a file of made-up rows with the given columns, row groups and
+    /// codecs, as a fixture for testing readers.
+    Generate {
+        /// Path of the file to write
+        output: String,
+        /// A column as `name:type[:option...]`, repeated for each column.
+        /// Types: bool, int32, int64, float, double, string, date and
+        /// timestamp. Options: `sequential` (the default), `random` or
+        /// `skewed`, `nulls=10%`, `distinct=N` and `codec=zstd(3)`
+        #[arg(long = "column", required = true)]
+        columns: Vec<ColumnSpec>,
+        /// Rows to write
+        #[arg(long, default_value_t = GenerateOptions::default().rows)]
+        rows: u64,
+        /// Row group size: bytes before compression such as `128MiB`, or
+        /// rows such as `100000rows`
+        #[arg(long)]
+        row_group_size: Option<RowGroupTarget>,
+        /// Codec of the columns that don't name their own, such as
+        /// `snappy` or `zstd(3)`
+        #[arg(long, value_parser = parse_codec, default_value = "snappy")]
+        codec: parquet::basic::Compression,
+        /// The same seed writes the same file
+        #[arg(long, default_value_t = GenerateOptions::default().seed)]
+        seed: u64,
+    },
+    /// Print every schema node with its type and repetition
+    Schema {
+        /// Path to the parquet file
+        path: String,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Plain)]
+        output: OutputFormat,
+    },
+    /// Print the file metadata: version, writer, rows, sizes, codecs and
+    /// the key-value metadata
+    Meta {
+        /// Path to the parquet file
+        path: String,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Plain)]
+        output: OutputFormat,
+    },
+    /// Print each row group's rows, sizes and byte range
+    RowGroups {
+        /// Path to the parquet file
+        path: String,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Plain)]
+        output: OutputFormat,
+    },
+    /// Print every page of every column chunk
+    Pages {
+        /// Path to the parquet file
+        path: String,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Plain)]
+        output: OutputFormat,
+    },
+    /// Print each column's sizes, null count and min/max over the file
+    Stats {
+        /// Path to the parquet file
+        path: String,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Plain)]
+        output: OutputFormat,
+    },
+    /// Print a completion script for the shell, e.g.
+    /// `parqeye completions zsh > ~/.zfunc/_parqeye`
+    Completions { shell: clap_complete::Shell },
+}
+
+/// How `schema`, `meta`, `row-groups`, `pages` and `stats` print their rows.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned columns for reading
+    Plain,
+    Csv,
+    Tsv,
+    /// An array of objects, one per row
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ProfileFormat {
+    Text,
+    Json,
+    Html,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum DuplicatesFormat {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum TimestampsFormat {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Utf8Format {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum BenchFormat {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum CodecsFormat {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum DiffFormat {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ExportFormatArg {
+    Csv,
+    /// An array of objects
+    Json,
+    /// One object per line
+    Jsonl,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
 }
 
 fn main() {
     let opts = Opts::parse();
-    if let Err(e) = run(&opts.path) {
+    match opts.command.unwrap_or(Commands::View(opts.view)) {
+        Commands::Summary { paths } => {
+            if !summary(&expand(&paths)) {
+                std::process::exit(1);
+            }
+        }
+        Commands::Check { paths } => {
+            let check = SchemaCheck::run(&expand(&paths));
+            println!("{check}");
+            if !check.is_consistent() {
+                std::process::exit(1);
+            }
+        }
+        Commands::Rows { paths } => {
+            if !reconcile_rows(&expand(&paths)) {
+                std::process::exit(1);
+            }
+        }
+        Commands::Offsets { paths } => {
+            if !check_offsets(&expand(&paths)) {
+                std::process::exit(1);
+            }
+        }
+        Commands::Profile {
+            path,
+            sample,
+            full,
+            top,
+            format,
+            output,
+        } => {
+            let options = ProfileOptions {
+                sample_rows: if full { usize::MAX } else { sample.max(1) },
+                top_k: top,
+            };
+            if let Err(e) = profile(&path, options, format, output.as_deref()) {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        Commands::Dups {
+            path,
+            key,
+            sample,
+            top,
+            format,
+        } => {
+            let options = DuplicateOptions {
+                sample_rows: sample.max(1),
+                key,
+                top_k: top,
+            };
+            match duplicates(&path, &options, format) {
+                Ok(true) => {}
+                Ok(false) => std::process::exit(1),
+                Err(e) => {
+                    eprintln!("Error: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Timestamps { path, format } => match timestamps(&path, format) {
+            Ok(true) => {}
+            Ok(false) => std::process::exit(1),
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        },
+        Commands::Utf8 { path, format } => match utf8(&path, format) {
+            Ok(true) => {}
+            Ok(false) => std::process::exit(1),
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        },
+        Commands::Bench { path, format } => {
+            if let Err(e) = bench(&path, format) {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        Commands::Codecs {
+            path,
+            level,
+            format,
+        } => {
+            if let Err(e) = codecs(&path, level, format) {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        Commands::Report {
+            path,
+            output,
+            format,
+        } => {
+            if let Err(e) = report(&path, format, output.as_deref()) {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        Commands::Exec { script, path } => {
+            if let Err(e) = exec(&script, &path) {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        Commands::Rewrite {
+            path,
+            row_group_size,
+            select,
+            drop_columns,
+            output,
+        } => {
+            let columns = if !select.is_empty() {
+                ColumnProjection::Select(select)
+            } else if !drop_columns.is_empty() {
+                ColumnProjection::Drop(drop_columns)
+            } else {
+                ColumnProjection::All
+            };
+            if let Err(e) = rewrite(&path, row_group_size, columns, output.as_deref()) {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        Commands::Extract {
+            row_group,
+            path,
+            output,
+        } => match extract_row_groups(&path, &output, row_group) {
+            Ok(extraction) => print!("{extraction}"),
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        },
+        Commands::Generate {
+            output,
+            columns,
+            rows,
+            row_group_size,
+            codec,
+            seed,
+        } => {
+            let options = GenerateOptions {
+                rows,
+                row_group_size,
+                codec,
+                seed,
+            };
+            match generate(&output, &columns, &options) {
+                Ok(generated) => print!("{generated}"),
+                Err(e) => {
+                    eprintln!("Error: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Schema { path, output } => print_view(&path, View::schema, output),
+        Commands::RowGroups { path, output } => print_view(&path, View::row_groups, output),
+        Commands::Pages { path, output } => print_view(&path, View::pages, output),
+        Commands::Stats { path, output } => print_view(&path, View::stats, output),
+        Commands::View(view) => {
+            let path = view.path.expect("clap requires a path");
+            let position = StartPosition {
+                tab: view.tab,
+                row_group: view.row_group,
+                row: view.row,
+                column: view.column,
+            };
+            let mode = RenderMode {
+                no_color: view.no_color
+                    || std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()),
+                ascii: view.ascii,
+            };
+            if let Err(e) = run(&path, &position, view.preset.as_deref(), mode, view.deletes) {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        Commands::Meta { path, output } => print_view(&path, View::metadata, output),
+        Commands::Diff {
+            left,
+            right,
+            format,
+        } => match diff(&left, &right, format) {
+            Ok(true) => {}
+            Ok(false) => std::process::exit(1),
+            Err(e) => {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        },
+        Commands::Export {
+            path,
+            format,
+            select,
+            drop_columns,
+            limit,
+            output,
+        } => {
+            let columns = if !select.is_empty() {
+                ColumnProjection::Select(select)
+            } else if !drop_columns.is_empty() {
+                ColumnProjection::Drop(drop_columns)
+            } else {
+                ColumnProjection::All
+            };
+            let options = ExportOptions { columns, limit };
+            if let Err(e) = export_rows(&path, format, &options, output.as_deref()) {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+        }
+        Commands::Completions { shell } => {
+            clap_complete::generate(
+                shell,
+                &mut Opts::command(),
+                "parqeye",
+                &mut std::io::stdout(),
+            );
+        }
+    }
+}
+
+fn expand(paths: &[String]) -> Vec<String> {
+    expand_paths(paths).unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    })
+}
+
+/// Print one report per file; returns false if any count disagrees.
+fn reconcile_rows(paths: &[String]) -> bool {
+    let mut ok = true;
+    for path in paths {
+        match RowCountReport::from_file(path) {
+            Ok(report) => {
+                ok &= report.mismatches().is_empty();
+                println!("{report}");
+            }
+            Err(e) => {
+                ok = false;
+                println!("{path}: error: {}", e.to_string().replace('\n', " "));
+            }
+        }
+    }
+    ok
+}
+
+/// Print one report per file; returns false if any has a problem.
+fn check_offsets(paths: &[String]) -> bool {
+    let mut ok = true;
+    for path in paths {
+        match OffsetReport::from_file(path) {
+            Ok(report) => {
+                ok &= report.problems.is_empty();
+                println!("{report}");
+            }
+            Err(e) => {
+                ok = false;
+                println!("{path}: error: {}", e.to_string().replace('\n', " "));
+            }
+        }
+    }
+    ok
+}
+
+fn profile(
+    path: &str,
+    options: ProfileOptions,
+    format: ProfileFormat,
+    output: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let profile = FileProfile::from_file(path, options)?;
+    let text = match format {
+        ProfileFormat::Text => profile.to_string(),
+        ProfileFormat::Json => serde_json::to_string_pretty(&profile)? + "\n",
+        ProfileFormat::Html => profile.to_html(),
+    };
+    match output {
+        Some(output) => std::fs::write(output, text)?,
+        None => print!("{text}"),
+    }
+    Ok(())
+}
+
+/// Print the duplicate report; returns false if any key repeats.
+fn duplicates(
+    path: &str,
+    options: &DuplicateOptions,
+    format: DuplicatesFormat,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let report = DuplicateReport::from_file(path, options)?;
+    match format {
+        DuplicatesFormat::Text => print!("{report}"),
+        DuplicatesFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+    Ok(report.duplicate_rows == 0)
+}
+
+/// Print the temporal columns; returns false if their conventions are
+/// mixed.
+fn timestamps(path: &str, format: TimestampsFormat) -> Result<bool, Box<dyn std::error::Error>> {
+    let audit = TemporalAudit::from_file(path)?;
+    match format {
+        TimestampsFormat::Text => print!("{audit}"),
+        TimestampsFormat::Json => println!("{}", serde_json::to_string_pretty(&audit)?),
+    }
+    Ok(audit.mixed.is_empty())
+}
+
+/// Print the UTF-8 check; returns false if any value is invalid.
+fn utf8(path: &str, format: Utf8Format) -> Result<bool, Box<dyn std::error::Error>> {
+    let report = Utf8Report::from_file(path)?;
+    match format {
+        Utf8Format::Text => print!("{report}"),
+        Utf8Format::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+    Ok(report.invalid_values() == 0)
+}
+
+fn bench(path: &str, format: BenchFormat) -> Result<(), Box<dyn std::error::Error>> {
+    let report = BenchReport::from_file(path)?;
+    match format {
+        BenchFormat::Text => print!("{report}"),
+        BenchFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+    Ok(())
+}
+
+fn codecs(
+    path: &str,
+    level: Option<u32>,
+    format: CodecsFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let report = CodecReport::from_file(path, level)?;
+    match format {
+        CodecsFormat::Text => print!("{report}"),
+        CodecsFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+    Ok(())
+}
+
+/// Print the comparison; returns false if the schemas differ.
+fn diff(left: &str, right: &str, format: DiffFormat) -> Result<bool, Box<dyn std::error::Error>> {
+    let diff = FileDiff::from_files(left, right)?;
+    match format {
+        DiffFormat::Text => println!("{diff}"),
+        DiffFormat::Json => println!("{}", serde_json::to_string_pretty(&diff)?),
+    }
+    Ok(diff.same_schema())
+}
+
+fn export_rows(
+    path: &str,
+    format: ExportFormatArg,
+    options: &ExportOptions,
+    output: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let format = match format {
+        ExportFormatArg::Csv => ExportFormat::Csv,
+        ExportFormatArg::Json => ExportFormat::Json,
+        ExportFormatArg::Jsonl => ExportFormat::Jsonl,
+    };
+    match output {
+        Some(output) => {
+            let file = std::io::BufWriter::new(std::fs::File::create(output)?);
+            let rows = export(path, file, format, options)?;
+            println!("wrote {} rows to {output}", commas(rows as u64));
+        }
+        None => {
+            export(path, std::io::stdout().lock(), format, options)?;
+        }
+    }
+    Ok(())
+}
+
+fn print_view(path: &str, view: fn(&ParquetCtx) -> View, output: OutputFormat) {
+    let ctx = ParquetCtx::from_file(path).unwrap_or_else(|e| {
         eprintln!("Error: {e}");
         std::process::exit(1);
+    });
+    let view = view(&ctx);
+    match output {
+        OutputFormat::Plain => print!("{}", view.to_plain()),
+        OutputFormat::Csv => print!("{}", view.to_csv()),
+        OutputFormat::Tsv => print!("{}", view.to_tsv()),
+        OutputFormat::Json => println!("{}", view.to_json()),
+    }
+}
+
+fn report(
+    path: &str,
+    format: Option<ReportFormat>,
+    output: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = AppConfig::load()?;
+    let ctx = ParquetCtx::from_file(path)?;
+    let analyzers = Analyzers::default().with_external(&config.analyzers);
+    let report = Report::with_analyzers(&ctx, &analyzers);
+    let markdown = output.is_some_and(|o| o.ends_with(".md") || o.ends_with(".markdown"));
+    let text = match format {
+        Some(ReportFormat::Markdown) => report.to_markdown(),
+        Some(ReportFormat::Html) => report.to_html(),
+        None if markdown => report.to_markdown(),
+        None => report.to_html(),
+    };
+    match output {
+        Some(output) => std::fs::write(output, text)?,
+        None => print!("{text}"),
+    }
+    Ok(())
+}
+
+/// Run the script's steps, printing what each wrote.
+fn exec(script: &str, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let script = Script::load(std::path::Path::new(script))?;
+    let config = AppConfig::load()?;
+    let analyzers = Analyzers::default().with_external(&config.analyzers);
+    for line in script.run(path, &analyzers)? {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+/// Print the row group plan and, given an output, carry it out.
+fn rewrite(
+    path: &str,
+    target: Option<RowGroupTarget>,
+    columns: ColumnProjection,
+    output: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (reader, _) = open_reader(path)?;
+    let plan = RowGroupPlan::new(path, &reader.metadata_ptr(), target, columns)?;
+    print!("{plan}");
+    if let Some(output) = output {
+        let rows = plan.execute(output)?;
+        println!(
+            "\nwrote {} rows in {} row groups to {output}",
+            commas(rows),
+            plan.planned.len()
+        );
     }
+    Ok(())
 }
 
-fn run(path: &str) -> Result<(), FileIOError> {
-    let file_info = ParquetCtx::from_file(path)?;
+/// Print the summary table; returns false if any file could not be read.
+fn summary(paths: &[String]) -> bool {
+    let summaries: Vec<_> = paths
+        .iter()
+        .map(|path| (path.clone(), FileSummary::from_file(path)))
+        .collect();
+    print!("{}", format_summary_table(&summaries));
+    summaries.iter().all(|(_, s)| s.is_ok())
+}
+
+fn run(
+    path: &str,
+    position: &StartPosition,
+    preset: Option<&str>,
+    mode: RenderMode,
+    deletes: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut warning = None;
+    // Pane sizes aren't written back over a config that failed to load, so
+    // the file stays as the user left it to be fixed.
+    let mut config_loaded = true;
+    let config = AppConfig::load().unwrap_or_else(|e| {
+        warning = Some(format!("{e}; using default settings"));
+        config_loaded = false;
+        AppConfig::default()
+    });
+    set_byte_units(config.display.byte_units);
+    set_relative_times(config.display.relative_times);
+    set_ratio_thresholds(config.display.poor_ratio, config.display.good_ratio);
+    let preset_commands = preset.map(|name| config.preset(name)).transpose()?;
+    let mut file_info = ParquetCtx::from_file_with(path, &config.display.preview())?;
+    if deletes {
+        let rows = file_info.metadata.num_rows as u64;
+        match DeleteInfo::for_iceberg_file(path, rows) {
+            Ok(Some(info)) => file_info.metadata = file_info.metadata.with_deletes(info),
+            Ok(None) => {
+                warning.get_or_insert("--deletes: not a data file of an Iceberg table".to_string());
+            }
+            Err(e) => {
+                warning.get_or_insert(format!("--deletes: {e}"));
+            }
+        }
+    }
+    // A position on the command line wins over the saved session, so check
+    // it before taking over the terminal.
+    let macros = Macros::load().unwrap_or_else(|e| {
+        warning.get_or_insert(format!("{e}; starting without macros"));
+        Macros::default()
+    });
+    let app = App::new(&file_info)
+        .with_config(config)
+        .with_render_mode(mode)
+        .with_macros(macros.clone());
+    let app = if position.is_empty() {
+        app
+    } else {
+        app.with_position(position)?
+    };
+    let mut app = match (preset, preset_commands) {
+        (Some(name), Some(commands)) => app.with_preset(name, commands),
+        _ => app,
+    };
 
     let mut terminal = ratatui::init();
-    let mut app = App::new(&file_info);
+    // Mouse support is best-effort; keyboard navigation works without it.
+    if let Err(e) = crossterm::execute!(std::io::stdout(), EnableMouseCapture) {
+        warning.get_or_insert(format!("mouse support unavailable: {e}"));
+    }
+    let problems = file_info.problems();
+    if !problems.is_empty() {
+        // What the file is missing matters most, so it comes first.
+        let problems = format!("could not read everything: {}", problems.join("; "));
+        warning = Some(match warning {
+            Some(earlier) => format!("{problems}; {earlier}"),
+            None => problems,
+        });
+    }
+    // A preset picks its own tab, so it starts without the saved session.
+    if position.is_empty() && preset.is_none() {
+        let session = Session::load(path).unwrap_or_else(|e| {
+            warning.get_or_insert(format!("{e}; starting a new session"));
+            None
+        });
+        if let Some(session) = session {
+            app = app.with_session(session);
+        }
+    }
+    if let Some(warning) = warning {
+        app = app.with_status(StatusMessage::error(warning));
+    }
     let result = app.run(&mut terminal);
+    let _ = crossterm::execute!(std::io::stdout(), DisableMouseCapture);
     ratatui::restore();
 
-    result.map_err(|e| FileIOError::Io { source: e })
+    if config_loaded && let Err(e) = app.persist_config() {
+        eprintln!("Warning: {e}");
+    }
+    if let Err(e) = app.session().save(path) {
+        eprintln!("Warning: {e}");
+    }
+    if app.macros() != &macros
+        && let Err(e) = app.macros().save()
+    {
+        eprintln!("Warning: {e}");
+    }
+
+    Ok(result?)
 }
@@ -0,0 +1,180 @@
+use ratatui::layout::{Position, Rect};
+
+/// What a scrollbar drag should move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollTarget {
+    SchemaTree,
+}
+
+/// A vertical scrollbar drawn during the last frame.
+#[derive(Debug, Clone, Copy)]
+pub struct ScrollbarRegion {
+    pub area: Rect,
+    pub total_items: usize,
+    pub visible_items: usize,
+    pub target: ScrollTarget,
+}
+
+impl ScrollbarRegion {
+    /// Scroll offset that puts the thumb under the given terminal row.
+    pub fn offset_at(&self, row: u16) -> usize {
+        let max_offset = self.total_items.saturating_sub(self.visible_items);
+        let track = self.area.height.saturating_sub(1) as usize;
+        if track == 0 {
+            return 0;
+        }
+        let pos = row.saturating_sub(self.area.y).min(self.area.height - 1) as usize;
+        (pos * max_offset + track / 2) / track
+    }
+}
+
+/// The border between the schema tree and the table next to it.
+#[derive(Debug, Clone, Copy)]
+pub struct PaneDivider {
+    /// The whole body both panes share.
+    pub body: Rect,
+    /// Column the divider is drawn in.
+    pub x: u16,
+}
+
+impl PaneDivider {
+    /// Tree width, as a percentage of the body, that puts the divider at
+    /// `column`.
+    pub fn percent_at(&self, column: u16) -> u16 {
+        let width = column.saturating_sub(self.body.x) as u32 + 1;
+        (width * 100 / self.body.width.max(1) as u32) as u16
+    }
+}
+
+/// A list of rows drawn one per line, where line `i` of `area` shows item
+/// `first_index + i`.
+#[derive(Debug, Clone, Copy)]
+pub struct ListRegion {
+    pub area: Rect,
+    pub first_index: usize,
+}
+
+impl ListRegion {
+    fn index_at(&self, column: u16, row: u16) -> Option<usize> {
+        self.area
+            .contains(Position::new(column, row))
+            .then(|| self.first_index + (row - self.area.y) as usize)
+    }
+}
+
+/// Hit-testing metadata recorded while rendering a frame, so mouse events
+/// can be mapped back to the element under the cursor.
+#[derive(Debug, Default, Clone)]
+pub struct MouseRegions {
+    /// Tab titles in the header, with the tab index they select.
+    pub tabs: Vec<(Rect, usize)>,
+    /// Rows of the schema tree / stats table, indexed into `FileSchema::columns`.
+    pub schema_rows: Vec<ListRegion>,
+    /// Rows of the data preview, indexed into the sample rows.
+    pub data_rows: Option<ListRegion>,
+    pub scrollbars: Vec<ScrollbarRegion>,
+    pub pane_divider: Option<PaneDivider>,
+}
+
+impl MouseRegions {
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn tab_at(&self, column: u16, row: u16) -> Option<usize> {
+        self.tabs
+            .iter()
+            .find(|(area, _)| area.contains(Position::new(column, row)))
+            .map(|(_, idx)| *idx)
+    }
+
+    pub fn schema_index_at(&self, column: u16, row: u16) -> Option<usize> {
+        self.schema_rows
+            .iter()
+            .find_map(|region| region.index_at(column, row))
+    }
+
+    pub fn data_row_at(&self, column: u16, row: u16) -> Option<usize> {
+        self.data_rows
+            .as_ref()
+            .and_then(|region| region.index_at(column, row))
+    }
+
+    pub fn scrollbar_at(&self, column: u16, row: u16) -> Option<ScrollbarRegion> {
+        self.scrollbars
+            .iter()
+            .find(|sb| sb.area.contains(Position::new(column, row)))
+            .copied()
+    }
+
+    pub fn pane_divider_at(&self, column: u16, row: u16) -> Option<PaneDivider> {
+        self.pane_divider
+            .filter(|d| d.x == column && d.body.contains(Position::new(column, row)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tab_at() {
+        let regions = MouseRegions {
+            tabs: vec![(Rect::new(1, 1, 5, 1), 0), (Rect::new(7, 1, 6, 1), 1)],
+            ..Default::default()
+        };
+        assert_eq!(regions.tab_at(2, 1), Some(0));
+        assert_eq!(regions.tab_at(12, 1), Some(1));
+        assert_eq!(regions.tab_at(6, 1), None);
+        assert_eq!(regions.tab_at(2, 2), None);
+    }
+
+    #[test]
+    fn test_list_rows_account_for_scroll() {
+        let regions = MouseRegions {
+            schema_rows: vec![ListRegion {
+                area: Rect::new(0, 4, 20, 10),
+                first_index: 7,
+            }],
+            ..Default::default()
+        };
+        assert_eq!(regions.schema_index_at(3, 4), Some(7));
+        assert_eq!(regions.schema_index_at(3, 6), Some(9));
+        assert_eq!(regions.schema_index_at(3, 3), None);
+        assert_eq!(regions.schema_index_at(25, 6), None);
+    }
+
+    #[test]
+    fn test_pane_divider_percent() {
+        let divider = PaneDivider {
+            body: Rect::new(0, 3, 100, 20),
+            x: 29,
+        };
+        assert_eq!(divider.percent_at(29), 30);
+        assert_eq!(divider.percent_at(49), 50);
+
+        let regions = MouseRegions {
+            pane_divider: Some(divider),
+            ..Default::default()
+        };
+        assert!(regions.pane_divider_at(29, 10).is_some());
+        assert!(regions.pane_divider_at(30, 10).is_none());
+        assert!(regions.pane_divider_at(29, 1).is_none());
+    }
+
+    #[test]
+    fn test_scrollbar_offset_spans_full_range() {
+        let sb = ScrollbarRegion {
+            area: Rect::new(30, 2, 1, 11),
+            total_items: 110,
+            visible_items: 10,
+            target: ScrollTarget::SchemaTree,
+        };
+        assert_eq!(sb.offset_at(2), 0);
+        assert_eq!(sb.offset_at(12), 100);
+        assert_eq!(sb.offset_at(7), 50);
+        // Dragging past either end clamps.
+        assert_eq!(sb.offset_at(0), 0);
+        assert_eq!(sb.offset_at(40), 100);
+    }
+}
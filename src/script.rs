@@ -0,0 +1,281 @@
+use serde::Deserialize;
+use std::fs;
+use std::io::BufWriter;
+use std::path::Path;
+
+use crate::file::analyzer::Analyzers;
+use crate::file::export::{ExportFormat, ExportOptions, export};
+use crate::file::parquet_ctx::ParquetCtx;
+use crate::file::profile::{FileProfile, ProfileOptions};
+use crate::file::report::Report;
+use crate::file::rewrite::ColumnProjection;
+use crate::file::utils::commas;
+
+/// Steps run one after another on a file by `parqeye exec`, without a
+/// terminal: the batch counterpart of the app.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Script {
+    #[serde(default)]
+    pub steps: Vec<Step>,
+}
+
+/// One step of a script. `select` and `drop` choose the columns the steps
+/// after them profile and export; outputs are written as their extension
+/// says.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "run", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Step {
+    /// Only these columns from here on; none selects them all again.
+    Select {
+        #[serde(default)]
+        columns: Vec<String>,
+    },
+    /// Leave out these columns from here on.
+    Drop { columns: Vec<String> },
+    /// Profile the columns to `.json`, `.html` or text.
+    Profile {
+        output: String,
+        /// Rows to read per column; defaults to the `profile` command's.
+        sample: Option<usize>,
+        top: Option<usize>,
+    },
+    /// Write the rows as `.json`, `.jsonl` or CSV.
+    Export {
+        output: String,
+        limit: Option<usize>,
+    },
+    /// Write the report of the whole file as `.md` or HTML.
+    Report { output: String },
+}
+
+impl Script {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        Self::parse(&contents).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let script: Script = toml::from_str(contents).map_err(|e| e.to_string())?;
+        if script.steps.is_empty() {
+            return Err("no steps to run".to_string());
+        }
+        Ok(script)
+    }
+
+    /// Run the steps on `path`, stopping at the first that fails. Returns
+    /// one line per step about what it wrote.
+    pub fn run(&self, path: &str, analyzers: &Analyzers) -> Result<Vec<String>, String> {
+        let mut columns = ColumnProjection::All;
+        let mut done = Vec::new();
+        for (i, step) in self.steps.iter().enumerate() {
+            let line = step
+                .run(path, &mut columns, analyzers)
+                .map_err(|e| format!("step {}: {e}", i + 1))?;
+            done.push(format!("step {}: {line}", i + 1));
+        }
+        Ok(done)
+    }
+}
+
+impl Step {
+    fn run(
+        &self,
+        path: &str,
+        columns: &mut ColumnProjection,
+        analyzers: &Analyzers,
+    ) -> Result<String, String> {
+        match self {
+            Step::Select { columns: names } if names.is_empty() => {
+                *columns = ColumnProjection::All;
+                Ok("selected every column".to_string())
+            }
+            Step::Select { columns: names } => {
+                *columns = ColumnProjection::Select(names.clone());
+                Ok(format!("selected {}", names.join(", ")))
+            }
+            Step::Drop { columns: names } => {
+                *columns = ColumnProjection::Drop(names.clone());
+                Ok(format!("dropped {}", names.join(", ")))
+            }
+            Step::Profile {
+                output,
+                sample,
+                top,
+            } => {
+                let defaults = ProfileOptions::default();
+                let options = ProfileOptions {
+                    sample_rows: sample.unwrap_or(defaults.sample_rows).max(1),
+                    top_k: top.unwrap_or(defaults.top_k),
+                };
+                let mut profile =
+                    FileProfile::from_file(path, options).map_err(|e| e.to_string())?;
+                let kept = |column: &str| match &*columns {
+                    ColumnProjection::All => true,
+                    ColumnProjection::Select(names) => names.iter().any(|n| covers(n, column)),
+                    ColumnProjection::Drop(names) => !names.iter().any(|n| covers(n, column)),
+                };
+                profile.columns.retain(|column| kept(&column.column));
+                profile.nested.retain(|nested| kept(&nested.column));
+                if profile.columns.is_empty() {
+                    return Err("no columns left to profile".to_string());
+                }
+                let text = if output.ends_with(".json") {
+                    serde_json::to_string_pretty(&profile).map_err(|e| e.to_string())? + "\n"
+                } else if output.ends_with(".html") || output.ends_with(".htm") {
+                    profile.to_html()
+                } else {
+                    profile.to_string()
+                };
+                fs::write(output, text).map_err(|e| format!("{output}: {e}"))?;
+                Ok(format!(
+                    "wrote the profile of {} columns to {output}",
+                    profile.columns.len()
+                ))
+            }
+            Step::Export { output, limit } => {
+                let format = if output.ends_with(".jsonl") || output.ends_with(".ndjson") {
+                    ExportFormat::Jsonl
+                } else if output.ends_with(".json") {
+                    ExportFormat::Json
+                } else {
+                    ExportFormat::Csv
+                };
+                let options = ExportOptions {
+                    columns: columns.clone(),
+                    limit: *limit,
+                };
+                let file = fs::File::create(output).map_err(|e| format!("{output}: {e}"))?;
+                let rows = export(path, BufWriter::new(file), format, &options)?;
+                Ok(format!("wrote {} rows to {output}", commas(rows as u64)))
+            }
+            Step::Report { output } => {
+                let ctx = ParquetCtx::from_file(path).map_err(|e| e.to_string())?;
+                let report = Report::with_analyzers(&ctx, analyzers);
+                let text = if output.ends_with(".md") || output.ends_with(".markdown") {
+                    report.to_markdown()
+                } else {
+                    report.to_html()
+                };
+                fs::write(output, text).map_err(|e| format!("{output}: {e}"))?;
+                Ok(format!("wrote the report to {output}"))
+            }
+        }
+    }
+}
+
+/// Whether the column name `name` is `column` or a group holding it.
+fn covers(name: &str, column: &str) -> bool {
+    column == name
+        || column
+            .strip_prefix(name)
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::generate::{ColumnSpec, GenerateOptions, generate};
+
+    #[test]
+    fn test_parse() {
+        let script = Script::parse(
+            r#"
+            [[steps]]
+            run = "select"
+            columns = ["id"]
+
+            [[steps]]
+            run = "export"
+            output = "rows.csv"
+            limit = 10
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            script.steps,
+            vec![
+                Step::Select {
+                    columns: vec!["id".to_string()]
+                },
+                Step::Export {
+                    output: "rows.csv".to_string(),
+                    limit: Some(10)
+                },
+            ]
+        );
+        assert!(Script::parse("").is_err());
+        assert!(Script::parse("[[steps]]\nrun = \"sing\"\n").is_err());
+        assert!(Script::parse("[[steps]]\nrun = \"report\"\nouput = \"r.md\"\n").is_err());
+    }
+
+    #[test]
+    fn test_run() {
+        let dir = std::env::temp_dir().join(format!("parqeye-script-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("data.parquet").display().to_string();
+        let columns: Vec<ColumnSpec> = ["id:int64", "name:string"]
+            .iter()
+            .map(|spec| spec.parse().unwrap())
+            .collect();
+        let options = GenerateOptions {
+            rows: 100,
+            ..Default::default()
+        };
+        generate(&file, &columns, &options).unwrap();
+
+        let out = |name: &str| dir.join(name).display().to_string();
+        let script = Script {
+            steps: vec![
+                Step::Select {
+                    columns: vec!["id".to_string()],
+                },
+                Step::Profile {
+                    output: out("profile.json"),
+                    sample: None,
+                    top: None,
+                },
+                Step::Export {
+                    output: out("rows.csv"),
+                    limit: Some(3),
+                },
+                Step::Report {
+                    output: out("report.md"),
+                },
+            ],
+        };
+        let done = script.run(&file, &Analyzers::default()).unwrap();
+        assert_eq!(done.len(), 4);
+        assert_eq!(
+            done[2],
+            format!("step 3: wrote 3 rows to {}", out("rows.csv"))
+        );
+
+        let profile = fs::read_to_string(out("profile.json")).unwrap();
+        assert!(profile.contains("\"id\""));
+        assert!(!profile.contains("\"name\""));
+        let rows = fs::read_to_string(out("rows.csv")).unwrap();
+        assert_eq!(rows.lines().count(), 4);
+        assert_eq!(rows.lines().next(), Some("id"));
+        assert!(
+            fs::read_to_string(out("report.md"))
+                .unwrap()
+                .starts_with('#')
+        );
+
+        let failing = Script {
+            steps: vec![
+                Step::Drop {
+                    columns: vec!["missing".to_string()],
+                },
+                Step::Export {
+                    output: out("none.csv"),
+                    limit: None,
+                },
+            ],
+        };
+        let err = failing.run(&file, &Analyzers::default()).unwrap_err();
+        assert!(err.starts_with("step 2:"), "{err}");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
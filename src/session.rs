@@ -0,0 +1,154 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::ConfigError;
+
+/// Where a file was left when parqeye last closed it, restored the next time
+/// it is opened. Saved per file under the config directory.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    /// Canonical path of the file, to tell apart files whose paths hash alike.
+    pub file: String,
+    pub tab: usize,
+    pub vertical_offset: usize,
+    pub horizontal_offset: usize,
+    /// Row group shown in the Row Groups tab.
+    pub row_group: usize,
+    pub tree_scroll_offset: usize,
+    pub data_vertical_scroll: usize,
+    /// First row of the rows loaded in the Visualize tab.
+    pub first_row: usize,
+    pub selected_group: Option<usize>,
+    pub collapsed_groups: BTreeSet<usize>,
+    pub schema_flat: bool,
+    pub show_field_ids: bool,
+    /// Pattern of the `:columns` filter.
+    pub column_filter: Option<String>,
+    /// Schema nodes of the bookmarked columns.
+    pub bookmarks: BTreeSet<usize>,
+    pub bookmarked_only: bool,
+}
+
+/// 64-bit FNV-1a, stable across builds unlike `DefaultHasher`.
+fn path_hash(path: &str) -> u64 {
+    path.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// `file_path` made absolute, so a file opened from different directories
+/// shares one session.
+pub fn canonical_path(file_path: &str) -> String {
+    fs::canonicalize(file_path)
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|_| file_path.to_string())
+}
+
+impl Session {
+    /// Directory of the session files, e.g. `~/.config/parqeye/sessions`.
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("parqeye").join("sessions"))
+    }
+
+    /// Session file for `file` (a canonical path) inside `dir`.
+    pub fn path_in(dir: &Path, file: &str) -> PathBuf {
+        dir.join(format!("{:016x}.toml", path_hash(file)))
+    }
+
+    /// The saved session of `file_path`, if there is one.
+    pub fn load(file_path: &str) -> Result<Option<Self>, ConfigError> {
+        match Self::default_dir() {
+            Some(dir) => Self::load_from(&dir, file_path),
+            None => Ok(None),
+        }
+    }
+
+    pub fn load_from(dir: &Path, file_path: &str) -> Result<Option<Self>, ConfigError> {
+        let file = canonical_path(file_path);
+        let path = Self::path_in(dir, &file);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&path).map_err(|source| ConfigError::Read {
+            path: path.clone(),
+            source,
+        })?;
+        let session: Session = toml::from_str(&contents).map_err(|e| ConfigError::Parse {
+            path: path.clone(),
+            details: e.to_string(),
+        })?;
+        Ok((session.file == file).then_some(session))
+    }
+
+    /// Save the session of `file_path` to the default location.
+    pub fn save(&self, file_path: &str) -> Result<(), ConfigError> {
+        match Self::default_dir() {
+            Some(dir) => self.save_in(&dir, file_path),
+            None => Ok(()),
+        }
+    }
+
+    pub fn save_in(&self, dir: &Path, file_path: &str) -> Result<(), ConfigError> {
+        let file = canonical_path(file_path);
+        let path = Self::path_in(dir, &file);
+        let write_err = |source| ConfigError::Write {
+            path: path.clone(),
+            source,
+        };
+        fs::create_dir_all(dir).map_err(write_err)?;
+        let session = Session {
+            file,
+            ..self.clone()
+        };
+        let contents = toml::to_string_pretty(&session).map_err(|e| ConfigError::Parse {
+            path: path.clone(),
+            details: e.to_string(),
+        })?;
+        fs::write(&path, contents).map_err(write_err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_data_path;
+
+    #[test]
+    fn test_path_hash_is_stable() {
+        assert_eq!(path_hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(path_hash("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(path_hash("/data/a.parquet"), path_hash("/data/b.parquet"));
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = std::env::temp_dir().join(format!("parqeye-session-{}", std::process::id()));
+        let file = test_data_path("alltypes_plain.parquet");
+        assert_eq!(Session::load_from(&dir, &file).unwrap(), None);
+
+        let session = Session {
+            tab: 2,
+            vertical_offset: 4,
+            collapsed_groups: BTreeSet::from([3]),
+            ..Session::default()
+        };
+        session.save_in(&dir, &file).unwrap();
+        let loaded = Session::load_from(&dir, &file).unwrap().unwrap();
+        assert_eq!(loaded.file, canonical_path(&file));
+        assert_eq!(
+            loaded,
+            Session {
+                file: loaded.file.clone(),
+                ..session
+            }
+        );
+
+        // Other files have sessions of their own.
+        let other = test_data_path("nulls.snappy.parquet");
+        assert_eq!(Session::load_from(&dir, &other).unwrap(), None);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
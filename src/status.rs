@@ -0,0 +1,78 @@
+use std::time::{Duration, Instant};
+
+/// How long a confirmation stays in the status line.
+pub const INFO_TIMEOUT: Duration = Duration::from_secs(3);
+/// Errors stay a little longer so they can be read.
+pub const ERROR_TIMEOUT: Duration = Duration::from_secs(6);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusKind {
+    /// Transient confirmation, e.g. "filter cleared".
+    Info,
+    /// Non-fatal error; the app keeps running.
+    Error,
+    /// Work in progress; stays until replaced or cleared.
+    Progress,
+}
+
+/// A message shown in the footer until it times out or is replaced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusMessage {
+    pub kind: StatusKind,
+    pub text: String,
+    expires_at: Option<Instant>,
+}
+
+impl StatusMessage {
+    pub fn info(text: impl Into<String>) -> Self {
+        Self::new(StatusKind::Info, text, Some(INFO_TIMEOUT))
+    }
+
+    pub fn error(text: impl Into<String>) -> Self {
+        Self::new(StatusKind::Error, text, Some(ERROR_TIMEOUT))
+    }
+
+    pub fn progress(text: impl Into<String>) -> Self {
+        Self::new(StatusKind::Progress, text, None)
+    }
+
+    pub fn new(kind: StatusKind, text: impl Into<String>, timeout: Option<Duration>) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+            expires_at: timeout.map(|t| Instant::now() + t),
+        }
+    }
+
+    /// When the message should disappear, if it times out at all.
+    pub fn expires_at(&self) -> Option<Instant> {
+        self.expires_at
+    }
+
+    pub fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|at| now >= at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages_expire_after_their_timeout() {
+        let now = Instant::now();
+        let info = StatusMessage::info("exported");
+        assert!(!info.is_expired(now));
+        assert!(info.is_expired(now + INFO_TIMEOUT + Duration::from_millis(10)));
+
+        let error = StatusMessage::error("failed");
+        assert!(!error.is_expired(now + INFO_TIMEOUT));
+    }
+
+    #[test]
+    fn test_progress_does_not_expire() {
+        let progress = StatusMessage::progress("scanning pages");
+        assert_eq!(progress.expires_at(), None);
+        assert!(!progress.is_expired(Instant::now() + Duration::from_secs(3600)));
+    }
+}
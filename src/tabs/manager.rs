@@ -1,7 +1,9 @@
 use crate::file::Renderable;
+use crate::file::schema::SchemaOutline;
+use crate::theme::Theme;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Stylize;
+use ratatui::style::{Modifier, Style, Stylize};
 use ratatui::text::Line;
 use ratatui::widgets::Tabs;
 use ratatui::widgets::Widget;
@@ -20,6 +22,7 @@ pub struct TabManager {
 
 impl TabManager {
     pub fn new(
+        schema_outline: SchemaOutline,
         num_columns: usize,
         num_row_groups: usize,
         sample_data_rows: usize,
@@ -35,11 +38,16 @@ impl TabManager {
                 Box::new(
                     MetadataTab::new().with_max_vertical_scroll(num_properties.saturating_sub(1)),
                 ),
-                Box::new(SchemaTab::new().with_max_vertical_scroll(num_columns)),
+                Box::new(
+                    SchemaTab::new()
+                        .with_max_vertical_scroll(num_columns)
+                        .with_outline(schema_outline.clone()),
+                ),
                 Box::new(
                     RowGroupsTab::new()
                         .with_max_horizontal_scroll(num_row_groups - 1)
-                        .with_max_vertical_scroll(num_columns),
+                        .with_max_vertical_scroll(num_columns)
+                        .with_outline(schema_outline),
                 ),
             ],
             active_tab: 0,
@@ -59,6 +67,12 @@ impl TabManager {
         }
     }
 
+    pub fn select(&mut self, idx: usize) {
+        if idx < self.tabs.len() {
+            self.active_tab = idx;
+        }
+    }
+
     #[allow(clippy::borrowed_box)]
     pub fn active_tab(&self) -> &Box<dyn Tab> {
         &self.tabs[self.active_tab]
@@ -73,6 +87,9 @@ impl TabManager {
             "[Tab]".green(),
             " Next Tab".into(),
             ", ".into(),
+            "[?]".green(),
+            " Help".into(),
+            ", ".into(),
             "[Q]".blue(),
             "uit".into(),
         ]);
@@ -89,10 +106,8 @@ impl TabManager {
 
         line.render(instruction_area, buf);
     }
-}
 
-impl Renderable for TabManager {
-    fn render_content(&self, area: Rect, buf: &mut Buffer) {
+    pub fn render_tabs(&self, area: Rect, buf: &mut Buffer, theme: &Theme) {
         let tab_titles: Vec<Line> = self
             .tabs
             .iter()
@@ -100,9 +115,21 @@ impl Renderable for TabManager {
             .collect();
         let tabs_widget: Tabs<'_> = Tabs::new(tab_titles)
             .select(self.active_tab)
+            .style(Style::default().fg(theme.text))
+            .highlight_style(
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::REVERSED),
+            )
             .padding(" ", " ")
             .divider(" ");
 
         tabs_widget.render(area, buf);
     }
 }
+
+impl Renderable for TabManager {
+    fn render_content(&self, area: Rect, buf: &mut Buffer) {
+        self.render_tabs(area, buf, &Theme::default());
+    }
+}
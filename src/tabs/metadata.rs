@@ -1,8 +1,8 @@
-use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::style::Stylize;
 use ratatui::text::Span;
 use std::io;
 
+use crate::keymap::Action;
 use crate::{app::AppState, tabs::Tab};
 
 pub struct MetadataTab {
@@ -36,11 +36,11 @@ impl MetadataTab {
 }
 
 impl Tab for MetadataTab {
-    fn on_event(&self, key_event: KeyEvent, state: &mut AppState) -> Result<(), io::Error> {
+    fn on_action(&self, action: Action, state: &mut AppState) -> Result<(), io::Error> {
         if self.max_vertical_scroll.is_some() {
-            match key_event.code {
-                KeyCode::Up if state.vertical_offset() > 0 => state.up(),
-                KeyCode::Down
+            match action {
+                Action::Up if state.vertical_offset() > 0 => state.up(),
+                Action::Down
                     if state.vertical_offset() < self.max_vertical_scroll.unwrap_or(usize::MAX) =>
                 {
                     state.down()
@@ -65,6 +65,13 @@ impl Tab for MetadataTab {
         }
     }
 
+    fn help(&self) -> Vec<(Action, &'static str)> {
+        vec![
+            (Action::Up, "Scroll properties up"),
+            (Action::Down, "Scroll properties down"),
+        ]
+    }
+
     fn to_string(&self) -> String {
         "Metadata".to_string()
     }
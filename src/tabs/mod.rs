@@ -10,12 +10,18 @@ pub use schema::SchemaTab;
 pub use visualize::VisualizeTab;
 
 use crate::app::AppState;
-use crossterm::event::KeyEvent;
+use crate::keymap::Action;
 use ratatui::text::Span;
 use std::io;
 
+/// Percentage of the body width one `<`/`>` press resizes the schema tree by.
+pub const TREE_RESIZE_STEP: i16 = 2;
+
 pub trait Tab {
-    fn on_event(&self, key_event: KeyEvent, state: &mut AppState) -> Result<(), io::Error>;
+    fn on_action(&self, action: Action, state: &mut AppState) -> Result<(), io::Error>;
     fn instructions(&self) -> Vec<Span<'static>>;
+    /// Actions this tab responds to, with what they do here. Listed in the
+    /// help overlay next to the keys currently bound to them.
+    fn help(&self) -> Vec<(Action, &'static str)>;
     fn to_string(&self) -> String;
 }
@@ -1,5 +1,7 @@
+use crate::file::schema::SchemaOutline;
+use crate::keymap::Action;
+use crate::tabs::TREE_RESIZE_STEP;
 use crate::{app::AppState, tabs::Tab};
-use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::style::Stylize;
 use ratatui::text::Span;
 use std::io;
@@ -7,6 +9,7 @@ use std::io;
 pub struct RowGroupsTab {
     pub max_horizontal_scroll: Option<usize>,
     pub max_vertical_scroll: Option<usize>,
+    pub outline: SchemaOutline,
 }
 
 impl Default for RowGroupsTab {
@@ -20,6 +23,7 @@ impl RowGroupsTab {
         Self {
             max_horizontal_scroll: None,
             max_vertical_scroll: None,
+            outline: SchemaOutline::default(),
         }
     }
 
@@ -32,23 +36,33 @@ impl RowGroupsTab {
         self.max_vertical_scroll = Some(max_vertical_scroll);
         self
     }
+
+    pub fn with_outline(mut self, outline: SchemaOutline) -> Self {
+        self.outline = outline;
+        self
+    }
 }
 
 impl Tab for RowGroupsTab {
-    fn on_event(&self, key_event: KeyEvent, state: &mut AppState) -> Result<(), io::Error> {
-        match key_event.code {
-            KeyCode::Up if state.vertical_offset() > 0 => state.up(),
-            KeyCode::Down
-                if state.vertical_offset() < self.max_vertical_scroll.unwrap_or(usize::MAX) =>
-            {
-                state.down()
+    fn on_action(&self, action: Action, state: &mut AppState) -> Result<(), io::Error> {
+        match action {
+            Action::Up => state.move_schema_cursor(&self.outline, -1),
+            Action::Down => state.move_schema_cursor(&self.outline, 1),
+            Action::Left | Action::Right if state.filtered_row_groups().is_some() => {
+                state.step_filtered_row_group(action == Action::Right)
             }
-            KeyCode::Left if state.horizontal_offset() > 0 => state.left(),
-            KeyCode::Right
-                if state.horizontal_offset() < self.max_horizontal_scroll.unwrap_or(usize::MAX) =>
+            Action::Left if state.row_group() > 0 => state.set_row_group(state.row_group() - 1),
+            Action::Right
+                if state.row_group() < self.max_horizontal_scroll.unwrap_or(usize::MAX) =>
             {
-                state.right()
+                state.set_row_group(state.row_group() + 1)
             }
+            Action::ShrinkTree => state.resize_tree_pane(-TREE_RESIZE_STEP),
+            Action::GrowTree => state.resize_tree_pane(TREE_RESIZE_STEP),
+            Action::ToggleTree => state.toggle_tree_pane(),
+            Action::ToggleGroup => state.toggle_group(&self.outline),
+            Action::ToggleLayout => state.toggle_row_group_layout(),
+            Action::MarkRowGroup => state.toggle_row_group_mark(),
             _ => {}
         }
         Ok(())
@@ -67,6 +81,67 @@ impl Tab for RowGroupsTab {
             "↓".blue(),
             " : ".into(),
             "Schema".into(),
+            ", ".into(),
+            "<".green(),
+            "/".white(),
+            ">".blue(),
+            " : ".into(),
+            "Resize".into(),
+            ", ".into(),
+            "t".green(),
+            " : ".into(),
+            "Tree".into(),
+            ", ".into(),
+            "o".green(),
+            " : ".into(),
+            "Layout".into(),
+            ", ".into(),
+            "m".green(),
+            " : ".into(),
+            "Compare".into(),
+        ]
+    }
+
+    fn help(&self) -> Vec<(Action, &'static str)> {
+        vec![
+            (Action::Up, "Previous column / group"),
+            (Action::Down, "Next column / group"),
+            (Action::Left, "Previous row group"),
+            (Action::Right, "Next row group"),
+            (Action::Goto, "Go to row group number"),
+            (
+                Action::Command,
+                "Filter row groups, e.g. :filter rows < 10000",
+            ),
+            (
+                Action::Command,
+                "Simulate pruning, e.g. :prune id BETWEEN 10 AND 20",
+            ),
+            (
+                Action::Command,
+                "Probe bloom filters, e.g. :bloom id 17, 42 or :bloom id @keys.txt",
+            ),
+            (
+                Action::Command,
+                "Look for duplicate keys across row groups, e.g. :dups id, date",
+            ),
+            (
+                Action::Command,
+                "Decode a dictionary encoded page of the column, e.g. :page 2",
+            ),
+            (Action::Bookmark, "Bookmark the column"),
+            (Action::ToggleBookmarked, "Show only the bookmarked columns"),
+            (
+                Action::Command,
+                "List only matching columns, e.g. :columns metrics.*",
+            ),
+            (Action::ToggleGroup, "Fold / unfold group"),
+            (Action::Copy, "Copy the column's path, index and type"),
+            (Action::ToggleLayout, "Byte offsets and range requests"),
+            (Action::MarkRowGroup, "Mark two row groups to compare them"),
+            (Action::ShrinkTree, "Shrink schema tree"),
+            (Action::GrowTree, "Grow schema tree"),
+            (Action::ToggleTree, "Show / hide schema tree"),
         ]
     }
 
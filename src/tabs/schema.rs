@@ -1,13 +1,16 @@
-use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::style::Stylize;
 use ratatui::text::Span;
 use std::io;
 
+use crate::file::schema::SchemaOutline;
+use crate::keymap::Action;
+use crate::tabs::TREE_RESIZE_STEP;
 use crate::{app::AppState, tabs::Tab};
 
 pub struct SchemaTab {
     pub max_horizontal_scroll: Option<usize>,
     pub max_vertical_scroll: Option<usize>,
+    pub outline: SchemaOutline,
 }
 
 impl Default for SchemaTab {
@@ -21,6 +24,7 @@ impl SchemaTab {
         Self {
             max_horizontal_scroll: None,
             max_vertical_scroll: None,
+            outline: SchemaOutline::default(),
         }
     }
 
@@ -33,23 +37,37 @@ impl SchemaTab {
         self.max_vertical_scroll = Some(max_vertical_scroll);
         self
     }
+
+    pub fn with_outline(mut self, outline: SchemaOutline) -> Self {
+        self.outline = outline;
+        self
+    }
 }
 
 impl Tab for SchemaTab {
-    fn on_event(&self, key_event: KeyEvent, state: &mut AppState) -> Result<(), io::Error> {
-        match key_event.code {
-            KeyCode::Up if state.vertical_offset() > 0 => state.up(),
-            KeyCode::Down
-                if state.vertical_offset() < self.max_vertical_scroll.unwrap_or(usize::MAX) =>
-            {
-                state.down()
-            }
-            KeyCode::Left if state.horizontal_offset() > 0 => state.left(),
-            KeyCode::Right
+    fn on_action(&self, action: Action, state: &mut AppState) -> Result<(), io::Error> {
+        match action {
+            Action::Up => state.move_schema_cursor(&self.outline, -1),
+            Action::Down => state.move_schema_cursor(&self.outline, 1),
+            // On a group row the arrows fold / unfold it instead of scrolling.
+            Action::Left if state.selected_group().is_some() => state.collapse_group(&self.outline),
+            Action::Right if state.selected_group().is_some() => state.expand_group(),
+            Action::Left if state.horizontal_offset() > 0 => state.left(),
+            Action::Right
                 if state.horizontal_offset() < self.max_horizontal_scroll.unwrap_or(usize::MAX) =>
             {
                 state.right()
             }
+            Action::PageLeft => state.page_left(),
+            Action::PageRight => state.page_right(),
+            Action::ShrinkTree => state.resize_tree_pane(-TREE_RESIZE_STEP),
+            Action::GrowTree => state.resize_tree_pane(TREE_RESIZE_STEP),
+            Action::ToggleTree => state.toggle_tree_pane(),
+            Action::ToggleSplit => state.toggle_split(),
+            Action::ToggleGroup => state.toggle_group(&self.outline),
+            Action::ToggleFlat => state.toggle_schema_flat(&self.outline),
+            Action::ToggleFieldIds => state.toggle_field_ids(),
+            Action::EstimateDistinct => state.start_distinct_estimates(self.outline.leaf_count()),
             _ => {}
         }
         Ok(())
@@ -68,6 +86,94 @@ impl Tab for SchemaTab {
             "↓".blue(),
             " : ".into(),
             "Schema".into(),
+            ", ".into(),
+            "<".green(),
+            "/".white(),
+            ">".blue(),
+            " : ".into(),
+            "Resize".into(),
+            ", ".into(),
+            "t".green(),
+            " : ".into(),
+            "Tree".into(),
+            ", ".into(),
+            "f".green(),
+            " : ".into(),
+            "Flat".into(),
+            ", ".into(),
+            "i".green(),
+            " : ".into(),
+            "Field IDs".into(),
+            ", ".into(),
+            "n".green(),
+            " : ".into(),
+            "Distinct".into(),
+        ]
+    }
+
+    fn help(&self) -> Vec<(Action, &'static str)> {
+        vec![
+            (Action::Up, "Previous column / group"),
+            (Action::Down, "Next column / group"),
+            (Action::Left, "Scroll stats left / fold group"),
+            (Action::Right, "Scroll stats right / unfold group"),
+            (Action::PageLeft, "Scroll a screenful of stats left"),
+            (Action::PageRight, "Scroll a screenful of stats right"),
+            (Action::ToggleGroup, "Fold / unfold group"),
+            (Action::ToggleFlat, "Tree / flat dotted paths"),
+            (Action::ToggleFieldIds, "Show / hide field ids"),
+            (Action::Copy, "Copy the column's path, index and type"),
+            (
+                Action::OpenExternal,
+                "Open the schema as SQL DDL in the pager / editor",
+            ),
+            (
+                Action::Command,
+                "Copy it as SQL or Spark, e.g. :copy sql (path, index, type, sql, spark)",
+            ),
+            (
+                Action::EstimateDistinct,
+                "Estimate distinct values from a sample",
+            ),
+            (
+                Action::Command,
+                "Profile columns from a sample, e.g. :profile 50000 (:profile off hides it)",
+            ),
+            (
+                Action::Command,
+                "Plot the column against another with their correlation, e.g. :corr price",
+            ),
+            (
+                Action::Command,
+                "Check that STRING columns are valid UTF-8, :utf8",
+            ),
+            (
+                Action::Command,
+                "Time decoding each column and name the slowest, :bench",
+            ),
+            (
+                Action::Command,
+                "Estimate codec levels and the savings of a higher one, :codecs [level]",
+            ),
+            (
+                Action::Bookmark,
+                "Bookmark the column, or every column of the group",
+            ),
+            (
+                Action::ToggleBookmarked,
+                "Show only the bookmarked columns, in every tab",
+            ),
+            (
+                Action::Command,
+                "List only matching columns, e.g. :columns ^metrics\\..*_p99$ or :columns *_id",
+            ),
+            (Action::ShrinkTree, "Shrink schema tree"),
+            (Action::GrowTree, "Grow schema tree"),
+            (Action::ToggleTree, "Show / hide schema tree"),
+            (
+                Action::ToggleSplit,
+                "Show the data preview below, scrolled to the selected column",
+            ),
         ]
     }
 
@@ -1,8 +1,8 @@
-use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::style::Stylize;
 use ratatui::text::Span;
 use std::io;
 
+use crate::keymap::Action;
 use crate::{app::AppState, tabs::Tab};
 
 pub struct VisualizeTab {
@@ -43,36 +43,36 @@ impl VisualizeTab {
 }
 
 impl Tab for VisualizeTab {
-    fn on_event(&self, key_event: KeyEvent, state: &mut AppState) -> Result<(), io::Error> {
+    fn on_action(&self, action: Action, state: &mut AppState) -> Result<(), io::Error> {
         let max_rows = self.max_rows.unwrap_or(0);
         let visible_rows = state.visible_data_rows();
 
-        match key_event.code {
-            // Row navigation (Up/Down arrows)
-            KeyCode::Up => {
-                if state.vertical_offset() > 0 {
-                    state.up();
-                    state.adjust_scroll_to_selection(visible_rows, max_rows);
-                }
+        match action {
+            // Row navigation
+            Action::Up if state.vertical_offset() > 0 => {
+                state.up();
+                state.adjust_scroll_to_selection(visible_rows, max_rows);
             }
-            KeyCode::Down => {
-                if state.vertical_offset() < max_rows.saturating_sub(1) {
-                    state.down();
-                    state.adjust_scroll_to_selection(visible_rows, max_rows);
-                }
+            Action::Down if state.vertical_offset() < max_rows.saturating_sub(1) => {
+                state.down();
+                state.adjust_scroll_to_selection(visible_rows, max_rows);
             }
-            // Page navigation (u/d keys)
-            KeyCode::Char('u') | KeyCode::Char('U') => {
+            // Page navigation
+            Action::PageUp => {
                 state.page_up(visible_rows, max_rows);
             }
-            KeyCode::Char('d') | KeyCode::Char('D') => {
+            Action::PageDown => {
                 state.page_down(visible_rows, max_rows);
             }
-            // Column navigation (Left/Right arrows)
-            KeyCode::Left if state.horizontal_offset() > 0 => state.left(),
+            // Column navigation
+            Action::Left if state.horizontal_offset() > 0 => state.left(),
             // Upper bound is enforced in AppState against the on-screen column
             // count each frame, so no guard is needed here.
-            KeyCode::Right => state.right(),
+            Action::Right => state.right(),
+            Action::PageLeft => state.page_left(),
+            Action::PageRight => state.page_right(),
+            Action::Inspect if max_rows > 0 => state.toggle_inspector(),
+            Action::ExpandRow => state.toggle_expand_row(),
             _ => {}
         }
         Ok(())
@@ -97,6 +97,62 @@ impl Tab for VisualizeTab {
             "d".blue(),
             " : ".into(),
             "Page".into(),
+            " | ".white(),
+            "v".green(),
+            " : ".into(),
+            "Inspect".into(),
+            " | ".white(),
+            "e".green(),
+            " : ".into(),
+            "Expand".into(),
+            " | ".white(),
+            "s".green(),
+            " : ".into(),
+            "Sample".into(),
+        ]
+    }
+
+    fn help(&self) -> Vec<(Action, &'static str)> {
+        vec![
+            (Action::Up, "Previous row"),
+            (Action::Down, "Next row"),
+            (Action::Left, "Scroll columns left"),
+            (Action::Right, "Scroll columns right"),
+            (Action::PageLeft, "Scroll a screenful of columns left"),
+            (Action::PageRight, "Scroll a screenful of columns right"),
+            (Action::PageUp, "Page up"),
+            (Action::PageDown, "Page down"),
+            (Action::Goto, "Go to row number"),
+            (Action::LastRows, "Jump to the last rows of the file"),
+            (
+                Action::Search,
+                "Search the loaded rows for text or a /regex/; :search! scans the file",
+            ),
+            (Action::NextMatch, "Next row with a match"),
+            (Action::PrevMatch, "Previous row with a match"),
+            (Action::Copy, "Copy the column's path, index and type"),
+            (Action::OpenExternal, "Open the value in the pager / editor"),
+            (Action::Bookmark, "Bookmark the column"),
+            (
+                Action::Inspect,
+                "Inspect the selected row as a tree of its values",
+            ),
+            (
+                Action::ExpandRow,
+                "Show nested values of the selected row over several lines",
+            ),
+            (
+                Action::WrapRow,
+                "Wrap the long values of the selected row to the column width",
+            ),
+            (
+                Action::Sample,
+                "Preview the head, tail, every nth row, a random sample or each row group",
+            ),
+            (
+                Action::Command,
+                "Pick a sample, e.g. :sample every:1000 or :sample random:42",
+            ),
         ]
     }
 
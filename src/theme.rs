@@ -0,0 +1,330 @@
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::file::utils::{RatioGrade, ratio_grade};
+
+/// Named color roles shared by every component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Regular text.
+    pub text: Color,
+    /// De-emphasized text: row numbers, axis titles, hints.
+    pub muted: Color,
+    pub border: Color,
+    /// Table column headers.
+    pub header: Color,
+    /// Titles and the active tab.
+    pub accent: Color,
+    /// Keys of key/value tables and secondary series in charts.
+    pub label: Color,
+    /// Foreground/background of the selected item.
+    pub selection_fg: Color,
+    pub selection_bg: Color,
+    /// Background of the cursor row in large tables (subtler than selection).
+    pub highlight_bg: Color,
+    /// Group nodes in the schema.
+    pub group: Color,
+    /// Positive / negative indicators, and the middle ground between them.
+    pub good: Color,
+    pub bad: Color,
+    pub warn: Color,
+}
+
+impl Theme {
+    pub const DARK: Theme = Theme {
+        text: Color::White,
+        muted: Color::DarkGray,
+        border: Color::DarkGray,
+        header: Color::Yellow,
+        accent: Color::LightYellow,
+        label: Color::Cyan,
+        selection_fg: Color::Black,
+        selection_bg: Color::Yellow,
+        highlight_bg: Color::Rgb(60, 60, 60),
+        group: Color::Green,
+        good: Color::Green,
+        bad: Color::Red,
+        warn: Color::Yellow,
+    };
+
+    pub const LIGHT: Theme = Theme {
+        text: Color::Black,
+        muted: Color::Gray,
+        border: Color::Gray,
+        header: Color::Blue,
+        accent: Color::Magenta,
+        label: Color::Blue,
+        selection_fg: Color::White,
+        selection_bg: Color::Blue,
+        highlight_bg: Color::Rgb(215, 215, 215),
+        group: Color::Green,
+        good: Color::Green,
+        bad: Color::Red,
+        warn: Color::Rgb(176, 128, 0),
+    };
+
+    pub const HIGH_CONTRAST: Theme = Theme {
+        text: Color::White,
+        muted: Color::Gray,
+        border: Color::White,
+        header: Color::LightYellow,
+        accent: Color::LightCyan,
+        label: Color::LightCyan,
+        selection_fg: Color::Black,
+        selection_bg: Color::White,
+        highlight_bg: Color::Blue,
+        group: Color::LightGreen,
+        good: Color::LightGreen,
+        bad: Color::LightRed,
+        warn: Color::LightYellow,
+    };
+
+    pub fn selection_style(&self) -> Style {
+        Style::default().fg(self.selection_fg).bg(self.selection_bg)
+    }
+
+    pub fn header_style(&self) -> Style {
+        Style::default()
+            .fg(self.header)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    pub fn border_style(&self) -> Style {
+        Style::default().fg(self.border)
+    }
+
+    /// Red, yellow or green for a compression ratio, by the thresholds in
+    /// the display config.
+    pub fn ratio_style(&self, ratio: f64) -> Style {
+        Style::default().fg(match ratio_grade(ratio) {
+            RatioGrade::Poor => self.bad,
+            RatioGrade::Fair => self.warn,
+            RatioGrade::Good => self.good,
+        })
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::DARK
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemePreset {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+}
+
+impl ThemePreset {
+    pub fn theme(&self) -> Theme {
+        match self {
+            ThemePreset::Dark => Theme::DARK,
+            ThemePreset::Light => Theme::LIGHT,
+            ThemePreset::HighContrast => Theme::HIGH_CONTRAST,
+        }
+    }
+}
+
+/// Fallbacks for limited terminals, logs and screen readers, applied to a
+/// finished frame: `no_color` drops every color, showing selections in
+/// reverse video instead, and `ascii` swaps the borders, arrows, bars and
+/// other glyphs for ASCII. The values shown are left alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RenderMode {
+    pub no_color: bool,
+    pub ascii: bool,
+}
+
+impl RenderMode {
+    pub fn apply(&self, buf: &mut Buffer) {
+        if !self.no_color && !self.ascii {
+            return;
+        }
+        for cell in &mut buf.content {
+            if self.no_color {
+                if cell.bg != Color::Reset {
+                    cell.modifier.insert(Modifier::REVERSED);
+                }
+                cell.fg = Color::Reset;
+                cell.bg = Color::Reset;
+            }
+            if self.ascii
+                && let Some(symbol) = ascii_symbol(cell.symbol())
+            {
+                cell.set_symbol(symbol);
+            }
+        }
+    }
+}
+
+/// ASCII stand-in for a glyph the app draws, if it has one.
+fn ascii_symbol(symbol: &str) -> Option<&'static str> {
+    let mut chars = symbol.chars();
+    let (Some(c), None) = (chars.next(), chars.next()) else {
+        return None;
+    };
+    Some(match c {
+        '─' | '━' | '═' | '╌' | '┄' | '—' | '–' => "-",
+        '│' | '┃' | '║' | '╎' | '┆' => "|",
+        '\u{2500}'..='\u{257f}' => "+",
+        '▁' | '▂' | '▃' => "_",
+        '░' => ".",
+        '▒' => ":",
+        '\u{2580}'..='\u{259f}' => "#",
+        '\u{2800}' => " ",
+        '\u{2801}'..='\u{28ff}' | '•' | '·' => ".",
+        '…' => "~",
+        '↑' | '▲' | '▴' => "^",
+        '↓' | '▼' | '▾' => "v",
+        '←' | '◄' | '◂' | '‹' => "<",
+        '→' | '►' | '▸' | '›' => ">",
+        '★' => "*",
+        '≈' => "~",
+        '≤' => "<",
+        '≥' => ">",
+        '×' | '✗' => "x",
+        '✓' => "+",
+        '⚠' => "!",
+        'ρ' => "p",
+        _ => return None,
+    })
+}
+
+/// `[theme]` section of the config: a preset plus optional per-role
+/// overrides. Colors are names (`"light-blue"`), indices (`"42"`) or hex
+/// (`"#ffaa00"`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    pub preset: ThemePreset,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub muted: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_fg: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_bg: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight_bg: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub good: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bad: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warn: Option<String>,
+}
+
+impl ThemeConfig {
+    /// Resolve the preset and apply the overrides on top of it.
+    pub fn to_theme(&self) -> Result<Theme, String> {
+        let mut theme = self.preset.theme();
+        let overrides = [
+            (&self.text, &mut theme.text),
+            (&self.muted, &mut theme.muted),
+            (&self.border, &mut theme.border),
+            (&self.header, &mut theme.header),
+            (&self.accent, &mut theme.accent),
+            (&self.label, &mut theme.label),
+            (&self.selection_fg, &mut theme.selection_fg),
+            (&self.selection_bg, &mut theme.selection_bg),
+            (&self.highlight_bg, &mut theme.highlight_bg),
+            (&self.group, &mut theme.group),
+            (&self.good, &mut theme.good),
+            (&self.bad, &mut theme.bad),
+            (&self.warn, &mut theme.warn),
+        ];
+        for (value, slot) in overrides {
+            if let Some(value) = value {
+                *slot = Color::from_str(value).map_err(|_| format!("unknown color '{value}'"))?;
+            }
+        }
+        Ok(theme)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_preset_is_dark() {
+        assert_eq!(ThemeConfig::default().to_theme().unwrap(), Theme::DARK);
+    }
+
+    #[test]
+    fn test_overrides_apply_on_top_of_preset() {
+        let config = ThemeConfig {
+            preset: ThemePreset::Light,
+            selection_bg: Some("#ff8800".to_string()),
+            border: Some("dark-gray".to_string()),
+            ..Default::default()
+        };
+        let theme = config.to_theme().unwrap();
+        assert_eq!(theme.selection_bg, Color::Rgb(0xff, 0x88, 0x00));
+        assert_eq!(theme.border, Color::DarkGray);
+        assert_eq!(theme.header, Theme::LIGHT.header);
+    }
+
+    #[test]
+    fn test_unknown_color_is_an_error() {
+        let config = ThemeConfig {
+            accent: Some("not-a-color".to_string()),
+            ..Default::default()
+        };
+        assert!(config.to_theme().is_err());
+    }
+
+    #[test]
+    fn test_render_mode() {
+        use ratatui::layout::Rect;
+        use ratatui::widgets::{Block, BorderType, Widget};
+
+        let area = Rect::new(0, 0, 8, 3);
+        let mut buf = Buffer::empty(area);
+        Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Theme::DARK.border_style())
+            .render(area, &mut buf);
+        buf.set_string(1, 1, "東…", Theme::DARK.selection_style());
+
+        let mut plain = buf.clone();
+        RenderMode {
+            no_color: true,
+            ascii: false,
+        }
+        .apply(&mut plain);
+        assert_eq!(plain[(0, 0)].symbol(), "╭");
+        assert_eq!(plain[(0, 0)].fg, Color::Reset);
+        assert_eq!(plain[(1, 1)].bg, Color::Reset);
+        assert!(plain[(1, 1)].modifier.contains(Modifier::REVERSED));
+        assert!(!plain[(0, 0)].modifier.contains(Modifier::REVERSED));
+
+        RenderMode {
+            no_color: false,
+            ascii: true,
+        }
+        .apply(&mut buf);
+        let line = |y| (0..8).map(|x| buf[(x, y)].symbol()).collect::<String>();
+        assert_eq!(line(0), "+------+");
+        // Values such as the CJK text are kept.
+        assert_eq!(line(1), "|東 ~   |");
+        assert_eq!(buf[(1, 1)].fg, Theme::DARK.selection_fg);
+    }
+}
@@ -2,47 +2,102 @@ use ratatui::{
     Frame,
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
-    prelude::Color,
     style::{Style, Stylize},
-    widgets::{Block, BorderType, Borders, Widget},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, LineGauge, Widget},
 };
 
 use crate::app::AppRenderView;
 use crate::components::{
-    DataTable, FileSchemaTable, RowGroupColumnMetadataComponent, RowGroupMetadata,
-    RowGroupProgressBar, SchemaTreeComponent, ScrollbarComponent,
+    BloomPanel, ColumnProfilePanel, DataTable, DecodedPagePanel, ErrorPanel, FileSchemaTable,
+    PruningPanel, RowGroupColumnMetadataComponent, RowGroupComparison, RowGroupLayout,
+    RowGroupMetadata, RowGroupProgressBar, SchemaTreeComponent, ScrollbarComponent, SizeBreakdown,
 };
-use crate::file::Renderable;
+use crate::file::utils::{display_width, human_readable_bytes};
+use crate::keymap::Action;
+use crate::mouse::{ListRegion, PaneDivider, ScrollTarget, ScrollbarRegion};
+use crate::status::StatusKind;
+
+/// Columns listed in the Metadata tab's size breakdown.
+const SIZE_BREAKDOWN_COLUMNS: usize = 10;
+
+/// Frames of the spinner shown while a task runs, one per tick.
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// The text of `buf`, one line per row with trailing spaces trimmed.
+pub fn buffer_to_string(buf: &Buffer) -> String {
+    let area = buf.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            let line: String = (area.left()..area.right())
+                .map(|x| buf[(x, y)].symbol())
+                .collect();
+            line.trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
 
 pub fn render_app<'a, 'b>(app: &'b AppRenderView<'a>, frame: &mut Frame)
 where
     'b: 'a,
 {
     frame.render_widget(AppWidget(app), frame.area());
+    app.render_mode.apply(frame.buffer_mut());
 }
 
 struct AppWidget<'a>(&'a AppRenderView<'a>);
 
 impl<'a> AppWidget<'a> {
-    // Helper function to calculate the tree index of the selected primitive column
-    fn calculate_selected_tree_index(&self, vertical_offset: usize) -> Option<usize> {
-        if vertical_offset == 0 {
-            return None;
+    // Schema nodes shown in the tree pane: the tree with folded groups'
+    // children hidden, or the leaves in the flat view.
+    fn visible_tree_nodes(&self) -> Vec<usize> {
+        self.0
+            .state()
+            .visible_schema_nodes(&self.0.parquet_ctx.schema.outline())
+    }
+
+    // "Schema Tree", then the `:columns` filter and the bookmarked-only
+    // view when they hide columns.
+    fn schema_tree_title(&self) -> String {
+        let state = self.0.state();
+        let mut title = "Schema Tree".to_string();
+        if let Some(filter) = state.column_filter() {
+            title.push_str(&format!(" · {filter}"));
+        }
+        if state.bookmarked_only() {
+            title.push_str(" · ★ only");
         }
+        title
+    }
+
+    // Width the tree pane content needs in the active view.
+    fn schema_tree_width(&self) -> u16 {
+        let schema = &self.0.parquet_ctx.schema;
+        let field_id_width = if self.0.state().show_field_ids() {
+            schema.field_id_width()
+        } else {
+            0
+        };
+        let width = if self.0.state().schema_flat() {
+            schema.flat_width()
+        } else {
+            schema.tree_width()
+        };
+        // Room for the bookmark marks.
+        let bookmark_width = if self.0.state().bookmarks().is_empty() {
+            0
+        } else {
+            2
+        };
+        (width + field_id_width + bookmark_width) as u16
+    }
 
-        let primitive_to_schema_map: Vec<usize> = self
-            .0
-            .parquet_ctx
-            .schema
-            .columns
-            .iter()
-            .enumerate()
-            .filter_map(|(idx, line)| {
-                matches!(line, crate::file::schema::SchemaInfo::Primitive { .. }).then_some(idx)
-            })
-            .collect();
-
-        primitive_to_schema_map.get(vertical_offset - 1).copied()
+    // Row of the tree cursor among the visible tree rows.
+    fn calculate_selected_tree_index(&self, visible_nodes: &[usize]) -> Option<usize> {
+        let outline = self.0.parquet_ctx.schema.outline();
+        let cursor = self.0.state().schema_cursor(&outline)?;
+        visible_nodes.iter().position(|&node| node == cursor)
     }
 
     // Helper function to calculate adjusted scroll offset to keep selected item visible
@@ -68,9 +123,12 @@ impl<'a> AppWidget<'a> {
     }
 
     // Calculate the adjusted scroll offset for the schema tree
-    fn calculate_adjusted_scroll_offset(&self, visible_tree_items: usize) -> usize {
-        let selected_tree_idx =
-            self.calculate_selected_tree_index(self.0.state().vertical_offset());
+    fn calculate_adjusted_scroll_offset(
+        &self,
+        visible_nodes: &[usize],
+        visible_tree_items: usize,
+    ) -> usize {
+        let selected_tree_idx = self.calculate_selected_tree_index(visible_nodes);
         self.calculate_scroll_to_show_item(
             selected_tree_idx,
             self.0.state().tree_scroll_offset(),
@@ -96,27 +154,58 @@ impl<'a> AppWidget<'a> {
         }
     }
 
-    // Render the schema tree section (tree + optional scrollbar
-    #[allow(clippy::too_many_arguments)]
+    // Width of the schema tree pane: fit to content unless the user resized or
+    // collapsed it.
+    fn tree_pane_width(&self, area: Rect, natural_width: u16) -> u16 {
+        self.0
+            .state()
+            .layout()
+            .tree_pane_width(area.width, natural_width)
+            .min(area.width)
+    }
+
+    // Record the tree/table border so it can be dragged to resize the panes.
+    fn record_pane_divider(&self, body: Rect, tree_pane: Rect) {
+        if tree_pane.width > 0 {
+            self.0.mouse_regions().borrow_mut().pane_divider = Some(PaneDivider {
+                body,
+                x: tree_pane.right() - 1,
+            });
+        }
+    }
+
+    // Render the schema tree section (tree + optional scrollbar)
     fn render_schema_tree_section(
         &self,
         area: Rect,
-        tree_width: u16,
         needs_scrollbar: bool,
         total_tree_items: usize,
         visible_tree_items: usize,
         adjusted_scroll: usize,
         buf: &mut Buffer,
     ) {
+        if area.width == 0 {
+            return;
+        }
         if needs_scrollbar {
             let [tree_area, scrollbar_area] =
-                Layout::horizontal([Constraint::Length(tree_width + 1), Constraint::Length(1)])
-                    .areas(area);
+                Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
 
             self.render_schema_tree_with_scroll(tree_area, adjusted_scroll, buf);
 
             ScrollbarComponent::vertical(total_tree_items, visible_tree_items, adjusted_scroll)
+                .with_theme(self.0.theme)
                 .render(scrollbar_area, buf);
+            self.0
+                .mouse_regions()
+                .borrow_mut()
+                .scrollbars
+                .push(ScrollbarRegion {
+                    area: scrollbar_area,
+                    total_items: total_tree_items,
+                    visible_items: visible_tree_items,
+                    target: ScrollTarget::SchemaTree,
+                });
         } else {
             self.render_schema_tree_with_scroll(area, adjusted_scroll, buf);
         }
@@ -124,62 +213,248 @@ impl<'a> AppWidget<'a> {
 
     // Render the schema table
     fn render_schema_table(&self, area: Rect, adjusted_scroll: usize, buf: &mut Buffer) {
-        FileSchemaTable::new(&self.0.parquet_ctx.schema)
+        // Rows start below the border and the header line, and skip the root.
+        self.0
+            .mouse_regions()
+            .borrow_mut()
+            .schema_rows
+            .push(ListRegion {
+                area: Rect::new(
+                    area.x,
+                    area.y + 2,
+                    area.width,
+                    area.height.saturating_sub(3),
+                ),
+                first_index: adjusted_scroll + 1,
+            });
+        let mut table = FileSchemaTable::new(&self.0.parquet_ctx.schema)
+            .with_theme(self.0.theme)
+            .with_distinct_estimates(self.0.state().distinct_estimates());
+        if let Some((done, total)) = self.0.state().distinct_progress() {
+            table = table.with_title(format!(
+                "Column Statistics · estimating distinct values {done} / {total}"
+            ));
+        }
+        table
+            .with_visible_nodes(self.visible_tree_nodes())
             .with_selected_index(self.0.state().vertical_offset())
+            .with_selected_group(self.0.state().selected_group())
             .with_horizontal_scroll(self.0.state().horizontal_offset())
             .with_vertical_scroll(adjusted_scroll)
+            .with_show_names(self.0.state().layout().tree_collapsed)
+            .with_field_ids(self.0.state().show_field_ids())
+            .with_sorted_by_size(self.0.state().sorted_by_size())
             .render(area, buf);
+        // Over the bottom border, between the corners.
+        if self.columns_scroll() && area.width > 2 && area.height > 0 {
+            self.render_column_scrollbar(
+                Rect::new(area.x + 1, area.bottom() - 1, area.width - 2, 1),
+                buf,
+            );
+        }
+    }
+
+    /// How far the table is scrolled through its columns, when they don't
+    /// all fit. The thumb covers the columns on screen.
+    fn render_column_scrollbar(&self, area: Rect, buf: &mut Buffer) {
+        let state = self.0.state();
+        let visible = state.visible_columns();
+        ScrollbarComponent::horizontal(
+            state.max_horizontal_offset() + visible,
+            visible,
+            state.horizontal_offset(),
+        )
+        .with_theme(self.0.theme)
+        .render(area, buf);
+    }
+
+    fn columns_scroll(&self) -> bool {
+        let max = self.0.state().max_horizontal_offset();
+        max > 0 && max != usize::MAX
     }
 
     fn render_tabs_view(&self, area: Rect, buf: &mut Buffer) {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::LightYellow));
+            .border_style(Style::default().fg(self.0.theme.accent))
+            // The basics of the file, so they're at hand on every tab.
+            .title_bottom(
+                Line::from(format!(" {} ", self.0.parquet_ctx.metadata.summary()))
+                    .fg(self.0.theme.muted),
+            );
         let inner_area = block.inner(area);
         block.render(area, buf);
 
-        let file_name_length = self.0.file_name().len() as u16;
+        let file_name_length = display_width(self.0.file_name()) as u16;
 
         let [tabs_area, file_name_area] =
             Layout::horizontal([Constraint::Min(0), Constraint::Length(file_name_length)])
                 .areas(inner_area);
-        self.0.tabs().render_content(tabs_area, buf);
-        self.0.file_name().green().render(file_name_area, buf);
+        self.0.tabs().render_tabs(tabs_area, buf, self.0.theme);
+        self.record_tab_regions(tabs_area);
+        self.0
+            .file_name()
+            .fg(self.0.theme.good)
+            .render(file_name_area, buf);
+    }
+
+    // Mirror the `Tabs` layout (1 cell of padding each side, 1 cell divider) so
+    // clicks on a title can be mapped back to its tab.
+    fn record_tab_regions(&self, tabs_area: Rect) {
+        let mut regions = self.0.mouse_regions().borrow_mut();
+        let mut x = tabs_area.x;
+        for (idx, tab) in self.0.tabs().tabs.iter().enumerate() {
+            let width = display_width(&tab.to_string()) as u16 + 2;
+            let title_area = Rect::new(x, tabs_area.y, width, 1).intersection(tabs_area);
+            regions.tabs.push((title_area, idx));
+            x = x.saturating_add(width + 1);
+        }
     }
 
     fn render_footer_view(&self, area: Rect, buf: &mut Buffer) {
-        let title_width = self.0.title.len() as u16;
-        let [title_area, footer_area] =
-            Layout::horizontal([Constraint::Length(title_width), Constraint::Fill(1)]).areas(area);
-        self.0.title.bold().fg(Color::Green).render(title_area, buf);
+        if let Some(input) = self.0.state().command_line() {
+            Line::from(vec![
+                ":".fg(self.0.theme.accent),
+                input.to_string().fg(self.0.theme.text),
+                "█".fg(self.0.theme.muted),
+            ])
+            .render(area, buf);
+            return;
+        }
+
+        let title_width = display_width(self.0.title) as u16;
+        let mut crumbs = vec![" ".into()];
+        for (idx, crumb) in self.0.breadcrumb().into_iter().enumerate() {
+            if idx > 0 {
+                crumbs.push(" › ".fg(self.0.theme.muted));
+            }
+            crumbs.push(crumb.fg(self.0.theme.text));
+        }
+        crumbs.push(" ".into());
+        let breadcrumb = Line::from(crumbs);
+        let [title_area, breadcrumb_area, footer_area] = Layout::horizontal([
+            Constraint::Length(title_width),
+            Constraint::Length(breadcrumb.width() as u16),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        self.0
+            .title
+            .bold()
+            .fg(self.0.theme.good)
+            .render(title_area, buf);
+        breadcrumb.render(breadcrumb_area, buf);
+
+        if let Some((label, progress)) = self.0.task {
+            let cancel = self
+                .0
+                .keymap
+                .keys_for(Action::Reset)
+                .first()
+                .map(|key| format!(" · {key} cancels"))
+                .unwrap_or_default();
+            // Tasks that can't tell how far they got show a spinner instead.
+            let label = match progress.total() {
+                0 => {
+                    let spinner = SPINNER[self.0.ticks % SPINNER.len()];
+                    format!("{spinner} {label}{cancel} ")
+                }
+                total => format!(
+                    "{label} {} / {}{cancel} ",
+                    human_readable_bytes(progress.done().min(total)),
+                    human_readable_bytes(total)
+                ),
+            };
+            LineGauge::default()
+                .label(label)
+                .filled_style(Style::default().fg(self.0.theme.accent))
+                .unfilled_style(Style::default().fg(self.0.theme.muted))
+                .ratio(progress.ratio())
+                .render(footer_area, buf);
+            return;
+        }
 
         self.0.tabs().render_instructions(footer_area, buf);
+        if let Some(status) = self.0.state().status() {
+            let color = match status.kind {
+                StatusKind::Info => self.0.theme.good,
+                StatusKind::Error => self.0.theme.bad,
+                StatusKind::Progress => self.0.theme.accent,
+            };
+            Clear.render(footer_area, buf);
+            format!(" {}", status.text)
+                .fg(color)
+                .render(footer_area, buf);
+        }
     }
 
     fn render_metadata_view(&self, area: Rect, buf: &mut Buffer) {
-        self.0
-            .parquet_ctx
-            .metadata
-            .render_with_scroll(area, buf, self.0.state().vertical_offset());
+        let ctx = self.0.parquet_ctx;
+        let breakdown = SizeBreakdown::new(
+            ctx.schema.largest_columns(SIZE_BREAKDOWN_COLUMNS),
+            ctx.file_size,
+        )
+        .with_theme(self.0.theme);
+        ctx.metadata.render_with_panel(
+            area,
+            buf,
+            self.0.state().vertical_offset(),
+            self.0.theme,
+            breakdown,
+        );
     }
 
     fn render_schema_view(&self, area: Rect, buf: &mut Buffer) {
-        let tree_width = self.0.parquet_ctx.schema.tree_width() as u16;
-        let total_tree_items = self.0.parquet_ctx.schema.columns.len();
+        // The profile spans the width below the tree and table so their rows
+        // stay aligned.
+        let area = match self.0.state().profile() {
+            Some(profile) => {
+                let [area, profile_area] =
+                    Layout::vertical([Constraint::Fill(1), Constraint::Length(8)]).areas(area);
+                let column = match self.0.state().selected_group() {
+                    Some(_) => None,
+                    None => self.0.state().vertical_offset().checked_sub(1),
+                };
+                let group = self
+                    .0
+                    .state()
+                    .selected_group()
+                    .map(|node| self.0.parquet_ctx.schema.column_path(node));
+                ColumnProfilePanel::new(profile, column)
+                    .with_group(group)
+                    .with_theme(self.0.theme)
+                    .render(profile_area, buf);
+                area
+            }
+            None => area,
+        };
+        let area = if self.0.state().layout().split {
+            let [area, preview_area] =
+                Layout::vertical([Constraint::Fill(3), Constraint::Fill(2)]).areas(area);
+            self.render_split_preview(preview_area, buf);
+            area
+        } else {
+            area
+        };
+        let tree_width = self.schema_tree_width();
+        let visible_nodes = self.visible_tree_nodes();
+        let total_tree_items = visible_nodes.len();
         let visible_tree_items = area.height.saturating_sub(2) as usize;
 
         let needs_scrollbar = total_tree_items > visible_tree_items;
-        let adjusted_scroll = self.calculate_adjusted_scroll_offset(visible_tree_items);
-        let tree_total_width = self.calculate_tree_width(tree_width, needs_scrollbar);
+        let adjusted_scroll =
+            self.calculate_adjusted_scroll_offset(&visible_nodes, visible_tree_items);
+        let tree_total_width =
+            self.tree_pane_width(area, self.calculate_tree_width(tree_width, needs_scrollbar));
 
         let [tree_container_area, central_area] =
             Layout::horizontal([Constraint::Length(tree_total_width), Constraint::Fill(1)])
                 .areas(area);
+        self.record_pane_divider(area, tree_container_area);
 
         self.render_schema_tree_section(
             tree_container_area,
-            tree_width,
             needs_scrollbar,
             total_tree_items,
             visible_tree_items,
@@ -190,30 +465,54 @@ impl<'a> AppWidget<'a> {
     }
 
     fn render_schema_tree_with_scroll(&self, area: Rect, scroll_offset: usize, buf: &mut Buffer) {
+        self.0
+            .mouse_regions()
+            .borrow_mut()
+            .schema_rows
+            .push(ListRegion {
+                area: Rect::new(
+                    area.x,
+                    area.y + 1,
+                    area.width,
+                    area.height.saturating_sub(2),
+                ),
+                first_index: scroll_offset,
+            });
         SchemaTreeComponent::new(&self.0.parquet_ctx.schema.columns)
-            .with_title("Schema Tree".to_string())
+            .with_theme(self.0.theme)
+            .with_title(self.schema_tree_title())
+            .with_visible_nodes(self.visible_tree_nodes())
+            .with_collapsed_groups(self.0.state().collapsed_groups())
+            .with_bookmarks(self.0.state().bookmarks())
+            .with_flat(self.0.state().schema_flat())
+            .with_field_ids(self.0.state().show_field_ids())
             .with_selected_index(self.0.state().vertical_offset())
+            .with_selected_group(self.0.state().selected_group())
             .with_scroll_offset(scroll_offset)
             .render(area, buf);
     }
 
     fn render_row_groups_view(&self, area: Rect, buf: &mut Buffer) {
-        let tree_width = self.0.parquet_ctx.schema.tree_width() as u16;
-        let total_tree_items = self.0.parquet_ctx.schema.columns.len();
+        let tree_width = self.schema_tree_width();
+        let visible_nodes = self.visible_tree_nodes();
+        let total_tree_items = visible_nodes.len();
         let visible_tree_items = area.height.saturating_sub(2) as usize;
 
         let needs_scrollbar = total_tree_items > visible_tree_items;
-        let adjusted_scroll = self.calculate_adjusted_scroll_offset(visible_tree_items);
-        let tree_total_width =
-            self.calculate_tree_width_for_row_groups(tree_width, needs_scrollbar);
+        let adjusted_scroll =
+            self.calculate_adjusted_scroll_offset(&visible_nodes, visible_tree_items);
+        let tree_total_width = self.tree_pane_width(
+            area,
+            self.calculate_tree_width_for_row_groups(tree_width, needs_scrollbar),
+        );
 
         let [tree_container_area, main_area] =
             Layout::horizontal([Constraint::Length(tree_total_width), Constraint::Fill(1)])
                 .areas(area);
+        self.record_pane_divider(area, tree_container_area);
 
         self.render_schema_tree_section(
             tree_container_area,
-            tree_width,
             needs_scrollbar,
             total_tree_items,
             visible_tree_items,
@@ -224,34 +523,146 @@ impl<'a> AppWidget<'a> {
         let [rg_progress, central_area] =
             Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(main_area);
 
-        RowGroupProgressBar::new(
+        let mut progress_bar = RowGroupProgressBar::new(
             &self.0.parquet_ctx.row_groups.row_groups,
-            self.0.state().horizontal_offset(),
+            self.0.state().row_group(),
         )
-        .render(rg_progress, buf);
-
-        if self.0.state().vertical_offset() > 0 {
-            RowGroupColumnMetadataComponent::new(
-                &self.0.parquet_ctx.row_groups.row_groups[self.0.state().horizontal_offset()]
-                    .column_metadata[self.0.state().vertical_offset() - 1],
-            )
-            .render(central_area, buf);
+        .with_theme(self.0.theme);
+        let scanned;
+        if let (Some(filter), Some(matches)) = (
+            self.0.state().row_group_filter(),
+            self.0.state().filtered_row_groups(),
+        ) {
+            progress_bar = progress_bar.with_filter(filter.to_string(), matches);
+        } else if let Some(pruning) = self.0.state().pruning() {
+            scanned = pruning.scanned();
+            progress_bar =
+                progress_bar.with_filter(format!("prune {}", pruning.predicate), &scanned);
+        } else if let Some(bloom) = self.0.state().bloom() {
+            scanned = bloom.read_row_groups();
+            progress_bar = progress_bar.with_filter(format!("bloom {}", bloom.column), &scanned);
+        }
+        progress_bar.render(rg_progress, buf);
+
+        let row_groups = &self.0.parquet_ctx.row_groups.row_groups;
+        let row_group = &row_groups[self.0.state().row_group()];
+        if let Some((a, b)) = self.0.state().compared_row_groups() {
+            RowGroupComparison::new(&row_groups[a], &row_groups[b])
+                .with_selected_column(self.0.state().vertical_offset().checked_sub(1))
+                .with_theme(self.0.theme)
+                .render(central_area, buf);
+        } else if self.0.state().row_group_layout() {
+            RowGroupLayout::new(row_group)
+                .with_selected_column(self.0.state().vertical_offset().checked_sub(1))
+                .with_theme(self.0.theme)
+                .render(central_area, buf);
+        } else if let Some(page) = self.0.state().decoded_page() {
+            DecodedPagePanel::new(page)
+                .with_theme(self.0.theme)
+                .render(central_area, buf);
+        } else if self.0.state().vertical_offset() > 0 {
+            let leaf = self.0.state().vertical_offset() - 1;
+            RowGroupColumnMetadataComponent::new(&row_group.column_metadata[leaf])
+                .with_row_group_chunks(
+                    row_groups
+                        .iter()
+                        .map(|rg| &rg.column_metadata[leaf])
+                        .collect(),
+                )
+                .with_page_previews(self.0.page_previews)
+                .with_theme(self.0.theme)
+                .render(central_area, buf);
+        } else if let Some(pruning) = self.0.state().pruning() {
+            PruningPanel::new(pruning, self.0.state().row_group())
+                .with_theme(self.0.theme)
+                .render(central_area, buf);
+        } else if let Some(bloom) = self.0.state().bloom() {
+            BloomPanel::new(bloom, self.0.state().row_group())
+                .with_theme(self.0.theme)
+                .render(central_area, buf);
         } else {
             // Display row group level statistics and charts when no column is selected
             RowGroupMetadata::new(
                 &self.0.parquet_ctx.row_groups.row_groups,
-                &self.0.parquet_ctx.row_groups.avg_median_stats,
-                self.0.state().horizontal_offset(),
+                &self.0.parquet_ctx.row_groups.aggregates,
+                self.0.state().row_group(),
             )
+            .with_theme(self.0.theme)
             .render(central_area, buf);
         }
     }
 
+    // The rows of the Visualize tab under the Schema tab's panes, scrolled so
+    // the top-level column holding the selected column comes first.
+    fn render_split_preview(&self, area: Rect, buf: &mut Buffer) {
+        let schema = &self.0.parquet_ctx.schema;
+        let outline = schema.outline();
+        let data = self.0.sample_data;
+        let column = self.0.state().schema_cursor(&outline).and_then(|mut node| {
+            while let Some(parent) = outline.parent(node).filter(|&parent| parent > 0) {
+                node = parent;
+            }
+            let name = schema.column_path(node);
+            data.flattened_columns
+                .iter()
+                .position(|column| *column == name)
+                .map(|position| (position, name))
+        });
+        let title = match &column {
+            Some((_, name)) => format!("Data Preview · {name}"),
+            None => "Data Preview".to_string(),
+        };
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(self.0.theme.border_style())
+            .title(Line::from(title.fg(self.0.theme.accent).bold()).centered());
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+        DataTable::new(data)
+            .with_theme(self.0.theme)
+            .with_max_column_width(self.0.config.display.cell_width)
+            .with_horizontal_scroll(column.map_or(0, |(position, _)| position))
+            .with_search(self.0.state().search())
+            .render(inner_area, buf)
+    }
+
     fn render_visualize_view(&self, area: Rect, buf: &mut Buffer) {
-        DataTable::new(&self.0.parquet_ctx.sample_data)
+        if let Some(error) = &self.0.parquet_ctx.sample_data_error
+            && self.0.sample_data.rows.is_empty()
+        {
+            ErrorPanel::new("Rows could not be read", error)
+                .with_theme(self.0.theme)
+                .render(area, buf);
+            return;
+        }
+        // The data table draws a two-line header above the rows.
+        self.0.mouse_regions().borrow_mut().data_rows = Some(ListRegion {
+            area: Rect::new(
+                area.x,
+                area.y + 2,
+                area.width,
+                area.height.saturating_sub(2),
+            ),
+            first_index: self.0.state().data_vertical_scroll(),
+        });
+        let area = if self.columns_scroll() {
+            // The row count set aside a line under the table for this.
+            let [area, scrollbar_area] =
+                Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+            self.render_column_scrollbar(scrollbar_area, buf);
+            area
+        } else {
+            area
+        };
+        DataTable::new(self.0.sample_data)
+            .with_theme(self.0.theme)
+            .with_max_column_width(self.0.config.display.cell_width)
             .with_horizontal_scroll(self.0.state().horizontal_offset())
             .with_vertical_scroll(self.0.state().data_vertical_scroll())
             .with_selected_row(Some(self.0.state().vertical_offset()))
+            .with_expanded_row(self.0.state().expand_row())
+            .with_wrapped_rows(self.0.state().wrapped_rows())
+            .with_search(self.0.state().search())
             .render(area, buf)
     }
 }
@@ -259,6 +670,7 @@ impl<'a> AppWidget<'a> {
 impl<'a> Widget for AppWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let app = self.0;
+        app.mouse_regions().borrow_mut().clear();
 
         let vertical = Layout::vertical([
             Constraint::Length(3),
@@ -277,5 +689,43 @@ impl<'a> Widget for AppWidget<'a> {
             "Visualize" => self.render_visualize_view(inner_area, buf),
             _ => {}
         }
+
+        if app.state().inspecting() {
+            let mut inspector = crate::components::RowInspector::new(
+                app.sample_data,
+                app.state().vertical_offset(),
+            );
+            if let Some(images) = app.inspector_images {
+                inspector = inspector.with_images(images);
+            }
+            inspector
+                .with_cursor(app.state().inspector_cursor())
+                .with_folded(app.state().inspector_folded())
+                .with_close_key(
+                    app.keymap
+                        .in_tab(app.tabs().active_tab)
+                        .describe(Action::Reset),
+                )
+                .with_theme(app.theme)
+                .render(inner_area, buf);
+        }
+
+        if let Some(correlation) = app.state().correlation() {
+            crate::components::CorrelationChart::new(correlation)
+                .with_close_key(
+                    app.keymap
+                        .in_tab(app.tabs().active_tab)
+                        .describe(Action::Reset),
+                )
+                .with_theme(app.theme)
+                .render(inner_area, buf);
+        }
+
+        if app.state().show_help() {
+            crate::components::HelpOverlay::new(app.keymap, app.tabs())
+                .with_scroll(app.state().help_scroll())
+                .with_theme(app.theme)
+                .render(area, buf);
+        }
     }
 }